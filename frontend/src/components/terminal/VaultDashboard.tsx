@@ -697,7 +697,7 @@ export const VaultDashboard: React.FC = () => {
                             wallet_address: walletTrim,
                             amount_usdc: amount,
                           });
-                          setActionStatus(`DEPOSIT OK // MINTED ${resp.shares_minted.toFixed(6)} SHARES @ ${formatUsd(resp.nav_per_share, 4)}`);
+                          setActionStatus(`DEPOSIT QUEUED // STRIKE ${new Date(resp.strike_at * 1000).toLocaleTimeString()} @ ~${formatUsd(resp.indicative_nav_per_share, 4)}`);
                           await refresh();
                           await refreshOverview();
                         } catch (e: any) {
@@ -756,7 +756,7 @@ export const VaultDashboard: React.FC = () => {
                             wallet_address: walletTrim,
                             shares,
                           });
-                          setActionStatus(`WITHDRAW OK // ${formatUsd(resp.amount_usdc, 2)} @ ${formatUsd(resp.nav_per_share, 4)}`);
+                          setActionStatus(`WITHDRAW QUEUED // STRIKE ${new Date(resp.strike_at * 1000).toLocaleTimeString()} @ ~${formatUsd(resp.indicative_nav_per_share, 4)}`);
                           await refresh();
                           await refreshOverview();
                         } catch (e: any) {
@@ -191,28 +191,33 @@ export interface VaultDepositRequest {
   amount_usdc: number;
 }
 
-export interface VaultDepositResponse {
+// Deposits/withdrawals are queued and filled at the next NAV strike.
+export interface VaultShareRequestResponse {
+  id: string;
   wallet_address: string;
-  amount_usdc: number;
-  shares_minted: number;
-  nav_per_share: number;
-  total_shares: number;
-  nav_usdc: number;
+  kind: 'SUBSCRIBE' | 'REDEEM';
+  amount_usdc: number | null;
+  shares: number | null;
+  status: 'PENDING' | 'FILLED' | 'REJECTED' | 'CANCELLED';
+  requested_at: number;
+  strike_id: string | null;
+  struck_at: number | null;
+  nav_per_share: number | null;
+  filled_usdc: number | null;
+  filled_shares: number | null;
+  note: string | null;
+  strike_at: number;
+  indicative_nav_per_share: number;
 }
 
+export type VaultDepositResponse = VaultShareRequestResponse;
+
 export interface VaultWithdrawRequest {
   wallet_address: string;
   shares: number;
 }
 
-export interface VaultWithdrawResponse {
-  wallet_address: string;
-  shares_burned: number;
-  amount_usdc: number;
-  nav_per_share: number;
-  total_shares: number;
-  nav_usdc: number;
-}
+export type VaultWithdrawResponse = VaultShareRequestResponse;
 
 // Backtest types
 export interface BacktestPnlPoint {
@@ -1,7 +1,7 @@
 //! Backtest V2 API Endpoints
 //!
-//! Read-only API for accessing backtest run artifacts.
-//! All endpoints return trust_level and disclaimers - the UI cannot omit them.
+//! API for accessing backtest run artifacts and launching backtest jobs.
+//! All run endpoints return trust_level and disclaimers - the UI cannot omit them.
 //!
 //! # Endpoints
 //!
@@ -12,6 +12,21 @@
 //! - `GET /api/v2/backtest/runs/:run_id/drawdown` - Get drawdown series
 //! - `GET /api/v2/backtest/runs/:run_id/window-pnl` - Get per-window PnL
 //! - `GET /api/v2/backtest/runs/:run_id/distributions` - Get distribution histograms
+//! - `GET /api/v2/backtest/runs/:run_id/diff/:other_run_id` - Explain fingerprint divergence
+//! - `GET /api/v2/backtest/strategies` - List strategies available to jobs
+//! - `POST /api/v2/backtest/jobs` - Enqueue a backtest job
+//! - `GET /api/v2/backtest/jobs` - List jobs
+//! - `GET /api/v2/backtest/jobs/:job_id` - Poll job status/progress
+//! - `POST /api/v2/backtest/jobs/:job_id/cancel` - Cancel a queued or running job
+//!
+//! # Jobs
+//!
+//! Job endpoints are only served when the state has a `BacktestJobQueue`
+//! (503 otherwise). Completed jobs are persisted into the artifact store and
+//! their `run_id` resolves through the run endpoints above. Job routes live on
+//! their own router ([`backtest_v2_jobs_router`]), which must be mounted behind
+//! the auth middleware: viewers cannot submit, and a job is only visible to and
+//! cancellable by the user who submitted it or an admin.
 //!
 //! # ETag Support
 //!
@@ -19,12 +34,14 @@
 //! Clients can use If-None-Match for conditional requests.
 
 use crate::backtest_v2::{
-    ArtifactResponse, ArtifactStore, ListRunsFilter, ListRunsResponse, MethodologyCapsule,
-    RunArtifact, RunDistributions, RunId, RunManifest, RunSortField, RunSummary, 
+    available_strategies, ArtifactResponse, ArtifactStore, BacktestJobQueue, BacktestJobSpec,
+    JobId, JobQueueError, JobStatus, ListRunsFilter, ListRunsResponse, MethodologyCapsule,
+    RunArtifact, RunDiff, RunDistributions, RunId, RunManifest, RunSortField, RunSummary, 
     RunTimeSeries, SortOrder, TrustLevelDto, TrustStatus, RUN_ARTIFACT_API_VERSION,
 };
+use crate::auth::models::{Claims, UserRole};
 use axum::{
-    extract::{Path, Query, State as AxumState},
+    extract::{Extension, Path, Query, State as AxumState},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
 };
@@ -36,6 +53,8 @@ use tracing::{debug, warn};
 /// This should be added to AppState.
 pub struct BacktestV2State {
     pub artifact_store: Arc<ArtifactStore>,
+    /// Job queue for launching runs over the API (None = job endpoints disabled).
+    pub job_queue: Option<Arc<BacktestJobQueue>>,
 }
 
 // =============================================================================
@@ -379,6 +398,52 @@ pub async fn get_run_full(
     }
 }
 
+// =============================================================================
+// RUN DIFF
+// =============================================================================
+
+/// Default records shown on each side of the first trace divergence.
+const DEFAULT_DIFF_CONTEXT: usize = 5;
+/// Maximum records shown on each side of the first trace divergence.
+const MAX_DIFF_CONTEXT: usize = 50;
+
+/// Query parameters for run diffs.
+#[derive(Debug, Deserialize)]
+pub struct RunDiffQuery {
+    /// Trace records shown around the divergence (default 5, max 50).
+    pub context: Option<usize>,
+}
+
+/// GET /api/v2/backtest/runs/:run_id/diff/:other_run_id - Explain fingerprint divergence
+pub async fn get_run_diff(
+    AxumState(state): AxumState<Arc<BacktestV2State>>,
+    Path((run_id, other_run_id)): Path<(String, String)>,
+    Query(query): Query<RunDiffQuery>,
+) -> Response {
+    let context = query.context.unwrap_or(DEFAULT_DIFF_CONTEXT).min(MAX_DIFF_CONTEXT);
+
+    let mut artifacts = Vec::with_capacity(2);
+    for id in [run_id, other_run_id] {
+        match state.artifact_store.get(&RunId(id.clone())) {
+            Ok(Some(artifact)) => artifacts.push(artifact),
+            Ok(None) => {
+                return error_response(StatusCode::NOT_FOUND, &format!("Run not found: {}", id))
+            }
+            Err(e) => {
+                warn!("Failed to load run for diff: {}", e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string());
+            }
+        }
+    }
+
+    let diff = RunDiff::compare(&artifacts[0], &artifacts[1], context);
+    Json(serde_json::json!({
+        "api_version": RUN_ARTIFACT_API_VERSION,
+        "diff": diff,
+    }))
+    .into_response()
+}
+
 // =============================================================================
 // STORE STATS
 // =============================================================================
@@ -415,6 +480,167 @@ pub async fn get_store_stats(
     }
 }
 
+// =============================================================================
+// JOBS
+// =============================================================================
+
+/// Response for a single job.
+#[derive(Debug, Serialize)]
+pub struct JobResponse {
+    pub api_version: String,
+    pub job: JobStatus,
+}
+
+/// Response for job listing.
+#[derive(Debug, Serialize)]
+pub struct ListJobsResponse {
+    pub api_version: String,
+    pub queued: usize,
+    pub jobs: Vec<JobStatus>,
+}
+
+/// Strategy available to jobs.
+#[derive(Debug, Serialize)]
+pub struct StrategyInfo {
+    pub name: String,
+    pub description: String,
+}
+
+fn job_response(status: StatusCode, job: JobStatus) -> Response {
+    let response = JobResponse {
+        api_version: RUN_ARTIFACT_API_VERSION.to_string(),
+        job,
+    };
+    (status, Json(response)).into_response()
+}
+
+fn job_error_response(e: &JobQueueError) -> Response {
+    let status = match e {
+        JobQueueError::Invalid(_) => StatusCode::BAD_REQUEST,
+        JobQueueError::QueueFull { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        JobQueueError::NotFound(_) => StatusCode::NOT_FOUND,
+        JobQueueError::AlreadyFinished { .. } => StatusCode::CONFLICT,
+    };
+    error_response(status, &e.to_string())
+}
+
+/// Whether `claims` may see and cancel `job`.
+fn can_access_job(claims: &Claims, job: &JobStatus) -> bool {
+    claims.role == UserRole::Admin || job.owner == claims.sub
+}
+
+/// Job queue from state, or a 503 response when jobs are disabled.
+fn job_queue(state: &BacktestV2State) -> Result<&BacktestJobQueue, Response> {
+    state.job_queue.as_deref().ok_or_else(|| {
+        error_response(StatusCode::SERVICE_UNAVAILABLE, "Backtest jobs are not enabled")
+    })
+}
+
+/// GET /api/v2/backtest/strategies - List strategies available to jobs
+pub async fn list_strategies() -> Response {
+    let mut strategies: Vec<StrategyInfo> = available_strategies()
+        .into_iter()
+        .map(|(name, description)| StrategyInfo {
+            name: name.to_string(),
+            description: description.to_string(),
+        })
+        .collect();
+    strategies.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(strategies).into_response()
+}
+
+/// POST /api/v2/backtest/jobs - Enqueue a backtest job
+pub async fn submit_job(
+    AxumState(state): AxumState<Arc<BacktestV2State>>,
+    Extension(claims): Extension<Claims>,
+    Json(spec): Json<BacktestJobSpec>,
+) -> Response {
+    if claims.role == UserRole::Viewer {
+        return error_response(StatusCode::FORBIDDEN, "Viewers cannot submit backtest jobs");
+    }
+    let queue = match job_queue(&state) {
+        Ok(q) => q,
+        Err(response) => return response,
+    };
+    
+    match queue.submit(spec, &claims.sub) {
+        Ok(job) => {
+            debug!("Backtest job submitted: {}", job.job_id);
+            job_response(StatusCode::ACCEPTED, job)
+        }
+        Err(e) => {
+            warn!("Backtest job rejected: {}", e);
+            job_error_response(&e)
+        }
+    }
+}
+
+/// GET /api/v2/backtest/jobs - List the caller's jobs (all jobs for admins), newest first
+pub async fn list_jobs(
+    AxumState(state): AxumState<Arc<BacktestV2State>>,
+    Extension(claims): Extension<Claims>,
+) -> Response {
+    let queue = match job_queue(&state) {
+        Ok(q) => q,
+        Err(response) => return response,
+    };
+    
+    let jobs = queue
+        .list()
+        .into_iter()
+        .filter(|job| can_access_job(&claims, job))
+        .collect();
+    let response = ListJobsResponse {
+        api_version: RUN_ARTIFACT_API_VERSION.to_string(),
+        queued: queue.queued_len(),
+        jobs,
+    };
+    Json(response).into_response()
+}
+
+/// GET /api/v2/backtest/jobs/:job_id - Poll job status and progress
+pub async fn get_job(
+    AxumState(state): AxumState<Arc<BacktestV2State>>,
+    Extension(claims): Extension<Claims>,
+    Path(job_id): Path<String>,
+) -> Response {
+    let queue = match job_queue(&state) {
+        Ok(q) => q,
+        Err(response) => return response,
+    };
+    
+    match queue.status(&JobId(job_id)) {
+        Some(job) if can_access_job(&claims, &job) => job_response(StatusCode::OK, job),
+        Some(_) => error_response(StatusCode::FORBIDDEN, "Job belongs to another user"),
+        None => error_response(StatusCode::NOT_FOUND, "Job not found"),
+    }
+}
+
+/// POST /api/v2/backtest/jobs/:job_id/cancel - Cancel a queued or running job
+pub async fn cancel_job(
+    AxumState(state): AxumState<Arc<BacktestV2State>>,
+    Extension(claims): Extension<Claims>,
+    Path(job_id): Path<String>,
+) -> Response {
+    let queue = match job_queue(&state) {
+        Ok(q) => q,
+        Err(response) => return response,
+    };
+    
+    let job_id = JobId(job_id);
+    match queue.status(&job_id) {
+        Some(job) if !can_access_job(&claims, &job) => {
+            return error_response(StatusCode::FORBIDDEN, "Job belongs to another user");
+        }
+        None => return error_response(StatusCode::NOT_FOUND, "Job not found"),
+        Some(_) => {}
+    }
+    match queue.cancel(&job_id) {
+        Ok(job) => job_response(StatusCode::OK, job),
+        Err(e) => job_error_response(&e),
+    }
+}
+
 // =============================================================================
 // PUBLIC API ENDPOINTS (no authentication required - published runs only)
 // =============================================================================
@@ -531,7 +757,7 @@ pub async fn get_run_full_public(
 // ROUTER
 // =============================================================================
 
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 
 /// Create the backtest v2 router (authenticated - all runs).
@@ -540,7 +766,7 @@ use axum::Router;
 /// 
 /// ```ignore
 /// let artifact_store = Arc::new(ArtifactStore::new("backtest_artifacts.db")?);
-/// let backtest_v2_state = Arc::new(BacktestV2State { artifact_store });
+/// let backtest_v2_state = Arc::new(BacktestV2State { artifact_store, job_queue: None });
 /// 
 /// let app = Router::new()
 ///     .nest("/api/v2/backtest", backtest_v2_router())
@@ -556,7 +782,29 @@ pub fn backtest_v2_router() -> Router<Arc<BacktestV2State>> {
         .route("/runs/:run_id/window-pnl", get(get_run_window_pnl))
         .route("/runs/:run_id/distributions", get(get_run_distributions))
         .route("/runs/:run_id/full", get(get_run_full))
+        .route("/runs/:run_id/diff/:other_run_id", get(get_run_diff))
         .route("/stats", get(get_store_stats))
+        .route("/strategies", get(list_strategies))
+}
+
+/// Create the backtest v2 job router.
+/// 
+/// Handlers read the caller's `Claims`, so this router MUST be mounted behind
+/// the auth middleware (nested under the same `/api/v2/backtest` prefix).
+/// 
+/// # Usage
+/// 
+/// ```ignore
+/// let jobs = backtest_v2_jobs_router()
+///     .route_layer(axum::middleware::from_fn_with_state(jwt_handler, auth_middleware))
+///     .with_state(backtest_v2_state);
+/// let app = Router::new().nest("/api/v2/backtest", backtest_v2_router().merge(jobs));
+/// ```
+pub fn backtest_v2_jobs_router() -> Router<Arc<BacktestV2State>> {
+    Router::new()
+        .route("/jobs", post(submit_job).get(list_jobs))
+        .route("/jobs/:job_id", get(get_job))
+        .route("/jobs/:job_id/cancel", post(cancel_job))
 }
 
 /// Create the public backtest v2 router (no authentication - published runs only).
@@ -615,16 +863,21 @@ mod tests {
                 oracle_config_hash: None,
                 latency_model: "Fixed".to_string(),
                 order_latency_ns: None,
+                latency_config_hash: 0,
                 oms_parity_mode: "Full".to_string(),
                 maker_fill_model: "Disabled".to_string(),
                 integrity_policy: "Strict".to_string(),
                 invariant_mode: "Hard".to_string(),
                 fee_rate_bps: None,
                 strategy_params_hash: 0,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "RecordedArrival".to_string(),
                 strict_accounting: true,
                 production_grade: true,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 0,
             },
             dataset: DatasetFingerprint {
@@ -721,6 +974,7 @@ mod tests {
         let store = ArtifactStore::in_memory().unwrap();
         let state = Arc::new(BacktestV2State {
             artifact_store: Arc::new(store),
+            job_queue: None,
         });
         
         let app = backtest_v2_router().with_state(state);
@@ -738,6 +992,7 @@ mod tests {
         let store = ArtifactStore::in_memory().unwrap();
         let state = Arc::new(BacktestV2State {
             artifact_store: Arc::new(store),
+            job_queue: None,
         });
         
         let app = backtest_v2_router().with_state(state);
@@ -755,6 +1010,233 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    struct UnusedLoader;
+
+    impl crate::backtest_v2::JobDatasetLoader for UnusedLoader {
+        fn load(
+            &self,
+            _dataset: &crate::backtest_v2::JobDataset,
+            _start_ns: i64,
+            _end_ns: i64,
+        ) -> Result<Vec<crate::backtest_v2::TimestampedEvent>, String> {
+            Err("no datasets in tests".to_string())
+        }
+    }
+
+    fn claims(sub: &str, role: UserRole) -> Claims {
+        Claims {
+            sub: sub.to_string(),
+            username: sub.to_string(),
+            role,
+            exp: usize::MAX,
+        }
+    }
+
+    /// Job router as the auth middleware would leave it: claims in the extensions.
+    fn jobs_app(state: Arc<BacktestV2State>, claims: Claims) -> Router {
+        backtest_v2_jobs_router().layer(Extension(claims)).with_state(state)
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_jobs_disabled_without_queue() {
+        let state = Arc::new(BacktestV2State {
+            artifact_store: Arc::new(ArtifactStore::in_memory().unwrap()),
+            job_queue: None,
+        });
+        
+        let app = jobs_app(state, claims("alice", UserRole::Trader));
+        
+        let response = app
+            .oneshot(Request::builder().uri("/jobs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_submit_poll_and_cancel_job() {
+        let store = Arc::new(ArtifactStore::in_memory().unwrap());
+        // No workers: the job stays queued until cancelled
+        let queue = BacktestJobQueue::new(
+            crate::backtest_v2::JobQueueConfig {
+                workers: 0,
+                ..Default::default()
+            },
+            store.clone(),
+            Arc::new(UnusedLoader),
+        );
+        let state = Arc::new(BacktestV2State {
+            artifact_store: store,
+            job_queue: Some(Arc::new(queue)),
+        });
+        let app = jobs_app(state.clone(), claims("alice", UserRole::Trader));
+        let other_app = jobs_app(state.clone(), claims("bob", UserRole::Trader));
+        let admin_app = jobs_app(state.clone(), claims("root", UserRole::Admin));
+        let viewer_app = jobs_app(state, claims("eve", UserRole::Viewer));
+        
+        let spec = serde_json::json!({
+            "strategy_name": "noop",
+            "dataset": { "name": "data.db", "market_ids": ["btc-updown-15m-1762755300"] },
+            "start_ns": 0,
+            "end_ns": 900_000_000_000i64,
+            "allow_non_production": true,
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/jobs")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(spec.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let job = body_json(response).await;
+        let job_id = job["job"]["job_id"].as_str().unwrap().to_string();
+        assert_eq!(job["job"]["state"], "queued");
+        assert_eq!(job["job"]["spec"]["seed"], 42);
+        assert_eq!(job["job"]["owner"], "alice");
+        
+        // Viewers cannot submit
+        let response = viewer_app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/jobs")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(spec.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        
+        // Other users neither see the job nor can poll or cancel it
+        let response = other_app
+            .clone()
+            .oneshot(Request::builder().uri("/jobs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["jobs"].as_array().unwrap().len(), 0);
+        let response = other_app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/jobs/{}", job_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/jobs/{}", job_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["job"]["queue_position"], 0);
+        
+        let cancel = |app: Router| {
+            let uri = format!("/jobs/{}/cancel", job_id);
+            async move {
+                app.oneshot(Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(cancel(other_app).await.status(), StatusCode::FORBIDDEN);
+        let response = cancel(admin_app).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["job"]["state"], "cancelled");
+        assert_eq!(cancel(app.clone()).await.status(), StatusCode::CONFLICT);
+        
+        // Unknown strategy is rejected up front
+        let bad = serde_json::json!({
+            "strategy_name": "no_such_strategy",
+            "dataset": { "name": "data.db", "market_ids": ["x"] },
+            "start_ns": 0,
+            "end_ns": 1,
+        });
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/jobs")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(bad.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_run_diff_locates_divergence() {
+        use crate::backtest_v2::{DecisionTrace, Side, TraceRecord};
+
+        let store = ArtifactStore::in_memory().unwrap();
+        for (run_id, price) in [("run_diff_a", 0.45), ("run_diff_b", 0.46)] {
+            let mut artifact = make_test_artifact(run_id);
+            let mut trace = DecisionTrace::new(100);
+            trace.push(TraceRecord::order_submit(1, "tok", Side::Buy, 0.40, 10.0), 1, 1_000);
+            trace.push(TraceRecord::order_submit(2, "tok", Side::Buy, price, 10.0), 2, 2_000);
+            artifact.results.decision_trace = Some(trace);
+            store.persist(&artifact).unwrap();
+        }
+        
+        let state = Arc::new(BacktestV2State {
+            artifact_store: Arc::new(store),
+            job_queue: None,
+        });
+        let app = backtest_v2_router().with_state(state);
+        
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/runs/run_diff_a/diff/run_diff_b?context=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["diff"]["identical"], false);
+        assert_eq!(body["diff"]["trace"]["status"], "diverged");
+        assert_eq!(body["diff"]["trace"]["index"], 1);
+        assert_eq!(body["diff"]["trace"]["event_index"], 2);
+        assert_eq!(body["diff"]["trace"]["context"].as_array().unwrap().len(), 2);
+        
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/runs/run_diff_a/diff/run_missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_get_run_with_etag() {
         let store = ArtifactStore::in_memory().unwrap();
@@ -763,6 +1245,7 @@ mod tests {
         
         let state = Arc::new(BacktestV2State {
             artifact_store: Arc::new(store),
+            job_queue: None,
         });
         
         let app = backtest_v2_router().with_state(state);
@@ -6,6 +6,7 @@
 //! - Direct database access without intermediate layers
 
 use crate::{
+    auth::models::{Claims, UserRole},
     models::{MarketSignal, SignalContext, SignalContextRecord, SignalType},
     scrapers::dome_rest::{DomeRestClient, OrdersFilter},
     scrapers::polymarket::OrderBook,
@@ -15,13 +16,13 @@ use crate::{
         WalletAnalytics, WalletAnalyticsParams, WALLET_ANALYTICS_CACHE_TTL_SECONDS,
     },
     vault::{
-        VaultActivityRecord, VaultDepositRequest, VaultEngineConfig, VaultStateResponse,
-        VaultWithdrawRequest,
+        HaltScope, HaltTrigger, VaultActivityRecord, VaultDepositRequest, VaultEngineConfig,
+        VaultStateResponse, VaultWithdrawRequest,
     },
     AppState,
 };
 use axum::{
-    extract::{Json as AxumJson, Query, State as AxumState},
+    extract::{Extension, Json as AxumJson, Query, State as AxumState},
     http::StatusCode,
     response::Json,
 };
@@ -1310,11 +1311,12 @@ pub async fn get_vault_state(AxumState(state): AxumState<AppState>) -> Json<Vaul
     Json(state.vault.state().await)
 }
 
-/// Mint vault shares against a USDC deposit (accounting-only; on-chain settlement TBD).
+/// Queue a USDC subscription; shares are minted at the next NAV strike
+/// (accounting-only; on-chain settlement TBD).
 pub async fn post_vault_deposit(
     AxumState(state): AxumState<AppState>,
     AxumJson(req): AxumJson<VaultDepositRequest>,
-) -> Result<Json<crate::vault::VaultDepositResponse>, StatusCode> {
+) -> Result<Json<crate::vault::VaultShareRequestResponse>, StatusCode> {
     state
         .vault
         .deposit(&req.wallet_address, req.amount_usdc)
@@ -1323,11 +1325,12 @@ pub async fn post_vault_deposit(
         .map_err(|_| StatusCode::BAD_REQUEST)
 }
 
-/// Burn vault shares for a USDC withdrawal (accounting-only; on-chain settlement TBD).
+/// Queue a share redemption; USDC is paid out at the next NAV strike that
+/// has the cash (accounting-only; on-chain settlement TBD).
 pub async fn post_vault_withdraw(
     AxumState(state): AxumState<AppState>,
     AxumJson(req): AxumJson<VaultWithdrawRequest>,
-) -> Result<Json<crate::vault::VaultWithdrawResponse>, StatusCode> {
+) -> Result<Json<crate::vault::VaultShareRequestResponse>, StatusCode> {
     match state.vault.withdraw(&req.wallet_address, req.shares).await {
         Ok(resp) => Ok(Json(resp)),
         Err(e) => {
@@ -1341,6 +1344,83 @@ pub async fn post_vault_withdraw(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct VaultWalletQuery {
+    pub wallet: String,
+    pub limit: Option<usize>,
+}
+
+/// A wallet's subscription/redemption requests, newest first.
+pub async fn get_vault_share_requests(
+    Query(params): Query<VaultWalletQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<Vec<crate::vault::VaultShareRequestRecord>>, StatusCode> {
+    if params.wallet.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    state
+        .vault
+        .db
+        .list_share_requests(&params.wallet, params.limit.unwrap_or(100))
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VaultCancelRequest {
+    pub wallet_address: String,
+    pub request_id: String,
+}
+
+/// Cancel a request that has not been struck yet.
+pub async fn post_vault_cancel_request(
+    AxumState(state): AxumState<AppState>,
+    AxumJson(req): AxumJson<VaultCancelRequest>,
+) -> Result<StatusCode, StatusCode> {
+    match state
+        .vault
+        .cancel_request(&req.wallet_address, &req.request_id)
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::CONFLICT),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Per-user vault statement: holdings, flows, fees and pending requests.
+pub async fn get_vault_statement(
+    Query(params): Query<VaultWalletQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<crate::vault::VaultUserStatement>, StatusCode> {
+    state
+        .vault
+        .statement(&params.wallet, params.limit.unwrap_or(200).clamp(1, 1000))
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VaultStrikesQuery {
+    pub limit: Option<usize>,
+}
+
+/// Recent NAV strikes, newest first.
+pub async fn get_vault_strikes(
+    Query(params): Query<VaultStrikesQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<Vec<crate::vault::VaultNavStrikeRecord>>, StatusCode> {
+    state
+        .vault
+        .db
+        .list_nav_strikes(params.limit.unwrap_or(96))
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct VaultOverviewQuery {
     pub wallet: Option<String>,
@@ -1714,6 +1794,8 @@ pub async fn get_vault_activity(
 #[derive(Debug, Serialize)]
 pub struct VaultConfigResponse {
     pub fetched_at: i64,
+    /// Strategy config version in effect
+    pub config_version: i64,
     pub engine_enabled: bool,
     pub paper: bool,
     pub updown_poll_ms: u64,
@@ -1761,12 +1843,14 @@ pub async fn get_vault_config(
     AxumState(state): AxumState<AppState>,
 ) -> Result<Json<VaultConfigResponse>, StatusCode> {
     let now = Utc::now().timestamp();
-    let cfg = VaultEngineConfig::from_env();
+    let active = state.strategy_config.active().await;
+    let cfg = active.params.engine.clone();
 
     let llm_usage_today = state.signal_storage.get_vault_llm_usage_today(now).ok();
 
     Ok(Json(VaultConfigResponse {
         fetched_at: now,
+        config_version: active.version,
         engine_enabled: cfg.enabled,
         paper: cfg.paper,
         updown_poll_ms: cfg.updown_poll_ms,
@@ -1873,6 +1957,379 @@ pub async fn get_vault_llm_models(
     }))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct VaultLlmEvalRequest {
+    /// "replay" (default) re-sends stored prompts; "recorded" re-scores past responses.
+    pub source: Option<crate::vault::LlmEvalSource>,
+    pub since_ts: Option<i64>,
+    pub until_ts: Option<i64>,
+    pub limit: Option<usize>,
+    /// Defaults to the LONG engine's `long_models`.
+    pub models: Option<Vec<String>>,
+    /// Replay against the local deterministic stub instead of OpenRouter.
+    #[serde(default)]
+    pub stub: bool,
+    pub stake_usdc: Option<f64>,
+}
+
+/// Upper bound on OpenRouter calls (prompts x models) one replay request may make.
+/// The eval runs inline, so this also bounds how long the request can take.
+const LLM_EVAL_MAX_PAID_CALLS: usize = 200;
+
+/// POST /api/admin/llm/eval - Score LONG models on resolved markets (Brier, calibration, PnL)
+pub async fn post_vault_llm_eval(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+    AxumJson(req): AxumJson<VaultLlmEvalRequest>,
+) -> Result<Json<crate::vault::LlmEvalReport>, (StatusCode, String)> {
+    require_admin(&claims).map_err(|status| (status, "admin only".to_string()))?;
+
+    let active = state.strategy_config.active().await;
+    let mut cfg = crate::vault::LlmEvalConfig::from_engine(&active.params.engine);
+    if let Some(models) = req.models {
+        cfg.models = models
+            .into_iter()
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty())
+            .collect();
+    }
+    cfg.since_ts = req.since_ts.unwrap_or(0);
+    cfg.until_ts = req.until_ts.unwrap_or(i64::MAX);
+    if let Some(stake) = req.stake_usdc.filter(|v| v.is_finite() && *v > 0.0) {
+        cfg.stake_usdc = stake;
+    }
+
+    let source = req.source.unwrap_or(crate::vault::LlmEvalSource::Replay);
+    cfg.max_prompts = if source == crate::vault::LlmEvalSource::Replay && !req.stub {
+        // Every prompt is re-sent to every model, each a paid call.
+        let max_prompts = LLM_EVAL_MAX_PAID_CALLS / cfg.models.len().max(1);
+        if max_prompts == 0 {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("at most {} models per paid replay", LLM_EVAL_MAX_PAID_CALLS),
+            ));
+        }
+        match req.limit {
+            Some(limit) if limit > max_prompts => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "limit {} exceeds {} prompts for {} models ({} paid calls max)",
+                        limit,
+                        max_prompts,
+                        cfg.models.len(),
+                        LLM_EVAL_MAX_PAID_CALLS
+                    ),
+                ));
+            }
+            Some(limit) => limit.max(1),
+            None => max_prompts,
+        }
+    } else {
+        req.limit.unwrap_or(200).clamp(1, 5000)
+    };
+    let resolver = crate::vault::GammaOutcomeResolver::new(
+        state.signal_storage.clone(),
+        state.http_client.clone(),
+    );
+
+    // The stub server lives until the report is built.
+    let stub = if source == crate::vault::LlmEvalSource::Replay && req.stub {
+        Some(
+            crate::vault::LlmStubServer::start()
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        )
+    } else {
+        None
+    };
+    let client = match (source, &stub) {
+        (crate::vault::LlmEvalSource::Recorded, _) => None,
+        (crate::vault::LlmEvalSource::Replay, Some(stub)) => {
+            Some(crate::vault::OpenRouterClient::new(
+                state.http_client.clone(),
+                "stub",
+                &stub.base_url(),
+            ))
+        }
+        (crate::vault::LlmEvalSource::Replay, None) => Some(
+            crate::vault::OpenRouterClient::from_env(state.http_client.clone())
+                .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e.to_string()))?,
+        ),
+    };
+
+    let report = crate::vault::run_llm_eval(
+        &state.signal_storage,
+        client.as_ref().map(|c| c as &dyn crate::vault::LlmClient),
+        &resolver,
+        &cfg,
+        source,
+    )
+    .await
+    .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    Ok(Json(report))
+}
+
+// =============================================================================
+// Trading Halt Controls API (Admin only)
+// =============================================================================
+
+fn require_admin(claims: &Claims) -> Result<(), StatusCode> {
+    if claims.role != UserRole::Admin {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradingHaltRequest {
+    /// "global", "strategy" or "asset".
+    pub scope: String,
+    /// Strategy name or asset symbol; ignored for the global scope.
+    pub target: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradingHaltResponse {
+    pub scope: String,
+    /// False when the scope was already halted (or not halted, on resume).
+    pub changed: bool,
+    pub orders_cancelled: usize,
+    pub status: crate::vault::TradingControlStatus,
+}
+
+/// GET /api/admin/trading/status - Active halts and per-strategy reject rates
+pub async fn get_trading_status(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<crate::vault::TradingControlStatus>, StatusCode> {
+    require_admin(&claims)?;
+    Ok(Json(state.trading_control.status()))
+}
+
+/// POST /api/admin/trading/halt - Halt a scope and cancel its resting orders
+pub async fn post_trading_halt(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+    AxumJson(req): AxumJson<TradingHaltRequest>,
+) -> Result<Json<TradingHaltResponse>, StatusCode> {
+    require_admin(&claims)?;
+    let scope = HaltScope::parse(&req.scope, req.target.as_deref())
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let reason = req.reason.unwrap_or_else(|| "operator kill switch".to_string());
+
+    let result = state
+        .trading_control
+        .halt(scope.clone(), HaltTrigger::Operator, &claims.username, &reason)
+        .await
+        .map_err(|e| {
+            warn!("Trading halt failed for {}: {}", scope.key(), e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(TradingHaltResponse {
+        scope: scope.key(),
+        changed: result.is_some(),
+        orders_cancelled: result.unwrap_or(0),
+        status: state.trading_control.status(),
+    }))
+}
+
+/// POST /api/admin/trading/resume - Lift a halt
+pub async fn post_trading_resume(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+    AxumJson(req): AxumJson<TradingHaltRequest>,
+) -> Result<Json<TradingHaltResponse>, StatusCode> {
+    require_admin(&claims)?;
+    let scope = HaltScope::parse(&req.scope, req.target.as_deref())
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let reason = req.reason.unwrap_or_else(|| "operator resume".to_string());
+
+    let changed = state
+        .trading_control
+        .resume(&scope, &claims.username, &reason)
+        .await
+        .map_err(|e| {
+            warn!("Trading resume failed for {}: {}", scope.key(), e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(TradingHaltResponse {
+        scope: scope.key(),
+        changed,
+        orders_cancelled: 0,
+        status: state.trading_control.status(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradingAuditQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradingAuditResponse {
+    pub fetched_at: i64,
+    pub records: Vec<crate::vault::VaultControlAuditRecord>,
+}
+
+/// GET /api/admin/trading/audit - Halt/resume audit log, newest first
+pub async fn get_trading_audit(
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<TradingAuditQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<TradingAuditResponse>, StatusCode> {
+    require_admin(&claims)?;
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    let records = state
+        .trading_control
+        .audit_log(limit)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(TradingAuditResponse {
+        fetched_at: Utc::now().timestamp(),
+        records,
+    }))
+}
+
+// =============================================================================
+// Strategy Config API (Admin only)
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct StrategyConfigUpdateRequest {
+    /// Partial parameter set (`{"engine": {...}, "unified_15m": {...}}`)
+    /// overlaid on the latest version.
+    pub params: Value,
+    pub note: Option<String>,
+}
+
+/// GET /api/admin/strategy-config - Active and pending config versions
+pub async fn get_strategy_config(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<crate::vault::StrategyConfigStatus>, StatusCode> {
+    require_admin(&claims)?;
+    // Promotes a version whose window boundary has passed.
+    state.strategy_config.active().await;
+    Ok(Json(state.strategy_config.status()))
+}
+
+/// POST /api/admin/strategy-config - Validate and stage a new version for the
+/// next 15-minute window boundary
+pub async fn post_strategy_config(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+    AxumJson(req): AxumJson<StrategyConfigUpdateRequest>,
+) -> Result<Json<crate::vault::StrategyConfigStatus>, (StatusCode, String)> {
+    require_admin(&claims).map_err(|status| (status, "admin only".to_string()))?;
+    let params = state
+        .strategy_config
+        .prepare(req.params)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    state
+        .strategy_config
+        .stage(params, "api", &claims.username, req.note)
+        .await
+        .map_err(|e| {
+            warn!("Failed to stage strategy config: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        })?;
+    Ok(Json(state.strategy_config.status()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StrategyConfigHistoryQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrategyConfigHistoryResponse {
+    pub fetched_at: i64,
+    pub versions: Vec<crate::vault::VaultStrategyConfigRecord>,
+}
+
+/// GET /api/admin/strategy-config/history - Recorded versions, newest first
+pub async fn get_strategy_config_history(
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<StrategyConfigHistoryQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<StrategyConfigHistoryResponse>, StatusCode> {
+    require_admin(&claims)?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let versions = state
+        .strategy_config
+        .history(limit)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(StrategyConfigHistoryResponse {
+        fetched_at: Utc::now().timestamp(),
+        versions,
+    }))
+}
+
+// =============================================================================
+// Capital Allocation API
+// =============================================================================
+
+/// GET /api/vault/allocations - Per-strategy budgets and usage
+pub async fn get_vault_allocations(
+    AxumState(state): AxumState<AppState>,
+) -> Json<crate::vault::CapitalAllocationStatus> {
+    Json(state.capital_allocator.status())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AllocationHistoryQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AllocationHistoryResponse {
+    pub fetched_at: i64,
+    pub records: Vec<crate::vault::VaultAllocationRecord>,
+}
+
+/// GET /api/vault/allocations/history - Budgets set by past rebalances, newest first
+pub async fn get_vault_allocation_history(
+    Query(params): Query<AllocationHistoryQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<AllocationHistoryResponse>, StatusCode> {
+    let limit = params.limit.unwrap_or(200).clamp(1, 5000);
+    let records = state
+        .vault
+        .db
+        .list_allocations(limit)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(AllocationHistoryResponse {
+        fetched_at: Utc::now().timestamp(),
+        records,
+    }))
+}
+
+/// POST /api/admin/allocations/rebalance - Rebalance now against current NAV
+pub async fn post_allocation_rebalance(
+    Extension(claims): Extension<Claims>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<crate::vault::CapitalAllocationStatus>, StatusCode> {
+    require_admin(&claims)?;
+    let ledger = state.vault.ledger.lock().await.clone();
+    let nav = crate::vault::approximate_nav_usdc(&ledger);
+    state.capital_allocator.sync_positions(&ledger);
+    state
+        .capital_allocator
+        .rebalance(nav, Utc::now().timestamp())
+        .await;
+    Ok(Json(state.capital_allocator.status()))
+}
+
 // =============================================================================
 // RN-JD Belief Volatility API
 // =============================================================================
@@ -2059,6 +2516,54 @@ pub struct LatencyDashboardResponse {
     pub recent_spans: Vec<crate::latency::LatencySpan>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LatencyExportQuery {
+    pub venue: Option<String>,
+    pub one_way_fraction: Option<f64>,
+}
+
+/// GET /api/admin/latency/export - Live latency as a backtest latency profile
+///
+/// Save the response and pass it to `backtest_run --latency-profile`.
+pub async fn get_latency_export(
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<LatencyExportQuery>,
+    AxumState(state): AxumState<AppState>,
+) -> Result<Json<crate::backtest_v2::LiveLatencyProfile>, (StatusCode, String)> {
+    use crate::backtest_v2::EmpiricalLatency;
+
+    require_admin(&claims).map_err(|status| (status, "admin only".to_string()))?;
+
+    let venue = params.venue.unwrap_or_else(|| "polymarket".to_string());
+    let buckets = crate::performance::global_venue_tracker().latency_buckets(&venue);
+    let decision_samples = state
+        .fast15m_latency_registry
+        .read()
+        .as_ref()
+        .map(|r| r.read().decision_samples_ns())
+        .unwrap_or_default();
+
+    Ok(Json(crate::backtest_v2::LiveLatencyProfile {
+        captured_at: Utc::now().timestamp(),
+        decision: EmpiricalLatency::from_samples_ns(&decision_samples, 24),
+        send_ack: buckets
+            .as_ref()
+            .and_then(|b| EmpiricalLatency::from_bucket_counts_us(&b.send_ack)),
+        send_fill: buckets
+            .as_ref()
+            .and_then(|b| EmpiricalLatency::from_bucket_counts_us(&b.send_fill)),
+        cancel_ack: buckets
+            .as_ref()
+            .and_then(|b| EmpiricalLatency::from_bucket_counts_us(&b.cancel_ack)),
+        one_way_fraction: params
+            .one_way_fraction
+            .filter(|f| f.is_finite())
+            .map(|f| f.clamp(0.0, 1.0))
+            .unwrap_or(0.5),
+        venue,
+    }))
+}
+
 // =============================================================================
 // Performance Profiling API
 // =============================================================================
@@ -21,6 +21,17 @@
 //!     -- Full artifact stored as compressed JSON blob
 //!     artifact_blob BLOB NOT NULL
 //! ) WITHOUT ROWID;
+//!
+//! -- Linked run sets (parameter sweeps, walk-forward folds, ...)
+//! CREATE TABLE run_groups (
+//!     group_id TEXT NOT NULL,
+//!     group_kind TEXT NOT NULL,
+//!     member_index INTEGER NOT NULL,
+//!     run_id TEXT NOT NULL,
+//!     role TEXT NOT NULL,
+//!     params_json TEXT NOT NULL,
+//!     PRIMARY KEY (group_id, member_index)
+//! ) WITHOUT ROWID;
 //! ```
 
 use crate::backtest_v2::publication::PublicationStatus;
@@ -30,7 +41,9 @@ use crate::backtest_v2::run_artifact::{
     TrustLevelDto, TrustStatus, RUN_ARTIFACT_API_VERSION, RUN_ARTIFACT_STORAGE_VERSION,
 };
 use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 use parking_lot::Mutex;
@@ -40,7 +53,8 @@ use tracing::{debug, info, warn, error};
 /// Version history:
 /// - v1: Initial schema
 /// - v2: Added publication_status column for public/internal separation
-const SCHEMA_VERSION: u32 = 2;
+/// - v3: Added run_groups table for linked run sets (parameter sweeps)
+const SCHEMA_VERSION: u32 = 3;
 
 /// Storage for run artifacts.
 pub struct ArtifactStore {
@@ -93,15 +107,23 @@ impl ArtifactStore {
             None => {
                 // Fresh database - create schema
                 self.create_schema_v2(&conn)?;
+                self.migrate_v2_to_v3(&conn)?;
                 conn.execute("INSERT INTO schema_version (version) VALUES (?)", [SCHEMA_VERSION])?;
                 info!("Created artifact store schema v{}", SCHEMA_VERSION);
             }
             Some(1) => {
-                // Migrate v1 -> v2
+                // Migrate v1 -> v2 -> v3
                 self.migrate_v1_to_v2(&conn)?;
+                self.migrate_v2_to_v3(&conn)?;
                 conn.execute("UPDATE schema_version SET version = ?", [SCHEMA_VERSION])?;
                 info!("Migrated artifact store schema from v1 to v{}", SCHEMA_VERSION);
             }
+            Some(2) => {
+                // Migrate v2 -> v3
+                self.migrate_v2_to_v3(&conn)?;
+                conn.execute("UPDATE schema_version SET version = ?", [SCHEMA_VERSION])?;
+                info!("Migrated artifact store schema from v2 to v{}", SCHEMA_VERSION);
+            }
             Some(v) if v == SCHEMA_VERSION => {
                 // Already at current version
                 debug!("Artifact store schema at v{}", SCHEMA_VERSION);
@@ -144,6 +166,27 @@ impl ArtifactStore {
         Ok(())
     }
     
+    fn migrate_v2_to_v3(&self, conn: &Connection) -> Result<(), ArtifactStoreError> {
+        conn.execute_batch(r#"
+            -- v3: Linked run sets. A run may belong to several groups; the
+            -- artifact itself stays immutable and content-addressed.
+            CREATE TABLE IF NOT EXISTS run_groups (
+                group_id TEXT NOT NULL,
+                group_kind TEXT NOT NULL,
+                member_index INTEGER NOT NULL,
+                run_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                params_json TEXT NOT NULL,
+                PRIMARY KEY (group_id, member_index)
+            ) WITHOUT ROWID;
+            
+            CREATE INDEX IF NOT EXISTS idx_run_groups_run
+                ON run_groups(run_id);
+        "#)?;
+        
+        Ok(())
+    }
+    
     fn create_schema_v2(&self, conn: &Connection) -> Result<(), ArtifactStoreError> {
         conn.execute_batch(r#"
             -- Main artifact table (v2 schema with publication and provenance)
@@ -658,6 +701,74 @@ impl ArtifactStore {
         })
     }
     
+    /// Record a run's membership in a linked run set.
+    /// 
+    /// The referenced run must already be persisted. Re-tagging the same
+    /// `(group_id, member_index)` replaces the previous entry.
+    pub fn tag_run_group(&self, member: &RunGroupMember) -> Result<(), ArtifactStoreError> {
+        if !self.exists(&member.run_id)? {
+            return Err(ArtifactStoreError::NotFound(member.run_id.as_str().to_string()));
+        }
+        
+        let params_json = serde_json::to_string(&member.params)?;
+        let conn = self.conn.lock();
+        conn.execute(
+            r#"INSERT OR REPLACE INTO run_groups (
+                group_id, group_kind, member_index, run_id, role, params_json
+            ) VALUES (?, ?, ?, ?, ?, ?)"#,
+            params![
+                member.group_id,
+                member.kind.as_db_str(),
+                member.member_index as i64,
+                member.run_id.as_str(),
+                member.role,
+                params_json,
+            ],
+        )?;
+        
+        debug!(
+            "Tagged run {} as {} #{} of {}",
+            member.run_id, member.role, member.member_index, member.group_id
+        );
+        Ok(())
+    }
+    
+    /// List all members of a linked run set, ordered by member index.
+    pub fn list_run_group(&self, group_id: &str) -> Result<Vec<RunGroupMember>, ArtifactStoreError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            r#"SELECT group_id, group_kind, member_index, run_id, role, params_json
+               FROM run_groups WHERE group_id = ? ORDER BY member_index ASC"#,
+        )?;
+        let rows = stmt.query_map([group_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?;
+        
+        let mut members = Vec::new();
+        for row in rows {
+            let (group_id, kind, member_index, run_id, role, params_json) = row?;
+            let kind = RunGroupKind::from_db_str(&kind).ok_or_else(|| {
+                ArtifactStoreError::NotFound(format!("Unknown run group kind: {}", kind))
+            })?;
+            members.push(RunGroupMember {
+                group_id,
+                kind,
+                member_index: member_index as u32,
+                run_id: RunId(run_id),
+                role,
+                params: serde_json::from_str(&params_json)?,
+            });
+        }
+        Ok(members)
+    }
+    
     /// Delete a run artifact (admin only - use with caution).
     #[cfg(test)]
     pub fn delete(&self, run_id: &RunId) -> Result<bool, ArtifactStoreError> {
@@ -706,6 +817,49 @@ impl ArtifactStore {
     }
 }
 
+/// Kind of linked run set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RunGroupKind {
+    /// Strategy parameter sweep (grid / random / Latin hypercube).
+    ParamSweep,
+    /// Walk-forward analysis (chosen train run + out-of-sample test run per fold).
+    WalkForward,
+}
+
+impl RunGroupKind {
+    fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::ParamSweep => "param_sweep",
+            Self::WalkForward => "walk_forward",
+        }
+    }
+    
+    fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "param_sweep" => Some(Self::ParamSweep),
+            "walk_forward" => Some(Self::WalkForward),
+            _ => None,
+        }
+    }
+}
+
+/// A single run's membership in a linked run set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunGroupMember {
+    /// Shared identifier of the run set (e.g. a sweep id).
+    pub group_id: String,
+    /// What kind of run set this is.
+    pub kind: RunGroupKind,
+    /// Position of this run within the set.
+    pub member_index: u32,
+    /// Persisted run this entry refers to.
+    pub run_id: RunId,
+    /// Role of the run within the set (e.g. "sweep_point").
+    pub role: String,
+    /// Strategy parameters that distinguish this member.
+    pub params: BTreeMap<String, f64>,
+}
+
 /// Statistics about the artifact store.
 #[derive(Debug, Clone)]
 pub struct ArtifactStoreStats {
@@ -800,16 +954,21 @@ mod tests {
                 oracle_config_hash: None,
                 latency_model: "Fixed".to_string(),
                 order_latency_ns: None,
+                latency_config_hash: 0,
                 oms_parity_mode: "Full".to_string(),
                 maker_fill_model: "Disabled".to_string(),
                 integrity_policy: "Strict".to_string(),
                 invariant_mode: "Hard".to_string(),
                 fee_rate_bps: None,
                 strategy_params_hash: 0,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "RecordedArrival".to_string(),
                 strict_accounting: true,
                 production_grade: true,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 0,
             },
             dataset: DatasetFingerprint {
@@ -968,6 +1127,44 @@ mod tests {
         assert_eq!(response.runs.len(), 2);
     }
 
+    #[test]
+    fn test_run_group_tagging() {
+        let store = ArtifactStore::in_memory().unwrap();
+        
+        for i in 0..3u32 {
+            let artifact = make_test_artifact(&format!("run_group_{}", i));
+            store.persist(&artifact).unwrap();
+            
+            let mut params = BTreeMap::new();
+            params.insert("clip_size".to_string(), 10.0 * (i + 1) as f64);
+            store.tag_run_group(&RunGroupMember {
+                group_id: "sweep_test".to_string(),
+                kind: RunGroupKind::ParamSweep,
+                member_index: i,
+                run_id: artifact.manifest.run_id.clone(),
+                role: "sweep_point".to_string(),
+                params,
+            }).unwrap();
+        }
+        
+        let members = store.list_run_group("sweep_test").unwrap();
+        assert_eq!(members.len(), 3);
+        assert_eq!(members[2].run_id.as_str(), "run_group_2");
+        assert_eq!(members[2].params.get("clip_size"), Some(&30.0));
+        assert!(store.list_run_group("missing").unwrap().is_empty());
+        
+        // Tagging an unknown run is rejected
+        let result = store.tag_run_group(&RunGroupMember {
+            group_id: "sweep_test".to_string(),
+            kind: RunGroupKind::ParamSweep,
+            member_index: 9,
+            run_id: RunId("run_missing".to_string()),
+            role: "sweep_point".to_string(),
+            params: BTreeMap::new(),
+        });
+        assert!(matches!(result, Err(ArtifactStoreError::NotFound(_))));
+    }
+
     #[test]
     fn test_stats() {
         let store = ArtifactStore::in_memory().unwrap();
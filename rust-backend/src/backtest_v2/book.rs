@@ -6,10 +6,11 @@
 use crate::backtest_v2::clock::Nanos;
 use crate::backtest_v2::events::{Level, Side};
 use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
 
 /// L2 Order Book representation.
 /// Uses BTreeMap for efficient sorted access to price levels.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBook {
     pub token_id: String,
     /// Bids sorted by price descending (best bid first)
@@ -27,7 +28,7 @@ pub struct OrderBook {
 /// Price wrapper for BTreeMap ordering.
 /// Bids: higher price = better (reverse order)
 /// Asks: lower price = better (natural order)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 struct OrderedPrice {
     price: f64,
     is_bid: bool,
@@ -59,7 +60,7 @@ impl Ord for OrderedPrice {
 }
 
 /// Internal book level representation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BookLevel {
     size: f64,
     order_count: Option<u32>,
@@ -383,6 +384,7 @@ pub struct DeltaResult {
 }
 
 /// Collection of order books by token_id.
+#[derive(Serialize, Deserialize)]
 pub struct BookManager {
     books: std::collections::HashMap<String, OrderBook>,
 }
@@ -10,6 +10,7 @@
 //! **CRITICAL**: Arrival time is captured at the EARLIEST possible point in the
 //! message handling path (before JSON parsing) to minimize measurement noise.
 
+use crate::backtest_v2::events::{Event, Level, TimestampedEvent};
 use anyhow::{Context, Result};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection, OpenFlags};
@@ -127,6 +128,28 @@ impl RecordedBookSnapshot {
             _ => false,
         }
     }
+
+    /// Convert to a backtest snapshot event timed at arrival.
+    pub fn to_timestamped_event(&self, source: u8) -> TimestampedEvent {
+        let to_levels = |levels: &[PriceLevel]| {
+            levels.iter().map(|l| Level::new(l.price, l.size)).collect()
+        };
+        TimestampedEvent {
+            time: self.arrival_time_as_nanos(),
+            source_time: self
+                .source_time_ns
+                .map(|t| t as i64)
+                .unwrap_or_else(|| self.arrival_time_as_nanos()),
+            seq: self.local_seq,
+            source,
+            event: Event::L2BookSnapshot {
+                token_id: self.token_id.clone(),
+                bids: to_levels(&self.bids),
+                asks: to_levels(&self.asks),
+                exchange_seq: self.exchange_seq.unwrap_or(0),
+            },
+        }
+    }
 }
 
 // =============================================================================
@@ -0,0 +1,308 @@
+//! Backtest Checkpoints
+//!
+//! Snapshots of a running `BacktestOrchestrator` taken at 15-minute window
+//! boundaries, from which a run can be resumed (after a crash) or forked
+//! (what-if branches from a mid-run state).
+//!
+//! # Contents
+//!
+//! A checkpoint holds the complete engine state between two events: sim
+//! clock, event queue, OMS and matching books, ledger, portfolio, settlement
+//! and accounting engines, integrity/invariant trackers, fingerprint
+//! accumulators and RNG positions, plus the strategy's own state from
+//! `Strategy::get_state`. Configuration is NOT stored; a checkpoint records
+//! the config fingerprint hash and can only be resumed under the same config.
+//!
+//! When the run was fed by a streaming feed, the feed itself is not captured
+//! either; the checkpoint records how many events had been pulled so the
+//! caller-supplied feed can be fast-forwarded on resume.
+//!
+//! # Determinism Contract
+//!
+//! Resuming from a checkpoint and running to completion produces the same
+//! `RunFingerprint` as the uninterrupted run, provided the strategy restores
+//! its full state in `Strategy::restore_state`.
+
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Checkpoint encoding version. Bumped whenever the engine state layout changes.
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+/// File extension for checkpoints written to disk.
+pub const CHECKPOINT_FILE_EXTENSION: &str = "ckpt";
+
+// =============================================================================
+// POLICY
+// =============================================================================
+
+/// When and where checkpoints are taken during `run()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointPolicy {
+    /// Take a checkpoint every N 15-minute windows (minimum 1).
+    pub interval_windows: u32,
+    /// Directory checkpoints are written to (None = in memory only).
+    pub directory: Option<PathBuf>,
+    /// Number of most recent checkpoints kept in memory.
+    pub retain_in_memory: usize,
+}
+
+impl Default for CheckpointPolicy {
+    fn default() -> Self {
+        Self {
+            interval_windows: 4,
+            directory: None,
+            retain_in_memory: 1,
+        }
+    }
+}
+
+impl CheckpointPolicy {
+    /// Checkpoint at every window boundary, kept in memory.
+    pub fn every_window() -> Self {
+        Self {
+            interval_windows: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Also persist checkpoints to `directory`.
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Spacing between checkpoints in nanoseconds.
+    pub fn interval_ns(&self) -> Nanos {
+        self.interval_windows.max(1) as Nanos * WINDOW_DURATION_NS
+    }
+}
+
+// =============================================================================
+// CHECKPOINT
+// =============================================================================
+
+/// Engine state captured at a window boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestCheckpoint {
+    pub format_version: u32,
+    /// Start of the window the next event falls in.
+    pub window_start_ns: Nanos,
+    /// Sim time at capture (time of the last processed event).
+    pub sim_time_ns: Nanos,
+    /// Sim time the original run started at.
+    pub run_start_ns: Nanos,
+    /// Events processed before the checkpoint.
+    pub events_processed: u64,
+    /// `ConfigFingerprint` hash of the run's config.
+    pub config_hash: u64,
+    /// `ConfigFingerprint::engine_hash` of the run's config; forks must match it.
+    pub engine_config_hash: u64,
+    /// Name of the strategy that produced the state.
+    pub strategy_name: String,
+    /// Strategy state from `Strategy::get_state`.
+    pub strategy_state: Option<String>,
+    /// Events pulled from the streaming feed (None = no streaming feed).
+    pub streaming_events_pulled: Option<u64>,
+    /// Hash of the encoded engine state.
+    pub state_hash: u64,
+    /// Encoded engine state (owned by the orchestrator).
+    state: Vec<u8>,
+}
+
+impl BacktestCheckpoint {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        window_start_ns: Nanos,
+        sim_time_ns: Nanos,
+        run_start_ns: Nanos,
+        events_processed: u64,
+        config_hash: u64,
+        engine_config_hash: u64,
+        strategy_name: String,
+        strategy_state: Option<String>,
+        streaming_events_pulled: Option<u64>,
+        state: Vec<u8>,
+    ) -> Self {
+        Self {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            window_start_ns,
+            sim_time_ns,
+            run_start_ns,
+            events_processed,
+            config_hash,
+            engine_config_hash,
+            strategy_name,
+            strategy_state,
+            streaming_events_pulled,
+            state_hash: hash_state(&state),
+            state,
+        }
+    }
+
+    pub(crate) fn state(&self) -> &[u8] {
+        &self.state
+    }
+
+    /// Size of the encoded engine state in bytes.
+    pub fn state_len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Check version and state integrity.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format_version != CHECKPOINT_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported checkpoint format version {} (expected {})",
+                self.format_version, CHECKPOINT_FORMAT_VERSION
+            ));
+        }
+        let actual = hash_state(&self.state);
+        if actual != self.state_hash {
+            return Err(format!(
+                "Checkpoint state hash mismatch: recorded {:016x}, computed {:016x}",
+                self.state_hash, actual
+            ));
+        }
+        Ok(())
+    }
+
+    /// Canonical file name, ordered by window start.
+    pub fn file_name(&self) -> String {
+        format!("checkpoint_{:020}.{}", self.window_start_ns, CHECKPOINT_FILE_EXTENSION)
+    }
+
+    /// Encode the checkpoint to bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        rmp_serde::to_vec_named(self).map_err(|e| format!("Failed to encode checkpoint: {}", e))
+    }
+
+    /// Decode and validate a checkpoint.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let checkpoint: Self =
+            rmp_serde::from_slice(bytes).map_err(|e| format!("Failed to decode checkpoint: {}", e))?;
+        checkpoint.validate()?;
+        Ok(checkpoint)
+    }
+
+    /// Write into `directory` under `file_name()`. The file is written to a
+    /// temporary name and renamed, so a crash never leaves a torn checkpoint.
+    pub fn write_to_dir(&self, directory: &Path) -> Result<PathBuf, String> {
+        std::fs::create_dir_all(directory)
+            .map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+        let path = directory.join(self.file_name());
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, self.to_bytes()?)
+            .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &path)
+            .map_err(|e| format!("Failed to rename {}: {}", tmp.display(), e))?;
+        Ok(path)
+    }
+
+    /// Read and validate a checkpoint file.
+    pub fn read_from(path: &Path) -> Result<Self, String> {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_bytes(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Latest checkpoint in `directory` (by window start), if any.
+    pub fn latest_in(directory: &Path) -> Result<Option<Self>, String> {
+        let entries = match std::fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to list {}: {}", directory.display(), e)),
+        };
+
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("Failed to list {}: {}", directory.display(), e))?
+                .path();
+            if path.extension().and_then(|e| e.to_str()) == Some(CHECKPOINT_FILE_EXTENSION) {
+                paths.push(path);
+            }
+        }
+        // Zero-padded window start makes name order chronological
+        paths.sort();
+
+        paths.last().map(|p| Self::read_from(p)).transpose()
+    }
+}
+
+fn hash_state(state: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(window_start_ns: Nanos) -> BacktestCheckpoint {
+        BacktestCheckpoint::new(
+            window_start_ns,
+            window_start_ns - 5,
+            0,
+            42,
+            0xABCD,
+            0xEF01,
+            "test".to_string(),
+            Some("{\"n\":1}".to_string()),
+            None,
+            vec![1, 2, 3, 4],
+        )
+    }
+
+    #[test]
+    fn test_bytes_roundtrip_and_corruption() {
+        let original = checkpoint(WINDOW_DURATION_NS);
+        let bytes = original.to_bytes().unwrap();
+        let decoded = BacktestCheckpoint::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.events_processed, 42);
+        assert_eq!(decoded.state(), original.state());
+        assert_eq!(decoded.strategy_state, original.strategy_state);
+
+        let mut tampered = original.clone();
+        tampered.state[0] ^= 0xFF;
+        assert!(tampered.validate().unwrap_err().contains("hash mismatch"));
+
+        let mut future = original;
+        future.format_version += 1;
+        assert!(future.validate().unwrap_err().contains("format version"));
+    }
+
+    #[test]
+    fn test_latest_in_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(BacktestCheckpoint::latest_in(&dir.path().join("missing")).unwrap().is_none());
+
+        for window in [3, 1, 12] {
+            checkpoint(window * WINDOW_DURATION_NS).write_to_dir(dir.path()).unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let latest = BacktestCheckpoint::latest_in(dir.path()).unwrap().unwrap();
+        assert_eq!(latest.window_start_ns, 12 * WINDOW_DURATION_NS);
+    }
+
+    #[test]
+    fn test_policy_interval() {
+        assert_eq!(CheckpointPolicy::every_window().interval_ns(), WINDOW_DURATION_NS);
+        let policy = CheckpointPolicy {
+            interval_windows: 0,
+            ..Default::default()
+        };
+        assert_eq!(policy.interval_ns(), WINDOW_DURATION_NS);
+        assert_eq!(CheckpointPolicy::default().interval_ns(), 4 * WINDOW_DURATION_NS);
+    }
+}
@@ -4,6 +4,7 @@
 //! Single source of truth for all simulation time - NEVER call system time.
 
 use std::fmt;
+use serde::{Deserialize, Serialize};
 
 /// Nanoseconds since Unix epoch (1970-01-01 00:00:00 UTC).
 /// i64 gives us ~292 years of range, sufficient for any backtest.
@@ -20,7 +21,7 @@ pub const NANOS_PER_SEC: i64 = 1_000_000_000;
 /// - `now()` returns the current simulation time, never system time
 /// - `advance_to()` only moves forward, panics on backward movement
 /// - All components must use this clock exclusively for timestamps
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimClock {
     current: Nanos,
 }
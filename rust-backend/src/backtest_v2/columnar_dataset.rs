@@ -0,0 +1,1912 @@
+//! Columnar Dataset Export and Replay
+//!
+//! Exports recorded market data (L2 snapshots/deltas, book snapshots, trade
+//! prints, oracle rounds) from the SQLite recorders into Parquet files
+//! partitioned by market and UTC day, and replays them through
+//! `MarketDataFeed` without touching SQLite.
+//!
+//! # Layout
+//!
+//! ```text
+//! <root>/manifest.json
+//! <root>/events/market=<token_id>/date=YYYY-MM-DD/part-00000.parquet
+//! <root>/oracle_rounds/feed=<feed_id>/date=YYYY-MM-DD/part-00000.parquet
+//! ```
+//!
+//! Market data is stored as normalized `TimestampedEvent` rows. Arrival time,
+//! source time, sequence and source are explicit columns, so a replay yields
+//! exactly the events the SQLite path would. Every row also carries a global
+//! export ordinal; the replay feed orders by `(time, ordinal)`, which matches
+//! `VecFeed` over the events in the order they were exported.
+//!
+//! # Fingerprint
+//!
+//! The exporter computes the `DatasetFingerprint` (one stream per record kind,
+//! records hashed canonically in export order) and stores it in the manifest
+//! together with per-partition row counts and content hashes. Partitions are
+//! integrity-checked whenever they are loaded, and `ColumnarDataset::verify`
+//! recomputes the full fingerprint from the files.
+//!
+//! # Replay
+//!
+//! `ColumnarReplayFeed` streams the files a row group at a time (see its docs)
+//! and can be handed to the orchestrator like any other `MarketDataFeed`.
+//! The `columnar_export` binary converts recorder databases into a dataset and
+//! verifies existing ones.
+
+use crate::backtest_v2::book_recorder::BookSnapshotStorage;
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
+use crate::backtest_v2::data_contract::{DatasetReadiness, HistoricalDataContract};
+use crate::backtest_v2::events::{Event, Level, Side, TimestampedEvent};
+use crate::backtest_v2::feed::MarketDataFeed;
+use crate::backtest_v2::fingerprint::{DatasetFingerprint, StreamFingerprint, StreamFingerprintBuilder};
+use crate::backtest_v2::l2_replay::L2ReplayFeed;
+use crate::backtest_v2::l2_storage::L2Storage;
+use crate::backtest_v2::oracle::{ChainlinkRound, OracleRoundStorage};
+use crate::backtest_v2::queue::StreamSource;
+use crate::backtest_v2::reproducibility::{CanonicalEncoder, RollingHash};
+use crate::backtest_v2::trade_print_storage::TradePrintFullStorage;
+use arrow_array::builder::{
+    Float64Builder, Int64Builder, ListBuilder, StringBuilder, UInt32Builder, UInt64Builder,
+    UInt8Builder,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Decimal128Type, Float64Type, Int64Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, Decimal128Array, Int64Array, ListArray, RecordBatch, StringArray,
+    UInt64Array, UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Current on-disk format version.
+pub const COLUMNAR_FORMAT_VERSION: u32 = 1;
+
+/// Manifest file name at the dataset root.
+pub const COLUMNAR_MANIFEST_FILE: &str = "manifest.json";
+
+/// Default rows buffered per partition before a record batch is written.
+pub const DEFAULT_COLUMNAR_BATCH_ROWS: usize = 65_536;
+
+/// Fingerprint stream name for oracle rounds.
+const ORACLE_STREAM_NAME: &str = "oracle_rounds";
+
+// =============================================================================
+// MANIFEST
+// =============================================================================
+
+/// Top-level stream a partition belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnarStream {
+    /// Market data events (book snapshots, deltas, trade prints), keyed by token.
+    MarketEvents,
+    /// Chainlink oracle rounds, keyed by feed.
+    OracleRounds,
+}
+
+impl ColumnarStream {
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::MarketEvents => "events",
+            Self::OracleRounds => "oracle_rounds",
+        }
+    }
+
+    fn key_name(self) -> &'static str {
+        match self {
+            Self::MarketEvents => "market",
+            Self::OracleRounds => "feed",
+        }
+    }
+}
+
+/// One Parquet file: a single stream, market (or feed) and UTC day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnarPartition {
+    pub stream: ColumnarStream,
+    /// Token id for market events, feed id for oracle rounds.
+    pub key: String,
+    /// UTC day (`YYYY-MM-DD`).
+    pub date: String,
+    /// File path relative to the dataset root.
+    pub path: String,
+    pub row_count: u64,
+    /// Earliest partitioning time in the file (ns).
+    pub start_time_ns: Nanos,
+    /// Latest partitioning time in the file (ns).
+    pub end_time_ns: Nanos,
+    /// Rolling hash over `(ordinal, record hash)` in file order.
+    pub content_hash: String,
+    /// Rows were written in non-decreasing partitioning time, so the replay
+    /// feed can stream the file a row group at a time.
+    #[serde(default)]
+    pub time_ordered: bool,
+}
+
+impl ColumnarPartition {
+    fn overlaps(&self, start_ns: Nanos, end_ns: Nanos) -> bool {
+        self.start_time_ns <= end_ns && self.end_time_ns >= start_ns
+    }
+}
+
+/// Dataset manifest written at the root of an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnarManifest {
+    pub format_version: u32,
+    /// Partitions sorted by (stream, key, date).
+    pub partitions: Vec<ColumnarPartition>,
+    /// Fingerprint of the exported records.
+    pub dataset_fingerprint: DatasetFingerprint,
+}
+
+impl ColumnarManifest {
+    /// Total exported rows across all partitions.
+    pub fn total_rows(&self) -> u64 {
+        self.partitions.iter().map(|p| p.row_count).sum()
+    }
+}
+
+// =============================================================================
+// FINGERPRINTING
+// =============================================================================
+
+/// Fingerprint stream name for a market data event, `None` if not exportable.
+fn event_stream_name(event: &Event) -> Option<&'static str> {
+    match event {
+        Event::L2BookSnapshot { .. } => Some("orderbook_snapshots"),
+        Event::L2Delta { .. } | Event::L2BookDelta { .. } => Some("orderbook_deltas"),
+        Event::TradePrint { .. } => Some("trades"),
+        _ => None,
+    }
+}
+
+fn write_str(encoder: &mut CanonicalEncoder, s: Option<&str>) {
+    match s {
+        None => encoder.write_u8(0),
+        Some(s) => {
+            encoder.write_u8(1);
+            encoder.write_u32(s.len() as u32);
+            for b in s.as_bytes() {
+                encoder.write_u8(*b);
+            }
+        }
+    }
+}
+
+fn write_levels(encoder: &mut CanonicalEncoder, levels: &[Level]) {
+    encoder.write_u32(levels.len() as u32);
+    for level in levels {
+        encoder.write_u64(level.price.to_bits());
+        encoder.write_u64(level.size.to_bits());
+        encoder.write_u32(level.order_count.map(|c| c + 1).unwrap_or(0));
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash = RollingHash::new();
+    hash.update(bytes);
+    hash.finish_u64()
+}
+
+/// Accumulates per-stream fingerprints in export order.
+#[derive(Default)]
+struct FingerprintAccumulator {
+    streams: BTreeMap<&'static str, StreamFingerprintBuilder>,
+}
+
+impl FingerprintAccumulator {
+    fn add(&mut self, stream: &'static str, timestamp_ns: Nanos, market_id: &str, record_hash: u64) {
+        self.streams
+            .entry(stream)
+            .or_insert_with(|| StreamFingerprintBuilder::new(stream))
+            .add_record(timestamp_ns, Some(market_id), record_hash);
+    }
+
+    fn build_streams(self) -> Vec<StreamFingerprint> {
+        self.streams.into_values().map(|b| b.build()).collect()
+    }
+}
+
+/// Compute the fingerprint an export of `events` and `rounds` (in this order)
+/// would record, e.g. to compare a SQLite-loaded dataset with its export.
+pub fn columnar_dataset_fingerprint(
+    contract: &HistoricalDataContract,
+    readiness: DatasetReadiness,
+    events: &[TimestampedEvent],
+    rounds: &[ChainlinkRound],
+) -> Result<DatasetFingerprint, String> {
+    let mut acc = FingerprintAccumulator::default();
+    for event in events {
+        acc.add(event.stream_name()?, event.fingerprint_time(), event.partition_key()?, event.record_hash());
+    }
+    for round in rounds {
+        acc.add(round.stream_name()?, round.fingerprint_time(), round.partition_key()?, round.record_hash());
+    }
+    Ok(DatasetFingerprint::new(contract, readiness, acc.build_streams()))
+}
+
+// =============================================================================
+// ROW ENCODING
+// =============================================================================
+
+/// A record type that can be stored in a columnar partition.
+trait ColumnarRow: Sized {
+    const STREAM: ColumnarStream;
+
+    fn schema() -> SchemaRef;
+    fn stream_name(&self) -> Result<&'static str, String>;
+    fn partition_key(&self) -> Result<&str, String>;
+    /// Time used for day partitioning and range queries.
+    fn partition_time(&self) -> Nanos;
+    /// Time recorded in the stream fingerprint.
+    fn fingerprint_time(&self) -> Nanos;
+    fn record_hash(&self) -> u64;
+    fn to_batch(rows: &[(u64, Self)]) -> Result<RecordBatch, String>;
+    fn read_batch(batch: &RecordBatch, out: &mut Vec<(u64, Self)>) -> Result<(), String>;
+}
+
+const KIND_BOOK_SNAPSHOT: u8 = 0;
+const KIND_L2_DELTA: u8 = 1;
+const KIND_BOOK_DELTA: u8 = 2;
+const KIND_TRADE_PRINT: u8 = 3;
+
+fn side_code(side: Side) -> u8 {
+    match side {
+        Side::Buy => 0,
+        Side::Sell => 1,
+    }
+}
+
+fn side_from_code(code: u8) -> Result<Side, String> {
+    match code {
+        0 => Ok(Side::Buy),
+        1 => Ok(Side::Sell),
+        other => Err(format!("invalid side code {}", other)),
+    }
+}
+
+fn list_field(name: &str, item: DataType) -> Field {
+    Field::new(name, DataType::List(Arc::new(Field::new_list_field(item, true))), true)
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef, String> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| format!("missing column '{}'", name))
+}
+
+fn type_error(name: &str) -> String {
+    format!("column '{}' has an unexpected type", name)
+}
+
+fn u64_col<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a UInt64Array, String> {
+    column(batch, name)?.as_primitive_opt::<UInt64Type>().ok_or_else(|| type_error(name))
+}
+
+fn i64_col<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a Int64Array, String> {
+    column(batch, name)?.as_primitive_opt::<Int64Type>().ok_or_else(|| type_error(name))
+}
+
+fn str_col<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a StringArray, String> {
+    column(batch, name)?.as_string_opt::<i32>().ok_or_else(|| type_error(name))
+}
+
+fn list_col<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ListArray, String> {
+    column(batch, name)?.as_list_opt::<i32>().ok_or_else(|| type_error(name))
+}
+
+fn decimal_col<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a Decimal128Array, String> {
+    column(batch, name)?.as_primitive_opt::<Decimal128Type>().ok_or_else(|| type_error(name))
+}
+
+fn opt_str(array: &StringArray, row: usize) -> Option<String> {
+    (!array.is_null(row)).then(|| array.value(row).to_string())
+}
+
+/// List builders for one side of the book (prices, sizes, order counts).
+struct LevelColumns {
+    prices: ListBuilder<Float64Builder>,
+    sizes: ListBuilder<Float64Builder>,
+    counts: ListBuilder<UInt32Builder>,
+}
+
+impl LevelColumns {
+    fn new() -> Self {
+        Self {
+            prices: ListBuilder::new(Float64Builder::new()),
+            sizes: ListBuilder::new(Float64Builder::new()),
+            counts: ListBuilder::new(UInt32Builder::new()),
+        }
+    }
+
+    fn append(&mut self, levels: Option<&[Level]>) {
+        let present = levels.is_some();
+        for level in levels.unwrap_or(&[]) {
+            self.prices.values().append_value(level.price);
+            self.sizes.values().append_value(level.size);
+            self.counts.values().append_option(level.order_count);
+        }
+        self.prices.append(present);
+        self.sizes.append(present);
+        self.counts.append(present);
+    }
+
+    fn finish(mut self) -> [ArrayRef; 3] {
+        [
+            Arc::new(self.prices.finish()),
+            Arc::new(self.sizes.finish()),
+            Arc::new(self.counts.finish()),
+        ]
+    }
+
+    fn read(batch: &RecordBatch, side: &str, row: usize) -> Result<Vec<Level>, String> {
+        let prices = list_col(batch, &format!("{}_prices", side))?;
+        let sizes = list_col(batch, &format!("{}_sizes", side))?;
+        let counts = list_col(batch, &format!("{}_counts", side))?;
+        if prices.is_null(row) {
+            return Ok(Vec::new());
+        }
+        let p = prices.value(row);
+        let s = sizes.value(row);
+        let c = counts.value(row);
+        let p = p.as_primitive_opt::<Float64Type>().ok_or_else(|| type_error(side))?;
+        let s = s.as_primitive_opt::<Float64Type>().ok_or_else(|| type_error(side))?;
+        let c = c.as_primitive_opt::<UInt32Type>().ok_or_else(|| type_error(side))?;
+        if p.len() != s.len() || p.len() != c.len() {
+            return Err(format!("ragged {} level lists at row {}", side, row));
+        }
+        Ok((0..p.len())
+            .map(|i| Level {
+                price: p.value(i),
+                size: s.value(i),
+                order_count: (!c.is_null(i)).then(|| c.value(i)),
+            })
+            .collect())
+    }
+}
+
+impl ColumnarRow for TimestampedEvent {
+    const STREAM: ColumnarStream = ColumnarStream::MarketEvents;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("ordinal", DataType::UInt64, false),
+            Field::new("time", DataType::Int64, false),
+            Field::new("source_time", DataType::Int64, false),
+            Field::new("seq", DataType::UInt64, false),
+            Field::new("source", DataType::UInt8, false),
+            Field::new("kind", DataType::UInt8, false),
+            Field::new("token_id", DataType::Utf8, false),
+            Field::new("exchange_seq", DataType::UInt64, true),
+            Field::new("side", DataType::UInt8, true),
+            Field::new("price", DataType::Float64, true),
+            Field::new("size", DataType::Float64, true),
+            Field::new("tag", DataType::Utf8, true),
+            list_field("bid_prices", DataType::Float64),
+            list_field("bid_sizes", DataType::Float64),
+            list_field("bid_counts", DataType::UInt32),
+            list_field("ask_prices", DataType::Float64),
+            list_field("ask_sizes", DataType::Float64),
+            list_field("ask_counts", DataType::UInt32),
+        ]))
+    }
+
+    fn stream_name(&self) -> Result<&'static str, String> {
+        event_stream_name(&self.event).ok_or_else(|| {
+            format!("event at {} is not market data and cannot be exported", self.time)
+        })
+    }
+
+    fn partition_key(&self) -> Result<&str, String> {
+        self.event
+            .token_id()
+            .ok_or_else(|| format!("event at {} has no token id", self.time))
+    }
+
+    fn partition_time(&self) -> Nanos {
+        self.time
+    }
+
+    fn fingerprint_time(&self) -> Nanos {
+        self.time
+    }
+
+    fn record_hash(&self) -> u64 {
+        let mut encoder = CanonicalEncoder::with_capacity(128);
+        encoder.write_i64(self.time);
+        encoder.write_i64(self.source_time);
+        encoder.write_u64(self.seq);
+        encoder.write_u8(self.source);
+        match &self.event {
+            Event::L2BookSnapshot { token_id, bids, asks, exchange_seq } => {
+                encoder.write_u8(KIND_BOOK_SNAPSHOT);
+                write_str(&mut encoder, Some(token_id));
+                encoder.write_u64(*exchange_seq);
+                write_levels(&mut encoder, bids);
+                write_levels(&mut encoder, asks);
+            }
+            Event::L2Delta { token_id, bid_updates, ask_updates, exchange_seq } => {
+                encoder.write_u8(KIND_L2_DELTA);
+                write_str(&mut encoder, Some(token_id));
+                encoder.write_u64(*exchange_seq);
+                write_levels(&mut encoder, bid_updates);
+                write_levels(&mut encoder, ask_updates);
+            }
+            Event::L2BookDelta { token_id, side, price, new_size, seq_hash } => {
+                encoder.write_u8(KIND_BOOK_DELTA);
+                write_str(&mut encoder, Some(token_id));
+                encoder.write_u8(side_code(*side));
+                encoder.write_u64(price.to_bits());
+                encoder.write_u64(new_size.to_bits());
+                write_str(&mut encoder, seq_hash.as_deref());
+            }
+            Event::TradePrint { token_id, price, size, aggressor_side, trade_id } => {
+                encoder.write_u8(KIND_TRADE_PRINT);
+                write_str(&mut encoder, Some(token_id));
+                encoder.write_u8(side_code(*aggressor_side));
+                encoder.write_u64(price.to_bits());
+                encoder.write_u64(size.to_bits());
+                write_str(&mut encoder, trade_id.as_deref());
+            }
+            _ => encoder.write_u8(u8::MAX),
+        }
+        hash_bytes(encoder.as_bytes())
+    }
+
+    fn to_batch(rows: &[(u64, Self)]) -> Result<RecordBatch, String> {
+        let mut ordinal = UInt64Builder::with_capacity(rows.len());
+        let mut time = Int64Builder::with_capacity(rows.len());
+        let mut source_time = Int64Builder::with_capacity(rows.len());
+        let mut seq = UInt64Builder::with_capacity(rows.len());
+        let mut source = UInt8Builder::with_capacity(rows.len());
+        let mut kind = UInt8Builder::with_capacity(rows.len());
+        let mut token_id = StringBuilder::new();
+        let mut exchange_seq = UInt64Builder::with_capacity(rows.len());
+        let mut side = UInt8Builder::with_capacity(rows.len());
+        let mut price = Float64Builder::with_capacity(rows.len());
+        let mut size = Float64Builder::with_capacity(rows.len());
+        let mut tag = StringBuilder::new();
+        let mut bids = LevelColumns::new();
+        let mut asks = LevelColumns::new();
+
+        for (ord, event) in rows {
+            ordinal.append_value(*ord);
+            time.append_value(event.time);
+            source_time.append_value(event.source_time);
+            seq.append_value(event.seq);
+            source.append_value(event.source);
+            match &event.event {
+                Event::L2BookSnapshot { token_id: t, bids: b, asks: a, exchange_seq: s } => {
+                    kind.append_value(KIND_BOOK_SNAPSHOT);
+                    token_id.append_value(t);
+                    exchange_seq.append_value(*s);
+                    side.append_null();
+                    price.append_null();
+                    size.append_null();
+                    tag.append_null();
+                    bids.append(Some(b));
+                    asks.append(Some(a));
+                }
+                Event::L2Delta { token_id: t, bid_updates: b, ask_updates: a, exchange_seq: s } => {
+                    kind.append_value(KIND_L2_DELTA);
+                    token_id.append_value(t);
+                    exchange_seq.append_value(*s);
+                    side.append_null();
+                    price.append_null();
+                    size.append_null();
+                    tag.append_null();
+                    bids.append(Some(b));
+                    asks.append(Some(a));
+                }
+                Event::L2BookDelta { token_id: t, side: sd, price: p, new_size, seq_hash } => {
+                    kind.append_value(KIND_BOOK_DELTA);
+                    token_id.append_value(t);
+                    exchange_seq.append_null();
+                    side.append_value(side_code(*sd));
+                    price.append_value(*p);
+                    size.append_value(*new_size);
+                    tag.append_option(seq_hash.as_deref());
+                    bids.append(None);
+                    asks.append(None);
+                }
+                Event::TradePrint { token_id: t, price: p, size: s, aggressor_side, trade_id } => {
+                    kind.append_value(KIND_TRADE_PRINT);
+                    token_id.append_value(t);
+                    exchange_seq.append_null();
+                    side.append_value(side_code(*aggressor_side));
+                    price.append_value(*p);
+                    size.append_value(*s);
+                    tag.append_option(trade_id.as_deref());
+                    bids.append(None);
+                    asks.append(None);
+                }
+                _ => return Err(event.stream_name().unwrap_err()),
+            }
+        }
+
+        let [bid_prices, bid_sizes, bid_counts] = bids.finish();
+        let [ask_prices, ask_sizes, ask_counts] = asks.finish();
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(ordinal.finish()),
+            Arc::new(time.finish()),
+            Arc::new(source_time.finish()),
+            Arc::new(seq.finish()),
+            Arc::new(source.finish()),
+            Arc::new(kind.finish()),
+            Arc::new(token_id.finish()),
+            Arc::new(exchange_seq.finish()),
+            Arc::new(side.finish()),
+            Arc::new(price.finish()),
+            Arc::new(size.finish()),
+            Arc::new(tag.finish()),
+            bid_prices,
+            bid_sizes,
+            bid_counts,
+            ask_prices,
+            ask_sizes,
+            ask_counts,
+        ];
+        RecordBatch::try_new(Self::schema(), columns)
+            .map_err(|e| format!("Failed to build event batch: {}", e))
+    }
+
+    fn read_batch(batch: &RecordBatch, out: &mut Vec<(u64, Self)>) -> Result<(), String> {
+        let ordinal = u64_col(batch, "ordinal")?;
+        let time = i64_col(batch, "time")?;
+        let source_time = i64_col(batch, "source_time")?;
+        let seq = u64_col(batch, "seq")?;
+        let source = column(batch, "source")?
+            .as_primitive_opt::<UInt8Type>()
+            .ok_or_else(|| type_error("source"))?;
+        let kind = column(batch, "kind")?
+            .as_primitive_opt::<UInt8Type>()
+            .ok_or_else(|| type_error("kind"))?;
+        let token_id = str_col(batch, "token_id")?;
+        let exchange_seq = u64_col(batch, "exchange_seq")?;
+        let side = column(batch, "side")?
+            .as_primitive_opt::<UInt8Type>()
+            .ok_or_else(|| type_error("side"))?;
+        let price = column(batch, "price")?
+            .as_primitive_opt::<Float64Type>()
+            .ok_or_else(|| type_error("price"))?;
+        let size = column(batch, "size")?
+            .as_primitive_opt::<Float64Type>()
+            .ok_or_else(|| type_error("size"))?;
+        let tag = str_col(batch, "tag")?;
+
+        for row in 0..batch.num_rows() {
+            let token = token_id.value(row).to_string();
+            let event = match kind.value(row) {
+                KIND_BOOK_SNAPSHOT => Event::L2BookSnapshot {
+                    token_id: token,
+                    bids: LevelColumns::read(batch, "bid", row)?,
+                    asks: LevelColumns::read(batch, "ask", row)?,
+                    exchange_seq: exchange_seq.value(row),
+                },
+                KIND_L2_DELTA => Event::L2Delta {
+                    token_id: token,
+                    bid_updates: LevelColumns::read(batch, "bid", row)?,
+                    ask_updates: LevelColumns::read(batch, "ask", row)?,
+                    exchange_seq: exchange_seq.value(row),
+                },
+                KIND_BOOK_DELTA => Event::L2BookDelta {
+                    token_id: token,
+                    side: side_from_code(side.value(row))?,
+                    price: price.value(row),
+                    new_size: size.value(row),
+                    seq_hash: opt_str(tag, row),
+                },
+                KIND_TRADE_PRINT => Event::TradePrint {
+                    token_id: token,
+                    price: price.value(row),
+                    size: size.value(row),
+                    aggressor_side: side_from_code(side.value(row))?,
+                    trade_id: opt_str(tag, row),
+                },
+                other => return Err(format!("unknown event kind {} at row {}", other, row)),
+            };
+            out.push((
+                ordinal.value(row),
+                TimestampedEvent {
+                    time: time.value(row),
+                    source_time: source_time.value(row),
+                    seq: seq.value(row),
+                    source: source.value(row),
+                    event,
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ColumnarRow for ChainlinkRound {
+    const STREAM: ColumnarStream = ColumnarStream::OracleRounds;
+
+    fn schema() -> SchemaRef {
+        let id = DataType::Decimal128(38, 0);
+        Arc::new(Schema::new(vec![
+            Field::new("ordinal", DataType::UInt64, false),
+            Field::new("feed_id", DataType::Utf8, false),
+            Field::new("round_id", id.clone(), false),
+            Field::new("answer", id.clone(), false),
+            Field::new("updated_at", DataType::UInt64, false),
+            Field::new("answered_in_round", id, false),
+            Field::new("started_at", DataType::UInt64, false),
+            Field::new("ingest_arrival_time_ns", DataType::UInt64, false),
+            Field::new("ingest_seq", DataType::UInt64, false),
+            Field::new("decimals", DataType::UInt8, false),
+            Field::new("asset_symbol", DataType::Utf8, false),
+            Field::new("raw_source_hash", DataType::Utf8, true),
+        ]))
+    }
+
+    fn stream_name(&self) -> Result<&'static str, String> {
+        Ok(ORACLE_STREAM_NAME)
+    }
+
+    fn partition_key(&self) -> Result<&str, String> {
+        Ok(&self.feed_id)
+    }
+
+    /// Rounds are loaded by `updated_at` (seconds), like `OracleRoundStorage`.
+    fn partition_time(&self) -> Nanos {
+        (self.updated_at as i64).saturating_mul(NANOS_PER_SEC)
+    }
+
+    fn fingerprint_time(&self) -> Nanos {
+        self.ingest_arrival_time_ns as i64
+    }
+
+    fn record_hash(&self) -> u64 {
+        let mut encoder = CanonicalEncoder::with_capacity(128);
+        write_str(&mut encoder, Some(&self.feed_id));
+        encoder.write_i128(self.round_id as i128);
+        encoder.write_i128(self.answer);
+        encoder.write_u64(self.updated_at);
+        encoder.write_i128(self.answered_in_round as i128);
+        encoder.write_u64(self.started_at);
+        encoder.write_u64(self.ingest_arrival_time_ns);
+        encoder.write_u64(self.ingest_seq);
+        encoder.write_u8(self.decimals);
+        write_str(&mut encoder, Some(&self.asset_symbol));
+        write_str(&mut encoder, self.raw_source_hash.as_deref());
+        hash_bytes(encoder.as_bytes())
+    }
+
+    fn to_batch(rows: &[(u64, Self)]) -> Result<RecordBatch, String> {
+        let decimal = |values: Vec<i128>| -> Result<ArrayRef, String> {
+            Decimal128Array::from(values)
+                .with_precision_and_scale(38, 0)
+                .map(|a| Arc::new(a) as ArrayRef)
+                .map_err(|e| format!("Failed to build decimal column: {}", e))
+        };
+        let u64s = |f: fn(&ChainlinkRound) -> u64| -> ArrayRef {
+            Arc::new(UInt64Array::from(rows.iter().map(|(_, r)| f(r)).collect::<Vec<_>>()))
+        };
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from(rows.iter().map(|(o, _)| *o).collect::<Vec<_>>())),
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|(_, r)| r.feed_id.as_str()))),
+            decimal(rows.iter().map(|(_, r)| r.round_id as i128).collect())?,
+            decimal(rows.iter().map(|(_, r)| r.answer).collect())?,
+            u64s(|r| r.updated_at),
+            decimal(rows.iter().map(|(_, r)| r.answered_in_round as i128).collect())?,
+            u64s(|r| r.started_at),
+            u64s(|r| r.ingest_arrival_time_ns),
+            u64s(|r| r.ingest_seq),
+            Arc::new(UInt8Array::from(
+                rows.iter().map(|(_, r)| r.decimals).collect::<Vec<_>>(),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|(_, r)| r.asset_symbol.as_str()),
+            )),
+            Arc::new(StringArray::from(
+                rows.iter().map(|(_, r)| r.raw_source_hash.as_deref()).collect::<Vec<_>>(),
+            )),
+        ];
+        RecordBatch::try_new(Self::schema(), columns)
+            .map_err(|e| format!("Failed to build oracle round batch: {}", e))
+    }
+
+    fn read_batch(batch: &RecordBatch, out: &mut Vec<(u64, Self)>) -> Result<(), String> {
+        let ordinal = u64_col(batch, "ordinal")?;
+        let feed_id = str_col(batch, "feed_id")?;
+        let round_id = decimal_col(batch, "round_id")?;
+        let answer = decimal_col(batch, "answer")?;
+        let updated_at = u64_col(batch, "updated_at")?;
+        let answered_in_round = decimal_col(batch, "answered_in_round")?;
+        let started_at = u64_col(batch, "started_at")?;
+        let ingest_arrival = u64_col(batch, "ingest_arrival_time_ns")?;
+        let ingest_seq = u64_col(batch, "ingest_seq")?;
+        let decimals = column(batch, "decimals")?
+            .as_primitive_opt::<UInt8Type>()
+            .ok_or_else(|| type_error("decimals"))?;
+        let asset_symbol = str_col(batch, "asset_symbol")?;
+        let raw_source_hash = str_col(batch, "raw_source_hash")?;
+
+        for row in 0..batch.num_rows() {
+            out.push((
+                ordinal.value(row),
+                ChainlinkRound {
+                    feed_id: feed_id.value(row).to_string(),
+                    round_id: round_id.value(row) as u128,
+                    answer: answer.value(row),
+                    updated_at: updated_at.value(row),
+                    answered_in_round: answered_in_round.value(row) as u128,
+                    started_at: started_at.value(row),
+                    ingest_arrival_time_ns: ingest_arrival.value(row),
+                    ingest_seq: ingest_seq.value(row),
+                    decimals: decimals.value(row),
+                    asset_symbol: asset_symbol.value(row).to_string(),
+                    raw_source_hash: opt_str(raw_source_hash, row),
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// EXPORTER
+// =============================================================================
+
+/// Export settings.
+#[derive(Debug, Clone)]
+pub struct ColumnarExportConfig {
+    /// Data contract recorded in the dataset fingerprint.
+    pub contract: HistoricalDataContract,
+    /// Readiness recorded in the dataset fingerprint.
+    pub readiness: DatasetReadiness,
+    /// Rows buffered per partition before a record batch is written.
+    pub batch_rows: usize,
+}
+
+impl ColumnarExportConfig {
+    pub fn new(contract: HistoricalDataContract, readiness: DatasetReadiness) -> Self {
+        Self {
+            contract,
+            readiness,
+            batch_rows: DEFAULT_COLUMNAR_BATCH_ROWS,
+        }
+    }
+}
+
+/// UTC day of a nanosecond timestamp.
+fn utc_date(time_ns: Nanos) -> String {
+    chrono::DateTime::from_timestamp(time_ns.div_euclid(NANOS_PER_SEC), 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "out-of-range".to_string())
+}
+
+/// Make a partition key safe to use as a path component.
+fn path_component(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+/// Open Parquet writer for one partition.
+struct PartitionWriter<R: ColumnarRow> {
+    partition: ColumnarPartition,
+    writer: ArrowWriter<File>,
+    pending: Vec<(u64, R)>,
+    hash: RollingHash,
+}
+
+impl<R: ColumnarRow> PartitionWriter<R> {
+    fn create(root: &Path, key: &str, date: &str, batch_rows: usize) -> Result<Self, String> {
+        let relative = format!(
+            "{}/{}={}/date={}/part-00000.parquet",
+            R::STREAM.dir_name(),
+            R::STREAM.key_name(),
+            path_component(key),
+            date
+        );
+        let path = root.join(&relative);
+        if path.exists() {
+            return Err(format!(
+                "Partition path collision for key '{}': {}",
+                key, relative
+            ));
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let file = File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_max_row_group_size(batch_rows)
+            .build();
+        let writer = ArrowWriter::try_new(file, R::schema(), Some(props))
+            .map_err(|e| format!("Failed to open Parquet writer: {}", e))?;
+
+        Ok(Self {
+            partition: ColumnarPartition {
+                stream: R::STREAM,
+                key: key.to_string(),
+                date: date.to_string(),
+                path: relative,
+                row_count: 0,
+                start_time_ns: Nanos::MAX,
+                end_time_ns: Nanos::MIN,
+                content_hash: String::new(),
+                time_ordered: true,
+            },
+            writer,
+            pending: Vec::new(),
+            hash: RollingHash::new(),
+        })
+    }
+
+    fn push(&mut self, ordinal: u64, row: R, record_hash: u64, batch_rows: usize) -> Result<(), String> {
+        let t = row.partition_time();
+        if self.partition.row_count > 0 && t < self.partition.end_time_ns {
+            self.partition.time_ordered = false;
+        }
+        self.partition.start_time_ns = self.partition.start_time_ns.min(t);
+        self.partition.end_time_ns = self.partition.end_time_ns.max(t);
+        self.partition.row_count += 1;
+        self.hash.update_u64(ordinal);
+        self.hash.update_u64(record_hash);
+        self.pending.push((ordinal, row));
+        if self.pending.len() >= batch_rows {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = R::to_batch(&self.pending)?;
+        self.writer
+            .write(&batch)
+            .map_err(|e| format!("Failed to write {}: {}", self.partition.path, e))?;
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn close(mut self) -> Result<ColumnarPartition, String> {
+        self.flush()?;
+        self.writer
+            .close()
+            .map_err(|e| format!("Failed to close {}: {}", self.partition.path, e))?;
+        self.partition.content_hash = self.hash.to_hex();
+        Ok(self.partition)
+    }
+}
+
+/// Writes recorded streams to a partitioned Parquet dataset.
+///
+/// Records keep the order they are added in (the export ordinal); call
+/// `finish` to close all partitions and write the manifest.
+pub struct ColumnarExporter {
+    root: PathBuf,
+    config: ColumnarExportConfig,
+    next_ordinal: u64,
+    event_partitions: BTreeMap<(String, String), PartitionWriter<TimestampedEvent>>,
+    round_partitions: BTreeMap<(String, String), PartitionWriter<ChainlinkRound>>,
+    fingerprint: FingerprintAccumulator,
+}
+
+impl ColumnarExporter {
+    /// Start a new export under `root`. Fails if a dataset already exists there.
+    pub fn create(root: impl AsRef<Path>, config: ColumnarExportConfig) -> Result<Self, String> {
+        let root = root.as_ref().to_path_buf();
+        if root.join(COLUMNAR_MANIFEST_FILE).exists() {
+            return Err(format!(
+                "Refusing to overwrite existing columnar dataset at {}",
+                root.display()
+            ));
+        }
+        if config.batch_rows == 0 {
+            return Err("batch_rows must be > 0".to_string());
+        }
+        std::fs::create_dir_all(&root)
+            .map_err(|e| format!("Failed to create {}: {}", root.display(), e))?;
+
+        Ok(Self {
+            root,
+            config,
+            next_ordinal: 0,
+            event_partitions: BTreeMap::new(),
+            round_partitions: BTreeMap::new(),
+            fingerprint: FingerprintAccumulator::default(),
+        })
+    }
+
+    /// Rows added so far.
+    pub fn rows_written(&self) -> u64 {
+        self.next_ordinal
+    }
+
+    fn push<R: ColumnarRow>(
+        root: &Path,
+        partitions: &mut BTreeMap<(String, String), PartitionWriter<R>>,
+        fingerprint: &mut FingerprintAccumulator,
+        ordinal: u64,
+        batch_rows: usize,
+        row: R,
+    ) -> Result<(), String> {
+        let stream = row.stream_name()?;
+        let key = row.partition_key()?.to_string();
+        let record_hash = row.record_hash();
+        fingerprint.add(stream, row.fingerprint_time(), &key, record_hash);
+
+        let date = utc_date(row.partition_time());
+        let slot = (key, date);
+        if !partitions.contains_key(&slot) {
+            let writer = PartitionWriter::create(root, &slot.0, &slot.1, batch_rows)?;
+            partitions.insert(slot.clone(), writer);
+        }
+        partitions
+            .get_mut(&slot)
+            .expect("partition just inserted")
+            .push(ordinal, row, record_hash, batch_rows)
+    }
+
+    /// Add one market data event (book snapshot, delta or trade print).
+    pub fn add_event(&mut self, event: TimestampedEvent) -> Result<(), String> {
+        Self::push(
+            &self.root,
+            &mut self.event_partitions,
+            &mut self.fingerprint,
+            self.next_ordinal,
+            self.config.batch_rows,
+            event,
+        )?;
+        self.next_ordinal += 1;
+        Ok(())
+    }
+
+    /// Add market data events in order; returns the number added.
+    pub fn add_events(
+        &mut self,
+        events: impl IntoIterator<Item = TimestampedEvent>,
+    ) -> Result<usize, String> {
+        let mut count = 0;
+        for event in events {
+            self.add_event(event)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Drain a feed into the export; returns the number of events added.
+    pub fn add_feed<F: MarketDataFeed + ?Sized>(&mut self, feed: &mut F) -> Result<usize, String> {
+        let mut count = 0;
+        while let Some(event) = feed.next_event() {
+            self.add_event(event)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Add one oracle round.
+    pub fn add_round(&mut self, round: ChainlinkRound) -> Result<(), String> {
+        Self::push(
+            &self.root,
+            &mut self.round_partitions,
+            &mut self.fingerprint,
+            self.next_ordinal,
+            self.config.batch_rows,
+            round,
+        )?;
+        self.next_ordinal += 1;
+        Ok(())
+    }
+
+    /// Add oracle rounds in order; returns the number added.
+    pub fn add_rounds(
+        &mut self,
+        rounds: impl IntoIterator<Item = ChainlinkRound>,
+    ) -> Result<usize, String> {
+        let mut count = 0;
+        for round in rounds {
+            self.add_round(round)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Export L2 snapshots and deltas for a token, as `L2ReplayFeed` replays them.
+    pub fn export_l2_storage(
+        &mut self,
+        storage: &L2Storage,
+        token_id: &str,
+        start_ns: Nanos,
+        end_ns: Nanos,
+    ) -> Result<usize, String> {
+        let mut feed = L2ReplayFeed::from_storage(storage, token_id, start_ns, end_ns)
+            .map_err(|e| format!("Failed to load L2 data for {}: {}", token_id, e))?;
+        self.add_feed(&mut feed)
+    }
+
+    /// Export recorded book snapshots for all tokens in an arrival-time range.
+    pub fn export_book_snapshots(
+        &mut self,
+        storage: &BookSnapshotStorage,
+        start_arrival_ns: u64,
+        end_arrival_ns: u64,
+    ) -> Result<usize, String> {
+        let snapshots = storage
+            .load_all_snapshots_in_range(start_arrival_ns, end_arrival_ns)
+            .map_err(|e| format!("Failed to load book snapshots: {}", e))?;
+        let source = StreamSource::MarketData as u8;
+        self.add_events(snapshots.iter().map(|s| s.to_timestamped_event(source)))
+    }
+
+    /// Export trade prints for all markets in a visible-time range.
+    pub fn export_trade_prints(
+        &mut self,
+        storage: &TradePrintFullStorage,
+        start_visible_ns: i64,
+        end_visible_ns: i64,
+    ) -> Result<usize, String> {
+        let prints = storage
+            .load_all_by_visible_ts(start_visible_ns, end_visible_ns)
+            .map_err(|e| format!("Failed to load trade prints: {}", e))?;
+        let source = StreamSource::MarketData as u8;
+        self.add_events(prints.iter().map(|p| p.to_timestamped_event(source)))
+    }
+
+    /// Export oracle rounds for a feed in an `updated_at` range (seconds).
+    pub fn export_oracle_rounds(
+        &mut self,
+        storage: &OracleRoundStorage,
+        feed_id: &str,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Result<usize, String> {
+        let rounds = storage
+            .load_rounds_in_range(feed_id, start_ts, end_ts)
+            .map_err(|e| format!("Failed to load oracle rounds for {}: {}", feed_id, e))?;
+        self.add_rounds(rounds)
+    }
+
+    /// Close all partitions and write the manifest.
+    pub fn finish(self) -> Result<ColumnarManifest, String> {
+        let mut partitions = Vec::with_capacity(self.event_partitions.len() + self.round_partitions.len());
+        for writer in self.event_partitions.into_values() {
+            partitions.push(writer.close()?);
+        }
+        for writer in self.round_partitions.into_values() {
+            partitions.push(writer.close()?);
+        }
+
+        let manifest = ColumnarManifest {
+            format_version: COLUMNAR_FORMAT_VERSION,
+            partitions,
+            dataset_fingerprint: DatasetFingerprint::new(
+                &self.config.contract,
+                self.config.readiness,
+                self.fingerprint.build_streams(),
+            ),
+        };
+
+        let json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        let path = self.root.join(COLUMNAR_MANIFEST_FILE);
+        std::fs::write(&path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        Ok(manifest)
+    }
+}
+
+// =============================================================================
+// READER
+// =============================================================================
+
+/// Selection of market events to load.
+#[derive(Debug, Clone)]
+pub struct ColumnarQuery {
+    /// Token ids to load (`None` = all markets).
+    pub markets: Option<Vec<String>>,
+    /// Inclusive arrival-time range.
+    pub start_ns: Nanos,
+    pub end_ns: Nanos,
+}
+
+impl ColumnarQuery {
+    /// Every market, every time.
+    pub fn all() -> Self {
+        Self {
+            markets: None,
+            start_ns: Nanos::MIN,
+            end_ns: Nanos::MAX,
+        }
+    }
+
+    /// Specific markets in `[start_ns, end_ns]`.
+    pub fn for_markets(markets: Vec<String>, start_ns: Nanos, end_ns: Nanos) -> Self {
+        Self {
+            markets: Some(markets),
+            start_ns,
+            end_ns,
+        }
+    }
+}
+
+/// An exported columnar dataset opened for reading.
+#[derive(Debug, Clone)]
+pub struct ColumnarDataset {
+    root: PathBuf,
+    manifest: ColumnarManifest,
+}
+
+impl ColumnarDataset {
+    /// Open the dataset at `root` by reading its manifest.
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, String> {
+        let root = root.as_ref().to_path_buf();
+        let path = root.join(COLUMNAR_MANIFEST_FILE);
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let manifest: ColumnarManifest = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid manifest {}: {}", path.display(), e))?;
+        if manifest.format_version != COLUMNAR_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported columnar format version {} (expected {})",
+                manifest.format_version, COLUMNAR_FORMAT_VERSION
+            ));
+        }
+        Ok(Self { root, manifest })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn manifest(&self) -> &ColumnarManifest {
+        &self.manifest
+    }
+
+    /// Fingerprint recorded at export time.
+    pub fn dataset_fingerprint(&self) -> &DatasetFingerprint {
+        &self.manifest.dataset_fingerprint
+    }
+
+    /// Token ids with exported market events.
+    pub fn markets(&self) -> Vec<String> {
+        self.keys(ColumnarStream::MarketEvents)
+    }
+
+    /// Feed ids with exported oracle rounds.
+    pub fn oracle_feeds(&self) -> Vec<String> {
+        self.keys(ColumnarStream::OracleRounds)
+    }
+
+    fn keys(&self, stream: ColumnarStream) -> Vec<String> {
+        self.manifest
+            .partitions
+            .iter()
+            .filter(|p| p.stream == stream)
+            .map(|p| p.key.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Read one partition and check it against the manifest.
+    fn read_partition<R: ColumnarRow>(&self, partition: &ColumnarPartition) -> Result<Vec<(u64, R)>, String> {
+        let path = self.root.join(&partition.path);
+        let file = File::open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .and_then(|b| b.build())
+            .map_err(|e| format!("Failed to read {}: {}", partition.path, e))?;
+
+        let mut rows = Vec::with_capacity(partition.row_count as usize);
+        for batch in reader {
+            let batch = batch.map_err(|e| format!("Failed to read {}: {}", partition.path, e))?;
+            R::read_batch(&batch, &mut rows)
+                .map_err(|e| format!("{}: {}", partition.path, e))?;
+        }
+
+        let mut hash = RollingHash::new();
+        for (ordinal, row) in &rows {
+            hash.update_u64(*ordinal);
+            hash.update_u64(row.record_hash());
+        }
+        if rows.len() as u64 != partition.row_count || hash.to_hex() != partition.content_hash {
+            return Err(format!(
+                "Partition {} does not match manifest ({} rows, hash {}; expected {} rows, hash {})",
+                partition.path,
+                rows.len(),
+                hash.to_hex(),
+                partition.row_count,
+                partition.content_hash
+            ));
+        }
+        Ok(rows)
+    }
+
+    /// Load market events matching `query`, ordered by `(time, ordinal)`.
+    ///
+    /// Fails if a requested market has no events in range.
+    pub fn load_events(&self, query: &ColumnarQuery) -> Result<Vec<TimestampedEvent>, String> {
+        let wanted: Option<BTreeSet<&str>> = query
+            .markets
+            .as_ref()
+            .map(|m| m.iter().map(String::as_str).collect());
+
+        let mut rows: Vec<(u64, TimestampedEvent)> = Vec::new();
+        for partition in &self.manifest.partitions {
+            if partition.stream != ColumnarStream::MarketEvents
+                || !partition.overlaps(query.start_ns, query.end_ns)
+                || wanted.as_ref().is_some_and(|w| !w.contains(partition.key.as_str()))
+            {
+                continue;
+            }
+            let loaded = self.read_partition::<TimestampedEvent>(partition)?;
+            rows.extend(
+                loaded
+                    .into_iter()
+                    .filter(|(_, e)| e.time >= query.start_ns && e.time <= query.end_ns),
+            );
+        }
+
+        if let Some(markets) = &query.markets {
+            for market in markets {
+                let found = rows
+                    .iter()
+                    .any(|(_, e)| e.event.token_id() == Some(market.as_str()));
+                if !found {
+                    return Err(format!("No events found for market '{}' in time range", market));
+                }
+            }
+        }
+
+        rows.sort_by_key(|(ordinal, e)| (e.time, *ordinal));
+        Ok(rows.into_iter().map(|(_, e)| e).collect())
+    }
+
+    /// Load oracle rounds for a feed with `updated_at` in `[start_ts, end_ts]`
+    /// (seconds), ordered like `OracleRoundStorage::load_rounds_in_range`.
+    pub fn load_oracle_rounds(
+        &self,
+        feed_id: &str,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Result<Vec<ChainlinkRound>, String> {
+        let start_ns = (start_ts as i64).saturating_mul(NANOS_PER_SEC);
+        let end_ns = (end_ts as i64).saturating_mul(NANOS_PER_SEC);
+
+        let mut rounds = Vec::new();
+        for partition in &self.manifest.partitions {
+            if partition.stream != ColumnarStream::OracleRounds
+                || partition.key != feed_id
+                || !partition.overlaps(start_ns, end_ns)
+            {
+                continue;
+            }
+            rounds.extend(
+                self.read_partition::<ChainlinkRound>(partition)?
+                    .into_iter()
+                    .map(|(_, r)| r)
+                    .filter(|r| r.updated_at >= start_ts && r.updated_at <= end_ts),
+            );
+        }
+        rounds.sort_by_key(|r| (r.updated_at, r.round_id));
+        Ok(rounds)
+    }
+
+    /// Recompute the dataset fingerprint from the files and compare it with
+    /// the manifest.
+    pub fn verify(&self) -> Result<(), String> {
+        // (ordinal, stream, fingerprint time, key, record hash)
+        let mut records: Vec<(u64, &'static str, Nanos, String, u64)> = Vec::new();
+        for partition in &self.manifest.partitions {
+            match partition.stream {
+                ColumnarStream::MarketEvents => {
+                    for (ordinal, e) in self.read_partition::<TimestampedEvent>(partition)? {
+                        records.push((ordinal, e.stream_name()?, e.fingerprint_time(), partition.key.clone(), e.record_hash()));
+                    }
+                }
+                ColumnarStream::OracleRounds => {
+                    for (ordinal, r) in self.read_partition::<ChainlinkRound>(partition)? {
+                        records.push((ordinal, r.stream_name()?, r.fingerprint_time(), partition.key.clone(), r.record_hash()));
+                    }
+                }
+            }
+        }
+        records.sort_by_key(|r| r.0);
+
+        let mut acc = FingerprintAccumulator::default();
+        for (_, stream, time, key, hash) in &records {
+            acc.add(stream, *time, key, *hash);
+        }
+        let streams = acc.build_streams();
+        if streams != self.manifest.dataset_fingerprint.streams {
+            return Err(format!(
+                "Dataset fingerprint mismatch at {}: files no longer match the exported streams",
+                self.root.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// REPLAY FEED
+// =============================================================================
+
+/// Replay rows of one partition, read lazily.
+struct PartitionCursor {
+    partition: ColumnarPartition,
+    path: PathBuf,
+    /// Next row group to read; `None` once the file is exhausted.
+    next_row_group: Option<usize>,
+    buffer: VecDeque<(u64, TimestampedEvent)>,
+    hash: RollingHash,
+    rows_read: u64,
+}
+
+impl PartitionCursor {
+    fn new(root: &Path, partition: &ColumnarPartition) -> Self {
+        Self {
+            path: root.join(&partition.path),
+            partition: partition.clone(),
+            next_row_group: Some(0),
+            buffer: VecDeque::new(),
+            hash: RollingHash::new(),
+            rows_read: 0,
+        }
+    }
+
+    fn head_key(&self) -> Option<(Nanos, u64)> {
+        self.buffer.front().map(|(ordinal, e)| (e.time, *ordinal))
+    }
+
+    /// Reader over one row group (or the whole file), plus the file's row group count.
+    fn open_reader(&self, row_group: Option<usize>) -> Result<(ParquetRecordBatchReader, usize), String> {
+        let file = File::open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| format!("Failed to read {}: {}", self.partition.path, e))?;
+        let count = builder.metadata().num_row_groups();
+        let builder = match row_group {
+            Some(group) => builder.with_row_groups((group..count.min(group + 1)).collect()),
+            None => builder,
+        };
+        let reader = builder
+            .with_batch_size(DEFAULT_COLUMNAR_BATCH_ROWS)
+            .build()
+            .map_err(|e| format!("Failed to read {}: {}", self.partition.path, e))?;
+        Ok((reader, count))
+    }
+
+    fn read(&mut self, reader: ParquetRecordBatchReader) -> Result<Vec<(u64, TimestampedEvent)>, String> {
+        let mut rows = Vec::new();
+        for batch in reader {
+            let batch = batch.map_err(|e| format!("Failed to read {}: {}", self.partition.path, e))?;
+            TimestampedEvent::read_batch(&batch, &mut rows)
+                .map_err(|e| format!("{}: {}", self.partition.path, e))?;
+        }
+        for (ordinal, row) in &rows {
+            self.hash.update_u64(*ordinal);
+            self.hash.update_u64(row.record_hash());
+        }
+        self.rows_read += rows.len() as u64;
+        Ok(rows)
+    }
+
+    /// Check the rows read against the manifest once the file is exhausted.
+    fn verify(&self) -> Result<(), String> {
+        if self.rows_read != self.partition.row_count || self.hash.to_hex() != self.partition.content_hash {
+            return Err(format!(
+                "Partition {} does not match manifest ({} rows, hash {}; expected {} rows, hash {})",
+                self.partition.path,
+                self.rows_read,
+                self.hash.to_hex(),
+                self.partition.row_count,
+                self.partition.content_hash
+            ));
+        }
+        Ok(())
+    }
+
+    /// Refill the buffer with the rows of the next row group in
+    /// `[start_ns, end_ns]`. Partitions that are not time ordered are read
+    /// whole and sorted, since their rows cannot be merged as they come.
+    fn fill(&mut self, start_ns: Nanos, end_ns: Nanos) -> Result<(), String> {
+        while self.buffer.is_empty() {
+            let Some(group) = self.next_row_group else {
+                return Ok(());
+            };
+            let ordered = self.partition.time_ordered;
+            let (reader, row_groups) = self.open_reader(ordered.then_some(group))?;
+            let mut rows = self.read(reader)?;
+            let next = group + 1;
+            self.next_row_group = (ordered && next < row_groups).then_some(next);
+            if self.next_row_group.is_none() {
+                self.verify()?;
+            }
+            if !ordered {
+                rows.sort_by_key(|(ordinal, e)| (e.time, *ordinal));
+            }
+            self.buffer.extend(
+                rows.into_iter()
+                    .filter(|(_, e)| e.time >= start_ns && e.time <= end_ns),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// `MarketDataFeed` that streams market events from a columnar dataset.
+///
+/// Partitions are opened when replay reaches their first timestamp and read a
+/// row group at a time, then merged by `(time, ordinal)`, so memory is bounded
+/// by the partitions live at once rather than by the query range. Partitions
+/// exported out of time order are read whole when they open. Each partition is
+/// checked against the manifest once it has been read to the end; a mismatch or
+/// read failure ends the feed (see `error`).
+pub struct ColumnarReplayFeed {
+    root: PathBuf,
+    query: ColumnarQuery,
+    /// Selected partitions by start time; `pending` indexes the next to open.
+    partitions: Vec<ColumnarPartition>,
+    pending: usize,
+    cursors: Vec<PartitionCursor>,
+    heap: BinaryHeap<Reverse<(Nanos, u64, usize)>>,
+    error: Option<String>,
+    peak_buffered: usize,
+    name: String,
+}
+
+impl ColumnarReplayFeed {
+    /// Open a replay of the events selected by `query`.
+    ///
+    /// Fails if a requested market has no partition in range, or if the first
+    /// row group cannot be read.
+    pub fn open(dataset: &ColumnarDataset, query: &ColumnarQuery) -> Result<Self, String> {
+        let wanted: Option<BTreeSet<&str>> = query
+            .markets
+            .as_ref()
+            .map(|m| m.iter().map(String::as_str).collect());
+        let mut partitions: Vec<ColumnarPartition> = dataset
+            .manifest()
+            .partitions
+            .iter()
+            .filter(|p| {
+                p.stream == ColumnarStream::MarketEvents
+                    && p.overlaps(query.start_ns, query.end_ns)
+                    && wanted.as_ref().map_or(true, |w| w.contains(p.key.as_str()))
+            })
+            .cloned()
+            .collect();
+        if let Some(markets) = &query.markets {
+            for market in markets {
+                if !partitions.iter().any(|p| &p.key == market) {
+                    return Err(format!("No events found for market '{}' in time range", market));
+                }
+            }
+        }
+        partitions.sort_by(|a, b| a.start_time_ns.cmp(&b.start_time_ns).then_with(|| a.path.cmp(&b.path)));
+
+        let mut feed = Self {
+            root: dataset.root().to_path_buf(),
+            query: query.clone(),
+            partitions,
+            pending: 0,
+            cursors: Vec::new(),
+            heap: BinaryHeap::new(),
+            error: None,
+            peak_buffered: 0,
+            name: format!("ColumnarReplayFeed({})", dataset.root().display()),
+        };
+        feed.settle();
+        match feed.error.take() {
+            Some(e) => Err(e),
+            None => Ok(feed),
+        }
+    }
+
+    /// Rows currently held in memory across open partitions.
+    pub fn buffered_rows(&self) -> usize {
+        self.cursors.iter().map(|c| c.buffer.len()).sum()
+    }
+
+    /// Highest `buffered_rows` seen since the last reset.
+    pub fn peak_buffered(&self) -> usize {
+        self.peak_buffered
+    }
+
+    /// Partitions opened so far.
+    pub fn partitions_opened(&self) -> usize {
+        self.cursors.len()
+    }
+
+    fn fail(&mut self, error: String) {
+        tracing::warn!("{}: {}", self.name, error);
+        self.error = Some(error);
+        self.heap.clear();
+    }
+
+    /// Load the next rows of cursor `index` and enqueue its new head.
+    fn advance(&mut self, index: usize) {
+        let cursor = &mut self.cursors[index];
+        if let Err(e) = cursor.fill(self.query.start_ns, self.query.end_ns) {
+            return self.fail(e);
+        }
+        if let Some((time, ordinal)) = cursor.head_key() {
+            self.heap.push(Reverse((time, ordinal, index)));
+        }
+        self.peak_buffered = self.peak_buffered.max(self.buffered_rows());
+    }
+
+    /// Open every pending partition that could hold the next event, so the
+    /// heap head is the true next event.
+    fn settle(&mut self) {
+        while self.error.is_none() && self.pending < self.partitions.len() {
+            let next_start = self.partitions[self.pending].start_time_ns;
+            let head = self.heap.peek().map(|Reverse((time, _, _))| *time);
+            if head.is_some_and(|t| t < next_start) {
+                break;
+            }
+            let cursor = PartitionCursor::new(&self.root, &self.partitions[self.pending]);
+            self.pending += 1;
+            self.cursors.push(cursor);
+            self.advance(self.cursors.len() - 1);
+        }
+    }
+}
+
+impl MarketDataFeed for ColumnarReplayFeed {
+    fn next_event(&mut self) -> Option<TimestampedEvent> {
+        let Reverse((_, _, index)) = self.heap.pop()?;
+        let (_, event) = self.cursors[index].buffer.pop_front()?;
+        if self.cursors[index].buffer.is_empty() {
+            self.advance(index);
+        } else if let Some((time, ordinal)) = self.cursors[index].head_key() {
+            self.heap.push(Reverse((time, ordinal, index)));
+        }
+        self.settle();
+        Some(event)
+    }
+
+    fn peek_time(&self) -> Option<Nanos> {
+        self.heap.peek().map(|Reverse((time, _, _))| *time)
+    }
+
+    fn reset(&mut self) {
+        self.pending = 0;
+        self.cursors.clear();
+        self.heap.clear();
+        self.error = None;
+        self.peak_buffered = 0;
+        self.settle();
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::book_recorder::{PriceLevel, RecordedBookSnapshot};
+    use crate::backtest_v2::feed::VecFeed;
+    use crate::backtest_v2::trade_print::PolymarketTradePrint;
+
+    const DAY_NS: Nanos = 86_400 * NANOS_PER_SEC;
+    const T0: Nanos = 1_705_320_000 * NANOS_PER_SEC;
+
+    fn config() -> ColumnarExportConfig {
+        ColumnarExportConfig::new(
+            HistoricalDataContract::polymarket_15m_updown_with_recorded_arrival(),
+            DatasetReadiness::MakerViable,
+        )
+    }
+
+    fn snapshot(token: &str, time: Nanos, seq: u64) -> TimestampedEvent {
+        TimestampedEvent {
+            time,
+            source_time: time - 1_000,
+            seq,
+            source: StreamSource::MarketData as u8,
+            event: Event::L2BookSnapshot {
+                token_id: token.to_string(),
+                bids: vec![Level { price: 0.49, size: 100.0, order_count: Some(3) }],
+                asks: vec![Level::new(0.51, 80.0), Level::new(0.52, 120.0)],
+                exchange_seq: seq,
+            },
+        }
+    }
+
+    fn sample_events() -> Vec<TimestampedEvent> {
+        let source = StreamSource::MarketData as u8;
+        vec![
+            snapshot("TOKEN_A", T0, 1),
+            TimestampedEvent {
+                time: T0 + 10,
+                source_time: T0 + 5,
+                seq: 2,
+                source,
+                event: Event::L2BookDelta {
+                    token_id: "TOKEN_A".to_string(),
+                    side: Side::Sell,
+                    price: 0.51,
+                    new_size: 0.0,
+                    seq_hash: Some("abc".to_string()),
+                },
+            },
+            TimestampedEvent {
+                time: T0 + 10,
+                source_time: T0 + 10,
+                seq: 1,
+                source,
+                event: Event::TradePrint {
+                    token_id: "TOKEN_B".to_string(),
+                    price: 0.5,
+                    size: 12.5,
+                    aggressor_side: Side::Buy,
+                    trade_id: None,
+                },
+            },
+            TimestampedEvent {
+                time: T0 + DAY_NS,
+                source_time: T0 + DAY_NS,
+                seq: 3,
+                source,
+                event: Event::L2Delta {
+                    token_id: "TOKEN_A".to_string(),
+                    bid_updates: vec![Level::new(0.48, 10.0)],
+                    ask_updates: vec![],
+                    exchange_seq: 3,
+                },
+            },
+            snapshot("TOKEN_B", T0 + 5, 7),
+        ]
+    }
+
+    fn assert_same_events(actual: &[TimestampedEvent], expected: &[TimestampedEvent]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert_eq!(
+                (a.time, a.source_time, a.seq, a.source),
+                (e.time, e.source_time, e.seq, e.source)
+            );
+            assert_eq!(a.event, e.event);
+        }
+    }
+
+    fn round(round_id: u128, updated_at: u64) -> ChainlinkRound {
+        ChainlinkRound {
+            feed_id: "btc_usd".to_string(),
+            round_id: (1u128 << 64) + round_id,
+            answer: 4_200_000_000_000 + round_id as i128,
+            updated_at,
+            answered_in_round: (1u128 << 64) + round_id,
+            started_at: updated_at - 1,
+            ingest_arrival_time_ns: updated_at * 1_000_000_000 + 250,
+            ingest_seq: round_id as u64,
+            decimals: 8,
+            asset_symbol: "BTC".to_string(),
+            raw_source_hash: (round_id != 1).then(|| format!("0x{:x}", round_id)),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_partitions_by_market_and_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = sample_events();
+
+        let mut exporter = ColumnarExporter::create(dir.path(), config()).unwrap();
+        exporter.add_events(events.clone()).unwrap();
+        let manifest = exporter.finish().unwrap();
+
+        // TOKEN_A spans two days, TOKEN_B one
+        let keys: Vec<_> = manifest
+            .partitions
+            .iter()
+            .map(|p| (p.key.as_str(), p.date.as_str(), p.row_count))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("TOKEN_A", "2024-01-15", 2),
+                ("TOKEN_A", "2024-01-16", 1),
+                ("TOKEN_B", "2024-01-15", 2),
+            ]
+        );
+        assert_eq!(manifest.total_rows(), 5);
+
+        // Replay matches VecFeed over the same events exactly
+        let dataset = ColumnarDataset::open(dir.path()).unwrap();
+        let mut feed = ColumnarReplayFeed::open(&dataset, &ColumnarQuery::all()).unwrap();
+        let mut reference = VecFeed::new("reference", events);
+        let mut replayed = Vec::new();
+        let mut expected = Vec::new();
+        while let Some(e) = feed.next_event() {
+            replayed.push(e);
+            expected.push(reference.next_event().unwrap());
+        }
+        assert!(reference.next_event().is_none());
+        assert_same_events(&replayed, &expected);
+
+        // TOKEN_B was exported out of time order and is read whole
+        assert!(!manifest.partitions[2].time_ordered);
+        assert!(manifest.partitions[0].time_ordered);
+
+        feed.reset();
+        assert_eq!(feed.peek_time(), Some(T0));
+        assert_eq!(std::iter::from_fn(|| feed.next_event()).count(), 5);
+        assert!(feed.error().is_none());
+    }
+
+    #[test]
+    fn test_replay_streams_row_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut events: Vec<_> = (0..100)
+            .flat_map(|i| {
+                let t = T0 + i * NANOS_PER_SEC;
+                [snapshot("TOKEN_A", t, i as u64), snapshot("TOKEN_B", t + 1, i as u64)]
+            })
+            .collect();
+        events.push(snapshot("TOKEN_C", T0 + DAY_NS, 0));
+
+        let mut exporter = ColumnarExporter::create(dir.path(), ColumnarExportConfig { batch_rows: 8, ..config() }).unwrap();
+        exporter.add_events(events.clone()).unwrap();
+        exporter.finish().unwrap();
+
+        let dataset = ColumnarDataset::open(dir.path()).unwrap();
+        let mut feed = ColumnarReplayFeed::open(&dataset, &ColumnarQuery::all()).unwrap();
+        // Partitions open only when replay reaches their first timestamp
+        assert_eq!(feed.partitions_opened(), 1);
+        let first = feed.next_event().unwrap();
+        assert_eq!(feed.partitions_opened(), 2);
+        let replayed: Vec<_> = std::iter::once(first)
+            .chain(std::iter::from_fn(|| feed.next_event()))
+            .collect();
+        assert_same_events(&replayed, &events);
+        assert!(feed.error().is_none());
+        assert_eq!(feed.partitions_opened(), 3);
+        // One row group per open partition is resident at a time
+        assert!(feed.peak_buffered() <= 16, "peak {}", feed.peak_buffered());
+
+        let late = ColumnarQuery::for_markets(vec!["TOKEN_A".to_string()], T0 + DAY_NS, Nanos::MAX);
+        assert!(ColumnarReplayFeed::open(&dataset, &late).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_preserved_and_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = sample_events();
+        let rounds = vec![round(1, 1_705_320_000), round(2, 1_705_320_060)];
+
+        let mut exporter = ColumnarExporter::create(dir.path(), config()).unwrap();
+        exporter.add_events(events.clone()).unwrap();
+        exporter.add_rounds(rounds.clone()).unwrap();
+        let manifest = exporter.finish().unwrap();
+
+        let cfg = config();
+        let expected =
+            columnar_dataset_fingerprint(&cfg.contract, cfg.readiness, &events, &rounds).unwrap();
+        assert_eq!(manifest.dataset_fingerprint, expected);
+        let names: Vec<_> = expected.streams.iter().map(|s| s.stream_name.as_str()).collect();
+        assert_eq!(names, vec!["oracle_rounds", "orderbook_deltas", "orderbook_snapshots", "trades"]);
+
+        let dataset = ColumnarDataset::open(dir.path()).unwrap();
+        assert_eq!(dataset.dataset_fingerprint(), &expected);
+        dataset.verify().unwrap();
+
+        // Swapping partition contents is caught on load and by verify
+        let a = dir.path().join(&manifest.partitions[0].path);
+        let b = dir.path().join(&manifest.partitions[2].path);
+        std::fs::copy(&b, &a).unwrap();
+        assert!(dataset.verify().is_err());
+        let query = ColumnarQuery::for_markets(vec!["TOKEN_A".to_string()], 0, Nanos::MAX);
+        let err = dataset.load_events(&query).unwrap_err();
+        assert!(err.contains("does not match manifest"), "{}", err);
+        // Single row group: the replay fails as soon as it reads the partition
+        let err = ColumnarReplayFeed::open(&dataset, &query).err().expect("corrupt partition");
+        assert!(err.contains("does not match manifest"), "{}", err);
+    }
+
+    #[test]
+    fn test_export_from_sqlite_storages() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let books = BookSnapshotStorage::open_memory().unwrap();
+        let snapshot = RecordedBookSnapshot::from_ws_message(
+            "TOKEN_A".to_string(),
+            vec![PriceLevel { price: 0.50, size: 100.0 }],
+            vec![PriceLevel { price: 0.51, size: 150.0 }],
+            Some(42),
+            Some(T0 as u64 - 1_000_000),
+            T0 as u64,
+            9,
+        );
+        books.store_snapshot(&snapshot).unwrap();
+
+        let trades = TradePrintFullStorage::open_memory().unwrap();
+        let mut print =
+            PolymarketTradePrint::new("MKT".to_string(), "TOKEN_A".to_string(), Side::Sell, 0.50, 25.0, T0 + 500);
+        print.trade_id = print.compute_hash_trade_id();
+        assert!(trades.store(&mut print).unwrap());
+
+        let oracle = OracleRoundStorage::open_memory().unwrap();
+        oracle.store_rounds(&[round(1, 1_705_320_000), round(2, 1_705_320_060)]).unwrap();
+
+        let mut exporter = ColumnarExporter::create(dir.path(), config()).unwrap();
+        assert_eq!(exporter.export_book_snapshots(&books, 0, u64::MAX / 2).unwrap(), 1);
+        assert_eq!(exporter.export_trade_prints(&trades, 0, i64::MAX).unwrap(), 1);
+        assert_eq!(exporter.export_oracle_rounds(&oracle, "btc_usd", 0, u64::MAX / 2).unwrap(), 2);
+        exporter.finish().unwrap();
+
+        let dataset = ColumnarDataset::open(dir.path()).unwrap();
+        let source = StreamSource::MarketData as u8;
+        let stored_print = trades.load_all_by_visible_ts(0, i64::MAX).unwrap().remove(0);
+        let events = dataset.load_events(&ColumnarQuery::all()).unwrap();
+        assert_same_events(
+            &events,
+            &[snapshot.to_timestamped_event(source), stored_print.to_timestamped_event(source)],
+        );
+        assert_eq!(events[0].time, T0);
+        assert_eq!(events[0].seq, 9);
+
+        assert_eq!(dataset.oracle_feeds(), vec!["btc_usd".to_string()]);
+        assert_eq!(
+            dataset.load_oracle_rounds("btc_usd", 0, u64::MAX / 2).unwrap(),
+            oracle.load_rounds_in_range("btc_usd", 0, u64::MAX / 2).unwrap()
+        );
+        assert_eq!(dataset.load_oracle_rounds("btc_usd", 1_705_320_030, 1_705_320_100).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_query_filters_markets_and_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut exporter = ColumnarExporter::create(dir.path(), config()).unwrap();
+        exporter.add_events(sample_events()).unwrap();
+        exporter.finish().unwrap();
+
+        let dataset = ColumnarDataset::open(dir.path()).unwrap();
+        assert_eq!(dataset.markets(), vec!["TOKEN_A".to_string(), "TOKEN_B".to_string()]);
+
+        let query = ColumnarQuery::for_markets(vec!["TOKEN_A".to_string()], T0, T0 + 10);
+        let events = dataset.load_events(&query).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.event.token_id() == Some("TOKEN_A")));
+
+        let missing = ColumnarQuery::for_markets(vec!["TOKEN_C".to_string()], 0, Nanos::MAX);
+        assert!(dataset.load_events(&missing).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_market_events_and_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut exporter = ColumnarExporter::create(dir.path(), config()).unwrap();
+        let timer = TimestampedEvent::new(T0, StreamSource::Timer as u8, Event::Timer { timer_id: 1, payload: None });
+        assert!(exporter.add_event(timer).is_err());
+        exporter.add_event(snapshot("TOKEN_A", T0, 1)).unwrap();
+        exporter.finish().unwrap();
+
+        assert!(ColumnarExporter::create(dir.path(), config()).is_err());
+    }
+}
@@ -791,7 +791,7 @@ impl DataQualitySummary {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DataContractValidator {
     contract: HistoricalDataContract,
     summary: DataQualitySummary,
@@ -2075,29 +2075,13 @@ impl ReplayValidation {
                             Ok(rounds) => {
                                 oracle_count += rounds.len() as u64;
                                 for round in rounds {
-                                    // Convert ChainlinkRound to Event
-                                    // Note: We don't have an Event::OracleRound variant
-                                    // so we'll store as a Signal event for now (or skip)
-                                    // For production-grade, we'd add Event::OracleRound
-                                    // For now, we just count and hash
+                                    // No Event::OracleRound variant; rounds travel as signals
                                     all_events.push(LoadedEvent {
                                         arrival_time_ns: round.ingest_arrival_time_ns,
                                         ingest_seq: round.ingest_seq,
                                         priority: 0, // System/Oracle priority (highest)
                                         source: SOURCE_ORACLE,
-                                        event: Event::Signal {
-                                            signal_id: format!("oracle_{}_{}", round.feed_id, round.round_id),
-                                            signal_type: "oracle_round".to_string(),
-                                            market_slug: round.asset_symbol.clone(),
-                                            confidence: 1.0,
-                                            details_json: serde_json::to_string(&serde_json::json!({
-                                                "feed_id": round.feed_id,
-                                                "round_id": round.round_id,
-                                                "answer": round.answer,
-                                                "updated_at": round.updated_at,
-                                                "decimals": round.decimals,
-                                            })).unwrap_or_default(),
-                                        },
+                                        event: round.to_signal_event(),
                                         stream_name: "oracle_rounds".to_string(),
                                         token_id: None,
                                         market_id: Some(round.asset_symbol),
@@ -364,6 +364,7 @@ pub enum EquityObservationTrigger {
 /// - After each fill (and fee) is posted to the ledger
 /// - After each settlement is posted to the ledger
 /// - At end-of-run finalization
+#[derive(Serialize, Deserialize)]
 pub struct EquityRecorder {
     /// The accumulated equity curve.
     curve: EquityCurve,
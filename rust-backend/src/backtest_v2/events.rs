@@ -70,6 +70,18 @@ pub enum TimeInForce {
     Gtt { expiry: Nanos },
 }
 
+/// How a batch of orders submitted together is admitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchMode {
+    /// Every order is accepted or the whole batch is rejected. Exact in
+    /// simulation; the live venue settles each order on its own, so live
+    /// senders only approximate it by cancelling the accepted orders after a
+    /// refusal, and fills already received are not undone.
+    AllOrNone,
+    /// Each order stands on its own; valid orders go through
+    Partial,
+}
+
 /// A single price level in the order book.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Level {
@@ -244,6 +256,23 @@ pub enum Event {
         cancelled_qty: Size,
     },
 
+    /// Replace (amend) acknowledgment: the order now rests at the new price/size.
+    ReplaceAck {
+        order_id: OrderId,
+        /// New limit price
+        price: Price,
+        /// Open quantity after the amend (before any crossing fills)
+        leaves_qty: Size,
+        /// Whether the order kept its queue priority (size-down at the same price)
+        kept_priority: bool,
+    },
+
+    /// Replace rejected; the original order is unchanged (or already done).
+    ReplaceReject {
+        order_id: OrderId,
+        reason: RejectReason,
+    },
+
     /// Market status change (halt/resume/close).
     MarketStatusChange {
         token_id: TokenId,
@@ -284,9 +313,9 @@ impl Event {
             Event::L2BookSnapshot { .. } => EventPriority::BookSnapshot,
             Event::L2Delta { .. } | Event::L2BookDelta { .. } => EventPriority::BookDelta,
             Event::TradePrint { .. } => EventPriority::TradePrint,
-            Event::OrderAck { .. } => EventPriority::OrderAck,
+            Event::OrderAck { .. } | Event::ReplaceAck { .. } => EventPriority::OrderAck,
             Event::Fill { .. } => EventPriority::Fill,
-            Event::OrderReject { .. } => EventPriority::OrderReject,
+            Event::OrderReject { .. } | Event::ReplaceReject { .. } => EventPriority::OrderReject,
             Event::CancelAck { .. } => EventPriority::CancelAck,
             Event::Signal { .. } | Event::Timer { .. } => EventPriority::Signal,
         }
@@ -311,14 +340,16 @@ impl Event {
             Event::OrderAck { order_id, .. }
             | Event::OrderReject { order_id, .. }
             | Event::Fill { order_id, .. }
-            | Event::CancelAck { order_id, .. } => Some(*order_id),
+            | Event::CancelAck { order_id, .. }
+            | Event::ReplaceAck { order_id, .. }
+            | Event::ReplaceReject { order_id, .. } => Some(*order_id),
             _ => None,
         }
     }
 }
 
 /// Timestamped event with sequence number for deterministic ordering.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimestampedEvent {
     /// Arrival timestamp in nanoseconds (when the backtest system sees/processes the event)
     pub time: Nanos,
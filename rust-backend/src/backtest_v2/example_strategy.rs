@@ -23,6 +23,7 @@ use crate::backtest_v2::strategy::{
     BookSnapshot, CancelAck, FillNotification, OpenOrder, OrderAck, OrderReject, Position,
     Strategy, StrategyContext, StrategyOrder, StrategyParams, TimerEvent, TradePrint,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Simple market-making strategy.
@@ -54,7 +55,7 @@ pub struct MarketMakerStrategy {
     pub stats: MarketMakerStats,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MarketMakerStats {
     pub quotes_sent: u64,
     pub fills_received: u64,
@@ -63,6 +64,16 @@ pub struct MarketMakerStats {
     pub rejects: u64,
 }
 
+/// Mutable state persisted through `get_state` / `restore_state`.
+#[derive(Serialize, Deserialize)]
+struct MarketMakerState {
+    bid_order_id: Option<u64>,
+    ask_order_id: Option<u64>,
+    last_mid: Option<f64>,
+    order_counter: u64,
+    stats: MarketMakerStats,
+}
+
 impl MarketMakerStrategy {
     pub fn new(params: &StrategyParams) -> Self {
         Self {
@@ -263,6 +274,27 @@ impl Strategy for MarketMakerStrategy {
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn get_state(&self) -> Option<String> {
+        let state = MarketMakerState {
+            bid_order_id: self.bid_order_id,
+            ask_order_id: self.ask_order_id,
+            last_mid: self.last_mid,
+            order_counter: self.order_counter,
+            stats: self.stats.clone(),
+        };
+        serde_json::to_string(&state).ok()
+    }
+
+    fn restore_state(&mut self, state: &str) -> Result<(), String> {
+        let state: MarketMakerState = serde_json::from_str(state).map_err(|e| e.to_string())?;
+        self.bid_order_id = state.bid_order_id;
+        self.ask_order_id = state.ask_order_id;
+        self.last_mid = state.last_mid;
+        self.order_counter = state.order_counter;
+        self.stats = state.stats;
+        Ok(())
+    }
 }
 
 /// Simple momentum strategy example.
@@ -25,6 +25,14 @@ pub trait MarketDataFeed: Send {
     fn name(&self) -> &str {
         "unknown"
     }
+
+    /// Error that ended the feed early, if any.
+    ///
+    /// Lazily loading feeds report storage failures here rather than
+    /// silently truncating the stream.
+    fn error(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// A feed backed by an in-memory vector of events.
@@ -9,7 +9,7 @@
 //!
 //! ```text
 //! RunFingerprint = H(
-//!   "RUNFP_V1" ||
+//!   "RUNFP_V3" ||
 //!   CodeFingerprint ||
 //!   ConfigFingerprint ||
 //!   DatasetFingerprint ||
@@ -45,7 +45,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
 /// Fingerprint version string - increment when format changes.
-pub const FINGERPRINT_VERSION: &str = "RUNFP_V2";
+pub const FINGERPRINT_VERSION: &str = "RUNFP_V3";
 
 /// Scale factor for converting prices to fixed-point integers.
 const PRICE_SCALE: f64 = 1e8;
@@ -360,6 +360,10 @@ pub struct ConfigFingerprint {
     pub latency_model: String,
     /// Order latency (ns) if fixed.
     pub order_latency_ns: Option<Nanos>,
+    /// Hash of the full `LatencyConfig` (every leg and regime), so changes the
+    /// order-send summary above cannot express still move the config hash.
+    #[serde(default)]
+    pub latency_config_hash: u64,
     /// OMS parity mode.
     pub oms_parity_mode: String,
     /// Maker fill model.
@@ -372,6 +376,15 @@ pub struct ConfigFingerprint {
     pub fee_rate_bps: Option<i64>,
     /// Strategy parameters hash.
     pub strategy_params_hash: u64,
+    /// Portfolio risk limits hash (None = risk gate disabled).
+    #[serde(default)]
+    pub risk_limits_hash: Option<u64>,
+    /// Market impact model hash (None = recorded depth not swept).
+    #[serde(default)]
+    pub market_impact_hash: Option<u64>,
+    /// Monte Carlo robustness config hash (its verdict feeds Gate D).
+    #[serde(default)]
+    pub monte_carlo_hash: u64,
     /// Arrival policy description.
     pub arrival_policy: String,
     /// Strict accounting enabled.
@@ -381,6 +394,10 @@ pub struct ConfigFingerprint {
     /// Allow non-production override.
     /// When true, non-production settings were explicitly permitted.
     pub allow_non_production: bool,
+    /// Parameter provenance (`SampleScope`), so an in-sample sweep point never
+    /// shares a run ID with an otherwise identical standalone run.
+    #[serde(default)]
+    pub sample_scope: String,
     /// Computed hash of all config.
     pub hash: u64,
 }
@@ -466,6 +483,7 @@ impl ConfigFingerprint {
             oracle_config_hash,
             latency_model,
             order_latency_ns,
+            latency_config_hash: config.latency.fingerprint_hash(),
             oms_parity_mode: format!("{:?}", config.oms_parity_mode),
             maker_fill_model: format!("{:?}", config.maker_fill_model),
             integrity_policy: format!("{:?}", config.integrity_policy),
@@ -475,15 +493,31 @@ impl ConfigFingerprint {
                 .unwrap_or_else(|| "Hard".to_string()),
             fee_rate_bps: Some((config.matching.fees.taker_fee_rate * 10000.0) as i64),
             strategy_params_hash,
+            risk_limits_hash: config.risk_limits.as_ref().map(|l| l.fingerprint_hash()),
+            market_impact_hash: config.market_impact.as_ref().map(|m| m.fingerprint_hash()),
+            monte_carlo_hash: config.monte_carlo.fingerprint_hash(),
             arrival_policy: config.arrival_policy.description().to_string(),
             strict_accounting: config.strict_accounting,
             production_grade: config.production_grade,
             allow_non_production: config.allow_non_production,
+            sample_scope: format!("{:?}", config.sample_scope),
             hash: 0,
         };
         fp.compute_hash();
         fp
     }
+
+    /// Hash of everything except the strategy parameters: the part of the
+    /// config that engine components (latency, matching, risk, settlement)
+    /// are built from.
+    pub fn engine_hash(&self) -> u64 {
+        let mut fp = Self {
+            strategy_params_hash: 0,
+            ..self.clone()
+        };
+        fp.compute_hash();
+        fp.hash
+    }
     
     fn compute_hash(&mut self) {
         let mut hasher = DefaultHasher::new();
@@ -506,16 +540,21 @@ impl ConfigFingerprint {
         // Other fields
         self.latency_model.hash(&mut hasher);
         self.order_latency_ns.hash(&mut hasher);
+        self.latency_config_hash.hash(&mut hasher);
         self.oms_parity_mode.hash(&mut hasher);
         self.maker_fill_model.hash(&mut hasher);
         self.integrity_policy.hash(&mut hasher);
         self.invariant_mode.hash(&mut hasher);
         self.fee_rate_bps.hash(&mut hasher);
         self.strategy_params_hash.hash(&mut hasher);
+        self.risk_limits_hash.hash(&mut hasher);
+        self.market_impact_hash.hash(&mut hasher);
+        self.monte_carlo_hash.hash(&mut hasher);
         self.arrival_policy.hash(&mut hasher);
         self.strict_accounting.hash(&mut hasher);
         self.production_grade.hash(&mut hasher);
         self.allow_non_production.hash(&mut hasher);
+        self.sample_scope.hash(&mut hasher);
         self.hash = hasher.finish();
     }
 }
@@ -549,6 +588,7 @@ impl StreamFingerprint {
 }
 
 /// Builder for incrementally computing a StreamFingerprint.
+#[derive(Serialize, Deserialize)]
 pub struct StreamFingerprintBuilder {
     stream_name: String,
     market_ids: std::collections::BTreeSet<String>,
@@ -813,6 +853,7 @@ fn hash_string(s: &str) -> u64 {
 }
 
 /// Builder for incrementally computing a BehaviorFingerprint.
+#[derive(Serialize, Deserialize)]
 pub struct BehaviorFingerprintBuilder {
     event_count: u64,
     rolling_hash: u64,
@@ -981,6 +1022,19 @@ impl BehaviorFingerprintBuilder {
         self.rolling_hash = hasher.finish();
     }
     
+    /// Record the decision trace hash (at finalization).
+    /// 
+    /// The decision trace is the canonical stream of submits, cancels, acks,
+    /// rejects, fills and settlements used by `RunDiff`. Mixing its hash in
+    /// ensures any order-level behavior change affects the fingerprint.
+    pub fn record_decision_trace_hash(&mut self, trace_hash: u64, record_count: u64) {
+        let mut hasher = DefaultHasher::new();
+        self.rolling_hash.hash(&mut hasher);
+        trace_hash.hash(&mut hasher);
+        record_count.hash(&mut hasher);
+        self.rolling_hash = hasher.finish();
+    }
+    
     fn add_event(&mut self, event: BehaviorEvent) {
         self.event_count += 1;
         let event_hash = event.hash_event();
@@ -1184,6 +1238,7 @@ impl RunFingerprint {
 /// Collects fingerprint data during a backtest run.
 /// 
 /// Create at run start, feed events during run, finalize at run end.
+#[derive(Serialize, Deserialize)]
 pub struct FingerprintCollector {
     /// Strategy identity (set at start).
     strategy_id: Option<StrategyId>,
@@ -1301,6 +1356,11 @@ impl FingerprintCollector {
         self.behavior.record_equity_curve_hash(equity_curve_hash, point_count);
     }
     
+    /// Record the decision trace hash (at finalization).
+    pub fn record_decision_trace_hash(&mut self, trace_hash: u64, record_count: u64) {
+        self.behavior.record_decision_trace_hash(trace_hash, record_count);
+    }
+    
     /// Finalize and produce the RunFingerprint.
     pub fn finalize(self) -> RunFingerprint {
         use crate::backtest_v2::data_contract::{
@@ -1354,16 +1414,21 @@ impl FingerprintCollector {
                 oracle_config_hash: None,
                 latency_model: "Unknown".to_string(),
                 order_latency_ns: None,
+                latency_config_hash: 0,
                 oms_parity_mode: "Unknown".to_string(),
                 maker_fill_model: "Unknown".to_string(),
                 integrity_policy: "Unknown".to_string(),
                 invariant_mode: "Unknown".to_string(),
                 fee_rate_bps: None,
                 strategy_params_hash: 0,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "Unknown".to_string(),
                 strict_accounting: false,
                 production_grade: false,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 0,
             }),
             dataset,
@@ -1507,16 +1572,21 @@ mod tests {
             oracle_config_hash: Some(0xABCD1234),
             latency_model: "Fixed".to_string(),
             order_latency_ns: Some(1_000_000),
+            latency_config_hash: 0,
             oms_parity_mode: "Full".to_string(),
             maker_fill_model: "ExplicitQueue".to_string(),
             integrity_policy: "Strict".to_string(),
             invariant_mode: "Hard".to_string(),
             fee_rate_bps: Some(10),
             strategy_params_hash: 0x1234,
+            risk_limits_hash: None,
+            market_impact_hash: None,
+            monte_carlo_hash: 0,
             arrival_policy: "RecordedArrival".to_string(),
             strict_accounting: true,
             production_grade: true,
             allow_non_production: false,
+            sample_scope: "Undeclared".to_string(),
             hash: 0x5678,
         };
         let dataset = DatasetFingerprint {
@@ -1569,7 +1639,7 @@ mod tests {
     
     #[test]
     fn test_fingerprint_version() {
-        assert_eq!(FINGERPRINT_VERSION, "RUNFP_V2");
+        assert_eq!(FINGERPRINT_VERSION, "RUNFP_V3");
     }
     
     // =========================================================================
@@ -1675,4 +1745,102 @@ mod tests {
         assert_eq!(fp.strategy.name, "captured_strategy");
         assert_eq!(fp.strategy.version, "3.0.0");
     }
+    
+    #[test]
+    fn test_config_fingerprint_covers_sample_scope() {
+        use crate::backtest_v2::orchestrator::BacktestConfig;
+        use crate::backtest_v2::walk_forward::SampleScope;
+        
+        let standalone = BacktestConfig::default();
+        let mut sweep_point = standalone.clone();
+        sweep_point.sample_scope = SampleScope::InSample {
+            group_id: "sweep_1".to_string(),
+        };
+        
+        assert_ne!(
+            ConfigFingerprint::from_config(&standalone).hash,
+            ConfigFingerprint::from_config(&sweep_point).hash,
+            "An in-sample sweep point must not share a config hash with a standalone run"
+        );
+    }
+    
+    #[test]
+    fn test_config_fingerprint_covers_latency_regimes() {
+        use crate::backtest_v2::latency_regime::LatencyRegimeConfig;
+        use crate::backtest_v2::orchestrator::BacktestConfig;
+        
+        let base = BacktestConfig::default();
+        let mut stressed = base.clone();
+        stressed.latency.regimes = LatencyRegimeConfig::polymarket_15m();
+        
+        let base_fp = ConfigFingerprint::from_config(&base);
+        let stressed_fp = ConfigFingerprint::from_config(&stressed);
+        assert_eq!(base_fp.latency_model, stressed_fp.latency_model);
+        assert_eq!(base_fp.order_latency_ns, stressed_fp.order_latency_ns);
+        assert_ne!(base_fp.latency_config_hash, stressed_fp.latency_config_hash);
+        assert_ne!(base_fp.hash, stressed_fp.hash);
+    }
+    
+    #[test]
+    fn test_config_fingerprint_covers_risk_limits() {
+        use crate::backtest_v2::orchestrator::BacktestConfig;
+        use crate::backtest_v2::risk::RiskLimits;
+        
+        let ungated = BacktestConfig::default();
+        let mut conservative = ungated.clone();
+        conservative.risk_limits = Some(RiskLimits::conservative());
+        let mut aggressive = ungated.clone();
+        aggressive.risk_limits = Some(RiskLimits::aggressive());
+        
+        let hashes = [
+            ConfigFingerprint::from_config(&ungated).hash,
+            ConfigFingerprint::from_config(&conservative).hash,
+            ConfigFingerprint::from_config(&aggressive).hash,
+        ];
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[1], hashes[2]);
+    }
+    
+    #[test]
+    fn test_config_fingerprint_covers_market_impact() {
+        use crate::backtest_v2::clock::NANOS_PER_SEC;
+        use crate::backtest_v2::market_impact::MarketImpactConfig;
+        use crate::backtest_v2::orchestrator::BacktestConfig;
+        
+        let legacy = BacktestConfig::default();
+        let mut instant = legacy.clone();
+        instant.market_impact = Some(MarketImpactConfig::none());
+        let mut half_life = legacy.clone();
+        half_life.market_impact = Some(MarketImpactConfig::half_life(30 * NANOS_PER_SEC, 0.0));
+        let mut permanent = legacy.clone();
+        permanent.market_impact = Some(MarketImpactConfig::half_life(30 * NANOS_PER_SEC, 0.01));
+        
+        let hashes = [
+            ConfigFingerprint::from_config(&legacy).hash,
+            ConfigFingerprint::from_config(&instant).hash,
+            ConfigFingerprint::from_config(&half_life).hash,
+            ConfigFingerprint::from_config(&permanent).hash,
+        ];
+        for i in 0..hashes.len() {
+            for j in i + 1..hashes.len() {
+                assert_ne!(hashes[i], hashes[j], "configs {} and {} share a hash", i, j);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_config_fingerprint_covers_monte_carlo() {
+        use crate::backtest_v2::orchestrator::BacktestConfig;
+        
+        let base = BacktestConfig::default();
+        let mut fewer_paths = base.clone();
+        fewer_paths.monte_carlo.iterations = 10;
+        let mut no_mc = base.clone();
+        no_mc.monte_carlo.enabled = false;
+        
+        let base_hash = ConfigFingerprint::from_config(&base).hash;
+        assert_ne!(base_hash, ConfigFingerprint::from_config(&fewer_paths).hash);
+        assert_ne!(base_hash, ConfigFingerprint::from_config(&no_mc).hash);
+    }
 }
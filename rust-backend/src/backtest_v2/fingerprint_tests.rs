@@ -159,16 +159,21 @@ fn test_config_sensitivity_fee_rate_changes_hash() {
         oracle_config_hash: None,
         latency_model: "Fixed".to_string(),
         order_latency_ns: Some(1_000_000),
+        latency_config_hash: 0,
         oms_parity_mode: "Full".to_string(),
         maker_fill_model: "ExplicitQueue".to_string(),
         integrity_policy: "Strict".to_string(),
         invariant_mode: "Hard".to_string(),
         fee_rate_bps: Some(10), // 10 bps
         strategy_params_hash: 0x1234,
+        risk_limits_hash: None,
+        market_impact_hash: None,
+        monte_carlo_hash: 0,
         arrival_policy: "RecordedArrival".to_string(),
         strict_accounting: true,
         production_grade: true,
         allow_non_production: false,
+        sample_scope: "Undeclared".to_string(),
         hash: 0,
     };
     
@@ -369,7 +374,7 @@ fn test_seed_sensitivity_different_seed_changes_hash() {
 
 #[test]
 fn test_version_string_correct() {
-    assert_eq!(FINGERPRINT_VERSION, "RUNFP_V1");
+    assert_eq!(FINGERPRINT_VERSION, "RUNFP_V3");
     
     let mut collector = FingerprintCollector::new();
     collector.record_decision(1, 1000, 1, 0x1234);
@@ -9,6 +9,8 @@
 //! - **Gate A: Zero-Edge Matching** - p_theory == p_mkt, expect PnL ~ 0 before fees
 //! - **Gate B: Martingale Price Path** - Random walk prices, no systematic profit
 //! - **Gate C: Signal Inversion** - Inverted signals should not both be profitable
+//! - **Gate D: Monte Carlo Robustness** - The run's own resampled 5th-percentile
+//!   total PnL must not be negative (only when a `MonteCarloReport` is supplied)
 //!
 //! # Hermetic Boundary Enforcement
 //!
@@ -32,6 +34,7 @@
 use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
 use crate::backtest_v2::events::{Event, Level, Price, Side, Size, TimestampedEvent};
 use crate::backtest_v2::ledger::{from_amount, to_amount, Amount};
+use crate::backtest_v2::monte_carlo::MonteCarloReport;
 use crate::backtest_v2::portfolio::Outcome;
 use crate::backtest_v2::strategy::{
     BookSnapshot, FillNotification, Strategy, StrategyContext, StrategyOrder, TradePrint,
@@ -74,6 +77,11 @@ pub struct GateTolerances {
     /// Maximum allowed correlation between original and inverted PnL.
     /// Default: -0.3 (should be negatively correlated if signal has value)
     pub max_inversion_correlation: f64,
+    
+    /// Minimum 5th-percentile total PnL across Monte Carlo methods (Gate D).
+    /// Default: $0.00 (a negative tail outcome blocks trust)
+    #[serde(default)]
+    pub min_monte_carlo_p5_pnl: f64,
 }
 
 impl Default for GateTolerances {
@@ -86,6 +94,7 @@ impl Default for GateTolerances {
             martingale_seeds: 100,
             max_martingale_drift_pct: 5.0,
             max_inversion_correlation: -0.3,
+            min_monte_carlo_p5_pnl: 0.0,
         }
     }
 }
@@ -475,6 +484,14 @@ impl<S: Strategy> Strategy for ZeroEdgeWrapper<S> {
         self.inner.on_cancel_ack(ctx, ack);
     }
     
+    fn on_replace_ack(&mut self, ctx: &mut StrategyContext, ack: &crate::backtest_v2::strategy::ReplaceAck) {
+        self.inner.on_replace_ack(ctx, ack);
+    }
+    
+    fn on_replace_reject(&mut self, ctx: &mut StrategyContext, reject: &crate::backtest_v2::strategy::ReplaceReject) {
+        self.inner.on_replace_reject(ctx, reject);
+    }
+    
     fn on_start(&mut self, ctx: &mut StrategyContext) {
         self.inner.on_start(ctx);
     }
@@ -538,6 +555,14 @@ impl<S: Strategy> Strategy for SignalInverter<S> {
         self.inner.on_cancel_ack(ctx, ack);
     }
     
+    fn on_replace_ack(&mut self, ctx: &mut StrategyContext, ack: &crate::backtest_v2::strategy::ReplaceAck) {
+        self.inner.on_replace_ack(ctx, ack);
+    }
+    
+    fn on_replace_reject(&mut self, ctx: &mut StrategyContext, reject: &crate::backtest_v2::strategy::ReplaceReject) {
+        self.inner.on_replace_reject(ctx, reject);
+    }
+    
     fn on_start(&mut self, ctx: &mut StrategyContext) {
         self.inner.on_start(ctx);
     }
@@ -689,6 +714,14 @@ impl GateSuite {
     /// 2. Martingale Gate - Random walk prices, no systematic profit
     /// 3. Signal Inversion Gate - Inverted signals must not both be profitable
     pub fn run(&self) -> GateSuiteReport {
+        self.run_with_monte_carlo(None)
+    }
+    
+    /// Run all mandatory gate tests plus Gate D over the run's Monte Carlo report.
+    /// 
+    /// Gate A-C validate the simulator; Gate D validates the run itself: a strategy
+    /// whose resampled 5th-percentile outcome is negative cannot be Trusted.
+    pub fn run_with_monte_carlo(&self, monte_carlo: Option<&MonteCarloReport>) -> GateSuiteReport {
         let start = std::time::Instant::now();
         let mut gates = Vec::new();
         
@@ -701,6 +734,11 @@ impl GateSuite {
         // Gate C: Signal inversion symmetry (MANDATORY)
         gates.push(self.run_gate_c_inversion());
         
+        // Gate D: Monte Carlo robustness of the run under test
+        if let Some(report) = monte_carlo {
+            gates.push(self.run_gate_d_monte_carlo(report));
+        }
+        
         let passed = gates.iter().all(|g| g.passed);
         
         // Build trust level with failure reasons if any gate failed
@@ -1095,6 +1133,52 @@ impl GateSuite {
         }
     }
     
+    /// Gate D: Monte Carlo robustness test.
+    /// 
+    /// Uses the resampled distributions of the run under test.
+    /// Expected: 5th-percentile total PnL >= tolerance under every method.
+    fn run_gate_d_monte_carlo(&self, report: &MonteCarloReport) -> GateTestResult {
+        let start = std::time::Instant::now();
+        let threshold = self.config.tolerances.min_monte_carlo_p5_pnl;
+        
+        let mut metrics = GateMetrics {
+            pnl_after_fees: report.observed_total_pnl,
+            ..Default::default()
+        };
+        
+        let worst = report
+            .distributions
+            .iter()
+            .min_by(|a, b| a.total_pnl.p5.total_cmp(&b.total_pnl.p5));
+        if let Some(d) = worst {
+            metrics.mean_pnl = Some(d.total_pnl.mean);
+            metrics.std_pnl = Some(d.total_pnl.std_dev);
+            metrics.positive_pnl_probability = Some(d.positive_probability);
+            metrics.max_drawdown = d.max_drawdown.p95;
+            metrics.sharpe = Some(d.sharpe.p50);
+        }
+        
+        let (passed, failure_reason) = match worst {
+            Some(d) if d.total_pnl.p5 < threshold => (
+                false,
+                Some(format!(
+                    "{:?}: 5th-percentile total PnL ${:.2} < ${:.2} (observed ${:.2})",
+                    d.method, d.total_pnl.p5, threshold, report.observed_total_pnl
+                )),
+            ),
+            _ => (true, None),
+        };
+        
+        GateTestResult {
+            name: "Gate D: Monte Carlo Robustness".to_string(),
+            passed,
+            failure_reason,
+            metrics,
+            failed_seeds: vec![],
+            execution_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+    
     fn run_directional_test(&self, seed: u64, invert: bool) -> GateMetrics {
         let mut price_gen = SyntheticPriceGenerator::new(0.5, 0.002, seed);
         
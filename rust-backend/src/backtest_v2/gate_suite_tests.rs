@@ -590,3 +590,53 @@ fn test_combined_gates_provide_trust_level() {
     println!("  Trust Level: {:?}", report.trust_level);
     println!("  Total execution time: {}ms", report.total_execution_ms);
 }
+
+// =============================================================================
+// MONTE CARLO ROBUSTNESS (GATE D)
+// =============================================================================
+
+#[test]
+fn test_negative_monte_carlo_tail_blocks_trust() {
+    use crate::backtest_v2::monte_carlo::{MonteCarloAnalyzer, MonteCarloConfig, MonteCarloInput};
+    
+    let config = GateSuiteConfig {
+        windows_per_gate: 5,
+        ..Default::default()
+    };
+    let suite = GateSuite::new(config);
+    let analyzer = MonteCarloAnalyzer::new(MonteCarloConfig::default()).unwrap();
+    
+    // Positive realized PnL carried by two outlier windows
+    let mut windows = vec![-1.0; 38];
+    windows.extend([30.0, 30.0]);
+    let fragile = analyzer.analyze(&MonteCarloInput {
+        window_pnls: windows,
+        trade_pnls: vec![],
+        initial_capital: 1000.0,
+    });
+    
+    let baseline = suite.run();
+    let report = suite.run_with_monte_carlo(Some(&fragile));
+    assert_eq!(report.gates.len(), baseline.gates.len() + 1);
+    
+    let gate_d = report.gates.last().unwrap();
+    assert_eq!(gate_d.name, "Gate D: Monte Carlo Robustness");
+    assert!(!gate_d.passed);
+    assert!(gate_d.failure_reason.as_ref().unwrap().contains("5th-percentile"));
+    assert!(!report.passed);
+    assert!(report
+        .trust_level
+        .failure_reasons()
+        .iter()
+        .any(|r| r.gate_name == gate_d.name));
+    
+    // A consistent edge passes Gate D and leaves the suite verdict unchanged
+    let steady = analyzer.analyze(&MonteCarloInput {
+        window_pnls: (0..40).map(|i| if i % 4 == 0 { -0.5 } else { 1.0 }).collect(),
+        trade_pnls: vec![],
+        initial_capital: 1000.0,
+    });
+    let report = suite.run_with_monte_carlo(Some(&steady));
+    assert!(report.gates.last().unwrap().passed);
+    assert_eq!(report.passed, baseline.passed);
+}
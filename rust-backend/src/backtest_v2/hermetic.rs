@@ -321,6 +321,7 @@ impl HermeticDecisionProof {
 }
 
 /// Builder for HermeticDecisionProof.
+#[derive(Serialize, Deserialize)]
 pub struct HermeticDecisionProofBuilder {
     decision_id: u64,
     strategy_name: String,
@@ -609,6 +610,7 @@ impl std::error::Error for HermeticAbort {}
 // =============================================================================
 
 /// Enforcer for hermetic strategy mode.
+#[derive(Serialize, Deserialize)]
 pub struct HermeticEnforcer {
     config: HermeticConfig,
     /// Next decision ID.
@@ -217,7 +217,7 @@ impl Default for SyncState {
 }
 
 /// Per-token stream state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TokenState {
     /// Current sync state.
     sync_state: SyncState,
@@ -294,6 +294,7 @@ pub enum HaltType {
 }
 
 /// Stream integrity guard - enforces pathology policies deterministically.
+#[derive(Serialize, Deserialize)]
 pub struct StreamIntegrityGuard {
     /// Policy configuration.
     policy: PathologyPolicy,
@@ -559,6 +559,7 @@ impl std::error::Error for InvariantAbort {}
 pub type InvariantResult<T> = Result<T, InvariantAbort>;
 
 /// Main invariant enforcer.
+#[derive(Serialize, Deserialize)]
 pub struct InvariantEnforcer {
     config: InvariantConfig,
     counters: InvariantCounters,
@@ -583,7 +584,9 @@ pub struct InvariantEnforcer {
     current_position: f64,
     open_order_count: usize,
     
-    // Fingerprinting
+    // Fingerprinting (hasher state is not serializable; never fed, so a
+    // fresh hasher is equivalent after a checkpoint restore)
+    #[serde(skip)]
     behavior_hasher: std::collections::hash_map::DefaultHasher,
     config_hash: u64,
     
@@ -1100,7 +1103,7 @@ impl InvariantEnforcer {
     }
 
     fn is_valid_transition(from: OrderState, to: OrderState) -> bool {
-        // OrderState variants: New, PendingAck, Live, PartiallyFilled, PendingCancel, Done
+        // OrderState variants: New, PendingAck, Live, PartiallyFilled, PendingCancel, PendingReplace, Done
         match (from, to) {
             // Normal flow
             (OrderState::New, OrderState::PendingAck) => true,
@@ -1116,6 +1119,13 @@ impl InvariantEnforcer {
             (OrderState::PendingCancel, OrderState::Done) => true,
             (OrderState::PendingCancel, OrderState::Live) => true, // Cancel rejected
             (OrderState::PendingCancel, OrderState::PartiallyFilled) => true,
+            // Replace flow
+            (OrderState::Live, OrderState::PendingReplace) => true,
+            (OrderState::PartiallyFilled, OrderState::PendingReplace) => true,
+            (OrderState::PendingReplace, OrderState::PendingReplace) => true, // Fill while pending
+            (OrderState::PendingReplace, OrderState::Live) => true,
+            (OrderState::PendingReplace, OrderState::PartiallyFilled) => true,
+            (OrderState::PendingReplace, OrderState::Done) => true,
             // Direct rejection from New
             (OrderState::New, OrderState::Done) => true,
             _ => false,
@@ -0,0 +1,892 @@
+//! Backtest Job Queue
+//!
+//! Asynchronous execution of backtests submitted over the API: a bounded queue
+//! of job specs drained by a fixed pool of worker threads. Each job loads its
+//! dataset, runs a `BacktestOrchestrator`, and persists the result into the
+//! `ArtifactStore`, where it becomes visible to the read-only run endpoints.
+//!
+//! # Lifecycle
+//!
+//! ```text
+//! Queued ──► Running ──► Completed (run_id)
+//!   │           │
+//!   │           ├──────► Failed (error)
+//!   ▼           ▼
+//! Cancelled ◄───┘
+//! ```
+//!
+//! Cancelling a queued job removes it from the queue. Cancelling a running job
+//! raises a flag on its [`RunControl`]; the orchestrator checks it between events
+//! and aborts the run. Cancelled and failed runs are never persisted.
+//!
+//! # Datasets
+//!
+//! Jobs name a dataset; a [`JobDatasetLoader`] turns it into events. The server
+//! uses [`SqliteDatasetLoader`], which only resolves plain file names inside a
+//! configured root directory, so API clients cannot reach arbitrary paths.
+//!
+//! Job state is held in memory: queued and running jobs do not survive a
+//! restart, but every completed run is durable in the `ArtifactStore`.
+
+use crate::backtest_v2::artifact_store::ArtifactStore;
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::events::TimestampedEvent;
+use crate::backtest_v2::feed::VecFeed;
+use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator};
+use crate::backtest_v2::param_sweep::persist_run;
+use crate::backtest_v2::run_artifact::RunId;
+use crate::backtest_v2::sqlite_dataset::load_events_from_sqlite;
+use crate::backtest_v2::strategy::StrategyParams;
+use crate::backtest_v2::strategy_factory::{available_strategies, make_strategy};
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+// =============================================================================
+// RUN CONTROL
+// =============================================================================
+
+/// Shared handle for observing and cancelling a run in progress.
+///
+/// Installed on an orchestrator with `BacktestOrchestrator::set_run_control`.
+#[derive(Debug, Clone, Default)]
+pub struct RunControl {
+    cancelled: Arc<AtomicBool>,
+    events_total: Arc<AtomicU64>,
+    events_processed: Arc<AtomicU64>,
+}
+
+impl RunControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. The run aborts at the next event boundary.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Events queued when the run started.
+    pub fn events_total(&self) -> u64 {
+        self.events_total.load(Ordering::Relaxed)
+    }
+
+    /// Events processed so far.
+    pub fn events_processed(&self) -> u64 {
+        self.events_processed.load(Ordering::Relaxed)
+    }
+
+    /// Fraction of queued events processed, in [0, 1].
+    pub fn progress(&self) -> f64 {
+        let total = self.events_total();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.events_processed() as f64 / total as f64).min(1.0)
+    }
+
+    pub(crate) fn set_events_total(&self, total: u64) {
+        self.events_total.store(total, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_events_processed(&self, processed: u64) {
+        self.events_processed.store(processed, Ordering::Relaxed);
+    }
+}
+
+// =============================================================================
+// JOB SPEC
+// =============================================================================
+
+/// Dataset reference of a job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobDataset {
+    /// Dataset name, resolved by the server's `JobDatasetLoader`.
+    pub name: String,
+    /// Markets to replay. More than one runs a multi-market portfolio backtest.
+    pub market_ids: Vec<String>,
+}
+
+/// A backtest job as submitted by a client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestJobSpec {
+    /// Strategy name from `strategy_factory::available_strategies`.
+    pub strategy_name: String,
+    /// Strategy parameters.
+    #[serde(default)]
+    pub params: BTreeMap<String, f64>,
+    pub dataset: JobDataset,
+    pub start_ns: Nanos,
+    pub end_ns: Nanos,
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+    /// Run with research-mode config (results are always Untrusted).
+    #[serde(default)]
+    pub allow_non_production: bool,
+}
+
+fn default_seed() -> u64 {
+    42
+}
+
+impl BacktestJobSpec {
+    /// Validate the spec before it is queued.
+    pub fn validate(&self) -> Result<(), String> {
+        let strategy = self.strategy_name.to_lowercase();
+        if !available_strategies().contains_key(strategy.as_str()) {
+            return Err(format!("Unknown strategy: {}", self.strategy_name));
+        }
+        if self.dataset.name.trim().is_empty() {
+            return Err("Dataset name is required".to_string());
+        }
+        if self.dataset.market_ids.is_empty() {
+            return Err("At least one market id is required".to_string());
+        }
+        if self.end_ns <= self.start_ns {
+            return Err(format!(
+                "Invalid time range: end {} <= start {}",
+                self.end_ns, self.start_ns
+            ));
+        }
+        if let Some((key, _)) = self.params.iter().find(|(_, v)| !v.is_finite()) {
+            return Err(format!("Parameter '{}' is not finite", key));
+        }
+        Ok(())
+    }
+
+    /// Backtest config for this job.
+    pub fn backtest_config(&self) -> BacktestConfig {
+        let base = if self.allow_non_production {
+            BacktestConfig {
+                allow_non_production: true,
+                ..BacktestConfig::research_mode()
+            }
+        } else {
+            BacktestConfig::production_grade_15m_updown()
+        };
+        BacktestConfig {
+            seed: self.seed,
+            strategy_params: self.strategy_params(),
+            ..base
+        }
+    }
+
+    /// Strategy params, including the run seed.
+    pub fn strategy_params(&self) -> StrategyParams {
+        self.params.iter().fold(
+            StrategyParams::new().with_param("seed", self.seed as f64),
+            |p, (k, v)| p.with_param(k.clone(), *v),
+        )
+    }
+}
+
+// =============================================================================
+// DATASET LOADING
+// =============================================================================
+
+/// Resolves a job's dataset into a time-ordered event stream.
+pub trait JobDatasetLoader: Send + Sync {
+    fn load(
+        &self,
+        dataset: &JobDataset,
+        start_ns: Nanos,
+        end_ns: Nanos,
+    ) -> Result<Vec<TimestampedEvent>, String>;
+}
+
+/// Loads SQLite datasets by file name from a root directory.
+#[derive(Debug, Clone)]
+pub struct SqliteDatasetLoader {
+    root: PathBuf,
+}
+
+impl SqliteDatasetLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolve a dataset name to a path inside the root. Only plain file names
+    /// are accepted.
+    pub fn resolve(&self, name: &str) -> Result<PathBuf, String> {
+        let valid = !name.is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains(['/', '\\'])
+            && !name.starts_with('.');
+        if !valid {
+            return Err(format!("Invalid dataset name: {}", name));
+        }
+        let path = self.root.join(name);
+        if !path.is_file() {
+            return Err(format!("Dataset not found: {}", name));
+        }
+        Ok(path)
+    }
+}
+
+impl JobDatasetLoader for SqliteDatasetLoader {
+    fn load(
+        &self,
+        dataset: &JobDataset,
+        start_ns: Nanos,
+        end_ns: Nanos,
+    ) -> Result<Vec<TimestampedEvent>, String> {
+        let path = self.resolve(&dataset.name)?;
+        let (events, _counts) = load_events_from_sqlite(
+            &path.to_string_lossy(),
+            &dataset.market_ids,
+            start_ns,
+            end_ns,
+        )?;
+        Ok(events)
+    }
+}
+
+// =============================================================================
+// JOB STATUS
+// =============================================================================
+
+/// Unique job identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct JobId(pub String);
+
+impl std::fmt::Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Job state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    /// Whether the job has reached a final state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Pollable job status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub job_id: JobId,
+    pub state: JobState,
+    pub spec: BacktestJobSpec,
+    /// Submitting user (auth subject); only they or an admin may see or cancel the job.
+    pub owner: String,
+    /// Wall-clock timestamps (ms since epoch).
+    pub submitted_at_ms: i64,
+    pub started_at_ms: Option<i64>,
+    pub finished_at_ms: Option<i64>,
+    /// Position in the queue (0 = next), while queued.
+    pub queue_position: Option<usize>,
+    /// Fraction of events processed, while running.
+    pub progress: f64,
+    pub events_processed: u64,
+    pub events_total: u64,
+    /// Persisted run, once completed.
+    pub run_id: Option<RunId>,
+    pub final_pnl: Option<f64>,
+    pub trusted: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// Job queue errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobQueueError {
+    /// Spec failed validation.
+    Invalid(String),
+    /// The queue is at capacity.
+    QueueFull { capacity: usize },
+    /// No job with this id.
+    NotFound(JobId),
+    /// The job already finished.
+    AlreadyFinished { job_id: JobId, state: JobState },
+}
+
+impl std::fmt::Display for JobQueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(msg) => write!(f, "Invalid job: {}", msg),
+            Self::QueueFull { capacity } => write!(f, "Job queue full ({} queued)", capacity),
+            Self::NotFound(id) => write!(f, "Job not found: {}", id),
+            Self::AlreadyFinished { job_id, state } => {
+                write!(f, "Job {} already finished ({:?})", job_id, state)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JobQueueError {}
+
+// =============================================================================
+// JOB QUEUE
+// =============================================================================
+
+/// Job queue configuration.
+#[derive(Debug, Clone)]
+pub struct JobQueueConfig {
+    /// Worker threads (concurrent runs).
+    pub workers: usize,
+    /// Maximum jobs waiting to run; further submissions are rejected.
+    pub max_queued: usize,
+    /// Finished jobs kept for polling; the oldest are evicted first.
+    pub max_retained: usize,
+    /// Source of the status timestamps (ms since epoch).
+    pub clock: fn() -> i64,
+}
+
+impl Default for JobQueueConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            max_queued: 32,
+            max_retained: 1_000,
+            clock: wall_clock_ms,
+        }
+    }
+}
+
+/// Wall-clock milliseconds for job status timestamps.
+///
+/// Job timestamps are API bookkeeping for the server, outside any simulation,
+/// so the hermetic wall-clock ban does not apply here.
+#[allow(clippy::disallowed_methods, clippy::disallowed_types)]
+pub fn wall_clock_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+struct JobEntry {
+    status: JobStatus,
+    control: RunControl,
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: HashMap<JobId, JobEntry>,
+    pending: VecDeque<JobId>,
+    /// Finished job ids, oldest first (for eviction).
+    finished: VecDeque<JobId>,
+    next_id: u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    config: JobQueueConfig,
+    state: Mutex<QueueState>,
+    work_ready: Condvar,
+    store: Arc<ArtifactStore>,
+    loader: Arc<dyn JobDatasetLoader>,
+}
+
+/// Bounded backtest job queue with a worker pool.
+pub struct BacktestJobQueue {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl BacktestJobQueue {
+    /// Create the queue and start its workers.
+    pub fn new(
+        config: JobQueueConfig,
+        store: Arc<ArtifactStore>,
+        loader: Arc<dyn JobDatasetLoader>,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            config: config.clone(),
+            state: Mutex::new(QueueState::default()),
+            work_ready: Condvar::new(),
+            store,
+            loader,
+        });
+        let workers = (0..config.workers)
+            .map(|i| {
+                let shared = shared.clone();
+                std::thread::Builder::new()
+                    .name(format!("backtest-job-{}", i))
+                    .spawn(move || worker_loop(&shared))
+                    .expect("failed to spawn backtest job worker")
+            })
+            .collect();
+        Self { shared, workers }
+    }
+
+    /// Validate and enqueue a job on behalf of `owner`.
+    pub fn submit(&self, spec: BacktestJobSpec, owner: &str) -> Result<JobStatus, JobQueueError> {
+        spec.validate().map_err(JobQueueError::Invalid)?;
+
+        let mut state = self.shared.state.lock();
+        if state.pending.len() >= self.shared.config.max_queued {
+            return Err(JobQueueError::QueueFull {
+                capacity: self.shared.config.max_queued,
+            });
+        }
+
+        state.next_id += 1;
+        let submitted_at_ms = (self.shared.config.clock)();
+        let job_id = JobId(format!("job_{}_{:06}", submitted_at_ms, state.next_id));
+        let status = JobStatus {
+            job_id: job_id.clone(),
+            state: JobState::Queued,
+            spec,
+            owner: owner.to_string(),
+            submitted_at_ms,
+            started_at_ms: None,
+            finished_at_ms: None,
+            queue_position: None,
+            progress: 0.0,
+            events_processed: 0,
+            events_total: 0,
+            run_id: None,
+            final_pnl: None,
+            trusted: None,
+            error: None,
+        };
+        state.jobs.insert(
+            job_id.clone(),
+            JobEntry {
+                status,
+                control: RunControl::new(),
+            },
+        );
+        state.pending.push_back(job_id.clone());
+        // Snapshot before releasing the lock: a worker may pick the job up immediately
+        let queued = snapshot(&state, &job_id, &state.jobs[&job_id]);
+        drop(state);
+
+        self.shared.work_ready.notify_one();
+        tracing::info!(job_id = %job_id, "Backtest job queued");
+        Ok(queued)
+    }
+
+    /// Current status of a job.
+    pub fn status(&self, job_id: &JobId) -> Option<JobStatus> {
+        let state = self.shared.state.lock();
+        state.jobs.get(job_id).map(|e| snapshot(&state, job_id, e))
+    }
+
+    /// All known jobs, newest first.
+    pub fn list(&self) -> Vec<JobStatus> {
+        let state = self.shared.state.lock();
+        let mut jobs: Vec<JobStatus> = state
+            .jobs
+            .iter()
+            .map(|(id, e)| snapshot(&state, id, e))
+            .collect();
+        jobs.sort_by(|a, b| {
+            b.submitted_at_ms
+                .cmp(&a.submitted_at_ms)
+                .then_with(|| b.job_id.cmp(&a.job_id))
+        });
+        jobs
+    }
+
+    /// Cancel a queued or running job.
+    pub fn cancel(&self, job_id: &JobId) -> Result<JobStatus, JobQueueError> {
+        let mut state = self.shared.state.lock();
+        let entry = state
+            .jobs
+            .get_mut(job_id)
+            .ok_or_else(|| JobQueueError::NotFound(job_id.clone()))?;
+
+        match entry.status.state {
+            JobState::Queued => {
+                entry.status.state = JobState::Cancelled;
+                entry.status.finished_at_ms = Some((self.shared.config.clock)());
+                entry.control.cancel();
+                state.pending.retain(|id| id != job_id);
+                retire(&mut state, &self.shared.config, job_id.clone());
+            }
+            JobState::Running => {
+                // The worker observes the flag and finalizes the state
+                entry.control.cancel();
+            }
+            terminal => {
+                return Err(JobQueueError::AlreadyFinished {
+                    job_id: job_id.clone(),
+                    state: terminal,
+                });
+            }
+        }
+        tracing::info!(job_id = %job_id, "Backtest job cancellation requested");
+
+        let entry = &state.jobs[job_id];
+        Ok(snapshot(&state, job_id, entry))
+    }
+
+    /// Number of jobs waiting to run.
+    pub fn queued_len(&self) -> usize {
+        self.shared.state.lock().pending.len()
+    }
+}
+
+impl Drop for BacktestJobQueue {
+    fn drop(&mut self) {
+        {
+            let mut state = self.shared.state.lock();
+            state.shutdown = true;
+            for entry in state.jobs.values() {
+                entry.control.cancel();
+            }
+        }
+        self.shared.work_ready.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Status snapshot with live progress filled in.
+fn snapshot(state: &QueueState, job_id: &JobId, entry: &JobEntry) -> JobStatus {
+    let mut status = entry.status.clone();
+    match status.state {
+        JobState::Queued => {
+            status.queue_position = state.pending.iter().position(|id| id == job_id);
+        }
+        JobState::Running => {
+            status.progress = entry.control.progress();
+            status.events_processed = entry.control.events_processed();
+            status.events_total = entry.control.events_total();
+        }
+        _ => {}
+    }
+    status
+}
+
+/// Record a finished job and evict the oldest beyond the retention limit.
+fn retire(state: &mut QueueState, config: &JobQueueConfig, job_id: JobId) {
+    state.finished.push_back(job_id);
+    while state.finished.len() > config.max_retained {
+        if let Some(old) = state.finished.pop_front() {
+            state.jobs.remove(&old);
+        }
+    }
+}
+
+fn worker_loop(shared: &Shared) {
+    loop {
+        let (job_id, spec, control) = {
+            let mut state = shared.state.lock();
+            loop {
+                if state.shutdown {
+                    return;
+                }
+                if let Some(job_id) = state.pending.pop_front() {
+                    let entry = state.jobs.get_mut(&job_id).expect("pending job exists");
+                    entry.status.state = JobState::Running;
+                    entry.status.started_at_ms = Some((shared.config.clock)());
+                    break (job_id, entry.status.spec.clone(), entry.control.clone());
+                }
+                shared.work_ready.wait(&mut state);
+            }
+        };
+
+        tracing::info!(job_id = %job_id, strategy = %spec.strategy_name, "Backtest job started");
+        // A panic (e.g. a strict accounting violation) fails the job, not the worker
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_job(shared, &spec, &control)))
+            .unwrap_or_else(|payload| {
+                Err(format!("Backtest panicked: {}", panic_message(&*payload)))
+            });
+
+        let mut state = shared.state.lock();
+        if let Some(entry) = state.jobs.get_mut(&job_id) {
+            entry.status.finished_at_ms = Some((shared.config.clock)());
+            entry.status.progress = control.progress();
+            entry.status.events_processed = control.events_processed();
+            entry.status.events_total = control.events_total();
+            match outcome {
+                Ok(done) => {
+                    entry.status.state = JobState::Completed;
+                    entry.status.progress = 1.0;
+                    entry.status.run_id = Some(done.run_id);
+                    entry.status.final_pnl = Some(done.final_pnl);
+                    entry.status.trusted = Some(done.trusted);
+                }
+                Err(_) if control.is_cancelled() => {
+                    entry.status.state = JobState::Cancelled;
+                }
+                Err(e) => {
+                    entry.status.state = JobState::Failed;
+                    entry.status.error = Some(e);
+                }
+            }
+            tracing::info!(job_id = %job_id, state = ?entry.status.state, "Backtest job finished");
+            retire(&mut state, &shared.config, job_id);
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+struct JobOutput {
+    run_id: RunId,
+    final_pnl: f64,
+    trusted: bool,
+}
+
+fn run_job(shared: &Shared, spec: &BacktestJobSpec, control: &RunControl) -> Result<JobOutput, String> {
+    let events = shared.loader.load(&spec.dataset, spec.start_ns, spec.end_ns)?;
+    if control.is_cancelled() {
+        return Err("Job cancelled".to_string());
+    }
+
+    let config = spec.backtest_config();
+    let mut strategy = make_strategy(&spec.strategy_name, &config.strategy_params)?;
+
+    let mut feed = VecFeed::new(&spec.dataset.name, events);
+    let mut orchestrator = BacktestOrchestrator::new(config.clone());
+    orchestrator.set_run_control(control.clone());
+    orchestrator
+        .load_feed(&mut feed)
+        .map_err(|e| format!("Failed to load feed: {}", e))?;
+    let results = orchestrator
+        .run(strategy.as_mut())
+        .map_err(|e| format!("Backtest error: {}", e))?;
+
+    let final_pnl = results.final_pnl;
+    let trusted = match &results.trust_decision {
+        Some(decision) => decision.is_trusted(),
+        None => results.trust_level.is_trusted(),
+    };
+    let run_id = persist_run(&shared.store, results, &config)?;
+    Ok(JobOutput {
+        run_id,
+        final_pnl,
+        trusted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::{Event, Level};
+    use crate::backtest_v2::queue::StreamSource;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    /// Synthetic dataset; optionally blocks until released.
+    struct TestLoader {
+        gate: Option<Mutex<mpsc::Receiver<()>>>,
+    }
+
+    impl JobDatasetLoader for TestLoader {
+        fn load(
+            &self,
+            _dataset: &JobDataset,
+            start_ns: Nanos,
+            _end_ns: Nanos,
+        ) -> Result<Vec<TimestampedEvent>, String> {
+            if let Some(gate) = &self.gate {
+                let _ = gate.lock().recv();
+            }
+            Ok((0..20)
+                .map(|i| {
+                    let mid = 0.5 + (i % 5) as f64 * 0.01;
+                    TimestampedEvent::new(
+                        start_ns + (i + 1) * 1_000_000_000,
+                        StreamSource::MarketData as u8,
+                        Event::L2BookSnapshot {
+                            token_id: "TEST".into(),
+                            bids: vec![Level::new(mid - 0.02, 100.0)],
+                            asks: vec![Level::new(mid + 0.02, 100.0)],
+                            exchange_seq: i as u64,
+                        },
+                    )
+                })
+                .collect())
+        }
+    }
+
+    /// Dataset loader that panics, standing in for a panicking run.
+    struct PanickingLoader;
+
+    impl JobDatasetLoader for PanickingLoader {
+        fn load(
+            &self,
+            _dataset: &JobDataset,
+            _start_ns: Nanos,
+            _end_ns: Nanos,
+        ) -> Result<Vec<TimestampedEvent>, String> {
+            panic!("loader exploded");
+        }
+    }
+
+    fn spec() -> BacktestJobSpec {
+        BacktestJobSpec {
+            strategy_name: "noop".into(),
+            params: BTreeMap::new(),
+            dataset: JobDataset {
+                name: "test".into(),
+                market_ids: vec!["TEST".into()],
+            },
+            start_ns: 0,
+            end_ns: 60_000_000_000,
+            seed: 7,
+            allow_non_production: true,
+        }
+    }
+
+    fn queue(
+        workers: usize,
+        max_queued: usize,
+        loader: impl JobDatasetLoader + 'static,
+    ) -> (BacktestJobQueue, Arc<ArtifactStore>) {
+        let store = Arc::new(ArtifactStore::in_memory().unwrap());
+        let queue = BacktestJobQueue::new(
+            JobQueueConfig {
+                workers,
+                max_queued,
+                max_retained: 100,
+                clock: || 1_700_000_000_000,
+            },
+            store.clone(),
+            Arc::new(loader),
+        );
+        (queue, store)
+    }
+
+    fn wait_terminal(queue: &BacktestJobQueue, job_id: &JobId) -> JobStatus {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        loop {
+            let status = queue.status(job_id).unwrap();
+            if status.state.is_terminal() {
+                return status;
+            }
+            assert!(Instant::now() < deadline, "job did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_job_runs_and_persists_artifact() {
+        let (queue, store) = queue(1, 4, TestLoader { gate: None });
+        let job = queue.submit(spec(), "alice").unwrap();
+        assert_eq!(job.state, JobState::Queued);
+        assert_eq!(job.owner, "alice");
+        assert_eq!(job.submitted_at_ms, 1_700_000_000_000);
+
+        let done = wait_terminal(&queue, &job.job_id);
+        assert_eq!(done.state, JobState::Completed, "error: {:?}", done.error);
+        assert_eq!(done.progress, 1.0);
+        assert_eq!(done.trusted, Some(false));
+
+        let run_id = done.run_id.unwrap();
+        let artifact = store.get(&run_id).unwrap().expect("artifact persisted");
+        assert_eq!(artifact.manifest.config_summary.seed, 7);
+    }
+
+    #[test]
+    fn test_panicking_job_fails_and_worker_survives() {
+        let (queue, _store) = queue(1, 4, PanickingLoader);
+        let first = queue.submit(spec(), "alice").unwrap();
+        let second = queue.submit(spec(), "alice").unwrap();
+
+        for job in [first, second] {
+            let done = wait_terminal(&queue, &job.job_id);
+            assert_eq!(done.state, JobState::Failed);
+            assert!(done.error.unwrap().contains("loader exploded"));
+        }
+    }
+
+    #[test]
+    fn test_submit_rejects_invalid_specs_and_full_queue() {
+        // No workers: jobs stay queued
+        let (queue, _store) = queue(0, 2, TestLoader { gate: None });
+
+        let bad_strategy = BacktestJobSpec {
+            strategy_name: "no_such_strategy".into(),
+            ..spec()
+        };
+        assert!(matches!(queue.submit(bad_strategy, "alice"), Err(JobQueueError::Invalid(_))));
+        let bad_range = BacktestJobSpec {
+            end_ns: 0,
+            ..spec()
+        };
+        assert!(matches!(queue.submit(bad_range, "alice"), Err(JobQueueError::Invalid(_))));
+
+        let first = queue.submit(spec(), "alice").unwrap();
+        let second = queue.submit(spec(), "alice").unwrap();
+        assert_eq!(queue.status(&first.job_id).unwrap().queue_position, Some(0));
+        assert_eq!(queue.status(&second.job_id).unwrap().queue_position, Some(1));
+        assert_eq!(
+            queue.submit(spec(), "alice").unwrap_err(),
+            JobQueueError::QueueFull { capacity: 2 }
+        );
+        assert_eq!(queue.list().len(), 2);
+    }
+
+    #[test]
+    fn test_cancel_queued_job() {
+        let (queue, _store) = queue(0, 4, TestLoader { gate: None });
+        let job = queue.submit(spec(), "alice").unwrap();
+
+        let cancelled = queue.cancel(&job.job_id).unwrap();
+        assert_eq!(cancelled.state, JobState::Cancelled);
+        assert_eq!(queue.queued_len(), 0);
+        assert!(matches!(
+            queue.cancel(&job.job_id),
+            Err(JobQueueError::AlreadyFinished { .. })
+        ));
+        assert!(matches!(
+            queue.cancel(&JobId("job_missing".into())),
+            Err(JobQueueError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_cancel_running_job_is_not_persisted() {
+        let (release, gate) = mpsc::channel();
+        let (queue, store) = queue(1, 4, TestLoader { gate: Some(Mutex::new(gate)) });
+        let job = queue.submit(spec(), "alice").unwrap();
+
+        // Wait until the worker is blocked in the loader
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while queue.status(&job.job_id).unwrap().state != JobState::Running {
+            assert!(Instant::now() < deadline, "job never started");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        queue.cancel(&job.job_id).unwrap();
+        release.send(()).unwrap();
+
+        let done = wait_terminal(&queue, &job.job_id);
+        assert_eq!(done.state, JobState::Cancelled);
+        assert!(done.run_id.is_none());
+        assert_eq!(store.stats().unwrap().total_runs, 0);
+    }
+
+    #[test]
+    fn test_sqlite_loader_rejects_paths_outside_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("data.db"), b"").unwrap();
+        let loader = SqliteDatasetLoader::new(dir.path());
+
+        assert!(loader.resolve("data.db").is_ok());
+        assert!(loader.resolve("missing.db").is_err());
+        for name in ["../data.db", "/etc/passwd", "sub/data.db", "..", ".hidden", ""] {
+            assert!(loader.resolve(name).is_err(), "{} should be rejected", name);
+        }
+    }
+}
@@ -279,6 +279,55 @@ impl L2Storage {
         })
     }
 
+    /// Open an existing recording for replay without touching its schema or
+    /// metadata. Sequence scope, origin and tick size are read back from the
+    /// recorded metadata.
+    pub fn open_read_only(db_path: &str) -> Result<Self> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let conn = Connection::open_with_flags(db_path, flags)
+            .with_context(|| format!("Failed to open L2 storage: {}", db_path))?;
+
+        let mut storage = Self {
+            conn: Arc::new(Mutex::new(conn)),
+            last_seq: Mutex::new(HashMap::new()),
+            last_hash: Mutex::new(HashMap::new()),
+            seq_scope: SequenceScope::default(),
+            seq_origin: SequenceOrigin::None,
+            tick_size: POLYMARKET_TICK_SIZE,
+            stats: L2StorageStats::default(),
+        };
+
+        if let Some(scope) = storage.get_metadata("seq_scope")? {
+            storage.seq_scope = match scope.as_str() {
+                "PerMarketSide" => SequenceScope::PerMarketSide,
+                _ => SequenceScope::PerMarket,
+            };
+        }
+        if let Some(origin) = storage.get_metadata("seq_origin")? {
+            storage.seq_origin = match origin.as_str() {
+                "Exchange" => SequenceOrigin::Exchange,
+                "SyntheticFromArrival" => SequenceOrigin::SyntheticFromArrival,
+                "DerivedFromHash" => SequenceOrigin::DerivedFromHash,
+                _ => SequenceOrigin::None,
+            };
+        }
+        if let Some(tick_size) = storage
+            .get_metadata("tick_size")?
+            .and_then(|v| v.parse().ok())
+        {
+            storage.tick_size = tick_size;
+        }
+
+        info!(
+            path = %db_path,
+            seq_scope = ?storage.seq_scope,
+            seq_origin = ?storage.seq_origin,
+            "L2 storage opened read-only"
+        );
+
+        Ok(storage)
+    }
+
     /// Set a metadata value.
     pub fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
         let conn = self.conn.lock();
@@ -1173,6 +1222,23 @@ mod tests {
         assert!(!metadata.is_production_grade()); // Synthetic sequences
     }
 
+    #[test]
+    fn test_storage_open_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("l2.db");
+        let path = path.to_str().unwrap();
+        {
+            let storage = L2Storage::open(path, SequenceScope::PerMarketSide, SequenceOrigin::Exchange).unwrap();
+            storage.store_snapshot(&make_test_snapshot(1, 1000000000)).unwrap();
+        }
+
+        let storage = L2Storage::open_read_only(path).unwrap();
+        assert_eq!(storage.seq_scope, SequenceScope::PerMarketSide);
+        assert_eq!(storage.seq_origin, SequenceOrigin::Exchange);
+        assert_eq!(storage.load_snapshots("token1", 0, i64::MAX as Nanos).unwrap().len(), 1);
+        assert!(storage.set_metadata("seq_origin", "None").is_err());
+    }
+
     #[test]
     fn test_storage_fingerprint() {
         let storage = L2Storage::open_memory(SequenceScope::PerMarket, SequenceOrigin::SyntheticFromArrival).unwrap();
@@ -2,10 +2,18 @@
 //!
 //! Configurable latency distributions with jitter, tail spikes, and queue position modeling.
 //! Supports deterministic replay via seeded RNG.
+//!
+//! Besides the parametric presets, `LatencyDistribution::Empirical` samples from a
+//! histogram fitted to live measurements (see `live_latency`).
+//!
+//! `LatencyConfig::regimes` makes venue-path latency depend on simulated time and
+//! load (window rollovers, delta bursts, belief volatility; see `latency_regime`).
 
 use crate::backtest_v2::clock::Nanos;
-use rand::rngs::StdRng;
+use crate::backtest_v2::events::TimestampedEvent;
+use crate::backtest_v2::latency_regime::{ActiveRegimes, LatencyRegimeConfig, LatencyRegimeState};
 use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
@@ -51,6 +59,9 @@ pub enum LatencyDistribution {
         scale_ns: f64,
         max_ns: Nanos,
     },
+
+    /// Empirical histogram (e.g. fitted from recorded live latencies).
+    Empirical { histogram: EmpiricalLatency },
 }
 
 impl Default for LatencyDistribution {
@@ -66,7 +77,7 @@ impl Default for LatencyDistribution {
 
 impl LatencyDistribution {
     /// Sample a latency from this distribution.
-    pub fn sample(&self, rng: &mut StdRng) -> Nanos {
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Nanos {
         match self {
             Self::Fixed { latency_ns } => *latency_ns,
 
@@ -113,6 +124,8 @@ impl LatencyDistribution {
                 let sample = sample_gamma(rng, *shape, *scale_ns);
                 (sample as Nanos).clamp(0, *max_ns)
             }
+
+            Self::Empirical { histogram } => histogram.sample(rng),
         }
     }
 
@@ -161,8 +174,155 @@ impl LatencyDistribution {
     }
 }
 
+/// Empirical latency histogram.
+///
+/// Bucket `i` covers `[bucket_edges_ns[i], bucket_edges_ns[i + 1])`. Samples pick a
+/// bucket in proportion to `counts`, then a uniform point inside it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmpiricalLatency {
+    /// Bucket edges (ascending, `counts.len() + 1` entries).
+    pub bucket_edges_ns: Vec<Nanos>,
+    /// Observations per bucket.
+    pub counts: Vec<u64>,
+}
+
+impl EmpiricalLatency {
+    /// Fit a histogram with `buckets` log-spaced buckets to raw samples.
+    /// Returns `None` if there are no non-negative samples.
+    pub fn from_samples_ns(samples: &[Nanos], buckets: usize) -> Option<Self> {
+        let mut samples: Vec<Nanos> = samples.iter().copied().filter(|s| *s >= 0).collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let min = samples[0];
+        let max = samples[samples.len() - 1];
+
+        // Log-spaced edges from min to max (inclusive of max).
+        let lo = min.max(1) as f64;
+        let hi = (max + 1) as f64;
+        let n = buckets.max(1);
+        let mut edges = vec![min];
+        for i in 1..n {
+            let edge = (lo * (hi / lo).powf(i as f64 / n as f64)) as Nanos;
+            if edge > *edges.last().unwrap() && edge <= max {
+                edges.push(edge);
+            }
+        }
+        edges.push(max + 1);
+
+        let counts = edges
+            .windows(2)
+            .map(|w| {
+                let start = samples.partition_point(|s| *s < w[0]);
+                let end = samples.partition_point(|s| *s < w[1]);
+                (end - start) as u64
+            })
+            .collect();
+
+        Some(Self {
+            bucket_edges_ns: edges,
+            counts,
+        })
+    }
+
+    /// Build from histogram buckets given as ascending `(upper_bound_us, count)`
+    /// pairs, where bucket `i` covers `(upper[i - 1], upper[i]]` and the first
+    /// bucket starts at 0. Returns `None` if all counts are zero.
+    pub fn from_bucket_counts_us(buckets: &[(u64, u64)]) -> Option<Self> {
+        let mut edges = Vec::with_capacity(buckets.len() + 1);
+        let mut counts = Vec::with_capacity(buckets.len());
+        let mut lower_ns: Nanos = 0;
+        for &(upper_us, count) in buckets {
+            let upper_ns = (upper_us.min(i64::MAX as u64 / NS_PER_US as u64) as Nanos) * NS_PER_US;
+            if upper_ns < lower_ns {
+                continue;
+            }
+            if count > 0 {
+                if edges.last() != Some(&lower_ns) {
+                    if !counts.is_empty() {
+                        // Gap of empty buckets between populated ones.
+                        counts.push(0);
+                    }
+                    edges.push(lower_ns);
+                }
+                // `(lower, upper]` in us maps to `[lower + 1ns, upper + 1ns)`.
+                counts.push(count);
+                edges.push(upper_ns + 1);
+            }
+            lower_ns = upper_ns + 1;
+        }
+        if counts.is_empty() {
+            return None;
+        }
+        Some(Self {
+            bucket_edges_ns: edges,
+            counts,
+        })
+    }
+
+    /// Total number of observations.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Mean latency (bucket midpoints).
+    pub fn mean_ns(&self) -> Nanos {
+        let total = self.total();
+        if total == 0 {
+            return 0;
+        }
+        let sum: f64 = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                c as f64 * (self.bucket_edges_ns[i] + self.bucket_edges_ns[i + 1]) as f64 / 2.0
+            })
+            .sum();
+        (sum / total as f64) as Nanos
+    }
+
+    /// Same shape with every edge multiplied by `factor` (e.g. to take one leg
+    /// of a round trip).
+    pub fn scaled(&self, factor: f64) -> Self {
+        let mut edges: Vec<Nanos> = self
+            .bucket_edges_ns
+            .iter()
+            .map(|e| (*e as f64 * factor.max(0.0)) as Nanos)
+            .collect();
+        for i in 1..edges.len() {
+            if edges[i] <= edges[i - 1] {
+                edges[i] = edges[i - 1] + 1;
+            }
+        }
+        Self {
+            bucket_edges_ns: edges,
+            counts: self.counts.clone(),
+        }
+    }
+
+    /// Draw a latency.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Nanos {
+        let total = self.total();
+        if total == 0 {
+            return 0;
+        }
+        let mut target = rng.gen_range(0..total);
+        for (i, &count) in self.counts.iter().enumerate() {
+            if target < count {
+                let lo = self.bucket_edges_ns[i];
+                let hi = self.bucket_edges_ns[i + 1];
+                return if hi > lo { rng.gen_range(lo..hi) } else { lo };
+            }
+            target -= count;
+        }
+        self.bucket_edges_ns.last().copied().unwrap_or(0)
+    }
+}
+
 /// Sample from normal distribution using Box-Muller transform.
-fn sample_normal(rng: &mut StdRng, mean: f64, std: f64) -> f64 {
+fn sample_normal<R: Rng + ?Sized>(rng: &mut R, mean: f64, std: f64) -> f64 {
     let u1: f64 = rng.gen();
     let u2: f64 = rng.gen();
     let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
@@ -170,7 +330,7 @@ fn sample_normal(rng: &mut StdRng, mean: f64, std: f64) -> f64 {
 }
 
 /// Sample from gamma distribution using Marsaglia and Tsang's method.
-fn sample_gamma(rng: &mut StdRng, shape: f64, scale: f64) -> f64 {
+fn sample_gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64, scale: f64) -> f64 {
     if shape < 1.0 {
         // Use Ahrens-Dieter method for shape < 1
         let u: f64 = rng.gen();
@@ -209,6 +369,9 @@ pub struct LatencyConfig {
     pub cancel_process: LatencyDistribution,
     /// Fill report latency (exchange -> strategy).
     pub fill_report: LatencyDistribution,
+    /// Time/load-dependent stress on the venue path (none = i.i.d.).
+    #[serde(default)]
+    pub regimes: LatencyRegimeConfig,
 }
 
 impl Default for LatencyConfig {
@@ -232,6 +395,7 @@ impl Default for LatencyConfig {
             fill_report: LatencyDistribution::Fixed {
                 latency_ns: 100 * NS_PER_US,
             },
+            regimes: LatencyRegimeConfig::none(),
         }
     }
 }
@@ -254,6 +418,7 @@ impl LatencyConfig {
                 max_ns: 3 * NS_PER_MS,
             },
             fill_report: LatencyDistribution::market_data_realistic(),
+            regimes: LatencyRegimeConfig::none(),
         }
     }
 
@@ -266,9 +431,23 @@ impl LatencyConfig {
             venue_process: LatencyDistribution::Fixed { latency_ns: 0 },
             cancel_process: LatencyDistribution::Fixed { latency_ns: 0 },
             fill_report: LatencyDistribution::Fixed { latency_ns: 0 },
+            regimes: LatencyRegimeConfig::none(),
         }
     }
 
+    /// Hash of every leg and regime, for run fingerprinting.
+    pub fn fingerprint_hash(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        // Plain data without maps, so the JSON form is canonical.
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(self)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Get the expected cancel latency (for race condition checks).
     /// Returns the mean/fixed value depending on distribution type.
     pub fn cancel_latency_ns(&self) -> Nanos {
@@ -280,6 +459,7 @@ impl LatencyConfig {
             LatencyDistribution::Exponential { mean_ns, .. } => *mean_ns,
             LatencyDistribution::WithTailSpikes { .. } => 150 * NS_PER_US, // Default estimate
             LatencyDistribution::Gamma { shape, scale_ns, .. } => (shape * scale_ns) as Nanos,
+            LatencyDistribution::Empirical { histogram } => histogram.mean_ns(),
         }
     }
     
@@ -295,6 +475,7 @@ impl LatencyConfig {
             LatencyDistribution::Exponential { mean_ns, .. } => *mean_ns,
             LatencyDistribution::WithTailSpikes { .. } => 200 * NS_PER_US,
             LatencyDistribution::Gamma { shape, scale_ns, .. } => (shape * scale_ns) as Nanos,
+            LatencyDistribution::Empirical { histogram } => histogram.mean_ns(),
         };
         let venue_process = match &self.venue_process {
             LatencyDistribution::Fixed { latency_ns } => *latency_ns,
@@ -304,21 +485,29 @@ impl LatencyConfig {
             LatencyDistribution::Exponential { mean_ns, .. } => *mean_ns,
             LatencyDistribution::WithTailSpikes { .. } => 100 * NS_PER_US,
             LatencyDistribution::Gamma { shape, scale_ns, .. } => (shape * scale_ns) as Nanos,
+            LatencyDistribution::Empirical { histogram } => histogram.mean_ns(),
         };
         order_send + venue_process
     }
 }
 
 /// Latency sampler with seeded RNG for deterministic replay.
+///
+/// The RNG is ChaCha12 (the `StdRng` algorithm) held by its concrete type so
+/// its position can be checkpointed.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LatencySampler {
     config: LatencyConfig,
-    rng: StdRng,
+    rng: ChaCha12Rng,
+    /// Observed time/load for `config.regimes`.
+    #[serde(default)]
+    regime: LatencyRegimeState,
     /// Statistics
     pub stats: LatencyStats,
 }
 
 /// Latency statistics.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LatencyStats {
     pub market_data_samples: u64,
     pub market_data_sum_ns: i64,
@@ -333,6 +522,9 @@ pub struct LatencyStats {
     pub cancel_sum_ns: i64,
     pub fill_report_samples: u64,
     pub fill_report_sum_ns: i64,
+    /// Venue-path samples drawn while at least one regime was active.
+    #[serde(default)]
+    pub stressed_samples: u64,
 }
 
 impl LatencyStats {
@@ -358,11 +550,61 @@ impl LatencySampler {
     pub fn new(config: LatencyConfig, seed: u64) -> Self {
         Self {
             config,
-            rng: StdRng::seed_from_u64(seed),
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            regime: LatencyRegimeState::default(),
             stats: LatencyStats::default(),
         }
     }
 
+    /// Advance simulated time for the latency regimes.
+    pub fn set_time(&mut self, now: Nanos) {
+        self.regime.set_time(now);
+    }
+
+    /// Observe a market event (window rollover and delta burst regimes).
+    pub fn observe_event(&mut self, event: &TimestampedEvent) {
+        self.regime.observe_event(&self.config.regimes, event);
+    }
+
+    /// Observe a market's mid price (belief volatility regime).
+    pub fn observe_mid(&mut self, token_id: &str, mid: f64, ts: Nanos) {
+        self.regime
+            .observe_mid(&self.config.regimes, token_id, mid, ts);
+    }
+
+    /// Regimes active at the current simulated time.
+    pub fn active_regimes(&self) -> ActiveRegimes {
+        self.regime.active(&self.config.regimes)
+    }
+
+    /// Apply active regime stress to a venue-path sample.
+    ///
+    /// Draws from the RNG only while a regime with a spike is active, so runs
+    /// without regimes keep the i.i.d. sample stream.
+    fn stress(&mut self, latency: Nanos) -> Nanos {
+        let regimes = &self.config.regimes;
+        if regimes.is_empty() {
+            return latency;
+        }
+        let active = self.regime.active(regimes);
+        if !active.any() {
+            return latency;
+        }
+        self.stats.stressed_samples += 1;
+
+        let stresses = regimes.active_stresses(active);
+        let multiplier: f64 = stresses.iter().map(|s| s.multiplier.max(0.0)).product();
+        let mut stressed = (latency as f64 * multiplier) as Nanos;
+        for stress in stresses {
+            if let Some(spike) = &stress.spike {
+                if stress.spike_prob > 0.0 && self.rng.gen::<f64>() < stress.spike_prob {
+                    stressed = stressed.max(spike.sample(&mut self.rng));
+                }
+            }
+        }
+        stressed
+    }
+
     /// Sample market data latency.
     pub fn sample_market_data(&mut self) -> Nanos {
         let latency = self.config.market_data.sample(&mut self.rng);
@@ -383,6 +625,7 @@ impl LatencySampler {
     /// Sample order send latency.
     pub fn sample_order_send(&mut self) -> Nanos {
         let latency = self.config.order_send.sample(&mut self.rng);
+        let latency = self.stress(latency);
         self.stats.order_send_samples += 1;
         self.stats.order_send_sum_ns += latency;
         latency
@@ -391,6 +634,7 @@ impl LatencySampler {
     /// Sample venue processing latency.
     pub fn sample_venue_process(&mut self) -> Nanos {
         let latency = self.config.venue_process.sample(&mut self.rng);
+        let latency = self.stress(latency);
         self.stats.venue_process_samples += 1;
         self.stats.venue_process_sum_ns += latency;
         latency
@@ -399,6 +643,7 @@ impl LatencySampler {
     /// Sample cancel processing latency.
     pub fn sample_cancel(&mut self) -> Nanos {
         let latency = self.config.cancel_process.sample(&mut self.rng);
+        let latency = self.stress(latency);
         self.stats.cancel_samples += 1;
         self.stats.cancel_sum_ns += latency;
         latency
@@ -407,6 +652,7 @@ impl LatencySampler {
     /// Sample fill report latency.
     pub fn sample_fill_report(&mut self) -> Nanos {
         let latency = self.config.fill_report.sample(&mut self.rng);
+        let latency = self.stress(latency);
         self.stats.fill_report_samples += 1;
         self.stats.fill_report_sum_ns += latency;
         latency
@@ -427,11 +673,11 @@ impl LatencySampler {
 
     /// Reseed RNG (for reproducibility).
     pub fn reseed(&mut self, seed: u64) {
-        self.rng = StdRng::seed_from_u64(seed);
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
     }
 
     /// Access to RNG for other uses.
-    pub fn rng(&mut self) -> &mut StdRng {
+    pub fn rng(&mut self) -> &mut ChaCha12Rng {
         &mut self.rng
     }
 }
@@ -439,6 +685,7 @@ impl LatencySampler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
 
     #[test]
     fn test_fixed_latency() {
@@ -520,6 +767,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sampler_stream_matches_std_rng() {
+        // Existing run fingerprints were produced with StdRng; the sampler's
+        // concrete ChaCha12 RNG must draw the identical stream.
+        let config = LatencyConfig::realistic();
+        let mut sampler = LatencySampler::new(config.clone(), 7);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            assert_eq!(sampler.sample_order_send(), config.order_send.sample(&mut rng));
+        }
+    }
+
+    #[test]
+    fn test_sampler_roundtrip_preserves_rng_position() {
+        let mut sampler = LatencySampler::new(LatencyConfig::realistic(), 11);
+        for _ in 0..37 {
+            sampler.sample_tick_to_trade();
+        }
+        let bytes = rmp_serde::to_vec_named(&sampler).unwrap();
+        let mut restored: LatencySampler = rmp_serde::from_slice(&bytes).unwrap();
+        for _ in 0..50 {
+            assert_eq!(restored.sample_tick_to_trade(), sampler.sample_tick_to_trade());
+        }
+        assert_eq!(restored.stats.venue_process_samples, sampler.stats.venue_process_samples);
+    }
+
     #[test]
     fn test_realistic_config() {
         let config = LatencyConfig::realistic();
@@ -557,4 +830,76 @@ mod tests {
         let avg = sum as f64 / n as f64;
         assert!((avg - 1000.0).abs() < 200.0);
     }
+
+    #[test]
+    fn test_empirical_from_samples() {
+        let samples: Vec<Nanos> = (1..=1000).map(|i| i * NS_PER_US).collect();
+        let hist = EmpiricalLatency::from_samples_ns(&samples, 20).unwrap();
+        assert_eq!(hist.total(), 1000);
+        assert_eq!(hist.bucket_edges_ns.len(), hist.counts.len() + 1);
+
+        let dist = LatencyDistribution::Empirical { histogram: hist };
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 10000;
+        let mut sum = 0i64;
+        for _ in 0..n {
+            let sample = dist.sample(&mut rng);
+            assert!((NS_PER_US..=1000 * NS_PER_US).contains(&sample));
+            sum += sample;
+        }
+
+        // Uniform 1..1000us has mean ~500us
+        let avg = sum as f64 / n as f64;
+        assert!((avg - 500.0 * NS_PER_US as f64).abs() < 50.0 * NS_PER_US as f64);
+        assert!(EmpiricalLatency::from_samples_ns(&[], 20).is_none());
+    }
+
+    #[test]
+    fn test_empirical_from_bucket_counts() {
+        // (upper_us, count): 10 in (0, 100], none in (100, 200], 10 in (200, 500]
+        let hist =
+            EmpiricalLatency::from_bucket_counts_us(&[(100, 10), (200, 0), (500, 10), (1000, 0)])
+                .unwrap();
+        assert_eq!(hist.counts, vec![10, 0, 10]);
+        assert_eq!(hist.total(), 20);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            let sample = hist.sample(&mut rng);
+            assert!(sample <= 100 * NS_PER_US || sample > 200 * NS_PER_US);
+            assert!(sample <= 500 * NS_PER_US);
+        }
+
+        let half = hist.scaled(0.5);
+        assert_eq!(half.counts, hist.counts);
+        assert!((half.mean_ns() - hist.mean_ns() / 2).abs() <= 1);
+    }
+
+    #[test]
+    fn test_rollover_regime_stresses_venue_path_only() {
+        use crate::backtest_v2::clock::NANOS_PER_SEC;
+        use crate::backtest_v2::latency_regime::{LatencyStress, RolloverRegime};
+        use crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
+
+        let mut config = LatencyConfig::default();
+        config.regimes.rollover = Some(RolloverRegime {
+            before_close_ns: 30 * NANOS_PER_SEC,
+            after_open_ns: 0,
+            stress: LatencyStress::multiplier(3.0),
+        });
+        let mut sampler = LatencySampler::new(config, 1);
+
+        sampler.set_time(WINDOW_DURATION_NS / 2);
+        assert_eq!(sampler.sample_order_send(), 200 * NS_PER_US);
+
+        sampler.set_time(WINDOW_DURATION_NS - 5 * NANOS_PER_SEC);
+        assert!(sampler.active_regimes().rollover);
+        assert_eq!(sampler.sample_order_send(), 600 * NS_PER_US);
+        assert_eq!(sampler.sample_cancel(), 450 * NS_PER_US);
+        assert_eq!(sampler.sample_market_data(), 100 * NS_PER_US);
+        assert_eq!(sampler.stats.stressed_samples, 2);
+
+        sampler.set_time(WINDOW_DURATION_NS + NANOS_PER_SEC);
+        assert_eq!(sampler.sample_order_send(), 200 * NS_PER_US);
+    }
 }
@@ -0,0 +1,390 @@
+//! Regime-Dependent Latency
+//!
+//! An i.i.d. latency sampler hides the correlation that matters most: venue
+//! acks slow down exactly when strategies want to trade. `LatencyRegimeConfig`
+//! makes venue-path latency depend on simulated time and load:
+//!
+//! 1. **Window rollover**: close to a 15M window close or open
+//! 2. **Delta bursts**: many `L2BookDelta`/`L2Delta` events in a short window
+//! 3. **High belief volatility**: realized log-odds volatility (`sigma_b`, same
+//!    definition as `vault::belief_vol`) of any observed market above a threshold
+//!
+//! Each active regime applies a `LatencyStress`: a multiplier, plus an optional
+//! tail spike that replaces the sample with `max(sample, spike)`. Stresses apply
+//! to the venue path (order send, venue process, cancel, fill report); market
+//! data and decision latency stay i.i.d.
+//!
+//! With no regimes configured the sampler draws exactly the same RNG stream as
+//! the plain i.i.d. sampler.
+
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
+use crate::backtest_v2::events::{Event, TimestampedEvent};
+use crate::backtest_v2::latency::{LatencyDistribution, NS_PER_MS};
+use crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// How an active regime distorts a latency sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStress {
+    /// Multiplier applied to the sampled latency.
+    pub multiplier: f64,
+    /// Probability of a tail spike while the regime is active.
+    pub spike_prob: f64,
+    /// Spike distribution; the sample becomes `max(sample, spike)`.
+    pub spike: Option<LatencyDistribution>,
+}
+
+impl LatencyStress {
+    /// Scale latency by `multiplier`, no extra spikes.
+    pub fn multiplier(multiplier: f64) -> Self {
+        Self {
+            multiplier,
+            spike_prob: 0.0,
+            spike: None,
+        }
+    }
+
+    /// Add a tail spike drawn with probability `spike_prob`.
+    pub fn with_spike(mut self, spike_prob: f64, spike: LatencyDistribution) -> Self {
+        self.spike_prob = spike_prob;
+        self.spike = Some(spike);
+        self
+    }
+}
+
+/// Heavier latency around 15M window rollovers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolloverRegime {
+    /// Active this long before a window closes.
+    pub before_close_ns: Nanos,
+    /// Active this long after a window opens.
+    pub after_open_ns: Nanos,
+    pub stress: LatencyStress,
+}
+
+/// Heavier latency during bursts of book delta traffic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaBurstRegime {
+    /// Lookback for counting deltas.
+    pub window_ns: Nanos,
+    /// Active when at least this many deltas arrived within `window_ns`.
+    pub min_deltas: usize,
+    pub stress: LatencyStress,
+}
+
+/// Heavier latency when belief volatility is high.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolatilityRegime {
+    /// Lookback for realized log-odds volatility.
+    pub lookback_ns: Nanos,
+    /// Active when any market's annualized `sigma_b` exceeds this.
+    pub sigma_b_threshold: f64,
+    pub stress: LatencyStress,
+}
+
+/// Time- and load-dependent latency regimes (all optional).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyRegimeConfig {
+    #[serde(default)]
+    pub rollover: Option<RolloverRegime>,
+    #[serde(default)]
+    pub delta_burst: Option<DeltaBurstRegime>,
+    #[serde(default)]
+    pub volatility: Option<VolatilityRegime>,
+}
+
+impl LatencyRegimeConfig {
+    /// No regimes (i.i.d. latency).
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Whether any regime is configured.
+    pub fn is_empty(&self) -> bool {
+        self.rollover.is_none() && self.delta_burst.is_none() && self.volatility.is_none()
+    }
+
+    /// Stresses of the active regimes.
+    pub fn active_stresses(&self, active: ActiveRegimes) -> Vec<&LatencyStress> {
+        let mut stresses = Vec::new();
+        if let Some(r) = self.rollover.as_ref().filter(|_| active.rollover) {
+            stresses.push(&r.stress);
+        }
+        if let Some(b) = self.delta_burst.as_ref().filter(|_| active.delta_burst) {
+            stresses.push(&b.stress);
+        }
+        if let Some(v) = self.volatility.as_ref().filter(|_| active.volatility) {
+            stresses.push(&v.stress);
+        }
+        stresses
+    }
+
+    /// Polymarket 15M Up/Down: ack latency degrades in the last 30s of a window
+    /// and the first 5s of the next, under delta bursts, and in fast markets.
+    pub fn polymarket_15m() -> Self {
+        Self {
+            rollover: Some(RolloverRegime {
+                before_close_ns: 30 * NANOS_PER_SEC,
+                after_open_ns: 5 * NANOS_PER_SEC,
+                stress: LatencyStress::multiplier(2.0).with_spike(
+                    0.05,
+                    LatencyDistribution::Uniform {
+                        min_ns: 50 * NS_PER_MS,
+                        max_ns: 500 * NS_PER_MS,
+                    },
+                ),
+            }),
+            delta_burst: Some(DeltaBurstRegime {
+                window_ns: NANOS_PER_SEC,
+                min_deltas: 200,
+                stress: LatencyStress::multiplier(1.5),
+            }),
+            volatility: Some(VolatilityRegime {
+                lookback_ns: 60 * NANOS_PER_SEC,
+                sigma_b_threshold: 200.0,
+                stress: LatencyStress::multiplier(1.5).with_spike(
+                    0.02,
+                    LatencyDistribution::Uniform {
+                        min_ns: 20 * NS_PER_MS,
+                        max_ns: 200 * NS_PER_MS,
+                    },
+                ),
+            }),
+        }
+    }
+}
+
+// =============================================================================
+// STATE
+// =============================================================================
+
+/// Regimes active at a point in simulated time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveRegimes {
+    pub rollover: bool,
+    pub delta_burst: bool,
+    pub volatility: bool,
+}
+
+impl ActiveRegimes {
+    pub fn any(&self) -> bool {
+        self.rollover || self.delta_burst || self.volatility
+    }
+}
+
+/// Observed time/load the regimes are evaluated against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyRegimeState {
+    now: Nanos,
+    /// Arrival times of recent book deltas.
+    delta_times: VecDeque<Nanos>,
+    /// token -> last observed log-odds.
+    last_log_odds: HashMap<String, f64>,
+    /// token -> (time, squared log-odds increment) within the lookback.
+    sq_increments: HashMap<String, VecDeque<(Nanos, f64)>>,
+}
+
+impl LatencyRegimeState {
+    /// Current simulated time.
+    pub fn now(&self) -> Nanos {
+        self.now
+    }
+
+    /// Advance simulated time.
+    pub fn set_time(&mut self, now: Nanos) {
+        self.now = self.now.max(now);
+    }
+
+    /// Observe a market event (advances time, counts book deltas).
+    pub fn observe_event(&mut self, config: &LatencyRegimeConfig, event: &TimestampedEvent) {
+        self.set_time(event.time);
+        let Some(burst) = &config.delta_burst else {
+            return;
+        };
+        if matches!(
+            event.event,
+            Event::L2BookDelta { .. } | Event::L2Delta { .. }
+        ) {
+            self.delta_times.push_back(event.time);
+        }
+        let cutoff = self.now - burst.window_ns;
+        while self.delta_times.front().is_some_and(|t| *t <= cutoff) {
+            self.delta_times.pop_front();
+        }
+    }
+
+    /// Observe a market's mid price (probability) for the volatility regime.
+    pub fn observe_mid(
+        &mut self,
+        config: &LatencyRegimeConfig,
+        token_id: &str,
+        mid: f64,
+        ts: Nanos,
+    ) {
+        let Some(vol) = &config.volatility else {
+            return;
+        };
+        if !mid.is_finite() {
+            return;
+        }
+        self.set_time(ts);
+        let x = logit(mid);
+        let prev = self.last_log_odds.insert(token_id.to_string(), x);
+        let increments = self.sq_increments.entry(token_id.to_string()).or_default();
+        if let Some(prev) = prev {
+            let dx = x - prev;
+            if dx != 0.0 {
+                increments.push_back((ts, dx * dx));
+            }
+        }
+        let cutoff = self.now - vol.lookback_ns;
+        while increments.front().is_some_and(|(t, _)| *t <= cutoff) {
+            increments.pop_front();
+        }
+    }
+
+    /// Annualized realized log-odds volatility of a market over `lookback_ns`.
+    pub fn sigma_b(&self, token_id: &str, lookback_ns: Nanos) -> f64 {
+        if lookback_ns <= 0 {
+            return 0.0;
+        }
+        let cutoff = self.now - lookback_ns;
+        let sum_sq: f64 = self
+            .sq_increments
+            .get(token_id)
+            .map(|inc| {
+                inc.iter()
+                    .filter(|(t, _)| *t > cutoff)
+                    .map(|(_, sq)| sq)
+                    .sum()
+            })
+            .unwrap_or(0.0);
+        let lookback_years = lookback_ns as f64 / NANOS_PER_SEC as f64 / SECONDS_PER_YEAR;
+        (sum_sq / lookback_years).sqrt()
+    }
+
+    /// Regimes active at the current time.
+    pub fn active(&self, config: &LatencyRegimeConfig) -> ActiveRegimes {
+        let rollover = config.rollover.as_ref().is_some_and(|r| {
+            let offset = self.now.rem_euclid(WINDOW_DURATION_NS);
+            offset < r.after_open_ns || WINDOW_DURATION_NS - offset <= r.before_close_ns
+        });
+        let delta_burst = config.delta_burst.as_ref().is_some_and(|b| {
+            let cutoff = self.now - b.window_ns;
+            self.delta_times.iter().filter(|t| **t > cutoff).count() >= b.min_deltas
+        });
+        let volatility = config.volatility.as_ref().is_some_and(|v| {
+            self.sq_increments
+                .keys()
+                .any(|token| self.sigma_b(token, v.lookback_ns) > v.sigma_b_threshold)
+        });
+        ActiveRegimes {
+            rollover,
+            delta_burst,
+            volatility,
+        }
+    }
+}
+
+/// Log-odds of a probability (clamped like `vault::belief_vol::logit`).
+fn logit(p: f64) -> f64 {
+    let p = p.clamp(0.0001, 0.9999);
+    (p / (1.0 - p)).ln()
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::Side;
+
+    fn delta(t: Nanos) -> TimestampedEvent {
+        TimestampedEvent::new(
+            t,
+            0,
+            Event::L2BookDelta {
+                token_id: "tok".to_string(),
+                side: Side::Buy,
+                price: 0.5,
+                new_size: 10.0,
+                seq_hash: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_rollover_regime() {
+        let config = LatencyRegimeConfig::polymarket_15m();
+        let mut state = LatencyRegimeState::default();
+
+        state.set_time(WINDOW_DURATION_NS + 60 * NANOS_PER_SEC);
+        assert!(!state.active(&config).rollover);
+
+        state.set_time(2 * WINDOW_DURATION_NS - 10 * NANOS_PER_SEC);
+        assert!(state.active(&config).rollover);
+
+        state.set_time(2 * WINDOW_DURATION_NS + NANOS_PER_SEC);
+        assert!(state.active(&config).rollover);
+    }
+
+    #[test]
+    fn test_delta_burst_regime() {
+        let config = LatencyRegimeConfig {
+            delta_burst: Some(DeltaBurstRegime {
+                window_ns: NANOS_PER_SEC,
+                min_deltas: 10,
+                stress: LatencyStress::multiplier(2.0),
+            }),
+            ..Default::default()
+        };
+        let mut state = LatencyRegimeState::default();
+        let start = 100 * NANOS_PER_SEC;
+
+        for i in 0..10 {
+            state.observe_event(&config, &delta(start + i * 10 * NS_PER_MS));
+        }
+        assert!(state.active(&config).delta_burst);
+
+        // Quiet for two seconds: the burst has passed.
+        state.set_time(start + 2 * NANOS_PER_SEC);
+        assert!(!state.active(&config).delta_burst);
+    }
+
+    #[test]
+    fn test_volatility_regime() {
+        let config = LatencyRegimeConfig {
+            volatility: Some(VolatilityRegime {
+                lookback_ns: 60 * NANOS_PER_SEC,
+                sigma_b_threshold: 50.0,
+                stress: LatencyStress::multiplier(2.0),
+            }),
+            ..Default::default()
+        };
+        let mut state = LatencyRegimeState::default();
+        let start = 100 * NANOS_PER_SEC;
+
+        // Flat market
+        for i in 0..30 {
+            state.observe_mid(&config, "tok", 0.50, start + i * NANOS_PER_SEC);
+        }
+        assert!(!state.active(&config).volatility);
+
+        // Price whipsaws 0.40 <-> 0.60 every second
+        for i in 30..60 {
+            let mid = if i % 2 == 0 { 0.40 } else { 0.60 };
+            state.observe_mid(&config, "tok", mid, start + i * NANOS_PER_SEC);
+        }
+        assert!(state.sigma_b("tok", 60 * NANOS_PER_SEC) > 50.0);
+        assert!(state.active(&config).volatility);
+    }
+}
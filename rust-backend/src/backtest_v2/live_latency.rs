@@ -0,0 +1,143 @@
+//! Live Latency Profiles
+//!
+//! Latency measured on the live trading path, exported from a running system
+//! (`GET /api/admin/latency/export`) and loaded into a `LatencyConfig` so the
+//! backtest samples our actual AWS path instead of hand-picked presets.
+//!
+//! # Sources
+//!
+//! - `decision`: FAST15M trade spans, price received -> order submitted
+//! - `send_ack` / `send_fill` / `cancel_ack`: venue round trips from the
+//!   performance venue tracker
+//!
+//! # Mapping to `LatencyConfig`
+//!
+//! Venue measurements are round trips; the backtest models legs. A round trip
+//! is split with `one_way_fraction` (outbound share):
+//!
+//! | Config component | Source                                                    |
+//! |------------------|-----------------------------------------------------------|
+//! | `decision`       | `decision`                                                |
+//! | `order_send`     | `send_ack` x `one_way_fraction`                           |
+//! | `venue_process`  | 0 (already inside the measured round trip)                |
+//! | `fill_report`    | `send_fill` (else `send_ack`) x (1 - `one_way_fraction`)  |
+//! | `cancel_process` | `cancel_ack` x `one_way_fraction`                         |
+//!
+//! Components without measurements keep the base configuration.
+//! `market_data` is never replaced.
+
+use crate::backtest_v2::latency::{EmpiricalLatency, LatencyConfig, LatencyDistribution};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+fn default_one_way_fraction() -> f64 {
+    0.5
+}
+
+/// Empirical latency histograms captured from the live system.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveLatencyProfile {
+    /// Capture time (unix seconds).
+    pub captured_at: i64,
+    /// Venue the round trips were measured against.
+    pub venue: String,
+    /// Price received -> order submitted.
+    pub decision: Option<EmpiricalLatency>,
+    /// Order send -> ack round trip.
+    pub send_ack: Option<EmpiricalLatency>,
+    /// Order send -> fill round trip.
+    pub send_fill: Option<EmpiricalLatency>,
+    /// Cancel send -> ack round trip.
+    pub cancel_ack: Option<EmpiricalLatency>,
+    /// Share of a round trip spent on the outbound leg.
+    #[serde(default = "default_one_way_fraction")]
+    pub one_way_fraction: f64,
+}
+
+impl LiveLatencyProfile {
+    /// Load a profile exported as JSON.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read latency profile {}: {}", path.display(), e))?;
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid latency profile {}: {}", path.display(), e))
+    }
+
+    /// Write the profile as pretty JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize latency profile: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write latency profile {}: {}", path.display(), e))
+    }
+
+    /// Replace the measured components of `base` with empirical distributions.
+    pub fn apply_to(&self, base: &LatencyConfig) -> LatencyConfig {
+        let out_leg = self.one_way_fraction.clamp(0.0, 1.0);
+        let back_leg = 1.0 - out_leg;
+        let empirical = |h: EmpiricalLatency| LatencyDistribution::Empirical { histogram: h };
+
+        let mut config = base.clone();
+        if let Some(h) = &self.decision {
+            config.decision = empirical(h.clone());
+        }
+        if let Some(h) = &self.send_ack {
+            config.order_send = empirical(h.scaled(out_leg));
+            config.venue_process = LatencyDistribution::Fixed { latency_ns: 0 };
+        }
+        if let Some(h) = self.send_fill.as_ref().or(self.send_ack.as_ref()) {
+            config.fill_report = empirical(h.scaled(back_leg));
+        }
+        if let Some(h) = &self.cancel_ack {
+            config.cancel_process = empirical(h.scaled(out_leg));
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::latency::NS_PER_US;
+
+    #[test]
+    fn test_apply_profile_and_roundtrip() {
+        let round_trip =
+            EmpiricalLatency::from_bucket_counts_us(&[(1_000, 5), (2_000, 5)]).unwrap();
+        let profile = LiveLatencyProfile {
+            captured_at: 1_700_000_000,
+            venue: "polymarket".to_string(),
+            decision: EmpiricalLatency::from_samples_ns(&[50 * NS_PER_US, 80 * NS_PER_US], 4),
+            send_ack: Some(round_trip.clone()),
+            send_fill: None,
+            cancel_ack: None,
+            one_way_fraction: 0.5,
+        };
+
+        let base = LatencyConfig::default();
+        let config = profile.apply_to(&base);
+        assert!(matches!(
+            config.decision,
+            LatencyDistribution::Empirical { .. }
+        ));
+        assert!(matches!(
+            config.venue_process,
+            LatencyDistribution::Fixed { latency_ns: 0 }
+        ));
+        // Outbound + return leg add back up to the measured round trip.
+        let LatencyDistribution::Empirical { histogram: back } = &config.fill_report else {
+            panic!("fill_report should be empirical");
+        };
+        let legs = config.order_latency_ns() + back.mean_ns();
+        assert!((legs - round_trip.mean_ns()).abs() <= 2);
+        // Unmeasured components keep the base.
+        assert_eq!(config.cancel_latency_ns(), base.cancel_latency_ns());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latency.json");
+        profile.save(&path).unwrap();
+        assert_eq!(LiveLatencyProfile::load(&path).unwrap(), profile);
+    }
+}
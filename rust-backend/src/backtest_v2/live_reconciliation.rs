@@ -0,0 +1,1230 @@
+//! Live-vs-Backtest Reconciliation
+//!
+//! Quantifies simulator bias on real deployments by replaying a live session
+//! through `BacktestOrchestrator` and reconciling the two executions order by
+//! order.
+//!
+//! # Live Session Journal
+//!
+//! `LiveSessionRecorder` records every order submit, ack, reject, fill, cancel
+//! request and cancel ack of a live strategy as `run_diff::TraceRecord`s - the
+//! same canonical, fixed-point encoding (`reproducibility::CanonicalEncoder`)
+//! the orchestrator uses for its decision trace - so both sides hash and compare
+//! identically. Venue fees, which the canonical record does not carry, are kept
+//! per order alongside the trace. The journal is append-only JSON lines (a
+//! header, then one line per record), so a crash loses at most the last line.
+//!
+//! # Replay
+//!
+//! `LiveReconciler` loads the recorded market data (`unified_recorder`) for the
+//! session window plus a warm-up and tail, runs the same strategy with the same
+//! parameters, and pairs every live order with the backtest order for the same
+//! token and side submitted closest in time. Backtest orders submitted outside
+//! the live window (warm-up, tail) are ignored.
+//!
+//! # Per-Order Output
+//!
+//! Like `shadow_maker::OrderDiscrepancy`, but for taker and maker flows:
+//! fill vs no-fill, fill size, average price and first-fill timing deltas, and
+//! PnL drift. Order PnL is marked at the last recorded mid of the token (falling
+//! back to the last trade print) so filled and unfilled orders are comparable.
+//! Backtest fees are the 15M taker fee schedule on taker fills; live fees are
+//! what the venue reported.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use tracing::warn;
+
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_MILLI, NANOS_PER_SEC};
+use crate::backtest_v2::events::{Event, OrderId, Side, TimestampedEvent};
+use crate::backtest_v2::feed::VecFeed;
+use crate::backtest_v2::fees_15m::calculate_fee_15m;
+use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator};
+use crate::backtest_v2::reproducibility::{PRICE_SCALE, SIZE_SCALE};
+use crate::backtest_v2::run_diff::{DecisionTrace, TraceRecord, TraceRecordKind};
+use crate::backtest_v2::strategy::StrategyParams;
+use crate::backtest_v2::strategy_factory::make_strategy;
+use crate::backtest_v2::unified_recorder::{UnifiedReplayFeed, UnifiedStorage};
+
+/// Live session journal format version.
+pub const LIVE_SESSION_FORMAT_VERSION: u32 = 1;
+
+/// Trace records kept in memory per live session (and per replay).
+pub const LIVE_TRACE_LIMIT: usize = 1_000_000;
+
+/// Quantities below this are treated as zero.
+const QTY_EPSILON: f64 = 1e-9;
+
+// =============================================================================
+// LIVE SESSION
+// =============================================================================
+
+/// What was deployed: enough to rebuild the strategy for replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveSessionHeader {
+    pub format_version: u32,
+    /// Strategy name (see `strategy_factory::make_strategy`).
+    pub strategy_name: String,
+    pub params: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub string_params: BTreeMap<String, String>,
+    pub tokens: Vec<String>,
+    /// Wall-clock time the strategy started (ns).
+    pub started_at_ns: Nanos,
+}
+
+impl LiveSessionHeader {
+    pub fn new(
+        strategy_name: impl Into<String>,
+        params: &StrategyParams,
+        tokens: Vec<String>,
+        started_at_ns: Nanos,
+    ) -> Self {
+        Self {
+            format_version: LIVE_SESSION_FORMAT_VERSION,
+            strategy_name: strategy_name.into(),
+            params: params.params.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            string_params: params
+                .strings
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            tokens,
+            started_at_ns,
+        }
+    }
+
+    /// Parameters as passed to the strategy.
+    pub fn strategy_params(&self) -> StrategyParams {
+        let params = self.params.iter().fold(StrategyParams::new(), |p, (k, v)| {
+            p.with_param(k.clone(), *v)
+        });
+        self.string_params
+            .iter()
+            .fold(params, |p, (k, v)| p.with_string(k.clone(), v.clone()))
+    }
+}
+
+/// One journal line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JournalLine {
+    Header(LiveSessionHeader),
+    Record {
+        record: TraceRecord,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fee_usdc: Option<f64>,
+    },
+}
+
+/// A recorded live session: header, canonical trace and venue fees.
+#[derive(Debug, Clone)]
+pub struct LiveSession {
+    pub header: LiveSessionHeader,
+    pub trace: DecisionTrace,
+    /// Fees reported by the venue, per live order (USDC).
+    pub fees: BTreeMap<OrderId, f64>,
+}
+
+impl LiveSession {
+    pub fn new(header: LiveSessionHeader) -> Self {
+        Self {
+            header,
+            trace: DecisionTrace::new(LIVE_TRACE_LIMIT),
+            fees: BTreeMap::new(),
+        }
+    }
+
+    /// Session window: start time to the last recorded record.
+    pub fn window(&self) -> (Nanos, Nanos) {
+        let start = self.header.started_at_ns;
+        let end = self
+            .trace
+            .records
+            .iter()
+            .map(|r| r.sim_time_ns)
+            .max()
+            .unwrap_or(start)
+            .max(start);
+        (start, end)
+    }
+
+    fn apply(&mut self, record: TraceRecord, fee_usdc: Option<f64>) {
+        if let (Some(order_id), Some(fee)) = (record.order_id, fee_usdc) {
+            *self.fees.entry(order_id).or_insert(0.0) += fee;
+        }
+        let (event_index, time) = (record.event_index, record.sim_time_ns);
+        self.trace.push(record, event_index, time);
+    }
+
+    /// Read a session journal. A torn final line (crash mid-write) is ignored.
+    pub fn read_from(path: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let mut lines = BufReader::new(file).lines().peekable();
+
+        let header = match lines.next() {
+            Some(Ok(line)) => match serde_json::from_str::<JournalLine>(&line) {
+                Ok(JournalLine::Header(header)) => header,
+                Ok(_) => {
+                    return Err(format!(
+                        "{}: journal does not start with a header",
+                        path.display()
+                    ))
+                }
+                Err(e) => return Err(format!("{}: invalid header: {}", path.display(), e)),
+            },
+            Some(Err(e)) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+            None => return Err(format!("{}: empty journal", path.display())),
+        };
+        if header.format_version != LIVE_SESSION_FORMAT_VERSION {
+            return Err(format!(
+                "{}: unsupported journal version {} (expected {})",
+                path.display(),
+                header.format_version,
+                LIVE_SESSION_FORMAT_VERSION
+            ));
+        }
+
+        let mut session = Self::new(header);
+        let mut line_no = 1;
+        while let Some(line) = lines.next() {
+            line_no += 1;
+            let line = line.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalLine>(&line) {
+                Ok(JournalLine::Record { record, fee_usdc }) => session.apply(record, fee_usdc),
+                Ok(JournalLine::Header(_)) => {
+                    return Err(format!("{}:{}: unexpected header", path.display(), line_no))
+                }
+                Err(_) if lines.peek().is_none() => {
+                    warn!(path = %path.display(), line = line_no, "Ignoring torn final journal line");
+                }
+                Err(e) => return Err(format!("{}:{}: {}", path.display(), line_no, e)),
+            }
+        }
+        Ok(session)
+    }
+}
+
+/// Records a live strategy's decisions, orders and fills.
+///
+/// `observe_event` is called once per input event (book update, trade, timer,
+/// execution event) so records carry an event index like backtest records do.
+pub struct LiveSessionRecorder {
+    session: LiveSession,
+    events: u64,
+    journal: Option<BufWriter<std::fs::File>>,
+}
+
+impl LiveSessionRecorder {
+    /// In-memory recorder (no journal).
+    pub fn new(header: LiveSessionHeader) -> Self {
+        Self {
+            session: LiveSession::new(header),
+            events: 0,
+            journal: None,
+        }
+    }
+
+    /// Recorder that also journals to `path` (truncated if it exists).
+    pub fn create(path: &Path, header: LiveSessionHeader) -> Result<Self, String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let file = std::fs::File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut recorder = Self {
+            journal: Some(BufWriter::new(file)),
+            ..Self::new(header.clone())
+        };
+        recorder.write_line(&JournalLine::Header(header));
+        Ok(recorder)
+    }
+
+    pub fn session(&self) -> &LiveSession {
+        &self.session
+    }
+
+    pub fn into_session(self) -> LiveSession {
+        self.session
+    }
+
+    /// Count one input event.
+    pub fn observe_event(&mut self) {
+        self.events += 1;
+    }
+
+    pub fn record(&mut self, record: TraceRecord, now: Nanos) {
+        self.push(record, None, now);
+    }
+
+    /// Record a fill and the fee the venue charged for it.
+    pub fn record_fill(
+        &mut self,
+        order_id: OrderId,
+        price: f64,
+        size: f64,
+        is_maker: bool,
+        fee_usdc: f64,
+        now: Nanos,
+    ) {
+        self.push(
+            TraceRecord::fill(order_id, price, size, is_maker),
+            Some(fee_usdc),
+            now,
+        );
+    }
+
+    fn push(&mut self, mut record: TraceRecord, fee_usdc: Option<f64>, now: Nanos) {
+        record.event_index = self.events;
+        record.sim_time_ns = now;
+        record.seq = self.session.trace.total_records;
+        if self.journal.is_some() {
+            self.write_line(&JournalLine::Record {
+                record: record.clone(),
+                fee_usdc,
+            });
+        }
+        self.session.apply(record, fee_usdc);
+    }
+
+    /// Append a line and flush. Journaling stops after the first I/O error.
+    fn write_line(&mut self, line: &JournalLine) {
+        let Some(journal) = self.journal.as_mut() else {
+            return;
+        };
+        let result = serde_json::to_string(line)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                writeln!(journal, "{}", json)
+                    .and_then(|_| journal.flush())
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            warn!(error = %e, "Live session journal write failed; journaling disabled");
+            self.journal = None;
+        }
+    }
+}
+
+// =============================================================================
+// ORDER EXECUTIONS
+// =============================================================================
+
+/// How an order's fills reached the book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionFlow {
+    Taker,
+    Maker,
+    /// Filled partly as taker, partly as maker.
+    Mixed,
+    Unfilled,
+}
+
+impl ExecutionFlow {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Taker => "TAKER",
+            Self::Maker => "MAKER",
+            Self::Mixed => "MIXED",
+            Self::Unfilled => "UNFILLED",
+        }
+    }
+}
+
+/// One order's execution, folded from a decision trace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderExecution {
+    pub order_id: OrderId,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    pub submitted_at_ns: Nanos,
+    pub rejected: bool,
+    pub filled_size: f64,
+    pub maker_filled_size: f64,
+    /// Sum of price * size over fills.
+    pub filled_notional: f64,
+    pub first_fill_at_ns: Option<Nanos>,
+    pub fees: f64,
+}
+
+impl OrderExecution {
+    pub fn is_filled(&self) -> bool {
+        self.filled_size > QTY_EPSILON
+    }
+
+    pub fn avg_fill_price(&self) -> Option<f64> {
+        self.is_filled()
+            .then(|| self.filled_notional / self.filled_size)
+    }
+
+    pub fn flow(&self) -> ExecutionFlow {
+        if !self.is_filled() {
+            ExecutionFlow::Unfilled
+        } else if self.maker_filled_size <= QTY_EPSILON {
+            ExecutionFlow::Taker
+        } else if self.filled_size - self.maker_filled_size <= QTY_EPSILON {
+            ExecutionFlow::Maker
+        } else {
+            ExecutionFlow::Mixed
+        }
+    }
+
+    /// PnL of the fills marked at `mark`, net of fees.
+    pub fn pnl_at(&self, mark: f64) -> f64 {
+        let gross = match self.side {
+            Side::Buy => mark * self.filled_size - self.filled_notional,
+            Side::Sell => self.filled_notional - mark * self.filled_size,
+        };
+        gross - self.fees
+    }
+}
+
+/// Fold a trace into per-order executions, in submit order.
+///
+/// Fees are estimated from the 15M schedule on taker fills; callers with
+/// venue-reported fees overwrite them.
+pub fn collect_order_executions(trace: &DecisionTrace) -> Vec<OrderExecution> {
+    let mut orders: BTreeMap<OrderId, OrderExecution> = BTreeMap::new();
+    let mut submit_order = Vec::new();
+
+    for record in &trace.records {
+        let Some(order_id) = record.order_id else {
+            continue;
+        };
+        match record.kind {
+            TraceRecordKind::OrderSubmit => {
+                let (Some(token_id), Some(side), Some(price), Some(size)) = (
+                    record.market.clone(),
+                    record.side,
+                    record.price_ticks,
+                    record.size_shares,
+                ) else {
+                    continue;
+                };
+                submit_order.push(order_id);
+                orders.insert(
+                    order_id,
+                    OrderExecution {
+                        order_id,
+                        token_id,
+                        side,
+                        price: price as f64 / PRICE_SCALE as f64,
+                        size: size as f64 / SIZE_SCALE as f64,
+                        submitted_at_ns: record.sim_time_ns,
+                        rejected: false,
+                        filled_size: 0.0,
+                        maker_filled_size: 0.0,
+                        filled_notional: 0.0,
+                        first_fill_at_ns: None,
+                        fees: 0.0,
+                    },
+                );
+            }
+            TraceRecordKind::OrderReject => {
+                if let Some(order) = orders.get_mut(&order_id) {
+                    order.rejected = true;
+                }
+            }
+            TraceRecordKind::ReplaceAck => {
+                let (Some(order), Some(price), Some(leaves)) = (
+                    orders.get_mut(&order_id),
+                    record.price_ticks,
+                    record.size_shares,
+                ) else {
+                    continue;
+                };
+                order.price = price as f64 / PRICE_SCALE as f64;
+                order.size = order.filled_size + leaves as f64 / SIZE_SCALE as f64;
+            }
+            TraceRecordKind::Fill => {
+                let (Some(order), Some(price), Some(size)) = (
+                    orders.get_mut(&order_id),
+                    record.price_ticks,
+                    record.size_shares,
+                ) else {
+                    continue;
+                };
+                let price = price as f64 / PRICE_SCALE as f64;
+                let size = size as f64 / SIZE_SCALE as f64;
+                let is_maker = record.is_maker.unwrap_or(false);
+                order.filled_size += size;
+                order.filled_notional += price * size;
+                if is_maker {
+                    order.maker_filled_size += size;
+                } else {
+                    order.fees += calculate_fee_15m(price, size, record.sim_time_ns);
+                }
+                order.first_fill_at_ns.get_or_insert(record.sim_time_ns);
+            }
+            _ => {}
+        }
+    }
+
+    submit_order
+        .into_iter()
+        .filter_map(|id| orders.remove(&id))
+        .collect()
+}
+
+/// Mark price per token: last snapshot mid, else last trade print.
+pub fn marks_from_events(events: &[TimestampedEvent]) -> BTreeMap<String, f64> {
+    let mut mids = BTreeMap::new();
+    let mut trades = BTreeMap::new();
+    for event in events {
+        match &event.event {
+            Event::L2BookSnapshot {
+                token_id,
+                bids,
+                asks,
+                ..
+            } => {
+                let best_bid = bids.iter().map(|l| l.price).fold(f64::NAN, f64::max);
+                let best_ask = asks.iter().map(|l| l.price).fold(f64::NAN, f64::min);
+                if best_bid.is_finite() && best_ask.is_finite() {
+                    mids.insert(token_id.clone(), (best_bid + best_ask) / 2.0);
+                }
+            }
+            Event::TradePrint {
+                token_id, price, ..
+            } => {
+                trades.insert(token_id.clone(), *price);
+            }
+            _ => {}
+        }
+    }
+    for (token_id, price) in trades {
+        mids.entry(token_id).or_insert(price);
+    }
+    mids
+}
+
+// =============================================================================
+// RECONCILIATION
+// =============================================================================
+
+/// Outcome of reconciling one live order with its backtest counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReconciliationClass {
+    /// Fill occurrence, size, price and timing agree within tolerance.
+    Matched,
+    /// Live filled, backtest did not.
+    MissedFill,
+    /// Backtest filled, live did not.
+    PhantomFill,
+    /// Both filled, sizes differ.
+    SizeMismatch,
+    /// Both filled, average prices differ.
+    PriceMismatch,
+    /// Both filled, first fills differ in time.
+    TimingMismatch,
+    /// No backtest order for this live order.
+    LiveOnly,
+    /// No live order for this backtest order.
+    BacktestOnly,
+}
+
+impl ReconciliationClass {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Matched => "MATCHED",
+            Self::MissedFill => "MISSED_FILL",
+            Self::PhantomFill => "PHANTOM_FILL",
+            Self::SizeMismatch => "SIZE_MISMATCH",
+            Self::PriceMismatch => "PRICE_MISMATCH",
+            Self::TimingMismatch => "TIMING_MISMATCH",
+            Self::LiveOnly => "LIVE_ONLY",
+            Self::BacktestOnly => "BACKTEST_ONLY",
+        }
+    }
+}
+
+/// Per-order reconciliation. Deltas are backtest minus live.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderReconciliation {
+    pub token_id: String,
+    pub side: Side,
+    pub classification: ReconciliationClass,
+    /// Live flow when the live order filled, else the backtest flow.
+    pub flow: ExecutionFlow,
+    pub live: Option<OrderExecution>,
+    pub backtest: Option<OrderExecution>,
+    pub submit_time_delta_ns: Option<i64>,
+    pub fill_size_delta: f64,
+    /// Backtest fill price advantage over live (positive = backtest filled better).
+    pub fill_price_edge: Option<f64>,
+    pub first_fill_time_delta_ns: Option<i64>,
+    /// Order PnL at the token mark, net of fees (0 when the side is missing).
+    pub live_pnl: f64,
+    pub backtest_pnl: f64,
+    /// `backtest_pnl - live_pnl`.
+    pub pnl_drift: f64,
+}
+
+/// Aggregate reconciliation statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconciliationSummary {
+    pub live_orders: u64,
+    pub backtest_orders: u64,
+    pub paired_orders: u64,
+    pub live_filled: u64,
+    pub backtest_filled: u64,
+    pub by_class: BTreeMap<String, u64>,
+    /// Paired orders by flow label.
+    pub by_flow: BTreeMap<String, u64>,
+    /// Mean backtest price advantage on paired taker fills.
+    pub mean_taker_price_edge: Option<f64>,
+    /// Mean backtest price advantage on paired maker fills.
+    pub mean_maker_price_edge: Option<f64>,
+    pub mean_abs_first_fill_delta_ns: Option<f64>,
+    pub live_pnl: f64,
+    pub backtest_pnl: f64,
+    /// Backtest PnL minus live PnL over the session orders.
+    pub pnl_drift: f64,
+}
+
+/// Tolerances and replay settings.
+#[derive(Debug, Clone)]
+pub struct ReconciliationConfig {
+    /// Base config for the replay; strategy params come from the session.
+    pub backtest: BacktestConfig,
+    /// Market data loaded before the session start.
+    pub warmup_ns: Nanos,
+    /// Market data loaded after the last live record (late fills).
+    pub tail_ns: Nanos,
+    /// Maximum submit time distance for pairing live and backtest orders.
+    pub match_window_ns: Nanos,
+    /// Relative fill size difference tolerated.
+    pub size_tolerance: f64,
+    /// Average fill price difference tolerated.
+    pub price_tolerance: f64,
+    /// First fill time difference tolerated.
+    pub timing_tolerance_ns: Nanos,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            backtest: BacktestConfig {
+                decision_trace_limit: LIVE_TRACE_LIMIT,
+                ..BacktestConfig::research_mode()
+            },
+            warmup_ns: 60 * NANOS_PER_SEC,
+            tail_ns: 60 * NANOS_PER_SEC,
+            match_window_ns: 5 * NANOS_PER_SEC,
+            size_tolerance: 0.01,
+            price_tolerance: 0.005,
+            timing_tolerance_ns: 250 * NANOS_PER_MILLI,
+        }
+    }
+}
+
+/// Live-vs-backtest reconciliation report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub strategy_name: String,
+    pub tokens: Vec<String>,
+    pub window_start_ns: Nanos,
+    pub window_end_ns: Nanos,
+    /// Canonical trace hashes of the two executions.
+    pub live_trace_hash: String,
+    pub backtest_trace_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backtest_fingerprint: Option<String>,
+    pub marks: BTreeMap<String, f64>,
+    pub orders: Vec<OrderReconciliation>,
+    pub summary: ReconciliationSummary,
+}
+
+impl ReconciliationReport {
+    /// Human-readable report.
+    pub fn format_report(&self) -> String {
+        let mut out = String::new();
+        out.push_str("═══════════════════════════════════════════════════════════════════\n");
+        out.push_str("  LIVE vs BACKTEST RECONCILIATION\n");
+        out.push_str("═══════════════════════════════════════════════════════════════════\n");
+        out.push_str(&format!("  Strategy: {}\n", self.strategy_name));
+        out.push_str(&format!(
+            "  Window:   {} → {} ({:.1}s)\n",
+            self.window_start_ns,
+            self.window_end_ns,
+            (self.window_end_ns - self.window_start_ns) as f64 / NANOS_PER_SEC as f64
+        ));
+        out.push_str(&format!(
+            "  Traces:   live {}  backtest {}\n",
+            self.live_trace_hash, self.backtest_trace_hash
+        ));
+
+        let s = &self.summary;
+        out.push_str("\n  ORDERS\n");
+        out.push_str(&format!(
+            "  live {} (filled {})  backtest {} (filled {})  paired {}\n",
+            s.live_orders, s.live_filled, s.backtest_orders, s.backtest_filled, s.paired_orders
+        ));
+        for (class, count) in &s.by_class {
+            out.push_str(&format!("    {:<16} {}\n", class, count));
+        }
+
+        out.push_str("\n  BIAS (backtest − live)\n");
+        let fmt_opt = |v: Option<f64>, scale: f64, unit: &str| {
+            v.map(|v| format!("{:+.4}{}", v * scale, unit))
+                .unwrap_or_else(|| "-".into())
+        };
+        out.push_str(&format!(
+            "    taker price edge   {}\n",
+            fmt_opt(s.mean_taker_price_edge, 1.0, "")
+        ));
+        out.push_str(&format!(
+            "    maker price edge   {}\n",
+            fmt_opt(s.mean_maker_price_edge, 1.0, "")
+        ));
+        out.push_str(&format!(
+            "    |first fill Δt|    {}\n",
+            fmt_opt(
+                s.mean_abs_first_fill_delta_ns,
+                1.0 / NANOS_PER_MILLI as f64,
+                "ms"
+            )
+        ));
+        out.push_str(&format!(
+            "    PnL                live {:+.4}  backtest {:+.4}  drift {:+.4}\n",
+            s.live_pnl, s.backtest_pnl, s.pnl_drift
+        ));
+
+        let mismatches: Vec<_> = self
+            .orders
+            .iter()
+            .filter(|o| o.classification != ReconciliationClass::Matched)
+            .collect();
+        if !mismatches.is_empty() {
+            out.push_str("\n  MISMATCHES\n");
+            for o in mismatches {
+                let id = |e: &Option<OrderExecution>| {
+                    e.as_ref()
+                        .map(|e| format!("#{}", e.order_id))
+                        .unwrap_or_else(|| "-".into())
+                };
+                out.push_str(&format!(
+                    "  {:<16} {:<8} live {:<7} bt {:<7} {} {} Δqty={:+.2} pnl={:+.4}\n",
+                    o.classification.label(),
+                    o.flow.label(),
+                    id(&o.live),
+                    id(&o.backtest),
+                    o.token_id,
+                    match o.side {
+                        Side::Buy => "B",
+                        Side::Sell => "S",
+                    },
+                    o.fill_size_delta,
+                    o.pnl_drift
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Replays live sessions and reconciles them against the backtest.
+pub struct LiveReconciler {
+    config: ReconciliationConfig,
+}
+
+impl LiveReconciler {
+    pub fn new(config: ReconciliationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Time range of market data needed to replay `session`.
+    pub fn replay_range(&self, session: &LiveSession) -> (Nanos, Nanos) {
+        let (start, end) = session.window();
+        (start - self.config.warmup_ns, end + self.config.tail_ns)
+    }
+
+    /// Load the session's market data from recorder storage and reconcile.
+    pub fn reconcile_from_storage(
+        &self,
+        session: &LiveSession,
+        storage: &UnifiedStorage,
+    ) -> Result<ReconciliationReport, String> {
+        let (start, end) = self.replay_range(session);
+        let mut events = Vec::new();
+        for token_id in &session.header.tokens {
+            let mut feed = UnifiedReplayFeed::from_storage(storage, token_id, start, end)
+                .map_err(|e| format!("Failed to load recorded data for {}: {}", token_id, e))?;
+            while let Some(event) = feed.next_event() {
+                events.push(event);
+            }
+        }
+        if events.is_empty() {
+            return Err(format!(
+                "No recorded market data for the session tokens in [{}, {}]",
+                start, end
+            ));
+        }
+        self.reconcile_events(session, events)
+    }
+
+    /// Replay `events` through the orchestrator and reconcile with `session`.
+    pub fn reconcile_events(
+        &self,
+        session: &LiveSession,
+        events: Vec<TimestampedEvent>,
+    ) -> Result<ReconciliationReport, String> {
+        let params = session.header.strategy_params();
+        let mut strategy = make_strategy(&session.header.strategy_name, &params)?;
+        let config = BacktestConfig {
+            strategy_params: params,
+            decision_trace_limit: self.config.backtest.decision_trace_limit.max(1),
+            ..self.config.backtest.clone()
+        };
+
+        let marks = marks_from_events(&events);
+        let mut feed = VecFeed::new("live_replay", events);
+        let mut orchestrator = BacktestOrchestrator::new(config);
+        orchestrator
+            .load_feed(&mut feed)
+            .map_err(|e| format!("Failed to load feed: {}", e))?;
+        let results = orchestrator
+            .run(strategy.as_mut())
+            .map_err(|e| format!("Backtest error: {}", e))?;
+
+        let trace = results.decision_trace.unwrap_or_default();
+        if trace.is_truncated() {
+            warn!(
+                stored = trace.records.len(),
+                total = trace.total_records,
+                "Backtest decision trace truncated; late orders are not reconciled"
+            );
+        }
+        let mut report = self.reconcile(session, &trace, &marks);
+        report.backtest_fingerprint = results.run_fingerprint.map(|f| f.hash_hex);
+        Ok(report)
+    }
+
+    /// Reconcile a live session with a backtest decision trace.
+    pub fn reconcile(
+        &self,
+        session: &LiveSession,
+        backtest: &DecisionTrace,
+        marks: &BTreeMap<String, f64>,
+    ) -> ReconciliationReport {
+        let (start, end) = session.window();
+
+        let mut live = collect_order_executions(&session.trace);
+        for order in &mut live {
+            if let Some(fee) = session.fees.get(&order.order_id) {
+                order.fees = *fee;
+            }
+        }
+        let mut unpaired: Vec<Option<OrderExecution>> = collect_order_executions(backtest)
+            .into_iter()
+            .filter(|o| o.submitted_at_ns >= start && o.submitted_at_ns <= end)
+            .map(Some)
+            .collect();
+        let backtest_orders = unpaired.len() as u64;
+
+        let mut orders = Vec::with_capacity(live.len());
+        for live_order in &live {
+            let best = unpaired
+                .iter()
+                .enumerate()
+                .filter_map(|(i, o)| o.as_ref().map(|o| (i, o)))
+                .filter(|(_, o)| o.token_id == live_order.token_id && o.side == live_order.side)
+                .map(|(i, o)| {
+                    let dt = (o.submitted_at_ns - live_order.submitted_at_ns).abs();
+                    let dp = (o.price - live_order.price).abs();
+                    (i, dt, dp)
+                })
+                .filter(|(_, dt, _)| *dt <= self.config.match_window_ns)
+                .min_by(|a, b| a.1.cmp(&b.1).then(a.2.total_cmp(&b.2)).then(a.0.cmp(&b.0)));
+            let paired = best.and_then(|(i, _, _)| unpaired[i].take());
+            orders.push(self.reconcile_order(Some(live_order.clone()), paired, marks));
+        }
+        for backtest_order in unpaired.into_iter().flatten() {
+            orders.push(self.reconcile_order(None, Some(backtest_order), marks));
+        }
+
+        let summary = Self::summarize(&orders, live.len() as u64, backtest_orders);
+        ReconciliationReport {
+            strategy_name: session.header.strategy_name.clone(),
+            tokens: session.header.tokens.clone(),
+            window_start_ns: start,
+            window_end_ns: end,
+            live_trace_hash: session.trace.hash_hex.clone(),
+            backtest_trace_hash: backtest.hash_hex.clone(),
+            backtest_fingerprint: None,
+            marks: marks.clone(),
+            orders,
+            summary,
+        }
+    }
+
+    fn reconcile_order(
+        &self,
+        live: Option<OrderExecution>,
+        backtest: Option<OrderExecution>,
+        marks: &BTreeMap<String, f64>,
+    ) -> OrderReconciliation {
+        let reference = live
+            .as_ref()
+            .or(backtest.as_ref())
+            .expect("at least one side of a reconciliation");
+        let token_id = reference.token_id.clone();
+        let side = reference.side;
+        let mark = marks.get(&token_id).copied().unwrap_or(reference.price);
+
+        let filled = |o: &Option<OrderExecution>| o.as_ref().map_or(0.0, |o| o.filled_size);
+        let pnl = |o: &Option<OrderExecution>| o.as_ref().map_or(0.0, |o| o.pnl_at(mark));
+        let fill_size_delta = filled(&backtest) - filled(&live);
+        let (live_pnl, backtest_pnl) = (pnl(&live), pnl(&backtest));
+
+        let (mut submit_time_delta_ns, mut fill_price_edge, mut first_fill_time_delta_ns) =
+            (None, None, None);
+        let classification = match (&live, &backtest) {
+            (Some(_), None) => ReconciliationClass::LiveOnly,
+            (None, Some(_)) => ReconciliationClass::BacktestOnly,
+            (Some(l), Some(b)) => {
+                submit_time_delta_ns = Some(b.submitted_at_ns - l.submitted_at_ns);
+                if let (Some(lp), Some(bp)) = (l.avg_fill_price(), b.avg_fill_price()) {
+                    fill_price_edge = Some(match side {
+                        Side::Buy => lp - bp,
+                        Side::Sell => bp - lp,
+                    });
+                }
+                if let (Some(lt), Some(bt)) = (l.first_fill_at_ns, b.first_fill_at_ns) {
+                    first_fill_time_delta_ns = Some(bt - lt);
+                }
+
+                match (l.is_filled(), b.is_filled()) {
+                    (false, false) => ReconciliationClass::Matched,
+                    (true, false) => ReconciliationClass::MissedFill,
+                    (false, true) => ReconciliationClass::PhantomFill,
+                    (true, true) => {
+                        let size_scale = l.filled_size.max(b.filled_size);
+                        if fill_size_delta.abs() > self.config.size_tolerance * size_scale {
+                            ReconciliationClass::SizeMismatch
+                        } else if fill_price_edge
+                            .is_some_and(|e| e.abs() > self.config.price_tolerance)
+                        {
+                            ReconciliationClass::PriceMismatch
+                        } else if first_fill_time_delta_ns
+                            .is_some_and(|dt| dt.abs() > self.config.timing_tolerance_ns)
+                        {
+                            ReconciliationClass::TimingMismatch
+                        } else {
+                            ReconciliationClass::Matched
+                        }
+                    }
+                }
+            }
+            (None, None) => unreachable!("checked above"),
+        };
+
+        let flow = match &live {
+            Some(l) if l.is_filled() => l.flow(),
+            _ => backtest
+                .as_ref()
+                .map_or(ExecutionFlow::Unfilled, |b| b.flow()),
+        };
+
+        OrderReconciliation {
+            token_id,
+            side,
+            classification,
+            flow,
+            live,
+            backtest,
+            submit_time_delta_ns,
+            fill_size_delta,
+            fill_price_edge,
+            first_fill_time_delta_ns,
+            live_pnl,
+            backtest_pnl,
+            pnl_drift: backtest_pnl - live_pnl,
+        }
+    }
+
+    fn summarize(
+        orders: &[OrderReconciliation],
+        live_orders: u64,
+        backtest_orders: u64,
+    ) -> ReconciliationSummary {
+        let mut summary = ReconciliationSummary {
+            live_orders,
+            backtest_orders,
+            ..Default::default()
+        };
+        let (mut taker_edges, mut maker_edges, mut fill_deltas) =
+            (Vec::new(), Vec::new(), Vec::new());
+
+        for o in orders {
+            *summary
+                .by_class
+                .entry(o.classification.label().to_string())
+                .or_insert(0) += 1;
+            if o.live.as_ref().is_some_and(|l| l.is_filled()) {
+                summary.live_filled += 1;
+            }
+            if o.backtest.as_ref().is_some_and(|b| b.is_filled()) {
+                summary.backtest_filled += 1;
+            }
+            if o.live.is_some() && o.backtest.is_some() {
+                summary.paired_orders += 1;
+                *summary
+                    .by_flow
+                    .entry(o.flow.label().to_string())
+                    .or_insert(0) += 1;
+                if let Some(edge) = o.fill_price_edge {
+                    match o.flow {
+                        ExecutionFlow::Taker => taker_edges.push(edge),
+                        ExecutionFlow::Maker => maker_edges.push(edge),
+                        _ => {}
+                    }
+                }
+                if let Some(dt) = o.first_fill_time_delta_ns {
+                    fill_deltas.push(dt.abs() as f64);
+                }
+            }
+            summary.live_pnl += o.live_pnl;
+            summary.backtest_pnl += o.backtest_pnl;
+            summary.pnl_drift += o.pnl_drift;
+        }
+
+        let mean = |v: &[f64]| (!v.is_empty()).then(|| v.iter().sum::<f64>() / v.len() as f64);
+        summary.mean_taker_price_edge = mean(&taker_edges);
+        summary.mean_maker_price_edge = mean(&maker_edges);
+        summary.mean_abs_first_fill_delta_ns = mean(&fill_deltas);
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::Level;
+    use crate::backtest_v2::queue::StreamSource;
+
+    const SEC: Nanos = NANOS_PER_SEC;
+
+    fn header() -> LiveSessionHeader {
+        LiveSessionHeader::new(
+            "noop",
+            &StrategyParams::new().with_param("half_spread", 0.01),
+            vec!["TOK".to_string()],
+            SEC,
+        )
+    }
+
+    fn trace_of(records: Vec<(TraceRecord, Nanos)>) -> DecisionTrace {
+        let mut trace = DecisionTrace::new(LIVE_TRACE_LIMIT);
+        for (i, (record, t)) in records.into_iter().enumerate() {
+            trace.push(record, i as u64, t);
+        }
+        trace
+    }
+
+    fn marks() -> BTreeMap<String, f64> {
+        BTreeMap::from([("TOK".to_string(), 0.50)])
+    }
+
+    #[test]
+    fn test_journal_round_trip_ignores_torn_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+
+        let mut recorder = LiveSessionRecorder::create(&path, header()).unwrap();
+        recorder.observe_event();
+        recorder.record(
+            TraceRecord::order_submit(1, "TOK", Side::Buy, 0.5, 10.0),
+            2 * SEC,
+        );
+        recorder.record(TraceRecord::order_ack(1), 2 * SEC);
+        recorder.observe_event();
+        recorder.record_fill(1, 0.5, 4.0, true, 0.0, 3 * SEC);
+        recorder.record_fill(1, 0.51, 6.0, false, 0.03, 3 * SEC);
+        let live = recorder.into_session();
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        write!(file, "{{\"type\":\"record\",\"rec").unwrap();
+        drop(file);
+
+        let read = LiveSession::read_from(&path).unwrap();
+        assert_eq!(read.header, header());
+        assert_eq!(read.trace.total_records, 4);
+        assert_eq!(read.trace.hash_hex, live.trace.hash_hex);
+        assert_eq!(read.trace.records[2].event_index, 2);
+        assert!((read.fees[&1] - 0.03).abs() < 1e-12);
+        assert_eq!(read.window(), (SEC, 3 * SEC));
+        assert_eq!(read.header.strategy_params().get("half_spread"), Some(0.01));
+    }
+
+    #[test]
+    fn test_pairs_orders_and_classifies_discrepancies() {
+        let mut live = LiveSession::new(header());
+        live.trace = trace_of(vec![
+            (
+                TraceRecord::order_submit(1, "TOK", Side::Buy, 0.52, 10.0),
+                SEC,
+            ),
+            (TraceRecord::fill(1, 0.51, 10.0, false), SEC + SEC / 10),
+            (
+                TraceRecord::order_submit(2, "TOK", Side::Sell, 0.55, 5.0),
+                2 * SEC,
+            ),
+            (TraceRecord::cancel_ack(2, 5.0), 3 * SEC),
+        ]);
+        live.fees.insert(1, 0.02);
+
+        let backtest = trace_of(vec![
+            // Warm-up order before the live session started
+            (
+                TraceRecord::order_submit(6, "TOK", Side::Buy, 0.52, 10.0),
+                SEC / 2,
+            ),
+            (
+                TraceRecord::order_submit(7, "TOK", Side::Buy, 0.52, 10.0),
+                SEC + SEC / 20,
+            ),
+            (TraceRecord::fill(7, 0.50, 10.0, false), SEC + SEC / 20),
+            (
+                TraceRecord::order_submit(8, "TOK", Side::Sell, 0.55, 5.0),
+                2 * SEC,
+            ),
+            (TraceRecord::fill(8, 0.55, 5.0, true), 2 * SEC + SEC / 2),
+            (
+                TraceRecord::order_submit(9, "TOK", Side::Buy, 0.40, 5.0),
+                3 * SEC,
+            ),
+        ]);
+
+        let report = LiveReconciler::new(ReconciliationConfig::default()).reconcile(
+            &live,
+            &backtest,
+            &marks(),
+        );
+        assert_eq!(report.orders.len(), 3);
+
+        let taker = &report.orders[0];
+        assert_eq!(taker.backtest.as_ref().unwrap().order_id, 7);
+        assert_eq!(taker.classification, ReconciliationClass::PriceMismatch);
+        assert_eq!(taker.flow, ExecutionFlow::Taker);
+        assert!((taker.fill_price_edge.unwrap() - 0.01).abs() < 1e-9);
+        assert_eq!(taker.submit_time_delta_ns, Some(SEC / 20));
+        // Live: 10 @ 0.51 + 0.02 fee marked at 0.50; backtest: 10 @ 0.50, pre-fee-era
+        assert!((taker.live_pnl + 0.12).abs() < 1e-9);
+        assert!(taker.backtest_pnl.abs() < 1e-9);
+        assert!((taker.pnl_drift - 0.12).abs() < 1e-9);
+
+        let maker = &report.orders[1];
+        assert_eq!(maker.classification, ReconciliationClass::PhantomFill);
+        assert_eq!(maker.flow, ExecutionFlow::Maker);
+        assert!((maker.fill_size_delta - 5.0).abs() < 1e-9);
+
+        assert_eq!(
+            report.orders[2].classification,
+            ReconciliationClass::BacktestOnly
+        );
+        assert_eq!(report.orders[2].backtest.as_ref().unwrap().order_id, 9);
+
+        let s = &report.summary;
+        assert_eq!(
+            (s.live_orders, s.backtest_orders, s.paired_orders),
+            (2, 3, 2)
+        );
+        assert_eq!((s.live_filled, s.backtest_filled), (1, 2));
+        assert_eq!(s.by_class["PHANTOM_FILL"], 1);
+        assert!((s.mean_taker_price_edge.unwrap() - 0.01).abs() < 1e-9);
+        assert!(report.format_report().contains("PHANTOM_FILL"));
+    }
+
+    #[test]
+    fn test_unpaired_outside_match_window() {
+        let mut live = LiveSession::new(header());
+        live.trace = trace_of(vec![
+            (
+                TraceRecord::order_submit(1, "TOK", Side::Buy, 0.50, 10.0),
+                SEC,
+            ),
+            (TraceRecord::fill(1, 0.50, 10.0, false), SEC),
+            (TraceRecord::order_ack(1), 20 * SEC),
+        ]);
+        let backtest = trace_of(vec![(
+            TraceRecord::order_submit(3, "TOK", Side::Buy, 0.50, 10.0),
+            15 * SEC,
+        )]);
+
+        let report = LiveReconciler::new(ReconciliationConfig::default()).reconcile(
+            &live,
+            &backtest,
+            &marks(),
+        );
+        let classes: Vec<_> = report.orders.iter().map(|o| o.classification).collect();
+        assert_eq!(
+            classes,
+            vec![
+                ReconciliationClass::LiveOnly,
+                ReconciliationClass::BacktestOnly
+            ]
+        );
+        assert!(report.format_report().contains("LIVE_ONLY"));
+    }
+
+    #[test]
+    fn test_replay_through_orchestrator() {
+        let events: Vec<_> = (0..20)
+            .map(|i| {
+                TimestampedEvent::new(
+                    (i + 1) * SEC,
+                    StreamSource::MarketData as u8,
+                    Event::L2BookSnapshot {
+                        token_id: "TOK".into(),
+                        bids: vec![Level::new(0.48, 100.0)],
+                        asks: vec![Level::new(0.52, 100.0)],
+                        exchange_seq: i as u64,
+                    },
+                )
+            })
+            .collect();
+
+        let mut recorder = LiveSessionRecorder::new(header());
+        recorder.record(
+            TraceRecord::order_submit(1, "TOK", Side::Buy, 0.52, 10.0),
+            5 * SEC,
+        );
+        recorder.record_fill(1, 0.52, 10.0, false, 0.0, 5 * SEC);
+        let session = recorder.into_session();
+
+        let config = ReconciliationConfig {
+            backtest: BacktestConfig {
+                decision_trace_limit: LIVE_TRACE_LIMIT,
+                ..BacktestConfig::test_config()
+            },
+            ..Default::default()
+        };
+        let report = LiveReconciler::new(config)
+            .reconcile_events(&session, events)
+            .unwrap();
+
+        // The noop strategy never trades: the live fill is unexplained
+        assert_eq!(report.summary.backtest_orders, 0);
+        assert_eq!(report.orders.len(), 1);
+        assert_eq!(
+            report.orders[0].classification,
+            ReconciliationClass::LiveOnly
+        );
+        assert!((report.marks["TOK"] - 0.50).abs() < 1e-9);
+        assert!((report.summary.pnl_drift - 0.2).abs() < 1e-9);
+    }
+}
@@ -333,7 +333,7 @@ impl RejectionReason {
 // =============================================================================
 
 /// Configuration for the maker fill gate.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MakerFillGateConfig {
     /// Whether production-grade strictness is required.
     pub production_grade: bool,
@@ -445,6 +445,7 @@ impl RejectionCounts {
 ///
 /// Every maker fill MUST pass through this gate. There is no other pathway
 /// by which a maker fill can be credited to PnL.
+#[derive(Serialize, Deserialize)]
 pub struct MakerFillGate {
     config: MakerFillGateConfig,
     stats: MakerFillGateStats,
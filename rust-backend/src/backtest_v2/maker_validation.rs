@@ -30,6 +30,7 @@
 
 use crate::backtest_v2::clock::Nanos;
 use crate::backtest_v2::latency::{LatencyConfig, LatencyDistribution, NS_PER_MS};
+use crate::backtest_v2::latency_regime::LatencyRegimeConfig;
 use crate::backtest_v2::orchestrator::MakerFillModel;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -294,6 +295,7 @@ impl MakerProfileConfigs {
                     fill_report: LatencyDistribution::Fixed { 
                         latency_ns: (c.fill_report_latency_ms * NS_PER_MS as f64) as Nanos 
                     },
+                    regimes: LatencyRegimeConfig::none(),
                 }
             }
             MakerExecutionProfile::NeutralMaker => {
@@ -315,6 +317,7 @@ impl MakerProfileConfigs {
                     fill_report: LatencyDistribution::Fixed { 
                         latency_ns: (n.fill_report_latency_ms * NS_PER_MS as f64) as Nanos 
                     },
+                    regimes: LatencyRegimeConfig::none(),
                 }
             }
             MakerExecutionProfile::MeasuredLiveMaker => {
@@ -345,6 +348,7 @@ impl MakerProfileConfigs {
                             std_ns: (m.fill_report_latency_mean_ms * 0.3 * NS_PER_MS as f64) as Nanos,
                             max_ns: (m.fill_report_latency_mean_ms * 3.0 * NS_PER_MS as f64) as Nanos,
                         },
+                        regimes: LatencyRegimeConfig::none(),
                     }
                 } else {
                     // Fixed mean values
@@ -365,6 +369,7 @@ impl MakerProfileConfigs {
                         fill_report: LatencyDistribution::Fixed { 
                             latency_ns: (m.fill_report_latency_mean_ms * NS_PER_MS as f64) as Nanos 
                         },
+                        regimes: LatencyRegimeConfig::none(),
                     }
                 }
             }
@@ -0,0 +1,637 @@
+//! Market Impact and Liquidity Replenishment
+//!
+//! Recorded books never see our own aggression: a snapshot taken one second
+//! after we swept three ask levels still shows them full, so a strategy that
+//! keeps hitting the book inside one 15M window is filled as if every order
+//! were the first. This module keeps the impact of our taker fills alive
+//! across subsequent book updates.
+//!
+//! # Components
+//!
+//! 1. **Temporary impact**: depth we consumed stays missing from the displayed
+//!    book until it refills, either exponentially (`ReplenishmentModel::HalfLife`)
+//!    or linearly at per-level rates estimated from recorded `L2BookDelta`
+//!    streams (`ReplenishmentModel::Empirical`).
+//! 2. **Permanent impact**: net aggressor volume shifts the whole book by
+//!    `permanent_ticks_per_share` ticks per share (buys up, sells down).
+//! 3. **Window reset**: all impact state is dropped at 15M window boundaries.
+//!
+//! `ReplenishmentModel::Instant` with zero permanent impact reproduces the
+//! legacy behaviour (consumed depth returns with the next book update).
+//!
+//! # Empirical Refill Rates
+//!
+//! `LevelRefillEstimator` replays recorded book events and accumulates every
+//! size *increase* at a level, bucketed by its distance (in ticks) from the
+//! same-side touch. Dividing by observed book-seconds gives shares/second of
+//! replenishment per level at each distance.
+
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
+use crate::backtest_v2::events::{Event, Level, Price, Side, Size, TimestampedEvent};
+use crate::backtest_v2::taker_slippage::{price_to_tick, PriceTick};
+use crate::backtest_v2::time_windows::window_index;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Remaining depletion below this is treated as fully refilled.
+const REFILL_EPSILON: Size = 1e-9;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// How consumed depth comes back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplenishmentModel {
+    /// Consumed depth returns with the next book update touching the level.
+    Instant,
+    /// Consumed depth refills exponentially with this half-life.
+    HalfLife { half_life_ns: Nanos },
+    /// Consumed depth refills linearly at empirical per-level rates.
+    Empirical { rates: LevelRefillRates },
+}
+
+/// Configuration for the market impact model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketImpactConfig {
+    /// Temporary impact / replenishment model.
+    pub replenishment: ReplenishmentModel,
+    /// Permanent book shift in ticks per share of net aggressor volume.
+    /// 0 disables permanent impact.
+    pub permanent_ticks_per_share: f64,
+    /// Drop all impact state at 15M window boundaries.
+    pub reset_each_window: bool,
+}
+
+impl Default for MarketImpactConfig {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl MarketImpactConfig {
+    /// No impact (legacy behaviour).
+    pub fn none() -> Self {
+        Self {
+            replenishment: ReplenishmentModel::Instant,
+            permanent_ticks_per_share: 0.0,
+            reset_each_window: true,
+        }
+    }
+
+    /// Exponential refill with the given half-life plus permanent impact.
+    pub fn half_life(half_life_ns: Nanos, permanent_ticks_per_share: f64) -> Self {
+        Self {
+            replenishment: ReplenishmentModel::HalfLife { half_life_ns },
+            permanent_ticks_per_share,
+            reset_each_window: true,
+        }
+    }
+
+    /// Refill at rates estimated from recorded deltas plus permanent impact.
+    pub fn empirical(rates: LevelRefillRates, permanent_ticks_per_share: f64) -> Self {
+        Self {
+            replenishment: ReplenishmentModel::Empirical { rates },
+            permanent_ticks_per_share,
+            reset_each_window: true,
+        }
+    }
+
+    /// Whether consumed depth is tracked across book updates.
+    pub fn tracks_depletion(&self) -> bool {
+        !matches!(self.replenishment, ReplenishmentModel::Instant)
+    }
+
+    /// Whether any impact is modelled at all.
+    pub fn is_enabled(&self) -> bool {
+        self.tracks_depletion() || self.permanent_ticks_per_share != 0.0
+    }
+
+    /// Stable hash of the model for the config fingerprint.
+    pub fn fingerprint_hash(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        match &self.replenishment {
+            ReplenishmentModel::Instant => 0u8.hash(&mut hasher),
+            ReplenishmentModel::HalfLife { half_life_ns } => {
+                1u8.hash(&mut hasher);
+                half_life_ns.hash(&mut hasher);
+            }
+            ReplenishmentModel::Empirical { rates } => {
+                2u8.hash(&mut hasher);
+                for rate in rates.bid_rates.iter().chain(&rates.ask_rates) {
+                    rate.to_bits().hash(&mut hasher);
+                }
+                rates.bid_rates.len().hash(&mut hasher);
+            }
+        }
+        self.permanent_ticks_per_share.to_bits().hash(&mut hasher);
+        self.reset_each_window.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// =============================================================================
+// EMPIRICAL REFILL RATES
+// =============================================================================
+
+/// Per-level replenishment rates (shares/second), indexed by distance in
+/// ticks from the same-side touch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LevelRefillRates {
+    /// Bid-side rates; `bid_rates[0]` is the best bid.
+    pub bid_rates: Vec<f64>,
+    /// Ask-side rates; `ask_rates[0]` is the best ask.
+    pub ask_rates: Vec<f64>,
+    /// Book-seconds of data the estimate is based on.
+    pub observed_secs: f64,
+}
+
+impl LevelRefillRates {
+    /// Refill rate for a resting level (`Side::Buy` = bids) at `distance_ticks`
+    /// from the touch. Levels beyond the estimated range use the deepest rate.
+    pub fn rate(&self, side: Side, distance_ticks: u32) -> f64 {
+        let rates = match side {
+            Side::Buy => &self.bid_rates,
+            Side::Sell => &self.ask_rates,
+        };
+        rates
+            .get(distance_ticks as usize)
+            .or_else(|| rates.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Estimate rates from a recorded event stream.
+    pub fn estimate<'a>(
+        events: impl IntoIterator<Item = &'a TimestampedEvent>,
+        tick_size: Price,
+        max_distance_ticks: usize,
+    ) -> Self {
+        let mut estimator = LevelRefillEstimator::new(tick_size, max_distance_ticks);
+        for event in events {
+            estimator.observe(event);
+        }
+        estimator.finish()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct EstimatorBook {
+    bids: BTreeMap<PriceTick, Size>,
+    asks: BTreeMap<PriceTick, Size>,
+    first_ts: Option<Nanos>,
+    last_ts: Nanos,
+}
+
+impl EstimatorBook {
+    fn touch(&mut self, ts: Nanos) {
+        if self.first_ts.is_none() {
+            self.first_ts = Some(ts);
+        }
+        self.last_ts = self.last_ts.max(ts);
+    }
+
+    fn observed_ns(&self) -> Nanos {
+        self.first_ts.map(|f| self.last_ts - f).unwrap_or(0)
+    }
+}
+
+/// Accumulates level replenishment from recorded book events.
+#[derive(Debug, Clone)]
+pub struct LevelRefillEstimator {
+    tick_size: Price,
+    max_distance_ticks: usize,
+    books: HashMap<String, EstimatorBook>,
+    bid_added: Vec<f64>,
+    ask_added: Vec<f64>,
+}
+
+impl LevelRefillEstimator {
+    pub fn new(tick_size: Price, max_distance_ticks: usize) -> Self {
+        Self {
+            tick_size,
+            max_distance_ticks,
+            books: HashMap::new(),
+            bid_added: vec![0.0; max_distance_ticks + 1],
+            ask_added: vec![0.0; max_distance_ticks + 1],
+        }
+    }
+
+    /// Observe one recorded event; non-book events are ignored.
+    pub fn observe(&mut self, event: &TimestampedEvent) {
+        match &event.event {
+            Event::L2BookSnapshot {
+                token_id,
+                bids,
+                asks,
+                ..
+            } => self.observe_snapshot(token_id, bids, asks, event.time),
+            Event::L2BookDelta {
+                token_id,
+                side,
+                price,
+                new_size,
+                ..
+            } => self.observe_delta(token_id, *side, *price, *new_size, event.time),
+            Event::L2Delta {
+                token_id,
+                bid_updates,
+                ask_updates,
+                ..
+            } => {
+                for level in bid_updates {
+                    self.observe_delta(token_id, Side::Buy, level.price, level.size, event.time);
+                }
+                for level in ask_updates {
+                    self.observe_delta(token_id, Side::Sell, level.price, level.size, event.time);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A snapshot resets the reconstructed book; it is not counted as refill.
+    pub fn observe_snapshot(&mut self, token_id: &str, bids: &[Level], asks: &[Level], ts: Nanos) {
+        let tick_size = self.tick_size;
+        let book = self.books.entry(token_id.to_string()).or_default();
+        book.touch(ts);
+        book.bids = bids
+            .iter()
+            .filter(|l| l.size > 0.0)
+            .map(|l| (price_to_tick(l.price, tick_size), l.size))
+            .collect();
+        book.asks = asks
+            .iter()
+            .filter(|l| l.size > 0.0)
+            .map(|l| (price_to_tick(l.price, tick_size), l.size))
+            .collect();
+    }
+
+    /// Observe a single level update (`new_size` is the new aggregate size).
+    pub fn observe_delta(
+        &mut self,
+        token_id: &str,
+        side: Side,
+        price: Price,
+        new_size: Size,
+        ts: Nanos,
+    ) {
+        let tick = price_to_tick(price, self.tick_size);
+        let book = self.books.entry(token_id.to_string()).or_default();
+        book.touch(ts);
+
+        let levels = match side {
+            Side::Buy => &mut book.bids,
+            Side::Sell => &mut book.asks,
+        };
+        let old_size = levels.get(&tick).copied().unwrap_or(0.0);
+
+        if new_size > old_size {
+            // Distance from the same-side touch before this update; improving
+            // the touch counts as distance 0.
+            let best = match side {
+                Side::Buy => levels.keys().next_back().copied(),
+                Side::Sell => levels.keys().next().copied(),
+            };
+            let distance = match (side, best) {
+                (Side::Buy, Some(b)) if tick < b => (b - tick) as usize,
+                (Side::Sell, Some(b)) if tick > b => (tick - b) as usize,
+                _ => 0,
+            };
+            if distance <= self.max_distance_ticks {
+                let added = match side {
+                    Side::Buy => &mut self.bid_added,
+                    Side::Sell => &mut self.ask_added,
+                };
+                added[distance] += new_size - old_size;
+            }
+        }
+
+        if new_size <= 0.0 {
+            levels.remove(&tick);
+        } else {
+            levels.insert(tick, new_size);
+        }
+    }
+
+    /// Rates over everything observed so far.
+    pub fn finish(&self) -> LevelRefillRates {
+        let observed_ns: Nanos = self.books.values().map(|b| b.observed_ns()).sum();
+        let observed_secs = observed_ns as f64 / NANOS_PER_SEC as f64;
+        let per_sec = |added: &[f64]| -> Vec<f64> {
+            added
+                .iter()
+                .map(|a| {
+                    if observed_secs > 0.0 {
+                        a / observed_secs
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+
+        LevelRefillRates {
+            bid_rates: per_sec(&self.bid_added),
+            ask_rates: per_sec(&self.ask_added),
+            observed_secs,
+        }
+    }
+}
+
+// =============================================================================
+// IMPACT STATE
+// =============================================================================
+
+/// Depth we consumed at one level that has not refilled yet.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Depletion {
+    /// Outstanding size as of `at`.
+    size: Size,
+    at: Nanos,
+    /// Ticks from the touch when consumed (selects the empirical rate).
+    distance: u32,
+}
+
+/// Impact state dropped when a new 15M window starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowReset {
+    /// Shift (ticks) the book must apply to undo the permanent impact.
+    pub undo_shift_ticks: i32,
+    /// Levels (after undoing the shift) whose consumed depth is restored.
+    pub refilled: Vec<(Side, PriceTick)>,
+}
+
+/// Impact state carried by one simulated book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketImpact {
+    config: MarketImpactConfig,
+    /// Keyed by (resting side, tick); `Side::Buy` = bid levels.
+    depletion: HashMap<(Side, PriceTick), Depletion>,
+    /// Signed aggressor volume this window (buys positive).
+    net_aggressor_volume: Size,
+    /// Current permanent shift applied to the book, in ticks.
+    shift_ticks: i32,
+    /// 15M window index the state belongs to.
+    window: Option<i64>,
+}
+
+impl MarketImpact {
+    pub fn new(config: MarketImpactConfig) -> Self {
+        Self {
+            config,
+            depletion: HashMap::new(),
+            net_aggressor_volume: 0.0,
+            shift_ticks: 0,
+            window: None,
+        }
+    }
+
+    pub fn config(&self) -> &MarketImpactConfig {
+        &self.config
+    }
+
+    /// Permanent shift currently applied to the book, in ticks.
+    pub fn shift_ticks(&self) -> i32 {
+        self.shift_ticks
+    }
+
+    /// Signed aggressor volume this window (buys positive).
+    pub fn net_aggressor_volume(&self) -> Size {
+        self.net_aggressor_volume
+    }
+
+    /// Depth still missing at a resting level at `now`.
+    pub fn outstanding(&self, side: Side, tick: PriceTick, now: Nanos) -> Size {
+        self.depletion
+            .get(&(side, tick))
+            .map(|d| self.remaining(side, d, now))
+            .unwrap_or(0.0)
+    }
+
+    /// Resting levels with outstanding depletion.
+    pub fn depleted_levels(&self) -> Vec<(Side, PriceTick)> {
+        self.depletion.keys().copied().collect()
+    }
+
+    /// Enter the window containing `now`. Returns what the book must undo
+    /// when a new window starts and state was reset.
+    pub fn roll_window(&mut self, now: Nanos) -> Option<WindowReset> {
+        if !self.config.is_enabled() {
+            return None;
+        }
+        let w = window_index(now);
+        match self.window {
+            Some(current) if current == w => None,
+            Some(_) if self.config.reset_each_window => {
+                self.window = Some(w);
+                let undo_shift_ticks = -self.shift_ticks;
+                let refilled = self
+                    .depletion
+                    .drain()
+                    .map(|((side, tick), _)| (side, shift_tick(tick, undo_shift_ticks)))
+                    .collect();
+                self.net_aggressor_volume = 0.0;
+                self.shift_ticks = 0;
+                Some(WindowReset {
+                    undo_shift_ticks,
+                    refilled,
+                })
+            }
+            _ => {
+                self.window = Some(w);
+                None
+            }
+        }
+    }
+
+    /// Record depth consumed from a resting level.
+    pub fn record_consumption(
+        &mut self,
+        side: Side,
+        tick: PriceTick,
+        distance_ticks: u32,
+        size: Size,
+        now: Nanos,
+    ) {
+        if !self.config.tracks_depletion() || size <= 0.0 {
+            return;
+        }
+        let outstanding = self.outstanding(side, tick, now);
+        self.depletion.insert(
+            (side, tick),
+            Depletion {
+                size: outstanding + size,
+                at: now,
+                distance: distance_ticks,
+            },
+        );
+    }
+
+    /// Record filled aggressor volume. Returns the change in permanent shift
+    /// (ticks) the book must apply.
+    pub fn record_aggression(&mut self, aggressor: Side, size: Size) -> i32 {
+        self.net_aggressor_volume += match aggressor {
+            Side::Buy => size,
+            Side::Sell => -size,
+        };
+        let target = (self.net_aggressor_volume * self.config.permanent_ticks_per_share).round();
+        let target = target.clamp(-98.0, 98.0) as i32;
+        let delta = target - self.shift_ticks;
+        if delta != 0 {
+            self.shift(delta);
+        }
+        delta
+    }
+
+    /// Forget levels that have fully refilled by `now`.
+    pub fn prune(&mut self, now: Nanos) {
+        let refilled: Vec<(Side, PriceTick)> = self
+            .depletion
+            .iter()
+            .filter(|((side, _), d)| self.remaining(*side, d, now) <= REFILL_EPSILON)
+            .map(|(k, _)| *k)
+            .collect();
+        for k in refilled {
+            self.depletion.remove(&k);
+        }
+    }
+
+    fn shift(&mut self, delta: i32) {
+        self.shift_ticks += delta;
+        let old = std::mem::take(&mut self.depletion);
+        for ((side, tick), d) in old {
+            let shifted = shift_tick(tick, delta);
+            self.depletion
+                .entry((side, shifted))
+                .and_modify(|e| e.size += d.size)
+                .or_insert(d);
+        }
+    }
+
+    fn remaining(&self, side: Side, d: &Depletion, now: Nanos) -> Size {
+        let dt = (now - d.at).max(0) as f64;
+        match &self.config.replenishment {
+            ReplenishmentModel::Instant => 0.0,
+            ReplenishmentModel::HalfLife { half_life_ns } => {
+                if *half_life_ns <= 0 {
+                    0.0
+                } else {
+                    d.size * 0.5f64.powf(dt / *half_life_ns as f64)
+                }
+            }
+            ReplenishmentModel::Empirical { rates } => {
+                let refilled = rates.rate(side, d.distance) * dt / NANOS_PER_SEC as f64;
+                (d.size - refilled).max(0.0)
+            }
+        }
+    }
+}
+
+/// Shift a tick by `delta`, staying inside the valid [1, 99] tick range.
+pub fn shift_tick(tick: PriceTick, delta: i32) -> PriceTick {
+    (tick as i64 + delta as i64).clamp(1, 99) as PriceTick
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC: Nanos = NANOS_PER_SEC;
+
+    #[test]
+    fn test_half_life_refill() {
+        let mut impact = MarketImpact::new(MarketImpactConfig::half_life(10 * SEC, 0.0));
+        impact.roll_window(0);
+        impact.record_consumption(Side::Sell, 55, 0, 100.0, 0);
+
+        assert!((impact.outstanding(Side::Sell, 55, 0) - 100.0).abs() < 1e-9);
+        assert!((impact.outstanding(Side::Sell, 55, 10 * SEC) - 50.0).abs() < 1e-9);
+        assert!((impact.outstanding(Side::Sell, 55, 20 * SEC) - 25.0).abs() < 1e-9);
+        assert_eq!(impact.outstanding(Side::Buy, 55, 0), 0.0);
+
+        // Consuming again stacks on what is still missing.
+        impact.record_consumption(Side::Sell, 55, 0, 10.0, 10 * SEC);
+        assert!((impact.outstanding(Side::Sell, 55, 10 * SEC) - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_permanent_impact_and_window_reset() {
+        let mut impact = MarketImpact::new(MarketImpactConfig::half_life(SEC, 0.01));
+        impact.roll_window(0);
+        impact.record_consumption(Side::Sell, 55, 0, 50.0, 0);
+
+        assert_eq!(impact.record_aggression(Side::Buy, 250.0), 3);
+        assert_eq!(impact.shift_ticks(), 3);
+        // Depletion moves with the book.
+        assert!(impact.outstanding(Side::Sell, 58, 0) > 0.0);
+        assert_eq!(impact.record_aggression(Side::Sell, 100.0), -1);
+
+        assert_eq!(impact.roll_window(SEC), None);
+        let next_window = crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
+        let reset = impact.roll_window(next_window).unwrap();
+        assert_eq!(reset.undo_shift_ticks, -2);
+        assert_eq!(reset.refilled, vec![(Side::Sell, 55)]);
+        assert_eq!(impact.shift_ticks(), 0);
+        assert!(impact.depleted_levels().is_empty());
+    }
+
+    #[test]
+    fn test_estimate_refill_rates_from_deltas() {
+        let delta = |t: Nanos, side: Side, price: Price, size: Size| {
+            TimestampedEvent::new(
+                t,
+                0,
+                Event::L2BookDelta {
+                    token_id: "tok".to_string(),
+                    side,
+                    price,
+                    new_size: size,
+                    seq_hash: None,
+                },
+            )
+        };
+        let events = vec![
+            TimestampedEvent::new(
+                0,
+                0,
+                Event::L2BookSnapshot {
+                    token_id: "tok".to_string(),
+                    bids: vec![Level::new(0.45, 100.0)],
+                    asks: vec![Level::new(0.55, 100.0), Level::new(0.56, 100.0)],
+                    exchange_seq: 1,
+                },
+            ),
+            // Touch eaten then refilled: +80 at distance 0.
+            delta(2 * SEC, Side::Sell, 0.55, 20.0),
+            delta(4 * SEC, Side::Sell, 0.55, 100.0),
+            // Second level grows by 30: distance 1.
+            delta(6 * SEC, Side::Sell, 0.56, 130.0),
+            // Bid touch grows by 50.
+            delta(10 * SEC, Side::Buy, 0.45, 150.0),
+        ];
+
+        let rates = LevelRefillRates::estimate(&events, 0.01, 5);
+        assert!((rates.observed_secs - 10.0).abs() < 1e-9);
+        assert!((rates.rate(Side::Sell, 0) - 8.0).abs() < 1e-9);
+        assert!((rates.rate(Side::Sell, 1) - 3.0).abs() < 1e-9);
+        assert!((rates.rate(Side::Buy, 0) - 5.0).abs() < 1e-9);
+        // Beyond the estimated range falls back to the deepest bucket.
+        assert_eq!(rates.rate(Side::Sell, 50), rates.rate(Side::Sell, 5));
+
+        let mut impact = MarketImpact::new(MarketImpactConfig::empirical(rates, 0.0));
+        impact.roll_window(0);
+        impact.record_consumption(Side::Sell, 55, 0, 40.0, 0);
+        assert!((impact.outstanding(Side::Sell, 55, 2 * SEC) - 24.0).abs() < 1e-9);
+        assert_eq!(impact.outstanding(Side::Sell, 55, 5 * SEC), 0.0);
+        impact.prune(5 * SEC);
+        assert!(impact.depleted_levels().is_empty());
+    }
+}
@@ -2,13 +2,24 @@
 //!
 //! Full CLOB simulator for binary outcome tokens with realistic exchange behavior.
 //! Supports FIFO matching, partial fills, self-trade prevention, and maker/taker fees.
+//!
+//! With a market impact model set (`MatchingEngine::set_market_impact`), each book
+//! also mirrors the recorded venue depth: marketable orders sweep it after our own
+//! resting orders, and the depth they consume refills per the impact model.
 
 use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::event_time::VisibleNanos;
 use crate::backtest_v2::events::{
-    Event, OrderId, OrderType, Price, RejectReason, Side, Size, TimeInForce, TimestampedEvent,
+    BatchMode, Event, Level, OrderId, OrderType, Price, RejectReason, Side, Size, TimeInForce,
+    TimestampedEvent,
 };
+use crate::backtest_v2::market_impact::MarketImpactConfig;
 use crate::backtest_v2::queue::StreamSource;
+use crate::backtest_v2::taker_slippage::{
+    SimulatedL2Book, TakerFillModel, TakerOrderRequest, TakerSlippageConfig,
+};
 use std::collections::{BTreeMap, HashMap, VecDeque};
+use serde::{Deserialize, Serialize};
 
 /// Price tick size for binary outcome markets.
 /// Polymarket uses 0.01 (1 cent) ticks in the 0-1 range.
@@ -32,7 +43,7 @@ pub fn ticks_to_price(ticks: PriceTicks, tick_size: f64) -> f64 {
 }
 
 /// Fee configuration for the matching engine.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeeConfig {
     /// Maker fee (negative = rebate). Typically -0.0001 to 0.001.
     pub maker_fee_rate: f64,
@@ -50,7 +61,7 @@ impl Default for FeeConfig {
 }
 
 /// Matching engine configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchingConfig {
     pub tick_size: f64,
     pub fees: FeeConfig,
@@ -81,7 +92,7 @@ impl Default for MatchingConfig {
 }
 
 /// Self-trade prevention modes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelfTradeMode {
     /// Cancel the incoming (newest) order.
     CancelNewest,
@@ -118,8 +129,18 @@ pub struct CancelRequest {
     pub client_order_id: Option<String>,
 }
 
-/// Internal order representation on the book.
+/// Replace (amend) request for a resting order.
 #[derive(Debug, Clone)]
+pub struct ReplaceRequest {
+    pub order_id: OrderId,
+    /// New limit price.
+    pub new_price: Price,
+    /// New total order quantity (quantity already filled counts against it).
+    pub new_size: Size,
+}
+
+/// Internal order representation on the book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BookOrder {
     order_id: OrderId,
     client_order_id: String,
@@ -128,16 +149,14 @@ struct BookOrder {
     price_ticks: PriceTicks,
     original_size: Size,
     remaining_size: Size,
-    #[allow(dead_code)]
     post_only: bool,
     #[allow(dead_code)]
     created_at: Nanos,
-    #[allow(dead_code)]
     time_in_force: TimeInForce,
 }
 
 /// A single price level with FIFO queue.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct PriceLevel {
     orders: VecDeque<BookOrder>,
     total_size: Size,
@@ -190,9 +209,24 @@ impl PriceLevel {
             None
         }
     }
+
+    fn get_order(&self, order_id: OrderId) -> Option<&BookOrder> {
+        self.orders.iter().find(|o| o.order_id == order_id)
+    }
+
+    /// Reduce an order's remaining size in place (keeps its queue position).
+    fn resize_order(&mut self, order_id: OrderId, new_remaining: Size) -> bool {
+        let Some(order) = self.orders.iter_mut().find(|o| o.order_id == order_id) else {
+            return false;
+        };
+        self.total_size += new_remaining - order.remaining_size;
+        order.remaining_size = new_remaining;
+        true
+    }
 }
 
 /// The limit order book for a single token.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LimitOrderBook {
     pub token_id: String,
     config: MatchingConfig,
@@ -208,17 +242,21 @@ pub struct LimitOrderBook {
     next_fill_id: u64,
     /// Statistics
     pub stats: MatchingStats,
+    /// Recorded venue depth, swept by marketable orders once our own resting
+    /// orders are exhausted (None = only our own orders match).
+    #[serde(default)]
+    venue_depth: Option<SimulatedL2Book>,
 }
 
 /// Location of an order in the book.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct OrderLocation {
     side: Side,
     price_ticks: PriceTicks,
 }
 
 /// Matching statistics.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MatchingStats {
     pub orders_submitted: u64,
     pub orders_accepted: u64,
@@ -228,6 +266,14 @@ pub struct MatchingStats {
     pub total_volume: f64,
     pub self_trades_prevented: u64,
     pub post_only_rejections: u64,
+    #[serde(default)]
+    pub orders_replaced: u64,
+    #[serde(default)]
+    pub replaces_rejected: u64,
+    #[serde(default)]
+    pub batches_submitted: u64,
+    #[serde(default)]
+    pub batches_rejected: u64,
 }
 
 /// Fill instruction generated during matching.
@@ -272,6 +318,48 @@ impl LimitOrderBook {
             next_order_id: 1,
             next_fill_id: 1,
             stats: MatchingStats::default(),
+            venue_depth: None,
+        }
+    }
+
+    /// Mirror recorded venue depth with the given impact model.
+    pub fn enable_venue_depth(&mut self, impact: MarketImpactConfig) {
+        let config = TakerSlippageConfig {
+            tick_size: self.config.tick_size,
+            taker_fee_rate: self.config.fees.taker_fee_rate,
+            min_order_size: 0.0,
+            max_order_size: self.config.max_order_size,
+            impact,
+            ..Default::default()
+        };
+        self.venue_depth = Some(SimulatedL2Book::new(self.token_id.clone(), config));
+    }
+
+    /// Recorded venue depth (None unless a market impact model is set).
+    pub fn venue_depth(&self) -> Option<&SimulatedL2Book> {
+        self.venue_depth.as_ref()
+    }
+
+    /// Apply a recorded book snapshot to the venue depth.
+    pub fn apply_venue_snapshot(&mut self, bids: &[Level], asks: &[Level], seq: u64, now: Nanos) {
+        if let Some(depth) = &mut self.venue_depth {
+            let bids: Vec<(Price, Size)> = bids.iter().map(|l| (l.price, l.size)).collect();
+            let asks: Vec<(Price, Size)> = asks.iter().map(|l| (l.price, l.size)).collect();
+            depth.apply_snapshot(&bids, &asks, seq, VisibleNanos(now));
+        }
+    }
+
+    /// Apply a recorded level update (`new_size` is the new aggregate size).
+    pub fn apply_venue_delta(
+        &mut self,
+        side: Side,
+        price: Price,
+        new_size: Size,
+        seq: u64,
+        now: Nanos,
+    ) {
+        if let Some(depth) = &mut self.venue_depth {
+            depth.apply_delta(side, price, new_size, seq, VisibleNanos(now));
         }
     }
 
@@ -280,6 +368,11 @@ impl LimitOrderBook {
         self.stats.orders_submitted += 1;
         let mut events = Vec::new();
 
+        // Let venue depth consumed by earlier orders refill up to now
+        if let Some(depth) = &mut self.venue_depth {
+            depth.refresh(VisibleNanos(now));
+        }
+
         // Validate order
         if let Some(reject_reason) = self.validate_order(&req) {
             self.stats.orders_rejected += 1;
@@ -390,6 +483,143 @@ impl LimitOrderBook {
         events
     }
 
+    /// Replace (amend) a resting order's price and/or size.
+    ///
+    /// `new_size` is the new total order quantity, so fills that raced the
+    /// amend cannot overfill. A size-down at the same price keeps queue
+    /// priority; a price change or size-up requeues the order at the back of
+    /// its level and may cross. Rejected amends leave the order unchanged.
+    pub fn replace_order(&mut self, req: ReplaceRequest, now: Nanos) -> Vec<TimestampedEvent> {
+        let mut events = Vec::new();
+
+        let Some(location) = self.orders.get(&req.order_id).cloned() else {
+            // Filled or cancelled before the amend arrived
+            self.stats.replaces_rejected += 1;
+            events.push(self.make_replace_reject_event(
+                req.order_id,
+                RejectReason::Unknown("Order not found".into()),
+                now,
+            ));
+            return events;
+        };
+
+        let new_ticks = price_to_ticks(req.new_price, self.config.tick_size);
+        if !(1..=99).contains(&new_ticks) {
+            self.stats.replaces_rejected += 1;
+            events.push(self.make_replace_reject_event(
+                req.order_id,
+                RejectReason::InvalidPrice,
+                now,
+            ));
+            return events;
+        }
+
+        let book = match location.side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let Some((filled, remaining, post_only)) = book
+            .get(&location.price_ticks)
+            .and_then(|level| level.get_order(req.order_id))
+            .map(|o| {
+                (
+                    o.original_size - o.remaining_size,
+                    o.remaining_size,
+                    o.post_only,
+                )
+            })
+        else {
+            self.stats.replaces_rejected += 1;
+            events.push(self.make_replace_reject_event(
+                req.order_id,
+                RejectReason::Unknown("Order not found".into()),
+                now,
+            ));
+            return events;
+        };
+
+        let new_leaves = req.new_size - filled;
+        if new_leaves <= 0.0 {
+            self.stats.replaces_rejected += 1;
+            events.push(self.make_replace_reject_event(
+                req.order_id,
+                RejectReason::InvalidSize,
+                now,
+            ));
+            return events;
+        }
+
+        let price_changed = new_ticks != location.price_ticks;
+        if price_changed && post_only && self.would_cross(location.side, new_ticks) {
+            self.stats.replaces_rejected += 1;
+            self.stats.post_only_rejections += 1;
+            events.push(self.make_replace_reject_event(
+                req.order_id,
+                RejectReason::Unknown("Post-only order would cross".into()),
+                now,
+            ));
+            return events;
+        }
+
+        let new_price = ticks_to_price(new_ticks, self.config.tick_size);
+        self.stats.orders_replaced += 1;
+
+        let book = match location.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+
+        // Size-down at the same price: amend in place, queue priority kept
+        if !price_changed && new_leaves <= remaining {
+            if let Some(level) = book.get_mut(&location.price_ticks) {
+                level.resize_order(req.order_id, new_leaves);
+            }
+            events.push(self.make_replace_ack_event(
+                req.order_id,
+                new_price,
+                new_leaves,
+                true,
+                now,
+            ));
+            return events;
+        }
+
+        // Otherwise the order loses priority: pull it and re-enter as new
+        let mut removed = None;
+        if let Some(level) = book.get_mut(&location.price_ticks) {
+            removed = level.remove_order(req.order_id);
+            if level.is_empty() {
+                book.remove(&location.price_ticks);
+            }
+        }
+        self.orders.remove(&req.order_id);
+        let Some(mut order) = removed else {
+            return events;
+        };
+        order.price_ticks = new_ticks;
+        order.original_size = req.new_size;
+        order.remaining_size = new_leaves;
+        order.created_at = now;
+
+        events.push(self.make_replace_ack_event(req.order_id, new_price, new_leaves, false, now));
+        events.extend(self.match_order(&mut order, now));
+
+        if order.remaining_size > 0.0 {
+            match order.time_in_force {
+                TimeInForce::Gtc | TimeInForce::Gtt { .. } => self.add_to_book(order),
+                TimeInForce::Ioc | TimeInForce::Fok => {
+                    events.push(self.make_cancel_ack_event(
+                        req.order_id,
+                        order.remaining_size,
+                        now,
+                    ));
+                }
+            }
+        }
+
+        events
+    }
+
     /// Get best bid price.
     pub fn best_bid(&self) -> Option<(Price, Size)> {
         self.bids.last_key_value().map(|(&ticks, level)| {
@@ -471,6 +701,33 @@ impl LimitOrderBook {
 
     // === Private methods ===
 
+    /// Would `submit_order` reject this order against the current book?
+    fn check_order(&self, req: &OrderRequest) -> Option<RejectReason> {
+        if let Some(reason) = self.validate_order(req) {
+            return Some(reason);
+        }
+        let price_ticks = price_to_ticks(req.price, self.config.tick_size);
+        if req.post_only && self.would_cross(req.side, price_ticks) {
+            return Some(RejectReason::Unknown("Post-only order would cross".into()));
+        }
+        None
+    }
+
+    /// Reject an order as part of a refused batch. Unlike validation
+    /// rejects the order consumes an ID, since the venue did receive it.
+    fn reject_batch_leg(
+        &mut self,
+        req: OrderRequest,
+        reason: RejectReason,
+        now: Nanos,
+    ) -> TimestampedEvent {
+        self.stats.orders_submitted += 1;
+        self.stats.orders_rejected += 1;
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        self.make_reject_event(order_id, Some(req.client_order_id), reason, now)
+    }
+
     fn validate_order(&self, req: &OrderRequest) -> Option<RejectReason> {
         let price_ticks = price_to_ticks(req.price, self.config.tick_size);
         if price_ticks < 1 || price_ticks > 99 {
@@ -510,7 +767,7 @@ impl LimitOrderBook {
     }
 
     fn available_liquidity(&self, side: Side, limit_ticks: PriceTicks) -> Size {
-        match side {
+        let resting: Size = match side {
             Side::Buy => self
                 .asks
                 .iter()
@@ -524,7 +781,15 @@ impl LimitOrderBook {
                 .take_while(|(&ticks, _)| ticks >= limit_ticks)
                 .map(|(_, level)| level.total_size)
                 .sum(),
-        }
+        };
+        let venue = self
+            .venue_depth
+            .as_ref()
+            .map(|depth| {
+                depth.available_liquidity(side, ticks_to_price(limit_ticks, self.config.tick_size))
+            })
+            .unwrap_or(0.0);
+        resting + venue
     }
 
     fn match_order(&mut self, order: &mut BookOrder, now: Nanos) -> Vec<TimestampedEvent> {
@@ -562,6 +827,55 @@ impl LimitOrderBook {
             }
         }
 
+        if order.remaining_size > 0.0 {
+            events.extend(self.sweep_venue_depth(order, now));
+        }
+
+        events
+    }
+
+    /// Fill what our own resting orders could not from recorded venue depth.
+    fn sweep_venue_depth(&mut self, order: &mut BookOrder, now: Nanos) -> Vec<TimestampedEvent> {
+        let Some(depth) = &mut self.venue_depth else {
+            return Vec::new();
+        };
+        let request = TakerOrderRequest {
+            order_id: order.order_id,
+            client_order_id: order.client_order_id.clone(),
+            token_id: self.token_id.clone(),
+            side: order.side,
+            limit_price: ticks_to_price(order.price_ticks, self.config.tick_size),
+            size: order.remaining_size,
+            // Whatever is left rests or is cancelled by the caller
+            time_in_force: TimeInForce::Ioc,
+            trader_id: order.trader_id.clone(),
+        };
+        let result =
+            TakerFillModel::new(depth.config().clone()).execute(&request, depth, VisibleNanos(now));
+
+        let ack_time = now + self.config.ack_latency_ns;
+        let mut events = Vec::with_capacity(result.fills.len());
+        for fill in result.fills {
+            order.remaining_size = (order.remaining_size - fill.size).max(0.0);
+            let fill_id = format!("fill_{}", self.next_fill_id);
+            self.next_fill_id += 1;
+            self.stats.fills += 1;
+            self.stats.total_volume += fill.size * fill.price;
+            events.push(TimestampedEvent::with_times(
+                now,
+                ack_time,
+                StreamSource::OrderManagement as u8,
+                Event::Fill {
+                    order_id: order.order_id,
+                    price: fill.price,
+                    size: fill.size,
+                    is_maker: false,
+                    leaves_qty: order.remaining_size,
+                    fee: fill.fee,
+                    fill_id: Some(fill_id),
+                },
+            ));
+        }
         events
     }
 
@@ -793,14 +1107,53 @@ impl LimitOrderBook {
             },
         )
     }
+
+    fn make_replace_ack_event(
+        &self,
+        order_id: OrderId,
+        price: Price,
+        leaves_qty: Size,
+        kept_priority: bool,
+        now: Nanos,
+    ) -> TimestampedEvent {
+        TimestampedEvent::with_times(
+            now,
+            now + self.config.ack_latency_ns,
+            StreamSource::OrderManagement as u8,
+            Event::ReplaceAck {
+                order_id,
+                price,
+                leaves_qty,
+                kept_priority,
+            },
+        )
+    }
+
+    fn make_replace_reject_event(
+        &self,
+        order_id: OrderId,
+        reason: RejectReason,
+        now: Nanos,
+    ) -> TimestampedEvent {
+        TimestampedEvent::with_times(
+            now,
+            now + self.config.ack_latency_ns,
+            StreamSource::OrderManagement as u8,
+            Event::ReplaceReject { order_id, reason },
+        )
+    }
 }
 
 /// Multi-token matching engine manager.
+#[derive(Serialize, Deserialize)]
 pub struct MatchingEngine {
     books: HashMap<String, LimitOrderBook>,
     config: MatchingConfig,
     /// Aggregate statistics
     pub total_stats: MatchingStats,
+    /// Impact model for recorded venue depth (None = venue depth not mirrored).
+    #[serde(default)]
+    market_impact: Option<MarketImpactConfig>,
 }
 
 impl MatchingEngine {
@@ -809,15 +1162,63 @@ impl MatchingEngine {
             books: HashMap::new(),
             config,
             total_stats: MatchingStats::default(),
+            market_impact: None,
         }
     }
 
+    /// Mirror recorded venue depth in every book and let marketable orders
+    /// sweep it, with `impact` governing how consumed depth comes back.
+    pub fn set_market_impact(&mut self, impact: MarketImpactConfig) {
+        for book in self.books.values_mut() {
+            book.enable_venue_depth(impact.clone());
+        }
+        self.market_impact = Some(impact);
+    }
+
     /// Get or create a book for a token.
     pub fn get_or_create_book(&mut self, token_id: &str) -> &mut LimitOrderBook {
         let config = self.config.clone();
-        self.books
-            .entry(token_id.to_string())
-            .or_insert_with(|| LimitOrderBook::new(token_id, config))
+        let impact = &self.market_impact;
+        self.books.entry(token_id.to_string()).or_insert_with(|| {
+            let mut book = LimitOrderBook::new(token_id, config);
+            if let Some(impact) = impact {
+                book.enable_venue_depth(impact.clone());
+            }
+            book
+        })
+    }
+
+    /// Apply a recorded book snapshot to the token's venue depth.
+    /// No-op unless a market impact model is set.
+    pub fn apply_book_snapshot(
+        &mut self,
+        token_id: &str,
+        bids: &[Level],
+        asks: &[Level],
+        seq: u64,
+        now: Nanos,
+    ) {
+        if self.market_impact.is_some() {
+            self.get_or_create_book(token_id)
+                .apply_venue_snapshot(bids, asks, seq, now);
+        }
+    }
+
+    /// Apply a recorded level update to the token's venue depth.
+    /// No-op unless a market impact model is set.
+    pub fn apply_book_delta(
+        &mut self,
+        token_id: &str,
+        side: Side,
+        price: Price,
+        new_size: Size,
+        seq: u64,
+        now: Nanos,
+    ) {
+        if self.market_impact.is_some() {
+            self.get_or_create_book(token_id)
+                .apply_venue_delta(side, price, new_size, seq, now);
+        }
     }
 
     /// Submit an order.
@@ -829,6 +1230,68 @@ impl MatchingEngine {
         events
     }
 
+    /// Submit several orders in one venue message.
+    ///
+    /// In `AllOrNone` mode the batch is first replayed against scratch copies
+    /// of the books, so each order is checked with the earlier orders of the
+    /// batch already applied (two FOK orders cannot both claim the same
+    /// liquidity, and a post-only order cannot cross an earlier leg). If any
+    /// would be rejected, all of them are, with the failing order's reason
+    /// named on each. In `Partial` mode the orders are processed in sequence
+    /// exactly as individual submissions.
+    pub fn submit_batch(
+        &mut self,
+        orders: Vec<OrderRequest>,
+        mode: BatchMode,
+        now: Nanos,
+    ) -> Vec<TimestampedEvent> {
+        let mut events = Vec::new();
+        if orders.is_empty() {
+            return events;
+        }
+        self.total_stats.batches_submitted += 1;
+
+        if mode == BatchMode::AllOrNone {
+            let mut scratch: HashMap<String, LimitOrderBook> = HashMap::new();
+            let mut failure = None;
+            for (index, req) in orders.iter().enumerate() {
+                let book = scratch
+                    .entry(req.token_id.clone())
+                    .or_insert_with(|| self.get_or_create_book(&req.token_id).clone());
+                if let Some(reason) = book.check_order(req) {
+                    failure = Some((index, reason));
+                    break;
+                }
+                book.submit_order(req.clone(), now);
+            }
+
+            if let Some((failed_index, reason)) = failure {
+                for (index, req) in orders.into_iter().enumerate() {
+                    let leg_reason = if index == failed_index {
+                        reason.clone()
+                    } else {
+                        RejectReason::Unknown(format!(
+                            "Batch rejected: order {} failed with {:?}",
+                            failed_index, reason
+                        ))
+                    };
+                    let book = self.get_or_create_book(&req.token_id);
+                    events.push(book.reject_batch_leg(req, leg_reason, now));
+                }
+                self.total_stats.batches_rejected += 1;
+                self.update_total_stats();
+                return events;
+            }
+        }
+
+        for req in orders {
+            let token_id = req.token_id.clone();
+            events.extend(self.get_or_create_book(&token_id).submit_order(req, now));
+        }
+        self.update_total_stats();
+        events
+    }
+
     /// Cancel an order.
     pub fn cancel_order(
         &mut self,
@@ -853,6 +1316,29 @@ impl MatchingEngine {
         }
     }
 
+    /// Replace (amend) an order.
+    pub fn replace_order(
+        &mut self,
+        token_id: &str,
+        req: ReplaceRequest,
+        now: Nanos,
+    ) -> Vec<TimestampedEvent> {
+        if let Some(book) = self.books.get_mut(token_id) {
+            let events = book.replace_order(req, now);
+            self.update_total_stats();
+            events
+        } else {
+            vec![TimestampedEvent::new(
+                now,
+                StreamSource::OrderManagement as u8,
+                Event::ReplaceReject {
+                    order_id: req.order_id,
+                    reason: RejectReason::Unknown("Token not found".into()),
+                },
+            )]
+        }
+    }
+
     /// Get a book (read-only).
     pub fn get_book(&self, token_id: &str) -> Option<&LimitOrderBook> {
         self.books.get(token_id)
@@ -864,7 +1350,12 @@ impl MatchingEngine {
     }
 
     fn update_total_stats(&mut self) {
-        self.total_stats = MatchingStats::default();
+        // Batch counters live on the engine, not on any one book
+        self.total_stats = MatchingStats {
+            batches_submitted: self.total_stats.batches_submitted,
+            batches_rejected: self.total_stats.batches_rejected,
+            ..Default::default()
+        };
         for book in self.books.values() {
             self.total_stats.orders_submitted += book.stats.orders_submitted;
             self.total_stats.orders_accepted += book.stats.orders_accepted;
@@ -874,6 +1365,8 @@ impl MatchingEngine {
             self.total_stats.total_volume += book.stats.total_volume;
             self.total_stats.self_trades_prevented += book.stats.self_trades_prevented;
             self.total_stats.post_only_rejections += book.stats.post_only_rejections;
+            self.total_stats.orders_replaced += book.stats.orders_replaced;
+            self.total_stats.replaces_rejected += book.stats.replaces_rejected;
         }
     }
 }
@@ -1090,4 +1583,205 @@ mod tests {
             }
         }
     }
+
+    fn maker_fill_id(events: &[TimestampedEvent]) -> Option<OrderId> {
+        events.iter().find_map(|e| match e.event {
+            Event::Fill {
+                order_id,
+                is_maker: true,
+                ..
+            } => Some(order_id),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_replace_queue_priority() {
+        let mut book = LimitOrderBook::new("token123", MatchingConfig::default());
+        book.submit_order(make_order(Side::Buy, 0.45, 100.0, "trader1"), 1000);
+        book.submit_order(make_order(Side::Buy, 0.45, 100.0, "trader2"), 2000);
+
+        // Size-down at the same price keeps priority
+        let replace = ReplaceRequest {
+            order_id: 1,
+            new_price: 0.45,
+            new_size: 60.0,
+        };
+        let events = book.replace_order(replace, 3000);
+        assert!(events.iter().any(|e| matches!(
+            e.event,
+            Event::ReplaceAck { kept_priority: true, leaves_qty, .. } if leaves_qty == 60.0
+        )));
+        let events = book.submit_order(make_order(Side::Sell, 0.45, 10.0, "trader3"), 4000);
+        assert_eq!(maker_fill_id(&events), Some(1));
+
+        // Size-up loses priority
+        let replace = ReplaceRequest {
+            order_id: 1,
+            new_price: 0.45,
+            new_size: 200.0,
+        };
+        let events = book.replace_order(replace, 5000);
+        assert!(events.iter().any(|e| matches!(
+            e.event,
+            Event::ReplaceAck { kept_priority: false, leaves_qty, .. } if leaves_qty == 190.0
+        )));
+        let events = book.submit_order(make_order(Side::Sell, 0.45, 10.0, "trader3"), 6000);
+        assert_eq!(maker_fill_id(&events), Some(2));
+        assert_eq!(book.stats.orders_replaced, 2);
+    }
+
+    #[test]
+    fn test_replace_price_change_crosses() {
+        let mut book = LimitOrderBook::new("token123", MatchingConfig::default());
+        book.submit_order(make_order(Side::Sell, 0.50, 40.0, "trader2"), 1000);
+        book.submit_order(make_order(Side::Buy, 0.45, 100.0, "trader1"), 2000);
+
+        let replace = ReplaceRequest {
+            order_id: 2,
+            new_price: 0.50,
+            new_size: 100.0,
+        };
+        let events = book.replace_order(replace, 3000);
+        assert!(matches!(
+            events[0].event,
+            Event::ReplaceAck {
+                kept_priority: false,
+                ..
+            }
+        ));
+        assert!(events.iter().any(|e| matches!(
+            e.event,
+            Event::Fill { order_id: 2, is_maker: false, size, leaves_qty, .. }
+                if size == 40.0 && leaves_qty == 60.0
+        )));
+        assert_eq!(book.best_bid(), Some((0.50, 60.0)));
+        assert_eq!(book.best_ask(), None);
+    }
+
+    #[test]
+    fn test_replace_counts_filled_quantity() {
+        let mut book = LimitOrderBook::new("token123", MatchingConfig::default());
+        book.submit_order(make_order(Side::Buy, 0.45, 100.0, "trader1"), 1000);
+        // 30 fills while the amend is in flight
+        book.submit_order(make_order(Side::Sell, 0.45, 30.0, "trader2"), 2000);
+
+        let replace = ReplaceRequest {
+            order_id: 1,
+            new_price: 0.45,
+            new_size: 20.0,
+        };
+        let events = book.replace_order(replace, 3000);
+        assert!(matches!(
+            events[0].event,
+            Event::ReplaceReject {
+                reason: RejectReason::InvalidSize,
+                ..
+            }
+        ));
+        assert_eq!(book.best_bid(), Some((0.45, 70.0)));
+
+        let replace = ReplaceRequest {
+            order_id: 1,
+            new_price: 0.45,
+            new_size: 50.0,
+        };
+        book.replace_order(replace, 4000);
+        assert_eq!(book.best_bid(), Some((0.45, 20.0)));
+
+        // Fully filled orders can no longer be amended
+        book.submit_order(make_order(Side::Sell, 0.45, 20.0, "trader2"), 5000);
+        let replace = ReplaceRequest {
+            order_id: 1,
+            new_price: 0.44,
+            new_size: 50.0,
+        };
+        let events = book.replace_order(replace, 6000);
+        assert!(matches!(events[0].event, Event::ReplaceReject { .. }));
+        assert_eq!(book.stats.replaces_rejected, 2);
+    }
+
+    #[test]
+    fn test_batch_all_or_none_and_partial() {
+        let mut engine = MatchingEngine::new(MatchingConfig::default());
+        engine.submit_order(make_order(Side::Sell, 0.50, 100.0, "maker"), 1000);
+
+        let mut yes_leg = make_order(Side::Buy, 0.45, 50.0, "arb");
+        yes_leg.post_only = true;
+        let mut no_leg = make_order(Side::Buy, 0.52, 50.0, "arb");
+        no_leg.token_id = "token456".into();
+        // Post-only leg that would cross the resting ask
+        let mut bad_leg = make_order(Side::Buy, 0.50, 50.0, "arb");
+        bad_leg.post_only = true;
+
+        let events = engine.submit_batch(
+            vec![yes_leg.clone(), no_leg.clone(), bad_leg.clone()],
+            BatchMode::AllOrNone,
+            2000,
+        );
+        assert_eq!(events.len(), 3);
+        assert!(events
+            .iter()
+            .all(|e| matches!(e.event, Event::OrderReject { order_id, .. } if order_id > 0)));
+        assert_eq!(engine.get_book("token123").unwrap().best_bid(), None);
+        assert_eq!(engine.get_book("token456").unwrap().order_count(), 0);
+        assert_eq!(engine.total_stats.batches_rejected, 1);
+
+        let events = engine.submit_batch(vec![yes_leg, no_leg, bad_leg], BatchMode::Partial, 3000);
+        let acks = events
+            .iter()
+            .filter(|e| matches!(e.event, Event::OrderAck { .. }))
+            .count();
+        let rejects = events
+            .iter()
+            .filter(|e| matches!(e.event, Event::OrderReject { .. }))
+            .count();
+        assert_eq!((acks, rejects), (2, 1));
+        assert_eq!(
+            engine.get_book("token123").unwrap().best_bid(),
+            Some((0.45, 50.0))
+        );
+        assert_eq!(
+            engine.get_book("token456").unwrap().best_bid(),
+            Some((0.52, 50.0))
+        );
+        assert_eq!(engine.total_stats.batches_submitted, 2);
+        assert_eq!(engine.total_stats.batches_rejected, 1);
+    }
+
+    #[test]
+    fn test_batch_all_or_none_fok_legs_share_liquidity() {
+        let mut engine = MatchingEngine::new(MatchingConfig::default());
+        engine.submit_order(make_order(Side::Sell, 0.50, 100.0, "maker"), 1000);
+
+        // Each leg fits the book on its own, but not both together
+        let mut leg = make_order(Side::Buy, 0.50, 60.0, "taker");
+        leg.time_in_force = TimeInForce::Fok;
+
+        let events =
+            engine.submit_batch(vec![leg.clone(), leg.clone()], BatchMode::AllOrNone, 2000);
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|e| matches!(e.event, Event::OrderReject { .. })));
+        assert_eq!(
+            engine.get_book("token123").unwrap().best_ask(),
+            Some((0.50, 100.0))
+        );
+        assert_eq!(engine.total_stats.fills, 0);
+        assert_eq!(engine.total_stats.batches_rejected, 1);
+
+        // A post-only leg crossing an earlier leg of the same batch
+        let resting = make_order(Side::Sell, 0.40, 10.0, "arb");
+        let mut crossing = make_order(Side::Buy, 0.40, 10.0, "arb");
+        crossing.post_only = true;
+        let events = engine.submit_batch(vec![resting, crossing], BatchMode::AllOrNone, 3000);
+        assert!(events
+            .iter()
+            .all(|e| matches!(e.event, Event::OrderReject { .. })));
+        assert_eq!(engine.get_book("token123").unwrap().order_count(), 1);
+
+        let events = engine.submit_batch(vec![leg], BatchMode::AllOrNone, 4000);
+        assert!(events.iter().any(|e| matches!(e.event, Event::Fill { .. })));
+    }
 }
@@ -75,6 +75,8 @@ pub mod merge;
 pub mod time_windows;
 // Taker slippage and fill model for realistic execution modeling
 pub mod taker_slippage;
+// Market impact and liquidity replenishment for repeated taker aggression
+pub mod market_impact;
 pub mod data_contract;
 pub mod data_pipeline;
 pub mod disclaimers;
@@ -96,6 +98,10 @@ pub mod integrity;
 pub mod invariants;
 pub mod ledger;
 pub mod latency;
+// Empirical latency profiles exported from the live system
+pub mod live_latency;
+// Time- and load-dependent latency regimes (rollovers, delta bursts, belief vol)
+pub mod latency_regime;
 // First-class latency visibility model with order lifecycle scheduling for 15M Up/Down
 pub mod latency_visibility;
 // Trade span instrumentation for backtest (mirrors live TradeSpan model)
@@ -117,6 +123,30 @@ pub mod queue;
 pub mod queue_model;
 pub mod risk;
 pub mod sensitivity;
+// Strategy parameter sweeps (grid / random / Latin hypercube) over StrategyParams
+pub mod param_sweep;
+// Walk-forward train/test splits with out-of-sample equity stitching
+pub mod walk_forward;
+// Multi-market runs: cross-market risk gate and per-market attribution
+pub mod multi_market;
+// Asynchronous backtest jobs (bounded queue + worker pool) for the HTTP API
+pub mod job_queue;
+// SQLite dataset loading (book snapshots, trade prints, dome order events)
+pub mod sqlite_dataset;
+// Runtime-loaded strategies from sandboxed WASM modules
+pub mod wasm_strategy;
+// Run diffing: component fingerprint changes and first divergent decision record
+pub mod run_diff;
+// Monte Carlo robustness of run PnL (block bootstrap, reshuffle, fill drop); gate suite input
+pub mod monte_carlo;
+// Parquet export of recorded streams (market/day partitions) and columnar replay feed
+pub mod columnar_dataset;
+// Lazy k-way merge feed over chunked L2 / trade print / oracle storage windows
+pub mod streaming_feed;
+// Orchestrator checkpoints at window boundaries for resume and what-if forks
+pub mod checkpoint;
+// Live session journal and live-vs-backtest per-order reconciliation
+pub mod live_reconciliation;
 pub mod sim_adapter;
 pub mod snapshot_sufficiency;
 pub mod strategy;
@@ -207,8 +237,8 @@ pub use data_contract::{
     RunGrade, StreamAvailability, StrategyCompatibility, StrategyRequirements, TradeHistory,
 };
 pub use events::{
-    Event, EventPriority, Level, MarketStatus, OrderId, OrderType, Price, RejectReason, Resolution,
-    Side, Size, TimeInForce, TimestampedEvent, TokenId,
+    BatchMode, Event, EventPriority, Level, MarketStatus, OrderId, OrderType, Price, RejectReason,
+    Resolution, Side, Size, TimeInForce, TimestampedEvent, TokenId,
 };
 pub use example_strategy::{MarketMakerStrategy, MomentumStrategy};
 pub use feed::{MarketDataFeed, MarketDataFeedExt, VecFeed};
@@ -236,7 +266,16 @@ pub use ledger::{
     LedgerConfig, LedgerEntry, LedgerMetadata, LedgerPosting, LedgerStats, ViolationType,
     from_amount, to_amount, AMOUNT_SCALE,
 };
-pub use latency::{LatencyConfig, LatencyDistribution, LatencySampler, LatencyStats};
+pub use latency::{
+    EmpiricalLatency, LatencyConfig, LatencyDistribution, LatencySampler, LatencyStats,
+};
+// Empirical latency profiles exported from the live system
+pub use live_latency::LiveLatencyProfile;
+// Time- and load-dependent latency regimes
+pub use latency_regime::{
+    ActiveRegimes, DeltaBurstRegime, LatencyRegimeConfig, LatencyRegimeState, LatencyStress,
+    RolloverRegime, VolatilityRegime,
+};
 // First-class latency visibility model with order lifecycle scheduling
 pub use latency_visibility::{
     JitterCategory, LatencyVisibilityApplier, LatencyVisibilityModel, LatencyVisibilityStats,
@@ -248,7 +287,7 @@ pub use latency_visibility::{
 };
 pub use matching::{
     CancelRequest, FeeConfig, LimitOrderBook, MatchingConfig, MatchingEngine, MatchingStats,
-    OrderRequest, SelfTradeMode,
+    OrderRequest, ReplaceRequest, SelfTradeMode,
 };
 pub use metrics::{
     AdverseSelectionAtHorizon, AdverseSelectionMetrics, BacktestReport, FillMetrics,
@@ -262,8 +301,9 @@ pub use normalize::{
 };
 pub use oms::{
     MarketStatus as OmsMarketStatus, OmsOrder, OmsStats, OrderManagementSystem, OrderState,
-    RateLimiter, TerminalReason, ValidationError, VenueConstraints,
+    PendingReplace, RateLimiter, TerminalReason, ValidationError, VenueConstraints,
 };
+pub use crate::venue_limits::POLYMARKET_MAX_BATCH_SIZE;
 pub use orchestrator::{
     BacktestConfig, BacktestOperatingMode, BacktestOrchestrator, BacktestResults, 
     MakerFillModel, ProductionGradeViolation, determine_operating_mode, format_operating_mode_banner,
@@ -296,6 +336,49 @@ pub use sensitivity::{
     SamplingSweepResults, SensitivityConfig, SensitivityReport, SweepPointMetrics,
     TrustRecommendation,
 };
+pub use param_sweep::{
+    ParamAxis, ParamDomain, ParamSweepConfig, ParamSweepReport, ParamSweepRunner, SweepId,
+    SweepPoint, SweepRunOutcome, SweepSampling, MAX_SWEEP_POINTS,
+};
+pub use walk_forward::{
+    SampleScope, WalkForwardConfig, WalkForwardFold, WalkForwardFoldOutcome, WalkForwardId,
+    WalkForwardObjective, WalkForwardReport, WalkForwardRunner,
+};
+pub use multi_market::{
+    asset_of, attribution_by_asset, format_attribution, MarketAttribution, PortfolioRiskGate,
+    PortfolioRiskStats,
+};
+pub use job_queue::{
+    BacktestJobQueue, BacktestJobSpec, JobDataset, JobDatasetLoader, JobId, JobQueueConfig,
+    JobQueueError, JobState, JobStatus, RunControl, SqliteDatasetLoader,
+};
+pub use sqlite_dataset::{load_events_from_sqlite, MarketLoadCounts};
+pub use columnar_dataset::{
+    columnar_dataset_fingerprint, ColumnarDataset, ColumnarExportConfig, ColumnarExporter,
+    ColumnarManifest, ColumnarPartition, ColumnarQuery, ColumnarReplayFeed, ColumnarStream,
+    COLUMNAR_FORMAT_VERSION, COLUMNAR_MANIFEST_FILE, DEFAULT_COLUMNAR_BATCH_ROWS,
+};
+pub use checkpoint::{
+    BacktestCheckpoint, CheckpointPolicy, CHECKPOINT_FILE_EXTENSION, CHECKPOINT_FORMAT_VERSION,
+};
+pub use streaming_feed::{
+    EventChunkSource, L2ChunkSource, OracleRoundChunkSource, StreamingMergeFeed, TimeWindows,
+    TradePrintChunkSource, VecChunkSource, DEFAULT_CHUNK_SPAN_NS,
+};
+pub use monte_carlo::{
+    MonteCarloAnalyzer, MonteCarloConfig, MonteCarloDistribution, MonteCarloInput,
+    MonteCarloMethod, MonteCarloReport, PercentileSummary,
+};
+pub use live_reconciliation::{
+    collect_order_executions, marks_from_events, ExecutionFlow, LiveReconciler, LiveSession,
+    LiveSessionHeader, LiveSessionRecorder, OrderExecution, OrderReconciliation,
+    ReconciliationClass, ReconciliationConfig, ReconciliationReport, ReconciliationSummary,
+    LIVE_SESSION_FORMAT_VERSION, LIVE_TRACE_LIMIT,
+};
+pub use run_diff::{
+    diff_runs, ComponentDiff, DecisionTrace, FieldChange, RunDiff, TraceComparison,
+    TraceContextRow, TraceDivergence, TraceRecord, TraceRecordKind, DEFAULT_DECISION_TRACE_LIMIT,
+};
 pub use maker_validation::{
     ConservativeConfig, MakerExecutionProfile, MakerFragilityFlags, MakerProfileConfigs,
     MakerSurvivalCriteria, MakerSurvivalStatus, MakerValidationConfig, MakerValidationResult,
@@ -372,8 +455,8 @@ pub use oracle::{
 pub use sim_adapter::{OmsParityMode, OmsParityStats, SimulatedOrderSender};
 pub use strategy::{
     BookSnapshot, CancelAck, FillNotification, OpenOrder, OrderAck, OrderReject, OrderSender,
-    Position, Strategy, StrategyCancel, StrategyContext, StrategyFactory, StrategyOrder,
-    StrategyParams, TimerEvent, TradePrint,
+    Position, ReplaceAck, ReplaceReject, Strategy, StrategyCancel, StrategyContext,
+    StrategyFactory, StrategyOrder, StrategyParams, StrategyReplace, TimerEvent, TradePrint,
 };
 pub use validation::{
     Checkpoint, DeterministicSeed, EventTracer, InvariantChecker, InvariantSummary,
@@ -457,6 +540,7 @@ pub use trust_gate::{
     TrustDecision, TrustFailureReason, TrustGate, TrustGateConfig, TrustGateError,
 };
 pub use strategy_factory::{available_strategies, make_strategy};
+pub use wasm_strategy::{WasmSandboxLimits, WasmStrategy, WasmStrategyFactory};
 pub use window_pnl::{
     WindowAccountingEngine, WindowAccountingError, WindowId, WindowPnL, WindowPnLSeries,
     align_to_window_start, parse_window_start_from_slug,
@@ -482,7 +566,9 @@ pub use run_artifact::{
     TrustDecisionSummary, TrustLevelDto, TrustStatus, WindowPnLPoint, WindowPnlHistogramResponse, 
     RUN_ARTIFACT_API_VERSION, RUN_ARTIFACT_STORAGE_VERSION, WINDOW_PNL_HISTOGRAM_SCHEMA_VERSION,
 };
-pub use artifact_store::{ArtifactStore, ArtifactStoreError, ArtifactStoreStats};
+pub use artifact_store::{
+    ArtifactStore, ArtifactStoreError, ArtifactStoreStats, RunGroupKind, RunGroupMember,
+};
 pub use publication::{
     PublicationDecision, PublicationError, PublicationGate, PublicationGateError,
     PublicationStatus,
@@ -529,6 +615,11 @@ pub use taker_slippage::{
     DEFAULT_TICK_SIZE as TAKER_DEFAULT_TICK_SIZE, MAX_PRICE as TAKER_MAX_PRICE,
     MIN_ORDER_SIZE as TAKER_MIN_ORDER_SIZE, MIN_PRICE as TAKER_MIN_PRICE,
 };
+// Market impact and liquidity replenishment
+pub use market_impact::{
+    LevelRefillEstimator, LevelRefillRates, MarketImpact, MarketImpactConfig, ReplenishmentModel,
+    WindowReset,
+};
 // Trade span instrumentation (mirrors live TradeSpan model)
 pub use latency_spans::{
     DecisionSpan, EventKind, LatencyPercentiles as SpanLatencyPercentiles, LatencySummary, OrderSpan,
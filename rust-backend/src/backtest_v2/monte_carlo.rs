@@ -0,0 +1,557 @@
+//! Monte Carlo Robustness Analysis
+//!
+//! Resamples a completed run's PnL to answer "how bad could this plausibly
+//! have been?" rather than trusting the single realized path.
+//!
+//! # Methods
+//!
+//! - **Window block bootstrap**: Resample the `WindowPnL` series (net PnL per
+//!   15-minute window) in contiguous blocks, preserving short-range
+//!   autocorrelation between neighbouring windows.
+//! - **Trade reshuffle**: Randomly permute the per-fill PnL sequence. Total PnL
+//!   is unchanged; drawdown and ruin expose path dependence.
+//! - **Fill drop**: Drop each fill independently with a fixed probability,
+//!   modelling fills that would not have happened live.
+//!
+//! Each method produces distributions of total PnL, max drawdown and Sharpe,
+//! plus a ruin probability. The report is attached to `BacktestResults` and is
+//! an input to the gate suite (Gate D): a run whose 5th-percentile total PnL is
+//! negative under any method cannot be Trusted.
+//!
+//! # Determinism
+//!
+//! Every method draws from a `ChaCha8Rng` seeded from `MonteCarloConfig::seed`
+//! and the method, so the same inputs always produce the same report.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// Monte Carlo robustness configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+    /// Whether to run the analysis.
+    pub enabled: bool,
+    /// Resampled paths per method.
+    pub iterations: usize,
+    /// RNG seed.
+    pub seed: u64,
+    /// Block length (in 15-minute windows) for the window bootstrap.
+    pub block_length: usize,
+    /// Probability that each fill is dropped in the fill-drop method.
+    pub fill_drop_probability: f64,
+    /// A path is ruined when cumulative PnL falls to `-ruin_fraction * initial_capital`.
+    pub ruin_fraction: f64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            iterations: 1000,
+            seed: 0x4d43_5242,
+            block_length: 4,
+            fill_drop_probability: 0.10,
+            ruin_fraction: 0.5,
+        }
+    }
+}
+
+impl MonteCarloConfig {
+    /// Disabled configuration.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.iterations == 0 {
+            return Err("iterations must be > 0".into());
+        }
+        if self.block_length == 0 {
+            return Err("block_length must be > 0".into());
+        }
+        if !(0.0..1.0).contains(&self.fill_drop_probability) {
+            return Err(format!(
+                "fill_drop_probability must be in [0, 1), got {}",
+                self.fill_drop_probability
+            ));
+        }
+        if !(self.ruin_fraction > 0.0 && self.ruin_fraction <= 1.0) {
+            return Err(format!("ruin_fraction must be in (0, 1], got {}", self.ruin_fraction));
+        }
+        Ok(())
+    }
+
+    /// Hash of every setting, for run fingerprinting.
+    pub fn fingerprint_hash(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.enabled.hash(&mut hasher);
+        self.iterations.hash(&mut hasher);
+        self.seed.hash(&mut hasher);
+        self.block_length.hash(&mut hasher);
+        self.fill_drop_probability.to_bits().hash(&mut hasher);
+        self.ruin_fraction.to_bits().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// =============================================================================
+// INPUT
+// =============================================================================
+
+/// PnL samples from a completed run.
+#[derive(Debug, Clone, Default)]
+pub struct MonteCarloInput {
+    /// Net PnL per 15-minute window, in window order.
+    pub window_pnls: Vec<f64>,
+    /// PnL attributed to each fill, in fill order.
+    pub trade_pnls: Vec<f64>,
+    /// Starting capital (for ruin probability).
+    pub initial_capital: f64,
+}
+
+impl MonteCarloInput {
+    /// Window PnL samples from a run's `WindowPnLSeries` (if present).
+    pub fn from_results(
+        results: &crate::backtest_v2::orchestrator::BacktestResults,
+        initial_capital: f64,
+    ) -> Self {
+        let window_pnls = results
+            .window_pnl
+            .as_ref()
+            .map(|series| {
+                series
+                    .windows
+                    .iter()
+                    .map(|w| crate::backtest_v2::ledger::from_amount(w.net_pnl))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            window_pnls,
+            trade_pnls: Vec::new(),
+            initial_capital,
+        }
+    }
+
+    /// Per-fill PnL from a cumulative PnL history (one entry per fill).
+    pub fn with_cumulative_trade_pnl(mut self, cumulative: &[f64]) -> Self {
+        let mut prev = 0.0;
+        self.trade_pnls = cumulative
+            .iter()
+            .map(|&pnl| {
+                let delta = pnl - prev;
+                prev = pnl;
+                delta
+            })
+            .collect();
+        self
+    }
+}
+
+// =============================================================================
+// REPORT
+// =============================================================================
+
+/// Resampling method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonteCarloMethod {
+    WindowBlockBootstrap,
+    TradeReshuffle,
+    FillDrop,
+}
+
+impl MonteCarloMethod {
+    fn seed_salt(self) -> u64 {
+        match self {
+            Self::WindowBlockBootstrap => 0x01,
+            Self::TradeReshuffle => 0x02,
+            Self::FillDrop => 0x03,
+        }
+    }
+}
+
+/// Percentile summary of a sampled metric.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PercentileSummary {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl PercentileSummary {
+    /// Summarize samples (linear interpolation between order statistics).
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let pct = |p: f64| {
+            let rank = p * (sorted.len() - 1) as f64;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+        };
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            p5: pct(0.05),
+            p25: pct(0.25),
+            p50: pct(0.50),
+            p75: pct(0.75),
+            p95: pct(0.95),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Distributions produced by one resampling method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloDistribution {
+    pub method: MonteCarloMethod,
+    /// Samples per path (windows or fills).
+    pub sample_count: usize,
+    pub iterations: usize,
+    pub total_pnl: PercentileSummary,
+    pub max_drawdown: PercentileSummary,
+    /// Per-period Sharpe (mean / std of path samples, not annualized).
+    pub sharpe: PercentileSummary,
+    /// Fraction of paths with total PnL > 0.
+    pub positive_probability: f64,
+    /// Fraction of paths that hit the ruin threshold.
+    pub ruin_probability: f64,
+}
+
+/// Monte Carlo robustness report for a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    pub config: MonteCarloConfig,
+    pub initial_capital: f64,
+    /// Realized total PnL of the run (from windows if available, else fills).
+    pub observed_total_pnl: f64,
+    pub distributions: Vec<MonteCarloDistribution>,
+}
+
+impl MonteCarloReport {
+    /// Worst 5th-percentile total PnL across methods (None if nothing was resampled).
+    pub fn p5_total_pnl(&self) -> Option<f64> {
+        self.distributions.iter().map(|d| d.total_pnl.p5).reduce(f64::min)
+    }
+
+    /// Worst ruin probability across methods.
+    pub fn ruin_probability(&self) -> f64 {
+        self.distributions.iter().map(|d| d.ruin_probability).fold(0.0, f64::max)
+    }
+
+    /// Distribution for a method, if it ran.
+    pub fn distribution(&self, method: MonteCarloMethod) -> Option<&MonteCarloDistribution> {
+        self.distributions.iter().find(|d| d.method == method)
+    }
+
+    /// Format as compact summary.
+    pub fn format_summary(&self) -> String {
+        let mut out = String::new();
+        out.push_str("=== MONTE CARLO ROBUSTNESS ===\n");
+        out.push_str(&format!("Observed total PnL: ${:.2}\n", self.observed_total_pnl));
+        if self.distributions.is_empty() {
+            out.push_str("No PnL samples to resample\n");
+        }
+        for d in &self.distributions {
+            out.push_str(&format!(
+                "[{:?}] {} paths x {} samples\n",
+                d.method, d.iterations, d.sample_count
+            ));
+            out.push_str(&format!(
+                "    Total PnL:    p5 ${:.2}  p50 ${:.2}  p95 ${:.2}\n",
+                d.total_pnl.p5, d.total_pnl.p50, d.total_pnl.p95
+            ));
+            out.push_str(&format!(
+                "    Max drawdown: p50 ${:.2}  p95 ${:.2}\n",
+                d.max_drawdown.p50, d.max_drawdown.p95
+            ));
+            out.push_str(&format!("    Sharpe p50:   {:.4}\n", d.sharpe.p50));
+            out.push_str(&format!(
+                "    P(PnL > 0):   {:.1}%   P(ruin): {:.2}%\n",
+                d.positive_probability * 100.0,
+                d.ruin_probability * 100.0
+            ));
+        }
+        out
+    }
+}
+
+// =============================================================================
+// ANALYZER
+// =============================================================================
+
+/// Runs the resampling methods over a run's PnL samples.
+pub struct MonteCarloAnalyzer {
+    config: MonteCarloConfig,
+}
+
+impl MonteCarloAnalyzer {
+    pub fn new(config: MonteCarloConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// Run every applicable method. Methods without samples are skipped.
+    pub fn analyze(&self, input: &MonteCarloInput) -> MonteCarloReport {
+        let mut distributions = Vec::new();
+
+        if !input.window_pnls.is_empty() {
+            let block = self.config.block_length.min(input.window_pnls.len());
+            distributions.push(self.simulate(MonteCarloMethod::WindowBlockBootstrap, input, |rng, out| {
+                let n = input.window_pnls.len();
+                while out.len() < n {
+                    let start = rng.gen_range(0..n);
+                    for k in 0..block {
+                        if out.len() == n {
+                            break;
+                        }
+                        // Circular blocks so every window is equally likely
+                        out.push(input.window_pnls[(start + k) % n]);
+                    }
+                }
+            }));
+        }
+
+        if !input.trade_pnls.is_empty() {
+            distributions.push(self.simulate(MonteCarloMethod::TradeReshuffle, input, |rng, out| {
+                out.extend_from_slice(&input.trade_pnls);
+                // Fisher-Yates
+                for i in (1..out.len()).rev() {
+                    let j = rng.gen_range(0..=i);
+                    out.swap(i, j);
+                }
+            }));
+
+            let p = self.config.fill_drop_probability;
+            distributions.push(self.simulate(MonteCarloMethod::FillDrop, input, |rng, out| {
+                out.extend(input.trade_pnls.iter().filter(|_| rng.gen::<f64>() >= p));
+            }));
+        }
+
+        let observed_total_pnl = if input.window_pnls.is_empty() {
+            input.trade_pnls.iter().sum()
+        } else {
+            input.window_pnls.iter().sum()
+        };
+
+        MonteCarloReport {
+            config: self.config.clone(),
+            initial_capital: input.initial_capital,
+            observed_total_pnl,
+            distributions,
+        }
+    }
+
+    fn simulate<F>(&self, method: MonteCarloMethod, input: &MonteCarloInput, mut sample_path: F) -> MonteCarloDistribution
+    where
+        F: FnMut(&mut ChaCha8Rng, &mut Vec<f64>),
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.config.seed ^ method.seed_salt().rotate_left(56));
+        let ruin_level = -self.config.ruin_fraction * input.initial_capital;
+        let iterations = self.config.iterations;
+
+        let mut totals = Vec::with_capacity(iterations);
+        let mut drawdowns = Vec::with_capacity(iterations);
+        let mut sharpes = Vec::with_capacity(iterations);
+        let mut ruined = 0usize;
+        let mut path = Vec::new();
+        let mut sample_count = 0;
+
+        for _ in 0..iterations {
+            path.clear();
+            sample_path(&mut rng, &mut path);
+            sample_count = sample_count.max(path.len());
+
+            let stats = PathStats::of(&path);
+            if stats.min_cumulative <= ruin_level {
+                ruined += 1;
+            }
+            totals.push(stats.total);
+            drawdowns.push(stats.max_drawdown);
+            sharpes.push(stats.sharpe);
+        }
+
+        let positive = totals.iter().filter(|&&t| t > 0.0).count();
+        MonteCarloDistribution {
+            method,
+            sample_count,
+            iterations,
+            total_pnl: PercentileSummary::from_samples(&totals),
+            max_drawdown: PercentileSummary::from_samples(&drawdowns),
+            sharpe: PercentileSummary::from_samples(&sharpes),
+            positive_probability: positive as f64 / iterations as f64,
+            ruin_probability: ruined as f64 / iterations as f64,
+        }
+    }
+}
+
+/// Summary statistics of one resampled PnL path.
+struct PathStats {
+    total: f64,
+    max_drawdown: f64,
+    min_cumulative: f64,
+    sharpe: f64,
+}
+
+impl PathStats {
+    fn of(path: &[f64]) -> Self {
+        let mut cumulative = 0.0f64;
+        let mut peak = 0.0f64;
+        let mut max_drawdown = 0.0f64;
+        let mut min_cumulative = 0.0f64;
+        for &pnl in path {
+            cumulative += pnl;
+            peak = peak.max(cumulative);
+            max_drawdown = max_drawdown.max(peak - cumulative);
+            min_cumulative = min_cumulative.min(cumulative);
+        }
+
+        let sharpe = if path.len() > 1 {
+            let mean = cumulative / path.len() as f64;
+            let variance = path.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / path.len() as f64;
+            if variance > 0.0 {
+                mean / variance.sqrt()
+            } else {
+                0.0
+            }
+        } else {
+            0.0
+        };
+
+        Self {
+            total: cumulative,
+            max_drawdown,
+            min_cumulative,
+            sharpe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzer() -> MonteCarloAnalyzer {
+        MonteCarloAnalyzer::new(MonteCarloConfig {
+            iterations: 500,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_percentile_summary() {
+        let samples: Vec<f64> = (0..=100).map(|i| i as f64).collect();
+        let s = PercentileSummary::from_samples(&samples);
+        assert_eq!(s.min, 0.0);
+        assert_eq!(s.max, 100.0);
+        assert!((s.p5 - 5.0).abs() < 1e-9);
+        assert!((s.p50 - 50.0).abs() < 1e-9);
+        assert!((s.p95 - 95.0).abs() < 1e-9);
+        assert!((s.mean - 50.0).abs() < 1e-9);
+        assert_eq!(PercentileSummary::from_samples(&[]).p5, 0.0);
+    }
+
+    #[test]
+    fn test_reshuffle_preserves_total_but_not_drawdown() {
+        let input = MonteCarloInput {
+            window_pnls: vec![],
+            trade_pnls: vec![10.0, 10.0, 10.0, -5.0, -5.0, -5.0, 10.0],
+            initial_capital: 1000.0,
+        };
+        let report = analyzer().analyze(&input);
+        let d = report.distribution(MonteCarloMethod::TradeReshuffle).unwrap();
+        assert!((d.total_pnl.min - 25.0).abs() < 1e-9);
+        assert!((d.total_pnl.max - 25.0).abs() < 1e-9);
+        assert!(d.max_drawdown.max >= 15.0 - 1e-9);
+        assert!(d.max_drawdown.min < d.max_drawdown.max);
+        assert_eq!(d.positive_probability, 1.0);
+
+        // Dropping fills moves total PnL
+        let drop = report.distribution(MonteCarloMethod::FillDrop).unwrap();
+        assert!(drop.total_pnl.min < drop.total_pnl.max);
+        assert!(report.distribution(MonteCarloMethod::WindowBlockBootstrap).is_none());
+    }
+
+    #[test]
+    fn test_fragile_edge_has_negative_p5() {
+        // Mostly small losses with two large wins: positive realized, fragile tail
+        let mut windows = vec![-1.0; 38];
+        windows.extend([30.0, 30.0]);
+        let input = MonteCarloInput {
+            window_pnls: windows,
+            trade_pnls: vec![],
+            initial_capital: 1000.0,
+        };
+        let report = analyzer().analyze(&input);
+        assert!((report.observed_total_pnl - 22.0).abs() < 1e-9);
+        assert!(report.p5_total_pnl().unwrap() < 0.0, "{}", report.format_summary());
+
+        // A consistent edge stays positive in the tail
+        let steady = MonteCarloInput {
+            window_pnls: (0..40).map(|i| if i % 4 == 0 { -0.5 } else { 1.0 }).collect(),
+            trade_pnls: vec![],
+            initial_capital: 1000.0,
+        };
+        assert!(analyzer().analyze(&steady).p5_total_pnl().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_ruin_probability_and_determinism() {
+        let input = MonteCarloInput {
+            window_pnls: vec![-60.0, 40.0, -60.0, 40.0, -60.0, 40.0],
+            trade_pnls: vec![],
+            initial_capital: 100.0,
+        };
+        let a = analyzer().analyze(&input);
+        let b = analyzer().analyze(&input);
+        let da = a.distribution(MonteCarloMethod::WindowBlockBootstrap).unwrap();
+        let db = b.distribution(MonteCarloMethod::WindowBlockBootstrap).unwrap();
+        assert!(da.ruin_probability > 0.0);
+        assert_eq!(da.ruin_probability, db.ruin_probability);
+        assert_eq!(da.total_pnl.p5, db.total_pnl.p5);
+        assert_eq!(a.ruin_probability(), da.ruin_probability);
+    }
+
+    #[test]
+    fn test_empty_input_and_validation() {
+        let report = analyzer().analyze(&MonteCarloInput::default());
+        assert!(report.distributions.is_empty());
+        assert!(report.p5_total_pnl().is_none());
+
+        assert!(MonteCarloAnalyzer::new(MonteCarloConfig { iterations: 0, ..Default::default() }).is_err());
+        assert!(MonteCarloAnalyzer::new(MonteCarloConfig {
+            fill_drop_probability: 1.0,
+            ..Default::default()
+        })
+        .is_err());
+
+        let input = MonteCarloInput::default().with_cumulative_trade_pnl(&[1.0, 3.0, 2.0]);
+        assert_eq!(input.trade_pnls, vec![1.0, 2.0, -1.0]);
+    }
+}
@@ -0,0 +1,765 @@
+//! Multi-Market Portfolio Runs
+//!
+//! Support for a single orchestrator run spanning many 15M Up/Down markets
+//! (e.g. BTC/ETH/SOL/XRP over consecutive windows) that draw on one capital pool.
+//!
+//! The orchestrator already routes every token through one `Ledger`, so cash and
+//! positions are shared by construction once all markets' events are merged into a
+//! single feed. This module adds what is needed to *validate* aggregate capital usage:
+//!
+//! - [`PortfolioRiskGate`]: pre-trade checks from `RiskLimits`, evaluated against the
+//!   book of *all* markets in the run. Installed on `SimulatedOrderSender` when
+//!   `BacktestConfig::risk_limits` is set; a blocked order is rejected synchronously
+//!   from `send_order`, exactly like an OMS validation failure.
+//! - [`MarketAttribution`]: per-market fills, volume, fees and settlement PnL,
+//!   reported in `BacktestResults::market_attribution`.
+//!
+//! # Exposure Model
+//!
+//! Binary tokens cost at most their purchase price, so exposure is measured as cost
+//! basis (USD paid for open positions) plus the notional of resting buy orders.
+//! Equity is book value: cash plus cost basis. It moves on fees and settlements,
+//! not on marks, so the drawdown stop cannot be tripped by quote noise.
+//!
+//! The gate keeps its own position book, fed by the orchestrator on every admitted
+//! fill and settlement. It is a risk view only and never feeds back into accounting;
+//! the `Ledger` remains the sole source of truth for PnL.
+
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::events::{Price, Side, Size};
+use crate::backtest_v2::portfolio::Outcome;
+use crate::backtest_v2::risk::{BlockReason, RiskCheckResult, RiskLimits};
+use crate::backtest_v2::strategy::OpenOrder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const DAY_NS: Nanos = 86_400_000_000_000;
+
+/// Asset symbol of a 15M Up/Down market id (`"btc-updown-15m-1762755300"` → `"btc"`).
+pub fn asset_of(market_id: &str) -> String {
+    market_id
+        .split('-')
+        .next()
+        .unwrap_or(market_id)
+        .to_lowercase()
+}
+
+// =============================================================================
+// PER-MARKET ATTRIBUTION
+// =============================================================================
+
+/// Contribution of a single market to a multi-market run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketAttribution {
+    /// Asset symbol (btc, eth, sol, xrp, ...).
+    pub asset: String,
+    /// Admitted fills.
+    pub fills: u64,
+    pub maker_fills: u64,
+    pub taker_fills: u64,
+    /// Traded notional (USD).
+    pub volume: f64,
+    /// Fees paid (USD).
+    pub fees: f64,
+    /// Realized PnL (USD) from closing fills and settlement, excluding fees.
+    pub realized_pnl: f64,
+    /// Whether the market settled during the run.
+    pub settled: bool,
+    /// Orders rejected by the portfolio risk gate.
+    pub orders_blocked: u64,
+}
+
+impl MarketAttribution {
+    /// Empty attribution for `market_id`.
+    pub fn new(market_id: &str) -> Self {
+        Self {
+            asset: asset_of(market_id),
+            ..Default::default()
+        }
+    }
+
+    /// Record an admitted fill.
+    pub fn record_fill(&mut self, is_maker: bool, size: Size, price: Price, fee: f64) {
+        self.fills += 1;
+        if is_maker {
+            self.maker_fills += 1;
+        } else {
+            self.taker_fills += 1;
+        }
+        self.volume += size * price;
+        self.fees += fee;
+    }
+
+    /// Record a settlement and the realized PnL it booked.
+    pub fn record_settlement(&mut self, realized_pnl: f64) {
+        self.realized_pnl += realized_pnl;
+        self.settled = true;
+    }
+
+    /// Realized PnL net of fees.
+    pub fn net_pnl(&self) -> f64 {
+        self.realized_pnl - self.fees
+    }
+}
+
+/// Roll per-market attribution up to per-asset totals.
+pub fn attribution_by_asset(
+    markets: &BTreeMap<String, MarketAttribution>,
+) -> BTreeMap<String, MarketAttribution> {
+    let mut assets: BTreeMap<String, MarketAttribution> = BTreeMap::new();
+    for m in markets.values() {
+        let a = assets.entry(m.asset.clone()).or_insert_with(|| MarketAttribution {
+            asset: m.asset.clone(),
+            settled: true,
+            ..Default::default()
+        });
+        a.fills += m.fills;
+        a.maker_fills += m.maker_fills;
+        a.taker_fills += m.taker_fills;
+        a.volume += m.volume;
+        a.fees += m.fees;
+        a.realized_pnl += m.realized_pnl;
+        a.settled &= m.settled;
+        a.orders_blocked += m.orders_blocked;
+    }
+    assets
+}
+
+/// Format per-market attribution as a compact text table.
+pub fn format_attribution(markets: &BTreeMap<String, MarketAttribution>) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{:<36} {:>6} {:>12} {:>10} {:>12} {:>8}\n",
+        "MARKET", "FILLS", "VOLUME", "FEES", "NET PNL", "BLOCKED"
+    ));
+    for (market_id, m) in markets {
+        out.push_str(&format!(
+            "{:<36} {:>6} {:>12.2} {:>10.2} {:>12.2} {:>8}{}\n",
+            market_id,
+            m.fills,
+            m.volume,
+            m.fees,
+            m.net_pnl(),
+            m.orders_blocked,
+            if m.settled { "" } else { "  (unsettled)" }
+        ));
+    }
+    out
+}
+
+// =============================================================================
+// PORTFOLIO RISK GATE
+// =============================================================================
+
+/// Aggregate statistics of the portfolio risk gate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioRiskStats {
+    pub orders_checked: u64,
+    pub orders_approved: u64,
+    pub orders_blocked: u64,
+    /// Blocks keyed by limit (e.g. "gross_exposure", "market_position").
+    pub blocks_by_limit: BTreeMap<String, u64>,
+    /// Highest gross exposure observed across all markets (USD).
+    pub peak_gross_exposure: f64,
+    /// Highest number of markets holding a position at the same time.
+    pub peak_concurrent_markets: usize,
+    /// Largest book-value drawdown observed (fraction of peak equity).
+    pub max_drawdown_pct: f64,
+    /// Whether the drawdown stop was triggered.
+    pub drawdown_stop_triggered: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct RiskPosition {
+    shares: f64,
+    cost_basis: f64,
+}
+
+/// Pre-trade risk checks across every market of a run.
+///
+/// Enforced limits (from `RiskLimits`): order size and notional, outstanding
+/// orders (total and per market), per-market position, gross exposure, minimum
+/// cash, drawdown stop with cooldown, daily loss and daily trade count. Sells
+/// do not add exposure and are only subject to the order-level limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioRiskGate {
+    limits: RiskLimits,
+    /// Maps a token id to its market id (not serialized; reinstalled by
+    /// `set_market_of` after a checkpoint restore).
+    #[serde(skip, default = "default_market_of")]
+    market_of: fn(&str) -> String,
+    cash: f64,
+    peak_equity: f64,
+    positions: HashMap<(String, Outcome), RiskPosition>,
+    cooldown_until: Nanos,
+    day_start: Nanos,
+    daily_loss: f64,
+    daily_trades: u64,
+    stats: PortfolioRiskStats,
+    blocks_by_market: BTreeMap<String, u64>,
+}
+
+fn token_as_market(token_id: &str) -> String {
+    token_id.to_string()
+}
+
+fn default_market_of() -> fn(&str) -> String {
+    token_as_market
+}
+
+impl PortfolioRiskGate {
+    /// Create a gate for a run starting with `initial_cash`. `market_of` maps a
+    /// token id to the market it belongs to.
+    pub fn new(limits: RiskLimits, initial_cash: f64, market_of: fn(&str) -> String) -> Self {
+        Self {
+            limits,
+            market_of,
+            cash: initial_cash,
+            peak_equity: initial_cash,
+            positions: HashMap::new(),
+            cooldown_until: 0,
+            day_start: 0,
+            daily_loss: 0.0,
+            daily_trades: 0,
+            stats: PortfolioRiskStats::default(),
+            blocks_by_market: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the token id -> market id mapping.
+    pub fn set_market_of(&mut self, market_of: fn(&str) -> String) {
+        self.market_of = market_of;
+    }
+
+    /// Configured limits.
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Gate statistics.
+    pub fn stats(&self) -> &PortfolioRiskStats {
+        &self.stats
+    }
+
+    /// Orders blocked per market.
+    pub fn blocks_by_market(&self) -> &BTreeMap<String, u64> {
+        &self.blocks_by_market
+    }
+
+    /// Book-value equity: cash plus cost basis of open positions.
+    pub fn equity(&self) -> f64 {
+        self.cash + self.positions.values().map(|p| p.cost_basis).sum::<f64>()
+    }
+
+    /// Cost basis held in `market_id`.
+    pub fn market_exposure(&self, market_id: &str) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((m, _), _)| m == market_id)
+            .map(|(_, p)| p.cost_basis.abs())
+            .sum()
+    }
+
+    /// Cost basis held across all markets.
+    pub fn gross_exposure(&self) -> f64 {
+        self.positions.values().map(|p| p.cost_basis.abs()).sum()
+    }
+
+    /// Check an order on `token_id` against all limits. `open_orders` are the
+    /// sender's currently resting orders.
+    pub fn check_order(
+        &mut self,
+        token_id: &str,
+        side: Side,
+        size: Size,
+        price: Price,
+        open_orders: &[OpenOrder],
+        now: Nanos,
+    ) -> RiskCheckResult {
+        self.stats.orders_checked += 1;
+        self.maybe_reset_daily(now);
+
+        let market_id = (self.market_of)(token_id);
+        let market_id = market_id.as_str();
+        let result = self.evaluate(market_id, side, size, price, open_orders, now);
+        match &result {
+            RiskCheckResult::Blocked(reason) => {
+                self.stats.orders_blocked += 1;
+                *self
+                    .stats
+                    .blocks_by_limit
+                    .entry(limit_key(reason).to_string())
+                    .or_insert(0) += 1;
+                *self.blocks_by_market.entry(market_id.to_string()).or_insert(0) += 1;
+                tracing::debug!(
+                    market_id = %market_id,
+                    side = ?side,
+                    size = %size,
+                    price = %price,
+                    reason = %reason,
+                    "Order blocked by portfolio risk gate"
+                );
+            }
+            _ => self.stats.orders_approved += 1,
+        }
+        result
+    }
+
+    fn evaluate(
+        &mut self,
+        market_id: &str,
+        side: Side,
+        size: Size,
+        price: Price,
+        open_orders: &[OpenOrder],
+        now: Nanos,
+    ) -> RiskCheckResult {
+        if now < self.cooldown_until {
+            return RiskCheckResult::Blocked(BlockReason::Cooldown {
+                remaining_ns: self.cooldown_until - now,
+            });
+        }
+
+        let drawdown = self.drawdown();
+        if drawdown > self.limits.max_drawdown_pct {
+            self.cooldown_until = now + self.limits.cooldown_ns;
+            self.stats.drawdown_stop_triggered = true;
+            return RiskCheckResult::Blocked(BlockReason::DrawdownStop {
+                current: drawdown,
+                limit: self.limits.max_drawdown_pct,
+            });
+        }
+
+        let limits = &self.limits;
+        if self.daily_loss > limits.max_daily_loss {
+            return RiskCheckResult::Blocked(BlockReason::DailyLoss {
+                current: self.daily_loss,
+                limit: limits.max_daily_loss,
+            });
+        }
+        if self.daily_trades >= limits.max_trades_per_day {
+            return RiskCheckResult::Blocked(BlockReason::DailyTrades {
+                current: self.daily_trades,
+                limit: limits.max_trades_per_day,
+            });
+        }
+        if size > limits.max_order_size {
+            return RiskCheckResult::Blocked(BlockReason::OrderSize {
+                requested: size,
+                limit: limits.max_order_size,
+            });
+        }
+        let notional = size * price;
+        if notional > limits.max_order_notional {
+            return RiskCheckResult::Blocked(BlockReason::OrderNotional {
+                requested: notional,
+                limit: limits.max_order_notional,
+            });
+        }
+
+        if open_orders.len() >= limits.max_outstanding_orders {
+            return RiskCheckResult::Blocked(BlockReason::OutstandingOrders {
+                current: open_orders.len(),
+                limit: limits.max_outstanding_orders,
+            });
+        }
+        let in_market: Vec<&OpenOrder> = open_orders
+            .iter()
+            .filter(|o| (self.market_of)(&o.token_id) == market_id)
+            .collect();
+        if in_market.len() >= limits.max_outstanding_orders_per_market {
+            return RiskCheckResult::Blocked(BlockReason::MarketOrders {
+                market_id: market_id.to_string(),
+                current: in_market.len(),
+                limit: limits.max_outstanding_orders_per_market,
+            });
+        }
+
+        if side == Side::Sell {
+            return RiskCheckResult::Approved;
+        }
+
+        // Buys add exposure: count resting buys as already committed
+        let resting_buys = |orders: &[&OpenOrder]| -> f64 {
+            orders
+                .iter()
+                .filter(|o| o.side == Side::Buy)
+                .map(|o| o.remaining_size * o.price)
+                .sum()
+        };
+        let all_orders: Vec<&OpenOrder> = open_orders.iter().collect();
+        let committed_market = self.market_exposure(market_id) + resting_buys(&in_market);
+        let committed_gross = self.gross_exposure() + resting_buys(&all_orders);
+        let equity = self.equity();
+
+        let max_market = limits
+            .max_market_position_usd
+            .min(equity * limits.max_market_position_pct);
+        if committed_market + notional > max_market {
+            return RiskCheckResult::Blocked(BlockReason::MarketPosition {
+                market_id: market_id.to_string(),
+                current: committed_market + notional,
+                limit: max_market,
+            });
+        }
+
+        let max_gross = equity * limits.max_gross_exposure_mult;
+        if committed_gross + notional > max_gross {
+            return RiskCheckResult::Blocked(BlockReason::GrossExposure {
+                current: committed_gross + notional,
+                limit: max_gross,
+            });
+        }
+
+        let min_cash = limits.min_cash_balance.max(equity * limits.min_cash_pct);
+        let cash_after = self.cash - resting_buys(&all_orders) - notional;
+        if cash_after < min_cash {
+            return RiskCheckResult::Blocked(BlockReason::MinCash {
+                current: cash_after,
+                required: min_cash,
+            });
+        }
+
+        RiskCheckResult::Approved
+    }
+
+    /// Apply an admitted fill to the gate's position book.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_fill(
+        &mut self,
+        market_id: &str,
+        outcome: Outcome,
+        side: Side,
+        size: Size,
+        price: Price,
+        fee: f64,
+        now: Nanos,
+    ) {
+        self.maybe_reset_daily(now);
+        let position = self
+            .positions
+            .entry((market_id.to_string(), outcome))
+            .or_default();
+        match side {
+            Side::Buy => {
+                position.shares += size;
+                position.cost_basis += size * price;
+                self.cash -= size * price + fee;
+            }
+            Side::Sell => {
+                let avg = if position.shares > 1e-12 {
+                    position.cost_basis / position.shares
+                } else {
+                    price
+                };
+                position.shares -= size;
+                position.cost_basis -= size * avg;
+                self.cash += size * price - fee;
+                let pnl = size * (price - avg);
+                if pnl < 0.0 {
+                    self.daily_loss -= pnl;
+                }
+            }
+        }
+        self.daily_loss += fee;
+        self.daily_trades += 1;
+        self.after_state_change();
+    }
+
+    /// Resolve every position in `market_id`. Returns the realized PnL.
+    pub fn record_settlement(&mut self, market_id: &str, winner: Outcome) -> f64 {
+        let mut realized = 0.0;
+        for outcome in [Outcome::Yes, Outcome::No] {
+            if let Some(p) = self.positions.remove(&(market_id.to_string(), outcome)) {
+                let payout = if outcome == winner { p.shares } else { 0.0 };
+                self.cash += payout;
+                realized += payout - p.cost_basis;
+            }
+        }
+        if realized < 0.0 {
+            self.daily_loss -= realized;
+        }
+        self.after_state_change();
+        realized
+    }
+
+    fn after_state_change(&mut self) {
+        self.positions
+            .retain(|_, p| p.shares.abs() > 1e-9 || p.cost_basis.abs() > 1e-9);
+
+        let gross = self.gross_exposure();
+        self.stats.peak_gross_exposure = self.stats.peak_gross_exposure.max(gross);
+        let mut markets: Vec<&String> = self.positions.keys().map(|(m, _)| m).collect();
+        markets.sort();
+        markets.dedup();
+        self.stats.peak_concurrent_markets = self.stats.peak_concurrent_markets.max(markets.len());
+
+        self.peak_equity = self.peak_equity.max(self.equity());
+        self.stats.max_drawdown_pct = self.stats.max_drawdown_pct.max(self.drawdown());
+    }
+
+    /// Book-value drawdown from peak equity (fraction).
+    pub fn drawdown(&self) -> f64 {
+        if self.peak_equity > 0.0 {
+            ((self.peak_equity - self.equity()) / self.peak_equity).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn maybe_reset_daily(&mut self, now: Nanos) {
+        if now - self.day_start >= DAY_NS {
+            self.day_start = now - now.rem_euclid(DAY_NS);
+            self.daily_loss = 0.0;
+            self.daily_trades = 0;
+        }
+    }
+}
+
+/// Stable key of a block reason for statistics.
+fn limit_key(reason: &BlockReason) -> &'static str {
+    match reason {
+        BlockReason::DrawdownStop { .. } => "drawdown_stop",
+        BlockReason::GrossExposure { .. } => "gross_exposure",
+        BlockReason::MarketPosition { .. } => "market_position",
+        BlockReason::OrderSize { .. } => "order_size",
+        BlockReason::OrderNotional { .. } => "order_notional",
+        BlockReason::OutstandingOrders { .. } => "outstanding_orders",
+        BlockReason::MarketOrders { .. } => "market_orders",
+        BlockReason::InsufficientCash { .. } => "insufficient_cash",
+        BlockReason::MinCash { .. } => "min_cash",
+        BlockReason::DailyLoss { .. } => "daily_loss",
+        BlockReason::DailyTrades { .. } => "daily_trades",
+        BlockReason::Cooldown { .. } => "cooldown",
+        BlockReason::InsufficientEdge { .. } => "insufficient_edge",
+        BlockReason::MarketHalted { .. } => "market_halted",
+        BlockReason::Custom(_) => "custom",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::feed::VecFeed;
+    use crate::backtest_v2::events::{Event, Level, TimestampedEvent};
+    use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator, MakerFillModel};
+    use crate::backtest_v2::queue::StreamSource;
+    use crate::backtest_v2::strategy::{
+        BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, Strategy,
+        StrategyContext, StrategyOrder, TimerEvent, TradePrint,
+    };
+
+    fn market_of(token_id: &str) -> String {
+        token_id.trim_end_matches("-yes").trim_end_matches("-no").to_string()
+    }
+
+    fn resting_buy(token_id: &str, size: f64, price: f64) -> OpenOrder {
+        OpenOrder {
+            order_id: 1,
+            client_order_id: "c1".into(),
+            token_id: token_id.into(),
+            side: Side::Buy,
+            price,
+            original_size: size,
+            remaining_size: size,
+            created_at: 0,
+        }
+    }
+
+    fn limits() -> RiskLimits {
+        RiskLimits {
+            max_gross_exposure_mult: 0.5,
+            max_market_position_usd: 300.0,
+            max_market_position_pct: 1.0,
+            max_order_size: 1_000.0,
+            max_order_notional: 1_000.0,
+            min_cash_balance: 0.0,
+            min_cash_pct: 0.0,
+            ..RiskLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_asset_of() {
+        assert_eq!(asset_of("btc-updown-15m-1762755300"), "btc");
+        assert_eq!(asset_of("XRP-updown-15m-1"), "xrp");
+    }
+
+    #[test]
+    fn test_gross_exposure_is_shared_across_markets() {
+        let mut gate = PortfolioRiskGate::new(limits(), 1_000.0, market_of);
+        // 500 gross allowed (0.5 x 1000 equity), 300 per market
+        gate.record_fill("btc-updown-15m-1", Outcome::Yes, Side::Buy, 400.0, 0.5, 0.0, 1);
+        gate.record_fill("eth-updown-15m-1", Outcome::Yes, Side::Buy, 400.0, 0.5, 0.0, 2);
+        assert!((gate.gross_exposure() - 400.0).abs() < 1e-9);
+
+        // A third market is within its own per-market limit but breaches the portfolio limit
+        let result = gate.check_order("sol-updown-15m-1-yes", Side::Buy, 250.0, 0.5, &[], 3);
+        assert!(matches!(result, RiskCheckResult::Blocked(BlockReason::GrossExposure { .. })));
+
+        // Resting buys elsewhere count toward the same budget
+        let resting = [resting_buy("eth-updown-15m-1-yes", 100.0, 0.5)];
+        let result = gate.check_order("sol-updown-15m-1-yes", Side::Buy, 150.0, 0.5, &resting, 4);
+        assert!(matches!(result, RiskCheckResult::Blocked(BlockReason::GrossExposure { .. })));
+        let result = gate.check_order("sol-updown-15m-1-no", Side::Buy, 100.0, 0.5, &[], 5);
+        assert!(result.is_approved());
+
+        // Sells are never blocked by exposure limits
+        let result = gate.check_order("btc-updown-15m-1-yes", Side::Sell, 400.0, 0.5, &[], 6);
+        assert!(result.is_approved());
+
+        assert_eq!(gate.stats().orders_blocked, 2);
+        assert_eq!(gate.stats().blocks_by_limit["gross_exposure"], 2);
+        assert_eq!(gate.blocks_by_market()["sol-updown-15m-1"], 2);
+        assert_eq!(gate.stats().peak_concurrent_markets, 2);
+    }
+
+    #[test]
+    fn test_settlement_frees_exposure_and_drawdown_stop_blocks() {
+        let mut gate = PortfolioRiskGate::new(
+            RiskLimits {
+                max_drawdown_pct: 0.10,
+                ..limits()
+            },
+            1_000.0,
+            market_of,
+        );
+        gate.record_fill("btc-updown-15m-1", Outcome::Yes, Side::Buy, 400.0, 0.5, 0.0, 1);
+        let pnl = gate.record_settlement("btc-updown-15m-1", Outcome::No);
+        assert!((pnl + 200.0).abs() < 1e-9);
+        assert_eq!(gate.gross_exposure(), 0.0);
+        assert!((gate.equity() - 800.0).abs() < 1e-9);
+        assert!((gate.drawdown() - 0.2).abs() < 1e-9);
+
+        let result = gate.check_order("eth-updown-15m-1-yes", Side::Buy, 10.0, 0.5, &[], 3);
+        assert!(matches!(result, RiskCheckResult::Blocked(BlockReason::DrawdownStop { .. })));
+        let result = gate.check_order("eth-updown-15m-1-yes", Side::Buy, 10.0, 0.5, &[], 4);
+        assert!(matches!(result, RiskCheckResult::Blocked(BlockReason::Cooldown { .. })));
+        assert!(gate.stats().drawdown_stop_triggered);
+    }
+
+    /// Rests a buy for `clip` at the ask once per token.
+    struct BuyOncePerToken {
+        clip: f64,
+        traded: std::collections::HashSet<String>,
+    }
+
+    impl Strategy for BuyOncePerToken {
+        fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+            let Some(ask) = book.best_ask() else { return };
+            if !self.traded.insert(book.token_id.clone()) {
+                return;
+            }
+            let order = StrategyOrder::limit(
+                format!("buy_{}", book.token_id),
+                &book.token_id,
+                Side::Buy,
+                ask.price,
+                self.clip,
+            );
+            let _ = ctx.orders.send_order(order);
+        }
+        fn on_trade(&mut self, _ctx: &mut StrategyContext, _trade: &TradePrint) {}
+        fn on_timer(&mut self, _ctx: &mut StrategyContext, _timer: &TimerEvent) {}
+        fn on_order_ack(&mut self, _ctx: &mut StrategyContext, _ack: &OrderAck) {}
+        fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {}
+        fn on_fill(&mut self, _ctx: &mut StrategyContext, _fill: &FillNotification) {}
+        fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, _ack: &CancelAck) {}
+        fn name(&self) -> &str {
+            "buy_once_per_token"
+        }
+    }
+
+    fn book(time: Nanos, token_id: &str) -> TimestampedEvent {
+        TimestampedEvent::new(
+            time,
+            StreamSource::MarketData as u8,
+            Event::L2BookSnapshot {
+                token_id: token_id.into(),
+                bids: vec![Level::new(0.48, 1_000.0)],
+                asks: vec![Level::new(0.52, 1_000.0)],
+                exchange_seq: 1,
+            },
+        )
+    }
+
+    #[test]
+    fn test_orchestrator_run_shares_limits_across_markets() {
+        let mut config = BacktestConfig::test_config();
+        config.maker_fill_model = MakerFillModel::MakerDisabled;
+        config.ledger_config = Some(crate::backtest_v2::ledger::LedgerConfig::default());
+        config.risk_limits = Some(RiskLimits {
+            // 10_000 initial cash -> 60 USD gross: room for one 52 USD clip
+            max_gross_exposure_mult: 0.006,
+            ..limits()
+        });
+
+        let mut orchestrator = BacktestOrchestrator::new(config);
+        let mut feed = VecFeed::new(
+            "multi_market",
+            vec![
+                book(1_000_000_000, "btc-updown-15m-1-yes"),
+                // The resting BTC buy already commits the gross budget
+                book(2_000_000_000, "eth-updown-15m-1-yes"),
+                TimestampedEvent::new(
+                    3_000_000_000,
+                    StreamSource::OrderManagement as u8,
+                    Event::Fill {
+                        order_id: 1,
+                        price: 0.52,
+                        size: 100.0,
+                        is_maker: false,
+                        leaves_qty: 0.0,
+                        fee: 0.0,
+                        fill_id: Some("fill1".into()),
+                    },
+                ),
+            ],
+        );
+        orchestrator.load_feed(&mut feed).unwrap();
+        let mut strategy = BuyOncePerToken {
+            clip: 100.0,
+            traded: Default::default(),
+        };
+        let results = orchestrator.run(&mut strategy).unwrap();
+
+        let btc = &results.market_attribution["btc-updown-15m-1"];
+        assert_eq!(btc.asset, "btc");
+        assert_eq!(btc.fills, 1);
+        assert!((btc.volume - 52.0).abs() < 1e-9);
+        assert_eq!(btc.orders_blocked, 0);
+
+        let eth = &results.market_attribution["eth-updown-15m-1"];
+        assert_eq!(eth.fills, 0);
+        assert_eq!(eth.orders_blocked, 1);
+
+        let risk = results.portfolio_risk.as_ref().unwrap();
+        assert_eq!(risk.orders_checked, 2);
+        assert_eq!(risk.blocks_by_limit["gross_exposure"], 1);
+        assert!((risk.peak_gross_exposure - 52.0).abs() < 1e-9);
+
+        // The fully filled order is booked against its own market in the shared ledger
+        let ledger = orchestrator.ledger().unwrap();
+        assert!((ledger.position_qty("btc-updown-15m-1", Outcome::Yes) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_attribution_rolls_up_by_asset() {
+        let mut markets = BTreeMap::new();
+        for (id, pnl) in [
+            ("btc-updown-15m-1", 5.0),
+            ("btc-updown-15m-2", -2.0),
+            ("eth-updown-15m-1", 1.0),
+        ] {
+            let mut m = MarketAttribution::new(id);
+            m.record_fill(false, 10.0, 0.5, 0.1);
+            m.record_settlement(pnl);
+            markets.insert(id.to_string(), m);
+        }
+        let assets = attribution_by_asset(&markets);
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets["btc"].fills, 2);
+        assert!((assets["btc"].net_pnl() - 2.8).abs() < 1e-9);
+        assert!(format_attribution(&markets).contains("eth-updown-15m-1"));
+    }
+}
@@ -5,14 +5,15 @@
 
 use crate::backtest_v2::clock::Nanos;
 use crate::backtest_v2::events::{
-    OrderId, OrderType, Price, RejectReason, Side, Size, TimeInForce,
+    BatchMode, OrderId, OrderType, Price, RejectReason, Side, Size, TimeInForce,
 };
 use crate::backtest_v2::matching::PriceTicks;
+use crate::venue_limits::POLYMARKET_MAX_BATCH_SIZE;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
 /// Order state machine states.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderState {
     /// Order created but not yet sent.
     New,
@@ -24,6 +25,8 @@ pub enum OrderState {
     PartiallyFilled,
     /// Cancel request sent, waiting for ack.
     PendingCancel,
+    /// Replace (amend) request sent, waiting for ack. Still live at the old terms.
+    PendingReplace,
     /// Order is done (filled, cancelled, rejected, or expired).
     Done,
 }
@@ -40,16 +43,26 @@ impl OrderState {
                 | OrderState::Live
                 | OrderState::PartiallyFilled
                 | OrderState::PendingCancel
+                | OrderState::PendingReplace
         )
     }
 
+    /// A replace in flight does not block a cancel: the venue cancels the
+    /// order under whichever terms it currently rests at.
     pub fn can_cancel(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Live | OrderState::PartiallyFilled | OrderState::PendingReplace
+        )
+    }
+
+    pub fn can_replace(&self) -> bool {
         matches!(self, OrderState::Live | OrderState::PartiallyFilled)
     }
 }
 
 /// Terminal state reason.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TerminalReason {
     Filled,
     Cancelled,
@@ -60,8 +73,18 @@ pub enum TerminalReason {
     MarketResolved,
 }
 
+/// Amended terms awaiting venue acknowledgment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingReplace {
+    pub request_id: u64,
+    pub price: Price,
+    /// New total order size (including quantity already filled).
+    pub size: Size,
+    pub sent_at: Nanos,
+}
+
 /// Order record in the OMS.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OmsOrder {
     pub order_id: OrderId,
     pub client_order_id: String,
@@ -89,6 +112,9 @@ pub struct OmsOrder {
     /// Cancel tracking
     pub cancel_sent_at: Option<Nanos>,
     pub cancel_request_id: Option<u64>,
+    /// Replace tracking
+    #[serde(default)]
+    pub pending_replace: Option<PendingReplace>,
 }
 
 impl OmsOrder {
@@ -129,6 +155,7 @@ impl OmsOrder {
             reduce_only,
             cancel_sent_at: None,
             cancel_request_id: None,
+            pending_replace: None,
         }
     }
 
@@ -189,11 +216,12 @@ impl OmsOrder {
         true
     }
 
-    /// Request cancel.
+    /// Request cancel. Any replace in flight is abandoned; its late ack is ignored.
     pub fn request_cancel(&mut self, request_id: u64, now: Nanos) -> bool {
         if !self.state.can_cancel() {
             return false;
         }
+        self.pending_replace = None;
         self.state = OrderState::PendingCancel;
         self.cancel_sent_at = Some(now);
         self.cancel_request_id = Some(request_id);
@@ -203,6 +231,7 @@ impl OmsOrder {
     /// Cancel acknowledged.
     pub fn cancel_ack(&mut self, cancelled_qty: Size, now: Nanos) -> bool {
         if self.state != OrderState::PendingCancel
+            && self.state != OrderState::PendingReplace
             && self.state != OrderState::Live
             && self.state != OrderState::PartiallyFilled
         {
@@ -231,6 +260,59 @@ impl OmsOrder {
         true
     }
 
+    /// Request replace. The order keeps trading at its old terms until acked.
+    pub fn request_replace(
+        &mut self,
+        request_id: u64,
+        price: Price,
+        size: Size,
+        now: Nanos,
+    ) -> bool {
+        if !self.state.can_replace() {
+            return false;
+        }
+        self.state = OrderState::PendingReplace;
+        self.pending_replace = Some(PendingReplace {
+            request_id,
+            price,
+            size,
+            sent_at: now,
+        });
+        true
+    }
+
+    /// Replace acknowledged. `leaves_qty` is authoritative: it already nets
+    /// out any fills the venue matched while the replace was in flight.
+    pub fn replace_ack(&mut self, price: Price, leaves_qty: Size, _now: Nanos) -> bool {
+        if self.state != OrderState::PendingReplace {
+            return false;
+        }
+        self.price = price;
+        self.remaining_qty = leaves_qty;
+        self.original_qty = self.filled_qty + leaves_qty;
+        self.state = if self.filled_qty > 0.0 {
+            OrderState::PartiallyFilled
+        } else {
+            OrderState::Live
+        };
+        self.pending_replace = None;
+        true
+    }
+
+    /// Replace rejected; the order stays live at its old terms.
+    pub fn replace_reject(&mut self, _now: Nanos) -> bool {
+        if self.state != OrderState::PendingReplace {
+            return false;
+        }
+        if self.filled_qty > 0.0 {
+            self.state = OrderState::PartiallyFilled;
+        } else {
+            self.state = OrderState::Live;
+        }
+        self.pending_replace = None;
+        true
+    }
+
     /// Mark as sent.
     pub fn mark_sent(&mut self, now: Nanos) -> bool {
         if self.state != OrderState::New {
@@ -243,7 +325,7 @@ impl OmsOrder {
 }
 
 /// Venue constraints configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VenueConstraints {
     /// Minimum order size.
     pub min_order_size: Size,
@@ -271,6 +353,13 @@ pub struct VenueConstraints {
     pub allowed_order_types: Vec<OrderType>,
     /// Time-in-force types allowed.
     pub allowed_tif: Vec<TimeInForce>,
+    /// Maximum orders in one batch submission.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+}
+
+fn default_max_batch_size() -> usize {
+    POLYMARKET_MAX_BATCH_SIZE
 }
 
 impl Default for VenueConstraints {
@@ -294,6 +383,7 @@ impl Default for VenueConstraints {
                 OrderType::Fok,
             ],
             allowed_tif: vec![TimeInForce::Gtc, TimeInForce::Ioc, TimeInForce::Fok],
+            max_batch_size: POLYMARKET_MAX_BATCH_SIZE,
         }
     }
 }
@@ -315,12 +405,13 @@ impl VenueConstraints {
             reduce_only_allowed: false,
             allowed_order_types: vec![OrderType::Limit],
             allowed_tif: vec![TimeInForce::Gtc, TimeInForce::Ioc, TimeInForce::Fok],
+            max_batch_size: POLYMARKET_MAX_BATCH_SIZE,
         }
     }
 }
 
 /// Market status for a token.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MarketStatus {
     /// Normal trading.
     Open,
@@ -333,7 +424,7 @@ pub enum MarketStatus {
 }
 
 /// Rate limiter with sliding window.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RateLimiter {
     /// Window size in nanoseconds.
     window_ns: Nanos,
@@ -360,15 +451,7 @@ impl RateLimiter {
 
     /// Try to consume a rate limit slot. Returns true if allowed.
     pub fn try_acquire(&mut self, now: Nanos) -> bool {
-        // Remove expired events
-        let cutoff = now - self.window_ns;
-        while let Some(&front) = self.events.front() {
-            if front < cutoff {
-                self.events.pop_front();
-            } else {
-                break;
-            }
-        }
+        self.expire(now);
 
         self.total_events += 1;
 
@@ -381,6 +464,36 @@ impl RateLimiter {
         }
     }
 
+    /// Try to consume `n` slots at once. Either all are granted or none
+    /// are, in which case all `n` count as dropped.
+    pub fn try_acquire_n(&mut self, now: Nanos, n: usize) -> bool {
+        self.expire(now);
+
+        self.total_events += n as u64;
+
+        if self.events.len() + n > self.max_events as usize {
+            self.dropped_events += n as u64;
+            false
+        } else {
+            self.events.extend(std::iter::repeat(now).take(n));
+            true
+        }
+    }
+
+    /// Consume as many of `n` slots as are free. Returns the number granted;
+    /// the rest count as dropped.
+    pub fn acquire_up_to(&mut self, now: Nanos, n: usize) -> usize {
+        self.expire(now);
+
+        let granted = (self.max_events as usize)
+            .saturating_sub(self.events.len())
+            .min(n);
+        self.total_events += n as u64;
+        self.dropped_events += (n - granted) as u64;
+        self.events.extend(std::iter::repeat(now).take(granted));
+        granted
+    }
+
     /// Current usage (0.0 to 1.0).
     pub fn usage(&self) -> f64 {
         self.events.len() as f64 / self.max_events as f64
@@ -397,6 +510,18 @@ impl RateLimiter {
         self.total_events = 0;
         self.dropped_events = 0;
     }
+
+    /// Remove events that have left the window.
+    fn expire(&mut self, now: Nanos) {
+        let cutoff = now - self.window_ns;
+        while let Some(&front) = self.events.front() {
+            if front < cutoff {
+                self.events.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
 }
 
 /// Validation result.
@@ -414,6 +539,7 @@ pub struct ValidationError {
 }
 
 /// Order Management System.
+#[derive(Serialize, Deserialize)]
 pub struct OrderManagementSystem {
     /// Venue constraints.
     constraints: VenueConstraints,
@@ -431,7 +557,7 @@ pub struct OrderManagementSystem {
     cancel_rate_limiter: RateLimiter,
     /// Next order ID.
     next_order_id: OrderId,
-    /// Next cancel request ID.
+    /// Next cancel request ID (shared with replace requests).
     next_cancel_id: u64,
     /// Statistics.
     pub stats: OmsStats,
@@ -440,7 +566,7 @@ pub struct OrderManagementSystem {
 }
 
 /// Pending message for out-of-order handling.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum PendingMessage {
     Fill {
         qty: Size,
@@ -467,6 +593,16 @@ pub struct OmsStats {
     pub cancels_rejected: u64,
     pub rate_limited_orders: u64,
     pub rate_limited_cancels: u64,
+    #[serde(default)]
+    pub orders_replaced: u64,
+    #[serde(default)]
+    pub replaces_rejected: u64,
+    #[serde(default)]
+    pub rate_limited_replaces: u64,
+    #[serde(default)]
+    pub batches_sent: u64,
+    #[serde(default)]
+    pub batches_rejected: u64,
     pub validation_failures: u64,
     pub out_of_order_messages: u64,
     pub total_volume: f64,
@@ -563,26 +699,75 @@ impl OrderManagementSystem {
             });
         }
 
-        let order = self.orders.get_mut(&order_id).ok_or(ValidationError {
-            reason: RejectReason::Unknown("Order not found".into()),
-            message: "Order not found".into(),
-        })?;
+        self.mark_order_sent(order_id, now)
+    }
 
-        if !order.mark_sent(now) {
+    /// Send several orders to the venue in one batch message.
+    ///
+    /// Every order in the batch takes a slot from the order rate limit. An
+    /// oversized batch is refused outright. In `AllOrNone` mode the batch is
+    /// also refused unless every order is unsent and fits in the rate limit;
+    /// in `Partial` mode orders are sent in sequence until the limit runs
+    /// out and each of the rest fails on its own.
+    pub fn send_batch(
+        &mut self,
+        order_ids: &[OrderId],
+        mode: BatchMode,
+        now: Nanos,
+    ) -> Result<Vec<Result<(), ValidationError>>, ValidationError> {
+        if order_ids.len() > self.constraints.max_batch_size {
+            self.stats.batches_rejected += 1;
             return Err(ValidationError {
-                reason: RejectReason::Unknown("Invalid order state for send".into()),
-                message: format!("Cannot send order in state {:?}", order.state),
+                reason: RejectReason::Unknown("Batch too large".into()),
+                message: format!(
+                    "Batch of {} orders exceeds venue limit {}",
+                    order_ids.len(),
+                    self.constraints.max_batch_size
+                ),
             });
         }
 
-        // Track open order
-        self.open_orders_by_token
-            .entry(order.token_id.clone())
-            .or_insert_with(Vec::new)
-            .push(order_id);
+        let granted = match mode {
+            BatchMode::AllOrNone => {
+                let unsendable = order_ids.iter().find(|id| {
+                    self.orders
+                        .get(*id)
+                        .map_or(true, |order| order.state != OrderState::New)
+                });
+                if let Some(order_id) = unsendable {
+                    self.stats.batches_rejected += 1;
+                    return Err(ValidationError {
+                        reason: RejectReason::Unknown("Invalid order state for send".into()),
+                        message: format!("Order {} in batch cannot be sent", order_id),
+                    });
+                }
+                if !self.order_rate_limiter.try_acquire_n(now, order_ids.len()) {
+                    self.stats.rate_limited_orders += order_ids.len() as u64;
+                    self.stats.batches_rejected += 1;
+                    return Err(ValidationError {
+                        reason: RejectReason::RateLimited,
+                        message: "Order rate limit exceeded for batch".into(),
+                    });
+                }
+                order_ids.len()
+            }
+            BatchMode::Partial => self.order_rate_limiter.acquire_up_to(now, order_ids.len()),
+        };
 
-        self.stats.orders_sent += 1;
-        Ok(())
+        self.stats.batches_sent += 1;
+        let mut results = Vec::with_capacity(order_ids.len());
+        for (index, &order_id) in order_ids.iter().enumerate() {
+            if index < granted {
+                results.push(self.mark_order_sent(order_id, now));
+            } else {
+                self.stats.rate_limited_orders += 1;
+                results.push(Err(ValidationError {
+                    reason: RejectReason::RateLimited,
+                    message: "Order rate limit exceeded".into(),
+                }));
+            }
+        }
+        Ok(results)
     }
 
     /// Handle order acknowledgment from venue.
@@ -740,6 +925,95 @@ impl OrderManagementSystem {
         false
     }
 
+    /// Request to amend an order's price and/or total size.
+    ///
+    /// Replaces count against the order rate limit, since the venue treats
+    /// them as a cancel plus a new order. Requests refused locally never
+    /// reach the venue and leave the rate budget untouched.
+    pub fn request_replace(
+        &mut self,
+        order_id: OrderId,
+        price: Price,
+        size: Size,
+        now: Nanos,
+    ) -> Result<u64, ValidationError> {
+        let (token_id, filled_qty) = match self.orders.get(&order_id) {
+            Some(order) if order.state.can_replace() => (order.token_id.clone(), order.filled_qty),
+            Some(order) => {
+                return Err(ValidationError {
+                    reason: RejectReason::Unknown("Cannot replace order".into()),
+                    message: format!("Cannot replace order in state {:?}", order.state),
+                })
+            }
+            None => {
+                return Err(ValidationError {
+                    reason: RejectReason::Unknown("Order not found".into()),
+                    message: "Order not found".into(),
+                })
+            }
+        };
+
+        self.validate_terms(&token_id, price, size)?;
+
+        if size <= filled_qty {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::InvalidSize,
+                message: format!("Size {} not above filled quantity {}", size, filled_qty),
+            });
+        }
+
+        // Rate limit check
+        if !self.order_rate_limiter.try_acquire(now) {
+            self.stats.rate_limited_replaces += 1;
+            return Err(ValidationError {
+                reason: RejectReason::RateLimited,
+                message: "Order rate limit exceeded".into(),
+            });
+        }
+
+        let request_id = self.next_cancel_id;
+        self.next_cancel_id += 1;
+
+        let order = self.orders.get_mut(&order_id).expect("order checked above");
+        if !order.request_replace(request_id, price, size, now) {
+            return Err(ValidationError {
+                reason: RejectReason::Unknown("Cannot replace order".into()),
+                message: format!("Cannot replace order in state {:?}", order.state),
+            });
+        }
+
+        Ok(request_id)
+    }
+
+    /// Handle replace acknowledgment from venue.
+    pub fn on_replace_ack(
+        &mut self,
+        order_id: OrderId,
+        price: Price,
+        leaves_qty: Size,
+        now: Nanos,
+    ) -> bool {
+        if let Some(order) = self.orders.get_mut(&order_id) {
+            if order.replace_ack(price, leaves_qty, now) {
+                self.stats.orders_replaced += 1;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Handle replace rejection from venue.
+    pub fn on_replace_reject(&mut self, order_id: OrderId, now: Nanos) -> bool {
+        if let Some(order) = self.orders.get_mut(&order_id) {
+            if order.replace_reject(now) {
+                self.stats.replaces_rejected += 1;
+                return true;
+            }
+        }
+        false
+    }
+
     /// Set market status for a token.
     pub fn set_market_status(&mut self, token_id: &str, status: MarketStatus) {
         self.market_status.insert(token_id.to_string(), status);
@@ -820,7 +1094,7 @@ impl OrderManagementSystem {
                 ids.iter()
                     .filter(|id| {
                         self.orders
-                            .get(id)
+                            .get(*id)
                             .map(|o| o.state.is_active())
                             .unwrap_or(false)
                     })
@@ -860,8 +1134,37 @@ impl OrderManagementSystem {
         self.pending_messages.clear();
     }
 
+    /// Venue constraints in force.
+    pub fn constraints(&self) -> &VenueConstraints {
+        &self.constraints
+    }
+
     // === Private methods ===
 
+    /// Move a created order to PendingAck once its rate limit slot is held.
+    fn mark_order_sent(&mut self, order_id: OrderId, now: Nanos) -> Result<(), ValidationError> {
+        let order = self.orders.get_mut(&order_id).ok_or(ValidationError {
+            reason: RejectReason::Unknown("Order not found".into()),
+            message: "Order not found".into(),
+        })?;
+
+        if !order.mark_sent(now) {
+            return Err(ValidationError {
+                reason: RejectReason::Unknown("Invalid order state for send".into()),
+                message: format!("Cannot send order in state {:?}", order.state),
+            });
+        }
+
+        // Track open order
+        self.open_orders_by_token
+            .entry(order.token_id.clone())
+            .or_insert_with(Vec::new)
+            .push(order_id);
+
+        self.stats.orders_sent += 1;
+        Ok(())
+    }
+
     fn validate_order(
         &mut self,
         token_id: &str,
@@ -874,63 +1177,7 @@ impl OrderManagementSystem {
         reduce_only: bool,
         _now: Nanos,
     ) -> Result<(), ValidationError> {
-        // Market status check
-        let status = self.get_market_status(token_id);
-        if status != MarketStatus::Open {
-            self.stats.validation_failures += 1;
-            return Err(ValidationError {
-                reason: RejectReason::MarketClosed,
-                message: format!("Market is {:?}", status),
-            });
-        }
-
-        // Size validation
-        if qty < self.constraints.min_order_size {
-            self.stats.validation_failures += 1;
-            return Err(ValidationError {
-                reason: RejectReason::InvalidSize,
-                message: format!(
-                    "Size {} below minimum {}",
-                    qty, self.constraints.min_order_size
-                ),
-            });
-        }
-        if qty > self.constraints.max_order_size {
-            self.stats.validation_failures += 1;
-            return Err(ValidationError {
-                reason: RejectReason::InvalidSize,
-                message: format!(
-                    "Size {} above maximum {}",
-                    qty, self.constraints.max_order_size
-                ),
-            });
-        }
-
-        // Price validation
-        if price < self.constraints.min_price || price > self.constraints.max_price {
-            self.stats.validation_failures += 1;
-            return Err(ValidationError {
-                reason: RejectReason::InvalidPrice,
-                message: format!(
-                    "Price {} outside range [{}, {}]",
-                    price, self.constraints.min_price, self.constraints.max_price
-                ),
-            });
-        }
-
-        // Tick size validation
-        let ticks = (price / self.constraints.tick_size).round();
-        let rounded_price = ticks * self.constraints.tick_size;
-        if (price - rounded_price).abs() > 1e-9 {
-            self.stats.validation_failures += 1;
-            return Err(ValidationError {
-                reason: RejectReason::InvalidPrice,
-                message: format!(
-                    "Price {} not on tick size {}",
-                    price, self.constraints.tick_size
-                ),
-            });
-        }
+        self.validate_terms(token_id, price, qty)?;
 
         // Order type validation
         if !self.constraints.allowed_order_types.contains(&order_type) {
@@ -997,6 +1244,74 @@ impl OrderManagementSystem {
         Ok(())
     }
 
+    /// Market status, size and price checks shared by new orders and replaces.
+    fn validate_terms(
+        &mut self,
+        token_id: &str,
+        price: Price,
+        qty: Size,
+    ) -> Result<(), ValidationError> {
+        // Market status check
+        let status = self.get_market_status(token_id);
+        if status != MarketStatus::Open {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::MarketClosed,
+                message: format!("Market is {:?}", status),
+            });
+        }
+
+        // Size validation
+        if qty < self.constraints.min_order_size {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::InvalidSize,
+                message: format!(
+                    "Size {} below minimum {}",
+                    qty, self.constraints.min_order_size
+                ),
+            });
+        }
+        if qty > self.constraints.max_order_size {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::InvalidSize,
+                message: format!(
+                    "Size {} above maximum {}",
+                    qty, self.constraints.max_order_size
+                ),
+            });
+        }
+
+        // Price validation
+        if price < self.constraints.min_price || price > self.constraints.max_price {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::InvalidPrice,
+                message: format!(
+                    "Price {} outside range [{}, {}]",
+                    price, self.constraints.min_price, self.constraints.max_price
+                ),
+            });
+        }
+
+        // Tick size validation
+        let ticks = (price / self.constraints.tick_size).round();
+        let rounded_price = ticks * self.constraints.tick_size;
+        if (price - rounded_price).abs() > 1e-9 {
+            self.stats.validation_failures += 1;
+            return Err(ValidationError {
+                reason: RejectReason::InvalidPrice,
+                message: format!(
+                    "Price {} not on tick size {}",
+                    price, self.constraints.tick_size
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
     fn remove_from_open_orders(&mut self, token_id: &str, order_id: OrderId) {
         if let Some(orders) = self.open_orders_by_token.get_mut(token_id) {
             orders.retain(|&id| id != order_id);
@@ -1375,4 +1690,216 @@ mod tests {
             RejectReason::DuplicateOrderId
         ));
     }
+
+    #[test]
+    fn test_replace_fill_race() {
+        let mut oms = create_oms();
+        let now = 1_000_000_000i64;
+
+        let order_id = oms
+            .create_order(
+                "order1".into(),
+                "token123".into(),
+                Side::Buy,
+                OrderType::Limit,
+                TimeInForce::Gtc,
+                0.50,
+                100.0,
+                false,
+                false,
+                now,
+            )
+            .unwrap();
+        oms.send_order(order_id, now + 1000).unwrap();
+        oms.on_order_ack(order_id, now + 2000);
+
+        // Shrink to 60 total; cannot shrink below what has filled
+        oms.request_replace(order_id, 0.51, 60.0, now + 3000)
+            .unwrap();
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::PendingReplace);
+        assert_eq!(order.price, 0.50);
+        assert!(order.state.is_active());
+
+        // Fill at the old price lands while the replace is in flight
+        oms.on_fill(order_id, 40.0, 0.50, 0.0, now + 4000);
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::PendingReplace);
+        assert_eq!(order.remaining_qty, 60.0);
+
+        // Venue nets the fill out of the new size
+        assert!(oms.on_replace_ack(order_id, 0.51, 20.0, now + 5000));
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+        assert_eq!(order.price, 0.51);
+        assert_eq!(order.remaining_qty, 20.0);
+        assert_eq!(order.original_qty, 60.0);
+        assert!(order.pending_replace.is_none());
+
+        // Replace below filled quantity is refused locally
+        let err = oms.request_replace(order_id, 0.51, 30.0, now + 6000);
+        assert!(matches!(
+            err,
+            Err(ValidationError {
+                reason: RejectReason::InvalidSize,
+                ..
+            })
+        ));
+
+        // A rejected replace leaves the order at its old terms
+        oms.request_replace(order_id, 0.52, 80.0, now + 7000)
+            .unwrap();
+        assert!(oms.on_replace_reject(order_id, now + 8000));
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+        assert_eq!(order.price, 0.51);
+
+        // Fully filled during a pending replace: the late ack is ignored
+        oms.request_replace(order_id, 0.52, 80.0, now + 9000)
+            .unwrap();
+        oms.on_fill(order_id, 20.0, 0.51, 0.0, now + 10000);
+        assert_eq!(oms.get_order(order_id).unwrap().state, OrderState::Done);
+        assert!(!oms.on_replace_ack(order_id, 0.52, 40.0, now + 11000));
+        assert_eq!(oms.stats.orders_replaced, 1);
+        assert_eq!(oms.stats.replaces_rejected, 1);
+    }
+
+    #[test]
+    fn test_cancel_during_pending_replace() {
+        let mut oms = create_oms();
+        let now = 1_000_000_000i64;
+
+        let order_id = oms
+            .create_order(
+                "order1".into(),
+                "token123".into(),
+                Side::Buy,
+                OrderType::Limit,
+                TimeInForce::Gtc,
+                0.50,
+                100.0,
+                false,
+                false,
+                now,
+            )
+            .unwrap();
+        oms.send_order(order_id, now + 1000).unwrap();
+        oms.on_order_ack(order_id, now + 2000);
+        oms.request_replace(order_id, 0.51, 60.0, now + 3000)
+            .unwrap();
+
+        // A second replace must wait, but a cancel goes straight through
+        assert!(oms
+            .request_replace(order_id, 0.52, 60.0, now + 3500)
+            .is_err());
+        oms.request_cancel(order_id, now + 4000).unwrap();
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::PendingCancel);
+        assert!(order.pending_replace.is_none());
+
+        // The abandoned replace's ack is ignored
+        assert!(!oms.on_replace_ack(order_id, 0.51, 60.0, now + 5000));
+        assert!(oms.on_cancel_ack(order_id, 100.0, now + 6000));
+        let order = oms.get_order(order_id).unwrap();
+        assert_eq!(order.state, OrderState::Done);
+        assert_eq!(order.price, 0.50);
+        assert_eq!(oms.stats.orders_replaced, 0);
+    }
+
+    #[test]
+    fn test_refused_replace_keeps_rate_budget() {
+        let mut oms = OrderManagementSystem::new(VenueConstraints {
+            max_orders_per_second: 2,
+            ..Default::default()
+        });
+        let now = 1_000_000_000i64;
+
+        let order_id = oms
+            .create_order(
+                "order1".into(),
+                "token123".into(),
+                Side::Buy,
+                OrderType::Limit,
+                TimeInForce::Gtc,
+                0.50,
+                100.0,
+                false,
+                false,
+                now,
+            )
+            .unwrap();
+        oms.send_order(order_id, now).unwrap();
+        oms.on_order_ack(order_id, now + 1000);
+
+        // Unknown orders and bad terms are refused without taking a slot
+        for _ in 0..5 {
+            assert!(oms.request_replace(999, 0.51, 60.0, now + 2000).is_err());
+            assert!(oms
+                .request_replace(order_id, 1.50, 60.0, now + 2000)
+                .is_err());
+        }
+        assert_eq!(oms.stats.rate_limited_replaces, 0);
+        oms.request_replace(order_id, 0.51, 60.0, now + 3000)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_batch_rate_limit_accounting() {
+        let mut oms = OrderManagementSystem::new(VenueConstraints {
+            max_orders_per_second: 5,
+            max_batch_size: 4,
+            ..Default::default()
+        });
+        let now = 1_000_000_000i64;
+        let create = |oms: &mut OrderManagementSystem, i: usize| {
+            oms.create_order(
+                format!("leg{}", i),
+                "token123".into(),
+                Side::Buy,
+                OrderType::Limit,
+                TimeInForce::Gtc,
+                0.50,
+                10.0,
+                false,
+                false,
+                now,
+            )
+            .unwrap()
+        };
+        let ids: Vec<OrderId> = (0..8).map(|i| create(&mut oms, i)).collect();
+
+        // Oversized batches never reach the rate limiter
+        assert!(oms.send_batch(&ids[..5], BatchMode::Partial, now).is_err());
+        assert_eq!(oms.stats.batches_rejected, 1);
+        assert_eq!(oms.stats.orders_sent, 0);
+
+        // Three legs take three of the five slots
+        let results = oms
+            .send_batch(&ids[..3], BatchMode::AllOrNone, now)
+            .unwrap();
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(oms.stats.orders_sent, 3);
+
+        // Three more do not fit: all-or-none refuses every leg
+        let err = oms
+            .send_batch(&ids[3..6], BatchMode::AllOrNone, now)
+            .unwrap_err();
+        assert_eq!(err.reason, RejectReason::RateLimited);
+        assert_eq!(oms.stats.rate_limited_orders, 3);
+        assert_eq!(oms.get_order(ids[3]).unwrap().state, OrderState::New);
+
+        // Partial sends what fits and rate limits the rest
+        let results = oms.send_batch(&ids[3..6], BatchMode::Partial, now).unwrap();
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+        assert_eq!(oms.stats.orders_sent, 5);
+        assert_eq!(oms.stats.rate_limited_orders, 4);
+        assert_eq!(oms.stats.batches_sent, 2);
+
+        // Window rolls over; already-sent legs fail an all-or-none batch
+        let later = now + 2_000_000_000;
+        assert!(oms
+            .send_batch(&[ids[0], ids[6]], BatchMode::AllOrNone, later)
+            .is_err());
+        assert_eq!(oms.get_order(ids[6]).unwrap().state, OrderState::New);
+    }
 }
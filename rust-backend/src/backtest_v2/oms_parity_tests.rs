@@ -5,6 +5,7 @@
 //! 2. Validation rules enforced identically
 //! 3. Market status checks enforced
 //! 4. Rejects and retries behave identically
+//! 5. Replaces share the order rate budget and keep/lose priority correctly
 
 use crate::backtest_v2::events::{Level, Side, TimestampedEvent, Event};
 use crate::backtest_v2::feed::VecFeed;
@@ -13,8 +14,8 @@ use crate::backtest_v2::oms::VenueConstraints;
 use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator};
 use crate::backtest_v2::sim_adapter::OmsParityMode;
 use crate::backtest_v2::strategy::{
-    BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, Strategy,
-    StrategyContext, StrategyOrder, TimerEvent, TradePrint,
+    BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, ReplaceAck, Strategy,
+    StrategyCancel, StrategyContext, StrategyOrder, StrategyReplace, TimerEvent, TradePrint,
 };
 use std::cell::RefCell;
 
@@ -398,3 +399,227 @@ fn test_results_contain_oms_stats() {
     let oms_parity = results.oms_parity.unwrap();
     assert!(oms_parity.oms_stats.is_some());
 }
+
+// =============================================================================
+// TEST 9: Requoting through replace
+// =============================================================================
+
+/// Test strategy that quotes a fixed number of orders and amends each one on ack
+struct RequoteStrategy {
+    token_id: String,
+    orders: usize,
+    quoted: bool,
+    /// (order_id, kept_priority, leaves_qty) for each replace ack
+    replace_acks: Vec<(u64, bool, f64)>,
+    replace_errors: Vec<String>,
+}
+
+impl RequoteStrategy {
+    fn new(token_id: &str, orders: usize) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            orders,
+            quoted: false,
+            replace_acks: Vec::new(),
+            replace_errors: Vec::new(),
+        }
+    }
+}
+
+impl Strategy for RequoteStrategy {
+    fn name(&self) -> &str {
+        "requote"
+    }
+
+    fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+        if book.token_id != self.token_id || self.quoted {
+            return;
+        }
+        self.quoted = true;
+        for i in 0..self.orders {
+            let order = StrategyOrder::limit(
+                format!("quote_{}", i),
+                &self.token_id,
+                Side::Buy,
+                0.45,
+                100.0,
+            );
+            let _ = ctx.orders.send_order(order);
+        }
+    }
+
+    fn on_order_ack(&mut self, ctx: &mut StrategyContext, ack: &OrderAck) {
+        // Shrink in place: should keep queue priority
+        let replace = StrategyReplace::new(ack.order_id, 0.45, 60.0);
+        if let Err(e) = ctx.orders.send_replace(replace) {
+            self.replace_errors.push(e);
+        }
+    }
+
+    fn on_replace_ack(&mut self, ctx: &mut StrategyContext, ack: &ReplaceAck) {
+        self.replace_acks
+            .push((ack.order_id, ack.kept_priority, ack.leaves_qty));
+        if ack.kept_priority {
+            // Reprice: goes to the back of the new level
+            let replace = StrategyReplace::new(ack.order_id, 0.46, 60.0);
+            if let Err(e) = ctx.orders.send_replace(replace) {
+                self.replace_errors.push(e);
+            }
+        }
+    }
+
+    fn on_trade(&mut self, _ctx: &mut StrategyContext, _trade: &TradePrint) {}
+    fn on_timer(&mut self, _ctx: &mut StrategyContext, _timer: &TimerEvent) {}
+    fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {}
+    fn on_fill(&mut self, _ctx: &mut StrategyContext, _fill: &FillNotification) {}
+    fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, _ack: &CancelAck) {}
+}
+
+#[test]
+fn test_replace_priority_full_mode() {
+    let events = vec![make_book_snapshot("TEST", 10 * NS_PER_MS, 1)];
+    let mut feed = VecFeed::new("test", events);
+
+    let config = BacktestConfig {
+        oms_parity_mode: OmsParityMode::Full,
+        venue_constraints: VenueConstraints::polymarket(),
+        ..BacktestConfig::test_config()
+    };
+    let mut orchestrator = BacktestOrchestrator::new(config);
+    orchestrator.load_feed(&mut feed).unwrap();
+
+    let mut strategy = RequoteStrategy::new("TEST", 1);
+    let results = orchestrator.run(&mut strategy).unwrap();
+
+    assert!(
+        strategy.replace_errors.is_empty(),
+        "{:?}",
+        strategy.replace_errors
+    );
+    assert_eq!(strategy.replace_acks.len(), 2);
+    assert!(strategy.replace_acks[0].1, "size-down keeps priority");
+    assert!(!strategy.replace_acks[1].1, "price change loses priority");
+    assert_eq!(strategy.replace_acks[1].2, 60.0);
+
+    let oms_stats = results.oms_parity.unwrap().oms_stats.unwrap();
+    assert_eq!(oms_stats.orders_replaced, 2);
+    assert_eq!(oms_stats.rate_limited_replaces, 0);
+}
+
+// =============================================================================
+// TEST 10: Replaces count against the order rate limit
+// =============================================================================
+
+#[test]
+fn test_replace_rate_limited_full_mode() {
+    // Quoting uses the whole one-second budget, so the amends must be refused
+    let constraints = VenueConstraints {
+        max_orders_per_second: 5,
+        ..VenueConstraints::polymarket()
+    };
+
+    let events = vec![make_book_snapshot("TEST", 10 * NS_PER_MS, 1)];
+    let mut feed = VecFeed::new("test", events);
+
+    let config = BacktestConfig {
+        oms_parity_mode: OmsParityMode::Full,
+        venue_constraints: constraints,
+        ..BacktestConfig::test_config()
+    };
+    let mut orchestrator = BacktestOrchestrator::new(config);
+    orchestrator.load_feed(&mut feed).unwrap();
+
+    let mut strategy = RequoteStrategy::new("TEST", 5);
+    let results = orchestrator.run(&mut strategy).unwrap();
+
+    assert_eq!(strategy.replace_errors.len(), 5);
+    assert!(strategy
+        .replace_errors
+        .iter()
+        .all(|e| e.contains("rate limit")));
+    assert!(strategy.replace_acks.is_empty());
+    let oms_stats = results.oms_parity.unwrap().oms_stats.unwrap();
+    assert_eq!(oms_stats.rate_limited_replaces, 5);
+    assert_eq!(oms_stats.orders_sent, 5);
+}
+
+// =============================================================================
+// TEST 11: Cancelling an order with a replace in flight
+// =============================================================================
+
+/// Test strategy that amends its quote on ack and cancels it straight away
+struct CancelDuringReplaceStrategy {
+    token_id: String,
+    quoted: bool,
+    cancel_acks: Vec<u64>,
+    errors: Vec<String>,
+}
+
+impl Strategy for CancelDuringReplaceStrategy {
+    fn name(&self) -> &str {
+        "cancel_during_replace"
+    }
+
+    fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+        if book.token_id != self.token_id || self.quoted {
+            return;
+        }
+        self.quoted = true;
+        let order = StrategyOrder::limit("quote", &self.token_id, Side::Buy, 0.45, 100.0);
+        let _ = ctx.orders.send_order(order);
+    }
+
+    fn on_order_ack(&mut self, ctx: &mut StrategyContext, ack: &OrderAck) {
+        let replace = StrategyReplace::new(ack.order_id, 0.46, 100.0);
+        if let Err(e) = ctx.orders.send_replace(replace) {
+            self.errors.push(e);
+        }
+        let cancel = StrategyCancel {
+            order_id: ack.order_id,
+            client_order_id: None,
+        };
+        if let Err(e) = ctx.orders.send_cancel(cancel) {
+            self.errors.push(e);
+        }
+    }
+
+    fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, ack: &CancelAck) {
+        self.cancel_acks.push(ack.order_id);
+    }
+
+    fn on_trade(&mut self, _ctx: &mut StrategyContext, _trade: &TradePrint) {}
+    fn on_timer(&mut self, _ctx: &mut StrategyContext, _timer: &TimerEvent) {}
+    fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {}
+    fn on_fill(&mut self, _ctx: &mut StrategyContext, _fill: &FillNotification) {}
+}
+
+#[test]
+fn test_cancel_during_replace_full_mode() {
+    let events = vec![make_book_snapshot("TEST", 10 * NS_PER_MS, 1)];
+    let mut feed = VecFeed::new("test", events);
+
+    let config = BacktestConfig {
+        oms_parity_mode: OmsParityMode::Full,
+        venue_constraints: VenueConstraints::polymarket(),
+        ..BacktestConfig::test_config()
+    };
+    let mut orchestrator = BacktestOrchestrator::new(config);
+    orchestrator.load_feed(&mut feed).unwrap();
+
+    let mut strategy = CancelDuringReplaceStrategy {
+        token_id: "TEST".to_string(),
+        quoted: false,
+        cancel_acks: Vec::new(),
+        errors: Vec::new(),
+    };
+    let results = orchestrator.run(&mut strategy).unwrap();
+
+    assert!(strategy.errors.is_empty(), "{:?}", strategy.errors);
+    assert_eq!(strategy.cancel_acks.len(), 1);
+
+    let oms_parity = results.oms_parity.unwrap();
+    assert!(oms_parity.valid_for_production);
+    let oms_stats = oms_parity.oms_stats.unwrap();
+    assert_eq!(oms_stats.orders_cancelled, 1);
+    assert_eq!(oms_stats.orders_replaced, 0);
+}
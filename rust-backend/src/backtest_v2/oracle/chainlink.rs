@@ -13,6 +13,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::events::{Event, TimestampedEvent};
 
 // =============================================================================
 // Configuration
@@ -212,6 +213,38 @@ impl ChainlinkRound {
     pub fn is_stale(&self) -> bool {
         self.answered_in_round < self.round_id
     }
+
+    /// Represent this round as an `oracle_round` signal event.
+    ///
+    /// There is no dedicated oracle event variant; replay paths carry rounds
+    /// through the event stream as signals.
+    pub fn to_signal_event(&self) -> Event {
+        Event::Signal {
+            signal_id: format!("oracle_{}_{}", self.feed_id, self.round_id),
+            signal_type: "oracle_round".to_string(),
+            market_slug: self.asset_symbol.clone(),
+            confidence: 1.0,
+            details_json: serde_json::to_string(&serde_json::json!({
+                "feed_id": self.feed_id,
+                "round_id": self.round_id,
+                "answer": self.answer,
+                "updated_at": self.updated_at,
+                "decimals": self.decimals,
+            }))
+            .unwrap_or_default(),
+        }
+    }
+
+    /// Convert to a timestamped signal event at ingest arrival time.
+    pub fn to_timestamped_event(&self, source: u8) -> TimestampedEvent {
+        TimestampedEvent {
+            time: self.ingest_arrival_time_ns as Nanos,
+            source_time: self.updated_at_ns(),
+            seq: self.ingest_seq,
+            source,
+            event: self.to_signal_event(),
+        }
+    }
 }
 
 // =============================================================================
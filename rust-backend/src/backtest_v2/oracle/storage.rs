@@ -345,6 +345,52 @@ impl OracleRoundStorage {
         Ok(rounds)
     }
 
+    /// Load rounds for a feed by arrival time (inclusive, nanoseconds).
+    ///
+    /// Ordered by (ingest_arrival_time_ns, ingest_seq): the order rounds
+    /// became visible to the recorder.
+    pub fn load_rounds_by_arrival(
+        &self,
+        feed_id: &str,
+        start_arrival_ns: u64,
+        end_arrival_ns: u64,
+    ) -> Result<Vec<ChainlinkRound>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT feed_id, round_id, answer, updated_at, answered_in_round,
+                   started_at, ingest_arrival_time_ns, ingest_seq, decimals,
+                   asset_symbol, raw_source_hash
+            FROM chainlink_rounds
+            WHERE feed_id = ?1 AND ingest_arrival_time_ns >= ?2 AND ingest_arrival_time_ns <= ?3
+            ORDER BY ingest_arrival_time_ns ASC, ingest_seq ASC
+            "#,
+        )?;
+
+        let rounds = stmt
+            .query_map(
+                params![feed_id, start_arrival_ns as i64, end_arrival_ns as i64],
+                |row| {
+                    Ok(ChainlinkRound {
+                        feed_id: row.get(0)?,
+                        round_id: row.get::<_, i64>(1)? as u128,
+                        answer: row.get::<_, i64>(2)? as i128,
+                        updated_at: row.get::<_, i64>(3)? as u64,
+                        answered_in_round: row.get::<_, i64>(4)? as u128,
+                        started_at: row.get::<_, i64>(5)? as u64,
+                        ingest_arrival_time_ns: row.get::<_, i64>(6)? as u64,
+                        ingest_seq: row.get::<_, i64>(7)? as u64,
+                        decimals: row.get::<_, i32>(8)? as u8,
+                        asset_symbol: row.get(9)?,
+                        raw_source_hash: row.get(10)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rounds)
+    }
+
     /// Get the latest round for a feed.
     pub fn get_latest_round(&self, feed_id: &str) -> Result<Option<ChainlinkRound>> {
         let conn = self.conn.lock();
@@ -13,6 +13,7 @@
 //! **Hard Invariant**: Strategy MUST only read state from events with `arrival_time <= decision_time`.
 
 use crate::backtest_v2::book::{BookManager, DeltaResult};
+use crate::backtest_v2::checkpoint::{BacktestCheckpoint, CheckpointPolicy};
 use crate::backtest_v2::clock::{Nanos, SimClock};
 use crate::backtest_v2::data_contract::{
     DataContractValidator, DataQualitySummary, DatasetReadiness, DatasetReadinessClassifier,
@@ -20,14 +21,16 @@ use crate::backtest_v2::data_contract::{
 };
 use crate::backtest_v2::events::{Event, Level, Side, TimestampedEvent};
 use crate::backtest_v2::feed::MarketDataFeed;
+use crate::backtest_v2::fingerprint::ConfigFingerprint;
 use crate::backtest_v2::latency::LatencyConfig;
-use crate::backtest_v2::matching::MatchingConfig;
+use crate::backtest_v2::matching::{MatchingConfig, PriceTicks};
 use crate::backtest_v2::oms::VenueConstraints;
 use crate::backtest_v2::queue::EventQueue;
 use crate::backtest_v2::sim_adapter::{OmsParityMode, OmsParityStats, SimulatedOrderSender};
+use crate::backtest_v2::time_windows::align_to_window_start;
 use crate::backtest_v2::strategy::{
-    BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, OrderSender, Strategy,
-    StrategyContext, StrategyParams, TimerEvent, TradePrint,
+    BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, OrderSender, ReplaceAck,
+    ReplaceReject, Strategy, StrategyContext, StrategyParams, TimerEvent, TradePrint,
 };
 use crate::backtest_v2::queue_model::{QueuePositionModel, QueueStats};
 use crate::backtest_v2::maker_fill_gate::{
@@ -385,6 +388,8 @@ pub struct BacktestConfig {
     pub integrity_policy: crate::backtest_v2::integrity::PathologyPolicy,
     /// Sensitivity analysis configuration.
     pub sensitivity: crate::backtest_v2::sensitivity::SensitivityConfig,
+    /// Monte Carlo robustness analysis of the run's PnL (feeds gate suite Gate D).
+    pub monte_carlo: crate::backtest_v2::monte_carlo::MonteCarloConfig,
     /// Production-grade mode: enforces ALL requirements, aborts on ANY downgrade.
     /// When true, automatically enforces: strict visibility, Hard invariants, strict integrity,
     /// Full OMS parity, ExactSpec settlement (required), DoubleEntryExact ledger (required),
@@ -464,6 +469,37 @@ pub struct BacktestConfig {
     /// If None for a production-grade run, the backtest will abort with a clear error.
     /// For non-production runs, defaults to "unnamed_strategy/0.0.0".
     pub strategy_id: Option<crate::backtest_v2::fingerprint::StrategyId>,
+    
+    /// SAMPLE SCOPE: Whether `strategy_params` were optimized on the data being replayed.
+    /// 
+    /// Set by `ParamSweepRunner` (in-sample) and `WalkForwardRunner` (out-of-sample
+    /// test slices). `TrustGate` refuses to certify in-sample runs. Part of the
+    /// config fingerprint, so an in-sample sweep point never shares a run ID with
+    /// an otherwise identical standalone run.
+    pub sample_scope: crate::backtest_v2::walk_forward::SampleScope,
+    
+    /// PORTFOLIO RISK LIMITS: Cross-market pre-trade limits for multi-market runs.
+    /// 
+    /// When set, a `PortfolioRiskGate` is installed on the order sender and every
+    /// order is checked against the combined book of all markets in the run.
+    /// Blocked orders are rejected synchronously from `send_order`.
+    pub risk_limits: Option<crate::backtest_v2::risk::RiskLimits>,
+    
+    /// MARKET IMPACT: Let marketable orders sweep the recorded venue depth.
+    /// 
+    /// When set, the matching engine mirrors every recorded book and fills the part
+    /// of an order our own resting orders cannot against it. The model keeps the
+    /// depth we consumed missing until it refills, so repeated aggression inside a
+    /// window gets worse prices (`MarketImpactConfig::none()` heals on the next
+    /// book update). Part of the config fingerprint.
+    pub market_impact: Option<crate::backtest_v2::market_impact::MarketImpactConfig>,
+    
+    /// DECISION TRACE: Maximum canonical trace records stored in the results (0 = disabled).
+    /// 
+    /// The trace (submits, cancels, acks, rejects, fills, settlements) is what `RunDiff`
+    /// uses to locate the first divergence between two runs. The trace hash always
+    /// covers every record; only storage is bounded.
+    pub decision_trace_limit: usize,
 }
 
 impl Default for BacktestConfig {
@@ -497,6 +533,7 @@ impl Default for BacktestConfig {
             integrity_policy: crate::backtest_v2::integrity::PathologyPolicy::strict(),
             // Production-grade sensitivity analysis
             sensitivity: crate::backtest_v2::sensitivity::SensitivityConfig::production_validation(),
+            monte_carlo: crate::backtest_v2::monte_carlo::MonteCarloConfig::default(),
             // PRODUCTION-GRADE IS THE DEFAULT
             production_grade: true,
             // Production-grade settlement spec
@@ -516,6 +553,10 @@ impl Default for BacktestConfig {
             // Strategy identity - MUST be provided for production-grade runs
             // Default() uses None; production runs will fail without explicit StrategyId
             strategy_id: None,
+            sample_scope: crate::backtest_v2::walk_forward::SampleScope::Undeclared,
+            risk_limits: None,
+            market_impact: None,
+            decision_trace_limit: crate::backtest_v2::run_diff::DEFAULT_DECISION_TRACE_LIMIT,
         }
     }
 }
@@ -550,6 +591,7 @@ impl BacktestConfig {
             gate_mode: crate::backtest_v2::gate_suite::GateMode::Strict,
             integrity_policy: crate::backtest_v2::integrity::PathologyPolicy::strict(),
             sensitivity: crate::backtest_v2::sensitivity::SensitivityConfig::production_validation(),
+            monte_carlo: crate::backtest_v2::monte_carlo::MonteCarloConfig::default(),
             production_grade: true,
             settlement_spec: Some(crate::backtest_v2::settlement::SettlementSpec::polymarket_15m_updown()),
             oracle_config: Some(crate::backtest_v2::oracle::OracleConfig::production_multi_asset_polygon()),
@@ -560,6 +602,10 @@ impl BacktestConfig {
             hermetic_config: crate::backtest_v2::hermetic::HermeticConfig::production(),
             // MUST be provided by caller for production-grade runs
             strategy_id: None,
+            sample_scope: crate::backtest_v2::walk_forward::SampleScope::Undeclared,
+            risk_limits: None,
+            market_impact: None,
+            decision_trace_limit: crate::backtest_v2::run_diff::DEFAULT_DECISION_TRACE_LIMIT,
         }
     }
     
@@ -596,6 +642,7 @@ impl BacktestConfig {
             gate_mode: crate::backtest_v2::gate_suite::GateMode::default(),
             integrity_policy: crate::backtest_v2::integrity::PathologyPolicy::default(),
             sensitivity: crate::backtest_v2::sensitivity::SensitivityConfig::default(),
+            monte_carlo: crate::backtest_v2::monte_carlo::MonteCarloConfig::default(),
             production_grade: false,
             settlement_spec: None,
             oracle_config: None, // Not required in research mode
@@ -608,6 +655,10 @@ impl BacktestConfig {
             hermetic_config: crate::backtest_v2::hermetic::HermeticConfig::default(),
             // Strategy identity optional for research mode (uses default if not provided)
             strategy_id: None,
+            sample_scope: crate::backtest_v2::walk_forward::SampleScope::Undeclared,
+            risk_limits: None,
+            market_impact: None,
+            decision_trace_limit: crate::backtest_v2::run_diff::DEFAULT_DECISION_TRACE_LIMIT,
         }
     }
     
@@ -716,6 +767,11 @@ impl BacktestConfig {
             violations.push("sensitivity.enabled must be true".to_string());
         }
 
+        // 6b. Monte Carlo robustness must be enabled (Gate D input)
+        if !self.monte_carlo.enabled {
+            violations.push("monte_carlo.enabled must be true".to_string());
+        }
+
         // 7. Settlement spec must be provided
         if self.settlement_spec.is_none() {
             violations.push("settlement_spec must be provided for production-grade backtest".to_string());
@@ -1023,6 +1079,8 @@ pub struct BacktestResults {
     pub first_invariant_violation: Option<String>,
     /// Sensitivity analysis report.
     pub sensitivity_report: crate::backtest_v2::sensitivity::SensitivityReport,
+    /// Monte Carlo robustness report (None when disabled).
+    pub monte_carlo: Option<crate::backtest_v2::monte_carlo::MonteCarloReport>,
     /// Whether this was a production-grade backtest.
     pub production_grade: bool,
     /// Production-grade validation failures (if any).
@@ -1085,6 +1143,18 @@ pub struct BacktestResults {
     /// Generated exactly once at finalization time, deterministic and reproducible.
     /// Included in JSON manifests for auditability.
     pub disclaimers: Option<crate::backtest_v2::disclaimers::DisclaimersBlock>,
+    
+    /// Per-market attribution (fills, volume, fees, settlement PnL) keyed by market id.
+    /// Populated for every market that traded or settled; in a multi-market run this
+    /// decomposes the shared-ledger totals by market.
+    #[serde(default)]
+    pub market_attribution: std::collections::BTreeMap<String, crate::backtest_v2::multi_market::MarketAttribution>,
+    
+    /// Portfolio risk gate statistics. Only populated when `risk_limits` is configured.
+    pub portfolio_risk: Option<crate::backtest_v2::multi_market::PortfolioRiskStats>,
+    
+    /// Canonical decision trace for run diffing. None when `decision_trace_limit` is 0.
+    pub decision_trace: Option<crate::backtest_v2::run_diff::DecisionTrace>,
 }
 
 // =============================================================================
@@ -1469,6 +1539,7 @@ impl Default for BacktestResults {
             invariant_violations_detected: 0,
             first_invariant_violation: None,
             sensitivity_report: crate::backtest_v2::sensitivity::SensitivityReport::default(),
+            monte_carlo: None,
             production_grade: false,
             production_grade_violations: vec![],
             allow_non_production: false,
@@ -1485,6 +1556,9 @@ impl Default for BacktestResults {
             final_equity: None,
             honesty_metrics: None,
             disclaimers: None,
+            market_attribution: std::collections::BTreeMap::new(),
+            portfolio_risk: None,
+            decision_trace: None,
         }
     }
 }
@@ -1566,6 +1640,28 @@ pub struct BacktestOrchestrator {
     /// Records points at economically meaningful times: fills, fees, settlements.
     /// Only active when ledger_config is configured.
     equity_recorder: Option<crate::backtest_v2::equity_curve::EquityRecorder>,
+    /// External progress/cancellation handle (API jobs).
+    run_control: Option<crate::backtest_v2::job_queue::RunControl>,
+    /// Canonical decision trace for run diffing (hash always maintained).
+    decision_trace: crate::backtest_v2::run_diff::DecisionTrace,
+    /// Lazily pulled market data feed (see `load_feed_streaming`).
+    streaming_feed: Option<Box<dyn MarketDataFeed>>,
+    /// Events pulled from the streaming feed so far.
+    streaming_events_pulled: u64,
+    /// Streaming events already consumed before a resumed checkpoint.
+    pending_stream_skip: Option<u64>,
+    /// Checkpoint schedule (None = checkpointing disabled).
+    checkpoint_policy: Option<CheckpointPolicy>,
+    /// Most recent checkpoints, oldest first.
+    checkpoints: Vec<BacktestCheckpoint>,
+    /// Sim time at which the next checkpoint is due.
+    next_checkpoint_ns: Option<Nanos>,
+    /// Sim time the run started at (restored on resume).
+    run_start_ns: Nanos,
+    /// State was restored from a checkpoint; `run()` skips startup.
+    resumed: bool,
+    /// Window of the checkpoint this orchestrator was resumed from.
+    resume_window_ns: Option<Nanos>,
 }
 
 impl BacktestOrchestrator {
@@ -1578,7 +1674,7 @@ impl BacktestOrchestrator {
         }
 
         // Create adapter with OMS parity enforcement
-        let adapter = SimulatedOrderSender::with_oms_parity(
+        let mut adapter = SimulatedOrderSender::with_oms_parity(
             config.matching.clone(),
             config.latency.clone(),
             &config.trader_id,
@@ -1587,7 +1683,28 @@ impl BacktestOrchestrator {
             config.oms_parity_mode,
         );
 
+        // Record order/cancel requests for the decision trace
+        adapter.enable_request_log();
+
+        // Mirror recorded depth in the matching engine when an impact model is set
+        if let Some(impact) = &config.market_impact {
+            adapter.matching_engine_mut().set_market_impact(impact.clone());
+        }
+
+        // Install the cross-market risk gate if portfolio limits are configured
+        if let Some(limits) = &config.risk_limits {
+            let initial_cash = config.ledger_config.as_ref()
+                .map(|lc| lc.initial_cash)
+                .unwrap_or_else(|| crate::backtest_v2::ledger::LedgerConfig::default().initial_cash);
+            adapter.set_risk_gate(crate::backtest_v2::multi_market::PortfolioRiskGate::new(
+                limits.clone(),
+                initial_cash,
+                Self::extract_market_id,
+            ));
+        }
+
         let data_validator = DataContractValidator::new(config.data_contract.clone());
+        let decision_trace = crate::backtest_v2::run_diff::DecisionTrace::new(config.decision_trace_limit);
 
         let maker_fill_model = config.maker_fill_model;
         let oms_parity_mode = config.oms_parity_mode;
@@ -1691,7 +1808,189 @@ impl BacktestOrchestrator {
             } else {
                 None
             },
+            run_control: None,
+            decision_trace,
+            streaming_feed: None,
+            streaming_events_pulled: 0,
+            pending_stream_skip: None,
+            checkpoint_policy: None,
+            checkpoints: Vec::new(),
+            next_checkpoint_ns: None,
+            run_start_ns: 0,
+            resumed: false,
+            resume_window_ns: None,
+        }
+    }
+    
+    /// Attach a progress/cancellation handle. When cancelled, `run()` aborts
+    /// at the next event with an error and produces no results.
+    pub fn set_run_control(&mut self, control: crate::backtest_v2::job_queue::RunControl) {
+        self.run_control = Some(control);
+    }
+
+    /// Take checkpoints at 15-minute window boundaries during `run()`.
+    pub fn set_checkpoint_policy(&mut self, policy: CheckpointPolicy) {
+        self.checkpoint_policy = Some(policy);
+    }
+
+    /// Checkpoints retained in memory, oldest first.
+    pub fn checkpoints(&self) -> &[BacktestCheckpoint] {
+        &self.checkpoints
+    }
+
+    /// Rebuild an orchestrator from a checkpoint taken under the same config.
+    ///
+    /// The strategy's state is restored via `Strategy::restore_state`; call
+    /// `run()` to continue. The finished run has the same `RunFingerprint` as
+    /// the uninterrupted one. If the checkpointed run used a streaming feed,
+    /// attach that feed again with `load_feed_streaming` before `run()`.
+    pub fn resume_from_checkpoint(
+        config: BacktestConfig,
+        checkpoint: &BacktestCheckpoint,
+        strategy: &mut dyn Strategy,
+    ) -> Result<Self> {
+        let config_hash = ConfigFingerprint::from_config(&config).hash;
+        if config_hash != checkpoint.config_hash {
+            anyhow::bail!(
+                "Checkpoint config hash {:016x} does not match config {:016x}; \
+                 use fork_from_checkpoint to branch with a different config",
+                checkpoint.config_hash,
+                config_hash
+            );
         }
+        if strategy.name() != checkpoint.strategy_name {
+            anyhow::bail!(
+                "Checkpoint was taken with strategy '{}', got '{}'",
+                checkpoint.strategy_name,
+                strategy.name()
+            );
+        }
+        Self::restore_checkpoint(config, checkpoint, strategy)
+    }
+
+    /// Branch a what-if run from a checkpoint under different strategy
+    /// parameters or a different strategy.
+    ///
+    /// Engine state (books, OMS, ledger, queue) continues from the checkpoint;
+    /// the new strategy parameters apply from here on and are recorded in the
+    /// run fingerprint. The rest of the config must match the checkpoint's,
+    /// since the restored engine components (latency model, matching engine,
+    /// risk limits, settlement) were built from it. Strategy state is restored
+    /// only when the strategy name matches the checkpoint's.
+    pub fn fork_from_checkpoint(
+        config: BacktestConfig,
+        checkpoint: &BacktestCheckpoint,
+        strategy: &mut dyn Strategy,
+    ) -> Result<Self> {
+        let engine_hash = ConfigFingerprint::from_config(&config).engine_hash();
+        if engine_hash != checkpoint.engine_config_hash {
+            anyhow::bail!(
+                "Checkpoint engine config hash {:016x} does not match config {:016x}; \
+                 a fork may only change strategy parameters",
+                checkpoint.engine_config_hash,
+                engine_hash
+            );
+        }
+        let mut orchestrator = Self::restore_checkpoint(config, checkpoint, strategy)?;
+        orchestrator.fingerprint_collector.set_config(&orchestrator.config);
+        Ok(orchestrator)
+    }
+
+    fn restore_checkpoint(
+        config: BacktestConfig,
+        checkpoint: &BacktestCheckpoint,
+        strategy: &mut dyn Strategy,
+    ) -> Result<Self> {
+        checkpoint.validate().map_err(anyhow::Error::msg)?;
+        let state: OrchestratorState = rmp_serde::from_slice(checkpoint.state())
+            .map_err(|e| anyhow::anyhow!("Failed to decode checkpoint state: {}", e))?;
+
+        if strategy.name() == checkpoint.strategy_name {
+            if let Some(ref strategy_state) = checkpoint.strategy_state {
+                strategy
+                    .restore_state(strategy_state)
+                    .map_err(|e| anyhow::anyhow!("Failed to restore strategy state: {}", e))?;
+            }
+        }
+
+        let mut orchestrator = Self::new(config);
+        orchestrator.apply_state(state);
+        if let Some(gate) = orchestrator.adapter.risk_gate_mut() {
+            gate.set_market_of(Self::extract_market_id);
+        }
+        orchestrator.run_start_ns = checkpoint.run_start_ns;
+        orchestrator.resumed = true;
+        orchestrator.resume_window_ns = Some(checkpoint.window_start_ns);
+        orchestrator.pending_stream_skip = checkpoint.streaming_events_pulled;
+
+        tracing::info!(
+            window_start = checkpoint.window_start_ns,
+            events_processed = checkpoint.events_processed,
+            "Backtest state restored from checkpoint"
+        );
+        Ok(orchestrator)
+    }
+
+    /// Take a checkpoint if the next event starts at or past the due boundary.
+    fn maybe_checkpoint(&mut self, strategy: &dyn Strategy) -> Result<()> {
+        let Some(due) = self.next_checkpoint_ns else {
+            return Ok(());
+        };
+        let feed_time = self.streaming_feed.as_ref().and_then(|feed| feed.peek_time());
+        let next_time = match (self.event_queue.peek_time(), feed_time) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => match a.or(b) {
+                Some(time) => time,
+                None => return Ok(()),
+            },
+        };
+        if next_time < due {
+            return Ok(());
+        }
+
+        let window_start = align_to_window_start(next_time);
+        self.capture_checkpoint(strategy, window_start)?;
+        if let Some(ref policy) = self.checkpoint_policy {
+            self.next_checkpoint_ns = Some(window_start + policy.interval_ns());
+        }
+        Ok(())
+    }
+
+    fn capture_checkpoint(&mut self, strategy: &dyn Strategy, window_start_ns: Nanos) -> Result<()> {
+        let state = rmp_serde::to_vec_named(&self.state_ref())
+            .map_err(|e| anyhow::anyhow!("Failed to encode checkpoint state: {}", e))?;
+        let config_fingerprint = ConfigFingerprint::from_config(&self.config);
+        let checkpoint = BacktestCheckpoint::new(
+            window_start_ns,
+            self.clock.now(),
+            self.run_start_ns,
+            self.results.events_processed,
+            config_fingerprint.hash,
+            config_fingerprint.engine_hash(),
+            strategy.name().to_string(),
+            strategy.get_state(),
+            self.streaming_feed.as_ref().map(|_| self.streaming_events_pulled),
+            state,
+        );
+
+        let Some(ref policy) = self.checkpoint_policy else {
+            return Ok(());
+        };
+        if let Some(ref directory) = policy.directory {
+            let path = checkpoint.write_to_dir(directory).map_err(anyhow::Error::msg)?;
+            tracing::info!(
+                path = %path.display(),
+                events_processed = checkpoint.events_processed,
+                "Checkpoint written"
+            );
+        }
+        let retain = policy.retain_in_memory;
+        self.checkpoints.push(checkpoint);
+        if self.checkpoints.len() > retain {
+            let excess = self.checkpoints.len() - retain;
+            self.checkpoints.drain(..excess);
+        }
+        Ok(())
     }
 
     /// Create a new orchestrator with production-grade validation.
@@ -1812,12 +2111,83 @@ impl BacktestOrchestrator {
     /// Load events from a data feed into the event queue.
     /// In production-grade mode, any data quality downgrade aborts with an error.
     pub fn load_feed<F: MarketDataFeed>(&mut self, feed: &mut F) -> Result<()> {
+        if self.resumed {
+            anyhow::bail!("Cannot load a feed after resume: the event queue was restored from the checkpoint");
+        }
         while let Some(event) = feed.next_event() {
             self.data_validator.observe(&event)?;
             self.event_queue.push_timestamped(event);
         }
+        if let Some(error) = feed.error() {
+            anyhow::bail!("Feed '{}' failed: {}", feed.name(), error);
+        }
+
+        self.check_data_quality()
+    }
+
+    /// Attach a feed that is pulled lazily during `run()` instead of being
+    /// drained into the event queue up front.
+    ///
+    /// Events are moved into the queue only once they are next in time, so
+    /// memory stays bounded by what the feed itself buffers (e.g. a
+    /// `StreamingMergeFeed` over recorded storage). Replaces any previously
+    /// attached streaming feed.
+    ///
+    /// After resuming from a checkpoint of a streaming run, pass the same
+    /// feed from its beginning: events consumed before the checkpoint are
+    /// skipped.
+    pub fn load_feed_streaming(&mut self, mut feed: Box<dyn MarketDataFeed>) -> Result<()> {
+        let skip = self.pending_stream_skip.take().unwrap_or(0);
+        for skipped in 0..skip {
+            if feed.next_event().is_none() {
+                if let Some(error) = feed.error() {
+                    anyhow::bail!("Streaming feed '{}' failed: {}", feed.name(), error);
+                }
+                anyhow::bail!(
+                    "Streaming feed '{}' ended after {} events; checkpoint consumed {}",
+                    feed.name(),
+                    skipped,
+                    skip
+                );
+            }
+        }
+        self.streaming_events_pulled = skip;
+        self.streaming_feed = Some(feed);
+        Ok(())
+    }
 
-        // In production-grade mode, check for any data quality downgrades
+    /// Move streaming feed events into the queue until the queue head is
+    /// earlier than the feed's next event.
+    fn pull_streaming_feed(&mut self) -> Result<()> {
+        let Some(feed) = self.streaming_feed.as_mut() else {
+            return Ok(());
+        };
+
+        let mut pulled = false;
+        while let Some(time) = feed.peek_time() {
+            if self.event_queue.peek_time().is_some_and(|head| time > head) {
+                break;
+            }
+            let Some(event) = feed.next_event() else {
+                break;
+            };
+            self.data_validator.observe(&event)?;
+            self.event_queue.push_timestamped(event);
+            self.streaming_events_pulled += 1;
+            pulled = true;
+        }
+        if let Some(error) = feed.error() {
+            anyhow::bail!("Streaming feed '{}' failed: {}", feed.name(), error);
+        }
+
+        if pulled {
+            self.check_data_quality()?;
+        }
+        Ok(())
+    }
+
+    /// In production-grade mode, abort on any data quality downgrade.
+    fn check_data_quality(&self) -> Result<()> {
         if self.config.production_grade {
             let summary = self.data_validator.summary();
             if !summary.is_production_grade {
@@ -1833,9 +2203,9 @@ impl BacktestOrchestrator {
         Ok(())
     }
 
-    /// Run the backtest with the given strategy.
-    /// In production-grade mode, validates all requirements upfront and blocks any downgrades.
-    pub fn run(&mut self, strategy: &mut dyn Strategy) -> Result<BacktestResults> {
+    /// Startup gating: operating mode, dataset readiness, maker paths and
+    /// production-grade checks. Skipped when resuming from a checkpoint.
+    fn prepare_run(&mut self) -> Result<()> {
         use crate::backtest_v2::data_contract::DatasetClassification;
         
         // =======================================================================
@@ -2201,7 +2571,13 @@ impl BacktestOrchestrator {
         // Record effective model in results
         self.results.effective_maker_model = self.effective_maker_model;
 
-        let wall_start = std::time::Instant::now();
+        Ok(())
+    }
+
+    /// Initialize the clock at the first event and call `on_start`.
+    /// Returns the run start time.
+    fn start_run(&mut self, strategy: &mut dyn Strategy) -> Result<Nanos> {
+        self.pull_streaming_feed()?;
         let start_time = self.event_queue.peek_time().unwrap_or(0);
         self.clock = SimClock::new(start_time);
         self.adapter.set_time(start_time);
@@ -2226,6 +2602,7 @@ impl BacktestOrchestrator {
                 self.decision_proofs.commit(proof);
             }
         }
+        self.drain_request_log();
         
         // === EQUITY CURVE: Record initial equity observation ===
         // This is recorded after on_start to capture the initial deposit state
@@ -2239,6 +2616,36 @@ impl BacktestOrchestrator {
             );
         }
 
+        self.run_start_ns = start_time;
+        Ok(start_time)
+    }
+
+    /// Run the backtest with the given strategy.
+    /// In production-grade mode, validates all requirements upfront and blocks any downgrades.
+    /// After `resume_from_checkpoint`, startup is skipped and the run continues
+    /// from the checkpointed state.
+    pub fn run(&mut self, strategy: &mut dyn Strategy) -> Result<BacktestResults> {
+        let start_time = if self.resumed {
+            if self.pending_stream_skip.is_some() {
+                anyhow::bail!(
+                    "Checkpoint was taken from a streaming run: attach the same feed with \
+                     load_feed_streaming() before run()"
+                );
+            }
+            self.run_start_ns
+        } else {
+            self.prepare_run()?;
+            self.start_run(strategy)?
+        };
+        let wall_start = std::time::Instant::now();
+
+        // First checkpoint boundary: one interval past the starting window
+        let base_window = self.resume_window_ns.unwrap_or_else(|| align_to_window_start(start_time));
+        self.next_checkpoint_ns = self
+            .checkpoint_policy
+            .as_ref()
+            .map(|policy| base_window + policy.interval_ns());
+
         // Main event loop
         let max_events = if self.config.max_events > 0 {
             self.config.max_events
@@ -2246,7 +2653,32 @@ impl BacktestOrchestrator {
             u64::MAX
         };
 
+        if let Some(ref control) = self.run_control {
+            let streaming_remaining = self
+                .streaming_feed
+                .as_ref()
+                .and_then(|feed| feed.remaining())
+                .unwrap_or(0);
+            control.set_events_total(
+                self.results.events_processed + (self.event_queue.len() + streaming_remaining) as u64,
+            );
+        }
+
         while self.results.events_processed < max_events {
+            // === RUN CONTROL: Progress reporting and cooperative cancellation ===
+            if let Some(ref control) = self.run_control {
+                if control.is_cancelled() {
+                    anyhow::bail!(
+                        "Backtest cancelled after {} events",
+                        self.results.events_processed
+                    );
+                }
+                control.set_events_processed(self.results.events_processed);
+            }
+
+            // === CHECKPOINT: Snapshot engine state at window boundaries ===
+            self.maybe_checkpoint(strategy)?;
+
             // Process any adapter-generated events
             let pending = self.adapter.take_pending_events();
             for event in pending {
@@ -2282,9 +2714,11 @@ impl BacktestOrchestrator {
                 if let Some(proof) = self.current_proof.take() {
                     self.decision_proofs.commit(proof);
                 }
+                self.drain_request_log();
             }
 
             // Get next event
+            self.pull_streaming_feed()?;
             let Some(event) = self.event_queue.pop() else {
                 break;
             };
@@ -2362,6 +2796,7 @@ impl BacktestOrchestrator {
             let decision_time = event.time;
             self.clock.advance_to(decision_time);
             self.adapter.set_time(decision_time);
+            self.adapter.latency_sampler().observe_event(&event);
             self.visibility.advance_to(decision_time);
             self.results.events_processed += 1;
 
@@ -2416,6 +2851,16 @@ impl BacktestOrchestrator {
 
             // Dispatch event to strategy (also feeds price data to settlement engine)
             self.dispatch_event(strategy, &event);
+            self.drain_request_log();
+
+            // Feed the post-event mid to the latency regimes (belief volatility)
+            if let Some(token_id) = event.event.token_id() {
+                if let Some(mid) = self.book_manager.get(token_id).and_then(|b| b.mid_price()) {
+                    self.adapter
+                        .latency_sampler()
+                        .observe_mid(token_id, mid, decision_time);
+                }
+            }
             
             // === INVARIANT ENFORCEMENT: Abort on first violation (Hard mode) ===
             // When invariant_mode is Hard (default), abort immediately on first violation.
@@ -2466,6 +2911,7 @@ impl BacktestOrchestrator {
                 self.decision_proofs.commit(proof);
             }
         }
+        self.drain_request_log();
 
         // Calculate final results
         self.finalize_results(wall_start, end_time - start_time);
@@ -2501,6 +2947,9 @@ impl BacktestOrchestrator {
             self.results.settlement_stats = Some(engine.stats.clone());
         }
 
+        // === MONTE CARLO ROBUSTNESS (input to the gate suite) ===
+        self.run_monte_carlo()?;
+
         // === RUN GATE SUITE (if enabled) ===
         self.run_gate_suite()?;
 
@@ -2547,6 +2996,15 @@ impl BacktestOrchestrator {
             );
         }
         
+        // Record decision trace hash (covers every record, stored or not)
+        self.fingerprint_collector.record_decision_trace_hash(
+            self.decision_trace.hash_u64(),
+            self.decision_trace.total_records,
+        );
+        if self.config.decision_trace_limit > 0 {
+            self.results.decision_trace = Some(self.decision_trace.clone());
+        }
+        
         // Take the collector and finalize it to produce the run fingerprint
         let fingerprint_collector = std::mem::take(&mut self.fingerprint_collector);
         let run_fingerprint = fingerprint_collector.finalize();
@@ -2709,11 +3167,11 @@ impl BacktestOrchestrator {
             }
         }
         
-        tracing::info!("Running gate suite (zero-edge, martingale, signal-inversion tests)");
+        tracing::info!("Running gate suite (zero-edge, martingale, signal-inversion, monte-carlo tests)");
         
         let gate_config = GateSuiteConfig::default();
         let suite = GateSuite::new(gate_config);
-        let report = suite.run();
+        let report = suite.run_with_monte_carlo(self.results.monte_carlo.as_ref());
         
         // Record results
         self.results.gate_suite_passed = report.passed;
@@ -2807,6 +3265,48 @@ impl BacktestOrchestrator {
         Ok(())
     }
 
+    /// Append a record to the decision trace at the current event index and time.
+    fn record_trace(&mut self, record: crate::backtest_v2::run_diff::TraceRecord) {
+        self.decision_trace.push(record, self.results.events_processed, self.clock.now());
+    }
+
+    /// Move order/cancel requests made by the strategy into the decision trace.
+    fn drain_request_log(&mut self) {
+        for record in self.adapter.take_request_log() {
+            self.record_trace(record);
+        }
+    }
+
+    /// Run Monte Carlo robustness analysis over the completed run's PnL.
+    /// 
+    /// Resamples window PnL (block bootstrap) and per-fill PnL (reshuffle, fill drop).
+    /// The report is attached to results and consumed by gate suite Gate D.
+    fn run_monte_carlo(&mut self) -> Result<()> {
+        use crate::backtest_v2::monte_carlo::{MonteCarloAnalyzer, MonteCarloInput};
+        
+        if !self.config.monte_carlo.enabled {
+            return Ok(());
+        }
+        
+        let analyzer = MonteCarloAnalyzer::new(self.config.monte_carlo.clone())
+            .map_err(|e| anyhow::anyhow!("Invalid monte_carlo config: {}", e))?;
+        let initial_capital = self.config.ledger_config.as_ref()
+            .map(|lc| lc.initial_cash)
+            .unwrap_or_else(|| crate::backtest_v2::ledger::LedgerConfig::default().initial_cash);
+        let input = MonteCarloInput::from_results(&self.results, initial_capital)
+            .with_cumulative_trade_pnl(&self.pnl_history);
+        let report = analyzer.analyze(&input);
+        
+        tracing::info!(
+            p5_total_pnl = ?report.p5_total_pnl(),
+            ruin_probability = %report.ruin_probability(),
+            "Monte Carlo robustness completed"
+        );
+        
+        self.results.monte_carlo = Some(report);
+        Ok(())
+    }
+
     /// Process pending settlement events and realize PnL.
     /// 
     /// When ledger is active, this routes all settlements through double-entry accounting.
@@ -2819,6 +3319,11 @@ impl BacktestOrchestrator {
         let decision_time = self.clock.now();
         
         for settlement in settlements {
+            self.record_trace(crate::backtest_v2::run_diff::TraceRecord::settlement(
+                &settlement.market_id,
+                &format!("{:?}", settlement.outcome),
+            ));
+            
             // Determine winner from outcome
             let winner = match &settlement.outcome {
                 SettlementOutcome::Resolved { winner, .. } => *winner,
@@ -2840,6 +3345,9 @@ impl BacktestOrchestrator {
                 }
             };
             
+            // Realized PnL booked by this settlement (per-market attribution)
+            let mut market_realized_pnl = 0.0;
+            
             // === LEDGER: Route settlement through double-entry accounting ===
             if let Some(ref mut ledger) = self.ledger {
                 let settlement_id = self.next_settlement_id;
@@ -2849,6 +3357,7 @@ impl BacktestOrchestrator {
                 ledger.set_decision_id(self.decision_proofs.all().len() as u64);
                 
                 // Post settlement to ledger
+                let realized_before = ledger.realized_pnl();
                 let result = ledger.post_settlement(
                     settlement_id,
                     &settlement.market_id,
@@ -2871,6 +3380,7 @@ impl BacktestOrchestrator {
                         self.results.first_accounting_violation = Some(format!("{:?}", violation));
                     }
                 }
+                market_realized_pnl = ledger.realized_pnl() - realized_before;
                 
                 // === INVARIANT CHECK: Settlement ===
                 // Check for duplicate settlement
@@ -2991,6 +3501,7 @@ impl BacktestOrchestrator {
                     let realized_pnl = settlement_value - cost_basis;
                     
                     self.settlement_realized_pnl += realized_pnl;
+                    market_realized_pnl += realized_pnl;
                     
                     tracing::debug!(
                         market_id = %settlement.market_id,
@@ -3003,6 +3514,15 @@ impl BacktestOrchestrator {
                     );
                 }
             }
+            
+            // === PER-MARKET ATTRIBUTION / PORTFOLIO RISK ===
+            self.results.market_attribution
+                .entry(settlement.market_id.clone())
+                .or_insert_with(|| crate::backtest_v2::multi_market::MarketAttribution::new(&settlement.market_id))
+                .record_settlement(market_realized_pnl);
+            if let Some(gate) = self.adapter.risk_gate_mut() {
+                gate.record_settlement(&settlement.market_id, winner);
+            }
         }
         
         Ok(())
@@ -3027,6 +3547,9 @@ impl BacktestOrchestrator {
 
                 // === BOOK STATE: Apply snapshot to BookManager ===
                 // This is the AUTHORITATIVE book state used for execution simulation
+                self.adapter.matching_engine_mut().apply_book_snapshot(
+                    token_id, bids, asks, *exchange_seq, timestamp,
+                );
                 {
                     let ob = self.book_manager.get_or_create(token_id);
                     ob.apply_snapshot(bids, asks, *exchange_seq, timestamp);
@@ -3093,6 +3616,14 @@ impl BacktestOrchestrator {
             } => {
                 proof = proof.with_market(token_id.clone());
                 
+                for (side, updates) in [(Side::Buy, bid_updates), (Side::Sell, ask_updates)] {
+                    for level in updates {
+                        self.adapter.matching_engine_mut().apply_book_delta(
+                            token_id, side, level.price, level.size, *exchange_seq, timestamp,
+                        );
+                    }
+                }
+
                 // === BOOK STATE: Apply delta to BookManager ===
                 // This maintains the AUTHORITATIVE book state
                 let delta_result = {
@@ -3206,6 +3737,9 @@ impl BacktestOrchestrator {
                 // Apply to book manager - get next expected sequence
                 let ob = self.book_manager.get_or_create(token_id);
                 let expected_seq = ob.last_seq.saturating_add(1);
+                self.adapter.matching_engine_mut().apply_book_delta(
+                    token_id, *side, *price, *new_size, expected_seq, timestamp,
+                );
                 let delta_result = ob.apply_delta(bid_updates, ask_updates, expected_seq, timestamp);
                 
                 // === BOOK INVARIANT CHECK: Crossed book detection ===
@@ -3299,6 +3833,9 @@ impl BacktestOrchestrator {
                 client_order_id,
                 exchange_time,
             } => {
+                self.adapter.process_order_ack(*order_id);
+                self.record_trace(crate::backtest_v2::run_diff::TraceRecord::order_ack(*order_id));
+
                 let ack = OrderAck {
                     order_id: *order_id,
                     client_order_id: client_order_id.clone(),
@@ -3324,6 +3861,11 @@ impl BacktestOrchestrator {
                     reason: format!("{:?}", reason),
                     timestamp,
                 };
+                self.adapter.process_order_reject(*order_id, &reject.reason);
+                self.record_trace(crate::backtest_v2::run_diff::TraceRecord::order_reject(
+                    *order_id,
+                    &reject.reason,
+                ));
 
                 let mut ctx = StrategyContext {
                     orders: &mut self.adapter,
@@ -3452,6 +3994,31 @@ impl BacktestOrchestrator {
                     self.results.total_volume += size * price;
                     self.results.total_fees += fee;
 
+                    // Resolve market/outcome/side BEFORE the adapter update: a fully
+                    // filled order is removed from the open order set by it.
+                    let fill_order_info = self.adapter.get_open_orders()
+                        .into_iter()
+                        .find(|o| o.order_id == *order_id)
+                        .map(|oi| {
+                            let outcome = if oi.token_id.to_lowercase().contains("no") {
+                                crate::backtest_v2::portfolio::Outcome::No
+                            } else {
+                                crate::backtest_v2::portfolio::Outcome::Yes
+                            };
+                            (Self::extract_market_id(&oi.token_id), outcome, oi.side)
+                        });
+
+                    // === PER-MARKET ATTRIBUTION / PORTFOLIO RISK ===
+                    if let Some((market_id, outcome, side)) = &fill_order_info {
+                        self.results.market_attribution
+                            .entry(market_id.clone())
+                            .or_insert_with(|| crate::backtest_v2::multi_market::MarketAttribution::new(market_id))
+                            .record_fill(*is_maker, *size, *price, *fee);
+                        if let Some(gate) = self.adapter.risk_gate_mut() {
+                            gate.record_fill(market_id, *outcome, *side, *size, *price, *fee, timestamp);
+                        }
+                    }
+
                     // =================================================================
                     // STRICT ACCOUNTING MODE: Ledger is the ONLY pathway
                     // =================================================================
@@ -3479,20 +4046,9 @@ impl BacktestOrchestrator {
                         use crate::backtest_v2::portfolio::Outcome;
                         use crate::backtest_v2::events::Side;
                         
-                        // Get order info to determine market/outcome
-                        // For now we use the token_id as market_id and assume Yes outcome
-                        // A full implementation would parse token_id to extract market and outcome
-                        let open_orders = self.adapter.get_open_orders();
-                        let order_info = open_orders.iter().find(|o| o.order_id == *order_id);
-                        
-                        let (market_id, outcome, side) = if let Some(oi) = order_info {
-                            let outcome = if oi.token_id.to_lowercase().contains("no") 
-                                || oi.token_id.to_lowercase().ends_with("-no") {
-                                Outcome::No
-                            } else {
-                                Outcome::Yes
-                            };
-                            (Self::extract_market_id(&oi.token_id), outcome, oi.side)
+                        // Order info was resolved before the adapter update
+                        let (market_id, outcome, side) = if let Some(info) = fill_order_info {
+                            info
                         } else {
                             // Fallback: use adapter positions to infer
                             let positions = self.adapter.get_all_positions();
@@ -3515,6 +4071,7 @@ impl BacktestOrchestrator {
                         ledger.set_decision_id(self.decision_proofs.all().len() as u64);
                         
                         // Post fill to ledger
+                        let realized_before = ledger.realized_pnl();
                         let result = ledger.post_fill(
                             fill_id,
                             &market_id,
@@ -3534,6 +4091,14 @@ impl BacktestOrchestrator {
                                 self.results.first_accounting_violation = Some(format!("{:?}", violation));
                             }
                         } else {
+                            // Closing fills realize PnL before settlement
+                            let realized_delta = ledger.realized_pnl() - realized_before;
+                            if realized_delta != 0.0 {
+                                if let Some(attribution) = self.results.market_attribution.get_mut(&market_id) {
+                                    attribution.realized_pnl += realized_delta;
+                                }
+                            }
+                            
                             // === EQUITY CURVE: Record observation after fill ===
                             if let Some(ref mut recorder) = self.equity_recorder {
                                 use crate::backtest_v2::equity_curve::EquityObservationTrigger;
@@ -3623,6 +4188,13 @@ impl BacktestOrchestrator {
                         }
                     }
 
+                    self.record_trace(crate::backtest_v2::run_diff::TraceRecord::fill(
+                        *order_id,
+                        *price,
+                        *size,
+                        *is_maker,
+                    ));
+
                     let fill = FillNotification {
                         order_id: *order_id,
                         client_order_id: None, // Would need lookup
@@ -3658,6 +4230,10 @@ impl BacktestOrchestrator {
                 cancelled_qty,
             } => {
                 self.adapter.process_cancel_ack(*order_id, *cancelled_qty);
+                self.record_trace(crate::backtest_v2::run_diff::TraceRecord::cancel_ack(
+                    *order_id,
+                    *cancelled_qty,
+                ));
 
                 let ack = CancelAck {
                     order_id: *order_id,
@@ -3673,6 +4249,60 @@ impl BacktestOrchestrator {
                 strategy.on_cancel_ack(&mut ctx, &ack);
             }
 
+            Event::ReplaceAck {
+                order_id,
+                price,
+                leaves_qty,
+                kept_priority,
+            } => {
+                self.adapter
+                    .process_replace_ack(*order_id, *price, *leaves_qty);
+                let price_ticks = (*price * 100.0).round() as PriceTicks;
+                self.queue_model
+                    .replace_order(*order_id, price_ticks, *leaves_qty, timestamp);
+                self.record_trace(crate::backtest_v2::run_diff::TraceRecord::replace_ack(
+                    *order_id,
+                    *price,
+                    *leaves_qty,
+                ));
+
+                let ack = ReplaceAck {
+                    order_id: *order_id,
+                    price: *price,
+                    leaves_qty: *leaves_qty,
+                    kept_priority: *kept_priority,
+                    timestamp,
+                };
+
+                let mut ctx = StrategyContext {
+                    orders: &mut self.adapter,
+                    timestamp,
+                    params: &self.config.strategy_params,
+                };
+                strategy.on_replace_ack(&mut ctx, &ack);
+            }
+
+            Event::ReplaceReject { order_id, reason } => {
+                self.adapter.process_replace_reject(*order_id);
+
+                let reject = ReplaceReject {
+                    order_id: *order_id,
+                    reason: format!("{:?}", reason),
+                    timestamp,
+                };
+                self.record_trace(crate::backtest_v2::run_diff::TraceRecord::replace_reject(
+                    *order_id,
+                    &reject.reason,
+                ));
+
+                let mut ctx = StrategyContext {
+                    orders: &mut self.adapter,
+                    timestamp,
+                    params: &self.config.strategy_params,
+                };
+                strategy.on_replace_reject(&mut ctx, &reject);
+            }
+
             Event::Timer { timer_id, payload } => {
                 let timer = TimerEvent {
                     timer_id: *timer_id,
@@ -3699,6 +4329,17 @@ impl BacktestOrchestrator {
     }
 
     fn finalize_results(&mut self, wall_start: std::time::Instant, duration_ns: Nanos) {
+        // === PORTFOLIO RISK: Gate statistics and per-market blocks ===
+        if let Some(gate) = self.adapter.risk_gate() {
+            self.results.portfolio_risk = Some(gate.stats().clone());
+            for (market_id, blocked) in gate.blocks_by_market() {
+                self.results.market_attribution
+                    .entry(market_id.clone())
+                    .or_insert_with(|| crate::backtest_v2::multi_market::MarketAttribution::new(market_id))
+                    .orders_blocked = *blocked;
+            }
+        }
+        
         // =================================================================
         // ACCOUNTING MODE ENFORCEMENT
         // =================================================================
@@ -4056,6 +4697,74 @@ impl BacktestOrchestrator {
     }
 }
 
+// =============================================================================
+// CHECKPOINT STATE
+// =============================================================================
+
+/// Declares the orchestrator fields captured in a checkpoint, generating a
+/// borrowed view for encoding and an owned form for decoding.
+macro_rules! checkpoint_state {
+    ($($field:ident: $ty:ty,)*) => {
+        #[derive(Serialize)]
+        struct OrchestratorStateRef<'a> {
+            $($field: &'a $ty,)*
+        }
+
+        #[derive(Deserialize)]
+        struct OrchestratorState {
+            $($field: $ty,)*
+        }
+
+        impl BacktestOrchestrator {
+            fn state_ref(&self) -> OrchestratorStateRef<'_> {
+                OrchestratorStateRef {
+                    $($field: &self.$field,)*
+                }
+            }
+
+            fn apply_state(&mut self, state: OrchestratorState) {
+                $(self.$field = state.$field;)*
+            }
+        }
+    };
+}
+
+// Everything except config, run control, the streaming feed and checkpoint
+// bookkeeping; those are supplied again when resuming.
+checkpoint_state! {
+    clock: SimClock,
+    event_queue: EventQueue,
+    adapter: SimulatedOrderSender,
+    results: BacktestResults,
+    data_validator: DataContractValidator,
+    visibility: VisibilityWatermark,
+    decision_proofs: DecisionProofBuffer,
+    current_proof: Option<DecisionProof>,
+    pnl_history: Vec<f64>,
+    last_mid: std::collections::HashMap<String, f64>,
+    book_manager: BookManager,
+    queue_model: QueuePositionModel,
+    pending_cancels: std::collections::HashMap<u64, Nanos>,
+    settlement_engine: Option<crate::backtest_v2::settlement::SettlementEngine>,
+    tracked_markets: std::collections::HashSet<String>,
+    pending_settlements: Vec<crate::backtest_v2::settlement::SettlementEvent>,
+    settlement_realized_pnl: f64,
+    ledger: Option<crate::backtest_v2::ledger::Ledger>,
+    next_fill_id: u64,
+    next_settlement_id: u64,
+    invariant_enforcer: crate::backtest_v2::invariants::InvariantEnforcer,
+    effective_maker_model: MakerFillModel,
+    dataset_readiness: Option<DatasetReadiness>,
+    maker_paths_enabled: bool,
+    maker_fill_gate: MakerFillGate,
+    fingerprint_collector: crate::backtest_v2::fingerprint::FingerprintCollector,
+    integrity_guard: crate::backtest_v2::integrity::StreamIntegrityGuard,
+    hermetic_enforcer: crate::backtest_v2::hermetic::HermeticEnforcer,
+    window_accounting: Option<crate::backtest_v2::window_pnl::WindowAccountingEngine>,
+    equity_recorder: Option<crate::backtest_v2::equity_curve::EquityRecorder>,
+    decision_trace: crate::backtest_v2::run_diff::DecisionTrace,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4063,6 +4772,7 @@ mod tests {
     use crate::backtest_v2::example_strategy::MarketMakerStrategy;
     use crate::backtest_v2::feed::VecFeed;
     use crate::backtest_v2::queue::StreamSource;
+    use crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
 
     fn make_book_event(time: Nanos, mid: f64) -> TimestampedEvent {
         TimestampedEvent::new(
@@ -4498,6 +5208,11 @@ mod tests {
         // Gate suite should have run and produced a report
         assert!(results.gate_suite_report.is_some(), "Gate suite report should be present");
         
+        // Monte Carlo report is attached and fed to the gate suite as Gate D
+        assert!(results.monte_carlo.is_some(), "Monte Carlo report should be present");
+        assert!(results.gate_suite_report.as_ref().unwrap().gates.iter()
+            .any(|g| g.name == "Gate D: Monte Carlo Robustness"));
+        
         // Trust level should not be Unknown or Bypassed
         assert_ne!(results.trust_level, TrustLevel::Unknown);
         assert_ne!(results.trust_level, TrustLevel::Bypassed);
@@ -5439,6 +6154,175 @@ mod tests {
         // Verify book state was tracked (mid price should be set)
         assert!(orchestrator.last_mid.contains_key("TEST"));
     }
+
+    #[test]
+    fn test_integration_streaming_feed_matches_loaded_feed() {
+        // Test: a lazily pulled feed replays exactly like a fully loaded one
+        use crate::backtest_v2::streaming_feed::{StreamingMergeFeed, VecChunkSource};
+
+        let events: Vec<_> = (1..=40)
+            .map(|i| make_book_event(i * 250_000_000, 0.50 + ((i % 7) as f64 - 3.0) * 0.01))
+            .collect();
+
+        let mut config = BacktestConfig::test_config();
+        config.maker_fill_model = MakerFillModel::MakerDisabled;
+
+        let mut loaded = BacktestOrchestrator::new(config.clone());
+        loaded.load_feed(&mut VecFeed::new("loaded", events.clone())).unwrap();
+        let expected = loaded.run(&mut NoOpStrategy).unwrap();
+
+        let mut streamed = BacktestOrchestrator::new(config);
+        streamed.load_feed_streaming(Box::new(StreamingMergeFeed::new(
+            "streamed",
+            vec![Box::new(VecChunkSource::new("book", events, 8))],
+        )))
+        .unwrap();
+        let actual = streamed.run(&mut NoOpStrategy).unwrap();
+
+        assert_eq!(actual.events_processed, 40);
+        assert_eq!(actual.events_processed, expected.events_processed);
+        assert_eq!(actual.total_decisions, expected.total_decisions);
+        assert_eq!(actual.duration_ns, expected.duration_ns);
+        assert_eq!(streamed.last_mid, loaded.last_mid);
+    }
+
+    /// Book updates every 30s for just under an hour: four 15-minute windows.
+    fn checkpoint_events() -> Vec<TimestampedEvent> {
+        (1..120)
+            .map(|i| make_book_event(i * 30_000_000_000, 0.50 + ((i % 9) as f64 - 4.0) * 0.01))
+            .collect()
+    }
+
+    fn checkpoint_config() -> BacktestConfig {
+        let mut config = BacktestConfig::test_config();
+        config.maker_fill_model = MakerFillModel::MakerDisabled;
+        config.strategy_params = StrategyParams::new()
+            .with_string("token_id", "TEST")
+            .with_param("half_spread", 0.01)
+            // Quote on book updates only
+            .with_param("requote_interval_sec", 1.0e6);
+        config
+    }
+
+    #[test]
+    fn test_checkpoint_resume_matches_uninterrupted_run() {
+        // Test: resuming from any mid-run checkpoint reproduces the run fingerprint
+        let config = checkpoint_config();
+
+        let mut full = BacktestOrchestrator::new(config.clone());
+        full.set_checkpoint_policy(CheckpointPolicy {
+            retain_in_memory: 16,
+            ..CheckpointPolicy::every_window()
+        });
+        full.load_feed(&mut VecFeed::new("test", checkpoint_events())).unwrap();
+        let mut strategy = MarketMakerStrategy::new(&config.strategy_params);
+        let expected = full.run(&mut strategy).unwrap();
+        let expected_hash = expected.run_fingerprint.as_ref().unwrap().hash_hex.clone();
+
+        let checkpoints = full.checkpoints().to_vec();
+        assert_eq!(checkpoints.len(), 3);
+        assert!(strategy.stats.quotes_sent > 0);
+
+        for checkpoint in &checkpoints {
+            assert_eq!(checkpoint.window_start_ns % WINDOW_DURATION_NS, 0);
+            let checkpoint = BacktestCheckpoint::from_bytes(&checkpoint.to_bytes().unwrap()).unwrap();
+
+            let mut resumed_strategy = MarketMakerStrategy::new(&config.strategy_params);
+            let mut resumed =
+                BacktestOrchestrator::resume_from_checkpoint(config.clone(), &checkpoint, &mut resumed_strategy)
+                    .unwrap();
+            let actual = resumed.run(&mut resumed_strategy).unwrap();
+
+            assert_eq!(actual.events_processed, expected.events_processed);
+            assert_eq!(actual.duration_ns, expected.duration_ns);
+            assert_eq!(resumed_strategy.stats.quotes_sent, strategy.stats.quotes_sent);
+            assert_eq!(
+                actual.run_fingerprint.as_ref().unwrap().hash_hex,
+                expected_hash,
+                "resume from window {} diverged",
+                checkpoint.window_start_ns
+            );
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_resume_streaming_feed_from_disk() {
+        // Test: a streaming run resumes from the latest on-disk checkpoint
+        use crate::backtest_v2::streaming_feed::{StreamingMergeFeed, VecChunkSource};
+
+        let config = checkpoint_config();
+        let dir = tempfile::tempdir().unwrap();
+        let streaming_feed = || -> Box<dyn MarketDataFeed> {
+            Box::new(StreamingMergeFeed::new(
+                "streamed",
+                vec![Box::new(VecChunkSource::new("book", checkpoint_events(), 16))],
+            ))
+        };
+
+        let mut full = BacktestOrchestrator::new(config.clone());
+        full.set_checkpoint_policy(CheckpointPolicy::every_window().with_directory(dir.path()));
+        full.load_feed_streaming(streaming_feed()).unwrap();
+        let expected = full.run(&mut MarketMakerStrategy::new(&config.strategy_params)).unwrap();
+
+        let checkpoint = BacktestCheckpoint::latest_in(dir.path()).unwrap().unwrap();
+        assert_eq!(checkpoint.window_start_ns, 3 * WINDOW_DURATION_NS);
+        assert!(checkpoint.streaming_events_pulled.unwrap() > 0);
+
+        let mut strategy = MarketMakerStrategy::new(&config.strategy_params);
+        let mut resumed =
+            BacktestOrchestrator::resume_from_checkpoint(config, &checkpoint, &mut strategy).unwrap();
+        assert!(resumed.run(&mut strategy).unwrap_err().to_string().contains("load_feed_streaming"));
+        resumed.load_feed_streaming(streaming_feed()).unwrap();
+        let actual = resumed.run(&mut strategy).unwrap();
+
+        assert_eq!(actual.events_processed, expected.events_processed);
+        assert_eq!(
+            actual.run_fingerprint.unwrap().hash_hex,
+            expected.run_fingerprint.unwrap().hash_hex
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_fork_with_different_config() {
+        // Test: resume requires the same config; fork branches under a new one
+        let config = checkpoint_config();
+
+        let mut full = BacktestOrchestrator::new(config.clone());
+        full.set_checkpoint_policy(CheckpointPolicy::every_window());
+        full.load_feed(&mut VecFeed::new("test", checkpoint_events())).unwrap();
+        let expected = full.run(&mut MarketMakerStrategy::new(&config.strategy_params)).unwrap();
+        let checkpoint = full.checkpoints().last().unwrap().clone();
+
+        let mut what_if = config.clone();
+        what_if.strategy_params = what_if.strategy_params.clone().with_param("half_spread", 0.03);
+        let mut strategy = MarketMakerStrategy::new(&what_if.strategy_params);
+
+        let err = BacktestOrchestrator::resume_from_checkpoint(what_if.clone(), &checkpoint, &mut strategy)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("fork_from_checkpoint"));
+
+        let mut fork = BacktestOrchestrator::fork_from_checkpoint(what_if, &checkpoint, &mut strategy).unwrap();
+        assert!(fork.load_feed(&mut VecFeed::new("extra", checkpoint_events())).is_err());
+        let forked = fork.run(&mut strategy).unwrap();
+
+        // Continues past the checkpoint rather than replaying from the start;
+        // the totals differ from `expected` since quotes (and fills) change
+        assert!(forked.events_processed > checkpoint.events_processed);
+        assert_ne!(
+            forked.run_fingerprint.unwrap().hash,
+            expected.run_fingerprint.unwrap().hash
+        );
+
+        // The restored engine was built from the checkpointed config, so a
+        // fork that changes anything outside strategy_params is refused
+        let mut engine_change = config.clone();
+        engine_change.matching.fees.taker_fee_rate += 0.01;
+        let err = BacktestOrchestrator::fork_from_checkpoint(engine_change, &checkpoint, &mut strategy)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("only change strategy parameters"));
+    }
     
     #[test]
     fn test_integration_book_snapshot_updates_book_manager() {
@@ -5702,6 +6586,111 @@ mod tests {
         }
     }
     
+    /// Sends one IOC buy per book update and records the fills per order.
+    struct RepeatedTaker {
+        size: f64,
+        sent: usize,
+        fills: std::collections::HashMap<crate::backtest_v2::events::OrderId, Vec<(f64, f64)>>,
+    }
+
+    impl RepeatedTaker {
+        /// VWAP of each order, in submission order.
+        fn vwaps(&self) -> Vec<f64> {
+            let mut ids: Vec<_> = self.fills.keys().copied().collect();
+            ids.sort_unstable();
+            ids.iter()
+                .map(|id| {
+                    let fills = &self.fills[id];
+                    let size: f64 = fills.iter().map(|(_, s)| s).sum();
+                    fills.iter().map(|(p, s)| p * s).sum::<f64>() / size
+                })
+                .collect()
+        }
+    }
+
+    impl Strategy for RepeatedTaker {
+        fn name(&self) -> &str { "RepeatedTaker" }
+        fn on_start(&mut self, _ctx: &mut StrategyContext) {}
+        fn on_stop(&mut self, _ctx: &mut StrategyContext) {}
+        fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+            self.sent += 1;
+            let order = crate::backtest_v2::strategy::StrategyOrder::limit(
+                format!("take_{}", self.sent),
+                &book.token_id,
+                Side::Buy,
+                0.60,
+                self.size,
+            )
+            .ioc();
+            ctx.orders.send_order(order).unwrap();
+        }
+        fn on_trade(&mut self, _ctx: &mut StrategyContext, _trade: &TradePrint) {}
+        fn on_fill(&mut self, _ctx: &mut StrategyContext, fill: &FillNotification) {
+            self.fills.entry(fill.order_id).or_default().push((fill.price, fill.size));
+        }
+        fn on_order_ack(&mut self, _ctx: &mut StrategyContext, _ack: &OrderAck) {}
+        fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {}
+        fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, _ack: &CancelAck) {}
+        fn on_timer(&mut self, _ctx: &mut StrategyContext, _timer: &TimerEvent) {}
+    }
+
+    #[test]
+    fn test_integration_market_impact_moves_taker_fills() {
+        // Test: repeated aggression inside one window pays more once consumed
+        // depth no longer heals with the next recorded snapshot
+        use crate::backtest_v2::clock::NANOS_PER_SEC;
+        use crate::backtest_v2::market_impact::MarketImpactConfig;
+
+        let run = |impact: MarketImpactConfig| {
+            // The same book is recorded every second
+            let events: Vec<_> = (1..=3)
+                .map(|i| {
+                    TimestampedEvent::new(
+                        i * 1_000_000_000,
+                        StreamSource::MarketData as u8,
+                        Event::L2BookSnapshot {
+                            token_id: "TEST".into(),
+                            bids: vec![Level::new(0.50, 1_000.0)],
+                            asks: vec![
+                                Level::new(0.55, 100.0),
+                                Level::new(0.56, 100.0),
+                                Level::new(0.57, 1_000.0),
+                            ],
+                            exchange_seq: i as u64,
+                        },
+                    )
+                })
+                .collect();
+            let mut config = BacktestConfig::test_config();
+            config.maker_fill_model = MakerFillModel::MakerDisabled;
+            config.market_impact = Some(impact);
+
+            let mut orchestrator = BacktestOrchestrator::new(config);
+            orchestrator.load_feed(&mut VecFeed::new("test", events)).unwrap();
+            let mut strategy = RepeatedTaker {
+                size: 100.0,
+                sent: 0,
+                fills: Default::default(),
+            };
+            let results = orchestrator.run(&mut strategy).unwrap();
+            (results, strategy.vwaps())
+        };
+
+        // Instant refill: every order fills at the recorded best ask
+        let (healed, healed_vwaps) = run(MarketImpactConfig::none());
+        assert_eq!(healed_vwaps.len(), 3);
+        assert!(healed_vwaps.iter().all(|v| (v - 0.55).abs() < 1e-9), "{:?}", healed_vwaps);
+        assert!(healed.taker_fills >= 3);
+
+        // A 60s half-life keeps the swept 0.55 level (mostly) missing
+        let (impacted, vwaps) = run(MarketImpactConfig::half_life(60 * NANOS_PER_SEC, 0.0));
+        assert_eq!(vwaps.len(), 3);
+        assert!((vwaps[0] - 0.55).abs() < 1e-9);
+        assert!(vwaps[1] > vwaps[0] + 0.005, "{:?}", vwaps);
+        assert!(vwaps[2] > vwaps[1] + 0.005, "{:?}", vwaps);
+        assert!(impacted.total_volume > healed.total_volume);
+    }
+    
     #[test]
     fn test_integration_run_fingerprint_generated() {
         // Test: A run fingerprint is generated at the end of the run
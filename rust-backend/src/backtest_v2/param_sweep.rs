@@ -0,0 +1,855 @@
+//! Strategy Parameter Sweep
+//!
+//! Grid / random / Latin-hypercube search over `StrategyParams` keys.
+//!
+//! Unlike `sensitivity.rs` (which sweeps *execution assumptions* such as latency,
+//! sampling and queue models around a fixed strategy), this module sweeps the
+//! *strategy's own parameters* around a fixed execution model.
+//!
+//! # Execution Model
+//!
+//! ```text
+//! ParamSweepConfig ──► sample_points() ──► [SweepPoint; N]
+//!                                               │
+//!                        rayon worker pool ◄────┘
+//!                               │
+//!           ┌───────────────────┼───────────────────┐
+//!           ▼                   ▼                   ▼
+//!   BacktestOrchestrator  BacktestOrchestrator  BacktestOrchestrator
+//!   (own feed, own RNG)   (own feed, own RNG)   (own feed, own RNG)
+//!           │                   │                   │
+//!           └───────────────────┼───────────────────┘
+//!                               ▼
+//!             ArtifactStore (one RunArtifact per point,
+//!                            tagged with the shared SweepId)
+//! ```
+//!
+//! # Determinism
+//!
+//! - Sample points are drawn from a seeded `ChaCha8Rng`, independent of worker count.
+//! - Every point is an independent orchestrator run with `BacktestConfig::seed`
+//!   unchanged, so a point's `RunFingerprint` is identical whether it was run
+//!   inside a sweep or alone via `backtest_run`.
+//! - The sampled parameters are written into `BacktestConfig::strategy_params`,
+//!   so they are covered by `ConfigFingerprint::strategy_params_hash` and two
+//!   points never collide on `RunId` unless their parameters are identical.
+//! - The `SweepId` is derived from the sweep definition only, so re-running the
+//!   same sweep re-tags the same run set.
+//!
+//! # Trust
+//!
+//! Every point is marked `SampleScope::InSample`: its parameters are chosen by
+//! looking at the very data it is scored on, so `TrustGate` never certifies a
+//! sweep point. Use `walk_forward` to obtain certifiable out-of-sample results.
+
+use crate::backtest_v2::artifact_store::{ArtifactStore, ArtifactStoreError, RunGroupKind, RunGroupMember};
+use crate::backtest_v2::events::TimestampedEvent;
+use crate::backtest_v2::feed::VecFeed;
+use crate::backtest_v2::gate_suite::TrustLevel;
+use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator, BacktestResults};
+use crate::backtest_v2::run_artifact::{RunArtifact, RunId};
+use crate::backtest_v2::strategy::StrategyParams;
+use crate::backtest_v2::strategy_factory::make_strategy;
+use crate::backtest_v2::walk_forward::SampleScope;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// Role label used when tagging sweep runs in the artifact store.
+pub const SWEEP_POINT_ROLE: &str = "sweep_point";
+
+/// Upper bound on the number of points a single sweep may expand to.
+/// Guards against accidental combinatorial explosion of grid sweeps.
+pub const MAX_SWEEP_POINTS: usize = 10_000;
+
+// =============================================================================
+// SWEEP DEFINITION
+// =============================================================================
+
+/// Domain of values a single parameter may take.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParamDomain {
+    /// Explicit list of values.
+    Discrete(Vec<f64>),
+    /// Closed interval `[min, max]`. Grid sampling uses `grid_steps` evenly
+    /// spaced values (including both endpoints); random and Latin-hypercube
+    /// sampling draw from the whole interval.
+    Continuous { min: f64, max: f64, grid_steps: usize },
+}
+
+/// One swept `StrategyParams` key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamAxis {
+    /// Key in `StrategyParams::params`.
+    pub key: String,
+    /// Values the key may take.
+    pub domain: ParamDomain,
+}
+
+impl ParamAxis {
+    /// Axis over an explicit list of values.
+    pub fn discrete(key: impl Into<String>, values: Vec<f64>) -> Self {
+        Self {
+            key: key.into(),
+            domain: ParamDomain::Discrete(values),
+        }
+    }
+
+    /// Axis over a closed interval.
+    pub fn continuous(key: impl Into<String>, min: f64, max: f64, grid_steps: usize) -> Self {
+        Self {
+            key: key.into(),
+            domain: ParamDomain::Continuous { min, max, grid_steps },
+        }
+    }
+
+    /// Values used for grid sampling.
+    pub fn grid_values(&self) -> Vec<f64> {
+        match &self.domain {
+            ParamDomain::Discrete(values) => values.clone(),
+            ParamDomain::Continuous { min, max, grid_steps } => match *grid_steps {
+                0 => vec![],
+                1 => vec![*min],
+                n => (0..n)
+                    .map(|i| min + (max - min) * i as f64 / (n - 1) as f64)
+                    .collect(),
+            },
+        }
+    }
+
+    /// Map a unit-interval sample `u ∈ [0, 1)` onto this axis.
+    fn value_at(&self, u: f64) -> f64 {
+        match &self.domain {
+            ParamDomain::Discrete(values) => {
+                let idx = ((u * values.len() as f64) as usize).min(values.len() - 1);
+                values[idx]
+            }
+            ParamDomain::Continuous { min, max, .. } => min + (max - min) * u,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.key.is_empty() {
+            return Err("sweep axis has an empty key".to_string());
+        }
+        match &self.domain {
+            ParamDomain::Discrete(values) => {
+                if values.is_empty() {
+                    return Err(format!("sweep axis '{}' has no values", self.key));
+                }
+                if values.iter().any(|v| !v.is_finite()) {
+                    return Err(format!("sweep axis '{}' has non-finite values", self.key));
+                }
+            }
+            ParamDomain::Continuous { min, max, grid_steps } => {
+                if !min.is_finite() || !max.is_finite() || min > max {
+                    return Err(format!(
+                        "sweep axis '{}' has invalid range [{}, {}]",
+                        self.key, min, max
+                    ));
+                }
+                if *grid_steps == 0 {
+                    return Err(format!("sweep axis '{}' has grid_steps = 0", self.key));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How sweep points are drawn from the parameter axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SweepSampling {
+    /// Full Cartesian product of every axis' grid values.
+    Grid,
+    /// Independent uniform samples per axis.
+    Random { samples: usize },
+    /// Latin hypercube: each axis is split into `samples` equal strata and
+    /// every stratum is used exactly once.
+    LatinHypercube { samples: usize },
+}
+
+impl SweepSampling {
+    /// Human-readable description.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Grid => "Full grid".to_string(),
+            Self::Random { samples } => format!("Random ({} samples)", samples),
+            Self::LatinHypercube { samples } => format!("Latin hypercube ({} samples)", samples),
+        }
+    }
+}
+
+/// Complete description of a parameter sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamSweepConfig {
+    /// Strategy name as accepted by `strategy_factory::make_strategy`.
+    pub strategy_name: String,
+    /// Parameters shared by every point (swept keys are overwritten).
+    pub base_params: BTreeMap<String, f64>,
+    /// Swept parameter axes.
+    pub axes: Vec<ParamAxis>,
+    /// Sampling method.
+    pub sampling: SweepSampling,
+    /// Seed for random / Latin-hypercube sampling. Independent of the
+    /// backtest seed in `BacktestConfig`.
+    pub sampling_seed: u64,
+    /// Number of rayon workers (None = rayon default, one per core).
+    pub max_workers: Option<usize>,
+}
+
+impl ParamSweepConfig {
+    /// Create a grid sweep over the given axes.
+    pub fn grid(strategy_name: impl Into<String>, axes: Vec<ParamAxis>) -> Self {
+        Self {
+            strategy_name: strategy_name.into(),
+            base_params: BTreeMap::new(),
+            axes,
+            sampling: SweepSampling::Grid,
+            sampling_seed: 42,
+            max_workers: None,
+        }
+    }
+
+    /// Validate the sweep definition.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.axes.is_empty() {
+            return Err("sweep has no parameter axes".to_string());
+        }
+        let mut seen = std::collections::HashSet::new();
+        for axis in &self.axes {
+            axis.validate()?;
+            if !seen.insert(axis.key.as_str()) {
+                return Err(format!("sweep axis '{}' is declared twice", axis.key));
+            }
+        }
+        let count = self.point_count();
+        if count == 0 {
+            return Err("sweep expands to zero points".to_string());
+        }
+        if count > MAX_SWEEP_POINTS {
+            return Err(format!(
+                "sweep expands to {} points (max {})",
+                count, MAX_SWEEP_POINTS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Number of points this sweep expands to.
+    pub fn point_count(&self) -> usize {
+        match self.sampling {
+            SweepSampling::Grid => self
+                .axes
+                .iter()
+                .map(|a| a.grid_values().len())
+                .try_fold(1usize, |acc, n| acc.checked_mul(n))
+                .unwrap_or(usize::MAX),
+            SweepSampling::Random { samples } | SweepSampling::LatinHypercube { samples } => samples,
+        }
+    }
+
+    /// Deterministic identifier for this sweep definition.
+    pub fn sweep_id(&self) -> SweepId {
+        let mut hasher = DefaultHasher::new();
+        self.strategy_name.to_lowercase().hash(&mut hasher);
+        for (key, value) in &self.base_params {
+            key.hash(&mut hasher);
+            value.to_bits().hash(&mut hasher);
+        }
+        for axis in &self.axes {
+            axis.key.hash(&mut hasher);
+            match &axis.domain {
+                ParamDomain::Discrete(values) => {
+                    0u8.hash(&mut hasher);
+                    for v in values {
+                        v.to_bits().hash(&mut hasher);
+                    }
+                }
+                ParamDomain::Continuous { min, max, grid_steps } => {
+                    1u8.hash(&mut hasher);
+                    min.to_bits().hash(&mut hasher);
+                    max.to_bits().hash(&mut hasher);
+                    grid_steps.hash(&mut hasher);
+                }
+            }
+        }
+        self.sampling.hash(&mut hasher);
+        self.sampling_seed.hash(&mut hasher);
+        SweepId(format!("sweep_{:016x}", hasher.finish()))
+    }
+
+    /// Expand the sweep definition into concrete points.
+    ///
+    /// Points are returned in a stable order that does not depend on the
+    /// number of workers used to run them.
+    pub fn sample_points(&self) -> Result<Vec<SweepPoint>, String> {
+        self.validate()?;
+
+        let rows: Vec<Vec<f64>> = match self.sampling {
+            SweepSampling::Grid => {
+                let grids: Vec<Vec<f64>> = self.axes.iter().map(|a| a.grid_values()).collect();
+                let mut rows: Vec<Vec<f64>> = vec![vec![]];
+                for grid in &grids {
+                    let mut next = Vec::with_capacity(rows.len() * grid.len());
+                    for row in &rows {
+                        for v in grid {
+                            let mut r = row.clone();
+                            r.push(*v);
+                            next.push(r);
+                        }
+                    }
+                    rows = next;
+                }
+                rows
+            }
+            SweepSampling::Random { samples } => {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(self.sampling_seed);
+                (0..samples)
+                    .map(|_| {
+                        self.axes
+                            .iter()
+                            .map(|a| a.value_at(rng.gen::<f64>()))
+                            .collect()
+                    })
+                    .collect()
+            }
+            SweepSampling::LatinHypercube { samples } => {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(self.sampling_seed);
+                // One independent stratum permutation per axis
+                let columns: Vec<Vec<f64>> = self
+                    .axes
+                    .iter()
+                    .map(|axis| {
+                        let mut strata: Vec<usize> = (0..samples).collect();
+                        strata.shuffle(&mut rng);
+                        strata
+                            .into_iter()
+                            .map(|s| {
+                                let u = (s as f64 + rng.gen::<f64>()) / samples as f64;
+                                axis.value_at(u)
+                            })
+                            .collect()
+                    })
+                    .collect();
+                (0..samples)
+                    .map(|i| columns.iter().map(|col| col[i]).collect())
+                    .collect()
+            }
+        };
+
+        Ok(rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| SweepPoint {
+                index,
+                params: self
+                    .axes
+                    .iter()
+                    .zip(row)
+                    .map(|(axis, v)| (axis.key.clone(), v))
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+/// Shared identifier tagging every run of one sweep.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SweepId(pub String);
+
+impl SweepId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SweepId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A single concrete parameter assignment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepPoint {
+    /// Stable index within the sweep.
+    pub index: usize,
+    /// Swept key → value.
+    pub params: BTreeMap<String, f64>,
+}
+
+impl SweepPoint {
+    /// Build the full `StrategyParams` for this point on top of `base`.
+    pub fn apply(&self, base: &StrategyParams) -> StrategyParams {
+        let mut params = base.clone();
+        for (key, value) in &self.params {
+            params.params.insert(key.clone(), *value);
+        }
+        params
+    }
+}
+
+// =============================================================================
+// SWEEP RESULTS
+// =============================================================================
+
+/// Outcome of one sweep point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepRunOutcome {
+    /// Point that was run.
+    pub point: SweepPoint,
+    /// Persisted run id (None if the run failed or no store was given).
+    pub run_id: Option<RunId>,
+    /// Run fingerprint hash (None if the run failed).
+    pub fingerprint_hex: Option<String>,
+    pub final_pnl: f64,
+    pub sharpe_ratio: Option<f64>,
+    pub max_drawdown: f64,
+    pub total_fills: u64,
+    pub trusted: bool,
+    /// Error message if the run or its persistence failed.
+    pub error: Option<String>,
+}
+
+impl SweepRunOutcome {
+    fn from_results(point: SweepPoint, results: &BacktestResults) -> Self {
+        Self {
+            point,
+            run_id: None,
+            fingerprint_hex: results.run_fingerprint.as_ref().map(|fp| fp.hash_hex.clone()),
+            final_pnl: results.final_pnl,
+            sharpe_ratio: results.sharpe_ratio,
+            max_drawdown: results.max_drawdown,
+            total_fills: results.total_fills,
+            trusted: matches!(results.trust_level, TrustLevel::Trusted),
+            error: None,
+        }
+    }
+
+    fn failed(point: SweepPoint, error: String) -> Self {
+        Self {
+            point,
+            run_id: None,
+            fingerprint_hex: None,
+            final_pnl: 0.0,
+            sharpe_ratio: None,
+            max_drawdown: 0.0,
+            total_fills: 0,
+            trusted: false,
+            error: Some(error),
+        }
+    }
+
+    /// Whether the backtest for this point completed.
+    pub fn completed(&self) -> bool {
+        self.fingerprint_hex.is_some()
+    }
+}
+
+/// Report for a whole sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamSweepReport {
+    pub sweep_id: SweepId,
+    pub strategy_name: String,
+    pub sampling: SweepSampling,
+    /// Outcomes ordered by point index.
+    pub outcomes: Vec<SweepRunOutcome>,
+}
+
+impl ParamSweepReport {
+    /// Number of points whose backtest completed.
+    pub fn completed_count(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.completed()).count()
+    }
+
+    /// Number of points that errored.
+    pub fn failed_count(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.error.is_some()).count()
+    }
+
+    /// Completed point with the highest final PnL (ties broken by lowest index).
+    pub fn best_by_pnl(&self) -> Option<&SweepRunOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.completed())
+            .fold(None, |best: Option<&SweepRunOutcome>, o| match best {
+                Some(b) if b.final_pnl >= o.final_pnl => Some(b),
+                _ => Some(o),
+            })
+    }
+
+    /// Format a compact text table of the sweep.
+    pub fn format_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "PARAMETER SWEEP {} — {} — {}\n",
+            self.sweep_id,
+            self.strategy_name,
+            self.sampling.description()
+        ));
+        out.push_str(&format!(
+            "{} points, {} completed, {} failed\n",
+            self.outcomes.len(),
+            self.completed_count(),
+            self.failed_count()
+        ));
+        for o in &self.outcomes {
+            let params: Vec<String> = o
+                .point
+                .params
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            match &o.error {
+                Some(err) => out.push_str(&format!(
+                    "  #{:<4} {:40} ERROR: {}\n",
+                    o.point.index,
+                    params.join(" "),
+                    err
+                )),
+                None => out.push_str(&format!(
+                    "  #{:<4} {:40} pnl={:>10.2} dd={:>8.2} fills={:<6} {}\n",
+                    o.point.index,
+                    params.join(" "),
+                    o.final_pnl,
+                    o.max_drawdown,
+                    o.total_fills,
+                    if o.trusted { "TRUSTED" } else { "untrusted" }
+                )),
+            }
+        }
+        out
+    }
+}
+
+// =============================================================================
+// SWEEP RUNNER
+// =============================================================================
+
+/// Runs a parameter sweep on top of `BacktestOrchestrator`.
+pub struct ParamSweepRunner {
+    config: ParamSweepConfig,
+    base_config: BacktestConfig,
+}
+
+impl ParamSweepRunner {
+    /// Create a runner. `base_config` is cloned per point; only its
+    /// `strategy_params` are replaced.
+    pub fn new(config: ParamSweepConfig, base_config: BacktestConfig) -> Result<Self, String> {
+        config.validate()?;
+        // Fail fast on unknown strategy names instead of once per point
+        make_strategy(&config.strategy_name, &StrategyParams::default())?;
+        Ok(Self { config, base_config })
+    }
+
+    /// Sweep definition.
+    pub fn config(&self) -> &ParamSweepConfig {
+        &self.config
+    }
+
+    /// Run every sweep point against the same event set.
+    ///
+    /// When `store` is given, each completed run is persisted as a `RunArtifact`
+    /// and tagged with the sweep id. A run that already exists in the store
+    /// (identical fingerprint) is tagged without being re-persisted.
+    pub fn run(
+        &self,
+        events: &[TimestampedEvent],
+        store: Option<&ArtifactStore>,
+    ) -> Result<ParamSweepReport, String> {
+        self.run_as_group(events, store, self.config.sweep_id())
+    }
+
+    /// Same as [`run`](Self::run), but tags the runs under `sweep_id` instead of
+    /// the id derived from the sweep definition. Used when the same sweep is run
+    /// over several datasets (e.g. one walk-forward train slice per fold).
+    pub fn run_as_group(
+        &self,
+        events: &[TimestampedEvent],
+        store: Option<&ArtifactStore>,
+        sweep_id: SweepId,
+    ) -> Result<ParamSweepReport, String> {
+        let points = self.config.sample_points()?;
+
+        let pool = {
+            let mut builder = rayon::ThreadPoolBuilder::new();
+            if let Some(n) = self.config.max_workers {
+                builder = builder.num_threads(n.max(1));
+            }
+            builder
+                .build()
+                .map_err(|e| format!("Failed to build sweep worker pool: {}", e))?
+        };
+
+        tracing::info!(
+            sweep_id = %sweep_id,
+            strategy = %self.config.strategy_name,
+            points = points.len(),
+            workers = pool.current_num_threads(),
+            "Starting parameter sweep"
+        );
+
+        let mut outcomes: Vec<SweepRunOutcome> = pool.install(|| {
+            points
+                .into_par_iter()
+                .map(|point| self.run_point(point, events, store, &sweep_id))
+                .collect()
+        });
+        outcomes.sort_by_key(|o| o.point.index);
+
+        Ok(ParamSweepReport {
+            sweep_id,
+            strategy_name: self.config.strategy_name.clone(),
+            sampling: self.config.sampling,
+            outcomes,
+        })
+    }
+
+    /// Full `StrategyParams` for a point: base config params, then the sweep's
+    /// `base_params`, then the point's swept values.
+    pub fn point_params(&self, point: &SweepPoint) -> StrategyParams {
+        let base_params = self
+            .config
+            .base_params
+            .iter()
+            .fold(self.base_config.strategy_params.clone(), |p, (k, v)| {
+                p.with_param(k.clone(), *v)
+            });
+        point.apply(&base_params)
+    }
+
+    fn run_point(
+        &self,
+        point: SweepPoint,
+        events: &[TimestampedEvent],
+        store: Option<&ArtifactStore>,
+        sweep_id: &SweepId,
+    ) -> SweepRunOutcome {
+        let params = self.point_params(&point);
+
+        let mut config = self.base_config.clone();
+        config.strategy_params = params.clone();
+        config.sample_scope = SampleScope::InSample {
+            group_id: sweep_id.0.clone(),
+        };
+
+        let mut strategy = match make_strategy(&self.config.strategy_name, &params) {
+            Ok(s) => s,
+            Err(e) => return SweepRunOutcome::failed(point, e),
+        };
+
+        let mut feed = VecFeed::new("sweep", events.to_vec());
+        let mut orchestrator = BacktestOrchestrator::new(config.clone());
+        if let Err(e) = orchestrator.load_feed(&mut feed) {
+            return SweepRunOutcome::failed(point, format!("Failed to load feed: {}", e));
+        }
+        let results = match orchestrator.run(strategy.as_mut()) {
+            Ok(r) => r,
+            Err(e) => return SweepRunOutcome::failed(point, format!("Backtest error: {}", e)),
+        };
+
+        let mut outcome = SweepRunOutcome::from_results(point, &results);
+
+        if let Some(store) = store {
+            let run_id = match persist_run(store, results, &config) {
+                Ok(id) => id,
+                Err(e) => {
+                    outcome.error = Some(e);
+                    return outcome;
+                }
+            };
+            let member = RunGroupMember {
+                group_id: sweep_id.0.clone(),
+                kind: RunGroupKind::ParamSweep,
+                member_index: outcome.point.index as u32,
+                run_id: run_id.clone(),
+                role: SWEEP_POINT_ROLE.to_string(),
+                params: outcome.point.params.clone(),
+            };
+            match store.tag_run_group(&member) {
+                Ok(()) => outcome.run_id = Some(run_id),
+                Err(e) => outcome.error = Some(format!("Failed to tag sweep run: {}", e)),
+            }
+        }
+
+        outcome
+    }
+}
+
+/// Persist a completed run, treating an identical run already in the store
+/// as success. Returns the run id either way.
+pub(crate) fn persist_run(
+    store: &ArtifactStore,
+    results: BacktestResults,
+    config: &BacktestConfig,
+) -> Result<RunId, String> {
+    let artifact = RunArtifact::from_results(results, config);
+    match store.persist(&artifact) {
+        Ok(()) | Err(ArtifactStoreError::AlreadyExists(_)) => Ok(artifact.run_id().clone()),
+        Err(e) => Err(format!("Failed to persist artifact: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::clock::Nanos;
+    use crate::backtest_v2::events::{Event, Level};
+    use crate::backtest_v2::orchestrator::MakerFillModel;
+    use crate::backtest_v2::queue::StreamSource;
+
+    fn make_book_event(time: Nanos, mid: f64) -> TimestampedEvent {
+        TimestampedEvent::new(
+            time,
+            StreamSource::MarketData as u8,
+            Event::L2BookSnapshot {
+                token_id: "TEST".into(),
+                bids: vec![Level::new(mid - 0.02, 100.0)],
+                asks: vec![Level::new(mid + 0.02, 100.0)],
+                exchange_seq: 1,
+            },
+        )
+    }
+
+    fn test_base_config() -> BacktestConfig {
+        let mut config = BacktestConfig::test_config();
+        config.maker_fill_model = MakerFillModel::MakerDisabled;
+        config
+    }
+
+    #[test]
+    fn test_grid_expands_cartesian_product() {
+        let sweep = ParamSweepConfig::grid(
+            "random_taker",
+            vec![
+                ParamAxis::discrete("clip_size", vec![5.0, 10.0, 20.0]),
+                ParamAxis::continuous("trade_probability", 0.0, 0.1, 2),
+            ],
+        );
+        assert_eq!(sweep.point_count(), 6);
+
+        let points = sweep.sample_points().unwrap();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0].params["clip_size"], 5.0);
+        assert_eq!(points[0].params["trade_probability"], 0.0);
+        assert_eq!(points[5].params["clip_size"], 20.0);
+        assert!((points[5].params["trade_probability"] - 0.1).abs() < 1e-12);
+        assert!(points.iter().enumerate().all(|(i, p)| p.index == i));
+    }
+
+    #[test]
+    fn test_latin_hypercube_covers_every_stratum() {
+        let sweep = ParamSweepConfig {
+            sampling: SweepSampling::LatinHypercube { samples: 8 },
+            ..ParamSweepConfig::grid(
+                "random_taker",
+                vec![
+                    ParamAxis::continuous("a", 0.0, 1.0, 2),
+                    ParamAxis::continuous("b", 10.0, 20.0, 2),
+                ],
+            )
+        };
+        let points = sweep.sample_points().unwrap();
+        assert_eq!(points.len(), 8);
+
+        let mut strata_a: Vec<usize> = points
+            .iter()
+            .map(|p| (p.params["a"] * 8.0) as usize)
+            .collect();
+        let mut strata_b: Vec<usize> = points
+            .iter()
+            .map(|p| ((p.params["b"] - 10.0) / 10.0 * 8.0) as usize)
+            .collect();
+        strata_a.sort();
+        strata_b.sort();
+        assert_eq!(strata_a, (0..8).collect::<Vec<_>>());
+        assert_eq!(strata_b, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_sampling_is_deterministic() {
+        let sweep = ParamSweepConfig {
+            sampling: SweepSampling::Random { samples: 5 },
+            sampling_seed: 7,
+            ..ParamSweepConfig::grid(
+                "random_taker",
+                vec![ParamAxis::continuous("clip_size", 1.0, 50.0, 2)],
+            )
+        };
+        assert_eq!(sweep.sample_points().unwrap(), sweep.sample_points().unwrap());
+        assert_eq!(sweep.sweep_id(), sweep.clone().sweep_id());
+
+        let other = ParamSweepConfig { sampling_seed: 8, ..sweep.clone() };
+        assert_ne!(sweep.sweep_id(), other.sweep_id());
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_sweeps() {
+        assert!(ParamSweepConfig::grid("noop", vec![]).validate().is_err());
+        assert!(ParamSweepConfig::grid("noop", vec![ParamAxis::discrete("a", vec![])])
+            .validate()
+            .is_err());
+        assert!(ParamSweepConfig::grid(
+            "noop",
+            vec![
+                ParamAxis::discrete("a", vec![1.0]),
+                ParamAxis::discrete("a", vec![2.0]),
+            ]
+        )
+        .validate()
+        .is_err());
+        assert!(ParamSweepConfig::grid("noop", vec![ParamAxis::continuous("a", 2.0, 1.0, 3)])
+            .validate()
+            .is_err());
+        assert!(ParamSweepRunner::new(
+            ParamSweepConfig::grid("no_such_strategy", vec![ParamAxis::discrete("a", vec![1.0])]),
+            test_base_config(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sweep_persists_and_tags_every_point() {
+        let events = vec![
+            make_book_event(1_000_000_000, 0.50),
+            make_book_event(2_000_000_000, 0.51),
+            make_book_event(3_000_000_000, 0.49),
+        ];
+        let sweep = ParamSweepConfig {
+            max_workers: Some(2),
+            ..ParamSweepConfig::grid(
+                "noop",
+                vec![ParamAxis::discrete("dummy", vec![1.0, 2.0, 3.0])],
+            )
+        };
+        let runner = ParamSweepRunner::new(sweep, test_base_config()).unwrap();
+        let store = ArtifactStore::in_memory().unwrap();
+
+        let report = runner.run(&events, Some(&store)).unwrap();
+        assert_eq!(report.outcomes.len(), 3);
+        assert_eq!(report.failed_count(), 0, "{}", report.format_text());
+        assert_eq!(report.completed_count(), 3);
+
+        // Distinct params → distinct fingerprints → distinct run ids
+        let run_ids: std::collections::HashSet<_> =
+            report.outcomes.iter().filter_map(|o| o.run_id.clone()).collect();
+        assert_eq!(run_ids.len(), 3);
+
+        let members = store.list_run_group(report.sweep_id.as_str()).unwrap();
+        assert_eq!(members.len(), 3);
+        assert!(members.iter().all(|m| m.kind == RunGroupKind::ParamSweep));
+        assert_eq!(members[1].params["dummy"], 2.0);
+
+        // Re-running the same sweep re-tags the same runs
+        let again = runner.run(&events, Some(&store)).unwrap();
+        assert_eq!(again.sweep_id, report.sweep_id);
+        assert_eq!(again.failed_count(), 0);
+        assert_eq!(store.list_run_group(report.sweep_id.as_str()).unwrap().len(), 3);
+    }
+}
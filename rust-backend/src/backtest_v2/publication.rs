@@ -515,16 +515,21 @@ mod tests {
                 oracle_config_hash: None,
                 latency_model: "Fixed".to_string(),
                 order_latency_ns: Some(1_000_000),
+                latency_config_hash: 0,
                 oms_parity_mode: "Full".to_string(),
                 maker_fill_model: "ExplicitQueue".to_string(),
                 integrity_policy: "Strict".to_string(),
                 invariant_mode: "Hard".to_string(),
                 fee_rate_bps: Some(10),
                 strategy_params_hash: 12345,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "RecordedArrival".to_string(),
                 strict_accounting: true,
                 production_grade: true,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 67890,
             },
             dataset: DatasetFingerprint {
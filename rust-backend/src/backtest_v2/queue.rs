@@ -7,6 +7,7 @@ use crate::backtest_v2::clock::Nanos;
 use crate::backtest_v2::events::{Event, TimestampedEvent};
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use serde::{Deserialize, Serialize};
 
 /// Source stream identifiers for deterministic ordering.
 /// Lower value = higher priority when timestamps match.
@@ -43,6 +44,7 @@ impl From<StreamSource> for u8 {
 /// 4. Sequence number (insertion order within same source)
 ///
 /// This guarantees identical output for identical input across runs.
+#[derive(Serialize, Deserialize)]
 pub struct EventQueue {
     /// Min-heap of events (Reverse for min-heap behavior)
     heap: BinaryHeap<Reverse<TimestampedEvent>>,
@@ -26,7 +26,7 @@ pub struct QueuePosition {
 }
 
 /// A single price level queue.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct LevelQueue {
     /// Orders in FIFO order.
     orders: VecDeque<QueueEntry>,
@@ -34,7 +34,7 @@ struct LevelQueue {
     total_size: Size,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct QueueEntry {
     order_id: OrderId,
     size: Size,
@@ -65,6 +65,17 @@ impl LevelQueue {
         }
     }
 
+    /// Shrink an order in place, keeping its queue position.
+    fn resize(&mut self, order_id: OrderId, new_size: Size) -> bool {
+        if let Some(entry) = self.orders.iter_mut().find(|e| e.order_id == order_id) {
+            self.total_size += new_size - entry.size;
+            entry.size = new_size;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Reduce front order size (for fills).
     fn reduce_front(&mut self, fill_size: Size) -> Option<(OrderId, Size, bool, bool)> {
         if let Some(front) = self.orders.front_mut() {
@@ -127,7 +138,7 @@ impl LevelQueue {
 }
 
 /// Queue position tracker for one side of the book.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct SideQueues {
     levels: BTreeMap<PriceTicks, LevelQueue>,
 }
@@ -157,7 +168,7 @@ impl SideQueues {
 }
 
 /// Order state for in-flight tracking.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InFlightOrder {
     pub order_id: OrderId,
     pub side: Side,
@@ -195,6 +206,7 @@ pub enum RaceResult {
 }
 
 /// Queue position model with race condition handling.
+#[derive(Serialize, Deserialize)]
 pub struct QueuePositionModel {
     /// Bid side queues (price -> queue).
     bids: SideQueues,
@@ -210,7 +222,7 @@ pub struct QueuePositionModel {
     pub stats: QueueStats,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct OrderLocation {
     side: Side,
     price_ticks: PriceTicks,
@@ -232,6 +244,12 @@ pub struct QueueStats {
     pub queue_volume_removed: f64,
     /// Total volume added to queue via deltas.
     pub queue_volume_added: f64,
+    /// Replaces that shrank the order in place and kept queue priority.
+    #[serde(default)]
+    pub replaces_kept_priority: u64,
+    /// Replaces that moved the order to the back of a (possibly new) level.
+    #[serde(default)]
+    pub replaces_lost_priority: u64,
 }
 
 impl QueueStats {
@@ -370,6 +388,52 @@ impl QueuePositionModel {
         None
     }
 
+    /// Apply an acknowledged replace to one of our orders.
+    ///
+    /// A size-down at the same price keeps the order's place in the queue;
+    /// a price change or size increase sends it to the back of the target
+    /// level. Returns whether priority was kept, or `None` if the order is
+    /// not tracked.
+    pub fn replace_order(
+        &mut self,
+        order_id: OrderId,
+        new_price_ticks: PriceTicks,
+        new_size: Size,
+        now: Nanos,
+    ) -> Option<bool> {
+        let location = self.our_orders.get(&order_id)?.clone();
+        let queues = match location.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+
+        let level = queues.get_mut(location.price_ticks)?;
+        let current_size = level.get_position(order_id)?.our_size;
+
+        if new_price_ticks == location.price_ticks && new_size <= current_size {
+            level.resize(order_id, new_size);
+            self.stats.replaces_kept_priority += 1;
+            return Some(true);
+        }
+
+        level.remove(order_id);
+        if level.is_empty() {
+            queues.remove_level(location.price_ticks);
+        }
+        queues
+            .get_or_create(new_price_ticks)
+            .push_back(order_id, new_size, true, now);
+        self.our_orders.insert(
+            order_id,
+            OrderLocation {
+                side: location.side,
+                price_ticks: new_price_ticks,
+            },
+        );
+        self.stats.replaces_lost_priority += 1;
+        Some(false)
+    }
+
     fn remove_from_any_side(&mut self, order_id: OrderId) -> Option<Size> {
         // Try bids
         for (&price, level) in self.bids.levels.iter_mut() {
@@ -788,4 +852,36 @@ mod tests {
         let prob = model.estimate_fill_probability(2, 50.0).unwrap();
         assert!((prob - 0.25).abs() < 0.001);
     }
+
+    #[test]
+    fn test_replace_priority_semantics() {
+        let mut model = QueuePositionModel::new();
+
+        model.add_order(1, Side::Buy, 45, 100.0, false, 1000); // External
+        model.add_order(2, Side::Buy, 45, 50.0, true, 2000); // Ours
+        model.add_order(3, Side::Buy, 45, 75.0, false, 3000); // External
+
+        // Size-down keeps our place behind order 1
+        assert_eq!(model.replace_order(2, 45, 30.0, 4000), Some(true));
+        let pos = model.get_position(2).unwrap();
+        assert_eq!(pos.position, 1);
+        assert_eq!(pos.our_size, 30.0);
+        assert_eq!(pos.joined_at, 2000);
+
+        // Size-up goes to the back of the level
+        assert_eq!(model.replace_order(2, 45, 40.0, 5000), Some(false));
+        let pos = model.get_position(2).unwrap();
+        assert_eq!(pos.position, 2);
+        assert_eq!(pos.size_ahead, 175.0);
+
+        // Price change joins the new level
+        assert_eq!(model.replace_order(2, 46, 40.0, 6000), Some(false));
+        let pos = model.get_position(2).unwrap();
+        assert_eq!(pos.position, 0);
+        assert_eq!(pos.joined_at, 6000);
+
+        assert_eq!(model.stats.replaces_kept_priority, 1);
+        assert_eq!(model.stats.replaces_lost_priority, 2);
+        assert_eq!(model.replace_order(99, 45, 10.0, 7000), None);
+    }
 }
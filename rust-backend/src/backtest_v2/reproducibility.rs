@@ -394,7 +394,7 @@ impl Default for CanonicalEncoder {
 /// - Detecting any change in the input stream
 ///
 /// Uses two 64-bit accumulators with fixed mixing constants.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct RollingHash {
     h0: u64,
     h1: u64,
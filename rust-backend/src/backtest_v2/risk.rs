@@ -8,9 +8,10 @@ use crate::backtest_v2::events::{OrderId, Price, Side, Size};
 use crate::backtest_v2::oms::OrderManagementSystem;
 use crate::backtest_v2::portfolio::{MarketId, Outcome, Portfolio, TokenId};
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
 /// Risk limits configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskLimits {
     /// Maximum gross exposure as multiple of equity.
     pub max_gross_exposure_mult: f64,
@@ -98,6 +99,28 @@ impl RiskLimits {
             cooldown_ns: 30_000_000_000,
         }
     }
+
+    /// Hash of every limit, for run fingerprinting.
+    pub fn fingerprint_hash(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.max_gross_exposure_mult.to_bits().hash(&mut hasher);
+        self.max_market_position_usd.to_bits().hash(&mut hasher);
+        self.max_market_position_pct.to_bits().hash(&mut hasher);
+        self.max_order_size.to_bits().hash(&mut hasher);
+        self.max_order_notional.to_bits().hash(&mut hasher);
+        self.max_outstanding_orders.hash(&mut hasher);
+        self.max_outstanding_orders_per_market.hash(&mut hasher);
+        self.max_drawdown_pct.to_bits().hash(&mut hasher);
+        self.min_cash_balance.to_bits().hash(&mut hasher);
+        self.min_cash_pct.to_bits().hash(&mut hasher);
+        self.max_daily_loss.to_bits().hash(&mut hasher);
+        self.max_trades_per_day.hash(&mut hasher);
+        self.cooldown_ns.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 /// Fractional Kelly sizing parameters.
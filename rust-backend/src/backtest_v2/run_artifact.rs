@@ -1526,16 +1526,21 @@ mod tests {
                 oracle_config_hash: None,
                 latency_model: "Fixed".to_string(),
                 order_latency_ns: None,
+                latency_config_hash: 0,
                 oms_parity_mode: "Full".to_string(),
                 maker_fill_model: "Disabled".to_string(),
                 integrity_policy: "Strict".to_string(),
                 invariant_mode: "Hard".to_string(),
                 fee_rate_bps: None,
                 strategy_params_hash: 0,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "RecordedArrival".to_string(),
                 strict_accounting: true,
                 production_grade: true,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 0x12345678,
             },
             dataset: crate::backtest_v2::fingerprint::DatasetFingerprint {
@@ -1598,16 +1603,21 @@ mod tests {
                     oracle_config_hash: None,
                     latency_model: "Fixed".to_string(),
                     order_latency_ns: None,
+                    latency_config_hash: 0,
                     oms_parity_mode: "Full".to_string(),
                     maker_fill_model: "Disabled".to_string(),
                     integrity_policy: "Strict".to_string(),
                     invariant_mode: "Hard".to_string(),
                     fee_rate_bps: None,
                     strategy_params_hash: 0,
+                    risk_limits_hash: None,
+                    market_impact_hash: None,
+                    monte_carlo_hash: 0,
                     arrival_policy: "RecordedArrival".to_string(),
                     strict_accounting: true,
                     production_grade: true,
                     allow_non_production: false,
+                    sample_scope: "Undeclared".to_string(),
                     hash: 0,
                 },
                 dataset: crate::backtest_v2::fingerprint::DatasetFingerprint {
@@ -1732,16 +1742,21 @@ mod tests {
                     oracle_config_hash: None,
                     latency_model: "Fixed".to_string(),
                     order_latency_ns: None,
+                    latency_config_hash: 0,
                     oms_parity_mode: "Full".to_string(),
                     maker_fill_model: "Disabled".to_string(),
                     integrity_policy: "Strict".to_string(),
                     invariant_mode: "Hard".to_string(),
                     fee_rate_bps: None,
                     strategy_params_hash: 0,
+                    risk_limits_hash: None,
+                    market_impact_hash: None,
+                    monte_carlo_hash: 0,
                     arrival_policy: "RecordedArrival".to_string(),
                     strict_accounting: true,
                     production_grade: true,
                     allow_non_production: false,
+                    sample_scope: "Undeclared".to_string(),
                     hash: 0,
                 },
                 dataset: crate::backtest_v2::fingerprint::DatasetFingerprint {
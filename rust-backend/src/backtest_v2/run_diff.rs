@@ -0,0 +1,886 @@
+//! Run Diffing
+//!
+//! Explains *why* two run fingerprints differ instead of only reporting that
+//! the hashes do not match.
+//!
+//! # Decision Trace
+//!
+//! During a run the orchestrator records a bounded `DecisionTrace`: one
+//! canonical record per order submit, cancel request, ack, reject, fill, cancel
+//! ack and settlement, tagged with the input event index and simulated time.
+//! Records are encoded with the `CanonicalEncoder` (fixed-point prices and
+//! sizes, little-endian) and chained into a `RollingHash`, so two traces are
+//! equal if and only if their canonical byte streams are equal. Only the first
+//! `decision_trace_limit` records are stored; the hash covers all of them.
+//!
+//! # Diff
+//!
+//! `RunDiff::compare` loads nothing itself - it takes two `RunArtifact`s and
+//! reports:
+//! 1. Which fingerprint components (strategy, code, config, dataset, seed,
+//!    behavior, registry) changed, with the changed fields.
+//! 2. The first divergent trace record, with a side-by-side window of the
+//!    surrounding records from both runs.
+
+use crate::backtest_v2::artifact_store::ArtifactStore;
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
+use crate::backtest_v2::events::{OrderId, Side};
+use crate::backtest_v2::fingerprint::RunFingerprint;
+use crate::backtest_v2::reproducibility::{
+    price_to_ticks, size_to_shares, CanonicalEncoder, RollingHash, PRICE_SCALE, SIZE_SCALE,
+};
+use crate::backtest_v2::run_artifact::{RunArtifact, RunId};
+use serde::{Deserialize, Serialize};
+
+/// Default number of trace records stored per run.
+pub const DEFAULT_DECISION_TRACE_LIMIT: usize = 10_000;
+
+/// Maximum changed fields reported per fingerprint component.
+const MAX_FIELD_CHANGES: usize = 50;
+
+// =============================================================================
+// DECISION TRACE
+// =============================================================================
+
+/// Kind of canonical trace record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceRecordKind {
+    OrderSubmit,
+    /// Order refused synchronously by the order sender (risk, OMS validation).
+    OrderSendRejected,
+    CancelRequest,
+    OrderAck,
+    OrderReject,
+    Fill,
+    CancelAck,
+    Settlement,
+    ReplaceRequest,
+    ReplaceReject,
+    ReplaceAck,
+}
+
+impl TraceRecordKind {
+    fn code(self) -> u8 {
+        match self {
+            Self::OrderSubmit => 0x01,
+            Self::OrderSendRejected => 0x02,
+            Self::CancelRequest => 0x03,
+            Self::OrderAck => 0x04,
+            Self::OrderReject => 0x05,
+            Self::Fill => 0x10,
+            Self::CancelAck => 0x11,
+            Self::Settlement => 0x20,
+            Self::ReplaceRequest => 0x06,
+            Self::ReplaceReject => 0x07,
+            Self::ReplaceAck => 0x12,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::OrderSubmit => "SUBMIT",
+            Self::OrderSendRejected => "SEND_REJ",
+            Self::CancelRequest => "CANCEL",
+            Self::OrderAck => "ACK",
+            Self::OrderReject => "REJECT",
+            Self::Fill => "FILL",
+            Self::CancelAck => "CXL_ACK",
+            Self::Settlement => "SETTLE",
+            Self::ReplaceRequest => "REPLACE",
+            Self::ReplaceReject => "RPL_REJ",
+            Self::ReplaceAck => "RPL_ACK",
+        }
+    }
+}
+
+/// One canonical record of observable run behavior.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceRecord {
+    /// Position in the trace (0-based).
+    pub seq: u64,
+    /// Number of input events processed when the record was emitted.
+    pub event_index: u64,
+    /// Simulated time (ns).
+    pub sim_time_ns: Nanos,
+    pub kind: TraceRecordKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<OrderId>,
+    /// Token id for order records, market id for settlements.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<Side>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_ticks: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_shares: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_maker: Option<bool>,
+    /// Reject reason or settlement outcome.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl TraceRecord {
+    fn new(kind: TraceRecordKind) -> Self {
+        Self {
+            seq: 0,
+            event_index: 0,
+            sim_time_ns: 0,
+            kind,
+            order_id: None,
+            market: None,
+            side: None,
+            price_ticks: None,
+            size_shares: None,
+            is_maker: None,
+            detail: None,
+        }
+    }
+
+    /// Order submission accepted by the order sender.
+    pub fn order_submit(order_id: OrderId, token_id: &str, side: Side, price: f64, size: f64) -> Self {
+        Self {
+            order_id: Some(order_id),
+            market: Some(token_id.to_string()),
+            side: Some(side),
+            price_ticks: Some(price_to_ticks(price)),
+            size_shares: Some(size_to_shares(size)),
+            ..Self::new(TraceRecordKind::OrderSubmit)
+        }
+    }
+
+    /// Order submission refused synchronously.
+    pub fn order_send_rejected(token_id: &str, side: Side, price: f64, size: f64, reason: &str) -> Self {
+        Self {
+            market: Some(token_id.to_string()),
+            side: Some(side),
+            price_ticks: Some(price_to_ticks(price)),
+            size_shares: Some(size_to_shares(size)),
+            detail: Some(reason.to_string()),
+            ..Self::new(TraceRecordKind::OrderSendRejected)
+        }
+    }
+
+    pub fn cancel_request(order_id: OrderId) -> Self {
+        Self {
+            order_id: Some(order_id),
+            ..Self::new(TraceRecordKind::CancelRequest)
+        }
+    }
+
+    pub fn order_ack(order_id: OrderId) -> Self {
+        Self {
+            order_id: Some(order_id),
+            ..Self::new(TraceRecordKind::OrderAck)
+        }
+    }
+
+    pub fn order_reject(order_id: OrderId, reason: &str) -> Self {
+        Self {
+            order_id: Some(order_id),
+            detail: Some(reason.to_string()),
+            ..Self::new(TraceRecordKind::OrderReject)
+        }
+    }
+
+    pub fn fill(order_id: OrderId, price: f64, size: f64, is_maker: bool) -> Self {
+        Self {
+            order_id: Some(order_id),
+            price_ticks: Some(price_to_ticks(price)),
+            size_shares: Some(size_to_shares(size)),
+            is_maker: Some(is_maker),
+            ..Self::new(TraceRecordKind::Fill)
+        }
+    }
+
+    pub fn cancel_ack(order_id: OrderId, cancelled_qty: f64) -> Self {
+        Self {
+            order_id: Some(order_id),
+            size_shares: Some(size_to_shares(cancelled_qty)),
+            ..Self::new(TraceRecordKind::CancelAck)
+        }
+    }
+
+    pub fn replace_request(order_id: OrderId, price: f64, size: f64) -> Self {
+        Self {
+            order_id: Some(order_id),
+            price_ticks: Some(price_to_ticks(price)),
+            size_shares: Some(size_to_shares(size)),
+            ..Self::new(TraceRecordKind::ReplaceRequest)
+        }
+    }
+
+    pub fn replace_ack(order_id: OrderId, price: f64, leaves_qty: f64) -> Self {
+        Self {
+            order_id: Some(order_id),
+            price_ticks: Some(price_to_ticks(price)),
+            size_shares: Some(size_to_shares(leaves_qty)),
+            ..Self::new(TraceRecordKind::ReplaceAck)
+        }
+    }
+
+    pub fn replace_reject(order_id: OrderId, reason: &str) -> Self {
+        Self {
+            order_id: Some(order_id),
+            detail: Some(reason.to_string()),
+            ..Self::new(TraceRecordKind::ReplaceReject)
+        }
+    }
+
+    pub fn settlement(market_id: &str, outcome: &str) -> Self {
+        Self {
+            market: Some(market_id.to_string()),
+            detail: Some(outcome.to_string()),
+            ..Self::new(TraceRecordKind::Settlement)
+        }
+    }
+
+    /// Encode to the canonical binary format.
+    pub fn encode(&self, encoder: &mut CanonicalEncoder) {
+        fn write_str(encoder: &mut CanonicalEncoder, s: Option<&str>) {
+            let bytes = s.unwrap_or("").as_bytes();
+            encoder.write_u32(bytes.len() as u32);
+            for b in bytes {
+                encoder.write_u8(*b);
+            }
+        }
+
+        encoder.write_u8(self.kind.code());
+        encoder.write_u64(self.event_index);
+        encoder.write_i64(self.sim_time_ns);
+        encoder.write_u64(self.order_id.unwrap_or(0));
+        write_str(encoder, self.market.as_deref());
+        encoder.write_u8(match self.side {
+            None => 0,
+            Some(Side::Buy) => 1,
+            Some(Side::Sell) => 2,
+        });
+        encoder.write_i64(self.price_ticks.unwrap_or(0));
+        encoder.write_i64(self.size_shares.unwrap_or(0));
+        encoder.write_u8(match self.is_maker {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+        write_str(encoder, self.detail.as_deref());
+    }
+
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::with_capacity(96);
+        self.encode(&mut encoder);
+        encoder.finish()
+    }
+
+    /// One-line summary for side-by-side dumps.
+    pub fn format_compact(&self) -> String {
+        let mut out = format!(
+            "ev={} t={:.6}s {}",
+            self.event_index,
+            self.sim_time_ns as f64 / NANOS_PER_SEC as f64,
+            self.kind.label()
+        );
+        if let Some(id) = self.order_id {
+            out.push_str(&format!(" #{}", id));
+        }
+        if let Some(ref market) = self.market {
+            out.push_str(&format!(" {}", market));
+        }
+        if let Some(side) = self.side {
+            out.push_str(match side {
+                Side::Buy => " B",
+                Side::Sell => " S",
+            });
+        }
+        match (self.price_ticks, self.size_shares) {
+            (Some(p), Some(s)) => out.push_str(&format!(
+                " {}@{:.4}",
+                s as f64 / SIZE_SCALE as f64,
+                p as f64 / PRICE_SCALE as f64
+            )),
+            (None, Some(s)) => out.push_str(&format!(" qty={}", s as f64 / SIZE_SCALE as f64)),
+            _ => {}
+        }
+        if let Some(maker) = self.is_maker {
+            out.push_str(if maker { " maker" } else { " taker" });
+        }
+        if let Some(ref detail) = self.detail {
+            out.push_str(&format!(" ({})", detail));
+        }
+        out
+    }
+}
+
+/// Bounded canonical trace of a run's observable behavior.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecisionTrace {
+    /// Stored records (the first `limit` of `total_records`).
+    pub records: Vec<TraceRecord>,
+    /// Records emitted during the run, stored or not.
+    pub total_records: u64,
+    /// Storage limit in effect for this run.
+    pub limit: usize,
+    /// Rolling hash over every record's canonical encoding.
+    pub hash_hex: String,
+    #[serde(default)]
+    hash: RollingHash,
+}
+
+impl DecisionTrace {
+    pub fn new(limit: usize) -> Self {
+        let hash = RollingHash::new();
+        Self {
+            records: Vec::new(),
+            total_records: 0,
+            limit,
+            hash_hex: hash.to_hex(),
+            hash,
+        }
+    }
+
+    /// Append a record, stamping its sequence, event index and time.
+    pub fn push(&mut self, mut record: TraceRecord, event_index: u64, sim_time_ns: Nanos) {
+        record.seq = self.total_records;
+        record.event_index = event_index;
+        record.sim_time_ns = sim_time_ns;
+        self.hash.update(&record.canonical_bytes());
+        self.hash_hex = self.hash.to_hex();
+        self.total_records += 1;
+        if self.records.len() < self.limit {
+            self.records.push(record);
+        }
+    }
+
+    /// Rolling hash over every record pushed so far.
+    pub fn hash_u64(&self) -> u64 {
+        self.hash.finish_u64()
+    }
+
+    /// True if records were emitted beyond the storage limit.
+    pub fn is_truncated(&self) -> bool {
+        self.total_records > self.records.len() as u64
+    }
+}
+
+// =============================================================================
+// RUN DIFF
+// =============================================================================
+
+/// A single changed field inside a fingerprint component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Dotted path within the component (e.g. `streams[0].record_count`).
+    pub path: String,
+    pub a: serde_json::Value,
+    pub b: serde_json::Value,
+}
+
+/// Comparison of one fingerprint component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentDiff {
+    pub component: String,
+    pub hash_a: String,
+    pub hash_b: String,
+    pub changed: bool,
+    pub changes: Vec<FieldChange>,
+}
+
+/// One row of the side-by-side dump around a divergence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceContextRow {
+    pub index: usize,
+    pub a: Option<TraceRecord>,
+    pub b: Option<TraceRecord>,
+    pub differs: bool,
+}
+
+/// First point at which two decision traces disagree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDivergence {
+    /// Index of the first differing record.
+    pub index: usize,
+    /// Earliest input event index of the two divergent records.
+    pub event_index: Option<u64>,
+    /// Earliest simulated time of the two divergent records.
+    pub sim_time_ns: Option<Nanos>,
+    /// Record from run A (None if A's trace ended first).
+    pub record_a: Option<TraceRecord>,
+    /// Record from run B (None if B's trace ended first).
+    pub record_b: Option<TraceRecord>,
+    /// Records surrounding the divergence from both runs.
+    pub context: Vec<TraceContextRow>,
+}
+
+/// Outcome of comparing the two decision traces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TraceComparison {
+    /// Traces are identical.
+    Identical { records: u64 },
+    /// Traces diverge at a stored record.
+    Diverged(Box<TraceDivergence>),
+    /// Stored prefixes match but the full-trace hashes differ.
+    BeyondRecordedWindow { recorded: usize },
+    /// At least one run has no trace (e.g. persisted before traces existed).
+    Unavailable { reason: String },
+}
+
+/// Explanation of the difference between two runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunDiff {
+    pub run_a: String,
+    pub run_b: String,
+    pub fingerprint_a: String,
+    pub fingerprint_b: String,
+    /// True when the run fingerprints are identical.
+    pub identical: bool,
+    pub components: Vec<ComponentDiff>,
+    pub trace: TraceComparison,
+}
+
+impl RunDiff {
+    /// Compare two run artifacts. `context` is the number of trace records
+    /// shown on each side of the first divergence.
+    pub fn compare(a: &RunArtifact, b: &RunArtifact, context: usize) -> Self {
+        let fa = &a.manifest.fingerprint;
+        let fb = &b.manifest.fingerprint;
+        Self {
+            run_a: a.run_id().to_string(),
+            run_b: b.run_id().to_string(),
+            fingerprint_a: fa.hash_hex.clone(),
+            fingerprint_b: fb.hash_hex.clone(),
+            identical: fa.hash_hex == fb.hash_hex,
+            components: diff_components(fa, fb),
+            trace: compare_traces(
+                a.results.decision_trace.as_ref(),
+                b.results.decision_trace.as_ref(),
+                context,
+            ),
+        }
+    }
+
+    /// Names of the fingerprint components that changed.
+    pub fn changed_components(&self) -> Vec<&str> {
+        self.components
+            .iter()
+            .filter(|c| c.changed)
+            .map(|c| c.component.as_str())
+            .collect()
+    }
+
+    /// First divergent trace record, if located.
+    pub fn divergence(&self) -> Option<&TraceDivergence> {
+        match &self.trace {
+            TraceComparison::Diverged(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Human-readable report with a side-by-side dump.
+    pub fn format_report(&self) -> String {
+        let mut out = String::new();
+        out.push_str("═══════════════════════════════════════════════════════════════════\n");
+        out.push_str("  RUN DIFF\n");
+        out.push_str("═══════════════════════════════════════════════════════════════════\n");
+        out.push_str(&format!("  A: {} ({})\n", self.run_a, self.fingerprint_a));
+        out.push_str(&format!("  B: {} ({})\n", self.run_b, self.fingerprint_b));
+        if self.identical {
+            out.push_str("\n  Fingerprints are identical.\n");
+            return out;
+        }
+
+        out.push_str("\n  COMPONENTS\n");
+        for c in &self.components {
+            let marker = if c.changed { "✗" } else { "✓" };
+            out.push_str(&format!(
+                "  {} {:<10} {} → {}\n",
+                marker, c.component, c.hash_a, c.hash_b
+            ));
+            for change in &c.changes {
+                out.push_str(&format!("      {}: {} → {}\n", change.path, change.a, change.b));
+            }
+        }
+
+        out.push_str("\n  DECISION TRACE\n");
+        match &self.trace {
+            TraceComparison::Identical { records } => {
+                out.push_str(&format!("  Identical ({} records)\n", records));
+            }
+            TraceComparison::BeyondRecordedWindow { recorded } => {
+                out.push_str(&format!(
+                    "  First {} records match; divergence is beyond the recorded window\n",
+                    recorded
+                ));
+            }
+            TraceComparison::Unavailable { reason } => {
+                out.push_str(&format!("  Unavailable: {}\n", reason));
+            }
+            TraceComparison::Diverged(d) => {
+                out.push_str(&format!(
+                    "  First divergence at record {} (event {}, t={})\n\n",
+                    d.index,
+                    d.event_index.map(|e| e.to_string()).unwrap_or_else(|| "-".into()),
+                    d.sim_time_ns.map(|t| t.to_string()).unwrap_or_else(|| "-".into()),
+                ));
+                for row in &d.context {
+                    let fmt = |r: &Option<TraceRecord>| {
+                        r.as_ref().map(|r| r.format_compact()).unwrap_or_else(|| "<end>".into())
+                    };
+                    out.push_str(&format!(
+                        "  {} {:>6} | {:<48} | {}\n",
+                        if row.differs { ">>" } else { "  " },
+                        row.index,
+                        fmt(&row.a),
+                        fmt(&row.b)
+                    ));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Load two runs from the artifact store and diff them.
+pub fn diff_runs(
+    store: &ArtifactStore,
+    run_a: &RunId,
+    run_b: &RunId,
+    context: usize,
+) -> Result<RunDiff, String> {
+    let load = |id: &RunId| -> Result<RunArtifact, String> {
+        store
+            .get(id)
+            .map_err(|e| format!("Failed to load {}: {}", id, e))?
+            .ok_or_else(|| format!("Run not found: {}", id))
+    };
+    Ok(RunDiff::compare(&load(run_a)?, &load(run_b)?, context))
+}
+
+fn diff_components(a: &RunFingerprint, b: &RunFingerprint) -> Vec<ComponentDiff> {
+    let components = [
+        ("strategy", serde_json::to_value(&a.strategy), serde_json::to_value(&b.strategy)),
+        ("code", serde_json::to_value(&a.code), serde_json::to_value(&b.code)),
+        ("config", serde_json::to_value(&a.config), serde_json::to_value(&b.config)),
+        ("dataset", serde_json::to_value(&a.dataset), serde_json::to_value(&b.dataset)),
+        ("seed", serde_json::to_value(&a.seed), serde_json::to_value(&b.seed)),
+        ("behavior", serde_json::to_value(&a.behavior), serde_json::to_value(&b.behavior)),
+        ("registry", serde_json::to_value(&a.registry), serde_json::to_value(&b.registry)),
+    ];
+
+    components
+        .into_iter()
+        .map(|(name, va, vb)| {
+            let va = va.unwrap_or(serde_json::Value::Null);
+            let vb = vb.unwrap_or(serde_json::Value::Null);
+            let mut changes = Vec::new();
+            diff_values("", &va, &vb, &mut changes);
+            // The component hash is reported separately
+            changes.retain(|c| c.path != "hash");
+            changes.truncate(MAX_FIELD_CHANGES);
+            ComponentDiff {
+                component: name.to_string(),
+                hash_a: component_hash(&va),
+                hash_b: component_hash(&vb),
+                changed: va != vb,
+                changes,
+            }
+        })
+        .collect()
+}
+
+fn component_hash(value: &serde_json::Value) -> String {
+    match value.get("hash").and_then(|h| h.as_u64()) {
+        Some(h) => format!("{:016x}", h),
+        None if value.is_null() => "-".to_string(),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Collect leaf-level differences between two JSON values.
+fn diff_values(path: &str, a: &serde_json::Value, b: &serde_json::Value, out: &mut Vec<FieldChange>) {
+    use serde_json::Value;
+
+    if a == b || out.len() > MAX_FIELD_CHANGES {
+        return;
+    }
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => {
+            let mut keys: Vec<&String> = ma.keys().chain(mb.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let null = Value::Null;
+                diff_values(
+                    &join(key),
+                    ma.get(key).unwrap_or(&null),
+                    mb.get(key).unwrap_or(&null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(va), Value::Array(vb)) => {
+            let null = Value::Null;
+            for i in 0..va.len().max(vb.len()) {
+                diff_values(
+                    &format!("{}[{}]", path, i),
+                    va.get(i).unwrap_or(&null),
+                    vb.get(i).unwrap_or(&null),
+                    out,
+                );
+            }
+        }
+        _ => out.push(FieldChange {
+            path: path.to_string(),
+            a: a.clone(),
+            b: b.clone(),
+        }),
+    }
+}
+
+fn compare_traces(
+    a: Option<&DecisionTrace>,
+    b: Option<&DecisionTrace>,
+    context: usize,
+) -> TraceComparison {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (None, None) => {
+            return TraceComparison::Unavailable {
+                reason: "neither run recorded a decision trace".into(),
+            }
+        }
+        (None, _) => {
+            return TraceComparison::Unavailable {
+                reason: "run A has no decision trace".into(),
+            }
+        }
+        (_, None) => {
+            return TraceComparison::Unavailable {
+                reason: "run B has no decision trace".into(),
+            }
+        }
+    };
+
+    if a.hash_hex == b.hash_hex && a.total_records == b.total_records {
+        return TraceComparison::Identical { records: a.total_records };
+    }
+
+    let stored = a.records.len().min(b.records.len());
+    let first_diff = (0..stored)
+        .find(|&i| a.records[i].canonical_bytes() != b.records[i].canonical_bytes())
+        .or_else(|| {
+            // Stored prefixes agree; one trace ending early is still a divergence
+            // unless that trace was cut off by its storage limit.
+            let a_ended = a.records.len() == stored && !a.is_truncated();
+            let b_ended = b.records.len() == stored && !b.is_truncated();
+            (a_ended || b_ended).then_some(stored)
+        });
+
+    let Some(index) = first_diff else {
+        return TraceComparison::BeyondRecordedWindow { recorded: stored };
+    };
+
+    let record_a = a.records.get(index).cloned();
+    let record_b = b.records.get(index).cloned();
+    let event_index = [&record_a, &record_b].iter().filter_map(|r| r.as_ref()).map(|r| r.event_index).min();
+    let sim_time_ns = [&record_a, &record_b].iter().filter_map(|r| r.as_ref()).map(|r| r.sim_time_ns).min();
+
+    let start = index.saturating_sub(context);
+    let end = (index + context + 1).min(a.records.len().max(b.records.len()));
+    let rows = (start..end)
+        .map(|i| {
+            let ra = a.records.get(i).cloned();
+            let rb = b.records.get(i).cloned();
+            let differs = match (&ra, &rb) {
+                (Some(x), Some(y)) => x.canonical_bytes() != y.canonical_bytes(),
+                _ => true,
+            };
+            TraceContextRow { index: i, a: ra, b: rb, differs }
+        })
+        .collect();
+
+    TraceComparison::Diverged(Box::new(TraceDivergence {
+        index,
+        event_index,
+        sim_time_ns,
+        record_a,
+        record_b,
+        context: rows,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::{Event, Level, TimestampedEvent};
+    use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator};
+    use crate::backtest_v2::strategy::{
+        BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, Strategy,
+        StrategyContext, StrategyOrder, TimerEvent, TradePrint,
+    };
+    use crate::backtest_v2::feed::VecFeed;
+    use crate::backtest_v2::queue::StreamSource;
+
+    fn trace_of(records: Vec<(TraceRecord, u64, Nanos)>) -> DecisionTrace {
+        let mut trace = DecisionTrace::new(DEFAULT_DECISION_TRACE_LIMIT);
+        for (r, ev, t) in records {
+            trace.push(r, ev, t);
+        }
+        trace
+    }
+
+    #[test]
+    fn test_trace_hash_is_canonical() {
+        let a = trace_of(vec![
+            (TraceRecord::order_submit(1, "tok", Side::Buy, 0.45, 10.0), 3, 1_000),
+            (TraceRecord::fill(1, 0.45, 10.0, true), 5, 2_000),
+        ]);
+        let b = trace_of(vec![
+            (TraceRecord::order_submit(1, "tok", Side::Buy, 0.45, 10.0), 3, 1_000),
+            (TraceRecord::fill(1, 0.45, 10.0, true), 5, 2_000),
+        ]);
+        let c = trace_of(vec![
+            (TraceRecord::order_submit(1, "tok", Side::Buy, 0.45, 10.0), 3, 1_000),
+            (TraceRecord::fill(1, 0.45, 10.0, false), 5, 2_000),
+        ]);
+        assert_eq!(a.hash_hex, b.hash_hex);
+        assert_ne!(a.hash_hex, c.hash_hex);
+
+        // Storage is bounded, the hash is not
+        let mut bounded = DecisionTrace::new(1);
+        bounded.push(TraceRecord::order_ack(1), 0, 0);
+        bounded.push(TraceRecord::order_ack(2), 1, 0);
+        assert_eq!(bounded.records.len(), 1);
+        assert_eq!(bounded.total_records, 2);
+        assert!(bounded.is_truncated());
+    }
+
+    #[test]
+    fn test_compare_traces_locates_first_divergence() {
+        let common: Vec<_> = (0..6)
+            .map(|i| (TraceRecord::order_ack(i), i, i as Nanos * 1_000))
+            .collect();
+        let mut ra = common.clone();
+        ra.push((TraceRecord::fill(6, 0.50, 5.0, false), 7, 7_000));
+        ra.push((TraceRecord::order_ack(7), 8, 8_000));
+        let mut rb = common;
+        rb.push((TraceRecord::fill(6, 0.51, 5.0, false), 7, 7_000));
+
+        let cmp = compare_traces(Some(&trace_of(ra)), Some(&trace_of(rb)), 2);
+        let TraceComparison::Diverged(d) = cmp else { panic!("expected divergence: {:?}", cmp) };
+        assert_eq!(d.index, 6);
+        assert_eq!(d.event_index, Some(7));
+        assert_eq!(d.sim_time_ns, Some(7_000));
+        assert_eq!(d.record_a.unwrap().price_ticks, Some(price_to_ticks(0.50)));
+        // Two before, the divergence, and one after (only A has it)
+        assert_eq!(d.context.iter().map(|r| r.index).collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+        assert_eq!(d.context.iter().map(|r| r.differs).collect::<Vec<_>>(), vec![false, false, true, true]);
+        assert!(d.context[3].b.is_none());
+    }
+
+    #[test]
+    fn test_compare_traces_prefix_and_truncation() {
+        let a = trace_of(vec![(TraceRecord::order_ack(1), 0, 0)]);
+        let b = trace_of(vec![(TraceRecord::order_ack(1), 0, 0), (TraceRecord::order_ack(2), 1, 5)]);
+        let TraceComparison::Diverged(d) = compare_traces(Some(&a), Some(&b), 1) else { panic!() };
+        assert_eq!(d.index, 1);
+        assert!(d.record_a.is_none());
+
+        // Both cut off at the same limit with identical prefixes
+        let mut ta = DecisionTrace::new(1);
+        let mut tb = DecisionTrace::new(1);
+        ta.push(TraceRecord::order_ack(1), 0, 0);
+        tb.push(TraceRecord::order_ack(1), 0, 0);
+        ta.push(TraceRecord::order_ack(2), 1, 0);
+        tb.push(TraceRecord::order_ack(3), 1, 0);
+        assert!(matches!(
+            compare_traces(Some(&ta), Some(&tb), 1),
+            TraceComparison::BeyondRecordedWindow { recorded: 1 }
+        ));
+        assert!(matches!(compare_traces(None, Some(&tb), 1), TraceComparison::Unavailable { .. }));
+    }
+
+    /// Buys `size` once per token on the first book update.
+    struct BuyOnce {
+        size: f64,
+        done: bool,
+    }
+
+    impl Strategy for BuyOnce {
+        fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+            if !self.done {
+                self.done = true;
+                let _ = ctx
+                    .orders
+                    .send_order(StrategyOrder::limit("c1", book.token_id.clone(), Side::Buy, 0.45, self.size));
+            }
+        }
+        fn on_trade(&mut self, _ctx: &mut StrategyContext, _trade: &TradePrint) {}
+        fn on_timer(&mut self, _ctx: &mut StrategyContext, _timer: &TimerEvent) {}
+        fn on_order_ack(&mut self, _ctx: &mut StrategyContext, _ack: &OrderAck) {}
+        fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {}
+        fn on_fill(&mut self, _ctx: &mut StrategyContext, _fill: &FillNotification) {}
+        fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, _ack: &CancelAck) {}
+        fn name(&self) -> &str {
+            "BuyOnce"
+        }
+    }
+
+    fn run(size: f64, seed: u64) -> RunArtifact {
+        let mut config = BacktestConfig::test_config();
+        config.seed = seed;
+        let events: Vec<TimestampedEvent> = (0..5)
+            .map(|i| {
+                TimestampedEvent::new(
+                    1_000_000_000 + i * 1_000_000,
+                    StreamSource::MarketData as u8,
+                    Event::L2BookSnapshot {
+                        token_id: "tok-up".into(),
+                        bids: vec![Level::new(0.44, 100.0)],
+                        asks: vec![Level::new(0.46, 100.0)],
+                        exchange_seq: i as u64 + 1,
+                    },
+                )
+            })
+            .collect();
+        let mut orchestrator = BacktestOrchestrator::new(config.clone());
+        orchestrator.load_feed(&mut VecFeed::new("test", events)).unwrap();
+        let results = orchestrator.run(&mut BuyOnce { size, done: false }).unwrap();
+        RunArtifact::from_results(results, &config)
+    }
+
+    #[test]
+    fn test_run_diff_explains_behavior_and_seed_changes() {
+        let base = run(10.0, 42);
+        let same = run(10.0, 42);
+        let bigger = run(20.0, 42);
+        let reseeded = run(10.0, 7);
+
+        let diff = RunDiff::compare(&base, &same, 3);
+        assert!(diff.identical, "{}", diff.format_report());
+        assert!(matches!(diff.trace, TraceComparison::Identical { .. }));
+
+        let diff = RunDiff::compare(&base, &bigger, 3);
+        assert!(!diff.identical);
+        assert_eq!(diff.changed_components(), vec!["behavior"]);
+        let d = diff.divergence().expect("trace divergence");
+        let (ra, rb) = (d.record_a.as_ref().unwrap(), d.record_b.as_ref().unwrap());
+        assert_eq!(ra.kind, TraceRecordKind::OrderSubmit);
+        assert_eq!(ra.size_shares, Some(size_to_shares(10.0)));
+        assert_eq!(rb.size_shares, Some(size_to_shares(20.0)));
+        assert!(diff.format_report().contains(">>"));
+
+        let diff = RunDiff::compare(&base, &reseeded, 3);
+        assert!(diff.changed_components().contains(&"seed"));
+        let seed = diff.components.iter().find(|c| c.component == "seed").unwrap();
+        assert!(seed.changes.iter().any(|c| c.path == "primary_seed"));
+    }
+}
@@ -129,6 +129,7 @@ impl LatencySweepConfig {
                     venue_process: dist.clone(),
                     cancel_process: dist.clone(),
                     fill_report: dist,
+                    regimes: base_config.regimes.clone(),
                 }
             }
             LatencyComponent::MarketData => LatencyConfig {
@@ -7,6 +7,7 @@
 //! - Sweep configuration generation
 
 use crate::backtest_v2::latency::{LatencyConfig, LatencyDistribution, NS_PER_MS};
+use crate::backtest_v2::latency_regime::LatencyRegimeConfig;
 use crate::backtest_v2::orchestrator::MakerFillModel;
 use crate::backtest_v2::sensitivity::*;
 
@@ -116,6 +117,7 @@ fn test_latency_config_generation_single_component() {
         venue_process: LatencyDistribution::Fixed { latency_ns: 100 * NS_PER_MS },
         cancel_process: LatencyDistribution::Fixed { latency_ns: 150 * NS_PER_MS },
         fill_report: LatencyDistribution::Fixed { latency_ns: 100 * NS_PER_MS },
+        regimes: LatencyRegimeConfig::none(),
     };
     
     let config = sweep.config_for_value(50.0, &base);
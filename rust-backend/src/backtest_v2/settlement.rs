@@ -357,6 +357,7 @@ impl SettlementState {
 // =============================================================================
 
 /// Settlement engine for tracking and resolving market windows.
+#[derive(Serialize, Deserialize)]
 pub struct SettlementEngine {
     /// Settlement specification.
     spec: SettlementSpec,
@@ -12,15 +12,20 @@
 
 use crate::backtest_v2::clock::Nanos;
 use crate::guard_direct_mutation;
-use crate::backtest_v2::events::{Event, OrderId, OrderType, Side, Size, TimeInForce, TimestampedEvent};
+use crate::backtest_v2::events::{
+    BatchMode, Event, OrderId, OrderType, Side, Size, TimeInForce, TimestampedEvent,
+};
 use crate::backtest_v2::latency::{LatencyConfig, LatencySampler};
 use crate::backtest_v2::matching::{
-    CancelRequest, LimitOrderBook, MatchingConfig, MatchingEngine, OrderRequest,
+    CancelRequest, LimitOrderBook, MatchingConfig, MatchingEngine, OrderRequest, ReplaceRequest,
 };
 use crate::backtest_v2::oms::{MarketStatus, OrderManagementSystem, OmsStats, VenueConstraints};
+use crate::backtest_v2::multi_market::PortfolioRiskGate;
 use crate::backtest_v2::queue::StreamSource;
+use crate::backtest_v2::risk::RiskCheckResult;
+use crate::backtest_v2::run_diff::TraceRecord;
 use crate::backtest_v2::strategy::{
-    OpenOrder, OrderSender, Position, StrategyCancel, StrategyOrder,
+    OpenOrder, OrderSender, Position, StrategyCancel, StrategyOrder, StrategyReplace,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -86,6 +91,7 @@ pub struct OmsParityStats {
 
 /// Simulated order sender for backtesting.
 /// Now with OMS parity enforcement for production-grade backtests.
+#[derive(Serialize, Deserialize)]
 pub struct SimulatedOrderSender {
     /// Current simulation time.
     current_time: Nanos,
@@ -111,9 +117,13 @@ pub struct SimulatedOrderSender {
     next_timer_id: u64,
     /// Scheduled timers.
     timers: HashMap<u64, ScheduledTimer>,
+    /// Cross-market pre-trade risk gate (multi-market runs).
+    risk_gate: Option<PortfolioRiskGate>,
+    /// Order/cancel requests since the last drain (None = not recording).
+    request_log: Option<Vec<TraceRecord>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct OpenOrderInternal {
     order_id: OrderId,
     client_order_id: String,
@@ -125,7 +135,7 @@ struct OpenOrderInternal {
     created_at: Nanos,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledTimer {
     pub timer_id: u64,
     pub fire_time: Nanos,
@@ -177,6 +187,8 @@ impl SimulatedOrderSender {
             pending_events: Vec::new(),
             next_timer_id: 1,
             timers: HashMap::new(),
+            risk_gate: None,
+            request_log: None,
         }
     }
 
@@ -207,6 +219,22 @@ impl SimulatedOrderSender {
         self.oms.stats.clone()
     }
 
+    /// Install a portfolio risk gate. Every subsequent order is checked against
+    /// it before OMS validation.
+    pub fn set_risk_gate(&mut self, gate: PortfolioRiskGate) {
+        self.risk_gate = Some(gate);
+    }
+
+    /// Get the portfolio risk gate (if installed).
+    pub fn risk_gate(&self) -> Option<&PortfolioRiskGate> {
+        self.risk_gate.as_ref()
+    }
+
+    /// Get mutable portfolio risk gate (if installed).
+    pub fn risk_gate_mut(&mut self) -> Option<&mut PortfolioRiskGate> {
+        self.risk_gate.as_mut()
+    }
+
     /// Set market status for a token.
     pub fn set_market_status(&mut self, token_id: &str, status: MarketStatus) {
         self.oms.set_market_status(token_id, status);
@@ -220,6 +248,17 @@ impl SimulatedOrderSender {
     /// Set current simulation time.
     pub fn set_time(&mut self, time: Nanos) {
         self.current_time = time;
+        self.latency.set_time(time);
+    }
+
+    /// Start recording order and cancel requests for the decision trace.
+    pub fn enable_request_log(&mut self) {
+        self.request_log.get_or_insert_with(Vec::new);
+    }
+
+    /// Get recorded order/cancel requests and clear the log.
+    pub fn take_request_log(&mut self) -> Vec<TraceRecord> {
+        self.request_log.as_mut().map(std::mem::take).unwrap_or_default()
     }
 
     /// Get pending events and clear the queue.
@@ -350,6 +389,23 @@ impl SimulatedOrderSender {
         self.open_orders.remove(&order_id);
     }
 
+    /// Process a replace ack (apply the new terms to OMS and open order tracking).
+    pub fn process_replace_ack(&mut self, order_id: OrderId, price: f64, leaves_qty: Size) {
+        self.oms
+            .on_replace_ack(order_id, price, leaves_qty, self.current_time);
+        if let Some(order) = self.open_orders.get_mut(&order_id) {
+            let filled = order.original_size - order.remaining_size;
+            order.price = price;
+            order.remaining_size = leaves_qty;
+            order.original_size = filled + leaves_qty;
+        }
+    }
+
+    /// Process a replace reject (order stays live at its old terms).
+    pub fn process_replace_reject(&mut self, order_id: OrderId) {
+        self.oms.on_replace_reject(order_id, self.current_time);
+    }
+
     /// Check and fire timers.
     pub fn check_timers(&mut self) -> Vec<ScheduledTimer> {
         let current = self.current_time;
@@ -371,10 +427,167 @@ impl SimulatedOrderSender {
     pub fn latency_sampler(&mut self) -> &mut LatencySampler {
         &mut self.latency
     }
-}
 
-impl OrderSender for SimulatedOrderSender {
-    fn send_order(&mut self, order: StrategyOrder) -> Result<OrderId, String> {
+    /// Validate and route an order to the matching engine.
+    fn submit_order(&mut self, order: StrategyOrder) -> Result<OrderId, String> {
+        let order_id = self.admit_order(&order)?;
+
+        // === OMS PARITY: Send order through OMS for rate limiting ===
+        if self.oms_parity_mode != OmsParityMode::Bypass {
+            if let Err(e) = self.oms.send_order(order_id, self.current_time) {
+                self.oms_parity_stats.would_reject_count += 1;
+                if e.message.contains("rate limit") {
+                    self.oms_parity_stats.rate_limited_orders += 1;
+                }
+                
+                match self.oms_parity_mode {
+                    OmsParityMode::Full => {
+                        return Err(format!("OMS send failed: {}", e.message));
+                    }
+                    OmsParityMode::Relaxed | OmsParityMode::Bypass => {
+                        self.oms_parity_stats.valid_for_production = false;
+                    }
+                }
+            }
+        }
+
+        // Sample latencies
+        let order_send_latency = self.latency.sample_order_send();
+        let venue_latency = self.latency.sample_venue_process();
+        let total_latency = order_send_latency + venue_latency;
+
+        // Submit to matching engine (at future time)
+        let submit_time = self.current_time + total_latency;
+        let matching_req = self.matching_request(&order);
+        let events = self.matching.submit_order(matching_req, submit_time);
+
+        self.track_open_order(order_id, order);
+
+        // Queue events for delivery
+        self.pending_events.extend(events);
+
+        Ok(order_id)
+    }
+
+    /// Validate and route a batch of orders to the matching engine as one message.
+    fn submit_batch(
+        &mut self,
+        orders: Vec<StrategyOrder>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<OrderId, String>>, String> {
+        let now = self.current_time;
+
+        // === OMS PARITY: Venue batch size limit ===
+        let max_batch_size = self.oms.constraints().max_batch_size;
+        if self.oms_parity_mode != OmsParityMode::Bypass && orders.len() > max_batch_size {
+            self.oms_parity_stats.would_reject_count += 1;
+            match self.oms_parity_mode {
+                OmsParityMode::Full => {
+                    return Err(format!(
+                        "Batch of {} orders exceeds venue limit {}",
+                        orders.len(),
+                        max_batch_size
+                    ));
+                }
+                OmsParityMode::Relaxed | OmsParityMode::Bypass => {
+                    self.oms_parity_stats.valid_for_production = false;
+                }
+            }
+        }
+
+        let mut results = Vec::with_capacity(orders.len());
+        for order in &orders {
+            let result = self.admit_order(order);
+            // Tracked up front so the risk gate sees earlier legs of the batch
+            if let Ok(order_id) = result {
+                self.track_open_order(order_id, order.clone());
+            }
+            results.push(result);
+        }
+
+        // An all-or-none batch with an invalid order never leaves the client
+        if mode == BatchMode::AllOrNone && results.iter().any(Result::is_err) {
+            for order_id in results.iter().filter_map(|r| r.as_ref().ok()) {
+                self.oms
+                    .on_order_reject(*order_id, "Batch aborted".into(), now);
+                self.open_orders.remove(order_id);
+            }
+            return Ok(results
+                .into_iter()
+                .map(|r| r.and(Err("Batch aborted: another order failed validation".into())))
+                .collect());
+        }
+
+        // === OMS PARITY: Send the batch through OMS for rate limiting ===
+        if self.oms_parity_mode != OmsParityMode::Bypass {
+            let (indices, order_ids): (Vec<usize>, Vec<OrderId>) = results
+                .iter()
+                .enumerate()
+                .filter_map(|(index, r)| r.as_ref().ok().map(|id| (index, *id)))
+                .unzip();
+            let sends = match self.oms.send_batch(&order_ids, mode, now) {
+                Ok(sends) => sends,
+                Err(e) => vec![Err(e); order_ids.len()],
+            };
+            for ((index, order_id), send) in indices.into_iter().zip(order_ids).zip(sends) {
+                let Err(e) = send else { continue };
+                self.oms_parity_stats.would_reject_count += 1;
+                if e.message.contains("rate limit") {
+                    self.oms_parity_stats.rate_limited_orders += 1;
+                }
+
+                match self.oms_parity_mode {
+                    OmsParityMode::Full => {
+                        self.open_orders.remove(&order_id);
+                        results[index] = Err(format!("OMS send failed: {}", e.message));
+                    }
+                    OmsParityMode::Relaxed | OmsParityMode::Bypass => {
+                        self.oms_parity_stats.valid_for_production = false;
+                    }
+                }
+            }
+        }
+
+        if results.iter().all(Result::is_err) {
+            return Ok(results);
+        }
+
+        // One message, so one trip through the send path
+        let total_latency = self.latency.sample_order_send() + self.latency.sample_venue_process();
+        let submit_time = now + total_latency;
+
+        let requests = orders
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_ok())
+            .map(|(order, _)| self.matching_request(order))
+            .collect();
+        let events = self.matching.submit_batch(requests, mode, submit_time);
+        self.pending_events.extend(events);
+
+        Ok(results)
+    }
+
+    /// Run the risk gate and create the order in the OMS, returning its ID.
+    fn admit_order(&mut self, order: &StrategyOrder) -> Result<OrderId, String> {
+        // === PORTFOLIO RISK: Cross-market limits are checked before anything else ===
+        if self.risk_gate.is_some() {
+            let open_orders = self.get_open_orders();
+            let now = self.current_time;
+            if let Some(gate) = self.risk_gate.as_mut() {
+                if let RiskCheckResult::Blocked(reason) = gate.check_order(
+                    &order.token_id,
+                    order.side,
+                    order.size,
+                    order.price,
+                    &open_orders,
+                    now,
+                ) {
+                    return Err(format!("Risk limit: {}", reason));
+                }
+            }
+        }
+
         // === OMS PARITY: Create order through OMS for validation ===
         let oms_result = if self.oms_parity_mode != OmsParityMode::Bypass {
             self.oms.create_order(
@@ -416,32 +629,12 @@ impl OrderSender for SimulatedOrderSender {
             }
         };
 
-        // === OMS PARITY: Send order through OMS for rate limiting ===
-        if self.oms_parity_mode != OmsParityMode::Bypass {
-            if let Err(e) = self.oms.send_order(order_id, self.current_time) {
-                self.oms_parity_stats.would_reject_count += 1;
-                if e.message.contains("rate limit") {
-                    self.oms_parity_stats.rate_limited_orders += 1;
-                }
-                
-                match self.oms_parity_mode {
-                    OmsParityMode::Full => {
-                        return Err(format!("OMS send failed: {}", e.message));
-                    }
-                    OmsParityMode::Relaxed | OmsParityMode::Bypass => {
-                        self.oms_parity_stats.valid_for_production = false;
-                    }
-                }
-            }
-        }
-
-        // Sample latencies
-        let order_send_latency = self.latency.sample_order_send();
-        let venue_latency = self.latency.sample_venue_process();
-        let total_latency = order_send_latency + venue_latency;
+        Ok(order_id)
+    }
 
-        // Create matching request
-        let matching_req = OrderRequest {
+    /// Build the matching engine request for a strategy order.
+    fn matching_request(&self, order: &StrategyOrder) -> OrderRequest {
+        OrderRequest {
             client_order_id: order.client_order_id.clone(),
             token_id: order.token_id.clone(),
             side: order.side,
@@ -452,13 +645,11 @@ impl OrderSender for SimulatedOrderSender {
             trader_id: self.trader_id.clone(),
             post_only: order.post_only,
             reduce_only: order.reduce_only,
-        };
-
-        // Submit to matching engine (at future time)
-        let submit_time = self.current_time + total_latency;
-        let events = self.matching.submit_order(matching_req, submit_time);
+        }
+    }
 
-        // Track open order
+    /// Track an order routed to the matching engine.
+    fn track_open_order(&mut self, order_id: OrderId, order: StrategyOrder) {
         self.open_orders.insert(
             order_id,
             OpenOrderInternal {
@@ -472,15 +663,68 @@ impl OrderSender for SimulatedOrderSender {
                 created_at: self.current_time,
             },
         );
+    }
+}
 
-        // Queue events for delivery
-        self.pending_events.extend(events);
+impl OrderSender for SimulatedOrderSender {
+    fn send_order(&mut self, order: StrategyOrder) -> Result<OrderId, String> {
+        if self.request_log.is_none() {
+            return self.submit_order(order);
+        }
+        let (token_id, side, price, size) = (order.token_id.clone(), order.side, order.price, order.size);
+        let result = self.submit_order(order);
+        let record = match &result {
+            Ok(order_id) => TraceRecord::order_submit(*order_id, &token_id, side, price, size),
+            Err(reason) => TraceRecord::order_send_rejected(&token_id, side, price, size, reason),
+        };
+        if let Some(log) = self.request_log.as_mut() {
+            log.push(record);
+        }
+        result
+    }
 
-        Ok(order_id)
+    fn send_batch(
+        &mut self,
+        orders: Vec<StrategyOrder>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<OrderId, String>>, String> {
+        if self.request_log.is_none() {
+            return self.submit_batch(orders, mode);
+        }
+        let legs: Vec<_> = orders
+            .iter()
+            .map(|o| (o.token_id.clone(), o.side, o.price, o.size))
+            .collect();
+        let result = self.submit_batch(orders, mode);
+        let records: Vec<TraceRecord> = legs
+            .iter()
+            .enumerate()
+            .map(|(index, (token_id, side, price, size))| {
+                let leg = match &result {
+                    Ok(results) => results[index].as_ref().map_err(String::as_str),
+                    Err(reason) => Err(reason.as_str()),
+                };
+                match leg {
+                    Ok(order_id) => {
+                        TraceRecord::order_submit(*order_id, token_id, *side, *price, *size)
+                    }
+                    Err(reason) => {
+                        TraceRecord::order_send_rejected(token_id, *side, *price, *size, reason)
+                    }
+                }
+            })
+            .collect();
+        if let Some(log) = self.request_log.as_mut() {
+            log.extend(records);
+        }
+        result
     }
 
     fn send_cancel(&mut self, cancel: StrategyCancel) -> Result<(), String> {
         let order_id = cancel.order_id;
+        if let Some(log) = self.request_log.as_mut() {
+            log.push(TraceRecord::cancel_request(order_id));
+        }
 
         // Get token ID from open orders
         let Some(order) = self.open_orders.get(&order_id) else {
@@ -525,6 +769,59 @@ impl OrderSender for SimulatedOrderSender {
         Ok(())
     }
 
+    fn send_replace(&mut self, replace: StrategyReplace) -> Result<(), String> {
+        let order_id = replace.order_id;
+        if let Some(log) = self.request_log.as_mut() {
+            log.push(TraceRecord::replace_request(
+                order_id,
+                replace.price,
+                replace.size,
+            ));
+        }
+
+        let Some(order) = self.open_orders.get(&order_id) else {
+            return Err("Order not found".into());
+        };
+        let token_id = order.token_id.clone();
+
+        // === OMS PARITY: Request replace through OMS for validation and rate limiting ===
+        if self.oms_parity_mode != OmsParityMode::Bypass {
+            if let Err(e) =
+                self.oms
+                    .request_replace(order_id, replace.price, replace.size, self.current_time)
+            {
+                self.oms_parity_stats.would_reject_count += 1;
+
+                match self.oms_parity_mode {
+                    OmsParityMode::Full => {
+                        return Err(format!("OMS replace failed: {}", e.message));
+                    }
+                    OmsParityMode::Relaxed | OmsParityMode::Bypass => {
+                        self.oms_parity_stats.valid_for_production = false;
+                    }
+                }
+            }
+        }
+
+        // A replace takes the same path to the venue as a new order
+        let replace_latency =
+            self.latency.sample_order_send() + self.latency.sample_venue_process();
+        let replace_time = self.current_time + replace_latency;
+
+        let replace_req = ReplaceRequest {
+            order_id,
+            new_price: replace.price,
+            new_size: replace.size,
+        };
+
+        let events = self
+            .matching
+            .replace_order(&token_id, replace_req, replace_time);
+        self.pending_events.extend(events);
+
+        Ok(())
+    }
+
     fn cancel_all(&mut self, token_id: &str) -> Result<usize, String> {
         let orders_to_cancel: Vec<OrderId> = self
             .open_orders
@@ -534,12 +831,25 @@ impl OrderSender for SimulatedOrderSender {
             .collect();
 
         let count = orders_to_cancel.len();
+        let mut failures = Vec::new();
 
         for order_id in orders_to_cancel {
-            let _ = self.send_cancel(StrategyCancel {
+            if let Err(e) = self.send_cancel(StrategyCancel {
                 order_id,
                 client_order_id: None,
-            });
+            }) {
+                failures.push(format!("{}: {}", order_id, e));
+            }
+        }
+
+        // Orders whose cancel was refused are still working
+        if !failures.is_empty() {
+            return Err(format!(
+                "{} of {} cancels failed: {}",
+                failures.len(),
+                count,
+                failures.join("; ")
+            ));
         }
 
         Ok(count)
@@ -741,4 +1051,98 @@ mod tests {
         assert_eq!(stats.mode, OmsParityMode::Full);
         assert!(stats.valid_for_production);
     }
+
+    #[test]
+    fn test_replace_round_trip() {
+        let mut sender = SimulatedOrderSender::new(
+            MatchingConfig::default(),
+            LatencyConfig::default(),
+            "test_trader",
+            42,
+        );
+
+        sender.set_time(1_000_000_000);
+
+        let order = StrategyOrder::limit("order1", "token123", Side::Buy, 0.45, 100.0);
+        let order_id = sender.send_order(order).unwrap();
+
+        // Replace before the ack is refused: the order is not live yet
+        assert!(sender
+            .send_replace(StrategyReplace::new(order_id, 0.46, 100.0))
+            .is_err());
+
+        sender.process_order_ack(order_id);
+        sender.take_pending_events();
+
+        sender
+            .send_replace(StrategyReplace::new(order_id, 0.46, 80.0))
+            .unwrap();
+        let events = sender.take_pending_events();
+        let (price, leaves_qty) = events
+            .iter()
+            .find_map(|e| match e.event {
+                Event::ReplaceAck {
+                    price, leaves_qty, ..
+                } => Some((price, leaves_qty)),
+                _ => None,
+            })
+            .expect("replace ack");
+        // One order-send plus venue leg, not a cancel followed by a new order
+        assert_eq!(events[0].time, 1_000_000_000 + 300_000);
+
+        sender.process_replace_ack(order_id, price, leaves_qty);
+        let open = sender.get_open_orders();
+        assert_eq!(open[0].price, 0.46);
+        assert_eq!(open[0].remaining_size, 80.0);
+        assert_eq!(sender.oms_stats().orders_replaced, 1);
+    }
+
+    #[test]
+    fn test_batch_all_or_none_admission() {
+        let mut sender = SimulatedOrderSender::new(
+            MatchingConfig::default(),
+            LatencyConfig::default(),
+            "test_trader",
+            42,
+        );
+        sender.set_time(1_000_000_000);
+
+        // One invalid leg aborts the whole batch before it is sent
+        let results = sender
+            .send_batch(
+                vec![
+                    StrategyOrder::limit("yes1", "yes_token", Side::Buy, 0.45, 100.0),
+                    StrategyOrder::limit("no1", "no_token", Side::Buy, 1.50, 100.0),
+                ],
+                BatchMode::AllOrNone,
+            )
+            .unwrap();
+        assert!(results.iter().all(Result::is_err));
+        assert!(sender.get_open_orders().is_empty());
+        assert!(sender.take_pending_events().is_empty());
+        assert_eq!(sender.oms_stats().orders_sent, 0);
+
+        let results = sender
+            .send_batch(
+                vec![
+                    StrategyOrder::limit("yes2", "yes_token", Side::Buy, 0.45, 100.0),
+                    StrategyOrder::limit("no2", "no_token", Side::Buy, 0.50, 100.0),
+                ],
+                BatchMode::AllOrNone,
+            )
+            .unwrap();
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(sender.get_open_orders().len(), 2);
+        assert_eq!(sender.oms_stats().batches_sent, 1);
+
+        // Both legs share one trip to the venue
+        let ack_times: Vec<Nanos> = sender
+            .take_pending_events()
+            .iter()
+            .filter(|e| matches!(e.event, Event::OrderAck { .. }))
+            .map(|e| e.time)
+            .collect();
+        assert_eq!(ack_times.len(), 2);
+        assert_eq!(ack_times[0], ack_times[1]);
+    }
 }
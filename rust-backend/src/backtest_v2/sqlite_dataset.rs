@@ -0,0 +1,359 @@
+//! SQLite Dataset Loading
+//!
+//! Loads recorded market data from a dataset SQLite file into a merged,
+//! time-ordered `TimestampedEvent` stream for `BacktestOrchestrator`.
+//!
+//! Sources, per market (token ids matched by `LIKE %market_id%`):
+//! 1. `book_snapshots` - recorded L2 orderbook snapshots
+//! 2. `trade_prints` - recorded trade prints
+//! 3. `dome_order_events` - tracked wallet orders, replayed as trade prints
+//!    plus a synthetic book (fallback/supplement)
+//!
+//! Missing tables are skipped. All markets share one sequence counter so the
+//! merged stream has a unique `(time, seq)` ordering.
+
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_MILLI, NANOS_PER_SEC};
+use crate::backtest_v2::events::{Event, Level, Side, TimestampedEvent};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Event counts loaded for one market.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketLoadCounts {
+    pub market_id: String,
+    pub book_snapshots: usize,
+    pub trade_prints: usize,
+    pub dome_order_events: usize,
+}
+
+impl MarketLoadCounts {
+    /// Total source rows loaded.
+    pub fn total(&self) -> usize {
+        self.book_snapshots + self.trade_prints + self.dome_order_events
+    }
+}
+
+/// Load and merge events for `market_ids` in `[start_ns, end_ns]`.
+///
+/// Fails if the database cannot be opened or any market has no events in range.
+pub fn load_events_from_sqlite(
+    db_path: &str,
+    market_ids: &[String],
+    start_ns: Nanos,
+    end_ns: Nanos,
+) -> Result<(Vec<TimestampedEvent>, Vec<MarketLoadCounts>), String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    let mut events = Vec::new();
+    // Shared across markets so merged events keep a unique, load-ordered tiebreak
+    let mut seq = 0u64;
+    let mut counts = Vec::with_capacity(market_ids.len());
+
+    for market_id in market_ids {
+        let market_counts = MarketLoadCounts {
+            market_id: market_id.clone(),
+            book_snapshots: load_book_snapshots(&conn, market_id, start_ns, end_ns, &mut events, &mut seq),
+            trade_prints: load_trade_prints(&conn, market_id, start_ns, end_ns, &mut events, &mut seq),
+            dome_order_events: load_dome_order_events(&conn, market_id, start_ns, end_ns, &mut events, &mut seq),
+        };
+
+        if market_counts.total() == 0 {
+            return Err(format!(
+                "No events found for market '{}' in time range",
+                market_id
+            ));
+        }
+        counts.push(market_counts);
+    }
+
+    // Sort by time then sequence (merges all markets into one stream)
+    events.sort_by_key(|e| (e.time, e.seq));
+
+    Ok((events, counts))
+}
+
+fn load_book_snapshots(
+    conn: &Connection,
+    market_id: &str,
+    start_ns: Nanos,
+    end_ns: Nanos,
+    events: &mut Vec<TimestampedEvent>,
+    seq: &mut u64,
+) -> usize {
+    let query = r#"
+        SELECT token_id, arrival_time_ns, bids_json, asks_json, COALESCE(exchange_seq, 0)
+        FROM book_snapshots
+        WHERE token_id LIKE ?
+          AND arrival_time_ns >= ? AND arrival_time_ns <= ?
+        ORDER BY arrival_time_ns ASC
+    "#;
+
+    let pattern = format!("%{}%", market_id);
+    let mut count = 0;
+
+    if let Ok(mut stmt) = conn.prepare(query) {
+        if let Ok(rows) = stmt.query_map(
+            rusqlite::params![&pattern, start_ns, end_ns],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            },
+        ) {
+            for row_result in rows {
+                if let Ok((token_id, arrival_ns, bids_json, asks_json, exchange_seq)) = row_result {
+                    let bids = parse_levels(&bids_json);
+                    let asks = parse_levels(&asks_json);
+
+                    let event = Event::L2BookSnapshot {
+                        token_id,
+                        bids,
+                        asks,
+                        exchange_seq: exchange_seq as u64,
+                    };
+
+                    events.push(TimestampedEvent {
+                        time: arrival_ns,
+                        source_time: arrival_ns,
+                        seq: *seq,
+                        source: 0,
+                        event,
+                    });
+                    *seq += 1;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+fn load_trade_prints(
+    conn: &Connection,
+    market_id: &str,
+    start_ns: Nanos,
+    end_ns: Nanos,
+    events: &mut Vec<TimestampedEvent>,
+    seq: &mut u64,
+) -> usize {
+    let query = r#"
+        SELECT token_id, arrival_time_ns, price, size, side, trade_id
+        FROM trade_prints
+        WHERE token_id LIKE ?
+          AND arrival_time_ns >= ? AND arrival_time_ns <= ?
+        ORDER BY arrival_time_ns ASC
+    "#;
+
+    let pattern = format!("%{}%", market_id);
+    let mut count = 0;
+
+    if let Ok(mut stmt) = conn.prepare(query) {
+        if let Ok(rows) = stmt.query_map(
+            rusqlite::params![&pattern, start_ns, end_ns],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            },
+        ) {
+            for row_result in rows {
+                if let Ok((token_id, arrival_ns, price, size, side_str, trade_id)) = row_result {
+                    let aggressor_side = if side_str.eq_ignore_ascii_case("BUY") {
+                        Side::Buy
+                    } else {
+                        Side::Sell
+                    };
+
+                    let event = Event::TradePrint {
+                        token_id,
+                        price,
+                        size,
+                        aggressor_side,
+                        trade_id,
+                    };
+
+                    events.push(TimestampedEvent {
+                        time: arrival_ns,
+                        source_time: arrival_ns,
+                        seq: *seq,
+                        source: 1,
+                        event,
+                    });
+                    *seq += 1;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+fn load_dome_order_events(
+    conn: &Connection,
+    market_id: &str,
+    start_ns: Nanos,
+    end_ns: Nanos,
+    events: &mut Vec<TimestampedEvent>,
+    seq: &mut u64,
+) -> usize {
+    // Convert nanos to seconds for dome_order_events timestamp column
+    let start_sec = start_ns / NANOS_PER_SEC;
+    let end_sec = end_ns / NANOS_PER_SEC;
+
+    let query = r#"
+        SELECT 
+            timestamp,
+            market_slug,
+            json_extract(payload_json, '$.side') as side,
+            json_extract(payload_json, '$.price') as price,
+            json_extract(payload_json, '$.shares_normalized') as shares,
+            json_extract(payload_json, '$.token_label') as outcome
+        FROM dome_order_events
+        WHERE market_slug LIKE ?
+          AND timestamp >= ? AND timestamp <= ?
+        ORDER BY timestamp ASC
+    "#;
+
+    let pattern = format!("%{}%", market_id);
+    let mut count = 0;
+
+    // Track synthetic book state per token
+    let mut synthetic_books: HashMap<String, (Vec<Level>, Vec<Level>)> = HashMap::new();
+
+    if let Ok(mut stmt) = conn.prepare(query) {
+        if let Ok(rows) = stmt.query_map(
+            rusqlite::params![&pattern, start_sec, end_sec],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<f64>>(3)?,
+                    row.get::<_, Option<f64>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            },
+        ) {
+            for row_result in rows {
+                if let Ok((timestamp, market_slug, side_opt, price_opt, shares_opt, outcome_opt)) =
+                    row_result
+                {
+                    let side_str = side_opt.unwrap_or_default();
+                    let price = price_opt.unwrap_or(0.5);
+                    let shares = shares_opt.unwrap_or(0.0);
+                    let outcome = outcome_opt.unwrap_or_else(|| "Yes".to_string());
+
+                    let time_ns = timestamp * NANOS_PER_SEC;
+                    let token_id = format!("{}_{}", market_slug, outcome);
+
+                    let aggressor_side = if side_str.eq_ignore_ascii_case("BUY") {
+                        Side::Buy
+                    } else {
+                        Side::Sell
+                    };
+
+                    // Create trade print from dome order
+                    let trade_event = Event::TradePrint {
+                        token_id: token_id.clone(),
+                        price,
+                        size: shares,
+                        aggressor_side,
+                        trade_id: Some(format!("dome_{}", *seq)),
+                    };
+
+                    events.push(TimestampedEvent {
+                        time: time_ns,
+                        source_time: time_ns,
+                        seq: *seq,
+                        source: 2,
+                        event: trade_event,
+                    });
+                    *seq += 1;
+
+                    // Build/update synthetic orderbook
+                    let (bids, asks) = synthetic_books.entry(token_id.clone()).or_insert_with(|| {
+                        (
+                            vec![
+                                Level::new(0.48, 100.0),
+                                Level::new(0.47, 200.0),
+                                Level::new(0.46, 300.0),
+                            ],
+                            vec![
+                                Level::new(0.52, 100.0),
+                                Level::new(0.53, 200.0),
+                                Level::new(0.54, 300.0),
+                            ],
+                        )
+                    });
+
+                    // Adjust book based on trade
+                    if aggressor_side == Side::Buy {
+                        if let Some(ask) = asks.first_mut() {
+                            ask.price = price;
+                            ask.size = (ask.size - shares).max(10.0);
+                        }
+                    } else {
+                        if let Some(bid) = bids.first_mut() {
+                            bid.price = price;
+                            bid.size = (bid.size - shares).max(10.0);
+                        }
+                    }
+
+                    bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
+                    asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+
+                    let book_event = Event::L2BookSnapshot {
+                        token_id,
+                        bids: bids.clone(),
+                        asks: asks.clone(),
+                        exchange_seq: *seq,
+                    };
+
+                    events.push(TimestampedEvent {
+                        time: time_ns + NANOS_PER_MILLI,
+                        source_time: time_ns,
+                        seq: *seq,
+                        source: 3,
+                        event: book_event,
+                    });
+                    *seq += 1;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+fn parse_levels(json_str: &str) -> Vec<Level> {
+    // Try parsing as array of [price, size] pairs
+    if let Ok(arr) = serde_json::from_str::<Vec<[f64; 2]>>(json_str) {
+        return arr.iter().map(|[p, s]| Level::new(*p, *s)).collect();
+    }
+    // Try parsing as array of objects
+    if let Ok(arr) = serde_json::from_str::<Vec<serde_json::Value>>(json_str) {
+        return arr
+            .iter()
+            .filter_map(|v| {
+                let price = v.get("price")?.as_f64()?;
+                let size = v.get("size").or(v.get("quantity"))?.as_f64()?;
+                Some(Level::new(price, size))
+            })
+            .collect();
+    }
+    vec![]
+}
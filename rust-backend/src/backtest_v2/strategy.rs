@@ -26,8 +26,11 @@
 #![deny(clippy::disallowed_methods)]
 
 use crate::backtest_v2::clock::Nanos;
-use crate::backtest_v2::events::{Level, OrderId, OrderType, Price, Side, Size, TimeInForce};
+use crate::backtest_v2::events::{
+    BatchMode, Level, OrderId, OrderType, Price, Side, Size, TimeInForce,
+};
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
 /// Book snapshot provided to strategies.
 #[derive(Debug, Clone)]
@@ -148,6 +151,28 @@ pub struct StrategyCancel {
     pub client_order_id: Option<String>,
 }
 
+/// Amend request sent by strategy (cancel-replace of a resting order).
+///
+/// `size` is the new total order size, including any quantity already
+/// filled. Shrinking at the same price keeps queue priority; any price
+/// change or size increase re-queues the order at the back.
+#[derive(Debug, Clone)]
+pub struct StrategyReplace {
+    pub order_id: OrderId,
+    pub price: Price,
+    pub size: Size,
+}
+
+impl StrategyReplace {
+    pub fn new(order_id: OrderId, price: Price, size: Size) -> Self {
+        Self {
+            order_id,
+            price,
+            size,
+        }
+    }
+}
+
 /// Fill notification received by strategy.
 #[derive(Debug, Clone)]
 pub struct FillNotification {
@@ -186,9 +211,30 @@ pub struct CancelAck {
     pub timestamp: Nanos,
 }
 
+/// Replace acknowledgment received by strategy.
+#[derive(Debug, Clone)]
+pub struct ReplaceAck {
+    pub order_id: OrderId,
+    pub price: Price,
+    /// Open quantity after the amend, net of fills matched before it applied.
+    pub leaves_qty: Size,
+    /// Whether the order kept its place in the queue.
+    pub kept_priority: bool,
+    pub timestamp: Nanos,
+}
+
+/// Replace rejection received by strategy. The order stays live at its old terms.
+#[derive(Debug, Clone)]
+pub struct ReplaceReject {
+    pub order_id: OrderId,
+    pub reason: String,
+    pub timestamp: Nanos,
+}
+
 /// Order sender interface - same API for live and backtest.
 ///
-/// In production: sends to exchange via gateway
+/// In production: queued and sent through an execution adapter
+/// (`vault::strategy_host::LiveOrderSender` in the server binary)
 /// In backtest: sends to matching simulator
 pub trait OrderSender: Send + Sync {
     /// Submit a new order.
@@ -197,6 +243,31 @@ pub trait OrderSender: Send + Sync {
     /// Cancel an existing order.
     fn send_cancel(&mut self, cancel: StrategyCancel) -> Result<(), String>;
 
+    /// Amend price and/or size of an existing order.
+    fn send_replace(&mut self, _replace: StrategyReplace) -> Result<(), String> {
+        Err("Order replace not supported by this sender".into())
+    }
+
+    /// Submit several orders in one venue message.
+    ///
+    /// The outer error refuses the batch as a whole; otherwise each order
+    /// gets its own result in submission order. Senders without a batch
+    /// path submit `Partial` batches one order at a time and cannot honour
+    /// `AllOrNone`.
+    fn send_batch(
+        &mut self,
+        orders: Vec<StrategyOrder>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<OrderId, String>>, String> {
+        match mode {
+            BatchMode::Partial => Ok(orders
+                .into_iter()
+                .map(|order| self.send_order(order))
+                .collect()),
+            BatchMode::AllOrNone => Err("All-or-none batches not supported by this sender".into()),
+        }
+    }
+
     /// Cancel all orders for a token.
     fn cancel_all(&mut self, token_id: &str) -> Result<usize, String>;
 
@@ -220,7 +291,7 @@ pub trait OrderSender: Send + Sync {
 }
 
 /// Position information.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Position {
     pub token_id: String,
     pub shares: Size,
@@ -327,6 +398,12 @@ pub trait Strategy: Send {
     /// Called when a cancel is acknowledged.
     fn on_cancel_ack(&mut self, ctx: &mut StrategyContext, ack: &CancelAck);
 
+    /// Called when a replace is acknowledged.
+    fn on_replace_ack(&mut self, _ctx: &mut StrategyContext, _ack: &ReplaceAck) {}
+
+    /// Called when a replace is rejected.
+    fn on_replace_reject(&mut self, _ctx: &mut StrategyContext, _reject: &ReplaceReject) {}
+
     /// Called once at strategy startup.
     fn on_start(&mut self, _ctx: &mut StrategyContext) {}
 
@@ -11,6 +11,10 @@
 //! ## Example Strategies (from example_strategy.rs)
 //! - `market_maker` - Two-sided market making around mid-price
 //! - `momentum` - Momentum-following strategy based on short-term price trends
+//!
+//! ## Runtime-loaded Strategies
+//! Sandboxed WASM modules are loaded through `wasm_strategy::WasmStrategyFactory`
+//! (`backtest_run --strategy-wasm <PATH>`) rather than by name here.
 
 use crate::backtest_v2::example_strategy::{MarketMakerStrategy, MomentumStrategy};
 use crate::backtest_v2::strategy::{
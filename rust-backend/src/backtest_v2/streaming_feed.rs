@@ -0,0 +1,738 @@
+//! Streaming Replay Feed
+//!
+//! K-way merge `MarketDataFeed` over lazily loaded chunks from the recorders
+//! (`L2Storage`, `TradePrintFullStorage`, `OracleRoundStorage`), so that
+//! multi-week ranges replay without materializing every event up front.
+//!
+//! # Memory Model
+//!
+//! Each source walks its range in consecutive half-open time windows and
+//! hands back one window at a time. Only the current chunk of each source
+//! (plus one merge head per source) is resident, so peak memory is bounded by
+//! the busiest window rather than by the length of the backtest.
+//!
+//! # Ordering
+//!
+//! The merge emits events in `TimestampedEvent` order
+//! `(time, priority, source, seq)`, the same order `EventQueue` pops them,
+//! with the source's registration index as the final tiebreak. Because
+//! windows are disjoint in time, a source never needs to see its next chunk
+//! to order the current one. A source whose chunk starts before the last
+//! event it emitted ends the feed with an error (see `MarketDataFeed::error`)
+//! rather than silently reordering history.
+
+use crate::backtest_v2::clock::{Nanos, NANOS_PER_SEC};
+use crate::backtest_v2::events::{EventPriority, TimestampedEvent};
+use crate::backtest_v2::feed::MarketDataFeed;
+use crate::backtest_v2::l2_replay::L2ReplayFeed;
+use crate::backtest_v2::l2_storage::L2Storage;
+use crate::backtest_v2::oracle::OracleRoundStorage;
+use crate::backtest_v2::queue::StreamSource;
+use crate::backtest_v2::trade_print_storage::TradePrintFullStorage;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Default chunk span: one 15-minute window per storage query.
+pub const DEFAULT_CHUNK_SPAN_NS: Nanos = 15 * 60 * NANOS_PER_SEC;
+
+// =============================================================================
+// CHUNK SOURCES
+// =============================================================================
+
+/// A lazily loaded, time-ordered stream of events, delivered in chunks.
+///
+/// Chunks must be non-overlapping in time and returned in ascending order.
+/// An empty chunk means the source is exhausted.
+pub trait EventChunkSource: Send {
+    /// Source identifier for logging/diagnostics.
+    fn name(&self) -> &str;
+
+    /// Load the next chunk of events.
+    fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String>;
+
+    /// Rewind to the start of the range.
+    fn reset(&mut self);
+}
+
+/// Walks `[start_ns, end_ns)` in half-open windows of `span_ns`.
+#[derive(Debug, Clone)]
+pub struct TimeWindows {
+    start_ns: Nanos,
+    end_ns: Nanos,
+    span_ns: Nanos,
+    cursor: Nanos,
+}
+
+impl TimeWindows {
+    pub fn new(start_ns: Nanos, end_ns: Nanos, span_ns: Nanos) -> Self {
+        Self {
+            start_ns,
+            end_ns,
+            span_ns: span_ns.max(1),
+            cursor: start_ns,
+        }
+    }
+
+    /// Next window `[from, to)`, clipped to the range end.
+    pub fn next_window(&mut self) -> Option<(Nanos, Nanos)> {
+        if self.cursor >= self.end_ns {
+            return None;
+        }
+        let from = self.cursor;
+        let to = from.saturating_add(self.span_ns).min(self.end_ns);
+        self.cursor = to;
+        Some((from, to))
+    }
+
+    pub fn reset(&mut self) {
+        self.cursor = self.start_ns;
+    }
+}
+
+/// L2 snapshots and deltas for one token, loaded a window at a time.
+pub struct L2ChunkSource {
+    storage: Arc<L2Storage>,
+    token_id: String,
+    windows: TimeWindows,
+    name: String,
+}
+
+impl L2ChunkSource {
+    pub fn new(
+        storage: Arc<L2Storage>,
+        token_id: impl Into<String>,
+        start_ns: Nanos,
+        end_ns: Nanos,
+        chunk_span_ns: Nanos,
+    ) -> Self {
+        let token_id = token_id.into();
+        Self {
+            name: format!("l2({})", token_id),
+            storage,
+            token_id,
+            windows: TimeWindows::new(start_ns, end_ns, chunk_span_ns),
+        }
+    }
+}
+
+impl EventChunkSource for L2ChunkSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String> {
+        while let Some((from, to)) = self.windows.next_window() {
+            let snapshots = self
+                .storage
+                .load_snapshots(&self.token_id, from, to)
+                .map_err(|e| format!("{}: failed to load snapshots: {}", self.name, e))?;
+            let deltas = self
+                .storage
+                .load_deltas(&self.token_id, from, to)
+                .map_err(|e| format!("{}: failed to load deltas: {}", self.name, e))?;
+            if snapshots.is_empty() && deltas.is_empty() {
+                continue;
+            }
+            let mut feed = L2ReplayFeed::from_events(snapshots, deltas, self.storage.tick_size());
+            return Ok(std::iter::from_fn(|| feed.next_event()).collect());
+        }
+        Ok(Vec::new())
+    }
+
+    fn reset(&mut self) {
+        self.windows.reset();
+    }
+}
+
+/// Trade prints for one market, loaded a window at a time by visible time.
+pub struct TradePrintChunkSource {
+    storage: Arc<TradePrintFullStorage>,
+    market_id: String,
+    windows: TimeWindows,
+    name: String,
+}
+
+impl TradePrintChunkSource {
+    pub fn new(
+        storage: Arc<TradePrintFullStorage>,
+        market_id: impl Into<String>,
+        start_ns: Nanos,
+        end_ns: Nanos,
+        chunk_span_ns: Nanos,
+    ) -> Self {
+        let market_id = market_id.into();
+        Self {
+            name: format!("trades({})", market_id),
+            storage,
+            market_id,
+            windows: TimeWindows::new(start_ns, end_ns, chunk_span_ns),
+        }
+    }
+}
+
+impl EventChunkSource for TradePrintChunkSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String> {
+        let source = StreamSource::MarketData as u8;
+        while let Some((from, to)) = self.windows.next_window() {
+            // Storage range is inclusive on both ends
+            let prints = self
+                .storage
+                .load_by_visible_ts(&self.market_id, from, to - 1)
+                .map_err(|e| format!("{}: failed to load trade prints: {}", self.name, e))?;
+            if prints.is_empty() {
+                continue;
+            }
+            return Ok(prints.iter().map(|p| p.to_timestamped_event(source)).collect());
+        }
+        Ok(Vec::new())
+    }
+
+    fn reset(&mut self) {
+        self.windows.reset();
+    }
+}
+
+/// Chainlink rounds for one feed, loaded a window at a time by arrival time.
+pub struct OracleRoundChunkSource {
+    storage: Arc<OracleRoundStorage>,
+    feed_id: String,
+    windows: TimeWindows,
+    name: String,
+}
+
+impl OracleRoundChunkSource {
+    pub fn new(
+        storage: Arc<OracleRoundStorage>,
+        feed_id: impl Into<String>,
+        start_ns: Nanos,
+        end_ns: Nanos,
+        chunk_span_ns: Nanos,
+    ) -> Self {
+        let feed_id = feed_id.into();
+        Self {
+            name: format!("oracle({})", feed_id),
+            storage,
+            feed_id,
+            windows: TimeWindows::new(start_ns.max(0), end_ns, chunk_span_ns),
+        }
+    }
+}
+
+impl EventChunkSource for OracleRoundChunkSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String> {
+        let source = StreamSource::MarketData as u8;
+        while let Some((from, to)) = self.windows.next_window() {
+            let rounds = self
+                .storage
+                .load_rounds_by_arrival(&self.feed_id, from as u64, (to - 1) as u64)
+                .map_err(|e| format!("{}: failed to load rounds: {}", self.name, e))?;
+            if rounds.is_empty() {
+                continue;
+            }
+            return Ok(rounds.iter().map(|r| r.to_timestamped_event(source)).collect());
+        }
+        Ok(Vec::new())
+    }
+
+    fn reset(&mut self) {
+        self.windows.reset();
+    }
+}
+
+/// In-memory events served in fixed-size chunks (tests and pre-loaded data).
+pub struct VecChunkSource {
+    events: Vec<TimestampedEvent>,
+    chunk_len: usize,
+    index: usize,
+    name: String,
+}
+
+impl VecChunkSource {
+    pub fn new(name: impl Into<String>, mut events: Vec<TimestampedEvent>, chunk_len: usize) -> Self {
+        events.sort();
+        Self {
+            events,
+            chunk_len: chunk_len.max(1),
+            index: 0,
+            name: name.into(),
+        }
+    }
+}
+
+impl EventChunkSource for VecChunkSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String> {
+        let end = (self.index + self.chunk_len).min(self.events.len());
+        let chunk = self.events[self.index..end].to_vec();
+        self.index = end;
+        Ok(chunk)
+    }
+
+    fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+// =============================================================================
+// STREAMING MERGE FEED
+// =============================================================================
+
+type OrderKey = (Nanos, EventPriority, u8, u64);
+
+fn order_key(event: &TimestampedEvent) -> OrderKey {
+    (event.time, event.event.priority(), event.source, event.seq)
+}
+
+/// Next event of one source, competing in the merge heap.
+struct MergeHead {
+    event: TimestampedEvent,
+    source_index: usize,
+}
+
+impl PartialEq for MergeHead {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MergeHead {}
+
+impl PartialOrd for MergeHead {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MergeHead {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.event
+            .cmp(&other.event)
+            .then_with(|| self.source_index.cmp(&other.source_index))
+    }
+}
+
+struct SourceCursor {
+    source: Box<dyn EventChunkSource>,
+    buffer: VecDeque<TimestampedEvent>,
+    last_key: Option<OrderKey>,
+}
+
+/// Deterministic k-way merge over chunked sources.
+pub struct StreamingMergeFeed {
+    cursors: Vec<SourceCursor>,
+    heap: BinaryHeap<Reverse<MergeHead>>,
+    error: Option<String>,
+    emitted: u64,
+    peak_buffered: usize,
+    name: String,
+}
+
+impl StreamingMergeFeed {
+    /// Create the merge and load the first chunk of every source.
+    pub fn new(name: impl Into<String>, sources: Vec<Box<dyn EventChunkSource>>) -> Self {
+        let mut feed = Self {
+            cursors: sources
+                .into_iter()
+                .map(|source| SourceCursor {
+                    source,
+                    buffer: VecDeque::new(),
+                    last_key: None,
+                })
+                .collect(),
+            heap: BinaryHeap::new(),
+            error: None,
+            emitted: 0,
+            peak_buffered: 0,
+            name: name.into(),
+        };
+        feed.prime();
+        feed
+    }
+
+    /// Number of sources being merged.
+    pub fn source_count(&self) -> usize {
+        self.cursors.len()
+    }
+
+    /// Events currently held in memory (chunk buffers plus merge heads).
+    pub fn buffered_events(&self) -> usize {
+        self.heap.len() + self.cursors.iter().map(|c| c.buffer.len()).sum::<usize>()
+    }
+
+    /// Highest `buffered_events` seen since the last reset.
+    pub fn peak_buffered(&self) -> usize {
+        self.peak_buffered
+    }
+
+    /// Events emitted since the last reset.
+    pub fn events_emitted(&self) -> u64 {
+        self.emitted
+    }
+
+    fn prime(&mut self) {
+        for index in 0..self.cursors.len() {
+            self.advance(index);
+        }
+    }
+
+    /// Move the next event of source `index` onto the heap, loading its next
+    /// chunk when the buffer runs dry.
+    fn advance(&mut self, index: usize) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let cursor = &mut self.cursors[index];
+        if cursor.buffer.is_empty() {
+            let mut chunk = match cursor.source.next_chunk() {
+                Ok(chunk) => chunk,
+                Err(e) => return self.fail(e),
+            };
+            chunk.sort();
+            if let (Some(last), Some(first)) = (cursor.last_key, chunk.first()) {
+                if order_key(first) < last {
+                    let message = format!(
+                        "{}: chunk starting at {} precedes last emitted event at {}",
+                        cursor.source.name(),
+                        first.time,
+                        last.0
+                    );
+                    return self.fail(message);
+                }
+            }
+            cursor.buffer.extend(chunk);
+        }
+
+        if let Some(event) = cursor.buffer.pop_front() {
+            cursor.last_key = Some(order_key(&event));
+            self.heap.push(Reverse(MergeHead {
+                event,
+                source_index: index,
+            }));
+        }
+        self.peak_buffered = self.peak_buffered.max(self.buffered_events());
+    }
+
+    fn fail(&mut self, error: String) {
+        warn!(feed = %self.name, %error, "Streaming feed stopped");
+        self.error = Some(error);
+        self.heap.clear();
+        for cursor in &mut self.cursors {
+            cursor.buffer.clear();
+        }
+    }
+}
+
+impl MarketDataFeed for StreamingMergeFeed {
+    fn next_event(&mut self) -> Option<TimestampedEvent> {
+        let Reverse(head) = self.heap.pop()?;
+        self.advance(head.source_index);
+        self.emitted += 1;
+        Some(head.event)
+    }
+
+    fn peek_time(&self) -> Option<Nanos> {
+        self.heap.peek().map(|Reverse(head)| head.event.time)
+    }
+
+    fn reset(&mut self) {
+        for cursor in &mut self.cursors {
+            cursor.source.reset();
+            cursor.buffer.clear();
+            cursor.last_key = None;
+        }
+        self.heap.clear();
+        self.error = None;
+        self.emitted = 0;
+        self.peak_buffered = 0;
+        self.prime();
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::{Event, Level, Side};
+    use crate::backtest_v2::l2_delta::{
+        EventTime, PolymarketL2Delta, PolymarketL2Snapshot, SequenceOrigin, SequenceScope,
+        TickPriceLevel,
+    };
+    use crate::backtest_v2::oracle::ChainlinkRound;
+    use crate::backtest_v2::queue::EventQueue;
+    use crate::backtest_v2::trade_print::PolymarketTradePrint;
+
+    const T0: Nanos = 1_705_320_000 * NANOS_PER_SEC;
+
+    fn event(time: Nanos, seq: u64, source: u8, snapshot: bool) -> TimestampedEvent {
+        let event = if snapshot {
+            Event::L2BookSnapshot {
+                token_id: "TOKEN".to_string(),
+                bids: vec![Level::new(0.49, 10.0)],
+                asks: vec![Level::new(0.51, 10.0)],
+                exchange_seq: seq,
+            }
+        } else {
+            Event::TradePrint {
+                token_id: "TOKEN".to_string(),
+                price: 0.5,
+                size: 1.0,
+                aggressor_side: Side::Buy,
+                trade_id: None,
+            }
+        };
+        TimestampedEvent {
+            time,
+            source_time: time - seq as Nanos,
+            seq,
+            source,
+            event,
+        }
+    }
+
+    fn drain(feed: &mut dyn MarketDataFeed) -> Vec<TimestampedEvent> {
+        std::iter::from_fn(|| feed.next_event()).collect()
+    }
+
+    /// Identity independent of `seq`, which `EventQueue` reassigns on insert.
+    fn keys(events: &[TimestampedEvent]) -> Vec<(Nanos, u8, Nanos)> {
+        events.iter().map(|e| (e.time, e.source, e.source_time)).collect()
+    }
+
+    #[test]
+    fn test_merge_order_matches_event_queue() {
+        let exchange = StreamSource::Exchange as u8;
+        let market = StreamSource::MarketData as u8;
+        // Interleaved times with ties across sources and priorities
+        let a: Vec<_> = (0..40).map(|i| event(T0 + (i / 2) * 10, i as u64, market, i % 3 == 0)).collect();
+        let b: Vec<_> = (0..25).map(|i| event(T0 + i * 15, 100 + i as u64, exchange, i % 2 == 0)).collect();
+
+        let mut queue = EventQueue::new();
+        for e in a.iter().chain(&b) {
+            queue.push_timestamped(e.clone());
+        }
+        let expected: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+
+        let mut feed = StreamingMergeFeed::new(
+            "merge",
+            vec![
+                Box::new(VecChunkSource::new("a", a, 4)),
+                Box::new(VecChunkSource::new("b", b, 3)),
+            ],
+        );
+        let merged = drain(&mut feed);
+
+        assert_eq!(keys(&merged), keys(&expected));
+        assert_eq!(feed.events_emitted(), 65);
+        assert!(feed.error().is_none());
+        // Never more than one chunk per source plus its merge head
+        assert!(feed.peak_buffered() <= 4 + 3 + 2, "peak {}", feed.peak_buffered());
+
+        feed.reset();
+        assert_eq!(keys(&drain(&mut feed)), keys(&expected));
+    }
+
+    fn snapshot(seq: u64, ingest_ts: Nanos) -> PolymarketL2Snapshot {
+        PolymarketL2Snapshot {
+            market_id: "market1".to_string(),
+            token_id: "token1".to_string(),
+            seq_snapshot: seq,
+            bids: vec![TickPriceLevel { price_ticks: 4500, size_fp: 1000_00000000 }],
+            asks: vec![TickPriceLevel { price_ticks: 5500, size_fp: 1500_00000000 }],
+            time: EventTime::ingest_only(ingest_ts),
+            total_bid_depth_fp: 1000_00000000,
+            total_ask_depth_fp: 1500_00000000,
+        }
+    }
+
+    fn delta(seq: u64, ingest_ts: Nanos) -> PolymarketL2Delta {
+        PolymarketL2Delta::absolute(
+            "market1".to_string(),
+            "token1".to_string(),
+            Side::Buy,
+            4500 - seq as i64,
+            500_00000000,
+            seq,
+            EventTime::ingest_only(ingest_ts),
+            Some(format!("hash_{}", seq)),
+        )
+    }
+
+    fn round(n: u64, arrival_ns: Nanos) -> ChainlinkRound {
+        ChainlinkRound {
+            feed_id: "btc_usd".to_string(),
+            round_id: n as u128,
+            answer: 4_200_000_000_000,
+            updated_at: (arrival_ns / NANOS_PER_SEC) as u64,
+            answered_in_round: n as u128,
+            started_at: (arrival_ns / NANOS_PER_SEC) as u64,
+            ingest_arrival_time_ns: arrival_ns as u64,
+            ingest_seq: n,
+            decimals: 8,
+            asset_symbol: "BTC".to_string(),
+            raw_source_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_storage_sources_stream_full_range() {
+        let span = 60 * NANOS_PER_SEC;
+        let end = T0 + 10 * span;
+
+        let l2 = Arc::new(L2Storage::open_memory(SequenceScope::PerMarket, SequenceOrigin::SyntheticFromArrival).unwrap());
+        l2.store_snapshot(&snapshot(0, T0)).unwrap();
+        for seq in 1..=30u64 {
+            // Includes a delta exactly on a window boundary
+            assert!(l2.store_delta(&delta(seq, T0 + seq as Nanos * 20 * NANOS_PER_SEC)).unwrap());
+        }
+
+        let trades = Arc::new(TradePrintFullStorage::open_memory().unwrap());
+        for i in 0..12 {
+            let mut print = PolymarketTradePrint::new(
+                "market1".to_string(),
+                "token1".to_string(),
+                Side::Sell,
+                0.50,
+                5.0 + i as f64,
+                T0 + i * 45 * NANOS_PER_SEC,
+            );
+            print.trade_id = print.compute_hash_trade_id();
+            assert!(trades.store(&mut print).unwrap());
+        }
+
+        let oracle = Arc::new(OracleRoundStorage::open_memory().unwrap());
+        let rounds: Vec<_> = (1..=9).map(|n| round(n, T0 + n as Nanos * span)).collect();
+        oracle.store_rounds(&rounds).unwrap();
+
+        // Reference: whole range loaded at once
+        let mut reference = Vec::new();
+        reference.extend(drain(&mut L2ReplayFeed::from_storage(&l2, "token1", T0, end).unwrap()));
+        let market = StreamSource::MarketData as u8;
+        for p in trades.load_by_visible_ts("market1", T0, end - 1).unwrap() {
+            reference.push(p.to_timestamped_event(market));
+        }
+        for r in oracle.load_rounds_by_arrival("btc_usd", T0 as u64, (end - 1) as u64).unwrap() {
+            reference.push(r.to_timestamped_event(market));
+        }
+        reference.sort();
+
+        let mut feed = StreamingMergeFeed::new(
+            "recorded",
+            vec![
+                Box::new(L2ChunkSource::new(l2, "token1", T0, end, span)),
+                Box::new(TradePrintChunkSource::new(trades, "market1", T0, end, span)),
+                Box::new(OracleRoundChunkSource::new(oracle, "btc_usd", T0, end, span)),
+            ],
+        );
+        let streamed = drain(&mut feed);
+
+        assert!(feed.error().is_none());
+        assert_eq!(streamed.len(), 1 + 29 + 12 + 9);
+        assert_eq!(keys(&streamed), keys(&reference));
+        for (s, r) in streamed.iter().zip(&reference) {
+            assert_eq!(s.event, r.event);
+        }
+        // One minute of data per source at a time, not the whole range
+        assert!(feed.peak_buffered() < streamed.len() / 2, "peak {}", feed.peak_buffered());
+    }
+
+    struct ScriptedSource {
+        chunks: Vec<Result<Vec<TimestampedEvent>, String>>,
+        index: usize,
+    }
+
+    impl EventChunkSource for ScriptedSource {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn next_chunk(&mut self) -> Result<Vec<TimestampedEvent>, String> {
+            let chunk = self.chunks.get(self.index).cloned().unwrap_or(Ok(Vec::new()));
+            self.index += 1;
+            chunk
+        }
+
+        fn reset(&mut self) {
+            self.index = 0;
+        }
+    }
+
+    #[test]
+    fn test_source_error_ends_feed() {
+        let market = StreamSource::MarketData as u8;
+        let mut feed = StreamingMergeFeed::new(
+            "failing",
+            vec![Box::new(ScriptedSource {
+                chunks: vec![
+                    Ok(vec![event(T0, 1, market, true)]),
+                    Err("disk I/O error".to_string()),
+                ],
+                index: 0,
+            })],
+        );
+
+        assert!(feed.next_event().is_some());
+        assert!(feed.next_event().is_none());
+        assert_eq!(feed.error(), Some("disk I/O error"));
+        assert_eq!(feed.peek_time(), None);
+    }
+
+    #[test]
+    fn test_backwards_chunk_is_rejected() {
+        let market = StreamSource::MarketData as u8;
+        let mut feed = StreamingMergeFeed::new(
+            "backwards",
+            vec![Box::new(ScriptedSource {
+                chunks: vec![
+                    Ok(vec![event(T0 + 100, 1, market, true), event(T0 + 200, 2, market, true)]),
+                    Ok(vec![event(T0 + 150, 3, market, true)]),
+                ],
+                index: 0,
+            })],
+        );
+
+        let emitted = drain(&mut feed);
+        assert_eq!(emitted.len(), 2);
+        assert!(feed.error().unwrap().contains("precedes last emitted event"));
+
+        // Reset clears the error and replays from the start
+        feed.reset();
+        assert!(feed.error().is_none());
+        assert_eq!(feed.peek_time(), Some(T0 + 100));
+    }
+
+    #[test]
+    fn test_time_windows_cover_range_once() {
+        let mut windows = TimeWindows::new(0, 25, 10);
+        let all: Vec<_> = std::iter::from_fn(|| windows.next_window()).collect();
+        assert_eq!(all, vec![(0, 10), (10, 20), (20, 25)]);
+
+        windows.reset();
+        assert_eq!(windows.next_window(), Some((0, 10)));
+        assert_eq!(TimeWindows::new(5, 5, 10).next_window(), None);
+    }
+}
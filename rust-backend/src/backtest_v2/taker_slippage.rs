@@ -39,6 +39,7 @@
 use crate::backtest_v2::event_time::VisibleNanos;
 use crate::backtest_v2::events::{OrderId, Price, Side, Size, TimeInForce};
 use crate::backtest_v2::fees_15m;
+use crate::backtest_v2::market_impact::{shift_tick, MarketImpact, MarketImpactConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -97,6 +98,9 @@ pub struct TakerSlippageConfig {
     /// - Uses the official Polymarket price-dependent fee table after January 6, 2025
     #[serde(default)]
     pub use_15m_fee_schedule: bool,
+    /// Market impact / liquidity replenishment model (default: none).
+    #[serde(default)]
+    pub impact: MarketImpactConfig,
 }
 
 impl Default for TakerSlippageConfig {
@@ -110,6 +114,7 @@ impl Default for TakerSlippageConfig {
             reject_on_empty_book: false,
             is_snapshot_only: false,
             use_15m_fee_schedule: false, // Default to old behavior for backwards compatibility
+            impact: MarketImpactConfig::none(),
         }
     }
 }
@@ -142,6 +147,7 @@ impl TakerSlippageConfig {
             reject_on_empty_book: false,
             is_snapshot_only: false,
             use_15m_fee_schedule: false,
+            impact: MarketImpactConfig::none(),
         }
     }
 
@@ -157,6 +163,7 @@ impl TakerSlippageConfig {
             reject_on_empty_book: false,
             is_snapshot_only: false,
             use_15m_fee_schedule: true,
+            impact: MarketImpactConfig::none(),
         }
     }
 }
@@ -205,7 +212,11 @@ impl SimulatedPriceLevel {
 ///
 /// This book tracks aggregate depth at each price level and supports
 /// consuming liquidity during taker order execution.
-#[derive(Debug, Clone)]
+///
+/// With a market impact model configured, depth we consumed is subtracted
+/// from subsequent recorded updates until it refills, and net aggression
+/// shifts the whole book (see `market_impact`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulatedL2Book {
     /// Token/market identifier.
     pub token_id: String,
@@ -213,6 +224,12 @@ pub struct SimulatedL2Book {
     bids: BTreeMap<PriceTick, SimulatedPriceLevel>,
     /// Asks: keyed by tick, best ask = lowest tick.
     asks: BTreeMap<PriceTick, SimulatedPriceLevel>,
+    /// Recorded bid depth (after permanent shift, before depletion).
+    displayed_bids: BTreeMap<PriceTick, Size>,
+    /// Recorded ask depth (after permanent shift, before depletion).
+    displayed_asks: BTreeMap<PriceTick, Size>,
+    /// Impact of our own fills.
+    impact: MarketImpact,
     /// Last sequence number (for delta tracking).
     pub last_seq: u64,
     /// Last update visible timestamp.
@@ -228,6 +245,9 @@ impl SimulatedL2Book {
             token_id: token_id.into(),
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
+            displayed_bids: BTreeMap::new(),
+            displayed_asks: BTreeMap::new(),
+            impact: MarketImpact::new(config.impact.clone()),
             last_seq: 0,
             last_update_ts: VisibleNanos(0),
             config,
@@ -242,23 +262,36 @@ impl SimulatedL2Book {
         seq: u64,
         visible_ts: VisibleNanos,
     ) {
+        self.roll_impact_window(visible_ts);
         self.bids.clear();
         self.asks.clear();
+        self.displayed_bids.clear();
+        self.displayed_asks.clear();
 
+        let shift = self.impact.shift_ticks();
         for &(price, size) in bids {
             if size > 0.0 && price >= MIN_PRICE && price <= MAX_PRICE {
-                let tick = price_to_tick(price, self.config.tick_size);
-                self.bids.insert(tick, SimulatedPriceLevel::new(size));
+                let tick = shift_tick(price_to_tick(price, self.config.tick_size), shift);
+                *self.displayed_bids.entry(tick).or_insert(0.0) += size;
             }
         }
 
         for &(price, size) in asks {
             if size > 0.0 && price >= MIN_PRICE && price <= MAX_PRICE {
-                let tick = price_to_tick(price, self.config.tick_size);
-                self.asks.insert(tick, SimulatedPriceLevel::new(size));
+                let tick = shift_tick(price_to_tick(price, self.config.tick_size), shift);
+                *self.displayed_asks.entry(tick).or_insert(0.0) += size;
             }
         }
 
+        let bid_ticks: Vec<PriceTick> = self.displayed_bids.keys().copied().collect();
+        for tick in bid_ticks {
+            self.restore_level(Side::Buy, tick, visible_ts);
+        }
+        let ask_ticks: Vec<PriceTick> = self.displayed_asks.keys().copied().collect();
+        for tick in ask_ticks {
+            self.restore_level(Side::Sell, tick, visible_ts);
+        }
+
         self.last_seq = seq;
         self.last_update_ts = visible_ts;
     }
@@ -272,22 +305,104 @@ impl SimulatedL2Book {
         seq: u64,
         visible_ts: VisibleNanos,
     ) {
-        let tick = price_to_tick(price, self.config.tick_size);
-        let levels = match side {
-            Side::Buy => &mut self.bids,
-            Side::Sell => &mut self.asks,
+        self.roll_impact_window(visible_ts);
+        let tick = shift_tick(
+            price_to_tick(price, self.config.tick_size),
+            self.impact.shift_ticks(),
+        );
+        let displayed = match side {
+            Side::Buy => &mut self.displayed_bids,
+            Side::Sell => &mut self.displayed_asks,
         };
 
         if new_size <= 0.0 {
-            levels.remove(&tick);
+            displayed.remove(&tick);
         } else {
-            levels.insert(tick, SimulatedPriceLevel::new(new_size));
+            displayed.insert(tick, new_size);
         }
+        self.restore_level(side, tick, visible_ts);
 
         self.last_seq = seq;
         self.last_update_ts = visible_ts;
     }
 
+    /// Let consumed depth refill up to `now` and roll impact state into the
+    /// current 15M window. No-op without a market impact model.
+    pub fn refresh(&mut self, now: VisibleNanos) {
+        self.roll_impact_window(now);
+        for (side, tick) in self.impact.depleted_levels() {
+            self.restore_level(side, tick, now);
+        }
+        self.impact.prune(now.0);
+    }
+
+    /// Market impact state of this book.
+    pub fn impact(&self) -> &MarketImpact {
+        &self.impact
+    }
+
+    /// Configuration the book was created with.
+    pub fn config(&self) -> &TakerSlippageConfig {
+        &self.config
+    }
+
+    /// Record filled aggressor volume and apply any permanent book shift.
+    pub fn apply_aggression(&mut self, aggressor: Side, size: Size) {
+        let delta = self.impact.record_aggression(aggressor, size);
+        if delta != 0 {
+            self.shift_levels(delta);
+        }
+    }
+
+    /// Reset impact state when a new 15M window starts.
+    fn roll_impact_window(&mut self, now: VisibleNanos) {
+        if let Some(reset) = self.impact.roll_window(now.0) {
+            if reset.undo_shift_ticks != 0 {
+                self.shift_levels(reset.undo_shift_ticks);
+            }
+            for (side, tick) in reset.refilled {
+                self.restore_level(side, tick, now);
+            }
+        }
+    }
+
+    /// Set a level to recorded depth minus our outstanding depletion.
+    fn restore_level(&mut self, side: Side, tick: PriceTick, now: VisibleNanos) {
+        let (displayed, levels) = match side {
+            Side::Buy => (&self.displayed_bids, &mut self.bids),
+            Side::Sell => (&self.displayed_asks, &mut self.asks),
+        };
+        let size = displayed.get(&tick).copied().unwrap_or(0.0)
+            - self.impact.outstanding(side, tick, now.0);
+
+        if size <= 0.0 {
+            levels.remove(&tick);
+        } else {
+            levels.insert(tick, SimulatedPriceLevel::new(size));
+        }
+    }
+
+    /// Move every level by `delta` ticks (permanent impact).
+    fn shift_levels(&mut self, delta: i32) {
+        fn shift_map<V>(map: &mut BTreeMap<PriceTick, V>, delta: i32, merge: impl Fn(&mut V, V)) {
+            for (tick, value) in std::mem::take(map) {
+                let shifted = shift_tick(tick, delta);
+                match map.get_mut(&shifted) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        map.insert(shifted, value);
+                    }
+                }
+            }
+        }
+
+        let merge_level = |a: &mut SimulatedPriceLevel, b: SimulatedPriceLevel| a.size += b.size;
+        shift_map(&mut self.bids, delta, merge_level);
+        shift_map(&mut self.asks, delta, merge_level);
+        shift_map(&mut self.displayed_bids, delta, |a, b| *a += b);
+        shift_map(&mut self.displayed_asks, delta, |a, b| *a += b);
+    }
+
     /// Get best bid (highest bid price).
     pub fn best_bid(&self) -> Option<(Price, Size)> {
         self.bids
@@ -692,6 +807,9 @@ impl TakerFillModel {
             };
         }
 
+        // Let depth consumed by earlier orders refill up to now
+        book.refresh(visible_ts);
+
         // Record arrival state
         let arrival_price = match order.side {
             Side::Buy => book.best_ask().map(|(p, _)| p),
@@ -735,6 +853,11 @@ impl TakerFillModel {
         let total_notional: f64 = fills.iter().map(|f| f.notional).sum();
         let unfilled = order.size - total_filled;
 
+        // Permanent impact of this order
+        if total_filled > 0.0 {
+            book.apply_aggression(order.side, total_filled);
+        }
+
         // Compute VWAP
         let vwap = if total_filled > 0.0 {
             fills.iter().map(|f| f.price * f.size).sum::<f64>() / total_filled
@@ -876,6 +999,7 @@ impl TakerFillModel {
         match order.side {
             Side::Buy => {
                 // Sweep asks from lowest (best) to highest, up to limit
+                let best_tick = book.asks.keys().next().copied().unwrap_or(0);
                 // Collect ticks to process (to avoid borrow issues)
                 let ticks_to_process: Vec<PriceTick> = book
                     .asks
@@ -901,6 +1025,13 @@ impl TakerFillModel {
 
                         fills.push(LevelFill::new(price, fill_size, fee, tick));
                         remaining -= fill_size;
+                        book.impact.record_consumption(
+                            Side::Sell,
+                            tick,
+                            tick.saturating_sub(best_tick),
+                            fill_size,
+                            visible_ts.0,
+                        );
                     }
 
                     // Remove empty levels
@@ -911,6 +1042,7 @@ impl TakerFillModel {
             }
             Side::Sell => {
                 // Sweep bids from highest (best) to lowest, down to limit
+                let best_tick = book.bids.keys().next_back().copied().unwrap_or(0);
                 let ticks_to_process: Vec<PriceTick> = book
                     .bids
                     .iter()
@@ -936,6 +1068,13 @@ impl TakerFillModel {
 
                         fills.push(LevelFill::new(price, fill_size, fee, tick));
                         remaining -= fill_size;
+                        book.impact.record_consumption(
+                            Side::Buy,
+                            tick,
+                            best_tick.saturating_sub(tick),
+                            fill_size,
+                            visible_ts.0,
+                        );
                     }
 
                     // Remove empty levels
@@ -1385,4 +1524,97 @@ mod tests {
         let (best_ask, _) = book.best_ask().unwrap();
         assert!((best_ask - 0.56).abs() < 1e-9);
     }
+
+    fn make_impact_book(impact: MarketImpactConfig) -> SimulatedL2Book {
+        let config = TakerSlippageConfig {
+            impact,
+            ..Default::default()
+        };
+        let mut book = SimulatedL2Book::new("test-token", config);
+        book.apply_snapshot(
+            &[(0.45, 100.0), (0.44, 200.0), (0.43, 300.0)],
+            &[(0.55, 100.0), (0.56, 200.0), (0.57, 300.0)],
+            1,
+            VisibleNanos(1000),
+        );
+        book
+    }
+
+    #[test]
+    fn test_impact_repeated_aggression_worsens_fills() {
+        let sec = crate::backtest_v2::clock::NANOS_PER_SEC;
+        let run = |impact: MarketImpactConfig| -> (f64, f64) {
+            let mut book = make_impact_book(impact);
+            let mut model = TakerFillModel::new(book.config.clone());
+            let order = TakerOrderRequest::ioc(
+                1,
+                "order1",
+                "test-token",
+                Side::Buy,
+                0.60,
+                150.0,
+                "trader1",
+            );
+
+            let first = model.execute(&order, &mut book, VisibleNanos(1000));
+            // The recorded book one second later still shows full depth.
+            book.apply_snapshot(
+                &[(0.45, 100.0), (0.44, 200.0), (0.43, 300.0)],
+                &[(0.55, 100.0), (0.56, 200.0), (0.57, 300.0)],
+                2,
+                VisibleNanos(1000 + sec),
+            );
+            let second = model.execute(&order, &mut book, VisibleNanos(1000 + sec));
+            (first.metrics.vwap, second.metrics.vwap)
+        };
+
+        // Legacy: the snapshot heals the book, both orders fill identically.
+        let (first, second) = run(MarketImpactConfig::none());
+        assert!((first - second).abs() < 1e-9);
+
+        // Half-life refill: most of the consumed depth is still missing.
+        let (first, second) = run(MarketImpactConfig::half_life(60 * sec, 0.0));
+        assert!(second > first + 0.005);
+    }
+
+    #[test]
+    fn test_impact_refills_and_resets_at_window_boundary() {
+        let sec = crate::backtest_v2::clock::NANOS_PER_SEC;
+        let mut book = make_impact_book(MarketImpactConfig::half_life(10 * sec, 0.0));
+        let mut model = TakerFillModel::new(book.config.clone());
+        let order =
+            TakerOrderRequest::ioc(1, "order1", "test-token", Side::Buy, 0.55, 100.0, "trader1");
+        model.execute(&order, &mut book, VisibleNanos(1000));
+        assert!((book.best_ask().unwrap().0 - 0.56).abs() < 1e-9);
+
+        // One half-life later half the touch is back.
+        book.refresh(VisibleNanos(1000 + 10 * sec));
+        let (best_ask, size) = book.best_ask().unwrap();
+        assert!((best_ask - 0.55).abs() < 1e-9);
+        assert!((size - 50.0).abs() < 1e-6);
+
+        // Next 15M window: fully restored.
+        let next_window = crate::backtest_v2::time_windows::WINDOW_DURATION_NS;
+        book.refresh(VisibleNanos(next_window));
+        assert!((book.best_ask().unwrap().1 - 100.0).abs() < 1e-9);
+        assert!(book.impact().depleted_levels().is_empty());
+    }
+
+    #[test]
+    fn test_permanent_impact_shifts_book() {
+        let sec = crate::backtest_v2::clock::NANOS_PER_SEC;
+        let mut book = make_impact_book(MarketImpactConfig::half_life(60 * sec, 0.01));
+        let mut model = TakerFillModel::new(book.config.clone());
+        let order =
+            TakerOrderRequest::ioc(1, "order1", "test-token", Side::Buy, 0.60, 200.0, "trader1");
+        model.execute(&order, &mut book, VisibleNanos(1000));
+
+        // 200 shares bought at 0.01 ticks/share: book moves up 2 ticks.
+        assert_eq!(book.impact().shift_ticks(), 2);
+        assert!((book.best_bid().unwrap().0 - 0.47).abs() < 1e-9);
+
+        // Recorded updates are shifted too.
+        book.apply_delta(Side::Buy, 0.46, 50.0, 2, VisibleNanos(2000));
+        assert!((book.best_bid().unwrap().0 - 0.48).abs() < 1e-9);
+    }
 }
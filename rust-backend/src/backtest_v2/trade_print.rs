@@ -42,7 +42,7 @@
 use crate::backtest_v2::event_time::{
     EventTime, FeedEvent, FeedEventPayload, FeedEventPriority, FeedSource, VisibleNanos,
 };
-use crate::backtest_v2::events::Side;
+use crate::backtest_v2::events::{Event, Side, TimestampedEvent};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -382,6 +382,23 @@ impl PolymarketTradePrint {
         )
     }
 
+    /// Convert to a backtest trade print event timed at visibility.
+    pub fn to_timestamped_event(&self, source: u8) -> TimestampedEvent {
+        TimestampedEvent {
+            time: self.visible_ts_ns,
+            source_time: self.exchange_ts_ns.unwrap_or(self.ingest_ts_ns),
+            seq: self.synthetic_trade_seq,
+            source,
+            event: Event::TradePrint {
+                token_id: self.token_id.clone(),
+                price: self.price,
+                size: self.size,
+                aggressor_side: self.aggressor_side,
+                trade_id: Some(self.trade_id.clone()),
+            },
+        }
+    }
+
     /// Convert to FeedEvent for unified queue.
     pub fn to_feed_event(&self, dataset_seq: u64) -> FeedEvent {
         FeedEvent::new(
@@ -15,6 +15,8 @@
 //! 5. A reproducible RunFingerprint is present and complete
 //! 6. production_grade == true
 //! 7. DatasetReadiness allows the claimed strategy type (Maker/Taker)
+//! 8. Strategy parameters were not optimized on the evaluated data
+//!    (see `walk_forward::SampleScope`)
 //!
 //! If ANY condition is false, the run MUST be labeled Untrusted.
 //!
@@ -45,6 +47,7 @@ use crate::backtest_v2::fingerprint::RunFingerprint;
 use crate::backtest_v2::gate_suite::{GateSuiteReport, TrustLevel as GateTrustLevel};
 use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestResults, MakerFillModel};
 use crate::backtest_v2::sensitivity::{SensitivityReport, TrustRecommendation};
+use crate::backtest_v2::walk_forward::SampleScope;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -206,6 +209,12 @@ pub enum TrustFailureReason {
         /// Sample of unknown tokens (first 3).
         sample_tokens: Vec<String>,
     },
+
+    /// Strategy parameters were optimized on the same data the run was evaluated on.
+    InSampleOnly {
+        /// Sweep or walk-forward group that selected the parameters.
+        group_id: String,
+    },
 }
 
 impl TrustFailureReason {
@@ -238,6 +247,7 @@ impl TrustFailureReason {
             Self::MissingMarketRegistry { .. } => "MISSING_MARKET_REGISTRY",
             Self::InvalidMarketRegistry { .. } => "INVALID_MARKET_REGISTRY",
             Self::DatasetRegistryIncompatible { .. } => "DATASET_REGISTRY_INCOMPATIBLE",
+            Self::InSampleOnly { .. } => "IN_SAMPLE_ONLY",
         }
     }
 
@@ -364,6 +374,12 @@ impl TrustFailureReason {
                     sample_tokens.join(", ")
                 )
             }
+            Self::InSampleOnly { group_id } => {
+                format!(
+                    "Parameters were optimized on the evaluated data (group {}). Only out-of-sample walk-forward results can be trusted.",
+                    group_id
+                )
+            }
         }
     }
 }
@@ -573,6 +589,9 @@ impl TrustGate {
         // Check 11: Maker fills must be valid (if maker fills occurred)
         Self::check_maker_fills(results, &mut reasons);
 
+        // Check 12: Parameters must not have been optimized on the evaluated data
+        Self::check_sample_scope(config, &mut reasons);
+
         // Final decision
         if reasons.is_empty() {
             TrustDecision::Trusted
@@ -901,6 +920,14 @@ impl TrustGate {
             });
         }
     }
+
+    fn check_sample_scope(config: &BacktestConfig, reasons: &mut Vec<TrustFailureReason>) {
+        if let SampleScope::InSample { group_id } = &config.sample_scope {
+            reasons.push(TrustFailureReason::InSampleOnly {
+                group_id: group_id.clone(),
+            });
+        }
+    }
 }
 
 // =============================================================================
@@ -1076,16 +1103,21 @@ mod tests {
                 oracle_config_hash: None,
                 latency_model: "Fixed".to_string(),
                 order_latency_ns: Some(1_000_000),
+                latency_config_hash: 0,
                 oms_parity_mode: "Full".to_string(),
                 maker_fill_model: "ExplicitQueue".to_string(),
                 integrity_policy: "Strict".to_string(),
                 invariant_mode: "Hard".to_string(),
                 fee_rate_bps: Some(10),
                 strategy_params_hash: 12345,
+                risk_limits_hash: None,
+                market_impact_hash: None,
+                monte_carlo_hash: 0,
                 arrival_policy: "RecordedArrival".to_string(),
                 strict_accounting: true,
                 production_grade: true,
                 allow_non_production: false,
+                sample_scope: "Undeclared".to_string(),
                 hash: 67890,
             },
             dataset: DatasetFingerprint {
@@ -1332,6 +1364,7 @@ mod tests {
             TrustFailureReason::SettlementModelNotExact { actual_model: "".to_string() },
             TrustFailureReason::OmsParityModeNotFull { actual_mode: "".to_string() },
             TrustFailureReason::MakerFillsInvalid { reason: "".to_string() },
+            TrustFailureReason::InSampleOnly { group_id: "".to_string() },
         ];
 
         let mut codes: Vec<&str> = reasons.iter().map(|r| r.code()).collect();
@@ -1382,4 +1415,44 @@ mod tests {
         non_prod.production_grade = false;
         assert!(!TrustGate::quick_check(&non_prod));
     }
+
+    #[test]
+    fn test_in_sample_parameters_are_untrusted() {
+        let mut config = make_production_config();
+        config.sample_scope = SampleScope::InSample {
+            group_id: "sweep_0000000000000001".to_string(),
+        };
+        let results = make_production_results();
+        let gate_report = make_passing_gate_suite_report();
+        let sensitivity_report = make_passing_sensitivity_report();
+        let fingerprint = make_valid_fingerprint();
+
+        let decision = TrustGate::evaluate(
+            &config,
+            &results,
+            Some(&gate_report),
+            Some(&sensitivity_report),
+            Some(&fingerprint),
+        );
+        assert!(!decision.is_trusted());
+        assert_eq!(decision.failure_count(), 1);
+        assert!(matches!(
+            &decision.failure_reasons()[0],
+            TrustFailureReason::InSampleOnly { group_id } if group_id == "sweep_0000000000000001"
+        ));
+
+        // Out-of-sample walk-forward slices are not penalized
+        config.sample_scope = SampleScope::OutOfSample {
+            group_id: "wf_0000000000000001".to_string(),
+            fold: 0,
+        };
+        let decision = TrustGate::evaluate(
+            &config,
+            &results,
+            Some(&gate_report),
+            Some(&sensitivity_report),
+            Some(&fingerprint),
+        );
+        assert!(decision.is_trusted(), "Expected Trusted, got {:?}", decision);
+    }
 }
@@ -179,7 +179,7 @@ impl ArrivalTimeMapper {
 }
 
 /// Tracks the visibility watermark - what data is visible at the current decision_time.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VisibilityWatermark {
     /// Current decision time - events with arrival_time > this are NOT visible.
     decision_time: Nanos,
@@ -357,7 +357,7 @@ impl DecisionProof {
 }
 
 /// Ring buffer for storing recent DecisionProofs.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DecisionProofBuffer {
     buffer: VecDeque<DecisionProof>,
     capacity: usize,
@@ -0,0 +1,728 @@
+//! Walk-Forward Analysis
+//!
+//! Rolling train/test evaluation of strategy parameters.
+//!
+//! A parameter sweep scores every point on the same data it was chosen from, so
+//! its best point is an in-sample result and overstates live performance. This
+//! module slices a dataset time range into consecutive folds, optimizes
+//! parameters on each fold's train slice, and evaluates the winner on the
+//! *following* test slice, which the optimizer never saw.
+//!
+//! # Fold Layout
+//!
+//! All boundaries are aligned to the 15-minute windows of `time_windows`, so a
+//! slice never cuts a market window in half.
+//!
+//! ```text
+//! windows:  |  0  |  1  |  2  |  3  |  4  |  5  |  6  |
+//! fold 0:   [ train  ][test]
+//! fold 1:         [ train  ][test]
+//! fold 2:               [ train  ][test]
+//! fold 3:                     [ train  ][test]
+//!
+//! (train_windows = 2, test_windows = 1, step_windows = 1, rolling)
+//! ```
+//!
+//! With `anchored = true`, every train slice starts at the first window
+//! (expanding train set).
+//!
+//! # Outputs
+//!
+//! - One `ParamSweepReport` per fold (train slice, tagged `{wf_id}_f{n}_train`).
+//! - One out-of-sample run per fold, marked `SampleScope::OutOfSample`.
+//! - A single out-of-sample `EquityCurve` stitched from the test runs.
+//! - A `RunGroupKind::WalkForward` group in the `ArtifactStore` linking, per
+//!   fold, the chosen train run (`wf_train`) and its test run (`wf_test`).
+//!
+//! # Trust
+//!
+//! `BacktestConfig::sample_scope` records how a run's parameters were chosen.
+//! `TrustGate` refuses to certify `SampleScope::InSample` runs, so only the
+//! out-of-sample test runs of a walk-forward can ever be Trusted.
+
+use crate::backtest_v2::artifact_store::{ArtifactStore, RunGroupKind, RunGroupMember};
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::equity_curve::EquityCurve;
+use crate::backtest_v2::events::TimestampedEvent;
+use crate::backtest_v2::feed::VecFeed;
+use crate::backtest_v2::gate_suite::TrustLevel;
+use crate::backtest_v2::ledger::to_amount;
+use crate::backtest_v2::orchestrator::{BacktestConfig, BacktestOrchestrator, BacktestResults};
+use crate::backtest_v2::param_sweep::{
+    persist_run, ParamSweepConfig, ParamSweepReport, ParamSweepRunner, SweepId, SweepRunOutcome,
+};
+use crate::backtest_v2::run_artifact::RunId;
+use crate::backtest_v2::strategy_factory::make_strategy;
+use crate::backtest_v2::time_windows::{window_index, window_start_from_index, WINDOW_DURATION_NS};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// Role label of the chosen train-slice run of a fold.
+pub const WF_TRAIN_ROLE: &str = "wf_train";
+
+/// Role label of the out-of-sample test-slice run of a fold.
+pub const WF_TEST_ROLE: &str = "wf_test";
+
+// =============================================================================
+// SAMPLE SCOPE
+// =============================================================================
+
+/// How a run's strategy parameters relate to the data it was evaluated on.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SampleScope {
+    /// No provenance declared (hand-configured parameters).
+    #[default]
+    Undeclared,
+    /// Parameters were selected by optimizing on this same data
+    /// (sweep points, walk-forward train slices). Never trusted.
+    InSample {
+        /// Sweep group the run belongs to.
+        group_id: String,
+    },
+    /// Parameters were selected on earlier data and evaluated on this unseen slice.
+    OutOfSample {
+        /// Walk-forward group the run belongs to.
+        group_id: String,
+        /// Fold index within the walk-forward.
+        fold: u32,
+    },
+}
+
+impl SampleScope {
+    /// Whether the parameters were optimized on the evaluated data.
+    pub fn is_in_sample(&self) -> bool {
+        matches!(self, Self::InSample { .. })
+    }
+}
+
+// =============================================================================
+// WALK-FORWARD DEFINITION
+// =============================================================================
+
+/// Metric used to pick the winning sweep point on a train slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WalkForwardObjective {
+    /// Highest final PnL.
+    FinalPnl,
+    /// Highest Sharpe ratio (points without a Sharpe ratio are skipped).
+    Sharpe,
+}
+
+impl WalkForwardObjective {
+    /// Score of a completed sweep point (higher is better).
+    pub fn score(&self, outcome: &SweepRunOutcome) -> Option<f64> {
+        if !outcome.completed() {
+            return None;
+        }
+        match self {
+            Self::FinalPnl => Some(outcome.final_pnl),
+            Self::Sharpe => outcome.sharpe_ratio.filter(|s| s.is_finite()),
+        }
+    }
+
+    /// Best-scoring point of a sweep (ties broken by lowest index).
+    pub fn select<'a>(&self, report: &'a ParamSweepReport) -> Option<(&'a SweepRunOutcome, f64)> {
+        report
+            .outcomes
+            .iter()
+            .filter_map(|o| self.score(o).map(|s| (o, s)))
+            .fold(None, |best, (o, s)| match best {
+                Some((_, bs)) if bs >= s => best,
+                _ => Some((o, s)),
+            })
+    }
+}
+
+/// Complete description of a walk-forward analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// Sweep run on every train slice.
+    pub sweep: ParamSweepConfig,
+    /// Train slice length in 15-minute windows.
+    pub train_windows: u32,
+    /// Test slice length in 15-minute windows.
+    pub test_windows: u32,
+    /// Fold advance in 15-minute windows. Equal to `test_windows` for
+    /// back-to-back, non-overlapping test slices.
+    pub step_windows: u32,
+    /// If true, every train slice starts at the first window (expanding).
+    pub anchored: bool,
+    /// Train-slice selection metric.
+    pub objective: WalkForwardObjective,
+}
+
+impl WalkForwardConfig {
+    /// Rolling walk-forward with back-to-back test slices, selecting by final PnL.
+    pub fn rolling(sweep: ParamSweepConfig, train_windows: u32, test_windows: u32) -> Self {
+        Self {
+            sweep,
+            train_windows,
+            test_windows,
+            step_windows: test_windows,
+            anchored: false,
+            objective: WalkForwardObjective::FinalPnl,
+        }
+    }
+
+    /// Validate the walk-forward definition.
+    pub fn validate(&self) -> Result<(), String> {
+        self.sweep.validate()?;
+        if self.train_windows == 0 {
+            return Err("walk-forward train_windows must be > 0".to_string());
+        }
+        if self.test_windows == 0 {
+            return Err("walk-forward test_windows must be > 0".to_string());
+        }
+        if self.step_windows == 0 {
+            return Err("walk-forward step_windows must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Slice `[start_ns, end_ns)` into folds.
+    ///
+    /// The first fold starts at the first window boundary at or after
+    /// `start_ns`; only windows that end at or before `end_ns` are used.
+    pub fn plan_folds(&self, start_ns: Nanos, end_ns: Nanos) -> Result<Vec<WalkForwardFold>, String> {
+        self.validate()?;
+        if start_ns < 0 || end_ns <= start_ns {
+            return Err(format!("invalid walk-forward range [{}, {})", start_ns, end_ns));
+        }
+
+        let first = window_index(start_ns + WINDOW_DURATION_NS - 1);
+        let last = window_index(end_ns); // exclusive
+        let (train, test, step) = (
+            self.train_windows as i64,
+            self.test_windows as i64,
+            self.step_windows as i64,
+        );
+
+        let mut folds = Vec::new();
+        let mut offset = 0i64;
+        while first + offset + train + test <= last {
+            let train_end = first + offset + train;
+            folds.push(WalkForwardFold {
+                index: folds.len() as u32,
+                train_start: window_start_from_index(if self.anchored { first } else { first + offset }),
+                train_end: window_start_from_index(train_end),
+                test_start: window_start_from_index(train_end),
+                test_end: window_start_from_index(train_end + test),
+            });
+            offset += step;
+        }
+
+        if folds.is_empty() {
+            return Err(format!(
+                "range [{}, {}) holds {} full window(s); need at least {} for one fold",
+                start_ns,
+                end_ns,
+                (last - first).max(0),
+                train + test
+            ));
+        }
+        Ok(folds)
+    }
+
+    /// Deterministic identifier for this walk-forward over `[start_ns, end_ns)`.
+    pub fn walk_forward_id(&self, start_ns: Nanos, end_ns: Nanos) -> WalkForwardId {
+        let mut hasher = DefaultHasher::new();
+        self.sweep.sweep_id().hash(&mut hasher);
+        self.train_windows.hash(&mut hasher);
+        self.test_windows.hash(&mut hasher);
+        self.step_windows.hash(&mut hasher);
+        self.anchored.hash(&mut hasher);
+        self.objective.hash(&mut hasher);
+        start_ns.hash(&mut hasher);
+        end_ns.hash(&mut hasher);
+        WalkForwardId(format!("wf_{:016x}", hasher.finish()))
+    }
+}
+
+/// Shared identifier of every run in one walk-forward.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WalkForwardId(pub String);
+
+impl WalkForwardId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Group id under which fold `fold`'s train sweep is tagged.
+    pub fn train_sweep_id(&self, fold: u32) -> SweepId {
+        SweepId(format!("{}_f{}_train", self.0, fold))
+    }
+}
+
+impl std::fmt::Display for WalkForwardId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// One train/test split. All bounds are window-aligned; ends are exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkForwardFold {
+    pub index: u32,
+    pub train_start: Nanos,
+    pub train_end: Nanos,
+    pub test_start: Nanos,
+    pub test_end: Nanos,
+}
+
+impl WalkForwardFold {
+    /// Events of the train slice.
+    pub fn train_events(&self, events: &[TimestampedEvent]) -> Vec<TimestampedEvent> {
+        slice_events(events, self.train_start, self.train_end)
+    }
+
+    /// Events of the test slice.
+    pub fn test_events(&self, events: &[TimestampedEvent]) -> Vec<TimestampedEvent> {
+        slice_events(events, self.test_start, self.test_end)
+    }
+}
+
+fn slice_events(events: &[TimestampedEvent], start: Nanos, end: Nanos) -> Vec<TimestampedEvent> {
+    events
+        .iter()
+        .filter(|e| e.time >= start && e.time < end)
+        .cloned()
+        .collect()
+}
+
+// =============================================================================
+// WALK-FORWARD RESULTS
+// =============================================================================
+
+/// Outcome of one fold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardFoldOutcome {
+    pub fold: WalkForwardFold,
+    /// Swept parameters chosen on the train slice.
+    pub chosen_params: Option<BTreeMap<String, f64>>,
+    /// Objective value of the chosen point on the train slice (in-sample).
+    pub train_score: Option<f64>,
+    /// Persisted run id of the chosen train point.
+    pub train_run_id: Option<RunId>,
+    /// Persisted run id of the out-of-sample test run.
+    pub test_run_id: Option<RunId>,
+    pub test_final_pnl: f64,
+    pub test_sharpe_ratio: Option<f64>,
+    pub test_max_drawdown: f64,
+    pub test_fills: u64,
+    pub test_trusted: bool,
+    /// Error message if the fold could not be completed.
+    pub error: Option<String>,
+}
+
+impl WalkForwardFoldOutcome {
+    fn new(fold: WalkForwardFold) -> Self {
+        Self {
+            fold,
+            chosen_params: None,
+            train_score: None,
+            train_run_id: None,
+            test_run_id: None,
+            test_final_pnl: 0.0,
+            test_sharpe_ratio: None,
+            test_max_drawdown: 0.0,
+            test_fills: 0,
+            test_trusted: false,
+            error: None,
+        }
+    }
+
+    fn failed(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+/// Report for a whole walk-forward.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    pub walk_forward_id: WalkForwardId,
+    pub strategy_name: String,
+    pub objective: WalkForwardObjective,
+    /// Outcomes ordered by fold index.
+    pub folds: Vec<WalkForwardFoldOutcome>,
+    /// Out-of-sample equity stitched from the test runs: each segment is
+    /// shifted to start at the previous segment's final equity.
+    pub oos_equity_curve: EquityCurve,
+}
+
+impl WalkForwardReport {
+    /// Number of folds that errored.
+    pub fn failed_count(&self) -> usize {
+        self.folds.iter().filter(|f| f.error.is_some()).count()
+    }
+
+    /// Sum of test-slice PnL over all completed folds.
+    pub fn oos_total_pnl(&self) -> f64 {
+        self.folds
+            .iter()
+            .filter(|f| f.error.is_none())
+            .map(|f| f.test_final_pnl)
+            .sum()
+    }
+
+    /// Sum of the chosen points' train-slice objective (in-sample reference).
+    pub fn in_sample_total_score(&self) -> f64 {
+        self.folds.iter().filter_map(|f| f.train_score).sum()
+    }
+
+    /// Format a compact text table of the walk-forward.
+    pub fn format_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "WALK-FORWARD {} — {} — objective {:?}\n",
+            self.walk_forward_id, self.strategy_name, self.objective
+        ));
+        out.push_str(&format!(
+            "{} folds, {} failed, out-of-sample pnl={:.2}\n",
+            self.folds.len(),
+            self.failed_count(),
+            self.oos_total_pnl()
+        ));
+        for f in &self.folds {
+            let params: Vec<String> = f
+                .chosen_params
+                .iter()
+                .flatten()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            match &f.error {
+                Some(err) => out.push_str(&format!("  fold {:<3} ERROR: {}\n", f.fold.index, err)),
+                None => out.push_str(&format!(
+                    "  fold {:<3} {:40} train={:>10.2} test={:>10.2} fills={:<6} {}\n",
+                    f.fold.index,
+                    params.join(" "),
+                    f.train_score.unwrap_or(0.0),
+                    f.test_final_pnl,
+                    f.test_fills,
+                    if f.test_trusted { "TRUSTED" } else { "untrusted" }
+                )),
+            }
+        }
+        out
+    }
+}
+
+/// Append one test run's equity to the stitched out-of-sample curve.
+///
+/// Runs without a ledger-derived curve contribute a single point at the end
+/// of their slice carrying their final PnL.
+fn append_segment(stitched: &mut EquityCurve, results: &BacktestResults, segment_end: Nanos) {
+    let carry = stitched.final_equity();
+    match results.equity_curve.as_ref().filter(|c| !c.is_empty()) {
+        Some(curve) => {
+            let offset = carry.map(|c| c - curve.points()[0].equity_value).unwrap_or(0);
+            for p in curve.points() {
+                stitched.try_record(
+                    p.time_ns,
+                    p.equity_value + offset,
+                    p.cash_balance + offset,
+                    p.position_value,
+                );
+            }
+        }
+        None => {
+            let equity = carry.unwrap_or(0) + to_amount(results.final_pnl);
+            stitched.try_record(segment_end - 1, equity, equity, 0);
+        }
+    }
+}
+
+// =============================================================================
+// WALK-FORWARD RUNNER
+// =============================================================================
+
+/// Runs a walk-forward analysis on top of `ParamSweepRunner`.
+pub struct WalkForwardRunner {
+    config: WalkForwardConfig,
+    base_config: BacktestConfig,
+    sweep_runner: ParamSweepRunner,
+}
+
+impl WalkForwardRunner {
+    /// Create a runner. `base_config` is cloned for every train point and test run.
+    pub fn new(config: WalkForwardConfig, base_config: BacktestConfig) -> Result<Self, String> {
+        config.validate()?;
+        let sweep_runner = ParamSweepRunner::new(config.sweep.clone(), base_config.clone())?;
+        Ok(Self {
+            config,
+            base_config,
+            sweep_runner,
+        })
+    }
+
+    /// Walk-forward definition.
+    pub fn config(&self) -> &WalkForwardConfig {
+        &self.config
+    }
+
+    /// Run every fold over `[start_ns, end_ns)` of `events`.
+    ///
+    /// Folds run sequentially; each train sweep uses the sweep's worker pool.
+    /// A failing fold is reported and skipped; it does not abort later folds.
+    pub fn run(
+        &self,
+        events: &[TimestampedEvent],
+        start_ns: Nanos,
+        end_ns: Nanos,
+        store: Option<&ArtifactStore>,
+    ) -> Result<WalkForwardReport, String> {
+        let folds = self.config.plan_folds(start_ns, end_ns)?;
+        let wf_id = self.config.walk_forward_id(start_ns, end_ns);
+
+        tracing::info!(
+            walk_forward_id = %wf_id,
+            strategy = %self.config.sweep.strategy_name,
+            folds = folds.len(),
+            "Starting walk-forward analysis"
+        );
+
+        let mut oos_equity_curve = EquityCurve::new();
+        let outcomes = folds
+            .into_iter()
+            .map(|fold| self.run_fold(fold, events, store, &wf_id, &mut oos_equity_curve))
+            .collect();
+
+        Ok(WalkForwardReport {
+            walk_forward_id: wf_id,
+            strategy_name: self.config.sweep.strategy_name.clone(),
+            objective: self.config.objective,
+            folds: outcomes,
+            oos_equity_curve,
+        })
+    }
+
+    fn run_fold(
+        &self,
+        fold: WalkForwardFold,
+        events: &[TimestampedEvent],
+        store: Option<&ArtifactStore>,
+        wf_id: &WalkForwardId,
+        oos_equity_curve: &mut EquityCurve,
+    ) -> WalkForwardFoldOutcome {
+        let mut outcome = WalkForwardFoldOutcome::new(fold);
+
+        // Optimize on the train slice
+        let train_report = match self.sweep_runner.run_as_group(
+            &fold.train_events(events),
+            store,
+            wf_id.train_sweep_id(fold.index),
+        ) {
+            Ok(r) => r,
+            Err(e) => return outcome.failed(format!("Train sweep failed: {}", e)),
+        };
+        let Some((chosen, score)) = self.config.objective.select(&train_report) else {
+            return outcome.failed(format!(
+                "No train point produced a {:?} score ({} of {} failed)",
+                self.config.objective,
+                train_report.failed_count(),
+                train_report.outcomes.len()
+            ));
+        };
+        outcome.chosen_params = Some(chosen.point.params.clone());
+        outcome.train_score = Some(score);
+        outcome.train_run_id = chosen.run_id.clone();
+
+        // Evaluate the chosen parameters on the unseen test slice
+        let params = self.sweep_runner.point_params(&chosen.point);
+        let mut config = self.base_config.clone();
+        config.strategy_params = params.clone();
+        config.sample_scope = SampleScope::OutOfSample {
+            group_id: wf_id.0.clone(),
+            fold: fold.index,
+        };
+
+        let mut strategy = match make_strategy(&self.config.sweep.strategy_name, &params) {
+            Ok(s) => s,
+            Err(e) => return outcome.failed(e),
+        };
+        let mut feed = VecFeed::new("walk_forward", fold.test_events(events));
+        let mut orchestrator = BacktestOrchestrator::new(config.clone());
+        if let Err(e) = orchestrator.load_feed(&mut feed) {
+            return outcome.failed(format!("Failed to load test feed: {}", e));
+        }
+        let results = match orchestrator.run(strategy.as_mut()) {
+            Ok(r) => r,
+            Err(e) => return outcome.failed(format!("Test backtest error: {}", e)),
+        };
+
+        outcome.test_final_pnl = results.final_pnl;
+        outcome.test_sharpe_ratio = results.sharpe_ratio;
+        outcome.test_max_drawdown = results.max_drawdown;
+        outcome.test_fills = results.total_fills;
+        outcome.test_trusted = matches!(results.trust_level, TrustLevel::Trusted);
+        append_segment(oos_equity_curve, &results, fold.test_end);
+
+        let Some(store) = store else {
+            return outcome;
+        };
+        let test_run_id = match persist_run(store, results, &config) {
+            Ok(id) => id,
+            Err(e) => return outcome.failed(e),
+        };
+
+        // Link train winner and test run: members 2n and 2n + 1
+        let mut members = vec![];
+        if let Some(train_run_id) = &outcome.train_run_id {
+            members.push(RunGroupMember {
+                group_id: wf_id.0.clone(),
+                kind: RunGroupKind::WalkForward,
+                member_index: fold.index * 2,
+                run_id: train_run_id.clone(),
+                role: WF_TRAIN_ROLE.to_string(),
+                params: chosen.point.params.clone(),
+            });
+        }
+        members.push(RunGroupMember {
+            group_id: wf_id.0.clone(),
+            kind: RunGroupKind::WalkForward,
+            member_index: fold.index * 2 + 1,
+            run_id: test_run_id.clone(),
+            role: WF_TEST_ROLE.to_string(),
+            params: chosen.point.params.clone(),
+        });
+        for member in &members {
+            if let Err(e) = store.tag_run_group(member) {
+                return outcome.failed(format!("Failed to tag walk-forward run: {}", e));
+            }
+        }
+        outcome.test_run_id = Some(test_run_id);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::{Event, Level};
+    use crate::backtest_v2::ledger::from_amount;
+    use crate::backtest_v2::orchestrator::MakerFillModel;
+    use crate::backtest_v2::param_sweep::ParamAxis;
+    use crate::backtest_v2::queue::StreamSource;
+
+    const W: Nanos = WINDOW_DURATION_NS;
+
+    fn make_book_event(time: Nanos, mid: f64) -> TimestampedEvent {
+        TimestampedEvent::new(
+            time,
+            StreamSource::MarketData as u8,
+            Event::L2BookSnapshot {
+                token_id: "TEST".into(),
+                bids: vec![Level::new(mid - 0.02, 100.0)],
+                asks: vec![Level::new(mid + 0.02, 100.0)],
+                exchange_seq: 1,
+            },
+        )
+    }
+
+    fn test_base_config() -> BacktestConfig {
+        let mut config = BacktestConfig::test_config();
+        config.maker_fill_model = MakerFillModel::MakerDisabled;
+        config
+    }
+
+    fn noop_sweep() -> ParamSweepConfig {
+        ParamSweepConfig::grid("noop", vec![ParamAxis::discrete("dummy", vec![1.0, 2.0])])
+    }
+
+    #[test]
+    fn test_rolling_folds_are_window_aligned() {
+        let config = WalkForwardConfig::rolling(noop_sweep(), 2, 1);
+        // Unaligned start is rounded up to the next window boundary
+        let folds = config.plan_folds(W / 3, 6 * W + W / 2).unwrap();
+
+        assert_eq!(folds.len(), 3);
+        assert_eq!(folds[0].train_start, W);
+        assert_eq!(folds[0].train_end, 3 * W);
+        assert_eq!(folds[0].test_start, 3 * W);
+        assert_eq!(folds[0].test_end, 4 * W);
+        assert_eq!(folds[2].train_start, 3 * W);
+        assert_eq!(folds[2].test_end, 6 * W);
+        for pair in folds.windows(2) {
+            assert_eq!(pair[0].test_end, pair[1].test_start);
+        }
+    }
+
+    #[test]
+    fn test_anchored_folds_expand_train_slice() {
+        let config = WalkForwardConfig {
+            anchored: true,
+            ..WalkForwardConfig::rolling(noop_sweep(), 2, 1)
+        };
+        let folds = config.plan_folds(0, 5 * W).unwrap();
+
+        assert_eq!(folds.len(), 3);
+        assert!(folds.iter().all(|f| f.train_start == 0));
+        assert_eq!(folds[2].train_end, 4 * W);
+
+        // Too short for a single fold
+        assert!(config.plan_folds(0, 2 * W + W / 2).is_err());
+        assert!(WalkForwardConfig::rolling(noop_sweep(), 0, 1).validate().is_err());
+    }
+
+    #[test]
+    fn test_segments_stitch_into_continuous_curve() {
+        let mut first = BacktestResults::default();
+        let mut curve = EquityCurve::new();
+        curve.record(10, to_amount(1000.0), to_amount(1000.0), 0);
+        curve.record(20, to_amount(1050.0), to_amount(1050.0), 0);
+        first.equity_curve = Some(curve);
+
+        let mut second = BacktestResults::default();
+        let mut curve = EquityCurve::new();
+        curve.record(30, to_amount(1000.0), to_amount(1000.0), 0);
+        curve.record(40, to_amount(980.0), to_amount(980.0), 0);
+        second.equity_curve = Some(curve);
+
+        let mut stitched = EquityCurve::new();
+        append_segment(&mut stitched, &first, 25);
+        append_segment(&mut stitched, &second, 45);
+
+        let equity: Vec<f64> = stitched.points().iter().map(|p| from_amount(p.equity_value)).collect();
+        assert_eq!(equity, vec![1000.0, 1050.0, 1050.0, 1030.0]);
+        assert!(stitched.verify_monotonicity());
+    }
+
+    #[test]
+    fn test_walk_forward_persists_linked_runs() {
+        let events: Vec<TimestampedEvent> = (0..5)
+            .flat_map(|w| {
+                vec![
+                    make_book_event(w * W + 1_000_000_000, 0.50),
+                    make_book_event(w * W + 60_000_000_000, 0.51),
+                ]
+            })
+            .collect();
+        let config = WalkForwardConfig::rolling(noop_sweep(), 2, 1);
+        let runner = WalkForwardRunner::new(config, test_base_config()).unwrap();
+        let store = ArtifactStore::in_memory().unwrap();
+
+        let report = runner.run(&events, 0, 5 * W, Some(&store)).unwrap();
+        assert_eq!(report.folds.len(), 3);
+        assert_eq!(report.failed_count(), 0, "{}", report.format_text());
+        assert_eq!(report.oos_equity_curve.len(), 3);
+        assert!(report.folds.iter().all(|f| !f.test_trusted));
+
+        // Each fold links its train winner and its test run
+        let members = store.list_run_group(report.walk_forward_id.as_str()).unwrap();
+        assert_eq!(members.len(), 6);
+        assert!(members.iter().all(|m| m.kind == RunGroupKind::WalkForward));
+        assert_eq!(members[0].role, WF_TRAIN_ROLE);
+        assert_eq!(members[1].role, WF_TEST_ROLE);
+        assert_eq!(members[0].params, members[1].params);
+
+        // Each fold's train sweep is tagged separately
+        let train_sweep = store
+            .list_run_group(report.walk_forward_id.train_sweep_id(1).as_str())
+            .unwrap();
+        assert_eq!(train_sweep.len(), 2);
+        assert!(train_sweep.iter().all(|m| m.kind == RunGroupKind::ParamSweep));
+    }
+}
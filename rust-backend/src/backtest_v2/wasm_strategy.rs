@@ -0,0 +1,948 @@
+//! Sandboxed WASM Strategies
+//!
+//! Loads a strategy from a WebAssembly module at runtime so research strategies
+//! can be iterated on without recompiling the backend. Modules run inside the
+//! `wasmi` interpreter and can only reach the outside world through the small
+//! `betterbot.*` host API below.
+//!
+//! # Hermetic Guarantees
+//!
+//! The sandbox preserves the guarantees of `hermetic.rs` by construction:
+//! - The ONLY imports a module may declare are the `betterbot.*` functions listed
+//!   below. WASI or any other import is rejected at load time, so there is no
+//!   filesystem, network, environment, or wall-clock access.
+//! - `betterbot.now` returns the simulated `StrategyContext::timestamp`.
+//! - Every callback runs with a fixed fuel budget; runaway loops trap
+//!   deterministically instead of depending on wall-clock timeouts.
+//! - Linear memory is capped by `WasmSandboxLimits::max_memory_bytes`.
+//!
+//! A module that traps (out of fuel, unreachable, out-of-bounds access) is
+//! marked faulted and receives no further callbacks, so it cannot emit orders
+//! after a failure.
+//!
+//! # Provenance
+//!
+//! The SHA-256 of the module bytes is used as the `StrategyId` code hash, so
+//! any change to the module changes the run fingerprint.
+//!
+//! # Guest ABI
+//!
+//! Exports (all optional):
+//! ```text
+//! on_start()
+//! on_stop()
+//! on_book(token: i32, bid_px: f64, bid_sz: f64, ask_px: f64, ask_sz: f64, ts: i64)
+//! on_trade(token: i32, price: f64, size: f64, aggressor: i32, ts: i64)
+//! on_timer(timer: i64, ts: i64)
+//! on_order_ack(order: i64, ts: i64)
+//! on_order_reject(order: i64, ts: i64)
+//! on_fill(order: i64, price: f64, size: f64, is_maker: i32, leaves: f64, fee: f64, ts: i64)
+//! on_cancel_ack(order: i64, cancelled: f64, ts: i64)
+//! memory                      (required only if `param` is used)
+//! ```
+//!
+//! Imports (module `betterbot`):
+//! ```text
+//! now() -> i64
+//! param(key_ptr: i32, key_len: i32, default: f64) -> f64
+//! position(token: i32) -> f64
+//! place_order(token: i32, side: i32, price: f64, size: f64, tif: i32, post_only: i32) -> i64
+//! cancel_order(order: i64) -> i32
+//! cancel_all(token: i32) -> i32
+//! schedule_timer(delay_ns: i64) -> i64
+//! ```
+//!
+//! Tokens are small integer handles assigned in the order the strategy first
+//! sees them. Sides are `0 = buy`, `1 = sell`; time-in-force is `0 = GTC`,
+//! `1 = IOC`, `2 = FOK`. A missing side of the book is passed as price and
+//! size `0.0`. Orders, cancels and timers are handles allocated by the host;
+//! the actions themselves are applied in call order when the callback returns.
+
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::events::{OrderId, Side, TimeInForce};
+use crate::backtest_v2::fingerprint::StrategyId;
+use crate::backtest_v2::hermetic::HermeticStrategy;
+use crate::backtest_v2::strategy::{
+    BookSnapshot, CancelAck, FillNotification, OrderAck, OrderReject, Strategy, StrategyCancel,
+    StrategyContext, StrategyFactory, StrategyOrder, StrategyParams, TimerEvent, TradePrint,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use wasmi::{
+    Caller, Config, Engine, Extern, ExternType, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc,
+};
+
+/// Import namespace of the host API.
+const HOST_MODULE: &str = "betterbot";
+
+/// Host functions a module may import.
+const HOST_FUNCTIONS: &[&str] = &[
+    "now",
+    "param",
+    "position",
+    "place_order",
+    "cancel_order",
+    "cancel_all",
+    "schedule_timer",
+];
+
+/// Synchronous rejects are fed back via `on_order_reject`, which may place more
+/// orders; bound that loop so a module cannot spin forever inside one event.
+const MAX_REJECT_ROUNDS: usize = 4;
+
+// =============================================================================
+// SANDBOX LIMITS
+// =============================================================================
+
+/// Resource limits applied to every module instance.
+#[derive(Debug, Clone)]
+pub struct WasmSandboxLimits {
+    /// Fuel (roughly, instructions) available to each callback.
+    pub fuel_per_callback: u64,
+    /// Maximum linear memory size.
+    pub max_memory_bytes: usize,
+    /// Maximum actions (orders, cancels, timers) a single callback may queue.
+    pub max_actions_per_callback: usize,
+}
+
+impl Default for WasmSandboxLimits {
+    fn default() -> Self {
+        Self {
+            fuel_per_callback: 10_000_000,
+            max_memory_bytes: 16 * 1024 * 1024,
+            max_actions_per_callback: 256,
+        }
+    }
+}
+
+// =============================================================================
+// FACTORY
+// =============================================================================
+
+/// Strategy factory backed by a compiled WASM module.
+///
+/// Validation (imports, exports, signatures, start function) happens once at
+/// load time; `create` instantiates a fresh, isolated store per strategy.
+pub struct WasmStrategyFactory {
+    name: String,
+    version: String,
+    code_hash: String,
+    engine: Engine,
+    module: Module,
+    limits: WasmSandboxLimits,
+}
+
+impl WasmStrategyFactory {
+    /// Load a module from raw bytes (binary `.wasm`).
+    pub fn from_bytes(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        bytes: &[u8],
+    ) -> Result<Self, String> {
+        Self::with_limits(name, version, bytes, WasmSandboxLimits::default())
+    }
+
+    /// Load a module from a `.wasm` file. The strategy name is the file stem.
+    pub fn from_file(path: impl AsRef<Path>, version: impl Into<String>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("Failed to read WASM module {}: {}", path.display(), e))?;
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| format!("Invalid WASM module path: {}", path.display()))?;
+        Self::from_bytes(name, version, &bytes)
+    }
+
+    /// Load a module with explicit sandbox limits.
+    pub fn with_limits(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        bytes: &[u8],
+        limits: WasmSandboxLimits,
+    ) -> Result<Self, String> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, bytes).map_err(|e| format!("Invalid WASM module: {}", e))?;
+
+        for import in module.imports() {
+            let allowed = import.module() == HOST_MODULE
+                && HOST_FUNCTIONS.contains(&import.name())
+                && matches!(import.ty(), ExternType::Func(_));
+            if !allowed {
+                return Err(format!(
+                    "WASM module imports '{}.{}': only {}.* host functions are available \
+                     (no WASI, I/O, clocks or shared memory in hermetic strategies)",
+                    import.module(),
+                    import.name(),
+                    HOST_MODULE
+                ));
+            }
+        }
+
+        let factory = Self {
+            name: name.into(),
+            version: version.into(),
+            code_hash: hex::encode(Sha256::digest(bytes)),
+            engine,
+            module,
+            limits,
+        };
+        // Instantiate once so signature mismatches and start-function traps
+        // surface at load time rather than mid-run.
+        factory.try_create(StrategyParams::default())?;
+        Ok(factory)
+    }
+
+    /// SHA-256 of the module bytes (hex).
+    pub fn code_hash(&self) -> &str {
+        &self.code_hash
+    }
+
+    /// Version recorded in the strategy identity.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Strategy identity for `BacktestConfig::strategy_id`, with the module
+    /// hash as the code hash.
+    pub fn strategy_id(&self) -> StrategyId {
+        StrategyId::with_code_hash(&self.name, &self.version, &self.code_hash)
+    }
+
+    /// Instantiate a strategy, returning an error instead of a faulted strategy.
+    pub fn try_create(&self, params: StrategyParams) -> Result<WasmStrategy, String> {
+        WasmStrategy::instantiate(self, params)
+    }
+}
+
+impl StrategyFactory for WasmStrategyFactory {
+    fn create(&self, params: StrategyParams) -> Box<dyn Strategy> {
+        match self.try_create(params) {
+            Ok(strategy) => Box::new(strategy),
+            // Instantiation was validated at load time; if it still fails, hand
+            // back an inert strategy that records the fault and never trades.
+            Err(e) => Box::new(WasmStrategy::faulted(&self.name, e)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// =============================================================================
+// HOST STATE
+// =============================================================================
+
+/// Action queued by the guest during a callback.
+#[derive(Debug, Clone)]
+enum HostAction {
+    Order {
+        handle: i64,
+        token: usize,
+        side: Side,
+        price: f64,
+        size: f64,
+        time_in_force: TimeInForce,
+        post_only: bool,
+    },
+    Cancel {
+        handle: i64,
+    },
+    CancelAll {
+        token: usize,
+    },
+    Timer {
+        handle: i64,
+        delay_ns: Nanos,
+    },
+}
+
+/// Per-instance state visible to host functions.
+struct HostState {
+    limits: StoreLimits,
+    max_actions: usize,
+    now: Nanos,
+    params: StrategyParams,
+    tokens: Vec<String>,
+    /// Position per token handle, snapshotted at callback entry.
+    positions: Vec<f64>,
+    actions: Vec<HostAction>,
+    next_handle: i64,
+}
+
+impl HostState {
+    fn push_action(&mut self, action: HostAction) -> bool {
+        if self.actions.len() >= self.max_actions {
+            return false;
+        }
+        self.actions.push(action);
+        true
+    }
+
+    fn alloc_handle(&mut self) -> i64 {
+        self.next_handle += 1;
+        self.next_handle
+    }
+
+    fn token(&self, token: i32) -> Option<usize> {
+        usize::try_from(token).ok().filter(|&t| t < self.tokens.len())
+    }
+}
+
+fn define_host_functions(linker: &mut Linker<HostState>) -> Result<(), String> {
+    let err = |e: wasmi::errors::LinkerError| format!("Failed to define host function: {}", e);
+
+    linker
+        .func_wrap(HOST_MODULE, "now", |caller: Caller<'_, HostState>| -> i64 {
+            caller.data().now
+        })
+        .map_err(err)?;
+
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "param",
+            |caller: Caller<'_, HostState>, ptr: i32, len: i32, default: f64| -> f64 {
+                let memory = match caller.get_export("memory").and_then(Extern::into_memory) {
+                    Some(m) => m,
+                    None => return default,
+                };
+                let (Ok(start), Ok(len)) = (usize::try_from(ptr), usize::try_from(len)) else {
+                    return default;
+                };
+                let key = memory
+                    .data(&caller)
+                    .get(start..start.saturating_add(len))
+                    .and_then(|bytes| std::str::from_utf8(bytes).ok());
+                key.and_then(|k| caller.data().params.get(k)).unwrap_or(default)
+            },
+        )
+        .map_err(err)?;
+
+    linker
+        .func_wrap(HOST_MODULE, "position", |caller: Caller<'_, HostState>, token: i32| -> f64 {
+            let state = caller.data();
+            state.token(token).map(|t| state.positions[t]).unwrap_or(0.0)
+        })
+        .map_err(err)?;
+
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "place_order",
+            |mut caller: Caller<'_, HostState>,
+             token: i32,
+             side: i32,
+             price: f64,
+             size: f64,
+             tif: i32,
+             post_only: i32|
+             -> i64 {
+                let state = caller.data_mut();
+                let Some(token) = state.token(token) else { return -1 };
+                let side = match side {
+                    0 => Side::Buy,
+                    1 => Side::Sell,
+                    _ => return -1,
+                };
+                let time_in_force = match tif {
+                    0 => TimeInForce::Gtc,
+                    1 => TimeInForce::Ioc,
+                    2 => TimeInForce::Fok,
+                    _ => return -1,
+                };
+                if !price.is_finite() || !size.is_finite() || size <= 0.0 {
+                    return -1;
+                }
+                let handle = state.alloc_handle();
+                let queued = state.push_action(HostAction::Order {
+                    handle,
+                    token,
+                    side,
+                    price,
+                    size,
+                    time_in_force,
+                    post_only: post_only != 0,
+                });
+                if queued { handle } else { -1 }
+            },
+        )
+        .map_err(err)?;
+
+    linker
+        .func_wrap(HOST_MODULE, "cancel_order", |mut caller: Caller<'_, HostState>, handle: i64| -> i32 {
+            caller.data_mut().push_action(HostAction::Cancel { handle }) as i32
+        })
+        .map_err(err)?;
+
+    linker
+        .func_wrap(HOST_MODULE, "cancel_all", |mut caller: Caller<'_, HostState>, token: i32| -> i32 {
+            let state = caller.data_mut();
+            match state.token(token) {
+                Some(token) => state.push_action(HostAction::CancelAll { token }) as i32,
+                None => 0,
+            }
+        })
+        .map_err(err)?;
+
+    linker
+        .func_wrap(HOST_MODULE, "schedule_timer", |mut caller: Caller<'_, HostState>, delay_ns: i64| -> i64 {
+            let state = caller.data_mut();
+            let handle = state.alloc_handle();
+            if state.push_action(HostAction::Timer { handle, delay_ns: delay_ns.max(0) }) {
+                handle
+            } else {
+                -1
+            }
+        })
+        .map_err(err)?;
+
+    Ok(())
+}
+
+// =============================================================================
+// STRATEGY
+// =============================================================================
+
+type BookArgs = (i32, f64, f64, f64, f64, i64);
+type TradeArgs = (i32, f64, f64, i32, i64);
+type FillArgs = (i64, f64, f64, i32, f64, f64, i64);
+
+/// Typed handles to the optional guest callbacks.
+struct GuestCallbacks {
+    on_start: Option<TypedFunc<(), ()>>,
+    on_stop: Option<TypedFunc<(), ()>>,
+    on_book: Option<TypedFunc<BookArgs, ()>>,
+    on_trade: Option<TypedFunc<TradeArgs, ()>>,
+    on_timer: Option<TypedFunc<(i64, i64), ()>>,
+    on_order_ack: Option<TypedFunc<(i64, i64), ()>>,
+    on_order_reject: Option<TypedFunc<(i64, i64), ()>>,
+    on_fill: Option<TypedFunc<FillArgs, ()>>,
+    on_cancel_ack: Option<TypedFunc<(i64, f64, i64), ()>>,
+}
+
+/// A strategy instance running inside the WASM sandbox.
+pub struct WasmStrategy {
+    name: String,
+    fuel_per_callback: u64,
+    /// None only for strategies that failed to instantiate.
+    runtime: Option<(Store<HostState>, GuestCallbacks)>,
+    token_index: HashMap<String, usize>,
+    /// Host order id -> guest order handle.
+    order_handles: HashMap<OrderId, i64>,
+    /// Guest order handle -> host order id.
+    handle_orders: HashMap<i64, OrderId>,
+    /// Host timer id -> guest timer handle.
+    timer_handles: HashMap<u64, i64>,
+    fault: Option<String>,
+    callbacks_run: u64,
+}
+
+impl WasmStrategy {
+    fn instantiate(factory: &WasmStrategyFactory, params: StrategyParams) -> Result<Self, String> {
+        let state = HostState {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(factory.limits.max_memory_bytes)
+                .instances(1)
+                .build(),
+            max_actions: factory.limits.max_actions_per_callback,
+            now: 0,
+            params,
+            tokens: Vec::new(),
+            positions: Vec::new(),
+            actions: Vec::new(),
+            next_handle: 0,
+        };
+        let mut store = Store::new(&factory.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(factory.limits.fuel_per_callback)
+            .map_err(|e| format!("Failed to set fuel: {}", e))?;
+
+        let mut linker = Linker::new(&factory.engine);
+        define_host_functions(&mut linker)?;
+        let instance = linker
+            .instantiate(&mut store, &factory.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| format!("Failed to instantiate WASM strategy '{}': {}", factory.name, e))?;
+
+        // Exports are optional, but an export with the wrong signature is an error.
+        macro_rules! callback {
+            ($name:literal) => {
+                match instance.get_export(&store, $name) {
+                    Some(_) => Some(instance.get_typed_func(&store, $name).map_err(|e| {
+                        format!("WASM export '{}' has the wrong signature: {}", $name, e)
+                    })?),
+                    None => None,
+                }
+            };
+        }
+        let callbacks = GuestCallbacks {
+            on_start: callback!("on_start"),
+            on_stop: callback!("on_stop"),
+            on_book: callback!("on_book"),
+            on_trade: callback!("on_trade"),
+            on_timer: callback!("on_timer"),
+            on_order_ack: callback!("on_order_ack"),
+            on_order_reject: callback!("on_order_reject"),
+            on_fill: callback!("on_fill"),
+            on_cancel_ack: callback!("on_cancel_ack"),
+        };
+        // Actions queued by a start function have no context to run in.
+        store.data_mut().actions.clear();
+
+        Ok(Self {
+            name: factory.name.clone(),
+            fuel_per_callback: factory.limits.fuel_per_callback,
+            runtime: Some((store, callbacks)),
+            token_index: HashMap::new(),
+            order_handles: HashMap::new(),
+            handle_orders: HashMap::new(),
+            timer_handles: HashMap::new(),
+            fault: None,
+            callbacks_run: 0,
+        })
+    }
+
+    fn faulted(name: &str, fault: String) -> Self {
+        tracing::error!(strategy = %name, "{}", fault);
+        Self {
+            name: name.to_string(),
+            fuel_per_callback: 0,
+            runtime: None,
+            token_index: HashMap::new(),
+            order_handles: HashMap::new(),
+            handle_orders: HashMap::new(),
+            timer_handles: HashMap::new(),
+            fault: Some(fault),
+            callbacks_run: 0,
+        }
+    }
+
+    /// Why the module stopped receiving callbacks, if it did.
+    pub fn fault(&self) -> Option<&str> {
+        self.fault.as_deref()
+    }
+
+    /// Number of guest callbacks that completed successfully.
+    pub fn callbacks_run(&self) -> u64 {
+        self.callbacks_run
+    }
+
+    fn token_handle(&mut self, token_id: &str) -> i32 {
+        if let Some(&t) = self.token_index.get(token_id) {
+            return t as i32;
+        }
+        let Some((store, _)) = self.runtime.as_mut() else { return -1 };
+        let state = store.data_mut();
+        let t = state.tokens.len();
+        state.tokens.push(token_id.to_string());
+        state.positions.push(0.0);
+        self.token_index.insert(token_id.to_string(), t);
+        t as i32
+    }
+
+    /// Run one guest callback with a fresh fuel budget, then apply its actions.
+    fn dispatch<F>(&mut self, ctx: &mut StrategyContext, call: F)
+    where
+        F: FnOnce(&GuestCallbacks, &mut Store<HostState>) -> Option<Result<(), wasmi::Error>>,
+    {
+        let mut rejects = self.invoke(ctx, call);
+        for _ in 0..MAX_REJECT_ROUNDS {
+            if rejects.is_empty() {
+                return;
+            }
+            let mut next = Vec::new();
+            for handle in rejects {
+                let ts = ctx.timestamp;
+                next.extend(self.invoke(ctx, |cb, store| {
+                    cb.on_order_reject.map(|f| f.call(store, (handle, ts)))
+                }));
+            }
+            rejects = next;
+        }
+        if !rejects.is_empty() {
+            tracing::debug!(strategy = %self.name, dropped = rejects.len(), "WASM reject feedback limit reached");
+        }
+    }
+
+    /// Returns handles of orders the host rejected synchronously.
+    fn invoke<F>(&mut self, ctx: &mut StrategyContext, call: F) -> Vec<i64>
+    where
+        F: FnOnce(&GuestCallbacks, &mut Store<HostState>) -> Option<Result<(), wasmi::Error>>,
+    {
+        if self.fault.is_some() {
+            return Vec::new();
+        }
+        let Some((store, callbacks)) = self.runtime.as_mut() else { return Vec::new() };
+
+        let state = store.data_mut();
+        state.now = ctx.timestamp;
+        state.actions.clear();
+        for (t, token_id) in state.tokens.iter().enumerate() {
+            state.positions[t] = ctx.orders.get_position(token_id).shares;
+        }
+        if let Err(e) = store.set_fuel(self.fuel_per_callback) {
+            self.fault = Some(format!("Failed to set fuel: {}", e));
+            return Vec::new();
+        }
+
+        match call(callbacks, store) {
+            None => return Vec::new(),
+            Some(Ok(())) => self.callbacks_run += 1,
+            Some(Err(e)) => {
+                let fault = format!("WASM strategy '{}' trapped at {}: {}", self.name, ctx.timestamp, e);
+                tracing::error!(strategy = %self.name, "{}", fault);
+                self.fault = Some(fault);
+                return Vec::new();
+            }
+        }
+
+        let state = store.data_mut();
+        let actions = std::mem::take(&mut state.actions);
+        let tokens = state.tokens.clone();
+        self.apply_actions(ctx, actions, &tokens)
+    }
+
+    fn apply_actions(
+        &mut self,
+        ctx: &mut StrategyContext,
+        actions: Vec<HostAction>,
+        tokens: &[String],
+    ) -> Vec<i64> {
+        let mut rejects = Vec::new();
+        for action in actions {
+            match action {
+                HostAction::Order { handle, token, side, price, size, time_in_force, post_only } => {
+                    let mut order = StrategyOrder::limit(
+                        format!("{}-{}", self.name, handle),
+                        tokens[token].clone(),
+                        side,
+                        price,
+                        size,
+                    );
+                    order.time_in_force = time_in_force;
+                    order.post_only = post_only;
+                    match ctx.orders.send_order(order) {
+                        Ok(order_id) => {
+                            self.order_handles.insert(order_id, handle);
+                            self.handle_orders.insert(handle, order_id);
+                        }
+                        Err(reason) => {
+                            tracing::debug!(strategy = %self.name, handle, %reason, "WASM order rejected");
+                            rejects.push(handle);
+                        }
+                    }
+                }
+                HostAction::Cancel { handle } => {
+                    if let Some(&order_id) = self.handle_orders.get(&handle) {
+                        let _ = ctx.orders.send_cancel(StrategyCancel {
+                            order_id,
+                            client_order_id: Some(format!("{}-{}", self.name, handle)),
+                        });
+                    }
+                }
+                HostAction::CancelAll { token } => {
+                    let _ = ctx.orders.cancel_all(&tokens[token]);
+                }
+                HostAction::Timer { handle, delay_ns } => {
+                    let timer_id = ctx.orders.schedule_timer(delay_ns, None);
+                    self.timer_handles.insert(timer_id, handle);
+                }
+            }
+        }
+        rejects
+    }
+}
+
+impl Strategy for WasmStrategy {
+    fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+        let token = self.token_handle(&book.token_id);
+        let (bid_px, bid_sz) = book.best_bid().map(|l| (l.price, l.size)).unwrap_or((0.0, 0.0));
+        let (ask_px, ask_sz) = book.best_ask().map(|l| (l.price, l.size)).unwrap_or((0.0, 0.0));
+        let ts = book.timestamp;
+        self.dispatch(ctx, |cb, store| {
+            cb.on_book.map(|f| f.call(store, (token, bid_px, bid_sz, ask_px, ask_sz, ts)))
+        });
+    }
+
+    fn on_trade(&mut self, ctx: &mut StrategyContext, trade: &TradePrint) {
+        let token = self.token_handle(&trade.token_id);
+        let aggressor = match trade.aggressor_side {
+            Side::Buy => 0,
+            Side::Sell => 1,
+        };
+        let (price, size, ts) = (trade.price, trade.size, trade.timestamp);
+        self.dispatch(ctx, |cb, store| {
+            cb.on_trade.map(|f| f.call(store, (token, price, size, aggressor, ts)))
+        });
+    }
+
+    fn on_timer(&mut self, ctx: &mut StrategyContext, timer: &TimerEvent) {
+        let Some(handle) = self.timer_handles.remove(&timer.timer_id) else { return };
+        let ts = timer.actual_time;
+        self.dispatch(ctx, |cb, store| cb.on_timer.map(|f| f.call(store, (handle, ts))));
+    }
+
+    fn on_order_ack(&mut self, ctx: &mut StrategyContext, ack: &OrderAck) {
+        let Some(&handle) = self.order_handles.get(&ack.order_id) else { return };
+        let ts = ack.timestamp;
+        self.dispatch(ctx, |cb, store| cb.on_order_ack.map(|f| f.call(store, (handle, ts))));
+    }
+
+    fn on_order_reject(&mut self, ctx: &mut StrategyContext, reject: &OrderReject) {
+        let Some(handle) = self.order_handles.remove(&reject.order_id) else { return };
+        self.handle_orders.remove(&handle);
+        let ts = reject.timestamp;
+        self.dispatch(ctx, |cb, store| cb.on_order_reject.map(|f| f.call(store, (handle, ts))));
+    }
+
+    fn on_fill(&mut self, ctx: &mut StrategyContext, fill: &FillNotification) {
+        let Some(&handle) = self.order_handles.get(&fill.order_id) else { return };
+        let args = (
+            handle,
+            fill.price,
+            fill.size,
+            fill.is_maker as i32,
+            fill.leaves_qty,
+            fill.fee,
+            fill.timestamp,
+        );
+        self.dispatch(ctx, |cb, store| cb.on_fill.map(|f| f.call(store, args)));
+    }
+
+    fn on_cancel_ack(&mut self, ctx: &mut StrategyContext, ack: &CancelAck) {
+        let Some(&handle) = self.order_handles.get(&ack.order_id) else { return };
+        let (cancelled, ts) = (ack.cancelled_qty, ack.timestamp);
+        self.dispatch(ctx, |cb, store| cb.on_cancel_ack.map(|f| f.call(store, (handle, cancelled, ts))));
+    }
+
+    fn on_start(&mut self, ctx: &mut StrategyContext) {
+        self.dispatch(ctx, |cb, store| cb.on_start.map(|f| f.call(store, ())));
+    }
+
+    fn on_stop(&mut self, ctx: &mut StrategyContext) {
+        self.dispatch(ctx, |cb, store| cb.on_stop.map(|f| f.call(store, ())));
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// The sandbox exposes no forbidden APIs; time comes only from `betterbot.now`.
+impl HermeticStrategy for WasmStrategy {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::events::Level;
+    use crate::backtest_v2::strategy::{OpenOrder, OrderSender, Position};
+
+    /// Records everything the strategy sends.
+    #[derive(Default)]
+    struct RecordingSender {
+        orders: Vec<StrategyOrder>,
+        cancels: Vec<StrategyCancel>,
+        timers: Vec<Nanos>,
+        reject_all: bool,
+        next_id: OrderId,
+        position: f64,
+    }
+
+    impl OrderSender for RecordingSender {
+        fn send_order(&mut self, order: StrategyOrder) -> Result<OrderId, String> {
+            self.orders.push(order);
+            if self.reject_all {
+                return Err("rejected".into());
+            }
+            self.next_id += 1;
+            Ok(self.next_id)
+        }
+        fn send_cancel(&mut self, cancel: StrategyCancel) -> Result<(), String> {
+            self.cancels.push(cancel);
+            Ok(())
+        }
+        fn cancel_all(&mut self, _token_id: &str) -> Result<usize, String> {
+            Ok(0)
+        }
+        fn get_position(&self, token_id: &str) -> Position {
+            Position {
+                token_id: token_id.to_string(),
+                shares: self.position,
+                ..Default::default()
+            }
+        }
+        fn get_all_positions(&self) -> HashMap<String, Position> {
+            HashMap::new()
+        }
+        fn get_open_orders(&self) -> Vec<OpenOrder> {
+            Vec::new()
+        }
+        fn now(&self) -> Nanos {
+            0
+        }
+        fn schedule_timer(&mut self, delay_ns: Nanos, _payload: Option<String>) -> u64 {
+            self.timers.push(delay_ns);
+            self.timers.len() as u64
+        }
+        fn cancel_timer(&mut self, _timer_id: u64) -> bool {
+            false
+        }
+    }
+
+    fn book(ts: Nanos) -> BookSnapshot {
+        BookSnapshot {
+            token_id: "tok-up".into(),
+            bids: vec![Level::new(0.45, 100.0)],
+            asks: vec![Level::new(0.55, 100.0)],
+            timestamp: ts,
+            exchange_seq: 1,
+        }
+    }
+
+    /// Buys `size` (param, default 5) at the best bid while flat and arms a
+    /// 1us timer; a reject arms a 99ns timer so the feedback is observable.
+    const QUOTER: &str = r#"
+        (module
+          (import "betterbot" "place_order" (func $place (param i32 i32 f64 f64 i32 i32) (result i64)))
+          (import "betterbot" "position" (func $position (param i32) (result f64)))
+          (import "betterbot" "param" (func $param (param i32 i32 f64) (result f64)))
+          (import "betterbot" "now" (func $now (result i64)))
+          (import "betterbot" "schedule_timer" (func $timer (param i64) (result i64)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "size")
+          (func (export "on_book") (param $tok i32) (param $bp f64) (param $bs f64)
+                                   (param $ap f64) (param $as f64) (param $ts i64)
+            (if (i64.ne (call $now) (local.get $ts)) (then unreachable))
+            (if (f64.eq (call $position (local.get $tok)) (f64.const 0))
+              (then
+                (drop (call $place (local.get $tok) (i32.const 0) (local.get $bp)
+                        (call $param (i32.const 0) (i32.const 4) (f64.const 5))
+                        (i32.const 0) (i32.const 1)))
+                (drop (call $timer (i64.const 1000))))))
+          (func (export "on_order_reject") (param i64 i64)
+            (drop (call $timer (i64.const 99)))))
+    "#;
+
+    fn factory(wat_src: &str) -> Result<WasmStrategyFactory, String> {
+        let bytes = wat::parse_str(wat_src).unwrap();
+        WasmStrategyFactory::from_bytes("quoter", "0.1.0", &bytes)
+    }
+
+    #[test]
+    fn test_wasm_strategy_places_orders_through_context() {
+        let factory = factory(QUOTER).unwrap();
+        let mut strategy = factory
+            .try_create(StrategyParams::new().with_param("size", 12.0))
+            .unwrap();
+        let mut sender = RecordingSender::default();
+        let params = StrategyParams::default();
+
+        let mut ctx = StrategyContext { orders: &mut sender, timestamp: 7_000, params: &params };
+        strategy.on_book_update(&mut ctx, &book(7_000));
+
+        assert_eq!(sender.orders.len(), 1);
+        let order = &sender.orders[0];
+        assert_eq!(order.token_id, "tok-up");
+        assert_eq!(order.side, Side::Buy);
+        assert_eq!(order.price, 0.45);
+        assert_eq!(order.size, 12.0);
+        assert!(order.post_only);
+        assert_eq!(order.client_order_id, "quoter-1");
+        assert_eq!(sender.timers, vec![1000]);
+        assert!(strategy.fault().is_none());
+
+        // Position is visible to the guest: no new order once long
+        sender.position = 12.0;
+        let mut ctx = StrategyContext { orders: &mut sender, timestamp: 8_000, params: &params };
+        strategy.on_book_update(&mut ctx, &book(8_000));
+        assert_eq!(sender.orders.len(), 1);
+        assert_eq!(strategy.callbacks_run(), 2);
+    }
+
+    #[test]
+    fn test_synchronous_rejects_are_fed_back() {
+        let factory = factory(QUOTER).unwrap();
+        let mut strategy = factory.try_create(StrategyParams::default()).unwrap();
+        let mut sender = RecordingSender { reject_all: true, ..Default::default() };
+        let params = StrategyParams::default();
+
+        let mut ctx = StrategyContext { orders: &mut sender, timestamp: 1, params: &params };
+        strategy.on_book_update(&mut ctx, &book(1));
+
+        assert_eq!(sender.orders.len(), 1);
+        assert_eq!(sender.timers, vec![1000, 99]);
+        assert!(strategy.order_handles.is_empty());
+        assert!(strategy.fault().is_none());
+    }
+
+    #[test]
+    fn test_forbidden_imports_rejected_at_load() {
+        let wasi = r#"
+            (module
+              (import "wasi_snapshot_preview1" "clock_time_get"
+                (func (param i32 i64 i32) (result i32))))
+        "#;
+        let err = factory(wasi).err().unwrap();
+        assert!(err.contains("wasi_snapshot_preview1.clock_time_get"), "{}", err);
+
+        let unknown_host = r#"
+            (module (import "betterbot" "wall_clock" (func (result i64))))
+        "#;
+        assert!(factory(unknown_host).is_err());
+
+        let wrong_signature = r#"
+            (module (func (export "on_book") (param i32)))
+        "#;
+        let err = factory(wrong_signature).err().unwrap();
+        assert!(err.contains("on_book"), "{}", err);
+    }
+
+    #[test]
+    fn test_runaway_loop_faults_and_stops_trading() {
+        let spin = r#"
+            (module
+              (import "betterbot" "place_order" (func $place (param i32 i32 f64 f64 i32 i32) (result i64)))
+              (func (export "on_book") (param $tok i32) (param f64 f64 f64 f64 i64)
+                (drop (call $place (local.get $tok) (i32.const 0) (f64.const 0.5) (f64.const 1)
+                        (i32.const 0) (i32.const 0)))
+                (loop $l (br $l))))
+        "#;
+        let mut strategy = factory(spin).unwrap().try_create(StrategyParams::default()).unwrap();
+        let mut sender = RecordingSender::default();
+        let params = StrategyParams::default();
+
+        for ts in 1..=3 {
+            let mut ctx = StrategyContext { orders: &mut sender, timestamp: ts, params: &params };
+            strategy.on_book_update(&mut ctx, &book(ts));
+        }
+
+        // Actions from the trapped callback are discarded
+        assert!(sender.orders.is_empty());
+        assert!(strategy.fault().unwrap().contains("trapped"));
+        assert_eq!(strategy.callbacks_run(), 0);
+    }
+
+    #[test]
+    fn test_code_hash_tracks_module_bytes() {
+        let a = factory(QUOTER).unwrap();
+        let b = factory(QUOTER).unwrap();
+        let c = factory(&QUOTER.replace("(i64.const 1000)", "(i64.const 2000)")).unwrap();
+
+        assert_eq!(a.code_hash().len(), 64);
+        assert_eq!(a.code_hash(), b.code_hash());
+        assert_ne!(a.code_hash(), c.code_hash());
+
+        let sid = a.strategy_id();
+        assert_eq!(sid.name, "quoter");
+        assert_eq!(sid.code_hash.as_deref(), Some(a.code_hash()));
+        assert!(sid.validate_for_production().is_ok());
+        assert_ne!(sid.compute_hash(), c.strategy_id().compute_hash());
+    }
+}
@@ -444,7 +444,7 @@ impl Default for WindowPnLSeries {
 }
 
 /// Window accounting error types.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WindowAccountingError {
     /// Sum of window values doesn't match reported total.
     SumMismatch {
@@ -523,7 +523,7 @@ impl std::error::Error for WindowAccountingError {}
 ///
 /// Tracks per-window PnL by aggregating ledger entries as they are posted.
 /// This is the central coordinator for window-level accounting.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WindowAccountingEngine {
     /// Active (unfinalied) windows by market_id -> window_start_ns -> WindowPnL.
     active_windows: HashMap<String, HashMap<WindowId, WindowPnL>>,
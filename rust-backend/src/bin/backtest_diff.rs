@@ -0,0 +1,82 @@
+//! Backtest Run Diff CLI
+//!
+//! Explains why two persisted runs have different fingerprints: which
+//! fingerprint components changed, and where their decision traces first
+//! diverge (with a side-by-side dump of the surrounding records).
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --bin backtest_diff -- \
+//!   --artifact-db artifacts.db \
+//!   run_0123abcd... run_4567ef01... \
+//!   --context 10
+//! ```
+//!
+//! # Exit Codes
+//!
+//! - 0: Fingerprints identical
+//! - 1: Runs differ
+//! - 2: Error (bad arguments, run not found, store failure)
+
+use betterbot_backend::backtest_v2::{diff_runs, ArtifactStore, RunId};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Explain the difference between two backtest runs
+#[derive(Parser, Debug)]
+#[command(name = "backtest_diff")]
+#[command(about = "Explain why two backtest run fingerprints differ")]
+struct Cli {
+    /// Artifact store SQLite path
+    #[arg(short, long)]
+    artifact_db: PathBuf,
+
+    /// Baseline run id
+    run_a: String,
+
+    /// Run id to compare against the baseline
+    run_b: String,
+
+    /// Trace records shown on each side of the first divergence
+    #[arg(short, long, default_value_t = 5)]
+    context: usize,
+
+    /// Print the diff as JSON instead of a text report
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let store = match ArtifactStore::new(&cli.artifact_db) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Error: failed to open artifact store: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let diff = match diff_runs(&store, &RunId(cli.run_a), &RunId(cli.run_b), cli.context) {
+        Ok(diff) => diff,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if cli.json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error: failed to serialize diff: {}", e);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        print!("{}", diff.format_report());
+    }
+
+    std::process::exit(if diff.identical { 0 } else { 1 });
+}
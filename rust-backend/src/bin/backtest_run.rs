@@ -14,6 +14,80 @@
 //!   --output results.json
 //! ```
 //!
+//! # Multi-Market Runs
+//!
+//! `--market` may be repeated. Events from every market are merged into a single
+//! feed and replayed through one orchestrator, so all markets draw on the same
+//! ledger. `--risk-limits` adds cross-market pre-trade limits, and the results
+//! carry per-market attribution.
+//!
+//! ```bash
+//! cargo run --bin backtest_run -- \
+//!   --db data.db \
+//!   --market btc-updown-15m-1762755300 --market eth-updown-15m-1762755300 \
+//!   --market sol-updown-15m-1762755300 --market xrp-updown-15m-1762755300 \
+//!   --start 2026-01-24T00:00:00Z --end 2026-01-24T06:00:00Z \
+//!   --strategy random_taker --allow-non-production --risk-limits conservative
+//! ```
+//!
+//! # WASM Strategies
+//!
+//! `--strategy-wasm` loads a strategy from a sandboxed WebAssembly module instead
+//! of a built-in (see `backtest_v2::wasm_strategy` for the guest ABI). The
+//! module's SHA-256 becomes the strategy code hash in the run fingerprint.
+//!
+//! ```bash
+//! cargo run --bin backtest_run -- \
+//!   --db data.db --market btc-updown-15m-1762755300 \
+//!   --start 2026-01-24T00:00:00Z --end 2026-01-24T06:00:00Z \
+//!   --strategy-wasm strategies/quoter.wasm --strategy-version 0.3.1
+//! ```
+//!
+//! # Parameter Sweeps
+//!
+//! Passing one or more `--sweep` / `--sweep-range` axes switches the runner into
+//! sweep mode: every sampled `StrategyParams` assignment is run as an independent
+//! backtest on a rayon worker pool, and (with `--artifact-db`) persisted as a
+//! `RunArtifact` tagged with a shared sweep id.
+//!
+//! ```bash
+//! cargo run --bin backtest_run -- \
+//!   --db data.db --market btc-updown-15m-1762755300 \
+//!   --start 2026-01-24T00:00:00Z --end 2026-01-24T06:00:00Z \
+//!   --strategy random_taker --allow-non-production \
+//!   --sweep clip_size=5,10,20 --sweep-range trade_probability=0.001:0.05:5 \
+//!   --artifact-db artifacts.db
+//! ```
+//!
+//! # Live Latency Profiles
+//!
+//! `--latency-profile` replaces the latency presets with empirical histograms
+//! exported from a running backend (see `backtest_v2::live_latency`).
+//!
+//! ```bash
+//! curl -H "Authorization: Bearer $TOKEN" \
+//!   "$API/api/admin/latency/export?venue=polymarket" > latency.json
+//! cargo run --bin backtest_run -- ... --latency-profile latency.json
+//! ```
+//!
+//! # Recorder Storage
+//!
+//! Instead of `--db`, the run can replay the recorders' own storage:
+//! `--l2-db` (snapshots and deltas for each `--token`), `--trades-db` (trade
+//! prints for each `--market`) and `--oracle-db` (Chainlink rounds for each
+//! `--oracle-feed`). These are streamed through a `StreamingMergeFeed` one
+//! `--chunk-minutes` window at a time, so multi-week ranges never sit in
+//! memory whole. Sweep mode still needs a `--db` dataset.
+//!
+//! ```bash
+//! cargo run --bin backtest_run -- \
+//!   --l2-db l2.db --token 7132...861 --token 4810...202 \
+//!   --trades-db trades.db --oracle-db chainlink.db --oracle-feed btc-usd \
+//!   --market btc-updown-15m-1762755300 \
+//!   --start 2026-01-01T00:00:00Z --end 2026-01-22T00:00:00Z \
+//!   --strategy noop
+//! ```
+//!
 //! # Exit Codes
 //!
 //! - 0: Success, TrustLevel == Trusted
@@ -23,18 +97,23 @@
 
 use betterbot_backend::backtest_v2::{
     available_strategies, make_strategy, BacktestConfig, BacktestOrchestrator, BacktestResults,
-    Event, HistoricalDataContract, Level, MakerFillModel, Nanos, RunFingerprint, Side,
-    StrategyParams, TimestampedEvent, TrustDecision, TrustLevel, VecFeed, NANOS_PER_MILLI,
-    NANOS_PER_SEC, ArtifactStore, RunArtifact,
+    HistoricalDataContract, MakerFillModel, RunFingerprint, Nanos, NANOS_PER_SEC,
+    EventChunkSource, L2ChunkSource, L2Storage, OracleRoundChunkSource, OracleRoundStorage,
+    OracleStorageConfig, StreamingMergeFeed, TradePrintChunkSource, TradePrintFullStorage,
+    DEFAULT_CHUNK_SPAN_NS,
+    StrategyParams, TimestampedEvent, TrustDecision, TrustLevel, VecFeed,
+    ArtifactStore, RunArtifact, ParamAxis, ParamSweepConfig, ParamSweepRunner,
+    SweepSampling, RiskLimits, format_attribution, load_events_from_sqlite,
+    StrategyFactory, StrategyId, WasmStrategyFactory, LiveLatencyProfile,
 };
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, OpenFlags};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 // =============================================================================
 // CLI ARGUMENTS
@@ -42,17 +121,30 @@ use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 struct CliArgs {
-    db_path: String,
-    market_id: String,
+    db_path: Option<String>,
+    l2_db_path: Option<String>,
+    token_ids: Vec<String>,
+    trades_db_path: Option<String>,
+    oracle_db_path: Option<String>,
+    oracle_feed_ids: Vec<String>,
+    chunk_span_ns: Nanos,
+    market_ids: Vec<String>,
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
     strategy_name: String,
+    strategy_wasm: Option<String>,
+    strategy_version: String,
     output_path: Option<String>,
     artifact_db_path: Option<String>,
     allow_non_production: bool,
     seed: u64,
     latency_ms: Option<u64>,
+    latency_profile: Option<String>,
     verbose: bool,
+    sweep_axes: Vec<ParamAxis>,
+    sweep_sampling: SweepSampling,
+    sweep_workers: Option<usize>,
+    risk_limits: Option<RiskLimits>,
 }
 
 impl CliArgs {
@@ -61,16 +153,30 @@ impl CliArgs {
         let mut i = 1;
 
         let mut db_path = None;
-        let mut market_id = None;
+        let mut l2_db_path = None;
+        let mut token_ids = Vec::new();
+        let mut trades_db_path = None;
+        let mut oracle_db_path = None;
+        let mut oracle_feed_ids = Vec::new();
+        let mut chunk_span_ns = DEFAULT_CHUNK_SPAN_NS;
+        let mut market_ids = Vec::new();
         let mut start_time = None;
         let mut end_time = None;
         let mut strategy_name = None;
+        let mut strategy_wasm = None;
+        let mut strategy_version = "0.1.0".to_string();
         let mut output_path = None;
         let mut artifact_db_path = None;
         let mut allow_non_production = false;
         let mut seed = 42u64;
         let mut latency_ms = None;
+        let mut latency_profile = None;
         let mut verbose = false;
+        let mut sweep_axes = Vec::new();
+        let mut sweep_method = "grid".to_string();
+        let mut sweep_samples = 32usize;
+        let mut sweep_workers = None;
+        let mut risk_limits = None;
 
         while i < args.len() {
             match args[i].as_str() {
@@ -78,9 +184,47 @@ impl CliArgs {
                     i += 1;
                     db_path = Some(args.get(i).ok_or("--db requires a path")?.clone());
                 }
+                "--l2-db" => {
+                    i += 1;
+                    l2_db_path = Some(args.get(i).ok_or("--l2-db requires a path")?.clone());
+                }
+                "--token" => {
+                    i += 1;
+                    let id = args.get(i).ok_or("--token requires an ID")?.clone();
+                    if !token_ids.contains(&id) {
+                        token_ids.push(id);
+                    }
+                }
+                "--trades-db" => {
+                    i += 1;
+                    trades_db_path = Some(args.get(i).ok_or("--trades-db requires a path")?.clone());
+                }
+                "--oracle-db" => {
+                    i += 1;
+                    oracle_db_path = Some(args.get(i).ok_or("--oracle-db requires a path")?.clone());
+                }
+                "--oracle-feed" => {
+                    i += 1;
+                    let id = args.get(i).ok_or("--oracle-feed requires an ID")?.clone();
+                    if !oracle_feed_ids.contains(&id) {
+                        oracle_feed_ids.push(id);
+                    }
+                }
+                "--chunk-minutes" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--chunk-minutes requires a number")?;
+                    let minutes: i64 = s.parse().map_err(|e| format!("Invalid chunk minutes: {}", e))?;
+                    if minutes <= 0 {
+                        return Err("--chunk-minutes must be positive".to_string());
+                    }
+                    chunk_span_ns = minutes * 60 * NANOS_PER_SEC;
+                }
                 "--market" | "-m" => {
                     i += 1;
-                    market_id = Some(args.get(i).ok_or("--market requires an ID")?.clone());
+                    let id = args.get(i).ok_or("--market requires an ID")?.clone();
+                    if !market_ids.contains(&id) {
+                        market_ids.push(id);
+                    }
                 }
                 "--start" | "-s" => {
                     i += 1;
@@ -104,6 +248,14 @@ impl CliArgs {
                     i += 1;
                     strategy_name = Some(args.get(i).ok_or("--strategy requires a name")?.clone());
                 }
+                "--strategy-wasm" => {
+                    i += 1;
+                    strategy_wasm = Some(args.get(i).ok_or("--strategy-wasm requires a path")?.clone());
+                }
+                "--strategy-version" => {
+                    i += 1;
+                    strategy_version = args.get(i).ok_or("--strategy-version requires a version")?.clone();
+                }
                 "--output" | "-o" => {
                     i += 1;
                     output_path = Some(args.get(i).ok_or("--output requires a path")?.clone());
@@ -125,9 +277,56 @@ impl CliArgs {
                     let s = args.get(i).ok_or("--latency-ms requires a number")?;
                     latency_ms = Some(s.parse().map_err(|e| format!("Invalid latency: {}", e))?);
                 }
+                "--latency-profile" => {
+                    i += 1;
+                    latency_profile = Some(
+                        args.get(i)
+                            .ok_or("--latency-profile requires a path")?
+                            .clone(),
+                    );
+                }
                 "--verbose" | "-v" => {
                     verbose = true;
                 }
+                "--sweep" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--sweep requires KEY=V1,V2,...")?;
+                    sweep_axes.push(parse_sweep_values(s)?);
+                }
+                "--sweep-range" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--sweep-range requires KEY=MIN:MAX:STEPS")?;
+                    sweep_axes.push(parse_sweep_range(s)?);
+                }
+                "--sweep-method" => {
+                    i += 1;
+                    sweep_method = args.get(i).ok_or("--sweep-method requires a method")?.clone();
+                }
+                "--sweep-samples" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--sweep-samples requires a number")?;
+                    sweep_samples = s.parse().map_err(|e| format!("Invalid sweep samples: {}", e))?;
+                }
+                "--sweep-workers" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--sweep-workers requires a number")?;
+                    sweep_workers = Some(s.parse().map_err(|e| format!("Invalid sweep workers: {}", e))?);
+                }
+                "--risk-limits" => {
+                    i += 1;
+                    let s = args.get(i).ok_or("--risk-limits requires a preset")?;
+                    risk_limits = Some(match s.to_lowercase().as_str() {
+                        "default" => RiskLimits::default(),
+                        "conservative" => RiskLimits::conservative(),
+                        "aggressive" => RiskLimits::aggressive(),
+                        other => {
+                            return Err(format!(
+                                "Unknown risk limits preset: {} (default, conservative, aggressive)",
+                                other
+                            ))
+                        }
+                    });
+                }
                 "--help" | "-h" => {
                     print_usage();
                     std::process::exit(0);
@@ -143,20 +342,109 @@ impl CliArgs {
             i += 1;
         }
 
+        let sweep_sampling = match sweep_method.to_lowercase().as_str() {
+            "grid" => SweepSampling::Grid,
+            "random" => SweepSampling::Random { samples: sweep_samples },
+            "lhs" | "latin-hypercube" => SweepSampling::LatinHypercube { samples: sweep_samples },
+            other => return Err(format!("Unknown sweep method: {} (grid, random, lhs)", other)),
+        };
+
+        let strategy_name = match (strategy_name, &strategy_wasm) {
+            (Some(_), Some(_)) => {
+                return Err("--strategy and --strategy-wasm are mutually exclusive".to_string())
+            }
+            (Some(name), None) => name,
+            (None, Some(path)) => format!("wasm:{}", path),
+            (None, None) => return Err("--strategy or --strategy-wasm is required".to_string()),
+        };
+        if strategy_wasm.is_some() && !sweep_axes.is_empty() {
+            return Err("--strategy-wasm cannot be combined with sweep mode".to_string());
+        }
+
+        let recorder = l2_db_path.is_some() || trades_db_path.is_some() || oracle_db_path.is_some();
+        match (&db_path, recorder) {
+            (Some(_), true) => {
+                return Err(
+                    "--db cannot be combined with --l2-db/--trades-db/--oracle-db".to_string(),
+                )
+            }
+            (None, false) => {
+                return Err("--db (or --l2-db/--trades-db/--oracle-db) is required".to_string())
+            }
+            _ => {}
+        }
+        if recorder && !sweep_axes.is_empty() {
+            return Err("Sweep mode requires a --db dataset".to_string());
+        }
+        if l2_db_path.is_some() != !token_ids.is_empty() {
+            return Err("--l2-db and --token must be given together".to_string());
+        }
+        if oracle_db_path.is_some() != !oracle_feed_ids.is_empty() {
+            return Err("--oracle-db and --oracle-feed must be given together".to_string());
+        }
+
         Ok(Self {
-            db_path: db_path.ok_or("--db is required")?,
-            market_id: market_id.ok_or("--market is required")?,
+            db_path,
+            l2_db_path,
+            token_ids,
+            trades_db_path,
+            oracle_db_path,
+            oracle_feed_ids,
+            chunk_span_ns,
+            market_ids: if market_ids.is_empty() {
+                return Err("--market is required".to_string());
+            } else {
+                market_ids
+            },
             start_time: start_time.ok_or("--start is required")?,
             end_time: end_time.ok_or("--end is required")?,
-            strategy_name: strategy_name.ok_or("--strategy is required")?,
+            strategy_name,
+            strategy_wasm,
+            strategy_version,
             output_path,
             artifact_db_path,
             allow_non_production,
             seed,
             latency_ms,
+            latency_profile,
             verbose,
+            sweep_axes,
+            sweep_sampling,
+            sweep_workers,
+            risk_limits,
+        })
+    }
+}
+
+/// Parse `KEY=V1,V2,...` into a discrete sweep axis.
+fn parse_sweep_values(s: &str) -> Result<ParamAxis, String> {
+    let (key, values) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid --sweep '{}': expected KEY=V1,V2,...", s))?;
+    let values = values
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<f64>()
+                .map_err(|e| format!("Invalid --sweep value '{}': {}", v, e))
         })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ParamAxis::discrete(key.trim(), values))
+}
+
+/// Parse `KEY=MIN:MAX:STEPS` into a continuous sweep axis.
+fn parse_sweep_range(s: &str) -> Result<ParamAxis, String> {
+    let (key, range) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid --sweep-range '{}': expected KEY=MIN:MAX:STEPS", s))?;
+    let parts: Vec<&str> = range.split(':').collect();
+    if parts.len() != 3 {
+        return Err(format!("Invalid --sweep-range '{}': expected KEY=MIN:MAX:STEPS", s));
     }
+    let min = parts[0].trim().parse::<f64>().map_err(|e| format!("Invalid sweep min: {}", e))?;
+    let max = parts[1].trim().parse::<f64>().map_err(|e| format!("Invalid sweep max: {}", e))?;
+    let steps = parts[2].trim().parse::<usize>().map_err(|e| format!("Invalid sweep steps: {}", e))?;
+    Ok(ParamAxis::continuous(key.trim(), min, max, steps))
 }
 
 fn print_usage() {
@@ -168,11 +456,14 @@ USAGE:
     backtest_run [OPTIONS]
 
 REQUIRED:
-    --db, -d <PATH>           SQLite dataset path
-    --market, -m <ID>         Market ID (e.g., btc-updown-15m-1762755300)
+    --db, -d <PATH>           SQLite dataset path (or recorder storage, below)
+    --market, -m <ID>         Market ID (e.g., btc-updown-15m-1762755300); repeat
+                              for a multi-market run sharing one ledger
     --start, -s <TIME>        Start time (RFC3339, e.g., 2026-01-24T00:00:00Z)
     --end, -e <TIME>          End time (RFC3339)
     --strategy, -S <NAME>     Strategy name (use --list-strategies for options)
+      or
+    --strategy-wasm <PATH>    Sandboxed WASM strategy module
 
 OPTIONS:
     --output, -o <PATH>       Output JSON path (default: stdout + results/<timestamp>.json)
@@ -180,8 +471,28 @@ OPTIONS:
     --allow-non-production    Allow non-production configurations (UNTRUSTED results)
     --seed <N>                Random seed (default: 42)
     --latency-ms <N>          Order latency override (ms)
+    --latency-profile <PATH>  Empirical latency exported from live
+                              (GET /api/admin/latency/export)
+    --risk-limits <PRESET>    Cross-market risk limits: default, conservative, aggressive
+    --strategy-version <VER>  Version recorded for --strategy-wasm (default: 0.1.0)
     --verbose, -v             Verbose output
     --list-strategies         List available strategies
+
+RECORDER STORAGE (streamed in chunks instead of --db):
+    --l2-db <PATH>            L2 snapshot/delta storage
+    --token <ID>              Token to replay from --l2-db (repeatable)
+    --trades-db <PATH>        Trade print storage, replayed for each --market
+    --oracle-db <PATH>        Chainlink round storage
+    --oracle-feed <ID>        Feed to replay from --oracle-db (repeatable)
+    --chunk-minutes <N>       Storage window per query (default: 15)
+
+SWEEP MODE (any --sweep/--sweep-range switches to sweep mode):
+    --sweep <KEY=V1,V2,..>    Sweep a strategy parameter over explicit values
+    --sweep-range <KEY=MIN:MAX:STEPS>
+                              Sweep a strategy parameter over a range
+    --sweep-method <M>        grid (default), random, lhs
+    --sweep-samples <N>       Sample count for random/lhs (default: 32)
+    --sweep-workers <N>       Worker threads (default: one per core)
     --help, -h                Show this help
 
 EXIT CODES:
@@ -200,6 +511,11 @@ EXAMPLES:
     backtest_run --db data.db --market btc-updown-15m-123 \
                  --start 2026-01-24T00:00:00Z --end 2026-01-24T06:00:00Z \
                  --strategy random_taker --allow-non-production
+
+    # Portfolio run across two markets with cross-market limits
+    backtest_run --db data.db --market btc-updown-15m-123 --market eth-updown-15m-123 \
+                 --start 2026-01-24T00:00:00Z --end 2026-01-24T06:00:00Z \
+                 --strategy random_taker --allow-non-production --risk-limits default
 "#
     );
 }
@@ -228,360 +544,17 @@ struct BacktestRunOutput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConfigSummary {
     strategy_name: String,
-    market_id: String,
+    market_ids: Vec<String>,
     start_time: String,
     end_time: String,
     db_path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recorder_db_paths: Vec<String>,
     seed: u64,
     production_grade: bool,
     allow_non_production: bool,
 }
 
-// =============================================================================
-// DATA LOADING
-// =============================================================================
-
-fn load_events_from_db(
-    db_path: &str,
-    market_id: &str,
-    start_ns: Nanos,
-    end_ns: Nanos,
-    verbose: bool,
-) -> Result<Vec<TimestampedEvent>, String> {
-    if verbose {
-        eprintln!("Opening database: {}", db_path);
-    }
-
-    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
-        .map_err(|e| format!("Failed to open database: {}", e))?;
-
-    let mut events = Vec::new();
-    let mut seq = 0u64;
-
-    // Try multiple table sources in order of preference
-    // 1. book_snapshots (recorded orderbook data)
-    // 2. dome_order_events (tracked wallet orders)
-
-    // Load from book_snapshots if available
-    let book_count = load_book_snapshots(&conn, market_id, start_ns, end_ns, &mut events, &mut seq);
-    if verbose && book_count > 0 {
-        eprintln!("Loaded {} book snapshots", book_count);
-    }
-
-    // Load from trade_prints if available
-    let trade_count = load_trade_prints(&conn, market_id, start_ns, end_ns, &mut events, &mut seq);
-    if verbose && trade_count > 0 {
-        eprintln!("Loaded {} trade prints", trade_count);
-    }
-
-    // Load from dome_order_events as fallback/supplement
-    let dome_count =
-        load_dome_order_events(&conn, market_id, start_ns, end_ns, &mut events, &mut seq);
-    if verbose && dome_count > 0 {
-        eprintln!("Loaded {} dome order events", dome_count);
-    }
-
-    if events.is_empty() {
-        return Err(format!(
-            "No events found for market '{}' in time range",
-            market_id
-        ));
-    }
-
-    // Sort by time then sequence
-    events.sort_by_key(|e| (e.time, e.seq));
-
-    if verbose {
-        eprintln!("Total events loaded: {}", events.len());
-    }
-
-    Ok(events)
-}
-
-fn load_book_snapshots(
-    conn: &Connection,
-    market_id: &str,
-    start_ns: Nanos,
-    end_ns: Nanos,
-    events: &mut Vec<TimestampedEvent>,
-    seq: &mut u64,
-) -> usize {
-    let query = r#"
-        SELECT token_id, arrival_time_ns, bids_json, asks_json, COALESCE(exchange_seq, 0)
-        FROM book_snapshots
-        WHERE token_id LIKE ?
-          AND arrival_time_ns >= ? AND arrival_time_ns <= ?
-        ORDER BY arrival_time_ns ASC
-    "#;
-
-    let pattern = format!("%{}%", market_id);
-    let mut count = 0;
-
-    if let Ok(mut stmt) = conn.prepare(query) {
-        if let Ok(rows) = stmt.query_map(
-            rusqlite::params![&pattern, start_ns, end_ns],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, i64>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, String>(3)?,
-                    row.get::<_, i64>(4)?,
-                ))
-            },
-        ) {
-            for row_result in rows {
-                if let Ok((token_id, arrival_ns, bids_json, asks_json, exchange_seq)) = row_result {
-                    let bids = parse_levels(&bids_json);
-                    let asks = parse_levels(&asks_json);
-
-                    let event = Event::L2BookSnapshot {
-                        token_id,
-                        bids,
-                        asks,
-                        exchange_seq: exchange_seq as u64,
-                    };
-
-                    events.push(TimestampedEvent {
-                        time: arrival_ns,
-                        source_time: arrival_ns,
-                        seq: *seq,
-                        source: 0,
-                        event,
-                    });
-                    *seq += 1;
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    count
-}
-
-fn load_trade_prints(
-    conn: &Connection,
-    market_id: &str,
-    start_ns: Nanos,
-    end_ns: Nanos,
-    events: &mut Vec<TimestampedEvent>,
-    seq: &mut u64,
-) -> usize {
-    let query = r#"
-        SELECT token_id, arrival_time_ns, price, size, side, trade_id
-        FROM trade_prints
-        WHERE token_id LIKE ?
-          AND arrival_time_ns >= ? AND arrival_time_ns <= ?
-        ORDER BY arrival_time_ns ASC
-    "#;
-
-    let pattern = format!("%{}%", market_id);
-    let mut count = 0;
-
-    if let Ok(mut stmt) = conn.prepare(query) {
-        if let Ok(rows) = stmt.query_map(
-            rusqlite::params![&pattern, start_ns, end_ns],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, i64>(1)?,
-                    row.get::<_, f64>(2)?,
-                    row.get::<_, f64>(3)?,
-                    row.get::<_, String>(4)?,
-                    row.get::<_, Option<String>>(5)?,
-                ))
-            },
-        ) {
-            for row_result in rows {
-                if let Ok((token_id, arrival_ns, price, size, side_str, trade_id)) = row_result {
-                    let aggressor_side = if side_str.eq_ignore_ascii_case("BUY") {
-                        Side::Buy
-                    } else {
-                        Side::Sell
-                    };
-
-                    let event = Event::TradePrint {
-                        token_id,
-                        price,
-                        size,
-                        aggressor_side,
-                        trade_id,
-                    };
-
-                    events.push(TimestampedEvent {
-                        time: arrival_ns,
-                        source_time: arrival_ns,
-                        seq: *seq,
-                        source: 1,
-                        event,
-                    });
-                    *seq += 1;
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    count
-}
-
-fn load_dome_order_events(
-    conn: &Connection,
-    market_id: &str,
-    start_ns: Nanos,
-    end_ns: Nanos,
-    events: &mut Vec<TimestampedEvent>,
-    seq: &mut u64,
-) -> usize {
-    // Convert nanos to seconds for dome_order_events timestamp column
-    let start_sec = start_ns / NANOS_PER_SEC;
-    let end_sec = end_ns / NANOS_PER_SEC;
-
-    let query = r#"
-        SELECT 
-            timestamp,
-            market_slug,
-            json_extract(payload_json, '$.side') as side,
-            json_extract(payload_json, '$.price') as price,
-            json_extract(payload_json, '$.shares_normalized') as shares,
-            json_extract(payload_json, '$.token_label') as outcome
-        FROM dome_order_events
-        WHERE market_slug LIKE ?
-          AND timestamp >= ? AND timestamp <= ?
-        ORDER BY timestamp ASC
-    "#;
-
-    let pattern = format!("%{}%", market_id);
-    let mut count = 0;
-
-    // Track synthetic book state per token
-    let mut synthetic_books: HashMap<String, (Vec<Level>, Vec<Level>)> = HashMap::new();
-
-    if let Ok(mut stmt) = conn.prepare(query) {
-        if let Ok(rows) = stmt.query_map(
-            rusqlite::params![&pattern, start_sec, end_sec],
-            |row| {
-                Ok((
-                    row.get::<_, i64>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, Option<f64>>(3)?,
-                    row.get::<_, Option<f64>>(4)?,
-                    row.get::<_, Option<String>>(5)?,
-                ))
-            },
-        ) {
-            for row_result in rows {
-                if let Ok((timestamp, market_slug, side_opt, price_opt, shares_opt, outcome_opt)) =
-                    row_result
-                {
-                    let side_str = side_opt.unwrap_or_default();
-                    let price = price_opt.unwrap_or(0.5);
-                    let shares = shares_opt.unwrap_or(0.0);
-                    let outcome = outcome_opt.unwrap_or_else(|| "Yes".to_string());
-
-                    let time_ns = timestamp * NANOS_PER_SEC;
-                    let token_id = format!("{}_{}", market_slug, outcome);
-
-                    let aggressor_side = if side_str.eq_ignore_ascii_case("BUY") {
-                        Side::Buy
-                    } else {
-                        Side::Sell
-                    };
-
-                    // Create trade print from dome order
-                    let trade_event = Event::TradePrint {
-                        token_id: token_id.clone(),
-                        price,
-                        size: shares,
-                        aggressor_side,
-                        trade_id: Some(format!("dome_{}", *seq)),
-                    };
-
-                    events.push(TimestampedEvent {
-                        time: time_ns,
-                        source_time: time_ns,
-                        seq: *seq,
-                        source: 2,
-                        event: trade_event,
-                    });
-                    *seq += 1;
-
-                    // Build/update synthetic orderbook
-                    let (bids, asks) = synthetic_books.entry(token_id.clone()).or_insert_with(|| {
-                        (
-                            vec![
-                                Level::new(0.48, 100.0),
-                                Level::new(0.47, 200.0),
-                                Level::new(0.46, 300.0),
-                            ],
-                            vec![
-                                Level::new(0.52, 100.0),
-                                Level::new(0.53, 200.0),
-                                Level::new(0.54, 300.0),
-                            ],
-                        )
-                    });
-
-                    // Adjust book based on trade
-                    if aggressor_side == Side::Buy {
-                        if let Some(ask) = asks.first_mut() {
-                            ask.price = price;
-                            ask.size = (ask.size - shares).max(10.0);
-                        }
-                    } else {
-                        if let Some(bid) = bids.first_mut() {
-                            bid.price = price;
-                            bid.size = (bid.size - shares).max(10.0);
-                        }
-                    }
-
-                    bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
-                    asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
-
-                    let book_event = Event::L2BookSnapshot {
-                        token_id,
-                        bids: bids.clone(),
-                        asks: asks.clone(),
-                        exchange_seq: *seq,
-                    };
-
-                    events.push(TimestampedEvent {
-                        time: time_ns + NANOS_PER_MILLI,
-                        source_time: time_ns,
-                        seq: *seq,
-                        source: 3,
-                        event: book_event,
-                    });
-                    *seq += 1;
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    count
-}
-
-fn parse_levels(json_str: &str) -> Vec<Level> {
-    // Try parsing as array of [price, size] pairs
-    if let Ok(arr) = serde_json::from_str::<Vec<[f64; 2]>>(json_str) {
-        return arr.iter().map(|[p, s]| Level::new(*p, *s)).collect();
-    }
-    // Try parsing as array of objects
-    if let Ok(arr) = serde_json::from_str::<Vec<serde_json::Value>>(json_str) {
-        return arr
-            .iter()
-            .filter_map(|v| {
-                let price = v.get("price")?.as_f64()?;
-                let size = v.get("size").or(v.get("quantity"))?.as_f64()?;
-                Some(Level::new(price, size))
-            })
-            .collect();
-    }
-    vec![]
-}
-
 // =============================================================================
 // MAIN
 // =============================================================================
@@ -599,7 +572,7 @@ fn main() {
     if args.verbose {
         eprintln!("Backtest Runner v{}", env!("CARGO_PKG_VERSION"));
         eprintln!("  Strategy: {}", args.strategy_name);
-        eprintln!("  Market:   {}", args.market_id);
+        eprintln!("  Markets:  {}", args.market_ids.join(", "));
         eprintln!("  Start:    {}", args.start_time);
         eprintln!("  End:      {}", args.end_time);
         eprintln!("  Seed:     {}", args.seed);
@@ -609,38 +582,68 @@ fn main() {
     let start_ns = args.start_time.timestamp_nanos_opt().unwrap_or(0);
     let end_ns = args.end_time.timestamp_nanos_opt().unwrap_or(0);
 
-    // Load events from database
-    let events = match load_events_from_db(
-        &args.db_path,
-        &args.market_id,
-        start_ns,
-        end_ns,
-        args.verbose,
-    ) {
-        Ok(e) => e,
-        Err(e) => {
-            eprintln!("Error loading data: {}", e);
-            std::process::exit(3);
+    // Load events from the dataset; recorder storage is streamed during the run instead
+    let events = args.db_path.as_ref().map(|db_path| {
+        if args.verbose {
+            eprintln!("Opening database: {}", db_path);
         }
-    };
+        match load_events_from_sqlite(db_path, &args.market_ids, start_ns, end_ns) {
+            Ok((events, counts)) => {
+                if args.verbose {
+                    for c in &counts {
+                        eprintln!(
+                            "Loaded {}: {} book snapshots, {} trade prints, {} dome order events",
+                            c.market_id, c.book_snapshots, c.trade_prints, c.dome_order_events
+                        );
+                    }
+                    eprintln!("Total events loaded: {}", events.len());
+                }
+                events
+            }
+            Err(e) => {
+                eprintln!("Error loading data: {}", e);
+                std::process::exit(3);
+            }
+        }
+    });
 
     // Create strategy
     let params = StrategyParams::new().with_param("seed", args.seed as f64);
-    let mut strategy = match make_strategy(&args.strategy_name, &params) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            print_strategies();
-            std::process::exit(2);
+    let mut strategy_id: Option<StrategyId> = None;
+    let mut strategy = if let Some(ref path) = args.strategy_wasm {
+        match WasmStrategyFactory::from_file(path, args.strategy_version.clone()) {
+            Ok(factory) => {
+                let sid = factory.strategy_id();
+                if args.verbose {
+                    eprintln!("Loaded WASM strategy: {}", sid.format_full());
+                }
+                strategy_id = Some(sid);
+                factory.create(params.clone())
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        match make_strategy(&args.strategy_name, &params) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                print_strategies();
+                std::process::exit(2);
+            }
         }
     };
 
     // Build backtest config
-    let config = if args.allow_non_production {
+    let mut config = if args.allow_non_production {
         // Research/non-production mode
         BacktestConfig {
             seed: args.seed,
             verbose: args.verbose,
+            risk_limits: args.risk_limits.clone(),
+            strategy_id: strategy_id.clone(),
             ..BacktestConfig::research_mode()
         }
     } else {
@@ -648,10 +651,31 @@ fn main() {
         BacktestConfig {
             seed: args.seed,
             verbose: args.verbose,
+            risk_limits: args.risk_limits.clone(),
+            strategy_id: strategy_id.clone(),
             ..BacktestConfig::production_grade_15m_updown()
         }
     };
 
+    // Replace latency presets with live measurements
+    if let Some(ref path) = args.latency_profile {
+        match LiveLatencyProfile::load(path) {
+            Ok(profile) => {
+                if args.verbose {
+                    eprintln!(
+                        "Loaded latency profile: venue={} captured_at={}",
+                        profile.venue, profile.captured_at
+                    );
+                }
+                config.latency = profile.apply_to(&config.latency);
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
     // Validate production-grade requirements
     if config.production_grade {
         if let Err(violation) = config.validate_production_grade() {
@@ -666,12 +690,29 @@ fn main() {
         }
     }
 
+    // Sweep mode: fan the sampled parameter sets out across workers and exit
+    if !args.sweep_axes.is_empty() {
+        run_sweep(&args, &config, events.as_deref().unwrap_or_default());
+    }
+
     // Create feed and orchestrator
-    let mut feed = VecFeed::new("dataset", events);
     let mut orchestrator = BacktestOrchestrator::new(config.clone());
 
     // Load feed into orchestrator
-    if let Err(e) = orchestrator.load_feed(&mut feed) {
+    let loaded = match events {
+        Some(events) => orchestrator
+            .load_feed(&mut VecFeed::new("dataset", events))
+            .map_err(|e| e.to_string()),
+        None => open_recorder_feed(&args, start_ns, end_ns).and_then(|feed| {
+            if args.verbose {
+                eprintln!("Streaming {} recorder sources", feed.source_count());
+            }
+            orchestrator
+                .load_feed_streaming(Box::new(feed))
+                .map_err(|e| e.to_string())
+        }),
+    };
+    if let Err(e) = loaded {
         eprintln!("Error loading feed: {}", e);
         std::process::exit(3);
     }
@@ -720,10 +761,15 @@ fn main() {
     let output = BacktestRunOutput {
         config_summary: ConfigSummary {
             strategy_name: args.strategy_name.clone(),
-            market_id: args.market_id.clone(),
+            market_ids: args.market_ids.clone(),
             start_time: args.start_time.to_rfc3339(),
             end_time: args.end_time.to_rfc3339(),
-            db_path: args.db_path.clone(),
+            db_path: args.db_path.clone().unwrap_or_default(),
+            recorder_db_paths: [&args.l2_db_path, &args.trades_db_path, &args.oracle_db_path]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
             seed: args.seed,
             production_grade: config.production_grade,
             allow_non_production: config.allow_non_production,
@@ -790,6 +836,152 @@ fn main() {
     std::process::exit(exit_code);
 }
 
+/// Merge the recorder storages named on the command line into one feed that
+/// loads `chunk_span_ns` of each source at a time.
+fn open_recorder_feed(
+    args: &CliArgs,
+    start_ns: Nanos,
+    end_ns: Nanos,
+) -> Result<StreamingMergeFeed, String> {
+    // The recorder storages create missing databases on open
+    let existing = |path: &String| {
+        if Path::new(path).exists() {
+            Ok(())
+        } else {
+            Err(format!("Recorder database not found: {}", path))
+        }
+    };
+    let span = args.chunk_span_ns;
+    let mut sources: Vec<Box<dyn EventChunkSource>> = Vec::new();
+
+    if let Some(ref path) = args.l2_db_path {
+        let storage = L2Storage::open_read_only(path)
+            .map_err(|e| format!("Failed to open L2 storage: {}", e))?;
+        let storage = Arc::new(storage);
+        for token_id in &args.token_ids {
+            sources.push(Box::new(L2ChunkSource::new(
+                storage.clone(),
+                token_id.clone(),
+                start_ns,
+                end_ns,
+                span,
+            )));
+        }
+    }
+
+    if let Some(ref path) = args.trades_db_path {
+        existing(path)?;
+        let storage = TradePrintFullStorage::open(path)
+            .map_err(|e| format!("Failed to open trade print storage: {}", e))?;
+        let storage = Arc::new(storage);
+        for market_id in &args.market_ids {
+            sources.push(Box::new(TradePrintChunkSource::new(
+                storage.clone(),
+                market_id.clone(),
+                start_ns,
+                end_ns,
+                span,
+            )));
+        }
+    }
+
+    if let Some(ref path) = args.oracle_db_path {
+        existing(path)?;
+        let storage = OracleRoundStorage::open(OracleStorageConfig {
+            db_path: path.clone(),
+            ..Default::default()
+        })
+        .map_err(|e| format!("Failed to open oracle storage: {}", e))?;
+        let storage = Arc::new(storage);
+        for feed_id in &args.oracle_feed_ids {
+            sources.push(Box::new(OracleRoundChunkSource::new(
+                storage.clone(),
+                feed_id.clone(),
+                start_ns,
+                end_ns,
+                span,
+            )));
+        }
+    }
+
+    Ok(StreamingMergeFeed::new("recorder", sources))
+}
+
+fn run_sweep(args: &CliArgs, config: &BacktestConfig, events: &[TimestampedEvent]) -> ! {
+    let mut base_params = BTreeMap::new();
+    base_params.insert("seed".to_string(), args.seed as f64);
+
+    let sweep = ParamSweepConfig {
+        strategy_name: args.strategy_name.clone(),
+        base_params,
+        axes: args.sweep_axes.clone(),
+        sampling: args.sweep_sampling,
+        sampling_seed: args.seed,
+        max_workers: args.sweep_workers,
+    };
+
+    let runner = match ParamSweepRunner::new(sweep, config.clone()) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Invalid sweep: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let store = match args.artifact_db_path.as_ref().map(ArtifactStore::new) {
+        Some(Ok(store)) => Some(store),
+        Some(Err(e)) => {
+            eprintln!("Error opening artifact store: {}", e);
+            std::process::exit(3);
+        }
+        None => None,
+    };
+
+    if args.verbose {
+        eprintln!(
+            "\nRunning sweep {} ({} points)...",
+            runner.config().sweep_id(),
+            runner.config().point_count()
+        );
+    }
+
+    let report = match runner.run(events, store.as_ref()) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Sweep error: {}", e);
+            std::process::exit(3);
+        }
+    };
+
+    eprintln!("\n{}", report.format_text());
+    if let Some(best) = report.best_by_pnl() {
+        eprintln!("Best by PnL: #{} (${:.2})", best.point.index, best.final_pnl);
+    }
+
+    let json = serde_json::to_string_pretty(&report).unwrap_or_else(|e| {
+        eprintln!("JSON serialization error: {}", e);
+        std::process::exit(3);
+    });
+    match args.output_path {
+        Some(ref path) => {
+            if let Err(e) = write_output_atomic(path, &json) {
+                eprintln!("Error writing output: {}", e);
+                std::process::exit(3);
+            }
+        }
+        None => println!("{}", json),
+    }
+
+    let exit_code = if report.failed_count() > 0 {
+        3
+    } else if report.outcomes.iter().all(|o| o.trusted) {
+        0
+    } else {
+        1
+    };
+    std::process::exit(exit_code);
+}
+
 fn write_output_atomic(path: &str, content: &str) -> Result<(), String> {
     let path = PathBuf::from(path);
 
@@ -846,6 +1038,19 @@ fn print_summary(results: &BacktestResults, exit_code: i32, exit_reason: &str) {
         eprintln!("Run Fingerprint:    {}", fp.format_compact());
     }
 
+    if results.market_attribution.len() > 1 {
+        eprintln!("{}", "-".repeat(70));
+        eprintln!("Per-Market Attribution:");
+        eprint!("{}", format_attribution(&results.market_attribution));
+    }
+
+    if let Some(ref risk) = results.portfolio_risk {
+        eprintln!(
+            "Portfolio Risk:     {} checked, {} blocked, peak gross ${:.2}",
+            risk.orders_checked, risk.orders_blocked, risk.peak_gross_exposure
+        );
+    }
+
     eprintln!("{}", "-".repeat(70));
     eprintln!("Exit Code:          {}", exit_code);
     eprintln!("Exit Reason:        {}", exit_reason);
@@ -0,0 +1,248 @@
+//! Columnar Dataset Export CLI
+//!
+//! Converts recorder databases into a partitioned Parquet dataset (see
+//! `backtest_v2::columnar_dataset`) and checks existing datasets against their
+//! manifest.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --bin columnar_export -- export \
+//!   --out datasets/2026-01-24 \
+//!   --book-db book_snapshots.db --trades-db trade_prints.db \
+//!   --l2-db l2_deltas.db --token 0xabc... \
+//!   --oracle-db chainlink_rounds.db --oracle-feed btc_usd \
+//!   --start 2026-01-24T00:00:00Z --end 2026-01-25T00:00:00Z
+//!
+//! cargo run --bin columnar_export -- verify datasets/2026-01-24
+//! ```
+//!
+//! # Exit Codes
+//!
+//! - 0: Success
+//! - 1: Dataset does not match its manifest
+//! - 2: Error (bad arguments, missing database, I/O failure)
+
+use betterbot_backend::backtest_v2::{
+    BookSnapshotStorage, ColumnarDataset, ColumnarExportConfig, ColumnarExporter, ColumnarManifest,
+    ColumnarQuery, ColumnarReplayFeed, DatasetReadinessClassifier, HistoricalDataContract,
+    L2Storage, MarketDataFeed, OracleRoundStorage, OracleStorageConfig, TradePrintFullStorage,
+    DEFAULT_COLUMNAR_BATCH_ROWS,
+};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+/// Export recorder databases to Parquet and verify columnar datasets
+#[derive(Parser, Debug)]
+#[command(name = "columnar_export")]
+#[command(about = "Convert recorded market data into a columnar (Parquet) dataset")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Export a time range from recorder databases into a new dataset
+    Export {
+        /// Dataset root to create (must not already hold a dataset)
+        #[arg(short, long)]
+        out: PathBuf,
+
+        /// Start time (RFC3339)
+        #[arg(short, long)]
+        start: DateTime<Utc>,
+
+        /// End time (RFC3339)
+        #[arg(short, long)]
+        end: DateTime<Utc>,
+
+        /// Book snapshot recorder database
+        #[arg(long)]
+        book_db: Option<String>,
+
+        /// Trade print recorder database
+        #[arg(long)]
+        trades_db: Option<String>,
+
+        /// L2 snapshot/delta database
+        #[arg(long, requires = "token")]
+        l2_db: Option<String>,
+
+        /// Token to export from --l2-db (repeatable)
+        #[arg(long, requires = "l2_db")]
+        token: Vec<String>,
+
+        /// Chainlink round database
+        #[arg(long, requires = "oracle_feed")]
+        oracle_db: Option<String>,
+
+        /// Feed to export from --oracle-db (repeatable)
+        #[arg(long, requires = "oracle_db")]
+        oracle_feed: Vec<String>,
+
+        /// Rows per Parquet row group
+        #[arg(long, default_value_t = DEFAULT_COLUMNAR_BATCH_ROWS)]
+        batch_rows: usize,
+    },
+
+    /// Recompute the dataset fingerprint and replay every partition
+    Verify {
+        /// Dataset root
+        root: PathBuf,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Commands::Export {
+            out,
+            start,
+            end,
+            book_db,
+            trades_db,
+            l2_db,
+            token,
+            oracle_db,
+            oracle_feed,
+            batch_rows,
+        } => export(
+            &out,
+            start,
+            end,
+            book_db.as_deref(),
+            trades_db.as_deref(),
+            l2_db.as_deref(),
+            &token,
+            oracle_db.as_deref(),
+            &oracle_feed,
+            batch_rows,
+        )
+        .map(|manifest| print_manifest(&out, &manifest)),
+        Commands::Verify { root } => ColumnarDataset::open(&root).map(|dataset| {
+            if let Err(e) = verify(&root, &dataset) {
+                eprintln!("Verification failed: {}", e);
+                std::process::exit(1);
+            }
+        }),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(2);
+    }
+}
+
+/// The recorder storages create missing databases on open.
+fn existing(path: &str) -> Result<&str, String> {
+    if Path::new(path).exists() {
+        Ok(path)
+    } else {
+        Err(format!("Recorder database not found: {}", path))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn export(
+    out: &Path,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    book_db: Option<&str>,
+    trades_db: Option<&str>,
+    l2_db: Option<&str>,
+    tokens: &[String],
+    oracle_db: Option<&str>,
+    oracle_feeds: &[String],
+    batch_rows: usize,
+) -> Result<ColumnarManifest, String> {
+    if book_db.is_none() && trades_db.is_none() && l2_db.is_none() && oracle_db.is_none() {
+        return Err(
+            "Nothing to export: pass --book-db, --trades-db, --l2-db or --oracle-db".to_string(),
+        );
+    }
+    if end <= start {
+        return Err("--end must be after --start".to_string());
+    }
+    let start_ns = start.timestamp_nanos_opt().ok_or("--start out of range")?;
+    let end_ns = end.timestamp_nanos_opt().ok_or("--end out of range")?;
+
+    let contract = HistoricalDataContract::polymarket_15m_updown_snapshots_and_trades();
+    let readiness = DatasetReadinessClassifier::new().classify_quick(&contract);
+    let config = ColumnarExportConfig {
+        batch_rows,
+        ..ColumnarExportConfig::new(contract, readiness)
+    };
+    let mut exporter = ColumnarExporter::create(out, config)?;
+
+    if let Some(path) = book_db {
+        let storage = BookSnapshotStorage::open(existing(path)?)
+            .map_err(|e| format!("Failed to open book snapshot storage: {}", e))?;
+        let n = exporter.export_book_snapshots(&storage, start_ns as u64, end_ns as u64)?;
+        eprintln!("Exported {} book snapshots", n);
+    }
+
+    if let Some(path) = trades_db {
+        let storage = TradePrintFullStorage::open(existing(path)?)
+            .map_err(|e| format!("Failed to open trade print storage: {}", e))?;
+        let n = exporter.export_trade_prints(&storage, start_ns, end_ns)?;
+        eprintln!("Exported {} trade prints", n);
+    }
+
+    if let Some(path) = l2_db {
+        let storage = L2Storage::open_read_only(path)
+            .map_err(|e| format!("Failed to open L2 storage: {}", e))?;
+        for token_id in tokens {
+            let n = exporter.export_l2_storage(&storage, token_id, start_ns, end_ns)?;
+            eprintln!("Exported {} L2 events for {}", n, token_id);
+        }
+    }
+
+    if let Some(path) = oracle_db {
+        let storage = OracleRoundStorage::open(OracleStorageConfig {
+            db_path: existing(path)?.to_string(),
+            ..Default::default()
+        })
+        .map_err(|e| format!("Failed to open oracle storage: {}", e))?;
+        for feed_id in oracle_feeds {
+            let n = exporter.export_oracle_rounds(
+                &storage,
+                feed_id,
+                start.timestamp() as u64,
+                end.timestamp() as u64,
+            )?;
+            eprintln!("Exported {} oracle rounds for {}", n, feed_id);
+        }
+    }
+
+    exporter.finish()
+}
+
+fn verify(root: &Path, dataset: &ColumnarDataset) -> Result<(), String> {
+    dataset.verify()?;
+
+    // Replay every market through the streaming feed as well
+    let mut feed = ColumnarReplayFeed::open(dataset, &ColumnarQuery::all())?;
+    let mut replayed = 0u64;
+    while feed.next_event().is_some() {
+        replayed += 1;
+    }
+    if let Some(e) = feed.error() {
+        return Err(e.to_string());
+    }
+
+    print_manifest(root, dataset.manifest());
+    println!("Replayed:    {} market events", replayed);
+    println!("Fingerprint: OK");
+    Ok(())
+}
+
+fn print_manifest(root: &Path, manifest: &ColumnarManifest) {
+    println!("Dataset:     {}", root.display());
+    println!("Partitions:  {}", manifest.partitions.len());
+    println!("Rows:        {}", manifest.total_rows());
+    println!("Readiness:   {}", manifest.dataset_fingerprint.readiness);
+    println!("Hash:        {:016x}", manifest.dataset_fingerprint.hash);
+}
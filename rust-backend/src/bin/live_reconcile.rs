@@ -0,0 +1,104 @@
+//! Live-vs-Backtest Reconciliation CLI
+//!
+//! Replays a recorded live session (`LIVE_STRATEGY_RECORD_PATH` journal)
+//! through the backtest over the market data captured by the unified recorder,
+//! and prints a per-order reconciliation: fill vs no-fill, price and timing
+//! deltas, and PnL drift.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --bin live_reconcile -- \
+//!   --session live_session.jsonl \
+//!   --db polymarket_historical.db \
+//!   --match-window-ms 5000
+//! ```
+//!
+//! # Exit Codes
+//!
+//! - 0: Every order reconciled within tolerance
+//! - 1: Discrepancies found
+//! - 2: Error (bad journal, missing data, backtest failure)
+
+use betterbot_backend::backtest_v2::clock::NANOS_PER_MILLI;
+use betterbot_backend::backtest_v2::unified_recorder::{UnifiedRecorderConfig, UnifiedStorage};
+use betterbot_backend::backtest_v2::{LiveReconciler, LiveSession, ReconciliationConfig};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Reconcile a live session against its backtest replay
+#[derive(Parser, Debug)]
+#[command(name = "live_reconcile")]
+#[command(about = "Reconcile a live strategy session against a backtest replay")]
+struct Cli {
+    /// Live session journal written by the strategy host
+    #[arg(short, long)]
+    session: PathBuf,
+
+    /// Unified recorder SQLite database
+    #[arg(short, long, default_value = "polymarket_historical.db")]
+    db: String,
+
+    /// Maximum submit time distance for pairing live and backtest orders (ms)
+    #[arg(long, default_value_t = 5_000)]
+    match_window_ms: i64,
+
+    /// Market data replayed before the session start (ms)
+    #[arg(long, default_value_t = 60_000)]
+    warmup_ms: i64,
+
+    /// Print the report as JSON instead of text
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let session = match LiveSession::read_from(&cli.session) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let storage = match UnifiedStorage::open(UnifiedRecorderConfig {
+        db_path: cli.db.clone(),
+        ..Default::default()
+    }) {
+        Ok(storage) => storage,
+        Err(e) => {
+            eprintln!("Error: failed to open recorder storage {}: {}", cli.db, e);
+            std::process::exit(2);
+        }
+    };
+
+    let reconciler = LiveReconciler::new(ReconciliationConfig {
+        match_window_ns: cli.match_window_ms * NANOS_PER_MILLI,
+        warmup_ns: cli.warmup_ms * NANOS_PER_MILLI,
+        ..Default::default()
+    });
+    let report = match reconciler.reconcile_from_storage(&session, &storage) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if cli.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error: failed to serialize report: {}", e);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        print!("{}", report.format_report());
+    }
+
+    let matched = report.summary.by_class.get("MATCHED").copied().unwrap_or(0);
+    let total = report.orders.len() as u64;
+    std::process::exit(if matched == total { 0 } else { 1 });
+}
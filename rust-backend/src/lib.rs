@@ -8,6 +8,7 @@ pub mod backtest_v2;
 pub mod edge;
 pub mod performance;
 pub mod route_quality;
+pub mod venue_limits;
 
 // Re-export latency at crate root for compatibility
 pub use performance::latency;
@@ -19,9 +19,11 @@ mod middleware; // Request logging and rate limiting
 mod models;
 mod performance; // Comprehensive performance profiling
 mod risk;
+mod route_quality; // Network route health (alerts feed the trading halt controls)
 mod scrapers;
 mod signals;
 mod vault; // Phase 8: User deposits & Kelly auto-trading
+mod venue_limits; // Venue hard limits shared by live execution and the backtest OMS
 
 use anyhow::{Context, Result};
 use axum::{
@@ -208,6 +210,12 @@ struct AppState {
     /// Optimized bookTicker feed (simd-json, zero-alloc, last-value snapshot)
     binance_book_ticker: Option<Arc<BinanceBookTickerFeed>>,
     vault: Arc<crate::vault::PooledVault>,
+    /// Kill switch and trading halts (operator + automatic triggers)
+    trading_control: Arc<crate::vault::TradingControl>,
+    /// Versioned strategy parameters (hot reload at 15m window boundaries)
+    strategy_config: Arc<crate::vault::StrategyConfigStore>,
+    /// Per-strategy capital budgets, checked before every vault buy
+    capital_allocator: Arc<crate::vault::CapitalAllocator>,
     /// Latency registry for reactive FAST15M engine (if enabled)
     fast15m_latency_registry:
         Arc<ParkingRwLock<Option<Arc<ParkingRwLock<crate::vault::Fast15mLatencyRegistry>>>>>,
@@ -393,26 +401,111 @@ async fn main() -> Result<()> {
     let (vault_cash_db, vault_total_shares_db) = vault_db.load_state().await.unwrap_or((0.0, 0.0));
     let vault_user_shares_db = vault_db.load_user_shares().await.unwrap_or_default();
 
-    let vault_initial_cash = if vault_cash_db > 0.0 {
+    // Inventory journaled before the last shutdown (reconciled with the venue
+    // when the vault engine starts).
+    let vault_journaled_positions = vault_db.load_positions().await.unwrap_or_default();
+
+    let vault_initial_cash = if vault_cash_db > 0.0 || !vault_journaled_positions.is_empty() {
         vault_cash_db
     } else {
         initial_bankroll
     };
     risk_manager.write().kelly.bankroll = vault_initial_cash;
 
-    let vault_ledger = Arc::new(tokio::sync::Mutex::new(crate::vault::VaultPaperLedger {
-        cash_usdc: vault_initial_cash,
-        ..Default::default()
-    }));
+    let vault_ledger = match crate::vault::restore_ledger(&vault_db, vault_initial_cash).await {
+        Ok(ledger) => {
+            if !ledger.positions.is_empty() {
+                info!("🏦 Restored {} journaled vault positions", ledger.positions.len());
+            }
+            ledger
+        }
+        Err(e) => {
+            warn!("Failed to restore journaled vault positions: {e}");
+            crate::vault::VaultPaperLedger {
+                cash_usdc: vault_initial_cash,
+                ..Default::default()
+            }
+        }
+    };
+    let vault_ledger = Arc::new(tokio::sync::Mutex::new(vault_ledger));
     let vault_shares = Arc::new(tokio::sync::Mutex::new(crate::vault::VaultShareState {
         total_shares: vault_total_shares_db,
         user_shares: vault_user_shares_db,
     }));
-    let vault = Arc::new(crate::vault::PooledVault::new(
+    // Per-user vault statements live alongside the user accounts.
+    let user_accounts_db_path =
+        resolve_data_path(env::var("USER_ACCOUNTS_DB_PATH").ok(), "betterbot_users.db");
+    let user_accounts = match crate::vault::UserAccountsDB::new(&user_accounts_db_path) {
+        Ok(db) => Some(Arc::new(db)),
+        Err(e) => {
+            warn!("Failed to open user accounts DB; vault statements disabled: {e}");
+            None
+        }
+    };
+    let vault = crate::vault::PooledVault::new(vault_db.clone(), vault_ledger, vault_shares)
+        .with_accounts(crate::vault::ShareAccountingConfig::from_env(), user_accounts);
+    if let Err(e) = vault.load_last_strike().await {
+        warn!("Failed to load last vault NAV strike; strikes wait until it loads: {e}");
+    }
+    let vault = Arc::new(vault);
+
+    let trading_control = match crate::vault::TradingControl::load(
+        crate::vault::TradingControlConfig::from_env(),
         vault_db.clone(),
-        vault_ledger,
-        vault_shares,
-    ));
+    )
+    .await
+    {
+        Ok(control) => Arc::new(control),
+        Err(e) => {
+            warn!("Failed to load persisted trading halts: {e}");
+            Arc::new(crate::vault::TradingControl::new(
+                crate::vault::TradingControlConfig::from_env(),
+                Some(vault_db.clone()),
+            ))
+        }
+    };
+
+    // Strategy parameters: env, overlaid by STRATEGY_CONFIG_PATH when present.
+    let strategy_config_path = env::var("STRATEGY_CONFIG_PATH").ok().map(PathBuf::from);
+    let strategy_config = match crate::vault::StrategyConfigStore::load(
+        crate::vault::VaultStrategyParams::from_env(),
+        vault_db.clone(),
+        strategy_config_path.clone(),
+    )
+    .await
+    {
+        Ok(store) => Arc::new(store),
+        Err(e) => {
+            warn!("Failed to load strategy config; using env (versions not recorded): {e}");
+            Arc::new(crate::vault::StrategyConfigStore::new(
+                crate::vault::VaultStrategyParams::from_env(),
+                strategy_config_path,
+            ))
+        }
+    };
+    strategy_config.spawn_file_watcher(Duration::from_secs(5));
+
+    // Capital budgets across vault strategies, rebuilt from trade history.
+    let capital_allocator = match crate::vault::CapitalAllocator::load(
+        crate::vault::CapitalAllocatorConfig::from_env(),
+        vault_db.clone(),
+        &vault,
+    )
+    .await
+    {
+        Ok(allocator) => Arc::new(allocator),
+        Err(e) => {
+            warn!("Failed to rebuild capital allocation from trade history: {e}");
+            let allocator = Arc::new(crate::vault::CapitalAllocator::new(
+                crate::vault::CapitalAllocatorConfig::from_env(),
+                Some(vault_db.clone()),
+            ));
+            let nav = crate::vault::approximate_nav_usdc(&*vault.ledger.lock().await);
+            allocator.rebalance(nav, Utc::now().timestamp()).await;
+            allocator
+        }
+    };
+    capital_allocator.spawn_rebalance_loop(vault.clone());
 
     let _ = vault
         .db
@@ -468,12 +561,17 @@ async fn main() -> Result<()> {
         .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
         .unwrap_or(false);
 
+    // Optional: backtest_v2 strategy running live (enable via LIVE_STRATEGY_ENABLED=1)
+    let live_strategy_enabled = env::var("LIVE_STRATEGY_ENABLED")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
+        .unwrap_or(false);
+
     // Optional: HFT-grade book cache (enable via HFT_BOOK_CACHE_ENABLED=1)
     let hft_book_cache_enabled = env::var("HFT_BOOK_CACHE_ENABLED")
         .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
         .unwrap_or(false);
 
-    let hft_book_cache = if hft_book_cache_enabled || orderflow_paper_enabled {
+    let hft_book_cache = if hft_book_cache_enabled || orderflow_paper_enabled || live_strategy_enabled {
         info!("📚 Spawning HFT-grade book cache (no REST in hot path)");
         Some(crate::scrapers::HftBookCache::spawn())
     } else {
@@ -487,7 +585,8 @@ async fn main() -> Result<()> {
 
     let (unified_15m_strategy, unified_15m_metrics) = if unified_15m_enabled && binance_enabled {
         info!("🎯 Initializing UNIFIED 15M Strategy (PRODUCTION MODE)");
-        let cfg = crate::vault::Unified15mConfig::from_env();
+        let active_config = strategy_config.current();
+        let cfg = active_config.params.unified_15m.clone();
         info!(
             "   min_edge: {:.1}%, kelly: {:.0}%, max_pos: ${:.0}, bankroll: ${:.0}",
             cfg.min_edge * 100.0,
@@ -496,11 +595,12 @@ async fn main() -> Result<()> {
             cfg.bankroll
         );
 
-        let strategy = crate::vault::Unified15mStrategy::new(
-            cfg,
+        let mut strategy = crate::vault::Unified15mStrategy::new(
+            cfg.clone(),
             binance_feed.clone(),
             belief_vol_tracker.clone(),
         );
+        strategy.apply_config(active_config.version, cfg);
         let metrics = strategy.metrics();
         (
             Some(Arc::new(tokio::sync::Mutex::new(strategy))),
@@ -564,6 +664,9 @@ async fn main() -> Result<()> {
         binance_arb_feed,
         binance_book_ticker,
         vault,
+        trading_control: trading_control.clone(),
+        strategy_config: strategy_config.clone(),
+        capital_allocator: capital_allocator.clone(),
         fast15m_latency_registry: Arc::new(ParkingRwLock::new(None)), // Will be set if reactive engine is enabled
         latency_registry: latency_registry.clone(),
         performance_profiler: performance_profiler.clone(),
@@ -597,8 +700,21 @@ async fn main() -> Result<()> {
     });
 
     // Phase 8: Vault engine (15m deterministic, non-15m router stub).
+    // Book staleness feeds the automatic trading halts.
+    if let Some(cache) = app_state.hft_book_cache.as_ref() {
+        app_state
+            .trading_control
+            .spawn_feed_monitor(cache.book_store().clone());
+    }
+
     crate::vault::VaultEngine::spawn(app_state.clone());
 
+    // Vault NAV strikes: queued subscriptions/redemptions fill at each 15m boundary.
+    app_state
+        .vault
+        .clone()
+        .spawn_strike_loop(app_state.hft_book_cache.clone());
+
     // Phase 8b: Reactive FAST15M engine (event-driven, <10ms latency target)
     let reactive_fast15m_enabled = env::var("REACTIVE_FAST15M_ENABLED")
         .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
@@ -705,6 +821,62 @@ async fn main() -> Result<()> {
         });
     }
 
+    if live_strategy_enabled {
+        let live_cfg = crate::vault::LiveStrategyConfig::from_env();
+        match (
+            app_state.hft_book_cache.clone(),
+            crate::backtest_v2::make_strategy(&live_cfg.strategy_name, &live_cfg.params),
+        ) {
+            (Some(book_cache), Ok(strategy)) => {
+                let live_paper = env::var("VAULT_ENGINE_PAPER")
+                    .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
+                    .unwrap_or(true);
+                // Paper fills walk the live book unless PAPER_FILL_MODEL=synthetic
+                let synthetic_fills = env::var("PAPER_FILL_MODEL")
+                    .map(|v| v.eq_ignore_ascii_case("synthetic"))
+                    .unwrap_or(false);
+                let exec: Arc<dyn crate::vault::ExecutionAdapter> = if live_paper && synthetic_fills {
+                    Arc::new(crate::vault::PaperExecutionAdapter::default())
+                } else if live_paper {
+                    let book_paper = crate::vault::BookPaperExecutionAdapter::new(
+                        crate::vault::BookPaperConfig::from_env(),
+                        book_cache.book_store().clone(),
+                    );
+                    book_paper.spawn_trade_listener();
+                    Arc::new(book_paper)
+                } else {
+                    match crate::vault::PolymarketClobAdapter::from_env() {
+                        Some(clob) => Arc::new(clob),
+                        None => {
+                            warn!("VAULT_ENGINE_PAPER=false but POLYMARKET_CLOB_* env vars not set; falling back to paper");
+                            Arc::new(crate::vault::PaperExecutionAdapter::default())
+                        }
+                    }
+                };
+                info!(
+                    strategy = %live_cfg.strategy_name,
+                    tokens = live_cfg.tokens.len(),
+                    paper = live_paper,
+                    "🧠 Spawning live strategy host (backtest_v2 strategy)"
+                );
+                // The host keeps running when the handle is dropped.
+                crate::vault::spawn_live_strategy(
+                    book_cache,
+                    exec,
+                    strategy,
+                    live_cfg,
+                    Some(app_state.trading_control.clone()),
+                )
+                .await;
+            }
+            (None, _) => warn!("LIVE_STRATEGY_ENABLED=1 but HFT book cache unavailable; live strategy NOT started"),
+            (_, Err(e)) => warn!(
+                error = %e,
+                "LIVE_STRATEGY_ENABLED=1 but strategy could not be built; live strategy NOT started"
+            ),
+        }
+    }
+
     // Spawn parallel data collection tasks
     tokio::spawn(parallel_data_collection(
         signal_storage.clone(),
@@ -719,6 +891,7 @@ async fn main() -> Result<()> {
         signal_tx.clone(),
         app_state.unified_15m_strategy.clone(),
         app_state.binance_feed.clone(),
+        app_state.strategy_config.clone(),
     ));
 
     // Persist 15m Up/Down window start/end prices + outcomes (independent of frontend).
@@ -791,6 +964,18 @@ async fn main() -> Result<()> {
         .route("/api/ab-test/summary", get(api::get_ab_test_summary))
         .route("/api/vault/deposit", post(api::post_vault_deposit))
         .route("/api/vault/withdraw", post(api::post_vault_withdraw))
+        .route("/api/vault/requests", get(api::get_vault_share_requests))
+        .route(
+            "/api/vault/requests/cancel",
+            post(api::post_vault_cancel_request),
+        )
+        .route("/api/vault/statement", get(api::get_vault_statement))
+        .route("/api/vault/strikes", get(api::get_vault_strikes))
+        .route("/api/vault/allocations", get(api::get_vault_allocations))
+        .route(
+            "/api/vault/allocations/history",
+            get(api::get_vault_allocation_history),
+        )
         .route("/api/trade/order", post(api::post_trade_order))
         .route("/api/risk/stats", get(api::get_risk_stats_simple))
         .route("/api/latency/stats", get(api::get_latency_stats))
@@ -832,6 +1017,24 @@ async fn main() -> Result<()> {
         .route("/api/paper/start", post(api::post_paper_trading_start))
         .route("/api/paper/stop", post(api::post_paper_trading_stop))
         .route("/api/paper/reset", post(api::post_paper_trading_reset))
+        .route("/api/admin/trading/status", get(api::get_trading_status))
+        .route("/api/admin/trading/halt", post(api::post_trading_halt))
+        .route("/api/admin/trading/resume", post(api::post_trading_resume))
+        .route("/api/admin/trading/audit", get(api::get_trading_audit))
+        .route(
+            "/api/admin/allocations/rebalance",
+            post(api::post_allocation_rebalance),
+        )
+        .route("/api/admin/llm/eval", post(api::post_vault_llm_eval))
+        .route("/api/admin/latency/export", get(api::get_latency_export))
+        .route(
+            "/api/admin/strategy-config",
+            get(api::get_strategy_config).post(api::post_strategy_config),
+        )
+        .route(
+            "/api/admin/strategy-config/history",
+            get(api::get_strategy_config_history),
+        )
         .route("/api/auth/me", get(auth_api::get_current_user))
         .route("/ws", get(websocket_handler))
         .route_layer(axum_mw::from_fn_with_state(
@@ -850,11 +1053,39 @@ async fn main() -> Result<()> {
     // - /api/v2/backtest/* - authenticated routes (all runs)
     // - /api/public/v2/backtest/* - public routes (published runs only)
     let (backtest_v2_routes, backtest_v2_public_routes) = if let Some(ref store) = app_state.backtest_artifact_store {
+        // Job submission is only enabled when a dataset directory is configured
+        let job_queue = env::var("BACKTEST_DATASET_DIR").ok().map(|dir| {
+            let workers = env::var("BACKTEST_JOB_WORKERS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(2);
+            info!("🧪 Backtest job queue enabled: datasets={} workers={}", dir, workers);
+            Arc::new(crate::backtest_v2::BacktestJobQueue::new(
+                crate::backtest_v2::JobQueueConfig {
+                    workers,
+                    ..Default::default()
+                },
+                store.clone(),
+                Arc::new(crate::backtest_v2::SqliteDatasetLoader::new(dir)),
+            ))
+        });
         let backtest_v2_state = Arc::new(api::BacktestV2State {
             artifact_store: store.clone(),
+            job_queue,
         });
+        // Job routes read the caller's claims: they only run behind the auth middleware
+        let job_routes = api::backtest_v2_jobs_router()
+            .route_layer(axum_mw::from_fn_with_state(
+                jwt_handler.clone(),
+                auth_middleware,
+            ))
+            .with_state(backtest_v2_state.clone());
         (
-            Some(api::backtest_v2_router().with_state(backtest_v2_state.clone())),
+            Some(
+                api::backtest_v2_router()
+                    .with_state(backtest_v2_state.clone())
+                    .merge(job_routes),
+            ),
             Some(api::backtest_v2_public_router().with_state(backtest_v2_state)),
         )
     } else {
@@ -1483,6 +1714,7 @@ async fn tracked_wallet_polling(
     signal_tx: broadcast::Sender<WsServerEvent>,
     unified_strategy: Option<Arc<tokio::sync::Mutex<crate::vault::Unified15mStrategy>>>,
     binance_feed: Arc<BinancePriceFeed>,
+    strategy_config: Arc<crate::vault::StrategyConfigStore>,
 ) -> Result<()> {
     info!("👑 Starting tracked wallet STREAMING system");
 
@@ -1679,7 +1911,11 @@ async fn tracked_wallet_polling(
                 // Only process 15m up/down markets
                 if market_slug.contains("-updown-15m-") {
                     if let (Some(ref strategy), Some(ref outcome)) = (&unified_strategy, &token_label) {
+                        let active_config = strategy_config.active().await;
                         let mut strat = strategy.lock().await;
+                        if strat.config_version() != active_config.version {
+                            strat.apply_config(active_config.version, active_config.params.unified_15m.clone());
+                        }
                         if let Some(trade) = strat.on_order(&market_slug, outcome, price, timestamp) {
                             info!(
                                 "[UNIFIED] Trade recorded: {} {} @ {:.4} -> {:.4} (PnL: ${:.2})",
@@ -1764,7 +2000,11 @@ async fn tracked_wallet_polling(
                         // === UNIFIED 15M STRATEGY: Feed REST order for processing ===
                         if order.market_slug.contains("-updown-15m-") {
                             if let (Some(ref strategy), Some(ref outcome)) = (&unified_strategy, &order.token_label) {
+                                let active_config = strategy_config.active().await;
                                 let mut strat = strategy.lock().await;
+                                if strat.config_version() != active_config.version {
+                                    strat.apply_config(active_config.version, active_config.params.unified_15m.clone());
+                                }
                                 if let Some(trade) = strat.on_order(&order.market_slug, outcome, order.price, order.timestamp) {
                                     info!(
                                         "[UNIFIED] Trade recorded (REST): {} {} @ {:.4} -> {:.4} (PnL: ${:.2})",
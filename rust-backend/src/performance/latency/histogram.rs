@@ -219,6 +219,18 @@ impl LatencyHistogram {
         self.max_us.load(Ordering::Relaxed)
     }
 
+    /// Snapshot of `(upper_bound_us, count)` per bucket, lowest first.
+    /// Bucket `i` covers `(bound[i - 1], bound[i]]`; bounds are capped at the
+    /// observed max so the overflow bucket stays finite.
+    pub fn bucket_counts(&self) -> Vec<(u64, u64)> {
+        let max_us = self.max_us.load(Ordering::Acquire);
+        BUCKET_BOUNDS
+            .iter()
+            .zip(self.buckets.iter())
+            .map(|(&bound, b)| (bound.min(max_us), b.load(Ordering::Acquire)))
+            .collect()
+    }
+
     /// Get summary for serialization
     /// 
     /// Takes a snapshot of all atomic values for consistent reporting.
@@ -370,4 +382,18 @@ mod tests {
         assert_eq!(h.count(), 3);
         assert!(h.p99() >= 5_000_000);
     }
+
+    #[test]
+    fn test_histogram_bucket_counts() {
+        let h = LatencyHistogram::new();
+        h.record(15);
+        h.record(20);
+        h.record(25_000_000); // overflow
+
+        let buckets = h.bucket_counts();
+        assert_eq!(buckets.len(), NUM_BUCKETS);
+        assert_eq!(buckets.iter().map(|(_, c)| c).sum::<u64>(), 3);
+        assert!(buckets.contains(&(20, 2)));
+        assert_eq!(buckets.last(), Some(&(25_000_000, 1)));
+    }
 }
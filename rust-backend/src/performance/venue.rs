@@ -95,6 +95,15 @@ impl VenueLatencyTracker {
         self.venues.read().get(venue).map(|m| m.snapshot())
     }
 
+    /// Get raw histogram buckets for a venue (exported as empirical latency)
+    pub fn latency_buckets(&self, venue: &str) -> Option<VenueLatencyBuckets> {
+        self.venues.read().get(venue).map(|m| VenueLatencyBuckets {
+            send_ack: m.send_to_ack.bucket_counts(),
+            send_fill: m.send_to_fill.bucket_counts(),
+            cancel_ack: m.cancel_to_ack.bucket_counts(),
+        })
+    }
+
     /// Get aggregated stats across all venues
     pub fn aggregate(&self) -> AggregateVenueStats {
         let venues = self.venues.read();
@@ -222,6 +231,14 @@ pub struct VenueSnapshot {
     pub connect_p99_us: u64,
 }
 
+/// Venue round-trip histograms as `(upper_bound_us, count)` buckets
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueLatencyBuckets {
+    pub send_ack: Vec<(u64, u64)>,
+    pub send_fill: Vec<(u64, u64)>,
+    pub cancel_ack: Vec<(u64, u64)>,
+}
+
 /// Aggregated stats across all venues
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct AggregateVenueStats {
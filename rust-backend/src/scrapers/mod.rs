@@ -40,7 +40,7 @@ pub use binance_book_ticker_metrics::{
 // Re-export HFT book cache types for convenience
 pub use polymarket_book_store::{
     BookLookupResult, BookSnapshot, BookStore, BookStoreConfig, BookStoreMetrics, CacheMissReason,
-    HftBookCache, PriceLevel, SubscriptionManager, WarmupManager, WarmupStatus,
+    HftBookCache, PriceLevel, PublicTradePrint, SubscriptionManager, WarmupManager, WarmupStatus,
 };
 
 // Re-export Binance session management types
@@ -30,7 +30,7 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc, watch, Notify},
+    sync::{broadcast, mpsc, watch, Notify},
     time::{interval, sleep, timeout},
 };
 use tokio_tungstenite::{connect_async, tungstenite::Message};
@@ -280,6 +280,21 @@ pub struct PriceLevel {
     pub size: f64,
 }
 
+/// Public trade print from the market channel (`last_trade_price`)
+#[derive(Debug, Clone)]
+pub struct PublicTradePrint {
+    pub token_id: String,
+    pub price: f64,
+    pub size: f64,
+    /// Taker side was BUY
+    pub is_buy: bool,
+    /// Exchange timestamp (ms, 0 if absent)
+    pub source_time_ms: u64,
+}
+
+/// Capacity of the trade print broadcast channel
+const TRADE_CHANNEL_CAPACITY: usize = 4096;
+
 /// State of a single token's book in the store
 #[derive(Debug)]
 pub(crate) struct TokenBookState {
@@ -372,15 +387,19 @@ pub struct BookStore {
     metrics: Arc<BookStoreMetrics>,
     /// Reference instant for monotonic time calculations
     epoch: Instant,
+    /// Trade prints for all subscribed tokens
+    trade_tx: broadcast::Sender<PublicTradePrint>,
 }
 
 impl BookStore {
     pub fn new(config: BookStoreConfig) -> Arc<Self> {
+        let (trade_tx, _) = broadcast::channel(TRADE_CHANNEL_CAPACITY);
         Arc::new(Self {
             config,
             books: RwLock::new(HashMap::with_capacity(256)),
             metrics: Arc::new(BookStoreMetrics::new()),
             epoch: Instant::now(),
+            trade_tx,
         })
     }
 
@@ -491,6 +510,17 @@ impl BookStore {
         books.get(token_id).map(|state| state.update_tx.subscribe())
     }
 
+    /// Subscribe to trade prints for all subscribed tokens
+    pub fn subscribe_trades(&self) -> broadcast::Receiver<PublicTradePrint> {
+        self.trade_tx.subscribe()
+    }
+
+    /// Internal: publish a trade print (called by WS consumer)
+    pub(crate) fn publish_trade(&self, trade: PublicTradePrint) {
+        // No receivers is fine: nobody is listening for trades
+        let _ = self.trade_tx.send(trade);
+    }
+
     /// Check if a token is ready (has valid snapshot)
     #[inline]
     pub fn is_ready(&self, token_id: &str) -> bool {
@@ -1645,6 +1675,16 @@ impl SubscriptionManager {
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(0);
 
+                if !asset_id.is_empty() && price > 0.0 && size > 0.0 {
+                    book_store.publish_trade(PublicTradePrint {
+                        token_id: asset_id.to_string(),
+                        price,
+                        size,
+                        is_buy: side.eq_ignore_ascii_case("BUY"),
+                        source_time_ms,
+                    });
+                }
+
                 // Record trade for historical persistence (if enabled)
                 if let Some(ref trade_recorder) = self.trade_recorder {
                     if !asset_id.is_empty() && price > 0.0 && size > 0.0 {
@@ -2112,6 +2152,11 @@ impl HftBookCache {
         self.book_store.subscribe_updates(token_id)
     }
 
+    /// Subscribe to trade prints for all subscribed tokens
+    pub fn subscribe_trades(&self) -> broadcast::Receiver<PublicTradePrint> {
+        self.book_store.subscribe_trades()
+    }
+
     /// Check if connected to WS
     pub fn is_connected(&self) -> bool {
         self.subscription_manager.is_connected()
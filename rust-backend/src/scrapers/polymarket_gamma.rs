@@ -33,6 +33,29 @@ pub struct GammaMarketLookup {
     pub outcomes: Vec<String>,
     #[serde(rename = "clobTokenIds", deserialize_with = "de_string_vec")]
     pub clob_token_ids: Vec<String>,
+    #[serde(rename = "outcomePrices", default, deserialize_with = "de_string_vec")]
+    pub outcome_prices: Vec<String>,
+}
+
+impl GammaMarketLookup {
+    /// Index of the winning outcome once the market is closed and settled
+    /// (exactly one outcome priced at ~1.0).
+    pub fn winning_outcome_index(&self) -> Option<usize> {
+        if self.closed != Some(true) || self.outcome_prices.len() != self.outcomes.len() {
+            return None;
+        }
+        let prices: Vec<f64> = self
+            .outcome_prices
+            .iter()
+            .map(|p| p.trim().parse::<f64>().unwrap_or(f64::NAN))
+            .collect();
+        let mut winners = prices.iter().enumerate().filter(|(_, p)| **p >= 0.99);
+        let (i, _) = winners.next()?;
+        if winners.next().is_some() {
+            return None;
+        }
+        Some(i)
+    }
 }
 
 fn de_string_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -93,6 +116,32 @@ pub async fn gamma_market_lookup(
         }
     }
 
+    let Some(m) = fetch_gamma_market(http, market_slug).await? else {
+        return Ok(None);
+    };
+
+    if let Ok(json) = serde_json::to_string(&m) {
+        let _ = storage.upsert_cache(&cache_key, &json, now);
+    }
+
+    Ok(Some(m))
+}
+
+/// Winning outcome index of a settled market. Always hits Gamma (the lookup
+/// cache may hold the market from before it closed); `None` until settled.
+pub async fn gamma_market_resolution(
+    http: &reqwest::Client,
+    market_slug: &str,
+) -> Result<Option<usize>> {
+    Ok(fetch_gamma_market(http, market_slug)
+        .await?
+        .and_then(|m| m.winning_outcome_index()))
+}
+
+async fn fetch_gamma_market(
+    http: &reqwest::Client,
+    market_slug: &str,
+) -> Result<Option<GammaMarketLookup>> {
     // Gamma API: /markets?slug=...&limit=1 returns Vec
     let response = http
         .get("https://gamma-api.polymarket.com/markets")
@@ -115,15 +164,7 @@ pub async fn gamma_market_lookup(
         })
         .context("gamma markets json parse")?;
 
-    let Some(m) = markets.into_iter().next() else {
-        return Ok(None);
-    };
-
-    if let Ok(json) = serde_json::to_string(&m) {
-        let _ = storage.upsert_cache(&cache_key, &json, now);
-    }
-
-    Ok(Some(m))
+    Ok(markets.into_iter().next())
 }
 
 pub async fn resolve_clob_token_id_by_slug(
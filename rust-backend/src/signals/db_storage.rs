@@ -61,6 +61,19 @@ pub struct VaultLlmModelRecordRow {
     pub error: Option<String>,
 }
 
+/// Prompt pair sent to the LONG models for one decision, kept for offline replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultLlmPromptRow {
+    pub decision_id: String,
+    pub market_slug: String,
+    pub created_at: i64,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub outcomes: Vec<String>,
+    /// Best ask per outcome at prompt time (same order as `outcomes`)
+    pub asks: Vec<Option<f64>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VaultLlmUsageStats {
     pub day_start_ts: i64,
@@ -359,6 +372,27 @@ CREATE TABLE IF NOT EXISTS vault_llm_model_records (
 
 CREATE INDEX IF NOT EXISTS idx_vault_llm_model_records_decision
     ON vault_llm_model_records(decision_id);
+
+-- Vault LONG engine: prompts per decision (for offline model replay/eval)
+CREATE TABLE IF NOT EXISTS vault_llm_prompts (
+    decision_id TEXT PRIMARY KEY,
+    market_slug TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    system_prompt TEXT NOT NULL,
+    user_prompt TEXT NOT NULL,
+    outcomes_json TEXT NOT NULL,
+    asks_json TEXT NOT NULL
+) WITHOUT ROWID;
+
+CREATE INDEX IF NOT EXISTS idx_vault_llm_prompts_created
+    ON vault_llm_prompts(created_at DESC);
+
+-- Settled market outcomes (winning outcome index), resolved once
+CREATE TABLE IF NOT EXISTS vault_llm_resolutions (
+    market_slug TEXT PRIMARY KEY,
+    winning_index INTEGER NOT NULL,
+    resolved_at INTEGER NOT NULL
+) WITHOUT ROWID;
 "#;
 
 /// High-performance signal storage
@@ -1965,6 +1999,91 @@ impl DbSignalStorage {
         Ok(out)
     }
 
+    pub async fn insert_vault_llm_prompt(&self, row: &VaultLlmPromptRow) -> Result<()> {
+        let outcomes_json = serde_json::to_string(&row.outcomes)?;
+        let asks_json = serde_json::to_string(&row.asks)?;
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO vault_llm_prompts \
+             (decision_id, market_slug, created_at, system_prompt, user_prompt, outcomes_json, asks_json) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                row.decision_id,
+                row.market_slug,
+                row.created_at,
+                row.system_prompt,
+                row.user_prompt,
+                outcomes_json,
+                asks_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Stored prompts created in `[since_ts, until_ts)`, oldest first.
+    pub fn get_vault_llm_prompts(
+        &self,
+        since_ts: i64,
+        until_ts: i64,
+        limit: usize,
+    ) -> Result<Vec<VaultLlmPromptRow>> {
+        let limit = limit.clamp(1, 10_000) as i64;
+        let conn = self.conn.lock();
+
+        let mut out: Vec<VaultLlmPromptRow> = Vec::new();
+        let mut stmt = conn.prepare_cached(
+            "SELECT decision_id, market_slug, created_at, system_prompt, user_prompt, outcomes_json, asks_json \
+             FROM vault_llm_prompts WHERE created_at >= ?1 AND created_at < ?2 ORDER BY created_at ASC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![since_ts, until_ts, limit], |row| {
+            let outcomes_json: String = row.get(5)?;
+            let asks_json: String = row.get(6)?;
+            Ok(VaultLlmPromptRow {
+                decision_id: row.get(0)?,
+                market_slug: row.get(1)?,
+                created_at: row.get(2)?,
+                system_prompt: row.get(3)?,
+                user_prompt: row.get(4)?,
+                outcomes: serde_json::from_str(&outcomes_json).unwrap_or_default(),
+                asks: serde_json::from_str(&asks_json).unwrap_or_default(),
+            })
+        })?;
+        for r in rows {
+            if let Ok(v) = r {
+                out.push(v);
+            }
+        }
+
+        Ok(out)
+    }
+
+    pub fn get_vault_llm_resolution(&self, market_slug: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare_cached(
+            "SELECT winning_index FROM vault_llm_resolutions WHERE market_slug = ?1",
+        )?;
+        let mut rows = stmt.query(params![market_slug.trim().to_lowercase()])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        Ok(Some(row.get(0)?))
+    }
+
+    pub fn upsert_vault_llm_resolution(
+        &self,
+        market_slug: &str,
+        winning_index: i64,
+        resolved_at: i64,
+    ) -> Result<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO vault_llm_resolutions (market_slug, winning_index, resolved_at) \
+             VALUES (?1, ?2, ?3)",
+            params![market_slug.trim().to_lowercase(), winning_index, resolved_at],
+        )?;
+        Ok(())
+    }
+
     pub fn get_vault_llm_usage_today(&self, now_ts: i64) -> Result<VaultLlmUsageStats> {
         let day_start_ts = (now_ts / 86_400) * 86_400;
         let conn = self.conn.lock();
@@ -0,0 +1,672 @@
+//! Book-Walking Paper Execution
+//!
+//! Paper adapter that fills against the live Polymarket book instead of the
+//! constant-slippage model of `PaperExecutionAdapter`, so paper PnL is
+//! comparable with backtest PnL:
+//!
+//! - Marketable orders walk the current `BookStore` snapshot level by level.
+//!   Liquidity taken is remembered until the snapshot changes, so back-to-back
+//!   orders do not fill twice against the same displayed size.
+//! - The unfilled remainder of a GTC order joins the queue behind the
+//!   displayed size at its price (`backtest_v2::queue_model::QueuePositionModel`)
+//!   and fills from public trade prints in FIFO order. Prints through its
+//!   price fill it in full.
+//! - Taker fills pay the 15M fee schedule (`backtest_v2::fees_15m`); maker
+//!   fills pay no fee.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, warn};
+
+use crate::backtest_v2::events::{OrderId, Side};
+use crate::backtest_v2::fees_15m::calculate_fee_15m;
+use crate::backtest_v2::matching::{price_to_ticks, PriceTicks};
+use crate::backtest_v2::queue_model::QueuePositionModel;
+use crate::scrapers::{BookSnapshot, BookStore, PublicTradePrint};
+use crate::vault::{
+    ExecutionAdapter, ExecutionEvent, OrderAck, OrderRequest, OrderSide, OrderState, OrderTracker,
+    TimeInForce,
+};
+
+/// Polymarket 15M tick size
+const TICK_SIZE: f64 = 0.01;
+/// Share quantities below this are treated as zero
+const SIZE_EPSILON: f64 = 1e-9;
+/// Queue ids for displayed size ahead of our orders (never collide with ours)
+const EXTERNAL_QUEUE_ID_BASE: OrderId = 1 << 62;
+
+#[derive(Debug, Clone)]
+pub struct BookPaperConfig {
+    /// Simulated order entry latency in ms
+    pub latency_ms: u64,
+    /// Orders are rejected when the book is older than this
+    pub book_max_stale_ms: u64,
+    /// Charge the 15M taker fee schedule
+    pub apply_fees: bool,
+}
+
+impl Default for BookPaperConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: 150,
+            book_max_stale_ms: 1_500,
+            apply_fees: true,
+        }
+    }
+}
+
+impl BookPaperConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("PAPER_BOOK_LATENCY_MS") {
+            if let Ok(ms) = v.parse() {
+                config.latency_ms = ms;
+            }
+        }
+        if let Ok(v) = std::env::var("PAPER_BOOK_MAX_STALE_MS") {
+            if let Ok(ms) = v.parse() {
+                config.book_max_stale_ms = ms;
+            }
+        }
+        if let Ok(v) = std::env::var("PAPER_BOOK_FEES") {
+            config.apply_fees = matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON");
+        }
+
+        config
+    }
+}
+
+/// A paper order resting in the queue model.
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    queue_id: OrderId,
+    token_id: String,
+    side: Side,
+    price: f64,
+}
+
+/// Liquidity already taken from one book snapshot.
+struct ConsumedLiquidity {
+    snapshot_at: Instant,
+    /// (taker side, price ticks) -> shares
+    taken: HashMap<(Side, PriceTicks), f64>,
+}
+
+#[derive(Default)]
+struct BookPaperState {
+    next_id: u64,
+    next_external_id: OrderId,
+    queues: HashMap<String, QueuePositionModel>,
+    /// Resting orders by paper order id
+    resting: HashMap<String, RestingOrder>,
+    by_queue_id: HashMap<OrderId, String>,
+    consumed: HashMap<String, ConsumedLiquidity>,
+}
+
+impl BookPaperState {
+    /// Levels of `book` a taker order would fill against: `(price, shares)`.
+    fn walk(
+        &self,
+        token_id: &str,
+        book: &BookSnapshot,
+        side: Side,
+        limit: f64,
+        size: f64,
+    ) -> Vec<(f64, f64)> {
+        let levels = match side {
+            Side::Buy => &book.asks,
+            Side::Sell => &book.bids,
+        };
+        let taken = self
+            .consumed
+            .get(token_id)
+            .filter(|c| c.snapshot_at == book.created_at);
+
+        let mut fills = Vec::new();
+        let mut remaining = size;
+        for level in levels {
+            let marketable = match side {
+                Side::Buy => level.price <= limit + SIZE_EPSILON,
+                Side::Sell => level.price >= limit - SIZE_EPSILON,
+            };
+            if !marketable || remaining <= SIZE_EPSILON {
+                break;
+            }
+            let already = taken
+                .and_then(|c| c.taken.get(&(side, price_to_ticks(level.price, TICK_SIZE))))
+                .copied()
+                .unwrap_or(0.0);
+            let shares = (level.size - already).max(0.0).min(remaining);
+            if shares > SIZE_EPSILON {
+                fills.push((level.price, shares));
+                remaining -= shares;
+            }
+        }
+        fills
+    }
+
+    fn consume(&mut self, token_id: &str, book: &BookSnapshot, side: Side, fills: &[(f64, f64)]) {
+        let consumed = self
+            .consumed
+            .entry(token_id.to_string())
+            .or_insert_with(|| ConsumedLiquidity {
+                snapshot_at: book.created_at,
+                taken: HashMap::new(),
+            });
+        if consumed.snapshot_at != book.created_at {
+            consumed.snapshot_at = book.created_at;
+            consumed.taken.clear();
+        }
+        for (price, shares) in fills {
+            *consumed
+                .taken
+                .entry((side, price_to_ticks(*price, TICK_SIZE)))
+                .or_insert(0.0) += shares;
+        }
+    }
+
+    /// Queue a resting order behind the displayed size at its price that is
+    /// not already ahead of our other orders there.
+    #[allow(clippy::too_many_arguments)]
+    fn join_queue(
+        &mut self,
+        order_id: &str,
+        token_id: &str,
+        side: Side,
+        price: f64,
+        size: f64,
+        displayed: f64,
+        now: i64,
+    ) {
+        let ticks = price_to_ticks(price, TICK_SIZE);
+        let queue = self.queues.entry(token_id.to_string()).or_default();
+
+        // External size already queued at this level (ahead of our last order)
+        let positions: Vec<_> = self
+            .resting
+            .values()
+            .filter(|o| {
+                o.token_id == token_id
+                    && o.side == side
+                    && price_to_ticks(o.price, TICK_SIZE) == ticks
+            })
+            .filter_map(|o| queue.get_position(o.queue_id))
+            .collect();
+        let level_total = positions
+            .iter()
+            .map(|p| p.size_ahead + p.our_size)
+            .fold(0.0, f64::max);
+        let ours: f64 = positions.iter().map(|p| p.our_size).sum();
+        let ahead = (displayed - (level_total - ours)).max(0.0);
+        if ahead > SIZE_EPSILON {
+            let external_id = EXTERNAL_QUEUE_ID_BASE + self.next_external_id;
+            self.next_external_id += 1;
+            queue.add_order(external_id, side, ticks, ahead, false, now);
+        }
+
+        self.next_id += 1;
+        let queue_id = self.next_id;
+        queue.add_order(queue_id, side, ticks, size, true, now);
+        self.by_queue_id.insert(queue_id, order_id.to_string());
+        self.resting.insert(
+            order_id.to_string(),
+            RestingOrder {
+                queue_id,
+                token_id: token_id.to_string(),
+                side,
+                price,
+            },
+        );
+    }
+
+    fn leave_queue(&mut self, order_id: &str) {
+        if let Some(order) = self.resting.remove(order_id) {
+            self.by_queue_id.remove(&order.queue_id);
+            if let Some(queue) = self.queues.get_mut(&order.token_id) {
+                queue.remove_order(order.queue_id);
+            }
+        }
+    }
+}
+
+/// Paper adapter filling against the live book (see module docs).
+#[derive(Clone)]
+pub struct BookPaperExecutionAdapter {
+    pub config: BookPaperConfig,
+    book_store: Arc<BookStore>,
+    orders: Arc<OrderTracker>,
+    state: Arc<Mutex<BookPaperState>>,
+}
+
+impl BookPaperExecutionAdapter {
+    pub fn new(config: BookPaperConfig, book_store: Arc<BookStore>) -> Self {
+        Self {
+            config,
+            book_store,
+            orders: Arc::new(OrderTracker::new()),
+            state: Arc::new(Mutex::new(BookPaperState::default())),
+        }
+    }
+
+    /// Fill resting orders from the store's public trade prints.
+    pub fn spawn_trade_listener(&self) -> tokio::task::JoinHandle<()> {
+        let adapter = self.clone();
+        let mut trades = self.book_store.subscribe_trades();
+        tokio::spawn(async move {
+            loop {
+                match trades.recv().await {
+                    Ok(trade) => {
+                        adapter.on_trade(&trade);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            skipped,
+                            "Book paper adapter missed trade prints; passive fills may lag"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Apply a public trade print to resting orders: orders priced through the
+    /// print fill in full, orders at the print price fill in queue order.
+    /// Returns the fill events.
+    pub fn on_trade(&self, trade: &PublicTradePrint) -> Vec<ExecutionEvent> {
+        // An aggressive buy lifts asks, an aggressive sell hits bids
+        let maker_side = if trade.is_buy { Side::Sell } else { Side::Buy };
+        let now = Utc::now().timestamp_nanos_opt().unwrap_or(0);
+
+        let fills: Vec<(String, f64, f64)> = {
+            let mut state = self.state.lock();
+            let mut fills = Vec::new();
+
+            let traded_through: Vec<(String, f64)> = state
+                .resting
+                .iter()
+                .filter(|(_, o)| o.token_id == trade.token_id && o.side == maker_side)
+                .filter(|(_, o)| match maker_side {
+                    Side::Sell => o.price < trade.price - SIZE_EPSILON,
+                    Side::Buy => o.price > trade.price + SIZE_EPSILON,
+                })
+                .map(|(id, o)| (id.clone(), o.price))
+                .collect();
+            for (order_id, price) in traded_through {
+                let remaining = self
+                    .orders
+                    .get(&order_id)
+                    .map_or(0.0, |o| o.remaining_size());
+                state.leave_queue(&order_id);
+                fills.push((order_id, price, remaining));
+            }
+
+            let ticks = price_to_ticks(trade.price, TICK_SIZE);
+            let queue_fills = match state.queues.get_mut(&trade.token_id) {
+                Some(queue) => queue.process_fills(maker_side, ticks, trade.size, now),
+                None => Vec::new(),
+            };
+            for fill in queue_fills {
+                if let Some(order_id) = state.by_queue_id.get(&fill.order_id).cloned() {
+                    let price = state
+                        .resting
+                        .get(&order_id)
+                        .map_or(trade.price, |o| o.price);
+                    fills.push((order_id, price, fill.size));
+                }
+            }
+            fills
+        };
+
+        let mut events = Vec::new();
+        for (order_id, price, size) in fills {
+            if let Some(event) = self.orders.fill(&order_id, price, size, 0.0, true) {
+                if let ExecutionEvent::Fill { remaining_size, .. } = &event {
+                    if *remaining_size <= SIZE_EPSILON {
+                        self.state.lock().leave_queue(&order_id);
+                    }
+                }
+                events.push(event);
+            }
+        }
+        if !events.is_empty() {
+            debug!(token_id = %trade.token_id, fills = events.len(), "Book paper passive fills");
+        }
+        events
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionAdapter for BookPaperExecutionAdapter {
+    async fn place_order(&self, req: OrderRequest) -> Result<OrderAck> {
+        let start = Instant::now();
+
+        if !(req.price.is_finite() && req.price > 0.0 && req.price < 1.0) {
+            self.orders.reject(&req, "invalid price");
+            return Err(anyhow!("invalid price"));
+        }
+        if !(req.notional_usdc.is_finite() && req.notional_usdc > 0.0) {
+            self.orders.reject(&req, "invalid notional");
+            return Err(anyhow!("invalid notional"));
+        }
+
+        if self.config.latency_ms > 0 {
+            sleep(Duration::from_millis(self.config.latency_ms)).await;
+        }
+
+        let Some(book) = self
+            .book_store
+            .get_book_if_fresh(&req.token_id, self.config.book_max_stale_ms)
+        else {
+            self.orders.reject(&req, "no fresh book");
+            return Err(anyhow!("no fresh book for token {}", req.token_id));
+        };
+
+        let side = match req.side {
+            OrderSide::Buy => Side::Buy,
+            OrderSide::Sell => Side::Sell,
+        };
+        // Size in shares at the limit price
+        let size = req.notional_usdc / req.price;
+        let now = Utc::now().timestamp_nanos_opt().unwrap_or(0);
+
+        let mut state = self.state.lock();
+        let fills = state.walk(&req.token_id, &book, side, req.price, size);
+        let filled_size: f64 = fills.iter().map(|(_, q)| q).sum();
+
+        if req.tif == TimeInForce::Fok && filled_size < size - SIZE_EPSILON {
+            drop(state);
+            self.orders
+                .reject(&req, "FOK order could not be fully filled");
+            return Err(anyhow!("FOK order could not be fully filled"));
+        }
+        state.consume(&req.token_id, &book, side, &fills);
+
+        let filled_notional: f64 = fills.iter().map(|(p, q)| p * q).sum();
+        let filled_price = if filled_size > SIZE_EPSILON {
+            filled_notional / filled_size
+        } else {
+            req.price
+        };
+        let fees_usdc = if self.config.apply_fees {
+            fills
+                .iter()
+                .map(|(p, q)| calculate_fee_15m(*p, *q, now))
+                .sum()
+        } else {
+            0.0
+        };
+
+        state.next_id += 1;
+        let order_id = format!("paperbook:{}:{}", state.next_id, req.client_order_id);
+        let resting_size = self.orders.record_submission(
+            &req,
+            &order_id,
+            size,
+            filled_size,
+            filled_price,
+            fees_usdc,
+            req.tif == TimeInForce::Gtc,
+        );
+        if resting_size > SIZE_EPSILON {
+            let own_levels = match side {
+                Side::Buy => &book.bids,
+                Side::Sell => &book.asks,
+            };
+            let displayed = own_levels
+                .iter()
+                .find(|l| (l.price - req.price).abs() < TICK_SIZE / 2.0)
+                .map_or(0.0, |l| l.size);
+            state.join_queue(
+                &order_id,
+                &req.token_id,
+                side,
+                req.price,
+                resting_size,
+                displayed,
+                now,
+            );
+        }
+        drop(state);
+
+        // Slippage of the average fill against the touch at arrival
+        let touch = match side {
+            Side::Buy => book.best_ask(),
+            Side::Sell => book.best_bid(),
+        };
+        let slippage_bps = match touch {
+            Some(touch) if filled_size > SIZE_EPSILON && touch > 0.0 => match side {
+                Side::Buy => (filled_price - touch) / touch * 10_000.0,
+                Side::Sell => (touch - filled_price) / touch * 10_000.0,
+            },
+            _ => 0.0,
+        };
+
+        Ok(OrderAck {
+            order_id,
+            filled_notional_usdc: filled_notional,
+            filled_price,
+            filled_at: Utc::now().timestamp(),
+            fees_usdc,
+            slippage_bps,
+            latency_ms: start.elapsed().as_millis() as u64,
+            resting_size,
+        })
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.orders.cancel(order_id)?;
+        self.state.lock().leave_queue(order_id);
+        Ok(())
+    }
+
+    async fn cancel_all(&self, token_id: Option<&str>) -> Result<Vec<String>> {
+        let mut cancelled = Vec::new();
+        for order in self.orders.open_orders(token_id) {
+            if self.cancel_order(&order.order_id).await.is_ok() {
+                cancelled.push(order.order_id);
+            }
+        }
+        Ok(cancelled)
+    }
+
+    async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+        self.orders
+            .get(order_id)
+            .ok_or_else(|| anyhow!("unknown order {}", order_id))
+    }
+
+    async fn open_orders(&self, token_id: Option<&str>) -> Result<Vec<OrderState>> {
+        Ok(self.orders.open_orders(token_id))
+    }
+
+    fn subscribe_events(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.orders.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::fees_15m::fee_per_share_15m;
+    use crate::scrapers::{BookStoreConfig, PriceLevel};
+    use crate::vault::OrderStatus;
+
+    fn level(price: f64, size: f64) -> PriceLevel {
+        PriceLevel { price, size }
+    }
+
+    fn adapter() -> BookPaperExecutionAdapter {
+        let store = BookStore::new(BookStoreConfig::default());
+        store.apply_snapshot(
+            "TOKEN",
+            vec![level(0.45, 100.0), level(0.44, 50.0)],
+            vec![level(0.50, 10.0), level(0.52, 10.0), level(0.55, 10.0)],
+            Some(1),
+        );
+        BookPaperExecutionAdapter::new(
+            BookPaperConfig {
+                latency_ms: 0,
+                ..Default::default()
+            },
+            store,
+        )
+    }
+
+    fn request(
+        id: &str,
+        side: OrderSide,
+        price: f64,
+        shares: f64,
+        tif: TimeInForce,
+    ) -> OrderRequest {
+        OrderRequest {
+            client_order_id: id.to_string(),
+            token_id: "TOKEN".to_string(),
+            side,
+            price,
+            notional_usdc: price * shares,
+            tif,
+            market_slug: None,
+            outcome: None,
+        }
+    }
+
+    fn trade(price: f64, size: f64, is_buy: bool) -> PublicTradePrint {
+        PublicTradePrint {
+            token_id: "TOKEN".to_string(),
+            price,
+            size,
+            is_buy,
+            source_time_ms: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_taker_walks_levels_and_remembers_consumed_liquidity() {
+        let exec = adapter();
+
+        let ack = exec
+            .place_order(request("a", OrderSide::Buy, 0.52, 15.0, TimeInForce::Ioc))
+            .await
+            .unwrap();
+        assert!((ack.filled_notional_usdc - (10.0 * 0.50 + 5.0 * 0.52)).abs() < 1e-9);
+        assert!((ack.filled_price - 7.6 / 15.0).abs() < 1e-9);
+        let expected_fee = fee_per_share_15m(0.50) * 10.0 + fee_per_share_15m(0.52) * 5.0;
+        assert!((ack.fees_usdc - expected_fee).abs() < 1e-9);
+        assert!(ack.slippage_bps > 0.0);
+
+        // Same snapshot: only the 5 shares left at 0.52 are available
+        let ack = exec
+            .place_order(request("b", OrderSide::Buy, 0.52, 15.0, TimeInForce::Ioc))
+            .await
+            .unwrap();
+        assert!((ack.filled_notional_usdc - 5.0 * 0.52).abs() < 1e-9);
+        assert_eq!(ack.resting_size, 0.0);
+        assert_eq!(
+            exec.order_status(&ack.order_id).await.unwrap().status,
+            OrderStatus::Cancelled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fok_and_stale_book_rejected_without_consuming() {
+        let exec = adapter();
+
+        assert!(exec
+            .place_order(request("fok", OrderSide::Buy, 0.55, 40.0, TimeInForce::Fok))
+            .await
+            .is_err());
+        let mut missing = request("x", OrderSide::Buy, 0.50, 1.0, TimeInForce::Ioc);
+        missing.token_id = "UNKNOWN".to_string();
+        assert!(exec.place_order(missing).await.is_err());
+
+        // Nothing was taken by the rejected FOK
+        let ack = exec
+            .place_order(request("fok", OrderSide::Buy, 0.55, 30.0, TimeInForce::Fok))
+            .await
+            .unwrap();
+        assert!((ack.filled_notional_usdc / ack.filled_price - 30.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_passive_order_fills_after_queue_ahead() {
+        let exec = adapter();
+        let mut events = exec.subscribe_events();
+
+        // Bid joins behind 100 displayed shares at 0.45
+        let ack = exec
+            .place_order(request("bid", OrderSide::Buy, 0.45, 20.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        assert_eq!(ack.filled_notional_usdc, 0.0);
+        assert!((ack.resting_size - 20.0).abs() < 1e-9);
+        while events.try_recv().is_ok() {}
+
+        // Prints on the other side and ahead of us do not fill
+        assert!(exec.on_trade(&trade(0.45, 60.0, true)).is_empty());
+        assert!(exec.on_trade(&trade(0.45, 60.0, false)).is_empty());
+
+        // 40 shares still ahead: a 50-share print fills 10 of ours
+        let fills = exec.on_trade(&trade(0.45, 50.0, false));
+        assert_eq!(fills.len(), 1);
+        match &fills[0] {
+            ExecutionEvent::Fill {
+                price,
+                size,
+                fee_usdc,
+                is_maker,
+                ..
+            } => {
+                assert_eq!(*price, 0.45);
+                assert!((size - 10.0).abs() < 1e-9);
+                assert_eq!(*fee_usdc, 0.0);
+                assert!(is_maker);
+            }
+            other => panic!("expected fill, got {:?}", other),
+        }
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            ExecutionEvent::Fill { .. }
+        ));
+
+        // A print through our price fills the rest
+        let fills = exec.on_trade(&trade(0.44, 1.0, false));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(
+            exec.order_status(&ack.order_id).await.unwrap().status,
+            OrderStatus::Filled
+        );
+        assert!(exec.open_orders(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_order_leaves_queue() {
+        let exec = adapter();
+
+        let first = exec
+            .place_order(request("a", OrderSide::Sell, 0.60, 10.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        let second = exec
+            .place_order(request("b", OrderSide::Sell, 0.60, 10.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        assert_eq!(exec.cancel_all(Some("TOKEN")).await.unwrap().len(), 2);
+        assert!(exec.cancel_order(&first.order_id).await.is_err());
+
+        assert!(exec.on_trade(&trade(0.60, 100.0, true)).is_empty());
+        assert!(exec.on_trade(&trade(0.70, 100.0, true)).is_empty());
+        assert_eq!(
+            exec.order_status(&second.order_id).await.unwrap().status,
+            OrderStatus::Cancelled
+        );
+    }
+}
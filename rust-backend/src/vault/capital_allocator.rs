@@ -0,0 +1,921 @@
+//! Capital Allocation Across Strategies
+//!
+//! Every vault engine sizes off the same cash balance, so when several fire
+//! on the same 15M window they can jointly commit far more than any one
+//! `*_max_position_pct` intends. The allocator splits NAV into per-strategy
+//! budgets ("sleeves") and every buy reserves against its sleeve before it is
+//! sent; an order larger than the sleeve's headroom is clipped, and one with
+//! no headroom is skipped.
+//!
+//! Sleeves:
+//! - `UPDOWN15M`: `FAST15M`, `FAST15M_REACTIVE`
+//! - `LONG`: `LONG`, `LONG_EXIT`
+//! - `LATENCY_ARB`
+//! - `ORDERFLOW`: `ORDERFLOW_PAPER`
+//!
+//! Other strategy names are their own sleeve and are only budgeted when they
+//! appear in `VAULT_ALLOC_WEIGHTS`.
+//!
+//! Deployed capital is the cost of a sleeve's open lots. A lot is released
+//! when it is sold, when the ledger no longer holds it, or once its market
+//! resolves: engines register the resolution time with `record_expiry`
+//! (UPDOWN15M windows resolve at the window end), since held-to-resolution
+//! positions are never sold.
+//!
+//! Budgets are reset every `rebalance_secs` by one of:
+//! - `fixed`: the configured weights
+//! - `risk_parity`: weights inversely proportional to each sleeve's realized
+//!   return volatility
+//! - `kelly`: fractional Kelly on each sleeve's realized edge (mean / variance)
+//!
+//! Realized returns come from the vault's `TRADE` activity: closing trades are
+//! matched against the sleeve's average cost and bucketed per `bucket_secs`.
+//! Sleeves with fewer than `min_samples` buckets keep their configured weight.
+
+use anyhow::Result;
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::vault::{
+    approximate_nav_usdc, parse_updown_15m_slug, OrderSide, PooledVault, VaultActivityRecord,
+    VaultAllocationRecord, VaultDb, VaultPaperLedger,
+};
+
+const VOL_FLOOR: f64 = 1e-4;
+
+/// Sleeve a strategy's orders are budgeted under.
+pub fn sleeve_for(strategy: &str) -> String {
+    match strategy.to_ascii_uppercase().as_str() {
+        "FAST15M" | "FAST15M_REACTIVE" | "UPDOWN15M" => "UPDOWN15M".to_string(),
+        "LONG" | "LONG_EXIT" => "LONG".to_string(),
+        "ORDERFLOW_PAPER" | "ORDERFLOW" => "ORDERFLOW".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationMethod {
+    Fixed,
+    RiskParity,
+    Kelly,
+}
+
+impl AllocationMethod {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fixed" => Some(Self::Fixed),
+            "risk_parity" | "riskparity" | "rp" => Some(Self::RiskParity),
+            "kelly" => Some(Self::Kelly),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::RiskParity => "risk_parity",
+            Self::Kelly => "kelly",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapitalAllocatorConfig {
+    /// When false, orders are tracked but never clipped
+    pub enabled: bool,
+    pub method: AllocationMethod,
+    /// Fixed weights (fraction of NAV) per sleeve; also the fallback for
+    /// sleeves without enough history
+    pub weights: BTreeMap<String, f64>,
+    /// Fraction of NAV never allocated
+    pub reserve_pct: f64,
+    pub rebalance_secs: u64,
+    /// History considered for return statistics
+    pub lookback_secs: i64,
+    /// Return sampling period
+    pub bucket_secs: i64,
+    pub min_samples: usize,
+    pub min_weight: f64,
+    pub max_weight: f64,
+    /// Fraction of full Kelly for the `kelly` method
+    pub kelly_fraction: f64,
+    /// Clipped orders below this are skipped
+    pub min_order_usdc: f64,
+}
+
+impl Default for CapitalAllocatorConfig {
+    fn default() -> Self {
+        let weights = [
+            ("UPDOWN15M", 0.40),
+            ("LONG", 0.30),
+            ("LATENCY_ARB", 0.15),
+            ("ORDERFLOW", 0.15),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        Self {
+            enabled: true,
+            method: AllocationMethod::Fixed,
+            weights,
+            reserve_pct: 0.10,
+            rebalance_secs: 3_600,
+            lookback_secs: 14 * 86_400,
+            bucket_secs: 3_600,
+            min_samples: 8,
+            min_weight: 0.02,
+            max_weight: 0.60,
+            kelly_fraction: 0.25,
+            min_order_usdc: 1.0,
+        }
+    }
+}
+
+impl CapitalAllocatorConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("VAULT_ALLOC_ENABLED") {
+            config.enabled = matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON");
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_METHOD") {
+            match AllocationMethod::parse(&v) {
+                Some(m) => config.method = m,
+                None => warn!(method = %v, "unknown VAULT_ALLOC_METHOD; using fixed"),
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_WEIGHTS") {
+            // "UPDOWN15M=0.4,LONG=0.3,..."
+            let weights: BTreeMap<String, f64> = v
+                .split(',')
+                .filter_map(|kv| {
+                    let (k, w) = kv.split_once('=')?;
+                    let w: f64 = w.trim().parse().ok()?;
+                    (w.is_finite() && w >= 0.0).then(|| (sleeve_for(k.trim()), w))
+                })
+                .collect();
+            if !weights.is_empty() {
+                config.weights = weights;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_RESERVE_PCT") {
+            if let Ok(x) = v.parse::<f64>() {
+                config.reserve_pct = x.clamp(0.0, 1.0);
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_REBALANCE_SECS") {
+            if let Ok(n) = v.parse::<u64>() {
+                config.rebalance_secs = n.max(60);
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_LOOKBACK_DAYS") {
+            if let Ok(n) = v.parse::<i64>() {
+                config.lookback_secs = n.max(1) * 86_400;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_BUCKET_SECS") {
+            if let Ok(n) = v.parse::<i64>() {
+                config.bucket_secs = n.max(60);
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_MIN_SAMPLES") {
+            if let Ok(n) = v.parse() {
+                config.min_samples = n;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_MIN_WEIGHT") {
+            if let Ok(x) = v.parse() {
+                config.min_weight = x;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_MAX_WEIGHT") {
+            if let Ok(x) = v.parse() {
+                config.max_weight = x;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_KELLY_FRACTION") {
+            if let Ok(x) = v.parse() {
+                config.kelly_fraction = x;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_ALLOC_MIN_ORDER_USD") {
+            if let Ok(x) = v.parse() {
+                config.min_order_usdc = x;
+            }
+        }
+
+        config
+    }
+}
+
+/// Mean and volatility of a sleeve's bucketed returns (PnL / cost closed).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReturnStats {
+    pub mean: f64,
+    pub vol: f64,
+    pub samples: usize,
+}
+
+impl ReturnStats {
+    pub fn from_returns(returns: &[f64]) -> Self {
+        let n = returns.len();
+        if n == 0 {
+            return Self::default();
+        }
+        let mean = returns.iter().sum::<f64>() / n as f64;
+        let var = if n > 1 {
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            mean,
+            vol: var.sqrt(),
+            samples: n,
+        }
+    }
+}
+
+/// Target weight per configured sleeve. Weights are clamped to
+/// `[min_weight, max_weight]` and scaled down so they never exceed
+/// `1 - reserve_pct` in total.
+pub fn target_weights(
+    cfg: &CapitalAllocatorConfig,
+    stats: &BTreeMap<String, ReturnStats>,
+) -> BTreeMap<String, f64> {
+    let usable = |s: &str| {
+        stats
+            .get(s)
+            .filter(|st| st.samples >= cfg.min_samples.max(2))
+            .copied()
+    };
+
+    let mut raw: BTreeMap<String, f64> = match cfg.method {
+        AllocationMethod::Fixed => cfg.weights.clone(),
+        AllocationMethod::RiskParity => {
+            let inv_vols: BTreeMap<&String, f64> = cfg
+                .weights
+                .keys()
+                .filter_map(|s| usable(s).map(|st| (s, 1.0 / st.vol.max(VOL_FLOOR))))
+                .collect();
+            if inv_vols.is_empty() {
+                cfg.weights.clone()
+            } else {
+                // Sleeves without history get the average inverse vol.
+                let neutral = inv_vols.values().sum::<f64>() / inv_vols.len() as f64;
+                let scores: BTreeMap<String, f64> = cfg
+                    .weights
+                    .keys()
+                    .map(|s| (s.clone(), inv_vols.get(s).copied().unwrap_or(neutral)))
+                    .collect();
+                let total_score: f64 = scores.values().sum();
+                let total_weight: f64 = cfg.weights.values().sum();
+                scores
+                    .into_iter()
+                    .map(|(s, score)| (s, score / total_score * total_weight))
+                    .collect()
+            }
+        }
+        AllocationMethod::Kelly => cfg
+            .weights
+            .iter()
+            .map(|(s, fixed)| {
+                let w = match usable(s) {
+                    Some(st) if st.mean > 0.0 => {
+                        cfg.kelly_fraction * st.mean / st.vol.max(VOL_FLOOR).powi(2)
+                    }
+                    Some(_) => 0.0,
+                    None => *fixed,
+                };
+                (s.clone(), w)
+            })
+            .collect(),
+    };
+
+    for w in raw.values_mut() {
+        *w = w.clamp(cfg.min_weight, cfg.max_weight.max(cfg.min_weight));
+    }
+    let investable = (1.0 - cfg.reserve_pct).max(0.0);
+    let total: f64 = raw.values().sum();
+    if total > investable && total > 0.0 {
+        let scale = investable / total;
+        for w in raw.values_mut() {
+            *w *= scale;
+        }
+    }
+    raw
+}
+
+#[derive(Debug, Clone, Default)]
+struct Lot {
+    shares: f64,
+    cost_usdc: f64,
+}
+
+/// A sleeve's open lots (for deployed capital) and realized PnL by bucket.
+#[derive(Debug, Clone, Default)]
+struct SleeveBook {
+    lots: HashMap<String, Lot>,
+    realized_pnl_usdc: f64,
+    /// bucket start -> (pnl, cost closed)
+    buckets: BTreeMap<i64, (f64, f64)>,
+}
+
+impl SleeveBook {
+    fn deployed_usdc(&self) -> f64 {
+        self.lots.values().map(|l| l.cost_usdc).sum()
+    }
+
+    fn apply(
+        &mut self,
+        token_id: &str,
+        side: OrderSide,
+        price: f64,
+        notional: f64,
+        ts: i64,
+        bucket_secs: i64,
+    ) {
+        if !(price > 0.0 && notional > 0.0) {
+            return;
+        }
+        match side {
+            OrderSide::Buy => {
+                let lot = self.lots.entry(token_id.to_string()).or_default();
+                lot.shares += notional / price;
+                lot.cost_usdc += notional;
+            }
+            OrderSide::Sell => {
+                let Some(lot) = self.lots.get_mut(token_id) else {
+                    return;
+                };
+                let sold = (notional / price).min(lot.shares);
+                if !(sold > 0.0) {
+                    return;
+                }
+                let cost = lot.cost_usdc * sold / lot.shares;
+                let pnl = sold * price - cost;
+                lot.shares -= sold;
+                lot.cost_usdc -= cost;
+                if lot.shares <= 1e-9 {
+                    self.lots.remove(token_id);
+                }
+                self.realized_pnl_usdc += pnl;
+                let bucket = self
+                    .buckets
+                    .entry(ts - ts.rem_euclid(bucket_secs))
+                    .or_insert((0.0, 0.0));
+                bucket.0 += pnl;
+                bucket.1 += cost;
+            }
+        }
+    }
+
+    fn stats_since(&self, since: i64) -> ReturnStats {
+        let returns: Vec<f64> = self
+            .buckets
+            .range(since..)
+            .filter(|(_, (_, cost))| *cost > 0.0)
+            .map(|(_, (pnl, cost))| pnl / cost)
+            .collect();
+        ReturnStats::from_returns(&returns)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct SleeveState {
+    book: SleeveBook,
+    weight: f64,
+    budget_usdc: f64,
+    reserved_usdc: f64,
+    stats: ReturnStats,
+    clipped_orders: u64,
+    denied_orders: u64,
+}
+
+#[derive(Debug, Default)]
+struct AllocatorInner {
+    sleeves: BTreeMap<String, SleeveState>,
+    /// token -> resolution time (unix seconds)
+    expiries: HashMap<String, i64>,
+    capital_usdc: f64,
+    rebalanced_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SleeveAllocation {
+    pub sleeve: String,
+    /// Whether orders are held to `budget_usdc`
+    pub budgeted: bool,
+    pub weight: f64,
+    pub budget_usdc: f64,
+    pub deployed_usdc: f64,
+    pub reserved_usdc: f64,
+    pub available_usdc: f64,
+    pub realized_pnl_usdc: f64,
+    pub stats: ReturnStats,
+    pub clipped_orders: u64,
+    pub denied_orders: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapitalAllocationStatus {
+    pub enabled: bool,
+    pub method: AllocationMethod,
+    pub capital_usdc: f64,
+    pub reserve_pct: f64,
+    pub rebalanced_at: Option<i64>,
+    pub sleeves: Vec<SleeveAllocation>,
+}
+
+/// Capital reserved for one in-flight buy. Dropping it releases the
+/// reservation; record the fill with `CapitalAllocator::record_fill` first.
+pub struct AllocationTicket {
+    allocator: Arc<CapitalAllocator>,
+    sleeve: String,
+    granted_usdc: f64,
+}
+
+impl AllocationTicket {
+    /// Notional the order may use (at most what was requested).
+    pub fn granted_usdc(&self) -> f64 {
+        self.granted_usdc
+    }
+}
+
+impl Drop for AllocationTicket {
+    fn drop(&mut self) {
+        let mut inner = self.allocator.inner.lock();
+        if let Some(s) = inner.sleeves.get_mut(&self.sleeve) {
+            s.reserved_usdc = (s.reserved_usdc - self.granted_usdc).max(0.0);
+        }
+    }
+}
+
+/// Shared per-sleeve budgets checked by every engine before it buys.
+pub struct CapitalAllocator {
+    cfg: CapitalAllocatorConfig,
+    db: Option<Arc<VaultDb>>,
+    inner: Mutex<AllocatorInner>,
+}
+
+impl CapitalAllocator {
+    pub fn new(cfg: CapitalAllocatorConfig, db: Option<Arc<VaultDb>>) -> Self {
+        let mut inner = AllocatorInner::default();
+        for sleeve in cfg.weights.keys() {
+            inner.sleeves.insert(sleeve.clone(), SleeveState::default());
+        }
+        Self {
+            cfg,
+            db,
+            inner: Mutex::new(inner),
+        }
+    }
+
+    /// Create with open lots and return history rebuilt from the vault's
+    /// trade activity, then budget against the vault's current NAV.
+    pub async fn load(
+        cfg: CapitalAllocatorConfig,
+        db: Arc<VaultDb>,
+        vault: &PooledVault,
+    ) -> Result<Self> {
+        let allocator = Self::new(cfg, Some(db.clone()));
+        let trades = db.list_trade_activity_since(0).await?;
+        allocator.replay(&trades);
+
+        let (nav, ledger_view) = {
+            let ledger = vault.ledger.lock().await;
+            (approximate_nav_usdc(&ledger), ledger.clone())
+        };
+        allocator.sync_positions(&ledger_view);
+        let now = Utc::now().timestamp();
+        allocator.release_expired(now);
+        allocator.rebalance(nav, now).await;
+        Ok(allocator)
+    }
+
+    pub fn config(&self) -> &CapitalAllocatorConfig {
+        &self.cfg
+    }
+
+    fn replay(&self, trades: &[VaultActivityRecord]) {
+        for t in trades {
+            let (Some(strategy), Some(token), Some(side), Some(price), Some(notional)) = (
+                t.strategy.as_deref(),
+                t.token_id.as_deref(),
+                t.side.as_deref(),
+                t.price,
+                t.notional_usdc,
+            ) else {
+                continue;
+            };
+            let side = match side {
+                "BUY" => OrderSide::Buy,
+                "SELL" => OrderSide::Sell,
+                _ => continue,
+            };
+            if let Some(market) = t.market_slug.as_deref().and_then(parse_updown_15m_slug) {
+                self.record_expiry(token, market.end_ts);
+            }
+            self.apply_fill(strategy, token, side, price, notional, t.ts);
+        }
+    }
+
+    fn apply_fill(
+        &self,
+        strategy: &str,
+        token_id: &str,
+        side: OrderSide,
+        price: f64,
+        notional: f64,
+        ts: i64,
+    ) {
+        let mut inner = self.inner.lock();
+        inner
+            .sleeves
+            .entry(sleeve_for(strategy))
+            .or_default()
+            .book
+            .apply(token_id, side, price, notional, ts, self.cfg.bucket_secs);
+    }
+
+    /// Reserve capital for a buy of `requested_usdc`. Returns a ticket for the
+    /// granted notional (clipped to the sleeve's headroom), or `None` if less
+    /// than `min_order_usdc` is available.
+    pub fn reserve(
+        self: &Arc<Self>,
+        strategy: &str,
+        requested_usdc: f64,
+    ) -> Option<AllocationTicket> {
+        if !(requested_usdc.is_finite() && requested_usdc > 0.0) {
+            return None;
+        }
+        let sleeve = sleeve_for(strategy);
+        let mut inner = self.inner.lock();
+        Self::release_expired_locked(&mut inner, Utc::now().timestamp());
+        let budgeted = self.cfg.enabled && self.cfg.weights.contains_key(&sleeve);
+        let state = inner.sleeves.entry(sleeve.clone()).or_default();
+
+        let granted = if budgeted {
+            let headroom =
+                (state.budget_usdc - state.book.deployed_usdc() - state.reserved_usdc).max(0.0);
+            let granted = requested_usdc.min(headroom);
+            if granted < self.cfg.min_order_usdc.min(requested_usdc) || granted <= 0.0 {
+                state.denied_orders += 1;
+                return None;
+            }
+            if granted < requested_usdc {
+                state.clipped_orders += 1;
+            }
+            granted
+        } else {
+            requested_usdc
+        };
+        state.reserved_usdc += granted;
+        drop(inner);
+
+        Some(AllocationTicket {
+            allocator: Arc::clone(self),
+            sleeve,
+            granted_usdc: granted,
+        })
+    }
+
+    /// Record an executed fill against the strategy's sleeve.
+    pub fn record_fill(
+        &self,
+        strategy: &str,
+        token_id: &str,
+        side: OrderSide,
+        price: f64,
+        notional_usdc: f64,
+    ) {
+        self.apply_fill(
+            strategy,
+            token_id,
+            side,
+            price,
+            notional_usdc,
+            Utc::now().timestamp(),
+        );
+    }
+
+    /// Record when `token_id`'s market resolves; its lots stop counting as
+    /// deployed from then on.
+    pub fn record_expiry(&self, token_id: &str, expires_at: i64) {
+        self.inner
+            .lock()
+            .expiries
+            .insert(token_id.to_string(), expires_at);
+    }
+
+    /// Release lots whose market resolved at or before `now`. Returns the
+    /// number of lots released.
+    pub fn release_expired(&self, now: i64) -> usize {
+        Self::release_expired_locked(&mut self.inner.lock(), now)
+    }
+
+    fn release_expired_locked(inner: &mut AllocatorInner, now: i64) -> usize {
+        let expired: Vec<String> = inner
+            .expiries
+            .iter()
+            .filter(|(_, &ts)| ts <= now)
+            .map(|(token, _)| token.clone())
+            .collect();
+        let mut released = 0;
+        for token in expired {
+            inner.expiries.remove(&token);
+            for s in inner.sleeves.values_mut() {
+                if s.book.lots.remove(&token).is_some() {
+                    released += 1;
+                }
+            }
+        }
+        released
+    }
+
+    /// Drop lots the ledger no longer holds and scale down lots that
+    /// together exceed the ledger's position.
+    pub fn sync_positions(&self, ledger: &VaultPaperLedger) {
+        let mut inner = self.inner.lock();
+        let mut held: HashMap<String, f64> = HashMap::new();
+        for s in inner.sleeves.values() {
+            for (token, lot) in &s.book.lots {
+                *held.entry(token.clone()).or_insert(0.0) += lot.shares;
+            }
+        }
+        for s in inner.sleeves.values_mut() {
+            s.book.lots.retain(|token, lot| {
+                let Some(pos) = ledger.positions.get(token) else {
+                    return false;
+                };
+                let tracked = held.get(token).copied().unwrap_or(0.0);
+                if tracked > pos.shares + 1e-9 && tracked > 0.0 {
+                    let scale = pos.shares / tracked;
+                    lot.shares *= scale;
+                    lot.cost_usdc *= scale;
+                }
+                lot.shares > 1e-9
+            });
+        }
+    }
+
+    /// Reset budgets against `capital_usdc` and record them.
+    pub async fn rebalance(&self, capital_usdc: f64, now: i64) -> Vec<VaultAllocationRecord> {
+        let since = now - self.cfg.lookback_secs;
+        let records = {
+            let mut inner = self.inner.lock();
+            let stats: BTreeMap<String, ReturnStats> = inner
+                .sleeves
+                .iter()
+                .map(|(k, s)| (k.clone(), s.book.stats_since(since)))
+                .collect();
+            let weights = target_weights(&self.cfg, &stats);
+            let capital = capital_usdc.max(0.0);
+            inner.capital_usdc = capital;
+            inner.rebalanced_at = Some(now);
+
+            let mut records = Vec::new();
+            for (sleeve, state) in inner.sleeves.iter_mut() {
+                state.stats = stats.get(sleeve).copied().unwrap_or_default();
+                state.weight = weights.get(sleeve).copied().unwrap_or(0.0);
+                state.budget_usdc = state.weight * capital;
+                if !weights.contains_key(sleeve) {
+                    continue;
+                }
+                records.push(VaultAllocationRecord {
+                    id: Uuid::new_v4().to_string(),
+                    ts: now,
+                    sleeve: sleeve.clone(),
+                    method: self.cfg.method.as_str().to_string(),
+                    weight: state.weight,
+                    budget_usdc: state.budget_usdc,
+                    deployed_usdc: state.book.deployed_usdc(),
+                    mean_return: (state.stats.samples > 0).then_some(state.stats.mean),
+                    return_vol: (state.stats.samples > 1).then_some(state.stats.vol),
+                    samples: state.stats.samples as i64,
+                });
+            }
+            records
+        };
+
+        if let Some(db) = &self.db {
+            if let Err(e) = db.insert_allocations(&records).await {
+                warn!(error = %e, "failed to record capital allocation");
+            }
+        }
+        records
+    }
+
+    pub fn status(&self) -> CapitalAllocationStatus {
+        let inner = self.inner.lock();
+        let sleeves = inner
+            .sleeves
+            .iter()
+            .map(|(sleeve, s)| {
+                let budgeted = self.cfg.enabled && self.cfg.weights.contains_key(sleeve);
+                let deployed = s.book.deployed_usdc();
+                SleeveAllocation {
+                    sleeve: sleeve.clone(),
+                    budgeted,
+                    weight: s.weight,
+                    budget_usdc: s.budget_usdc,
+                    deployed_usdc: deployed,
+                    reserved_usdc: s.reserved_usdc,
+                    available_usdc: (s.budget_usdc - deployed - s.reserved_usdc).max(0.0),
+                    realized_pnl_usdc: s.book.realized_pnl_usdc,
+                    stats: s.stats,
+                    clipped_orders: s.clipped_orders,
+                    denied_orders: s.denied_orders,
+                }
+            })
+            .collect();
+        CapitalAllocationStatus {
+            enabled: self.cfg.enabled,
+            method: self.cfg.method,
+            capital_usdc: inner.capital_usdc,
+            reserve_pct: self.cfg.reserve_pct,
+            rebalanced_at: inner.rebalanced_at,
+            sleeves,
+        }
+    }
+
+    /// Rebalance against the vault's NAV every `rebalance_secs`.
+    pub fn spawn_rebalance_loop(self: &Arc<Self>, vault: Arc<PooledVault>) {
+        let allocator = Arc::clone(self);
+        let period = Duration::from_secs(self.cfg.rebalance_secs);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let (nav, ledger_view) = {
+                    let ledger = vault.ledger.lock().await;
+                    (approximate_nav_usdc(&ledger), ledger.clone())
+                };
+                allocator.sync_positions(&ledger_view);
+                let now = Utc::now().timestamp();
+                allocator.release_expired(now);
+                let records = allocator.rebalance(nav, now).await;
+                info!(
+                    nav_usdc = nav,
+                    method = allocator.cfg.method.as_str(),
+                    sleeves = records.len(),
+                    "capital allocation rebalanced"
+                );
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::VaultPaperPosition;
+
+    async fn allocator(cfg: CapitalAllocatorConfig, capital: f64) -> Arc<CapitalAllocator> {
+        let a = Arc::new(CapitalAllocator::new(cfg, None));
+        a.rebalance(capital, 0).await;
+        a
+    }
+
+    #[tokio::test]
+    async fn test_engines_on_the_same_window_share_one_budget() {
+        let a = allocator(CapitalAllocatorConfig::default(), 1_000.0).await;
+        // UPDOWN15M: 40% of NAV, scaled to 90% investable -> 360.
+        let t1 = a.reserve("FAST15M", 300.0).unwrap();
+        assert_eq!(t1.granted_usdc(), 300.0);
+        let t2 = a.reserve("FAST15M_REACTIVE", 300.0).unwrap();
+        assert!((t2.granted_usdc() - 60.0).abs() < 1e-9);
+        assert!(a.reserve("FAST15M", 10.0).is_none());
+
+        // Other sleeves are unaffected.
+        assert!(a.reserve("LONG", 100.0).is_some());
+
+        // Unfilled reservations are released on drop; fills stay deployed.
+        a.record_fill("FAST15M", "tok", OrderSide::Buy, 0.5, 300.0);
+        drop(t1);
+        drop(t2);
+        let t3 = a.reserve("FAST15M", 100.0).unwrap();
+        assert!((t3.granted_usdc() - 60.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_sells_release_capital_and_realize_returns() {
+        let a = allocator(CapitalAllocatorConfig::default(), 1_000.0).await;
+        a.record_fill("LONG", "tok", OrderSide::Buy, 0.40, 200.0);
+        a.record_fill("LONG_EXIT", "tok", OrderSide::Sell, 0.50, 125.0);
+        let long = a
+            .status()
+            .sleeves
+            .into_iter()
+            .find(|s| s.sleeve == "LONG")
+            .unwrap();
+        // Sold half the shares: cost 100 released, 25 realized.
+        assert!((long.deployed_usdc - 100.0).abs() < 1e-9);
+        assert!((long.realized_pnl_usdc - 25.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_sync_drops_lots_the_ledger_no_longer_holds() {
+        let a = allocator(CapitalAllocatorConfig::default(), 1_000.0).await;
+        a.record_fill("FAST15M", "gone", OrderSide::Buy, 0.5, 50.0);
+        a.record_fill("FAST15M", "kept", OrderSide::Buy, 0.5, 50.0);
+        let mut ledger = VaultPaperLedger::default();
+        ledger.positions.insert(
+            "kept".to_string(),
+            VaultPaperPosition {
+                token_id: "kept".to_string(),
+                outcome: "Up".to_string(),
+                shares: 50.0,
+                cost_usdc: 25.0,
+                avg_price: 0.5,
+            },
+        );
+        a.sync_positions(&ledger);
+        let up = a
+            .status()
+            .sleeves
+            .into_iter()
+            .find(|s| s.sleeve == "UPDOWN15M")
+            .unwrap();
+        assert!((up.deployed_usdc - 25.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_resolution_releases_held_lots() {
+        let a = allocator(CapitalAllocatorConfig::default(), 1_000.0).await;
+        // Two windows of buys held to resolution fill the 360 UPDOWN15M budget.
+        // `reserve` also releases against the wall clock: use a future window.
+        let end_ts = Utc::now().timestamp() + 15 * 60;
+        a.record_expiry("up-1", end_ts);
+        a.record_fill("FAST15M", "up-1", OrderSide::Buy, 0.5, 200.0);
+        a.record_fill("FAST15M_REACTIVE", "down-2", OrderSide::Buy, 0.5, 160.0);
+        assert!(a.reserve("FAST15M", 10.0).is_none());
+
+        // Nothing resolves before the window ends.
+        assert_eq!(a.release_expired(end_ts - 1), 0);
+        assert!(a.reserve("FAST15M", 10.0).is_none());
+
+        // Crossing the boundary frees the resolved window's capital only.
+        assert_eq!(a.release_expired(end_ts), 1);
+        let up = a
+            .status()
+            .sleeves
+            .into_iter()
+            .find(|s| s.sleeve == "UPDOWN15M")
+            .unwrap();
+        assert!((up.deployed_usdc - 160.0).abs() < 1e-9);
+        assert!((a.reserve("FAST15M", 500.0).unwrap().granted_usdc() - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_risk_parity_and_kelly_follow_realized_returns() {
+        let mut cfg = CapitalAllocatorConfig {
+            weights: [("A".to_string(), 0.5), ("B".to_string(), 0.5)]
+                .into_iter()
+                .collect(),
+            reserve_pct: 0.0,
+            min_samples: 3,
+            min_weight: 0.0,
+            max_weight: 1.0,
+            ..Default::default()
+        };
+        let stats: BTreeMap<String, ReturnStats> = [
+            (
+                "A".to_string(),
+                ReturnStats::from_returns(&[0.01, 0.03, 0.02, 0.02]),
+            ),
+            (
+                "B".to_string(),
+                ReturnStats::from_returns(&[0.10, -0.06, 0.04, 0.0]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        cfg.method = AllocationMethod::RiskParity;
+        let w = target_weights(&cfg, &stats);
+        assert!(w["A"] > w["B"]);
+        assert!((w["A"] + w["B"] - 1.0).abs() < 1e-9);
+
+        cfg.method = AllocationMethod::Kelly;
+        cfg.kelly_fraction = 0.01;
+        let w = target_weights(&cfg, &stats);
+        assert!(w["A"] > w["B"]);
+        assert!(w["A"] + w["B"] <= 1.0 + 1e-9);
+
+        // Too little history: fixed weights.
+        cfg.min_samples = 10;
+        let w = target_weights(&cfg, &stats);
+        assert_eq!(w["A"], 0.5);
+        assert_eq!(w["B"], 0.5);
+    }
+}
@@ -12,6 +12,7 @@
 
 use anyhow::Result;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, VecDeque},
     env,
@@ -19,23 +20,26 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::sync::Mutex;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
     models::{MarketSignal, SignalType, WsServerEvent},
     scrapers::{polymarket::OrderBook, polymarket_gamma},
+    signals::db_storage::VaultLlmPromptRow,
     vault::{
-        calculate_kelly_position, estimate_p_up_enhanced, p_up_driftless_lognormal,
-        parse_decision_dsl, parse_updown_15m_slug, shrink_to_half, DecisionAction,
-        ExecutionAdapter, KellyParams, OpenRouterClient, OrderRequest, OrderSide,
-        PaperExecutionAdapter, PolymarketClobAdapter, TimeInForce, UpDown15mMarket, UpDownAsset,
-        VaultActivityRecord, VaultNavSnapshotRecord,
+        best_ask_cached, calculate_kelly_position, estimate_p_up_enhanced,
+        p_up_driftless_lognormal, parse_decision_dsl, parse_updown_15m_slug, recover_vault_state,
+        shrink_to_half, DecisionAction, ExecutionAdapter, KellyParams, OpenRouterClient, OrderAck,
+        OrderRequest, OrderSide, PaperExecutionAdapter, PolymarketClobAdapter, RecoveryConfig,
+        StalenessConfig, TimeInForce, UpDown15mMarket, UpDownAsset, VaultActivityRecord,
+        VaultJournal, VaultNavSnapshotRecord,
     },
     AppState,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VaultEngineConfig {
     pub enabled: bool,
     pub paper: bool,
@@ -774,18 +778,25 @@ pub struct VaultEngine {
     state: AppState,
     cfg: VaultEngineConfig,
     exec: Arc<dyn ExecutionAdapter>,
+    /// Live venue, for balance/position reconciliation on startup
+    venue: Option<Arc<PolymarketClobAdapter>>,
+    journal: VaultJournal,
+    /// Strategy config version `cfg` was taken from; recorded on every order
+    config_version: i64,
     llm: Option<OpenRouterClient>,
     long_state: Arc<Mutex<LongEngineState>>,
 }
 
 impl VaultEngine {
     pub fn spawn(state: AppState) {
-        let mut cfg = VaultEngineConfig::from_env();
+        let active = state.strategy_config.current();
+        let mut cfg = active.params.engine.clone();
         if !cfg.enabled {
             info!("vault engine disabled (set VAULT_ENGINE_ENABLED=1)");
             return;
         }
 
+        let mut venue: Option<Arc<PolymarketClobAdapter>> = None;
         let exec: Arc<dyn ExecutionAdapter> = if cfg.paper {
             info!("vault engine running in PAPER mode");
             Arc::new(PaperExecutionAdapter::default())
@@ -793,7 +804,9 @@ impl VaultEngine {
             match PolymarketClobAdapter::from_env() {
                 Some(clob) => {
                     info!("vault engine running in LIVE mode (Polymarket CLOB)");
-                    Arc::new(clob)
+                    let clob = Arc::new(clob);
+                    venue = Some(clob.clone());
+                    clob
                 }
                 None => {
                     warn!(
@@ -804,6 +817,13 @@ impl VaultEngine {
             }
         };
 
+        // Halts cancel this engine's resting orders.
+        for strategy in ["FAST15M", "LONG", "LONG_EXIT"] {
+            state
+                .trading_control
+                .register_adapter(strategy, exec.clone());
+        }
+
         let long_state = Arc::new(Mutex::new(LongEngineState::new()));
 
         let mut llm: Option<OpenRouterClient> = None;
@@ -829,21 +849,160 @@ impl VaultEngine {
             state: state.clone(),
             cfg: cfg.clone(),
             exec,
+            venue,
+            journal: VaultJournal::new(state.vault.db.clone()),
+            config_version: active.version,
             llm,
             long_state,
         };
 
-        tokio::spawn(engine.clone().run_updown15m());
-
         // Periodic NAV snapshots (for PERFORMANCE tab).
         tokio::spawn(engine.clone().run_nav_snapshot_loop());
 
-        if engine.cfg.long_enabled {
-            tokio::spawn(engine.clone().run_long_engine());
+        // Trading resumes only once journaled orders and inventory are
+        // reconciled with the venue.
+        tokio::spawn(async move {
+            if !engine.recover().await {
+                return;
+            }
+
+            tokio::spawn(engine.clone().run_updown15m());
+
+            if engine.cfg.long_enabled {
+                tokio::spawn(engine.clone().run_long_engine());
+            }
+
+            // Signal router (non-15m): currently logs only.
+            tokio::spawn(engine.run_signal_router(state.signal_broadcast.subscribe()));
+        });
+    }
+
+    /// Startup recovery. Returns false if trading must not start.
+    async fn recover(&self) -> bool {
+        let recovery_cfg = RecoveryConfig::from_env();
+        let report = match recover_vault_state(
+            &self.state.vault,
+            self.exec.as_ref(),
+            self.venue.as_deref(),
+            &recovery_cfg,
+        )
+        .await
+        {
+            Ok(report) => report,
+            Err(e) if self.venue.is_some() => {
+                error!(error = %e, "vault recovery failed; LIVE trading not started");
+                return false;
+            }
+            Err(e) => {
+                warn!(error = %e, "vault recovery failed; continuing in PAPER mode");
+                return true;
+            }
+        };
+
+        if self.venue.is_some()
+            && recovery_cfg.reconcile_venue
+            && !report.safe_to_trade(&recovery_cfg)
+        {
+            error!(
+                discrepancies = report.position_discrepancies.len(),
+                journal_cash_usdc = report.journal_cash_usdc,
+                venue_cash_usdc = ?report.venue_cash_usdc,
+                "journaled vault state does not match venue (set VAULT_RECOVERY_ADOPT_VENUE=1 to adopt it); LIVE trading not started"
+            );
+            return false;
         }
+        true
+    }
 
-        // Signal router (non-15m): currently logs only.
-        tokio::spawn(engine.run_signal_router(state.signal_broadcast.subscribe()));
+    /// Pick up a strategy config version promoted at a window boundary.
+    /// Startup-only settings cannot change at runtime, except that spawn may
+    /// have turned the LONG engine off.
+    async fn refresh_config(&mut self) {
+        let active = self.state.strategy_config.active().await;
+        if active.version == self.config_version {
+            return;
+        }
+        let long_enabled = self.cfg.long_enabled;
+        self.cfg = active.params.engine.clone();
+        self.cfg.long_enabled = long_enabled;
+        self.config_version = active.version;
+        info!(
+            version = active.version,
+            updown_min_edge = self.cfg.updown_min_edge,
+            long_min_edge = self.cfg.long_min_edge,
+            "vault engine config updated"
+        );
+    }
+
+    /// Send an order through the write-ahead journal: the submission is
+    /// journaled before the venue sees it, and the fill is journaled with the
+    /// resulting position and cash once the ledger is updated. Returns the ack
+    /// with post-trade cash and total vault shares.
+    async fn place_journaled(
+        &self,
+        req: &OrderRequest,
+        strategy: &str,
+    ) -> Result<(OrderAck, f64, f64)> {
+        let mut order = self
+            .journal
+            .record_submit(req, strategy, Some(self.config_version))
+            .await?;
+        let placed = self.exec.place_order(req.clone()).await;
+        self.state
+            .trading_control
+            .record_order_outcome(strategy, placed.is_err())
+            .await;
+        let ack = match placed {
+            Ok(ack) => ack,
+            Err(e) => {
+                if let Err(je) = self.journal.record_reject(&mut order, &e.to_string()).await {
+                    warn!(
+                        error = %je,
+                        client_order_id = %req.client_order_id,
+                        "failed to journal reject"
+                    );
+                }
+                return Err(e);
+            }
+        };
+
+        let mut ledger = self.state.vault.ledger.lock().await;
+        match req.side {
+            OrderSide::Buy => {
+                ledger.apply_buy(
+                    &req.token_id,
+                    req.outcome.as_deref().unwrap_or(""),
+                    ack.filled_price,
+                    ack.filled_notional_usdc,
+                    ack.fees_usdc,
+                );
+            }
+            OrderSide::Sell => {
+                ledger.apply_sell(
+                    &req.token_id,
+                    ack.filled_price,
+                    ack.filled_notional_usdc,
+                    ack.fees_usdc,
+                );
+            }
+        }
+        self.state.capital_allocator.record_fill(
+            strategy,
+            &req.token_id,
+            req.side,
+            ack.filled_price,
+            ack.filled_notional_usdc,
+        );
+        let total_shares = self.state.vault.shares.lock().await.total_shares;
+        if let Err(e) = self
+            .journal
+            .record_execution(&mut order, &ack, &ledger, total_shares)
+            .await
+        {
+            // The order is journaled as PENDING; restart recovery reconciles it.
+            warn!(error = %e, order_id = %ack.order_id, "failed to journal fill");
+        }
+        Ok((ack, ledger.cash_usdc, total_shares))
     }
 
     async fn run_signal_router(self, mut rx: tokio::sync::broadcast::Receiver<WsServerEvent>) {
@@ -915,7 +1074,7 @@ impl VaultEngine {
         long.upsert_market_signal(signal, now);
     }
 
-    async fn run_long_engine(self) -> Result<()> {
+    async fn run_long_engine(mut self) -> Result<()> {
         let Some(llm) = self.llm.clone() else {
             return Ok(());
         };
@@ -925,13 +1084,23 @@ impl VaultEngine {
 
         loop {
             interval.tick().await;
+            self.refresh_config().await;
 
-            if let Err(e) = self.long_manage_exits().await {
-                warn!(error = %e, "vault LONG exit loop error");
+            if self
+                .state
+                .trading_control
+                .halt_for("LONG_EXIT", None)
+                .is_none()
+            {
+                if let Err(e) = self.long_manage_exits().await {
+                    warn!(error = %e, "vault LONG exit loop error");
+                }
             }
 
-            if let Err(e) = self.long_process_pending(&llm).await {
-                warn!(error = %e, "vault LONG decision loop error");
+            if self.state.trading_control.halt_for("LONG", None).is_none() {
+                if let Err(e) = self.long_process_pending(&llm).await {
+                    warn!(error = %e, "vault LONG decision loop error");
+                }
             }
         }
     }
@@ -1080,26 +1249,7 @@ impl VaultEngine {
                 outcome: Some(outcome.clone()),
             };
 
-            let ack = self.exec.place_order(req).await?;
-            let (cash_usdc, total_shares) = {
-                let mut ledger = self.state.vault.ledger.lock().await;
-                ledger.apply_sell(
-                    &token_id,
-                    ack.filled_price,
-                    ack.filled_notional_usdc,
-                    ack.fees_usdc,
-                );
-                (
-                    ledger.cash_usdc,
-                    self.state.vault.shares.lock().await.total_shares,
-                )
-            };
-            let _ = self
-                .state
-                .vault
-                .db
-                .upsert_state(cash_usdc, total_shares, ack.filled_at)
-                .await;
+            let (ack, cash_usdc, total_shares) = self.place_journaled(&req, "LONG_EXIT").await?;
 
             let (nav_usdc, positions_value_usdc, nav_per_share) = {
                 let ledger = self.state.vault.ledger.lock().await;
@@ -1142,6 +1292,7 @@ impl VaultEngine {
                     notional_usdc: Some(ack.filled_notional_usdc),
                     strategy: Some("LONG_EXIT".to_string()),
                     decision_id: prior_meta.and_then(|m| m.decision_id),
+                    config_version: Some(self.config_version),
                 })
                 .await;
             let _ = self
@@ -1242,6 +1393,28 @@ impl VaultEngine {
         let system = long_system_prompt();
         let user = long_user_prompt(signal, &gamma, expiry_ts, tte_days);
 
+        // Keep the exact prompts (and the asks they were made against) for offline replay.
+        // Cache-only: a REST book fetch here would delay the scout call.
+        let staleness = StalenessConfig::long_strategy();
+        let asks: Vec<Option<f64>> = gamma
+            .clob_token_ids
+            .iter()
+            .map(|token_id| best_ask_cached(&self.state.polymarket_market_ws, token_id, &staleness))
+            .collect();
+        self.state
+            .signal_storage
+            .insert_vault_llm_prompt(&VaultLlmPromptRow {
+                decision_id: decision_id.clone(),
+                market_slug: market_slug.to_string(),
+                created_at: now,
+                system_prompt: system.clone(),
+                user_prompt: user.clone(),
+                outcomes: gamma.outcomes.clone(),
+                asks,
+            })
+            .await
+            .ok();
+
         let scout_out = llm
             .chat_completion(
                 scout_model,
@@ -1538,6 +1711,14 @@ impl VaultEngine {
         if notional < self.cfg.long_min_trade_usd {
             return Ok(());
         }
+        let Some(allocation) = self.state.capital_allocator.reserve("LONG", notional) else {
+            debug!(market_slug = %market_slug, notional, "LONG budget exhausted");
+            return Ok(());
+        };
+        let notional = allocation.granted_usdc();
+        if notional < self.cfg.long_min_trade_usd {
+            return Ok(());
+        }
 
         let client_order_id = Uuid::new_v5(
             &Uuid::NAMESPACE_URL,
@@ -1556,28 +1737,9 @@ impl VaultEngine {
             outcome: Some(gamma.outcomes[win_i].clone()),
         };
 
-        let ack = self.exec.place_order(req.clone()).await?;
+        let (ack, cash_usdc, total_shares) = self.place_journaled(&req, "LONG").await?;
         let updated_at = ack.filled_at;
 
-        let cash_usdc = {
-            let mut ledger = self.state.vault.ledger.lock().await;
-            ledger.apply_buy(
-                &req.token_id,
-                req.outcome.as_deref().unwrap_or(""),
-                ack.filled_price,
-                ack.filled_notional_usdc,
-                ack.fees_usdc,
-            );
-            ledger.cash_usdc
-        };
-        let total_shares = self.state.vault.shares.lock().await.total_shares;
-        let _ = self
-            .state
-            .vault
-            .db
-            .upsert_state(cash_usdc, total_shares, updated_at)
-            .await;
-
         let (nav_usdc, positions_value_usdc, nav_per_share) = {
             let ledger = self.state.vault.ledger.lock().await;
             let nav = crate::vault::approximate_nav_usdc(&ledger);
@@ -1609,6 +1771,7 @@ impl VaultEngine {
                 notional_usdc: Some(ack.filled_notional_usdc),
                 strategy: Some("LONG".to_string()),
                 decision_id: Some(decision_id.clone()),
+                config_version: Some(self.config_version),
             })
             .await;
         let _ = self
@@ -1746,38 +1909,33 @@ impl VaultEngine {
             .await;
     }
 
-    async fn run_updown15m(self) -> Result<()> {
+    async fn run_updown15m(mut self) -> Result<()> {
         let mut interval = tokio::time::interval(Duration::from_millis(self.cfg.updown_poll_ms));
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         let mut token_cache: HashMap<String, (String, String)> = HashMap::new();
         let mut last_trade_ts: HashMap<String, i64> = HashMap::new();
-        let mut halted = false;
+        let control = self.state.trading_control.clone();
 
         loop {
             interval.tick().await;
+            self.refresh_config().await;
             let now = Utc::now().timestamp();
 
             // === DRAWDOWN CIRCUIT BREAKER ===
-            if !halted {
-                let nav_usdc = {
-                    let ledger = self.state.vault.ledger.lock().await;
-                    crate::vault::approximate_nav_usdc(&ledger)
-                };
-                let drawdown = 1.0 - (nav_usdc / self.cfg.initial_bankroll);
-                if drawdown >= self.cfg.max_drawdown_pct {
-                    warn!(
-                        nav_usdc = %nav_usdc,
-                        initial = %self.cfg.initial_bankroll,
-                        drawdown_pct = %(drawdown * 100.0),
-                        max_drawdown_pct = %(self.cfg.max_drawdown_pct * 100.0),
-                        "DRAWDOWN CIRCUIT BREAKER TRIGGERED - HALTING ALL TRADING"
-                    );
-                    halted = true;
-                }
-            }
-            if halted {
-                // Engine is halted due to drawdown - skip all trading
+            // Global halt until an operator resumes via the admin API.
+            let nav_usdc = {
+                let ledger = self.state.vault.ledger.lock().await;
+                crate::vault::approximate_nav_usdc(&ledger)
+            };
+            control
+                .check_drawdown(
+                    nav_usdc,
+                    self.cfg.initial_bankroll,
+                    self.cfg.max_drawdown_pct,
+                )
+                .await;
+            if control.halt_for("FAST15M", None).is_some() {
                 continue;
             }
             let start_ts = now - (now % (15 * 60));
@@ -1789,6 +1947,10 @@ impl VaultEngine {
                 UpDownAsset::Sol,
                 UpDownAsset::Xrp,
             ] {
+                if control.halt_for("FAST15M", Some(asset.as_str())).is_some() {
+                    continue;
+                }
+
                 let slug = format!("{}-updown-15m-{}", asset.as_str(), start_ts);
                 let market = UpDown15mMarket {
                     asset,
@@ -1824,38 +1986,34 @@ impl VaultEngine {
                         if up.is_empty() || down.is_empty() {
                             continue;
                         }
+                        control.register_token(&up, asset.as_str());
+                        control.register_token(&down, asset.as_str());
                         token_cache.insert(slug.clone(), (up.clone(), down.clone()));
                         (up, down)
                     }
                 };
 
-                if let Some(req) = self
+                if let Some(mut req) = self
                     .evaluate_updown15m(&market, &slug, &token_up, &token_down)
                     .await?
                 {
-                    let ack = self.exec.place_order(req.clone()).await?;
+                    // Shared with the reactive engine: both draw on UPDOWN15M.
+                    self.state
+                        .capital_allocator
+                        .record_expiry(&req.token_id, market.end_ts);
+                    let Some(allocation) = self
+                        .state
+                        .capital_allocator
+                        .reserve("FAST15M", req.notional_usdc)
+                    else {
+                        debug!(market_slug = %slug, "UPDOWN15M budget exhausted");
+                        continue;
+                    };
+                    req.notional_usdc = allocation.granted_usdc();
+                    let (ack, cash_usdc, total_shares) =
+                        self.place_journaled(&req, "FAST15M").await?;
                     let updated_at = ack.filled_at;
                     if req.side == OrderSide::Buy {
-                        let cash_usdc = {
-                            let mut ledger = self.state.vault.ledger.lock().await;
-                            ledger.apply_buy(
-                                &req.token_id,
-                                req.outcome.as_deref().unwrap_or(""),
-                                ack.filled_price,
-                                ack.filled_notional_usdc,
-                                ack.fees_usdc,
-                            );
-                            ledger.cash_usdc
-                        };
-
-                        let total_shares = self.state.vault.shares.lock().await.total_shares;
-                        let _ = self
-                            .state
-                            .vault
-                            .db
-                            .upsert_state(cash_usdc, total_shares, updated_at)
-                            .await;
-
                         let (nav_usdc, positions_value_usdc, nav_per_share) = {
                             let ledger = self.state.vault.ledger.lock().await;
                             let nav = crate::vault::approximate_nav_usdc(&ledger);
@@ -1887,6 +2045,7 @@ impl VaultEngine {
                                 notional_usdc: Some(ack.filled_notional_usdc),
                                 strategy: Some("FAST15M".to_string()),
                                 decision_id: None,
+                                config_version: Some(self.config_version),
                             })
                             .await;
                         let _ = self
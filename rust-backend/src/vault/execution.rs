@@ -4,15 +4,27 @@ use base64::{
     Engine,
 };
 use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
 use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use reqwest::{Client, Method};
+use serde::{Deserialize, Deserializer, Serialize};
 use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::broadcast;
 use tokio::time::{sleep, Duration};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, info, warn};
 
+use crate::performance::global_venue_tracker;
+use crate::venue_limits::POLYMARKET_MAX_BATCH_SIZE;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OrderSide {
     Buy,
@@ -57,11 +69,365 @@ pub struct OrderAck {
     /// Simulated latency in ms
     #[serde(default)]
     pub latency_ms: u64,
+    /// Shares left resting on the book (GTC orders not filled immediately)
+    #[serde(default)]
+    pub resting_size: f64,
+}
+
+/// Venue-side order lifecycle state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrderStatus {
+    /// Resting on the book, nothing filled yet
+    Open,
+    /// Resting on the book with some size filled
+    PartiallyFilled,
+    Filled,
+    /// Removed from the book (by us, IOC expiry or the venue), possibly after partial fills
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    pub fn is_open(self) -> bool {
+        matches!(self, OrderStatus::Open | OrderStatus::PartiallyFilled)
+    }
+}
+
+/// An order as currently known to an execution adapter. Sizes are in shares.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderState {
+    pub order_id: String,
+    pub client_order_id: String,
+    pub token_id: String,
+    pub side: OrderSide,
+    /// Limit price
+    pub price: f64,
+    pub size: f64,
+    pub filled_size: f64,
+    /// Volume-weighted fill price (0 when nothing filled)
+    pub avg_fill_price: f64,
+    pub status: OrderStatus,
+    pub updated_at: i64,
+}
+
+impl OrderState {
+    pub fn remaining_size(&self) -> f64 {
+        (self.size - self.filled_size).max(0.0)
+    }
+}
+
+/// Order lifecycle events published by execution adapters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    Ack {
+        order_id: String,
+        client_order_id: String,
+        token_id: String,
+        at: i64,
+    },
+    /// `size` shares filled; `filled_size` is cumulative for the order
+    Fill {
+        order_id: String,
+        client_order_id: String,
+        token_id: String,
+        side: OrderSide,
+        price: f64,
+        size: f64,
+        filled_size: f64,
+        remaining_size: f64,
+        fee_usdc: f64,
+        is_maker: bool,
+        at: i64,
+    },
+    /// Unfilled remainder removed from the book
+    Cancelled {
+        order_id: String,
+        client_order_id: String,
+        cancelled_size: f64,
+        at: i64,
+    },
+    Rejected {
+        client_order_id: String,
+        token_id: String,
+        reason: String,
+        at: i64,
+    },
 }
 
+impl ExecutionEvent {
+    pub fn order_id(&self) -> Option<&str> {
+        match self {
+            ExecutionEvent::Ack { order_id, .. }
+            | ExecutionEvent::Fill { order_id, .. }
+            | ExecutionEvent::Cancelled { order_id, .. } => Some(order_id),
+            ExecutionEvent::Rejected { .. } => None,
+        }
+    }
+}
+
+/// Order entry and lifecycle management for a venue.
+///
+/// `place_order` returns the immediate fill. A GTC order that does not fill in
+/// full rests on the book (`OrderAck::resting_size`); its later fills and
+/// cancellation are published on `subscribe_events`.
 #[async_trait::async_trait]
 pub trait ExecutionAdapter: Send + Sync {
     async fn place_order(&self, req: OrderRequest) -> Result<OrderAck>;
+
+    /// Place several orders, returning one result per request in order.
+    /// Venues accept or reject each order of a batch on its own; adapters
+    /// without a batch endpoint place them one after another.
+    async fn place_orders(&self, reqs: Vec<OrderRequest>) -> Vec<Result<OrderAck>> {
+        let mut results = Vec::with_capacity(reqs.len());
+        for req in reqs {
+            results.push(self.place_order(req).await);
+        }
+        results
+    }
+
+    /// Cancel a resting order.
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        Err(anyhow!("cancel not supported by this adapter (order {})", order_id))
+    }
+
+    /// Cancel all resting orders, optionally only for `token_id`. Returns the cancelled order ids.
+    async fn cancel_all(&self, token_id: Option<&str>) -> Result<Vec<String>> {
+        Err(anyhow!(
+            "cancel-all not supported by this adapter (token {:?})",
+            token_id
+        ))
+    }
+
+    async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+        Err(anyhow!(
+            "order status not supported by this adapter (order {})",
+            order_id
+        ))
+    }
+
+    /// Resting orders, optionally only for `token_id`.
+    async fn open_orders(&self, token_id: Option<&str>) -> Result<Vec<OrderState>> {
+        Err(anyhow!(
+            "open orders not supported by this adapter (token {:?})",
+            token_id
+        ))
+    }
+
+    /// Acks, fills, cancels and rejects. Adapters without an event stream return a closed receiver.
+    fn subscribe_events(&self) -> broadcast::Receiver<ExecutionEvent> {
+        broadcast::channel(1).1
+    }
+}
+
+// ============================================================================
+// Order Tracker
+// ============================================================================
+
+const EXECUTION_EVENT_CAPACITY: usize = 4096;
+/// Terminal orders are pruned once the table grows past this
+const MAX_TRACKED_ORDERS: usize = 10_000;
+/// Share quantities below this are treated as zero
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Order table and event channel shared by the adapters.
+#[derive(Debug)]
+pub struct OrderTracker {
+    orders: Mutex<HashMap<String, OrderState>>,
+    events: broadcast::Sender<ExecutionEvent>,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EXECUTION_EVENT_CAPACITY);
+        Self {
+            orders: Mutex::new(HashMap::new()),
+            events,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, event: ExecutionEvent) {
+        // No subscribers is fine
+        let _ = self.events.send(event);
+    }
+
+    /// Record an accepted order and publish its ack.
+    pub fn insert(&self, state: OrderState) {
+        let event = ExecutionEvent::Ack {
+            order_id: state.order_id.clone(),
+            client_order_id: state.client_order_id.clone(),
+            token_id: state.token_id.clone(),
+            at: state.updated_at,
+        };
+        {
+            let mut orders = self.orders.lock();
+            if orders.len() >= MAX_TRACKED_ORDERS {
+                orders.retain(|_, o| o.status.is_open());
+            }
+            orders.insert(state.order_id.clone(), state);
+        }
+        self.publish(event);
+    }
+
+    /// Apply a fill to an open order (clamped to its remaining size) and
+    /// publish it. Unknown and closed orders are ignored.
+    pub fn fill(
+        &self,
+        order_id: &str,
+        price: f64,
+        size: f64,
+        fee_usdc: f64,
+        is_maker: bool,
+    ) -> Option<ExecutionEvent> {
+        let event = {
+            let mut orders = self.orders.lock();
+            let order = orders.get_mut(order_id)?;
+            let size = size.min(order.remaining_size());
+            if !order.status.is_open() || size <= SIZE_EPSILON {
+                return None;
+            }
+            let filled = order.filled_size + size;
+            order.avg_fill_price = (order.avg_fill_price * order.filled_size + price * size) / filled;
+            order.filled_size = filled;
+            order.status = if order.remaining_size() <= SIZE_EPSILON {
+                OrderStatus::Filled
+            } else {
+                OrderStatus::PartiallyFilled
+            };
+            order.updated_at = Utc::now().timestamp();
+
+            ExecutionEvent::Fill {
+                order_id: order.order_id.clone(),
+                client_order_id: order.client_order_id.clone(),
+                token_id: order.token_id.clone(),
+                side: order.side,
+                price,
+                size,
+                filled_size: order.filled_size,
+                remaining_size: order.remaining_size(),
+                fee_usdc,
+                is_maker,
+                at: order.updated_at,
+            }
+        };
+        self.publish(event.clone());
+        Some(event)
+    }
+
+    /// Close an open order and publish the cancellation of its remainder.
+    pub fn cancel(&self, order_id: &str) -> Result<OrderState> {
+        let (state, event) = {
+            let mut orders = self.orders.lock();
+            let order = orders
+                .get_mut(order_id)
+                .ok_or_else(|| anyhow!("unknown order {}", order_id))?;
+            if !order.status.is_open() {
+                return Err(anyhow!("order {} is not open ({:?})", order_id, order.status));
+            }
+            order.status = OrderStatus::Cancelled;
+            order.updated_at = Utc::now().timestamp();
+            let event = ExecutionEvent::Cancelled {
+                order_id: order.order_id.clone(),
+                client_order_id: order.client_order_id.clone(),
+                cancelled_size: order.remaining_size(),
+                at: order.updated_at,
+            };
+            (order.clone(), event)
+        };
+        self.publish(event);
+        Ok(state)
+    }
+
+    /// Publish the rejection of an order that never reached the book.
+    pub fn reject(&self, req: &OrderRequest, reason: &str) {
+        self.publish(ExecutionEvent::Rejected {
+            client_order_id: req.client_order_id.clone(),
+            token_id: req.token_id.clone(),
+            reason: reason.to_string(),
+            at: Utc::now().timestamp(),
+        });
+    }
+
+    /// Bring a tracked order up to date with the venue's view, publishing
+    /// missed fills (at the limit price) and cancellation.
+    pub fn reconcile(&self, venue: &OrderState) {
+        let Some(local) = self.get(&venue.order_id) else {
+            self.orders.lock().insert(venue.order_id.clone(), venue.clone());
+            return;
+        };
+        let missed = venue.filled_size - local.filled_size;
+        if missed > SIZE_EPSILON {
+            self.fill(&venue.order_id, venue.price, missed, 0.0, true);
+        }
+        if venue.status == OrderStatus::Cancelled {
+            let _ = self.cancel(&venue.order_id);
+        }
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<OrderState> {
+        self.orders.lock().get(order_id).cloned()
+    }
+
+    pub fn open_orders(&self, token_id: Option<&str>) -> Vec<OrderState> {
+        let mut open: Vec<OrderState> = self
+            .orders
+            .lock()
+            .values()
+            .filter(|o| o.status.is_open() && token_id.is_none_or(|t| o.token_id == t))
+            .cloned()
+            .collect();
+        open.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+        open
+    }
+
+    /// Record the outcome of an order submission: ack, immediate fill, and
+    /// cancellation of the remainder unless it rests on the book.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn record_submission(
+        &self,
+        req: &OrderRequest,
+        order_id: &str,
+        size: f64,
+        filled_size: f64,
+        filled_price: f64,
+        fees_usdc: f64,
+        rests: bool,
+    ) -> f64 {
+        let now = Utc::now().timestamp();
+        self.insert(OrderState {
+            order_id: order_id.to_string(),
+            client_order_id: req.client_order_id.clone(),
+            token_id: req.token_id.clone(),
+            side: req.side,
+            price: req.price,
+            size,
+            filled_size: 0.0,
+            avg_fill_price: 0.0,
+            status: OrderStatus::Open,
+            updated_at: now,
+        });
+        if filled_size > SIZE_EPSILON {
+            self.fill(order_id, filled_price, filled_size, fees_usdc, false);
+        }
+        match self.get(order_id) {
+            Some(state) if state.status.is_open() && rests => state.remaining_size(),
+            Some(state) if state.status.is_open() => {
+                let _ = self.cancel(order_id);
+                0.0
+            }
+            _ => 0.0,
+        }
+    }
 }
 
 /// Paper execution configuration for realistic simulation
@@ -147,19 +513,44 @@ impl PaperExecutionConfig {
 #[derive(Debug, Clone)]
 pub struct PaperExecutionAdapter {
     pub config: PaperExecutionConfig,
+    orders: Arc<OrderTracker>,
 }
 
 impl Default for PaperExecutionAdapter {
     fn default() -> Self {
-        Self {
-            config: PaperExecutionConfig::from_env(),
-        }
+        Self::new(PaperExecutionConfig::from_env())
     }
 }
 
 impl PaperExecutionAdapter {
     pub fn new(config: PaperExecutionConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            orders: Arc::new(OrderTracker::new()),
+        }
+    }
+
+    /// Fill resting orders on `token_id` that the market has traded through:
+    /// bids at or above `best_ask`, asks at or below `best_bid`. Fills are
+    /// maker fills at the order's limit price. Returns the fill events.
+    pub fn match_resting_orders(
+        &self,
+        token_id: &str,
+        best_bid: Option<f64>,
+        best_ask: Option<f64>,
+    ) -> Vec<ExecutionEvent> {
+        self.orders
+            .open_orders(Some(token_id))
+            .into_iter()
+            .filter(|o| match o.side {
+                OrderSide::Buy => best_ask.is_some_and(|ask| o.price >= ask),
+                OrderSide::Sell => best_bid.is_some_and(|bid| o.price <= bid),
+            })
+            .filter_map(|o| {
+                self.orders
+                    .fill(&o.order_id, o.price, o.remaining_size(), 0.0, true)
+            })
+            .collect()
     }
 }
 
@@ -170,9 +561,11 @@ impl ExecutionAdapter for PaperExecutionAdapter {
 
         // Validate inputs
         if !(req.price.is_finite() && req.price > 0.0 && req.price < 1.0) {
+            self.orders.reject(&req, "invalid price");
             return Err(anyhow!("invalid price"));
         }
         if !(req.notional_usdc.is_finite() && req.notional_usdc > 0.0) {
+            self.orders.reject(&req, "invalid notional");
             return Err(anyhow!("invalid notional"));
         }
 
@@ -183,6 +576,7 @@ impl ExecutionAdapter for PaperExecutionAdapter {
 
         // Random rejection (simulates network errors, invalid token, etc.)
         if rng.gen::<f64>() < self.config.reject_prob {
+            self.orders.reject(&req, "order rejected (simulated)");
             return Err(anyhow!("order rejected (simulated)"));
         }
 
@@ -208,24 +602,68 @@ impl ExecutionAdapter for PaperExecutionAdapter {
 
         // For FOK orders, reject if partial
         if req.tif == TimeInForce::Fok && fill_ratio < 1.0 {
+            self.orders.reject(&req, "FOK order could not be fully filled");
             return Err(anyhow!("FOK order could not be fully filled"));
         }
 
-        let filled_notional = req.notional_usdc * fill_ratio;
+        // Size in shares at the limit price; the unfilled part of a GTC order rests
+        let size = req.notional_usdc / req.price;
+        let filled_size = size * fill_ratio;
+        let filled_notional = filled_size * filled_price;
 
         // Calculate fees
         let fees_usdc = filled_notional * self.config.fee_rate;
 
+        let order_id = format!("paper:{}", req.client_order_id);
+        let resting_size = self.orders.record_submission(
+            &req,
+            &order_id,
+            size,
+            filled_size,
+            filled_price,
+            fees_usdc,
+            req.tif == TimeInForce::Gtc,
+        );
+
         Ok(OrderAck {
-            order_id: format!("paper:{}", req.client_order_id),
+            order_id,
             filled_notional_usdc: filled_notional,
             filled_price,
             filled_at: Utc::now().timestamp(),
             fees_usdc,
             slippage_bps: total_slippage_bps,
             latency_ms: total_latency_ms,
+            resting_size,
         })
     }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.orders.cancel(order_id).map(|_| ())
+    }
+
+    async fn cancel_all(&self, token_id: Option<&str>) -> Result<Vec<String>> {
+        Ok(self
+            .orders
+            .open_orders(token_id)
+            .into_iter()
+            .filter_map(|o| self.orders.cancel(&o.order_id).ok())
+            .map(|o| o.order_id)
+            .collect())
+    }
+
+    async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+        self.orders
+            .get(order_id)
+            .ok_or_else(|| anyhow!("unknown order {}", order_id))
+    }
+
+    async fn open_orders(&self, token_id: Option<&str>) -> Result<Vec<OrderState>> {
+        Ok(self.orders.open_orders(token_id))
+    }
+
+    fn subscribe_events(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.orders.subscribe()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -259,6 +697,24 @@ impl ExecutionAdapter for DomeExecutionAdapter {
             "Dome router execution not configured (endpoint + payload pending)"
         ))
     }
+
+    // Nothing can rest through the router yet, so there is never anything to
+    // cancel or query.
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        Err(anyhow!("Dome router: unknown order {}", order_id))
+    }
+
+    async fn cancel_all(&self, _token_id: Option<&str>) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+        Err(anyhow!("Dome router: unknown order {}", order_id))
+    }
+
+    async fn open_orders(&self, _token_id: Option<&str>) -> Result<Vec<OrderState>> {
+        Ok(Vec::new())
+    }
 }
 
 // ============================================================================
@@ -293,18 +749,26 @@ impl PolymarketClobCredentials {
     }
 }
 
+/// Venue name under which CLOB round trips are recorded in the venue tracker
+const CLOB_VENUE: &str = "polymarket";
+
 /// Live execution adapter for Polymarket CLOB
 #[derive(Clone)]
 pub struct PolymarketClobAdapter {
     client: Client,
     creds: PolymarketClobCredentials,
     host: String,
+    ws_url: String,
+    orders: Arc<OrderTracker>,
+    /// User channel WS task spawned (on first `subscribe_events`)
+    user_stream_started: Arc<AtomicBool>,
 }
 
 impl std::fmt::Debug for PolymarketClobAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PolymarketClobAdapter")
             .field("host", &self.host)
+            .field("ws_url", &self.ws_url)
             .field("api_key", &"[REDACTED]")
             .finish()
     }
@@ -342,6 +806,127 @@ struct ClobOrderResponse {
     avg_price: Option<String>,
 }
 
+/// Response from the CLOB cancel endpoints
+#[derive(Debug, Default, Deserialize)]
+struct ClobCancelResponse {
+    #[serde(default)]
+    canceled: Vec<String>,
+    #[serde(default)]
+    not_canceled: HashMap<String, serde_json::Value>,
+}
+
+/// Order as returned by `/data/order/{id}` and `/data/orders`
+#[derive(Debug, Deserialize)]
+struct ClobOpenOrder {
+    #[serde(alias = "orderID", alias = "order_id")]
+    id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(alias = "assetId", alias = "token_id", default)]
+    asset_id: String,
+    #[serde(default)]
+    side: String,
+    #[serde(default, deserialize_with = "de_f64_lenient")]
+    price: f64,
+    #[serde(default, deserialize_with = "de_f64_lenient")]
+    original_size: f64,
+    #[serde(default, deserialize_with = "de_f64_lenient")]
+    size_matched: f64,
+    #[serde(default, deserialize_with = "de_i64_lenient")]
+    created_at: i64,
+}
+
+/// `/data/orders` is paginated on newer deployments and a bare list on older ones
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ClobOrdersResponse {
+    Page {
+        data: Vec<ClobOpenOrder>,
+        #[serde(default)]
+        next_cursor: Option<String>,
+    },
+    List(Vec<ClobOpenOrder>),
+}
+
+/// Cursor the CLOB returns on the last page
+const CLOB_END_CURSOR: &str = "LTE=";
+
+/// Maker side of a user-channel trade message
+#[derive(Debug, Deserialize)]
+struct ClobMakerOrder {
+    order_id: String,
+    #[serde(default, deserialize_with = "de_f64_lenient")]
+    matched_amount: f64,
+    #[serde(default, deserialize_with = "de_f64_lenient")]
+    price: f64,
+}
+
+/// User-channel WS message (trade and order events)
+#[derive(Debug, Deserialize)]
+struct ClobUserMessage {
+    #[serde(default)]
+    event_type: String,
+    /// Trade status (MATCHED, MINED, CONFIRMED, ...) or order event type
+    /// (PLACEMENT, UPDATE, CANCELLATION)
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    maker_orders: Vec<ClobMakerOrder>,
+}
+
+fn de_f64_lenient<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => Ok(n.as_f64().unwrap_or(0.0)),
+        serde_json::Value::String(s) => Ok(s.trim().parse().unwrap_or(0.0)),
+        _ => Ok(0.0),
+    }
+}
+
+fn de_i64_lenient<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => Ok(n.as_i64().unwrap_or(0)),
+        serde_json::Value::String(s) => Ok(s.trim().parse().unwrap_or(0)),
+        _ => Ok(0),
+    }
+}
+
+impl ClobOpenOrder {
+    fn into_state(self, client_order_id: String) -> OrderState {
+        let status = match self.status.to_ascii_uppercase().as_str() {
+            "MATCHED" => OrderStatus::Filled,
+            s if s.starts_with("CANCELED") || s.starts_with("CANCELLED") => OrderStatus::Cancelled,
+            _ if self.size_matched > 0.0 => OrderStatus::PartiallyFilled,
+            _ => OrderStatus::Open,
+        };
+        OrderState {
+            order_id: self.id,
+            client_order_id,
+            token_id: self.asset_id,
+            side: if self.side.eq_ignore_ascii_case("SELL") {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            },
+            price: self.price,
+            size: self.original_size,
+            filled_size: self.size_matched,
+            avg_fill_price: if self.size_matched > 0.0 { self.price } else { 0.0 },
+            status,
+            updated_at: self.created_at,
+        }
+    }
+}
+
 /// Account balance response from Polymarket
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PolymarketBalance {
@@ -381,6 +966,7 @@ pub struct PolymarketAccountInfo {
 impl PolymarketClobAdapter {
     pub const CLOB_HOST: &'static str = "https://clob.polymarket.com";
     pub const DATA_API_HOST: &'static str = "https://data-api.polymarket.com";
+    pub const USER_WS_URL: &'static str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
 
     pub fn new(creds: PolymarketClobCredentials) -> Self {
         let client = Client::builder()
@@ -392,9 +978,19 @@ impl PolymarketClobAdapter {
             client,
             creds,
             host: Self::CLOB_HOST.to_string(),
+            ws_url: Self::USER_WS_URL.to_string(),
+            orders: Arc::new(OrderTracker::new()),
+            user_stream_started: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Use another CLOB REST host and user-channel WS URL (e.g. a local mock).
+    pub fn with_endpoints(mut self, host: impl Into<String>, ws_url: impl Into<String>) -> Self {
+        self.host = host.into();
+        self.ws_url = ws_url.into();
+        self
+    }
+
     pub fn from_env() -> Option<Self> {
         let creds = PolymarketClobCredentials::from_env();
         if creds.is_none() {
@@ -613,14 +1209,53 @@ impl PolymarketClobAdapter {
             ("POLY_PASSPHRASE".to_string(), self.creds.passphrase.clone()),
         ])
     }
-}
 
-#[async_trait::async_trait]
-impl ExecutionAdapter for PolymarketClobAdapter {
-    async fn place_order(&self, req: OrderRequest) -> Result<OrderAck> {
-        let start = std::time::Instant::now();
+    /// Send an authenticated request and return the response body.
+    async fn signed_request(&self, method: Method, path: &str, body: &str) -> Result<String> {
+        let headers = self.auth_headers(method.as_str(), path, body)?;
+        let url = format!("{}{}", self.host, path);
 
-        // Validate inputs
+        let mut request = self
+            .client
+            .request(method.clone(), &url)
+            .header("Content-Type", "application/json");
+        for (key, value) in headers {
+            request = request.header(&key, &value);
+        }
+        if !body.is_empty() {
+            request = request.body(body.to_string());
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("CLOB {} {} failed", method, path))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(anyhow!("CLOB {} {} failed ({}): {}", method, path, status, text));
+        }
+        Ok(text)
+    }
+
+    /// Mark the cancelled ids in the tracker and return them.
+    fn apply_cancel_response(&self, text: &str, latency: Duration) -> Result<Vec<String>> {
+        let resp: ClobCancelResponse =
+            serde_json::from_str(text).context("failed to parse CLOB cancel response")?;
+        let venues = global_venue_tracker();
+        for order_id in &resp.canceled {
+            venues.record_cancel_ack(CLOB_VENUE, latency.as_micros() as u64);
+            // Orders placed outside this process are not tracked
+            let _ = self.orders.cancel(order_id);
+        }
+        if !resp.not_canceled.is_empty() {
+            warn!(not_canceled = ?resp.not_canceled, "CLOB did not cancel some orders");
+        }
+        Ok(resp.canceled)
+    }
+
+    /// Validate a request and build its CLOB payload. Returns the payload and share size.
+    fn order_payload(req: &OrderRequest) -> Result<(ClobOrderPayload, f64)> {
         if !(req.price.is_finite() && req.price > 0.0 && req.price < 1.0) {
             return Err(anyhow!("invalid price: {}", req.price));
         }
@@ -631,7 +1266,6 @@ impl ExecutionAdapter for PolymarketClobAdapter {
         // Calculate size (shares) from notional
         let size = req.notional_usdc / req.price;
 
-        // Build order payload
         let side_str = match req.side {
             OrderSide::Buy => "BUY",
             OrderSide::Sell => "SELL",
@@ -651,6 +1285,238 @@ impl ExecutionAdapter for PolymarketClobAdapter {
             order_type: Some("LIMIT".to_string()),
             time_in_force: Some(tif_str.to_string()),
         };
+        Ok((payload, size))
+    }
+
+    /// Turn a CLOB order response into an ack and record it in the tracker.
+    fn accept_order(
+        &self,
+        req: &OrderRequest,
+        size: f64,
+        resp: ClobOrderResponse,
+        latency: Duration,
+    ) -> Result<OrderAck> {
+        let latency_ms = latency.as_millis() as u64;
+        let venues = global_venue_tracker();
+        if let Some(err) = resp.error_msg {
+            if !err.is_empty() {
+                venues.record_reject(CLOB_VENUE);
+                self.orders.reject(req, &err);
+                return Err(anyhow!("CLOB error: {}", err));
+            }
+        }
+
+        let order_id = resp
+            .order_id
+            .unwrap_or_else(|| format!("clob:{}", req.client_order_id));
+
+        // "live" = resting on the book; otherwise matched on arrival
+        let resting = resp
+            .status
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("live"));
+
+        // Parse fill info if available, otherwise assume full fill at limit price
+        // (or no fill for a resting order)
+        let filled_size: f64 = resp
+            .filled_size
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(if resting { 0.0 } else { size });
+        let filled_price: f64 = resp
+            .avg_price
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(req.price);
+        let filled_notional = filled_size * filled_price;
+
+        // Fills matched on arrival are reported in the same response as the ack
+        venues.record_send_ack(CLOB_VENUE, latency.as_micros() as u64);
+        if filled_size > 0.0 {
+            venues.record_send_fill(CLOB_VENUE, latency.as_micros() as u64);
+        }
+
+        // Polymarket taker fee is ~0.5%
+        let fees_usdc = filled_notional * 0.005;
+
+        let resting_size = self.orders.record_submission(
+            req,
+            &order_id,
+            size,
+            filled_size,
+            filled_price,
+            fees_usdc,
+            resting,
+        );
+        if resting {
+            self.ensure_user_stream();
+        }
+
+        info!(
+            order_id = %order_id,
+            filled_size = %filled_size,
+            filled_price = %filled_price,
+            filled_notional = %filled_notional,
+            resting_size = %resting_size,
+            latency_ms = %latency_ms,
+            "CLOB order accepted"
+        );
+
+        Ok(OrderAck {
+            order_id,
+            filled_notional_usdc: filled_notional,
+            filled_price,
+            filled_at: Utc::now().timestamp(),
+            fees_usdc,
+            slippage_bps: 0.0, // Would need pre-trade quote to calculate
+            latency_ms,
+            resting_size,
+        })
+    }
+
+    fn ensure_user_stream(&self) {
+        if self.user_stream_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let adapter = self.clone();
+                handle.spawn(async move { adapter.run_user_stream().await });
+            }
+            Err(_) => {
+                warn!("No tokio runtime; CLOB user stream not started");
+                self.user_stream_started.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Follow the authenticated user channel, reconnecting with backoff.
+    /// Resting-order fills arrive here; immediate (taker) fills are already
+    /// reported by `place_order`. Each (re)subscribe is followed by a REST
+    /// reconciliation of the orders open locally.
+    async fn run_user_stream(self) {
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            match self.user_stream_session().await {
+                Ok(()) => {
+                    backoff = Duration::from_secs(1);
+                    warn!("CLOB user stream closed; reconnecting");
+                }
+                Err(e) => warn!(error = %e, "CLOB user stream error; reconnecting"),
+            }
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_secs(30));
+        }
+    }
+
+    async fn user_stream_session(&self) -> Result<()> {
+        let (ws, _) = connect_async(&self.ws_url)
+            .await
+            .context("CLOB user WS connect failed")?;
+        let (mut write, mut read) = ws.split();
+
+        let subscribe = serde_json::json!({
+            "auth": {
+                "apiKey": self.creds.api_key,
+                "secret": self.creds.secret,
+                "passphrase": self.creds.passphrase,
+            },
+            "type": "user",
+            "markets": [],
+        });
+        write.send(Message::Text(subscribe.to_string())).await?;
+        info!(url = %self.ws_url, "CLOB user stream subscribed");
+
+        // Fills and cancels while disconnected were never pushed on this channel
+        if let Err(e) = self.reconcile_open_orders().await {
+            warn!(error = %e, "CLOB open order reconciliation failed");
+        }
+
+        let mut ping = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+                msg = read.next() => match msg {
+                    Some(Ok(Message::Text(text))) => self.handle_user_message(&text),
+                    Some(Ok(Message::Ping(payload))) => write.send(Message::Pong(payload)).await?,
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                },
+            }
+        }
+    }
+
+    /// Bring every order open locally up to date with the venue: the venue's
+    /// open orders first, then the status of each one it no longer lists
+    /// (filled or cancelled in the meantime).
+    async fn reconcile_open_orders(&self) -> Result<()> {
+        let tracked = self.orders.open_orders(None);
+        if tracked.is_empty() {
+            return Ok(());
+        }
+        let venue_open: HashSet<String> = self
+            .open_orders(None)
+            .await?
+            .into_iter()
+            .map(|o| o.order_id)
+            .collect();
+        for order in tracked {
+            if venue_open.contains(&order.order_id) {
+                continue;
+            }
+            if let Err(e) = self.order_status(&order.order_id).await {
+                warn!(order_id = %order.order_id, error = %e, "CLOB order status failed");
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_user_message(&self, text: &str) {
+        if text == "PONG" {
+            return;
+        }
+        let messages: Vec<ClobUserMessage> = match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(serde_json::Value::Array(items)) => items
+                .into_iter()
+                .filter_map(|v| serde_json::from_value(v).ok())
+                .collect(),
+            Ok(value) => serde_json::from_value(value).into_iter().collect(),
+            Err(e) => {
+                debug!(error = %e, "unparseable CLOB user message");
+                return;
+            }
+        };
+
+        for msg in messages {
+            match msg.event_type.as_str() {
+                // A trade is reported again as it is mined and confirmed
+                "trade" if msg.status.eq_ignore_ascii_case("MATCHED") => {
+                    for maker in &msg.maker_orders {
+                        self.orders.fill(
+                            &maker.order_id,
+                            maker.price,
+                            maker.matched_amount,
+                            0.0,
+                            true,
+                        );
+                    }
+                }
+                "order" if msg.kind.eq_ignore_ascii_case("CANCELLATION") => {
+                    let _ = self.orders.cancel(&msg.id);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionAdapter for PolymarketClobAdapter {
+    async fn place_order(&self, req: OrderRequest) -> Result<OrderAck> {
+        let start = std::time::Instant::now();
+
+        let (payload, size) = Self::order_payload(&req)?;
 
         let body = serde_json::to_string(&payload).context("failed to serialize order")?;
         let path = "/order";
@@ -661,7 +1527,7 @@ impl ExecutionAdapter for PolymarketClobAdapter {
 
         debug!(
             token_id = %req.token_id,
-            side = %side_str,
+            side = %payload.side,
             price = %req.price,
             size = %size,
             notional = %req.notional_usdc,
@@ -686,7 +1552,8 @@ impl ExecutionAdapter for PolymarketClobAdapter {
             .context("CLOB request failed")?;
 
         let status = response.status();
-        let latency_ms = start.elapsed().as_millis() as u64;
+        let latency = start.elapsed();
+        let latency_ms = latency.as_millis() as u64;
 
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
@@ -696,6 +1563,8 @@ impl ExecutionAdapter for PolymarketClobAdapter {
                 latency_ms = %latency_ms,
                 "CLOB order rejected"
             );
+            global_venue_tracker().record_reject(CLOB_VENUE);
+            self.orders.reject(&req, &error_text);
             return Err(anyhow!("CLOB order rejected ({}): {}", status, error_text));
         }
 
@@ -703,47 +1572,303 @@ impl ExecutionAdapter for PolymarketClobAdapter {
         let resp: ClobOrderResponse =
             serde_json::from_str(&resp_text).context("failed to parse CLOB response")?;
 
-        if let Some(err) = resp.error_msg {
-            if !err.is_empty() {
-                return Err(anyhow!("CLOB error: {}", err));
+        self.accept_order(&req, size, resp, latency)
+    }
+
+    async fn place_orders(&self, reqs: Vec<OrderRequest>) -> Vec<Result<OrderAck>> {
+        if reqs.len() > POLYMARKET_MAX_BATCH_SIZE {
+            return reqs
+                .iter()
+                .map(|_| {
+                    Err(anyhow!(
+                        "batch of {} orders exceeds CLOB limit {}",
+                        reqs.len(),
+                        POLYMARKET_MAX_BATCH_SIZE
+                    ))
+                })
+                .collect();
+        }
+        let start = std::time::Instant::now();
+
+        // Invalid orders fail locally; the rest go out in one request
+        let mut results: Vec<Option<Result<OrderAck>>> = reqs.iter().map(|_| None).collect();
+        let mut payloads = Vec::new();
+        let mut sent = Vec::new();
+        for (index, req) in reqs.iter().enumerate() {
+            match Self::order_payload(req) {
+                Ok((payload, size)) => {
+                    payloads.push(payload);
+                    sent.push((index, size));
+                }
+                Err(e) => results[index] = Some(Err(e)),
             }
         }
 
-        let order_id = resp
-            .order_id
-            .unwrap_or_else(|| format!("clob:{}", req.client_order_id));
+        if !payloads.is_empty() {
+            debug!(orders = payloads.len(), "CLOB batch order submission");
+            let responses = match serde_json::to_string(&payloads) {
+                Ok(body) => self
+                    .signed_request(Method::POST, "/orders", &body)
+                    .await
+                    .and_then(|text| {
+                        serde_json::from_str::<Vec<ClobOrderResponse>>(&text)
+                            .context("failed to parse CLOB batch response")
+                    }),
+                Err(e) => Err(anyhow!("failed to serialize batch: {}", e)),
+            };
+            let latency = start.elapsed();
+
+            match responses {
+                Ok(responses) => {
+                    let mut responses = responses.into_iter();
+                    for (index, size) in sent {
+                        let req = &reqs[index];
+                        results[index] = Some(match responses.next() {
+                            Some(resp) => self.accept_order(req, size, resp, latency),
+                            None => {
+                                self.orders.reject(req, "missing from batch response");
+                                Err(anyhow!("CLOB batch response missing order {}", index))
+                            }
+                        });
+                    }
+                }
+                Err(e) => {
+                    let latency_ms = latency.as_millis() as u64;
+                    warn!(error = %e, latency_ms = %latency_ms, "CLOB batch rejected");
+                    for (index, _) in sent {
+                        global_venue_tracker().record_reject(CLOB_VENUE);
+                        self.orders.reject(&reqs[index], &e.to_string());
+                        results[index] = Some(Err(anyhow!("CLOB batch rejected: {}", e)));
+                    }
+                }
+            }
+        }
 
-        // Parse fill info if available, otherwise assume full fill at limit price
-        let filled_size: f64 = resp
-            .filled_size
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(size);
-        let filled_price: f64 = resp
-            .avg_price
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(req.price);
-        let filled_notional = filled_size * filled_price;
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(anyhow!("order not submitted"))))
+            .collect()
+    }
 
-        // Polymarket taker fee is ~0.5%
-        let fees_usdc = filled_notional * 0.005;
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let body = serde_json::json!({ "orderID": order_id }).to_string();
+        let start = std::time::Instant::now();
+        let text = self.signed_request(Method::DELETE, "/order", &body).await?;
+        let cancelled = self.apply_cancel_response(&text, start.elapsed())?;
+        if cancelled.iter().any(|id| id == order_id) {
+            Ok(())
+        } else {
+            Err(anyhow!("CLOB did not cancel order {}: {}", order_id, text))
+        }
+    }
 
-        info!(
-            order_id = %order_id,
-            filled_size = %filled_size,
-            filled_price = %filled_price,
-            filled_notional = %filled_notional,
-            latency_ms = %latency_ms,
-            "CLOB order filled"
-        );
+    async fn cancel_all(&self, token_id: Option<&str>) -> Result<Vec<String>> {
+        let start = std::time::Instant::now();
+        let text = match token_id {
+            Some(token_id) => {
+                let body = serde_json::json!({ "asset_id": token_id }).to_string();
+                self.signed_request(Method::DELETE, "/cancel-market-orders", &body)
+                    .await?
+            }
+            None => self.signed_request(Method::DELETE, "/cancel-all", "").await?,
+        };
+        self.apply_cancel_response(&text, start.elapsed())
+    }
 
-        Ok(OrderAck {
-            order_id,
-            filled_notional_usdc: filled_notional,
-            filled_price,
-            filled_at: Utc::now().timestamp(),
-            fees_usdc,
-            slippage_bps: 0.0, // Would need pre-trade quote to calculate
-            latency_ms,
+    async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+        let path = format!("/data/order/{}", order_id);
+        let text = self.signed_request(Method::GET, &path, "").await?;
+        let order: ClobOpenOrder =
+            serde_json::from_str(&text).context("failed to parse CLOB order")?;
+
+        let client_order_id = self
+            .orders
+            .get(order_id)
+            .map(|o| o.client_order_id)
+            .unwrap_or_default();
+        let state = order.into_state(client_order_id);
+        self.orders.reconcile(&state);
+        Ok(state)
+    }
+
+    async fn open_orders(&self, token_id: Option<&str>) -> Result<Vec<OrderState>> {
+        let mut states = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut params = Vec::new();
+            if let Some(token_id) = token_id {
+                params.push(format!("asset_id={}", token_id));
+            }
+            if let Some(cursor) = &cursor {
+                params.push(format!("next_cursor={}", cursor));
+            }
+            let path = if params.is_empty() {
+                "/data/orders".to_string()
+            } else {
+                format!("/data/orders?{}", params.join("&"))
+            };
+
+            let text = self.signed_request(Method::GET, &path, "").await?;
+            let (orders, next) = match serde_json::from_str::<ClobOrdersResponse>(&text)
+                .context("failed to parse CLOB orders")?
+            {
+                ClobOrdersResponse::Page { data, next_cursor } => (data, next_cursor),
+                ClobOrdersResponse::List(orders) => (orders, None),
+            };
+            for order in orders {
+                let client_order_id = self
+                    .orders
+                    .get(&order.id)
+                    .map(|o| o.client_order_id)
+                    .unwrap_or_default();
+                let state = order.into_state(client_order_id);
+                self.orders.reconcile(&state);
+                if state.status.is_open() {
+                    states.push(state);
+                }
+            }
+
+            match next {
+                Some(next) if !next.is_empty() && next != CLOB_END_CURSOR && cursor.as_ref() != Some(&next) => {
+                    cursor = Some(next);
+                }
+                _ => break,
+            }
+        }
+        Ok(states)
+    }
+
+    fn subscribe_events(&self) -> broadcast::Receiver<ExecutionEvent> {
+        let rx = self.orders.subscribe();
+        self.ensure_user_stream();
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paper(partial_fill_prob: f64) -> PaperExecutionAdapter {
+        PaperExecutionAdapter::new(PaperExecutionConfig {
+            base_latency_ms: 0,
+            latency_jitter_ms: 0,
+            base_slippage_bps: 0.0,
+            slippage_bps_per_1k: 0.0,
+            partial_fill_prob,
+            min_fill_ratio: 0.5,
+            reject_prob: 0.0,
+            ..Default::default()
         })
     }
+
+    fn request(id: &str, side: OrderSide, price: f64, tif: TimeInForce) -> OrderRequest {
+        OrderRequest {
+            client_order_id: id.to_string(),
+            token_id: "TOKEN".to_string(),
+            side,
+            price,
+            notional_usdc: 5.0,
+            tif,
+            market_slug: None,
+            outcome: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paper_gtc_remainder_rests_until_market_crosses() {
+        let exec = paper(1.0);
+        let mut events = exec.subscribe_events();
+
+        let ack = exec
+            .place_order(request("bid", OrderSide::Buy, 0.50, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        let filled = ack.filled_notional_usdc / ack.filled_price;
+        assert!(ack.resting_size > 0.0);
+        assert!((filled + ack.resting_size - 10.0).abs() < 1e-9);
+        assert!(matches!(events.try_recv().unwrap(), ExecutionEvent::Ack { .. }));
+        assert!(matches!(events.try_recv().unwrap(), ExecutionEvent::Fill { is_maker: false, .. }));
+
+        // Ask above the bid: nothing happens
+        assert!(exec.match_resting_orders("TOKEN", Some(0.48), Some(0.52)).is_empty());
+        assert_eq!(exec.open_orders(Some("TOKEN")).await.unwrap().len(), 1);
+
+        let fills = exec.match_resting_orders("TOKEN", Some(0.45), Some(0.49));
+        assert_eq!(fills.len(), 1);
+        match &fills[0] {
+            ExecutionEvent::Fill {
+                price,
+                size,
+                remaining_size,
+                is_maker,
+                ..
+            } => {
+                assert_eq!(*price, 0.50);
+                assert!((size - ack.resting_size).abs() < 1e-9);
+                assert_eq!(*remaining_size, 0.0);
+                assert!(is_maker);
+            }
+            other => panic!("expected fill, got {:?}", other),
+        }
+        let state = exec.order_status(&ack.order_id).await.unwrap();
+        assert_eq!(state.status, OrderStatus::Filled);
+        assert!((state.filled_size - 10.0).abs() < 1e-9);
+        assert!(exec.open_orders(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_paper_ioc_remainder_cancelled_and_gtc_cancel() {
+        let exec = paper(1.0);
+        let mut events = exec.subscribe_events();
+
+        let ioc = exec
+            .place_order(request("ioc", OrderSide::Buy, 0.50, TimeInForce::Ioc))
+            .await
+            .unwrap();
+        assert_eq!(ioc.resting_size, 0.0);
+        let events_seen: Vec<ExecutionEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert!(matches!(events_seen.last(), Some(ExecutionEvent::Cancelled { .. })));
+        assert_eq!(
+            exec.order_status(&ioc.order_id).await.unwrap().status,
+            OrderStatus::Cancelled
+        );
+
+        let gtc = exec
+            .place_order(request("ask", OrderSide::Sell, 0.60, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        assert!(gtc.resting_size > 0.0);
+        assert_eq!(exec.cancel_all(Some("OTHER")).await.unwrap().len(), 0);
+        assert_eq!(exec.cancel_all(Some("TOKEN")).await.unwrap(), vec![gtc.order_id.clone()]);
+        assert!(exec.cancel_order(&gtc.order_id).await.is_err());
+        assert!(exec.match_resting_orders("TOKEN", Some(0.70), Some(0.71)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_paper_full_fill_and_reject_events() {
+        let exec = paper(0.0);
+        let mut events = exec.subscribe_events();
+
+        let ack = exec
+            .place_order(request("full", OrderSide::Buy, 0.25, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        assert_eq!(ack.resting_size, 0.0);
+        assert!((ack.filled_notional_usdc - 5.0).abs() < 1e-9);
+        assert_eq!(
+            exec.order_status(&ack.order_id).await.unwrap().status,
+            OrderStatus::Filled
+        );
+
+        assert!(exec
+            .place_order(request("bad", OrderSide::Buy, 1.5, TimeInForce::Gtc))
+            .await
+            .is_err());
+        let last = std::iter::from_fn(|| events.try_recv().ok()).last();
+        assert!(matches!(
+            last,
+            Some(ExecutionEvent::Rejected { client_order_id, .. }) if client_order_id == "bad"
+        ));
+    }
 }
@@ -331,6 +331,15 @@ impl Fast15mLatencyRegistry {
         self.recent_spans.push_back(span);
     }
 
+    /// Price received -> order submitted (ns) for recent spans that submitted
+    pub fn decision_samples_ns(&self) -> Vec<i64> {
+        self.recent_spans
+            .iter()
+            .filter(|s| s.order_submitted_ns > s.price_received_ns)
+            .map(|s| (s.order_submitted_ns - s.price_received_ns) as i64)
+            .collect()
+    }
+
     pub fn summary(&self) -> LatencySummary {
         LatencySummary {
             evaluations: self.evaluations,
@@ -741,13 +750,28 @@ impl<E: ExecutionAdapter + Send + Sync + 'static> ReactiveFast15mEngine<E> {
             return Ok(Some(span));
         }
 
+        // Shares the UPDOWN15M budget with the polling FAST15M engine
+        self.state
+            .capital_allocator
+            .record_expiry(&side_token, end_ts);
+        let Some(allocation) = self
+            .state
+            .capital_allocator
+            .reserve("FAST15M_REACTIVE", kelly.position_size_usd)
+        else {
+            span.skip_reason = Some("budget".to_string());
+            span.finalize();
+            self.latency_registry.write().record_span(span.clone());
+            return Ok(Some(span));
+        };
+
         // Submit order
         let req = OrderRequest {
             client_order_id: Uuid::new_v4().to_string(),
             token_id: side_token.clone(),
             side: OrderSide::Buy,
             price: side_price,
-            notional_usdc: kelly.position_size_usd,
+            notional_usdc: allocation.granted_usdc(),
             tif: TimeInForce::Ioc,
             market_slug: Some(slug.clone()),
             outcome: Some(side_outcome.clone()),
@@ -756,6 +780,14 @@ impl<E: ExecutionAdapter + Send + Sync + 'static> ReactiveFast15mEngine<E> {
         span.order_submitted_ns = Self::now_ns();
         let ack = self.exec.place_order(req.clone()).await?;
         span.order_acked_ns = Self::now_ns();
+        self.state.capital_allocator.record_fill(
+            "FAST15M_REACTIVE",
+            &req.token_id,
+            req.side,
+            ack.filled_price,
+            ack.filled_notional_usdc,
+        );
+        drop(allocation);
 
         // Update ledger
         if req.side == OrderSide::Buy {
@@ -810,6 +842,7 @@ impl<E: ExecutionAdapter + Send + Sync + 'static> ReactiveFast15mEngine<E> {
                     notional_usdc: Some(ack.filled_notional_usdc),
                     strategy: Some("FAST15M_REACTIVE".to_string()),
                     decision_id: None,
+                    config_version: None,
                 })
                 .await;
 
@@ -31,8 +31,8 @@ use crate::{
     performance::latency::{HistogramSummary, LatencyHistogram},
     scrapers::polymarket::{Order, OrderBook},
     vault::{
-        calculate_kelly_position, ExecutionAdapter, KellyParams, OrderAck, OrderRequest, OrderSide,
-        TimeInForce,
+        calculate_kelly_position, CapitalAllocator, ExecutionAdapter, KellyParams, OrderAck,
+        OrderRequest, OrderSide, TimeInForce,
     },
     AppState,
 };
@@ -909,73 +909,218 @@ pub enum TradeDecision {
     },
 }
 
-/// Execute a trade decision (non-recursive helper)
-async fn execute_single_leg(
-    decision: &TradeDecision,
+/// Place one leg; buys are held to the LATENCY_ARB capital budget and skipped
+/// (`Ok(None)`) when it is exhausted
+async fn place_budgeted(
+    mut req: OrderRequest,
+    timeout_ms: u64,
     executor: &Arc<dyn ExecutionAdapter>,
+    allocator: &Arc<CapitalAllocator>,
 ) -> Result<Option<OrderAck>> {
-    match decision {
-        TradeDecision::NoAction { .. } => Ok(None),
+    let allocation = match req.side {
+        OrderSide::Buy => match allocator.reserve("LATENCY_ARB", req.notional_usdc) {
+            Some(a) => {
+                req.notional_usdc = a.granted_usdc();
+                Some(a)
+            }
+            None => return Ok(None),
+        },
+        OrderSide::Sell => None,
+    };
+    let token_id = req.token_id.clone();
+    let side = req.side;
+
+    let ack = tokio::time::timeout(Duration::from_millis(timeout_ms), executor.place_order(req))
+        .await
+        .map_err(|_| anyhow!("Order timed out"))??;
+
+    allocator.record_fill(
+        "LATENCY_ARB",
+        &token_id,
+        side,
+        ack.filled_price,
+        ack.filled_notional_usdc,
+    );
+    drop(allocation);
+    Ok(Some(ack))
+}
+
+/// Place both legs of a true arbitrage in one batch. Buys are held to the
+/// LATENCY_ARB capital budget; if either leg cannot be funded neither is sent,
+/// and a partial grant scales both legs by the same factor so they stay
+/// matched. A rejected leg fails the whole pair: whatever of the other leg
+/// rests on the book is cancelled, and any part of it that already filled is
+/// reported in the error as a one-sided position to unwind.
+async fn place_pair(
+    legs: [(OrderRequest, u64); 2],
+    executor: &Arc<dyn ExecutionAdapter>,
+    allocator: &Arc<CapitalAllocator>,
+) -> Result<Option<OrderAck>> {
+    let timeout_ms = legs[0].1.max(legs[1].1);
+    let mut reqs: Vec<OrderRequest> = legs.into_iter().map(|(req, _)| req).collect();
+    let mut allocations = Vec::new();
+    let mut scale: f64 = 1.0;
+    for req in &reqs {
+        if req.side == OrderSide::Buy {
+            match allocator.reserve("LATENCY_ARB", req.notional_usdc) {
+                Some(a) => {
+                    scale = scale.min(a.granted_usdc() / req.notional_usdc);
+                    allocations.push(a);
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+    if scale < 1.0 {
+        for req in &mut reqs {
+            req.notional_usdc *= scale;
+        }
+    }
+    let sides: Vec<(String, OrderSide)> = reqs
+        .iter()
+        .map(|req| (req.token_id.clone(), req.side))
+        .collect();
+
+    let results = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        executor.place_orders(reqs),
+    )
+    .await
+    .map_err(|_| anyhow!("Order timed out"))?;
+
+    for ((token_id, side), result) in sides.iter().zip(&results) {
+        if let Ok(ack) = result {
+            allocator.record_fill(
+                "LATENCY_ARB",
+                token_id,
+                *side,
+                ack.filled_price,
+                ack.filled_notional_usdc,
+            );
+        }
+    }
+    drop(allocations);
+
+    let mut results = results.into_iter();
+    let ack1 = results
+        .next()
+        .unwrap_or_else(|| Err(anyhow!("No result for first leg")));
+    let ack2 = results
+        .next()
+        .unwrap_or_else(|| Err(anyhow!("No result for second leg")));
+    match (ack1, ack2) {
+        (Ok(ack1), Ok(_)) => Ok(Some(ack1)),
+        (Ok(placed), Err(e)) => Err(abandon_leg(executor, &sides[0].0, placed, e, "Second").await),
+        (Err(e), Ok(placed)) => Err(abandon_leg(executor, &sides[1].0, placed, e, "First").await),
+        (Err(e), Err(_)) => Err(e),
+    }
+}
+
+/// Pull the surviving leg of a pair whose other leg (`which`) was rejected
+async fn abandon_leg(
+    executor: &Arc<dyn ExecutionAdapter>,
+    token_id: &str,
+    placed: OrderAck,
+    rejected: anyhow::Error,
+    which: &str,
+) -> anyhow::Error {
+    let mut unwound = Vec::new();
+    if placed.resting_size > 0.0 {
+        match executor.cancel_order(&placed.order_id).await {
+            Ok(()) => unwound.push(format!("cancelled {:.2} resting", placed.resting_size)),
+            Err(e) => {
+                warn!(
+                    order_id = %placed.order_id,
+                    token_id = %token_id,
+                    error = %e,
+                    "Failed to cancel surviving arbitrage leg"
+                );
+                unwound.push(format!(
+                    "{:.2} still resting, cancel failed: {}",
+                    placed.resting_size, e
+                ));
+            }
+        }
+    }
+    if placed.filled_notional_usdc > 0.0 {
+        warn!(
+            order_id = %placed.order_id,
+            token_id = %token_id,
+            filled_notional = %placed.filled_notional_usdc,
+            "Arbitrage pair left one-sided; unwind required"
+        );
+        unwound.push(format!(
+            "{:.2} USDC filled one-sided on {}",
+            placed.filled_notional_usdc, token_id
+        ));
+    }
+    if unwound.is_empty() {
+        unwound.push("nothing filled".to_string());
+    }
+    rejected.context(format!(
+        "{} arbitrage leg rejected; other leg {}: {}",
+        which,
+        placed.order_id,
+        unwound.join(", ")
+    ))
+}
+
+/// Build the order for a single-leg decision, with its timeout
+fn leg_request(decision: &TradeDecision) -> Option<(OrderRequest, u64)> {
+    let (token_id, side, price, size_usd, timeout_ms, tif) = match decision {
         TradeDecision::AggressiveClip {
             token_id,
             side,
             price,
             size_usd,
             timeout_ms,
-        } => {
-            let client_order_id = Uuid::new_v4().to_string();
-            let req = OrderRequest {
-                client_order_id,
-                token_id: token_id.clone(),
-                side: *side,
-                price: *price,
-                notional_usdc: *size_usd,
-                tif: TimeInForce::Ioc,
-                market_slug: None,
-                outcome: None,
-            };
-
-            let ack = tokio::time::timeout(
-                Duration::from_millis(*timeout_ms),
-                executor.place_order(req),
-            )
-            .await
-            .map_err(|_| anyhow!("Order timed out"))??;
-
-            Ok(Some(ack))
-        }
+        } => (
+            token_id,
+            side,
+            price,
+            size_usd,
+            timeout_ms,
+            TimeInForce::Ioc,
+        ),
         TradeDecision::PassiveOrder {
             token_id,
             side,
             price,
             size_usd,
             timeout_ms,
-        } => {
-            let client_order_id = Uuid::new_v4().to_string();
-            let req = OrderRequest {
-                client_order_id,
-                token_id: token_id.clone(),
-                side: *side,
-                price: *price,
-                notional_usdc: *size_usd,
-                tif: TimeInForce::Gtc,
-                market_slug: None,
-                outcome: None,
-            };
-
-            let ack = tokio::time::timeout(
-                Duration::from_millis(*timeout_ms),
-                executor.place_order(req),
-            )
-            .await
-            .map_err(|_| anyhow!("Order timed out"))??;
+        } => (
+            token_id,
+            side,
+            price,
+            size_usd,
+            timeout_ms,
+            TimeInForce::Gtc,
+        ),
+        TradeDecision::NoAction { .. } | TradeDecision::TwoLegArbitrage { .. } => return None,
+    };
+    let req = OrderRequest {
+        client_order_id: Uuid::new_v4().to_string(),
+        token_id: token_id.clone(),
+        side: *side,
+        price: *price,
+        notional_usdc: *size_usd,
+        tif,
+        market_slug: None,
+        outcome: None,
+    };
+    Some((req, *timeout_ms))
+}
 
-            Ok(Some(ack))
-        }
-        TradeDecision::TwoLegArbitrage { .. } => {
-            // This case is handled by execute_decision
-            Ok(None)
-        }
+/// Execute a trade decision (non-recursive helper)
+async fn execute_single_leg(
+    decision: &TradeDecision,
+    executor: &Arc<dyn ExecutionAdapter>,
+    allocator: &Arc<CapitalAllocator>,
+) -> Result<Option<OrderAck>> {
+    match leg_request(decision) {
+        Some((req, timeout_ms)) => place_budgeted(req, timeout_ms, executor, allocator).await,
+        // TwoLegArbitrage is handled by execute_decision
+        None => Ok(None),
     }
 }
 
@@ -983,25 +1128,32 @@ async fn execute_single_leg(
 pub async fn execute_decision(
     decision: &TradeDecision,
     executor: &Arc<dyn ExecutionAdapter>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<Option<OrderAck>> {
+    let allocator = &state.capital_allocator;
     match decision {
         TradeDecision::TwoLegArbitrage {
             leg1,
             leg2,
             is_true_arb,
         } => {
-            // For true arb, execute both legs
+            // For true arb, submit both legs together
+            if *is_true_arb {
+                if let (Some(first), Some(second)) = (leg_request(leg1), leg_request(leg2)) {
+                    return place_pair([first, second], executor, allocator).await;
+                }
+            }
+
             // For directional, only execute if first leg fills
-            let ack1 = execute_single_leg(leg1, executor).await?;
+            let ack1 = execute_single_leg(leg1, executor, allocator).await?;
 
-            if *is_true_arb || ack1.is_some() {
-                let _ack2 = execute_single_leg(leg2, executor).await?;
+            if ack1.is_some() {
+                let _ack2 = execute_single_leg(leg2, executor, allocator).await?;
             }
 
             Ok(ack1)
         }
-        _ => execute_single_leg(decision, executor).await,
+        _ => execute_single_leg(decision, executor, allocator).await,
     }
 }
 
@@ -1652,4 +1804,97 @@ mod tests {
         // Should be limited but still allow some
         assert!((limit_check - 10.0).abs() < 1e-9);
     }
+
+    /// Rejects token "REJECT"; IOC orders fill in full, GTC orders rest
+    struct PairExecution {
+        cancels: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionAdapter for PairExecution {
+        async fn place_order(&self, req: OrderRequest) -> Result<OrderAck> {
+            if req.token_id == "REJECT" {
+                return Err(anyhow!("mock reject"));
+            }
+            let filled = req.tif == TimeInForce::Ioc;
+            Ok(OrderAck {
+                order_id: req.client_order_id,
+                filled_notional_usdc: if filled { req.notional_usdc } else { 0.0 },
+                filled_price: req.price,
+                filled_at: 0,
+                fees_usdc: 0.0,
+                slippage_bps: 0.0,
+                latency_ms: 0,
+                resting_size: if filled {
+                    0.0
+                } else {
+                    req.notional_usdc / req.price
+                },
+            })
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> Result<()> {
+            self.cancels.lock().push(order_id.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_one_leg_rejected_pulls_the_other() {
+        let allocator = Arc::new(CapitalAllocator::new(Default::default(), None));
+        allocator.rebalance(1_000.0, 0).await;
+        let exec = Arc::new(PairExecution {
+            cancels: Mutex::new(Vec::new()),
+        });
+        let executor: Arc<dyn ExecutionAdapter> = exec.clone();
+        let leg = |token_id: &str, passive: bool| {
+            let decision = if passive {
+                TradeDecision::PassiveOrder {
+                    token_id: token_id.to_string(),
+                    side: OrderSide::Buy,
+                    price: 0.40,
+                    size_usd: 4.0,
+                    timeout_ms: 1_000,
+                }
+            } else {
+                TradeDecision::AggressiveClip {
+                    token_id: token_id.to_string(),
+                    side: OrderSide::Buy,
+                    price: 0.50,
+                    size_usd: 5.0,
+                    timeout_ms: 1_000,
+                }
+            };
+            leg_request(&decision).unwrap()
+        };
+
+        // A resting survivor is cancelled
+        let resting = leg("YES", true);
+        let resting_id = resting.0.client_order_id.clone();
+        let err = place_pair([resting, leg("REJECT", false)], &executor, &allocator)
+            .await
+            .unwrap_err();
+        assert_eq!(*exec.cancels.lock(), vec![resting_id]);
+        assert!(
+            err.to_string().contains("cancelled 10.00 resting"),
+            "{}",
+            err
+        );
+
+        // A filled survivor cannot be pulled and is reported for unwind
+        let err = place_pair(
+            [leg("REJECT", true), leg("NO", false)],
+            &executor,
+            &allocator,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(exec.cancels.lock().len(), 1);
+        assert!(err.to_string().starts_with("First arbitrage leg rejected"));
+        assert!(
+            err.to_string().contains("5.00 USDC filled one-sided on NO"),
+            "{}",
+            err
+        );
+    }
 }
@@ -201,15 +201,43 @@ pub struct LlmCallOutput {
     pub latency_ms: u64,
 }
 
+/// Chat-completion backend the LONG decision prompts can be sent to.
+#[async_trait::async_trait]
+pub trait LlmClient: Send + Sync {
+    async fn chat_completion(
+        &self,
+        model: &str,
+        system: &str,
+        user: &str,
+        max_tokens: u32,
+        temperature: f64,
+        timeout: Duration,
+    ) -> Result<LlmCallOutput>;
+}
+
+pub const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
 #[derive(Clone)]
 pub struct OpenRouterClient {
     http: reqwest::Client,
     api_key: String,
+    base_url: String,
     referer: Option<String>,
     title: Option<String>,
 }
 
 impl OpenRouterClient {
+    /// Client for any OpenAI-compatible endpoint (e.g. a local stub server).
+    pub fn new(http: reqwest::Client, api_key: &str, base_url: &str) -> Self {
+        Self {
+            http,
+            api_key: api_key.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            referer: None,
+            title: None,
+        }
+    }
+
     pub fn from_env(http: reqwest::Client) -> Result<Self> {
         let api_key = std::env::var("OPENROUTER_API_KEY")
             .context("OPENROUTER_API_KEY missing (set env var)")?;
@@ -223,10 +251,15 @@ impl OpenRouterClient {
         let title = std::env::var("OPENROUTER_APP_TITLE")
             .ok()
             .filter(|s| !s.trim().is_empty());
+        let base_url = std::env::var("OPENROUTER_BASE_URL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| OPENROUTER_BASE_URL.to_string());
 
         Ok(Self {
             http,
             api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
             referer,
             title,
         })
@@ -261,7 +294,7 @@ impl OpenRouterClient {
 
         let mut http_req = self
             .http
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .timeout(timeout)
             .header(
                 reqwest::header::AUTHORIZATION,
@@ -311,6 +344,30 @@ impl OpenRouterClient {
     }
 }
 
+#[async_trait::async_trait]
+impl LlmClient for OpenRouterClient {
+    async fn chat_completion(
+        &self,
+        model: &str,
+        system: &str,
+        user: &str,
+        max_tokens: u32,
+        temperature: f64,
+        timeout: Duration,
+    ) -> Result<LlmCallOutput> {
+        OpenRouterClient::chat_completion(
+            self,
+            model,
+            system,
+            user,
+            max_tokens,
+            temperature,
+            timeout,
+        )
+        .await
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct ChatCompletionRequest {
     pub model: String,
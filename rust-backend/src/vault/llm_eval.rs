@@ -0,0 +1,551 @@
+//! Offline replay and scoring of the LONG LLM models
+//!
+//! Every LONG evaluation stores its prompt pair (`vault_llm_prompts`). This
+//! module replays those prompts and scores each model in `long_models`
+//! against the market's eventual outcome:
+//! 1. Replay: re-send the stored prompts through any `LlmClient` (OpenRouter
+//!    or the local `LlmStubServer`); Recorded: re-score the responses the
+//!    engine already paid for (`vault_llm_model_records`)
+//! 2. Resolve each market's winning outcome (Gamma, cached once settled)
+//! 3. Score per model: Brier score and calibration buckets of P_TRUE for the
+//!    picked outcome, and flat-stake PnL of its BUYs at the ask seen at
+//!    prompt time (+ fee buffer)
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::scrapers::polymarket_gamma;
+use crate::signals::db_storage::{DbSignalStorage, VaultLlmPromptRow};
+use crate::vault::{parse_decision_dsl, LlmClient, ParsedDecisionDsl, VaultEngineConfig};
+
+const CALIBRATION_BUCKETS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmEvalSource {
+    /// Re-send stored prompts to the models
+    Replay,
+    /// Re-score the model responses recorded at decision time
+    Recorded,
+}
+
+#[derive(Debug, Clone)]
+pub struct LlmEvalConfig {
+    pub models: Vec<String>,
+    pub since_ts: i64,
+    pub until_ts: i64,
+    pub max_prompts: usize,
+    pub max_tokens: u32,
+    pub temperature: f64,
+    pub timeout: Duration,
+    /// Notional per BUY at SIZE_MULT=1
+    pub stake_usdc: f64,
+    pub fee_buffer: f64,
+}
+
+impl LlmEvalConfig {
+    /// Same models and call parameters the LONG engine uses.
+    pub fn from_engine(cfg: &VaultEngineConfig) -> Self {
+        Self {
+            models: cfg.long_models.clone(),
+            since_ts: 0,
+            until_ts: i64::MAX,
+            max_prompts: 500,
+            max_tokens: cfg.long_llm_max_tokens,
+            temperature: cfg.long_llm_temperature,
+            timeout: Duration::from_secs(cfg.long_llm_timeout_sec),
+            stake_usdc: 100.0,
+            fee_buffer: cfg.long_fee_buffer,
+        }
+    }
+}
+
+/// Winning outcome index for a market, `None` while unresolved.
+#[async_trait::async_trait]
+pub trait OutcomeResolver: Send + Sync {
+    async fn winning_index(&self, market_slug: &str) -> Result<Option<usize>>;
+}
+
+/// Resolves against Gamma; settled outcomes are persisted and never re-fetched.
+pub struct GammaOutcomeResolver {
+    storage: Arc<DbSignalStorage>,
+    http: reqwest::Client,
+}
+
+impl GammaOutcomeResolver {
+    pub fn new(storage: Arc<DbSignalStorage>, http: reqwest::Client) -> Self {
+        Self { storage, http }
+    }
+}
+
+#[async_trait::async_trait]
+impl OutcomeResolver for GammaOutcomeResolver {
+    async fn winning_index(&self, market_slug: &str) -> Result<Option<usize>> {
+        if let Some(i) = self.storage.get_vault_llm_resolution(market_slug)? {
+            return Ok(Some(i as usize));
+        }
+        let Some(i) = polymarket_gamma::gamma_market_resolution(&self.http, market_slug).await?
+        else {
+            return Ok(None);
+        };
+        self.storage
+            .upsert_vault_llm_resolution(market_slug, i as i64, Utc::now().timestamp())?;
+        Ok(Some(i))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationBucket {
+    pub p_lo: f64,
+    pub p_hi: f64,
+    pub count: u64,
+    pub mean_p: Option<f64>,
+    pub hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelEvalReport {
+    pub model: String,
+    pub calls: u64,
+    pub call_errors: u64,
+    pub parse_errors: u64,
+    /// Decisions with an outcome and P_TRUE (scored for Brier/calibration)
+    pub forecasts: u64,
+    pub brier_score: Option<f64>,
+    pub buys: u64,
+    pub holds: u64,
+    /// BUYs with a usable ask at prompt time
+    pub trades: u64,
+    pub wins: u64,
+    pub staked_usdc: f64,
+    pub pnl_usdc: f64,
+    pub roi: Option<f64>,
+    pub avg_latency_ms: Option<f64>,
+    pub total_tokens: u64,
+    pub calibration: Vec<CalibrationBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmEvalReport {
+    pub generated_at: i64,
+    pub source: LlmEvalSource,
+    pub since_ts: i64,
+    pub prompts: usize,
+    pub resolved: usize,
+    pub unresolved: usize,
+    pub models: Vec<ModelEvalReport>,
+}
+
+#[derive(Debug, Default)]
+struct ModelScore {
+    calls: u64,
+    call_errors: u64,
+    parse_errors: u64,
+    forecasts: u64,
+    brier_sum: f64,
+    buys: u64,
+    holds: u64,
+    trades: u64,
+    wins: u64,
+    staked_usdc: f64,
+    pnl_usdc: f64,
+    latency_ms_sum: u64,
+    latency_samples: u64,
+    total_tokens: u64,
+    /// (count, sum p, hits) per bucket
+    buckets: [(u64, f64, u64); CALIBRATION_BUCKETS],
+}
+
+impl ModelScore {
+    fn score(
+        &mut self,
+        decision: &ParsedDecisionDsl,
+        prompt: &VaultLlmPromptRow,
+        winner: usize,
+        cfg: &LlmEvalConfig,
+    ) {
+        let picked = decision.map_outcome_index(&prompt.outcomes);
+
+        if let (Some(i), Some(p)) = (picked, decision.p_true) {
+            let hit = i == winner;
+            let y = if hit { 1.0 } else { 0.0 };
+            self.forecasts += 1;
+            self.brier_sum += (p - y) * (p - y);
+            let b = ((p * CALIBRATION_BUCKETS as f64) as usize).min(CALIBRATION_BUCKETS - 1);
+            self.buckets[b].0 += 1;
+            self.buckets[b].1 += p;
+            self.buckets[b].2 += hit as u64;
+        }
+
+        if !decision.is_buy() {
+            self.holds += 1;
+            return;
+        }
+        self.buys += 1;
+
+        let Some(i) = picked else {
+            return;
+        };
+        let Some(ask) = prompt
+            .asks
+            .get(i)
+            .copied()
+            .flatten()
+            .filter(|a| *a > 0.0 && *a < 1.0)
+        else {
+            return;
+        };
+        let stake = cfg.stake_usdc * decision.size_mult.unwrap_or(1.0).clamp(0.0, 1.0);
+        if stake <= 0.0 {
+            return;
+        }
+        let entry = (ask + cfg.fee_buffer).clamp(0.0001, 0.9999);
+        self.trades += 1;
+        self.staked_usdc += stake;
+        if i == winner {
+            self.wins += 1;
+            self.pnl_usdc += stake * (1.0 / entry - 1.0);
+        } else {
+            self.pnl_usdc -= stake;
+        }
+    }
+
+    fn report(&self, model: &str) -> ModelEvalReport {
+        let calibration = self
+            .buckets
+            .iter()
+            .enumerate()
+            .map(|(b, (count, p_sum, hits))| CalibrationBucket {
+                p_lo: b as f64 / CALIBRATION_BUCKETS as f64,
+                p_hi: (b + 1) as f64 / CALIBRATION_BUCKETS as f64,
+                count: *count,
+                mean_p: (*count > 0).then(|| p_sum / *count as f64),
+                hit_rate: (*count > 0).then(|| *hits as f64 / *count as f64),
+            })
+            .collect();
+
+        ModelEvalReport {
+            model: model.to_string(),
+            calls: self.calls,
+            call_errors: self.call_errors,
+            parse_errors: self.parse_errors,
+            forecasts: self.forecasts,
+            brier_score: (self.forecasts > 0).then(|| self.brier_sum / self.forecasts as f64),
+            buys: self.buys,
+            holds: self.holds,
+            trades: self.trades,
+            wins: self.wins,
+            staked_usdc: self.staked_usdc,
+            pnl_usdc: self.pnl_usdc,
+            roi: (self.staked_usdc > 0.0).then(|| self.pnl_usdc / self.staked_usdc),
+            avg_latency_ms: (self.latency_samples > 0)
+                .then(|| self.latency_ms_sum as f64 / self.latency_samples as f64),
+            total_tokens: self.total_tokens,
+            calibration,
+        }
+    }
+}
+
+/// Replay or re-score stored LONG prompts and report per-model quality.
+/// `client` is required for `LlmEvalSource::Replay`.
+pub async fn run_llm_eval(
+    storage: &DbSignalStorage,
+    client: Option<&dyn LlmClient>,
+    resolver: &dyn OutcomeResolver,
+    cfg: &LlmEvalConfig,
+    source: LlmEvalSource,
+) -> Result<LlmEvalReport> {
+    if cfg.models.is_empty() {
+        return Err(anyhow!("no models to evaluate"));
+    }
+    let client = match (source, client) {
+        (LlmEvalSource::Replay, None) => {
+            return Err(anyhow!("replay requires an LLM client"));
+        }
+        (_, c) => c,
+    };
+
+    let prompts = storage.get_vault_llm_prompts(cfg.since_ts, cfg.until_ts, cfg.max_prompts)?;
+    let mut scores: HashMap<&str, ModelScore> = cfg
+        .models
+        .iter()
+        .map(|m| (m.as_str(), ModelScore::default()))
+        .collect();
+    let mut winners: HashMap<String, Option<usize>> = HashMap::new();
+    let mut resolved = 0usize;
+
+    for prompt in &prompts {
+        let winner = match winners.get(&prompt.market_slug) {
+            Some(w) => *w,
+            None => {
+                let w = match resolver.winning_index(&prompt.market_slug).await {
+                    Ok(w) => w.filter(|i| *i < prompt.outcomes.len()),
+                    Err(e) => {
+                        warn!(market_slug = %prompt.market_slug, error = %e, "LLM eval: resolution lookup failed");
+                        None
+                    }
+                };
+                winners.insert(prompt.market_slug.clone(), w);
+                w
+            }
+        };
+        let Some(winner) = winner else {
+            continue;
+        };
+        resolved += 1;
+
+        let recorded = match source {
+            LlmEvalSource::Recorded => {
+                storage.get_vault_llm_model_records(&prompt.decision_id, 100)?
+            }
+            LlmEvalSource::Replay => Vec::new(),
+        };
+
+        for model in &cfg.models {
+            let score = scores.get_mut(model.as_str()).expect("model score");
+
+            let content = match (source, client) {
+                (LlmEvalSource::Replay, Some(client)) => {
+                    score.calls += 1;
+                    match client
+                        .chat_completion(
+                            model,
+                            &prompt.system_prompt,
+                            &prompt.user_prompt,
+                            cfg.max_tokens,
+                            cfg.temperature,
+                            cfg.timeout,
+                        )
+                        .await
+                    {
+                        Ok(call) => {
+                            score.latency_ms_sum += call.latency_ms;
+                            score.latency_samples += 1;
+                            score.total_tokens += call.usage.total_tokens.unwrap_or(0);
+                            call.content
+                        }
+                        Err(e) => {
+                            warn!(model = %model, error = %e, "LLM eval: replay call failed");
+                            score.call_errors += 1;
+                            continue;
+                        }
+                    }
+                }
+                _ => {
+                    // The engine only calls the full panel after a scout BUY.
+                    let Some(rec) = recorded.iter().find(|r| &r.model == model) else {
+                        continue;
+                    };
+                    score.calls += 1;
+                    if let Some(ms) = rec.latency_ms {
+                        score.latency_ms_sum += ms.max(0) as u64;
+                        score.latency_samples += 1;
+                    }
+                    score.total_tokens += rec.total_tokens.unwrap_or(0).max(0) as u64;
+                    let Some(raw) = rec.raw_dsl.clone() else {
+                        score.call_errors += 1;
+                        continue;
+                    };
+                    raw
+                }
+            };
+
+            match parse_decision_dsl(&content) {
+                Ok(decision) => score.score(&decision, prompt, winner, cfg),
+                Err(_) => score.parse_errors += 1,
+            }
+        }
+    }
+
+    Ok(LlmEvalReport {
+        generated_at: Utc::now().timestamp(),
+        source,
+        since_ts: cfg.since_ts,
+        prompts: prompts.len(),
+        resolved,
+        unresolved: prompts.len() - resolved,
+        models: cfg
+            .models
+            .iter()
+            .map(|m| scores[m.as_str()].report(m))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{LlmStubServer, OpenRouterClient};
+
+    struct FixedResolver(HashMap<String, usize>);
+
+    #[async_trait::async_trait]
+    impl OutcomeResolver for FixedResolver {
+        async fn winning_index(&self, market_slug: &str) -> Result<Option<usize>> {
+            Ok(self.0.get(market_slug).copied())
+        }
+    }
+
+    fn eval_cfg(models: &[&str]) -> LlmEvalConfig {
+        LlmEvalConfig {
+            models: models.iter().map(|m| m.to_string()).collect(),
+            since_ts: 0,
+            until_ts: i64::MAX,
+            max_prompts: 100,
+            max_tokens: 200,
+            temperature: 0.0,
+            timeout: Duration::from_secs(5),
+            stake_usdc: 100.0,
+            fee_buffer: 0.0,
+        }
+    }
+
+    fn prompt(decision_id: &str, market_slug: &str, price: f64) -> VaultLlmPromptRow {
+        VaultLlmPromptRow {
+            decision_id: decision_id.to_string(),
+            market_slug: market_slug.to_string(),
+            created_at: 1_700_000_000,
+            system_prompt: "sys".to_string(),
+            user_prompt: format!(
+                "market_slug={}\nmarket_snapshot: current_price={:.4}",
+                market_slug, price
+            ),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            asks: vec![Some(price), Some(1.0 - price)],
+        }
+    }
+
+    #[test]
+    fn test_scores_brier_calibration_and_pnl() {
+        let cfg = eval_cfg(&["m"]);
+        let p = prompt("d1", "mkt", 0.40);
+        let mut score = ModelScore::default();
+
+        // Right at p=0.7 on YES (ask 0.40): +150 on a 100 stake.
+        let buy_yes = parse_decision_dsl("ACTION=BUY\nOUTCOME_INDEX=0\nP_TRUE=0.7").unwrap();
+        score.score(&buy_yes, &p, 0, &cfg);
+        // Wrong at p=0.8 on NO, half size: -50.
+        let buy_no =
+            parse_decision_dsl("ACTION=BUY\nOUTCOME_INDEX=1\nP_TRUE=0.8\nSIZE_MULT=0.5").unwrap();
+        score.score(&buy_no, &p, 0, &cfg);
+        // HOLD without a forecast only counts as a hold.
+        score.score(&parse_decision_dsl("ACTION=HOLD").unwrap(), &p, 0, &cfg);
+
+        let r = score.report("m");
+        assert_eq!(
+            (r.forecasts, r.buys, r.holds, r.trades, r.wins),
+            (2, 2, 1, 2, 1)
+        );
+        let brier = (0.3f64.powi(2) + 0.8f64.powi(2)) / 2.0;
+        assert!((r.brier_score.unwrap() - brier).abs() < 1e-12);
+        assert!((r.pnl_usdc - 100.0).abs() < 1e-9);
+        assert!((r.staked_usdc - 150.0).abs() < 1e-9);
+        assert_eq!(r.calibration[7].count, 1);
+        assert_eq!(r.calibration[7].hit_rate, Some(1.0));
+        assert_eq!(r.calibration[8].hit_rate, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_replays_stored_prompts_against_stub() {
+        let storage = DbSignalStorage::new(":memory:").unwrap();
+        storage
+            .insert_vault_llm_prompt(&prompt("d1", "resolved-mkt", 0.65))
+            .await
+            .unwrap();
+        storage
+            .insert_vault_llm_prompt(&prompt("d2", "open-mkt", 0.30))
+            .await
+            .unwrap();
+
+        let stub = LlmStubServer::start().await.unwrap();
+        let client = OpenRouterClient::new(reqwest::Client::new(), "stub", &stub.base_url());
+        let resolver = FixedResolver(HashMap::from([("resolved-mkt".to_string(), 0)]));
+        let cfg = eval_cfg(&["model-a", "model-b"]);
+
+        let report = run_llm_eval(
+            &storage,
+            Some(&client),
+            &resolver,
+            &cfg,
+            LlmEvalSource::Replay,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            (report.prompts, report.resolved, report.unresolved),
+            (2, 1, 1)
+        );
+        assert_eq!(report.models.len(), 2);
+        for m in &report.models {
+            assert_eq!((m.calls, m.call_errors, m.parse_errors), (1, 0, 0));
+            assert_eq!(m.forecasts, 1);
+            assert!(m.total_tokens > 0);
+        }
+
+        // The stub is deterministic: a second replay scores identically.
+        let again = run_llm_eval(
+            &storage,
+            Some(&client),
+            &resolver,
+            &cfg,
+            LlmEvalSource::Replay,
+        )
+        .await
+        .unwrap();
+        for (a, b) in report.models.iter().zip(&again.models) {
+            assert_eq!(a.brier_score, b.brier_score);
+            assert_eq!(a.pnl_usdc, b.pnl_usdc);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rescores_recorded_responses() {
+        let storage = DbSignalStorage::new(":memory:").unwrap();
+        storage
+            .insert_vault_llm_prompt(&prompt("d1", "mkt", 0.50))
+            .await
+            .unwrap();
+        storage
+            .insert_vault_llm_model_record(
+                "r1",
+                "d1",
+                "model-a",
+                1_700_000_000,
+                true,
+                Some("BUY"),
+                Some(1),
+                Some(0.6),
+                None,
+                None,
+                None,
+                None,
+                Some("ACTION=BUY\nOUTCOME_INDEX=1\nP_TRUE=0.6"),
+                Some(900),
+                Some(100),
+                Some(20),
+                Some(120),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let resolver = FixedResolver(HashMap::from([("mkt".to_string(), 1)]));
+        let cfg = eval_cfg(&["model-a", "model-b"]);
+        let report = run_llm_eval(&storage, None, &resolver, &cfg, LlmEvalSource::Recorded)
+            .await
+            .unwrap();
+
+        let a = &report.models[0];
+        assert_eq!((a.calls, a.trades, a.wins, a.total_tokens), (1, 1, 1, 120));
+        assert!((a.pnl_usdc - 100.0).abs() < 1e-9);
+        assert!((a.brier_score.unwrap() - 0.16).abs() < 1e-12);
+        // model-b was never called for this decision.
+        assert_eq!(report.models[1].calls, 0);
+        assert_eq!(report.models[1].brier_score, None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Local deterministic LLM stub
+//!
+//! OpenAI-compatible `/chat/completions` server for replaying LONG prompts
+//! without paying for model calls:
+//! 1. The reply is a pure function of (model, user prompt) - same input, same DSL
+//! 2. P_TRUE is anchored on the prompt's `current_price` and jittered per model
+//! 3. Token usage is approximated from prompt/reply length
+//!
+//! Point an `OpenRouterClient::new(http, "stub", &stub.base_url())` at it.
+
+use anyhow::{Context, Result};
+use axum::{routing::post, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use tokio::task::JoinHandle;
+
+/// Running stub bound to a local port; the server stops on drop.
+pub struct LlmStubServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl LlmStubServer {
+    pub async fn start() -> Result<Self> {
+        let router = Router::new().route("/chat/completions", post(chat_completions));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("bind LLM stub")?;
+        let addr = listener.local_addr().context("LLM stub address")?;
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Ok(Self { addr, task })
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for LlmStubServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StubRequest {
+    model: String,
+    messages: Vec<StubMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StubMessage {
+    role: String,
+    content: String,
+}
+
+async fn chat_completions(Json(req): Json<StubRequest>) -> Json<Value> {
+    let user = req
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| m.content.as_str())
+        .unwrap_or("");
+    let prompt_len: usize = req.messages.iter().map(|m| m.content.len()).sum();
+    let content = stub_decision_dsl(&req.model, user);
+
+    let prompt_tokens = (prompt_len / 4) as u64;
+    let completion_tokens = (content.len() / 4) as u64;
+    Json(json!({
+        "choices": [{ "message": { "role": "assistant", "content": content } }],
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens,
+        },
+    }))
+}
+
+/// Deterministic decision DSL for a (model, user prompt) pair.
+pub fn stub_decision_dsl(model: &str, user: &str) -> String {
+    let h = fnv1a(
+        model.as_bytes(),
+        fnv1a(user.as_bytes(), 0xcbf2_9ce4_8422_2325),
+    );
+
+    let price = prompt_current_price(user).unwrap_or(0.5);
+    // Jitter in [-0.15, 0.15] around the quoted price.
+    let jitter = ((h >> 16) % 3001) as f64 / 10_000.0 - 0.15;
+    let p_yes = (price + jitter).clamp(0.02, 0.98);
+
+    if h % 4 == 0 {
+        return format!(
+            "ACTION=HOLD\nOUTCOME_INDEX=0\nP_TRUE={:.4}\nUNCERTAINTY=HIGH\nSIZE_MULT=0\nFLAGS=STUB\nRATIONALE_HASH={:016x}",
+            p_yes, h
+        );
+    }
+
+    let (outcome_index, p_true) = if p_yes >= 0.5 {
+        (0, p_yes)
+    } else {
+        (1, 1.0 - p_yes)
+    };
+    let size_mult = 0.25 + ((h >> 40) % 76) as f64 / 100.0;
+    format!(
+        "ACTION=BUY\nOUTCOME_INDEX={}\nP_TRUE={:.4}\nUNCERTAINTY=MED\nSIZE_MULT={:.2}\nFLAGS=STUB\nRATIONALE_HASH={:016x}",
+        outcome_index, p_true, size_mult, h
+    )
+}
+
+fn prompt_current_price(user: &str) -> Option<f64> {
+    let rest = &user[user.find("current_price=")? + "current_price=".len()..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    rest[..end]
+        .parse::<f64>()
+        .ok()
+        .filter(|p| p.is_finite() && *p > 0.0 && *p < 1.0)
+}
+
+fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    bytes.iter().fold(seed, |h, b| {
+        (h ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{parse_decision_dsl, OpenRouterClient};
+    use std::time::Duration;
+
+    #[test]
+    fn test_stub_dsl_is_deterministic_and_parses() {
+        let user = "market_slug=x\nmarket_snapshot: current_price=0.7200 liquidity=1000";
+        let a = stub_decision_dsl("model-a", user);
+        assert_eq!(a, stub_decision_dsl("model-a", user));
+
+        for model in ["model-a", "model-b", "model-c", "model-d"] {
+            let parsed = parse_decision_dsl(&stub_decision_dsl(model, user)).unwrap();
+            let p = parsed.p_true.unwrap();
+            assert!((0.0001..=0.9999).contains(&p));
+            assert!(parsed.outcome_index.unwrap() < 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_openrouter_client_talks_to_stub() {
+        let stub = LlmStubServer::start().await.unwrap();
+        let client = OpenRouterClient::new(reqwest::Client::new(), "stub", &stub.base_url());
+
+        let user = "market_snapshot: current_price=0.3000";
+        let out = client
+            .chat_completion("model-a", "sys", user, 200, 0.0, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(out.content, stub_decision_dsl("model-a", user));
+        assert!(out.usage.total_tokens.unwrap() > 0);
+    }
+}
@@ -0,0 +1,626 @@
+//! Local mock of the Polymarket CLOB for tests
+//!
+//! Serves the REST endpoints used by `PolymarketClobAdapter` (order entry,
+//! cancel, order queries) and the authenticated user WS channel:
+//! 1. GTC orders rest on the book ("live"); IOC/FOK orders match in full
+//! 2. `fill_resting` matches a resting order and pushes the trade to the user channel
+//! 3. `cancel_by_venue` cancels an order on the venue side (e.g. market resolved)
+//! 4. `disconnect_users` drops every user channel connection
+//! 5. Requests without L2 auth headers are refused with 401
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+#[derive(Debug, Clone)]
+pub struct MockClobOrder {
+    pub id: String,
+    pub asset_id: String,
+    pub side: String,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    /// LIVE, MATCHED or CANCELED
+    pub status: String,
+}
+
+impl MockClobOrder {
+    fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "status": self.status,
+            "asset_id": self.asset_id,
+            "side": self.side,
+            "price": format!("{}", self.price),
+            "original_size": format!("{}", self.original_size),
+            "size_matched": format!("{}", self.size_matched),
+            "created_at": 1_700_000_000,
+        })
+    }
+}
+
+#[derive(Default)]
+struct MockClobBook {
+    orders: HashMap<String, MockClobOrder>,
+    next_id: u64,
+    next_trade_id: u64,
+}
+
+#[derive(Clone)]
+struct MockClobShared {
+    book: Arc<Mutex<MockClobBook>>,
+    user_tx: broadcast::Sender<String>,
+    user_connections: Arc<AtomicUsize>,
+    disconnect_tx: broadcast::Sender<()>,
+}
+
+/// Running mock CLOB bound to a local port.
+pub struct MockClob {
+    addr: SocketAddr,
+    shared: MockClobShared,
+}
+
+impl MockClob {
+    pub async fn start() -> Self {
+        let (user_tx, _) = broadcast::channel(256);
+        let (disconnect_tx, _) = broadcast::channel(4);
+        let shared = MockClobShared {
+            book: Arc::new(Mutex::new(MockClobBook::default())),
+            user_tx,
+            user_connections: Arc::new(AtomicUsize::new(0)),
+            disconnect_tx,
+        };
+
+        let router = Router::new()
+            .route("/order", post(post_order).delete(cancel_order))
+            .route("/cancel-all", delete(cancel_all))
+            .route("/cancel-market-orders", delete(cancel_market_orders))
+            .route("/data/order/:id", get(get_order))
+            .route("/data/orders", get(get_orders))
+            .route("/ws/user", get(user_ws))
+            .with_state(shared.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock CLOB");
+        let addr = listener.local_addr().expect("mock CLOB address");
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Self { addr, shared }
+    }
+
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}/ws/user", self.addr)
+    }
+
+    pub fn order(&self, order_id: &str) -> Option<MockClobOrder> {
+        self.shared.book.lock().orders.get(order_id).cloned()
+    }
+
+    /// Wait until `n` user channel clients have subscribed.
+    pub async fn wait_for_user_subscribers(&self, n: usize) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if self.shared.user_connections.load(Ordering::SeqCst) >= n {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        false
+    }
+
+    /// Close every user channel connection and wait until they are gone.
+    /// Trades published before clients reconnect are never delivered.
+    pub async fn disconnect_users(&self) -> bool {
+        let _ = self.shared.disconnect_tx.send(());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if self.shared.user_connections.load(Ordering::SeqCst) == 0 {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        false
+    }
+
+    /// Match `size` shares of a resting order (as maker) and publish the trade.
+    pub fn fill_resting(&self, order_id: &str, size: f64) {
+        let message = {
+            let mut book = self.shared.book.lock();
+            book.next_trade_id += 1;
+            let trade_id = format!("trade-{}", book.next_trade_id);
+            let order = book.orders.get_mut(order_id).expect("unknown mock order");
+            let size = size.min(order.original_size - order.size_matched);
+            order.size_matched += size;
+            if order.original_size - order.size_matched <= 1e-9 {
+                order.status = "MATCHED".to_string();
+            }
+            json!([{
+                "event_type": "trade",
+                "type": "TRADE",
+                "id": trade_id,
+                "asset_id": order.asset_id,
+                "side": if order.side == "BUY" { "SELL" } else { "BUY" },
+                "price": format!("{}", order.price),
+                "size": format!("{}", size),
+                "status": "MATCHED",
+                "taker_order_id": "0xexternal",
+                "maker_orders": [{
+                    "order_id": order.id,
+                    "asset_id": order.asset_id,
+                    "matched_amount": format!("{}", size),
+                    "price": format!("{}", order.price),
+                }],
+                "fee_rate_bps": "0",
+            }])
+        };
+        let _ = self.shared.user_tx.send(message.to_string());
+    }
+
+    /// Cancel an order on the venue side and publish the cancellation.
+    pub fn cancel_by_venue(&self, order_id: &str) {
+        let message = {
+            let mut book = self.shared.book.lock();
+            let order = book.orders.get_mut(order_id).expect("unknown mock order");
+            order.status = "CANCELED".to_string();
+            json!({
+                "event_type": "order",
+                "type": "CANCELLATION",
+                "id": order.id,
+                "asset_id": order.asset_id,
+                "size_matched": format!("{}", order.size_matched),
+            })
+        };
+        let _ = self.shared.user_tx.send(message.to_string());
+    }
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    ["poly_api_key", "poly_signature", "poly_timestamp", "poly_passphrase"]
+        .iter()
+        .all(|name| headers.get(*name).is_some_and(|v| !v.is_empty()))
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({ "error": "Unauthorized/Invalid api key" })),
+    )
+        .into_response()
+}
+
+fn parse_f64(value: &Value) -> f64 {
+    match value {
+        Value::String(s) => s.parse().unwrap_or(0.0),
+        v => v.as_f64().unwrap_or(0.0),
+    }
+}
+
+async fn post_order(
+    State(shared): State<MockClobShared>,
+    headers: HeaderMap,
+    Json(payload): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let price = parse_f64(&payload["price"]);
+    let size = parse_f64(&payload["size"]);
+    if !(price > 0.0 && price < 1.0 && size > 0.0) {
+        return Json(json!({ "success": false, "errorMsg": "invalid order" })).into_response();
+    }
+    let rests = payload["timeInForce"].as_str().unwrap_or("GTC") == "GTC";
+
+    let mut book = shared.book.lock();
+    book.next_id += 1;
+    let order = MockClobOrder {
+        id: format!("0xorder{}", book.next_id),
+        asset_id: payload["tokenID"].as_str().unwrap_or_default().to_string(),
+        side: payload["side"].as_str().unwrap_or("BUY").to_string(),
+        price,
+        original_size: size,
+        size_matched: if rests { 0.0 } else { size },
+        status: if rests { "LIVE" } else { "MATCHED" }.to_string(),
+    };
+    let response = json!({
+        "success": true,
+        "errorMsg": "",
+        "orderID": order.id,
+        "status": if rests { "live" } else { "matched" },
+        "filledSize": format!("{}", order.size_matched),
+        "avgPrice": format!("{}", price),
+    });
+    book.orders.insert(order.id.clone(), order);
+    Json(response).into_response()
+}
+
+/// Cancel the given orders that are still live.
+fn cancel_ids(shared: &MockClobShared, ids: &[String]) -> Value {
+    let mut book = shared.book.lock();
+    let mut canceled = Vec::new();
+    let mut not_canceled = serde_json::Map::new();
+    for id in ids {
+        match book.orders.get_mut(id) {
+            Some(order) if order.status == "LIVE" => {
+                order.status = "CANCELED".to_string();
+                canceled.push(id.clone());
+            }
+            Some(_) => {
+                not_canceled.insert(id.clone(), json!("order can't be canceled"));
+            }
+            None => {
+                not_canceled.insert(id.clone(), json!("order not found"));
+            }
+        }
+    }
+    json!({ "canceled": canceled, "not_canceled": not_canceled })
+}
+
+fn live_ids(shared: &MockClobShared, asset_id: Option<&str>) -> Vec<String> {
+    let book = shared.book.lock();
+    let mut ids: Vec<String> = book
+        .orders
+        .values()
+        .filter(|o| o.status == "LIVE" && asset_id.is_none_or(|a| o.asset_id == a))
+        .map(|o| o.id.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
+async fn cancel_order(
+    State(shared): State<MockClobShared>,
+    headers: HeaderMap,
+    Json(payload): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let id = payload["orderID"].as_str().unwrap_or_default().to_string();
+    Json(cancel_ids(&shared, &[id])).into_response()
+}
+
+async fn cancel_all(State(shared): State<MockClobShared>, headers: HeaderMap) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let ids = live_ids(&shared, None);
+    Json(cancel_ids(&shared, &ids)).into_response()
+}
+
+async fn cancel_market_orders(
+    State(shared): State<MockClobShared>,
+    headers: HeaderMap,
+    Json(payload): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let ids = live_ids(&shared, payload["asset_id"].as_str());
+    Json(cancel_ids(&shared, &ids)).into_response()
+}
+
+async fn get_order(
+    State(shared): State<MockClobShared>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    match shared.book.lock().orders.get(&id) {
+        Some(order) => Json(order.to_json()).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "error": "order not found" }))).into_response(),
+    }
+}
+
+async fn get_orders(
+    State(shared): State<MockClobShared>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let ids = live_ids(&shared, query.get("asset_id").map(String::as_str));
+    let book = shared.book.lock();
+    let data: Vec<Value> = ids.iter().map(|id| book.orders[id].to_json()).collect();
+    Json(json!({ "data": data, "next_cursor": "LTE=" })).into_response()
+}
+
+async fn user_ws(State(shared): State<MockClobShared>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| user_session(socket, shared))
+}
+
+async fn user_session(mut socket: WebSocket, shared: MockClobShared) {
+    // The first message must be an authenticated user subscription
+    let subscribed = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<Value>(&text)
+            .map(|v| v["type"] == "user" && v["auth"]["apiKey"].as_str().is_some_and(|k| !k.is_empty()))
+            .unwrap_or(false),
+        _ => false,
+    };
+    if !subscribed {
+        return;
+    }
+
+    let mut user_rx = shared.user_tx.subscribe();
+    let mut disconnect_rx = shared.disconnect_tx.subscribe();
+    shared.user_connections.fetch_add(1, Ordering::SeqCst);
+    loop {
+        tokio::select! {
+            _ = disconnect_rx.recv() => break,
+            msg = user_rx.recv() => match msg {
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) if text == "PING" => {
+                    if socket.send(Message::Text("PONG".to_string())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    drop(user_rx);
+    shared.user_connections.fetch_sub(1, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{
+        ExecutionAdapter, ExecutionEvent, OrderRequest, OrderSide, OrderStatus,
+        PolymarketClobAdapter, PolymarketClobCredentials, TimeInForce,
+    };
+
+    fn adapter(clob: &MockClob) -> PolymarketClobAdapter {
+        PolymarketClobAdapter::new(PolymarketClobCredentials {
+            api_key: "key".to_string(),
+            secret: "c2VjcmV0".to_string(),
+            passphrase: "pass".to_string(),
+        })
+        .with_endpoints(clob.http_url(), clob.ws_url())
+    }
+
+    fn request(id: &str, token_id: &str, price: f64, notional_usdc: f64, tif: TimeInForce) -> OrderRequest {
+        OrderRequest {
+            client_order_id: id.to_string(),
+            token_id: token_id.to_string(),
+            side: OrderSide::Buy,
+            price,
+            notional_usdc,
+            tif,
+            market_slug: None,
+            outcome: None,
+        }
+    }
+
+    async fn next_event(rx: &mut broadcast::Receiver<ExecutionEvent>) -> ExecutionEvent {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("execution event timed out")
+            .expect("execution event channel closed")
+    }
+
+    #[tokio::test]
+    async fn test_resting_order_fill_and_cancel_over_ws() {
+        let clob = MockClob::start().await;
+        let exec = adapter(&clob);
+        let mut events = exec.subscribe_events();
+        assert!(clob.wait_for_user_subscribers(1).await);
+
+        let ack = exec
+            .place_order(request("bid-1", "TOKEN", 0.40, 4.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        assert_eq!(ack.filled_notional_usdc, 0.0);
+        assert!((ack.resting_size - 10.0).abs() < 1e-9);
+        assert!(matches!(next_event(&mut events).await, ExecutionEvent::Ack { .. }));
+
+        clob.fill_resting(&ack.order_id, 4.0);
+        match next_event(&mut events).await {
+            ExecutionEvent::Fill {
+                order_id,
+                client_order_id,
+                size,
+                remaining_size,
+                is_maker,
+                ..
+            } => {
+                assert_eq!(order_id, ack.order_id);
+                assert_eq!(client_order_id, "bid-1");
+                assert!((size - 4.0).abs() < 1e-9);
+                assert!((remaining_size - 6.0).abs() < 1e-9);
+                assert!(is_maker);
+            }
+            other => panic!("expected fill, got {:?}", other),
+        }
+
+        let status = exec.order_status(&ack.order_id).await.unwrap();
+        assert_eq!(status.status, OrderStatus::PartiallyFilled);
+        assert_eq!(status.client_order_id, "bid-1");
+        assert_eq!(exec.open_orders(Some("TOKEN")).await.unwrap().len(), 1);
+
+        exec.cancel_order(&ack.order_id).await.unwrap();
+        match next_event(&mut events).await {
+            ExecutionEvent::Cancelled { cancelled_size, .. } => {
+                assert!((cancelled_size - 6.0).abs() < 1e-9)
+            }
+            other => panic!("expected cancel, got {:?}", other),
+        }
+        assert_eq!(clob.order(&ack.order_id).unwrap().status, "CANCELED");
+        assert!(exec.open_orders(None).await.unwrap().is_empty());
+        assert!(exec.cancel_order(&ack.order_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ioc_fills_immediately_and_cancel_all_by_token() {
+        let clob = MockClob::start().await;
+        let exec = adapter(&clob);
+
+        let ioc = exec
+            .place_order(request("take", "A", 0.50, 5.0, TimeInForce::Ioc))
+            .await
+            .unwrap();
+        assert!((ioc.filled_notional_usdc - 5.0).abs() < 1e-9);
+        assert_eq!(ioc.resting_size, 0.0);
+        assert_eq!(
+            exec.order_status(&ioc.order_id).await.unwrap().status,
+            OrderStatus::Filled
+        );
+
+        let a = exec
+            .place_order(request("a", "A", 0.30, 3.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        let b = exec
+            .place_order(request("b", "B", 0.30, 3.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+
+        assert_eq!(exec.cancel_all(Some("A")).await.unwrap(), vec![a.order_id.clone()]);
+        let open = exec.open_orders(None).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].order_id, b.order_id);
+        assert_eq!(exec.cancel_all(None).await.unwrap(), vec![b.order_id]);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_reconciles_fills_missed_in_gap() {
+        let clob = MockClob::start().await;
+        let exec = adapter(&clob);
+        let mut events = exec.subscribe_events();
+        assert!(clob.wait_for_user_subscribers(1).await);
+
+        let partial = exec
+            .place_order(request("partial", "TOKEN", 0.40, 4.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        let full = exec
+            .place_order(request("full", "TOKEN", 0.20, 2.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        next_event(&mut events).await; // ack
+        next_event(&mut events).await; // ack
+
+        // Both fills happen while the user channel is down
+        assert!(clob.disconnect_users().await);
+        clob.fill_resting(&partial.order_id, 4.0);
+        clob.fill_resting(&full.order_id, 10.0);
+
+        // Reconnect reconciles: one order still listed as open, one not
+        let mut fills = HashMap::new();
+        for _ in 0..2 {
+            match next_event(&mut events).await {
+                ExecutionEvent::Fill {
+                    client_order_id,
+                    size,
+                    is_maker,
+                    ..
+                } => {
+                    assert!(is_maker);
+                    fills.insert(client_order_id, size);
+                }
+                other => panic!("expected fill, got {:?}", other),
+            }
+        }
+        assert!((fills["partial"] - 4.0).abs() < 1e-9);
+        assert!((fills["full"] - 10.0).abs() < 1e-9);
+        assert!(clob.wait_for_user_subscribers(1).await);
+
+        assert_eq!(
+            exec.order_status(&partial.order_id).await.unwrap().status,
+            OrderStatus::PartiallyFilled
+        );
+        assert_eq!(
+            exec.order_status(&full.order_id).await.unwrap().status,
+            OrderStatus::Filled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_venue_cancel_and_auth() {
+        let clob = MockClob::start().await;
+        let exec = adapter(&clob);
+        let mut events = exec.subscribe_events();
+        assert!(clob.wait_for_user_subscribers(1).await);
+
+        let ack = exec
+            .place_order(request("bid", "TOKEN", 0.20, 2.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        next_event(&mut events).await; // ack
+
+        clob.cancel_by_venue(&ack.order_id);
+        assert!(matches!(
+            next_event(&mut events).await,
+            ExecutionEvent::Cancelled { .. }
+        ));
+        assert!(exec.open_orders(None).await.unwrap().is_empty());
+
+        let unsigned = reqwest::Client::new()
+            .get(format!("{}/data/orders", clob.http_url()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unsigned.status().as_u16(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_round_trips_feed_venue_latency_tracker() {
+        let clob = MockClob::start().await;
+        let exec = adapter(&clob);
+        let venues = crate::performance::global_venue_tracker();
+        let before = venues.get("polymarket").unwrap();
+
+        exec.place_order(request("take", "A", 0.50, 5.0, TimeInForce::Ioc))
+            .await
+            .unwrap();
+        let rest = exec
+            .place_order(request("rest", "A", 0.30, 3.0, TimeInForce::Gtc))
+            .await
+            .unwrap();
+        exec.cancel_order(&rest.order_id).await.unwrap();
+
+        // The tracker is process-wide, so other tests may add to it as well
+        let after = venues.get("polymarket").unwrap();
+        assert!(after.total_orders >= before.total_orders + 2);
+        assert!(after.total_fills > before.total_fills);
+        assert!(after.total_cancels > before.total_cancels);
+        let buckets = venues.latency_buckets("polymarket").unwrap();
+        assert!(buckets.send_ack.iter().map(|(_, n)| n).sum::<u64>() >= 2);
+        assert!(buckets.cancel_ack.iter().map(|(_, n)| n).sum::<u64>() >= 1);
+    }
+}
@@ -14,6 +14,8 @@ pub mod ab_test;
 pub mod backtest_rnjd;
 pub mod belief_vol;
 pub mod book_access; // HFT-grade cache-only book access (no REST in hot path)
+pub mod book_paper_execution; // Paper fills against the live book (queue model + 15M fees)
+pub mod capital_allocator; // Per-strategy capital budgets enforced at order time
 pub mod engine;
 pub mod execution;
 pub mod fast15m_reactive;
@@ -21,11 +23,19 @@ pub mod hft_paper_strategy; // HFT paper trading with RN-JD core (DEPRECATED - u
 pub mod kelly;
 pub mod latency_arb;
 pub mod llm;
+pub mod llm_eval; // Offline replay + Brier/calibration/PnL scoring of LONG models
+pub mod llm_stub; // Local deterministic OpenAI-compatible LLM server
+#[cfg(test)]
+pub mod mock_clob; // Local mock CLOB HTTP/WS server for tests
 pub mod orderflow_paper;
 pub mod paper_ledger;
 pub mod pool;
 pub mod rnjd;
+pub mod state_journal; // Write-ahead order/fill/position journal + restart recovery
+pub mod strategy_config; // Versioned, hot-reloadable strategy parameters
+pub mod strategy_host; // Live host for backtest_v2 strategies (same code as backtest)
 pub mod trade_executor;
+pub mod trading_control; // Kill switch: global/strategy/asset halts, auto triggers, audit log
 pub mod unified_15m_strategy; // PRODUCTION: Unified 15M strategy for live trading
 pub mod updown15m;
 pub mod user_accounts;
@@ -42,6 +52,11 @@ pub use book_access::{
     bid_ask_spread_hft, get_book_auto, get_book_cached, get_book_hft, BookResult, HasHftCache,
     SkipReason, StalenessConfig,
 };
+pub use book_paper_execution::{BookPaperConfig, BookPaperExecutionAdapter};
+pub use capital_allocator::{
+    sleeve_for, target_weights, AllocationMethod, AllocationTicket, CapitalAllocationStatus,
+    CapitalAllocator, CapitalAllocatorConfig, ReturnStats, SleeveAllocation,
+};
 pub use engine::*;
 pub use execution::*;
 pub use fast15m_reactive::*;
@@ -52,6 +67,11 @@ pub use hft_paper_strategy::{
 pub use kelly::*;
 pub use latency_arb::*;
 pub use llm::*;
+pub use llm_eval::{
+    run_llm_eval, CalibrationBucket, GammaOutcomeResolver, LlmEvalConfig, LlmEvalReport,
+    LlmEvalSource, ModelEvalReport, OutcomeResolver,
+};
+pub use llm_stub::{stub_decision_dsl, LlmStubServer};
 pub use orderflow_paper::{
     spawn_orderflow_paper_engine, OrderflowPaperConfig, OrderflowPaperMetrics,
     OrderflowPaperMetricsSummary,
@@ -62,7 +82,23 @@ pub use rnjd::{
     estimate_p_up_enhanced, estimate_p_up_rnjd, price_vol_to_belief_vol, rn_drift,
     JumpRegimeDetector, RnjdEstimate, RnjdParams,
 };
+pub use state_journal::{
+    adopt_venue_positions, diff_positions, recover_vault_state, restore_ledger,
+    PositionDiscrepancy, RecoveryConfig, RecoveryReport, VaultJournal,
+};
+pub use strategy_config::{
+    next_window_boundary, StrategyConfigStatus, StrategyConfigStore, StrategyConfigVersion,
+    VaultStrategyParams,
+};
+pub use strategy_host::{
+    spawn_live_strategy, LiveOrderSender, LiveStrategyConfig, LiveStrategyHandle,
+    LiveStrategyHost, LiveStrategyMetrics, LiveStrategyMetricsSummary,
+};
 pub use trade_executor::*;
+pub use trading_control::{
+    ControlEvent, HaltScope, HaltState, HaltTrigger, TradingControl, TradingControlConfig,
+    TradingControlStatus,
+};
 pub use unified_15m_strategy::{
     ExitReason, MetricsSummary, OpenPosition, PositionSide, StrategyMetrics, TradeRecord,
     Unified15mConfig, Unified15mStrategy,
@@ -203,6 +203,7 @@ pub struct OrderflowPaperMetrics {
     skipped_imbalance: AtomicU64,
     skipped_cooldown: AtomicU64,
     skipped_liquidity: AtomicU64,
+    skipped_budget: AtomicU64,
     queue_drops: AtomicU64,
     errors: AtomicU64,
 }
@@ -217,6 +218,7 @@ pub struct OrderflowPaperMetricsSummary {
     pub skipped_imbalance: u64,
     pub skipped_cooldown: u64,
     pub skipped_liquidity: u64,
+    pub skipped_budget: u64,
     pub queue_drops: u64,
     pub errors: u64,
 }
@@ -232,6 +234,7 @@ impl OrderflowPaperMetrics {
             skipped_imbalance: self.skipped_imbalance.load(Ordering::Relaxed),
             skipped_cooldown: self.skipped_cooldown.load(Ordering::Relaxed),
             skipped_liquidity: self.skipped_liquidity.load(Ordering::Relaxed),
+            skipped_budget: self.skipped_budget.load(Ordering::Relaxed),
             queue_drops: self.queue_drops.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
         }
@@ -367,6 +370,29 @@ impl OrderflowPaperEngine {
             (OrderSide::Sell, best_bid, notional)
         };
 
+        // Buys draw on the ORDERFLOW budget; sells release it via the fill.
+        let allocation = match side {
+            OrderSide::Buy => {
+                match self
+                    .state
+                    .capital_allocator
+                    .reserve("ORDERFLOW_PAPER", notional)
+                {
+                    Some(a) => Some(a),
+                    None => {
+                        self.metrics.skipped_budget.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                }
+            }
+            OrderSide::Sell => None,
+        };
+        let notional = allocation.as_ref().map_or(notional, |a| a.granted_usdc());
+        if notional < self.cfg.min_trade_usd {
+            self.metrics.skipped_budget.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
         let req = OrderRequest {
             client_order_id: Uuid::new_v4().to_string(),
             token_id: token_id.to_string(),
@@ -423,6 +449,14 @@ impl OrderflowPaperEngine {
                 (cash, total_shares)
             }
         };
+        self.state.capital_allocator.record_fill(
+            "ORDERFLOW_PAPER",
+            &req.token_id,
+            req.side,
+            ack.filled_price,
+            ack.filled_notional_usdc,
+        );
+        drop(allocation);
 
         let _ = self
             .state
@@ -467,6 +501,7 @@ impl OrderflowPaperEngine {
                 notional_usdc: Some(ack.filled_notional_usdc),
                 strategy: Some("ORDERFLOW_PAPER".to_string()),
                 decision_id: None,
+                config_version: None,
             })
             .await;
 
@@ -1,11 +1,35 @@
-use anyhow::{anyhow, Result};
+//! Pooled vault share accounting.
+//!
+//! Deposits and withdrawals do not touch shares directly: they are queued as
+//! subscription/redemption requests and filled together at the next NAV
+//! strike on a 15-minute window boundary. At a strike, positions are marked to
+//! book mids, the high-water-mark performance fee is crystallized (as shares
+//! minted to the fee wallet), and every queued request fills at the same
+//! post-fee NAV per share. A holder's share count therefore only changes
+//! through their own requests.
+
+use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::sync::Mutex;
+use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::vault::{VaultActivityRecord, VaultDb, VaultNavSnapshotRecord, VaultPaperLedger};
+use crate::scrapers::HftBookCache;
+use crate::vault::{
+    next_window_boundary, UserAccountsDB, VaultActivityRecord, VaultDb, VaultNavSnapshotRecord,
+    VaultNavStrikeRecord, VaultPaperLedger, VaultShareRequestRecord, VaultStatementEntry,
+};
+
+const SHARE_EPS: f64 = 1e-9;
 
 #[derive(Debug, Clone, Default)]
 pub struct VaultShareState {
@@ -22,12 +46,70 @@ impl VaultShareState {
     }
 }
 
+/// Share-class accounting parameters.
+#[derive(Debug, Clone)]
+pub struct ShareAccountingConfig {
+    /// Fraction of gains above the high-water mark taken as performance fee
+    pub performance_fee_rate: f64,
+    /// Wallet credited with performance-fee shares
+    pub fee_wallet: String,
+    /// Books older than this are not used as marks (position valued at cost)
+    pub mark_max_stale_ms: u64,
+    /// Smallest subscription accepted
+    pub min_subscription_usdc: f64,
+}
+
+impl Default for ShareAccountingConfig {
+    fn default() -> Self {
+        Self {
+            performance_fee_rate: 0.20,
+            fee_wallet: "vault-manager".to_string(),
+            mark_max_stale_ms: 60_000,
+            min_subscription_usdc: 1.0,
+        }
+    }
+}
+
+impl ShareAccountingConfig {
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        Self {
+            performance_fee_rate: std::env::var("VAULT_PERFORMANCE_FEE_RATE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| (0.0..1.0).contains(v))
+                .unwrap_or(d.performance_fee_rate),
+            fee_wallet: std::env::var("VAULT_FEE_WALLET")
+                .ok()
+                .map(|v| v.trim().to_lowercase())
+                .filter(|v| !v.is_empty())
+                .unwrap_or(d.fee_wallet),
+            mark_max_stale_ms: std::env::var("VAULT_STRIKE_MARK_MAX_STALE_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(d.mark_max_stale_ms),
+            min_subscription_usdc: std::env::var("VAULT_MIN_SUBSCRIPTION_USDC")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .unwrap_or(d.min_subscription_usdc),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VaultStateResponse {
     pub cash_usdc: f64,
+    /// Cost-based NAV between strikes
     pub nav_usdc: f64,
     pub total_shares: f64,
     pub nav_per_share: f64,
+    /// NAV per share at which the last strike filled requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_strike_nav_per_share: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_strike_at: Option<i64>,
+    pub next_strike_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,29 +119,36 @@ pub struct VaultDepositRequest {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VaultDepositResponse {
+pub struct VaultWithdrawRequest {
     pub wallet_address: String,
-    pub amount_usdc: f64,
-    pub shares_minted: f64,
-    pub nav_per_share: f64,
-    pub total_shares: f64,
-    pub nav_usdc: f64,
+    pub shares: f64,
 }
 
+/// A queued subscription or redemption, with the strike it will fill at.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VaultWithdrawRequest {
-    pub wallet_address: String,
-    pub shares: f64,
+pub struct VaultShareRequestResponse {
+    #[serde(flatten)]
+    pub request: VaultShareRequestRecord,
+    pub strike_at: i64,
+    /// Last strike's NAV per share; the actual fill price is set at `strike_at`
+    pub indicative_nav_per_share: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VaultWithdrawResponse {
+pub struct VaultUserStatement {
     pub wallet_address: String,
-    pub shares_burned: f64,
-    pub amount_usdc: f64,
+    pub shares: f64,
     pub nav_per_share: f64,
-    pub total_shares: f64,
-    pub nav_usdc: f64,
+    pub value_usdc: f64,
+    pub subscribed_usdc: f64,
+    pub redeemed_usdc: f64,
+    pub performance_fees_usdc: f64,
+    /// value + redeemed - subscribed
+    pub pnl_usdc: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of_strike: Option<i64>,
+    pub pending: Vec<VaultShareRequestRecord>,
+    pub entries: Vec<VaultStatementEntry>,
 }
 
 #[derive(Clone)]
@@ -67,6 +156,11 @@ pub struct PooledVault {
     pub db: Arc<VaultDb>,
     pub ledger: Arc<Mutex<VaultPaperLedger>>,
     pub shares: Arc<Mutex<VaultShareState>>,
+    pub accounting: ShareAccountingConfig,
+    accounts: Option<Arc<UserAccountsDB>>,
+    last_strike: Arc<parking_lot::RwLock<Option<VaultNavStrikeRecord>>>,
+    /// Whether `last_strike` reflects the DB; strikes wait until it does.
+    last_strike_loaded: Arc<AtomicBool>,
 }
 
 impl PooledVault {
@@ -75,7 +169,39 @@ impl PooledVault {
         ledger: Arc<Mutex<VaultPaperLedger>>,
         shares: Arc<Mutex<VaultShareState>>,
     ) -> Self {
-        Self { db, ledger, shares }
+        Self {
+            db,
+            ledger,
+            shares,
+            accounting: ShareAccountingConfig::default(),
+            accounts: None,
+            last_strike: Arc::new(parking_lot::RwLock::new(None)),
+            last_strike_loaded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set share accounting parameters and the accounts DB that receives
+    /// per-user statement lines.
+    pub fn with_accounts(
+        mut self,
+        accounting: ShareAccountingConfig,
+        accounts: Option<Arc<UserAccountsDB>>,
+    ) -> Self {
+        self.accounting = accounting;
+        self.accounts = accounts;
+        self
+    }
+
+    /// Restore the last strike (high-water mark, indicative NAV per share).
+    pub async fn load_last_strike(&self) -> Result<()> {
+        let strike = self.db.load_last_nav_strike().await?;
+        *self.last_strike.write() = strike;
+        self.last_strike_loaded.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    pub fn last_strike(&self) -> Option<VaultNavStrikeRecord> {
+        self.last_strike.read().clone()
     }
 
     pub async fn state(&self) -> VaultStateResponse {
@@ -87,19 +213,31 @@ impl PooledVault {
         } else {
             1.0
         };
+        let last = self.last_strike();
         VaultStateResponse {
             cash_usdc: ledger.cash_usdc,
             nav_usdc,
             total_shares: shares.total_shares,
             nav_per_share,
+            last_strike_nav_per_share: last.as_ref().map(|s| s.nav_per_share),
+            last_strike_at: last.as_ref().map(|s| s.ts),
+            next_strike_at: next_window_boundary(Utc::now().timestamp()),
         }
     }
 
+    async fn indicative_nav_per_share(&self) -> f64 {
+        match self.last_strike() {
+            Some(s) => s.nav_per_share,
+            None => self.state().await.nav_per_share,
+        }
+    }
+
+    /// Queue a subscription for the next strike.
     pub async fn deposit(
         &self,
         wallet_address: &str,
         amount_usdc: f64,
-    ) -> Result<VaultDepositResponse> {
+    ) -> Result<VaultShareRequestResponse> {
         let wallet = wallet_address.trim().to_lowercase();
         if wallet.is_empty() {
             return Err(anyhow!("wallet_address required"));
@@ -107,83 +245,48 @@ impl PooledVault {
         if !(amount_usdc.is_finite() && amount_usdc > 0.0) {
             return Err(anyhow!("invalid amount"));
         }
+        if amount_usdc < self.accounting.min_subscription_usdc {
+            return Err(anyhow!(
+                "invalid amount: minimum subscription is {} USDC",
+                self.accounting.min_subscription_usdc
+            ));
+        }
 
+        let indicative = self.indicative_nav_per_share().await;
         let now = Utc::now().timestamp();
-        let mut ledger = self.ledger.lock().await;
-        let mut shares = self.shares.lock().await;
-
-        let nav_before = approximate_nav_usdc(&ledger);
-        let nav_per_share = if shares.total_shares > 0.0 {
-            (nav_before / shares.total_shares).max(1e-9)
-        } else {
-            1.0
-        };
-        let shares_minted = amount_usdc / nav_per_share;
-
-        ledger.cash_usdc += amount_usdc;
-        shares.total_shares += shares_minted;
-        *shares.user_shares.entry(wallet.clone()).or_insert(0.0) += shares_minted;
-
-        let nav_after = approximate_nav_usdc(&ledger);
-        let nav_per_share_after = if shares.total_shares > 0.0 {
-            nav_after / shares.total_shares
-        } else {
-            1.0
+        let request = VaultShareRequestRecord {
+            id: Uuid::new_v4().to_string(),
+            wallet_address: wallet,
+            kind: "SUBSCRIBE".to_string(),
+            amount_usdc: Some(amount_usdc),
+            shares: None,
+            status: "PENDING".to_string(),
+            requested_at: now,
+            strike_id: None,
+            struck_at: None,
+            nav_per_share: None,
+            filled_usdc: None,
+            filled_shares: None,
+            note: None,
         };
+        // Held so the request can't land half-way through a strike.
+        let _shares = self.shares.lock().await;
+        self.db.insert_share_request(&request).await?;
 
-        self.db
-            .upsert_state(ledger.cash_usdc, shares.total_shares, now)
-            .await?;
-        self.db
-            .set_user_shares(&wallet, shares.user_shares[&wallet], now)
-            .await?;
-
-        self.db
-            .insert_activity(&VaultActivityRecord {
-                id: Uuid::new_v4().to_string(),
-                ts: now,
-                kind: "DEPOSIT".to_string(),
-                wallet_address: Some(wallet.clone()),
-                amount_usdc: Some(amount_usdc),
-                shares: Some(shares_minted),
-                token_id: None,
-                market_slug: None,
-                outcome: None,
-                side: None,
-                price: None,
-                notional_usdc: None,
-                strategy: None,
-                decision_id: None,
-            })
-            .await?;
-        self.db
-            .insert_nav_snapshot(&VaultNavSnapshotRecord {
-                id: Uuid::new_v4().to_string(),
-                ts: now,
-                nav_usdc: nav_after,
-                cash_usdc: ledger.cash_usdc,
-                positions_value_usdc: (nav_after - ledger.cash_usdc).max(0.0),
-                total_shares: shares.total_shares,
-                nav_per_share: nav_per_share_after,
-                source: "deposit".to_string(),
-            })
-            .await?;
-
-        Ok(VaultDepositResponse {
-            wallet_address: wallet,
-            amount_usdc,
-            shares_minted,
-            nav_per_share: nav_per_share_after,
-            total_shares: shares.total_shares,
-            nav_usdc: nav_after,
+        Ok(VaultShareRequestResponse {
+            request,
+            strike_at: next_window_boundary(now),
+            indicative_nav_per_share: indicative,
         })
     }
 
+    /// Queue a redemption for the next strike. Shares already queued for
+    /// redemption can't be redeemed twice.
     pub async fn withdraw(
         &self,
         wallet_address: &str,
         shares_to_burn: f64,
-    ) -> Result<VaultWithdrawResponse> {
+    ) -> Result<VaultShareRequestResponse> {
         let wallet = wallet_address.trim().to_lowercase();
         if wallet.is_empty() {
             return Err(anyhow!("wallet_address required"));
@@ -192,91 +295,424 @@ impl PooledVault {
             return Err(anyhow!("invalid shares"));
         }
 
+        let indicative = self.indicative_nav_per_share().await;
         let now = Utc::now().timestamp();
-        let mut ledger = self.ledger.lock().await;
-        let mut shares = self.shares.lock().await;
-
-        let user_shares = shares.shares_of(&wallet);
-        if user_shares + 1e-9 < shares_to_burn {
+        let shares = self.shares.lock().await;
+        let queued: f64 = self
+            .db
+            .load_pending_share_requests()
+            .await?
+            .iter()
+            .filter(|r| r.kind == "REDEEM" && r.wallet_address == wallet)
+            .filter_map(|r| r.shares)
+            .sum();
+        if shares.shares_of(&wallet) - queued + SHARE_EPS < shares_to_burn {
             return Err(anyhow!("insufficient shares"));
         }
-        if shares.total_shares <= 0.0 {
-            return Err(anyhow!("vault has no shares"));
-        }
 
-        let nav_before = approximate_nav_usdc(&ledger);
-        let nav_per_share = (nav_before / shares.total_shares).max(1e-9);
-        let amount_usdc = shares_to_burn * nav_per_share;
+        let request = VaultShareRequestRecord {
+            id: Uuid::new_v4().to_string(),
+            wallet_address: wallet,
+            kind: "REDEEM".to_string(),
+            amount_usdc: None,
+            shares: Some(shares_to_burn),
+            status: "PENDING".to_string(),
+            requested_at: now,
+            strike_id: None,
+            struck_at: None,
+            nav_per_share: None,
+            filled_usdc: None,
+            filled_shares: None,
+            note: None,
+        };
+        self.db.insert_share_request(&request).await?;
 
-        if ledger.cash_usdc + 1e-9 < amount_usdc {
-            return Err(anyhow!(
-                "insufficient cash (positions liquidation not implemented)"
-            ));
+        Ok(VaultShareRequestResponse {
+            request,
+            strike_at: next_window_boundary(now),
+            indicative_nav_per_share: indicative,
+        })
+    }
+
+    /// Cancel a pending request. Returns false if it was not pending.
+    pub async fn cancel_request(&self, wallet_address: &str, request_id: &str) -> Result<bool> {
+        let _shares = self.shares.lock().await;
+        self.db
+            .cancel_share_request(request_id, wallet_address)
+            .await
+    }
+
+    /// Run a NAV strike at `now`: mark positions with `mark` (falling back to
+    /// cost), crystallize the performance fee, then fill subscriptions and
+    /// redemptions (FIFO) requested at or before `now`. A redemption the cash
+    /// balance can't cover stays queued, along with every redemption behind it.
+    ///
+    /// Refuses to strike until the last strike has been loaded: without it the
+    /// high-water mark is unknown and the fee would be charged again on gains
+    /// already charged.
+    pub async fn strike_at(
+        &self,
+        now: i64,
+        mark: &(dyn Fn(&str) -> Option<f64> + Sync),
+    ) -> Result<VaultNavStrikeRecord> {
+        if !self.last_strike_loaded.load(Ordering::Acquire) {
+            self.load_last_strike()
+                .await
+                .context("last NAV strike not loaded; refusing to strike")?;
         }
 
-        ledger.cash_usdc -= amount_usdc;
-        shares.total_shares -= shares_to_burn;
-        let new_user = (user_shares - shares_to_burn).max(0.0);
-        if new_user <= 0.0 {
-            shares.user_shares.remove(&wallet);
-        } else {
-            shares.user_shares.insert(wallet.clone(), new_user);
+        let mut ledger = self.ledger.lock().await;
+        let mut shares = self.shares.lock().await;
+
+        let mut positions_value = 0.0;
+        let mut marked = 0i64;
+        let mut unmarked = 0i64;
+        for p in ledger.positions.values() {
+            if p.shares <= 0.0 {
+                continue;
+            }
+            match mark(&p.token_id).filter(|m| m.is_finite() && (0.0..=1.0).contains(m)) {
+                Some(m) => {
+                    positions_value += p.shares * m;
+                    marked += 1;
+                }
+                None => {
+                    positions_value += p.shares * p.avg_price;
+                    unmarked += 1;
+                }
+            }
         }
 
-        let nav_after = approximate_nav_usdc(&ledger);
-        let nav_per_share_after = if shares.total_shares > 0.0 {
-            nav_after / shares.total_shares
+        let strike_id = Uuid::new_v4().to_string();
+        let mut cash = ledger.cash_usdc;
+        let mut next = shares.clone();
+        let mut statements = Vec::new();
+        let mut touched: Vec<String> = Vec::new();
+        let nav = (cash + positions_value).max(0.0);
+        // The first strike seeds the high-water mark at the current NAV per
+        // share, so gains made before the fee existed are never billed.
+        let prev_hwm = match self.last_strike() {
+            Some(s) => s.high_water_mark,
+            None if next.total_shares > SHARE_EPS => nav / next.total_shares,
+            None => 1.0,
+        };
+
+        // Performance fee: a share of the gain above the high-water mark,
+        // paid by minting shares so NAV stays invested.
+        let mut fee_usdc = 0.0;
+        let mut fee_shares = 0.0;
+        let nav_per_share = if next.total_shares > SHARE_EPS {
+            let gross = nav / next.total_shares;
+            if gross > prev_hwm && self.accounting.performance_fee_rate > 0.0 {
+                fee_usdc =
+                    self.accounting.performance_fee_rate * (gross - prev_hwm) * next.total_shares;
+                fee_shares = fee_usdc * next.total_shares / (nav - fee_usdc);
+
+                let fee_wallet = self.accounting.fee_wallet.clone();
+                let total_before = next.total_shares;
+                for (wallet, held) in &next.user_shares {
+                    let charged = fee_usdc * held / total_before;
+                    if charged < SHARE_EPS || *wallet == fee_wallet {
+                        continue;
+                    }
+                    statements.push((wallet.clone(), "PERFORMANCE_FEE", charged, 0.0, None));
+                }
+                next.total_shares += fee_shares;
+                *next.user_shares.entry(fee_wallet.clone()).or_insert(0.0) += fee_shares;
+                statements.push((fee_wallet.clone(), "PERFORMANCE_FEE", 0.0, fee_shares, None));
+                touched.push(fee_wallet);
+            }
+            nav / next.total_shares
         } else {
             1.0
         };
+        let high_water_mark = prev_hwm.max(nav_per_share);
 
+        let pending: Vec<VaultShareRequestRecord> = self
+            .db
+            .load_pending_share_requests()
+            .await?
+            .into_iter()
+            .filter(|r| r.requested_at <= now)
+            .collect();
+        let mut settled = Vec::new();
+        let mut subscriptions_usdc = 0.0;
+        let mut redemptions_usdc = 0.0;
+        let mut shares_minted = 0.0;
+        let mut shares_burned = 0.0;
+
+        for mut req in pending.iter().filter(|r| r.kind == "SUBSCRIBE").cloned() {
+            let amount = req.amount_usdc.unwrap_or(0.0);
+            if !(amount.is_finite()
+                && amount > 0.0
+                && amount >= self.accounting.min_subscription_usdc)
+            {
+                req.status = "REJECTED".to_string();
+                req.note = Some("invalid amount".to_string());
+            } else {
+                let minted = amount / nav_per_share;
+                cash += amount;
+                next.total_shares += minted;
+                *next
+                    .user_shares
+                    .entry(req.wallet_address.clone())
+                    .or_insert(0.0) += minted;
+                subscriptions_usdc += amount;
+                shares_minted += minted;
+                fill(&mut req, nav_per_share, amount, minted);
+                statements.push((
+                    req.wallet_address.clone(),
+                    "SUBSCRIBE",
+                    amount,
+                    minted,
+                    Some(req.id.clone()),
+                ));
+                touched.push(req.wallet_address.clone());
+            }
+            req.strike_id = Some(strike_id.clone());
+            req.struck_at = Some(now);
+            settled.push(req);
+        }
+
+        for mut req in pending.iter().filter(|r| r.kind == "REDEEM").cloned() {
+            let burn = req.shares.unwrap_or(0.0);
+            let held = next.shares_of(&req.wallet_address);
+            if !(burn.is_finite() && burn > 0.0) || held + SHARE_EPS < burn {
+                req.status = "REJECTED".to_string();
+                req.note = Some("insufficient shares".to_string());
+                req.strike_id = Some(strike_id.clone());
+                req.struck_at = Some(now);
+                settled.push(req);
+                continue;
+            }
+            let burn = burn.min(held);
+            let amount = burn * nav_per_share;
+            if cash + SHARE_EPS < amount {
+                // FIFO: this and everything behind it wait for cash.
+                break;
+            }
+            cash = (cash - amount).max(0.0);
+            next.total_shares = (next.total_shares - burn).max(0.0);
+            let left = held - burn;
+            if left <= SHARE_EPS {
+                next.user_shares.remove(&req.wallet_address);
+            } else {
+                next.user_shares.insert(req.wallet_address.clone(), left);
+            }
+            redemptions_usdc += amount;
+            shares_burned += burn;
+            fill(&mut req, nav_per_share, amount, burn);
+            req.strike_id = Some(strike_id.clone());
+            req.struck_at = Some(now);
+            statements.push((
+                req.wallet_address.clone(),
+                "REDEEM",
+                amount,
+                -burn,
+                Some(req.id.clone()),
+            ));
+            touched.push(req.wallet_address.clone());
+            settled.push(req);
+        }
+
+        touched.sort();
+        touched.dedup();
+        let user_shares: Vec<(String, f64)> = touched
+            .iter()
+            .map(|w| (w.clone(), next.shares_of(w)))
+            .collect();
+
+        let nav_after = (cash + positions_value).max(0.0);
+        let strike = VaultNavStrikeRecord {
+            id: strike_id.clone(),
+            ts: now,
+            nav_usdc: nav_after,
+            cash_usdc: cash,
+            positions_value_usdc: positions_value,
+            marked_positions: marked,
+            unmarked_positions: unmarked,
+            total_shares: next.total_shares,
+            nav_per_share,
+            high_water_mark,
+            performance_fee_usdc: fee_usdc,
+            fee_shares,
+            subscriptions_usdc,
+            redemptions_usdc,
+            shares_minted,
+            shares_burned,
+        };
+        let snapshot = VaultNavSnapshotRecord {
+            id: Uuid::new_v4().to_string(),
+            ts: now,
+            nav_usdc: nav_after,
+            cash_usdc: cash,
+            positions_value_usdc: positions_value,
+            total_shares: next.total_shares,
+            nav_per_share: if next.total_shares > SHARE_EPS {
+                nav_after / next.total_shares
+            } else {
+                1.0
+            },
+            source: "strike".to_string(),
+        };
         self.db
-            .upsert_state(ledger.cash_usdc, shares.total_shares, now)
-            .await?;
-        self.db
-            .set_user_shares(&wallet, shares.shares_of(&wallet), now)
+            .commit_nav_strike(&strike, &settled, &user_shares, &snapshot)
             .await?;
 
-        self.db
-            .insert_activity(&VaultActivityRecord {
+        ledger.cash_usdc = cash;
+        *shares = next;
+        *self.last_strike.write() = Some(strike.clone());
+
+        let statements: Vec<VaultStatementEntry> = statements
+            .into_iter()
+            .map(
+                |(wallet, kind, amount_usdc, delta, request_id)| VaultStatementEntry {
+                    shares_after: shares.shares_of(&wallet),
+                    wallet_address: wallet,
+                    ts: now,
+                    kind: kind.to_string(),
+                    amount_usdc,
+                    shares: delta,
+                    nav_per_share,
+                    request_id,
+                    strike_id: strike_id.clone(),
+                },
+            )
+            .collect();
+        drop(shares);
+        drop(ledger);
+
+        for e in statements.iter().filter(|e| e.kind != "PERFORMANCE_FEE") {
+            let kind = if e.kind == "SUBSCRIBE" {
+                "DEPOSIT"
+            } else {
+                "WITHDRAW"
+            };
+            let rec = VaultActivityRecord {
                 id: Uuid::new_v4().to_string(),
                 ts: now,
-                kind: "WITHDRAW".to_string(),
-                wallet_address: Some(wallet.clone()),
-                amount_usdc: Some(amount_usdc),
-                shares: Some(shares_to_burn),
+                kind: kind.to_string(),
+                wallet_address: Some(e.wallet_address.clone()),
+                amount_usdc: Some(e.amount_usdc),
+                shares: Some(e.shares.abs()),
                 token_id: None,
                 market_slug: None,
                 outcome: None,
                 side: None,
-                price: None,
+                price: Some(nav_per_share),
                 notional_usdc: None,
                 strategy: None,
                 decision_id: None,
-            })
-            .await?;
-        self.db
-            .insert_nav_snapshot(&VaultNavSnapshotRecord {
-                id: Uuid::new_v4().to_string(),
-                ts: now,
-                nav_usdc: nav_after,
-                cash_usdc: ledger.cash_usdc,
-                positions_value_usdc: (nav_after - ledger.cash_usdc).max(0.0),
-                total_shares: shares.total_shares,
-                nav_per_share: nav_per_share_after,
-                source: "withdraw".to_string(),
-            })
-            .await?;
+                config_version: None,
+            };
+            if let Err(e) = self.db.insert_activity(&rec).await {
+                warn!(error = %e, "failed to record vault strike activity");
+            }
+        }
+        if let Some(accounts) = &self.accounts {
+            if !statements.is_empty() {
+                if let Err(e) = accounts.record_statement_entries(&statements).await {
+                    warn!(error = %e, strike_id = %strike.id, "failed to record vault statements");
+                }
+            }
+        }
+
+        Ok(strike)
+    }
 
-        Ok(VaultWithdrawResponse {
+    /// A wallet's holdings, lifetime flows and statement lines.
+    pub async fn statement(
+        &self,
+        wallet_address: &str,
+        limit: usize,
+    ) -> Result<VaultUserStatement> {
+        let wallet = wallet_address.trim().to_lowercase();
+        if wallet.is_empty() {
+            return Err(anyhow!("wallet_address required"));
+        }
+        let shares = self.shares.lock().await.shares_of(&wallet);
+        let last = self.last_strike();
+        let nav_per_share = match &last {
+            Some(s) => s.nav_per_share,
+            None => self.state().await.nav_per_share,
+        };
+        let pending = self
+            .db
+            .list_share_requests(&wallet, limit)
+            .await?
+            .into_iter()
+            .filter(|r| r.status == "PENDING")
+            .collect();
+        let (totals, entries) = match &self.accounts {
+            Some(accounts) => (
+                accounts.statement_totals(&wallet).await?,
+                accounts.statement_entries(&wallet, limit).await?,
+            ),
+            None => (Default::default(), Vec::new()),
+        };
+        let value_usdc = shares * nav_per_share;
+
+        Ok(VaultUserStatement {
             wallet_address: wallet,
-            shares_burned: shares_to_burn,
-            amount_usdc,
-            nav_per_share: nav_per_share_after,
-            total_shares: shares.total_shares,
-            nav_usdc: nav_after,
+            shares,
+            nav_per_share,
+            value_usdc,
+            subscribed_usdc: totals.subscribed_usdc,
+            redeemed_usdc: totals.redeemed_usdc,
+            performance_fees_usdc: totals.performance_fees_usdc,
+            pnl_usdc: value_usdc + totals.redeemed_usdc - totals.subscribed_usdc,
+            as_of_strike: last.map(|s| s.ts),
+            pending,
+            entries,
         })
     }
+
+    /// Strike at every window boundary, marking to `book_cache` mids when
+    /// available.
+    pub fn spawn_strike_loop(self: Arc<Self>, book_cache: Option<Arc<HftBookCache>>) {
+        tokio::spawn(async move {
+            loop {
+                let now = Utc::now().timestamp();
+                let boundary = next_window_boundary(now);
+
+                // Make sure held tokens have live books by strike time.
+                if let Some(cache) = &book_cache {
+                    for token in self.ledger.lock().await.positions.keys() {
+                        cache.request_subscribe(token);
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs((boundary - now).max(1) as u64)).await;
+
+                let max_stale_ms = self.accounting.mark_max_stale_ms;
+                let mark = |token: &str| {
+                    book_cache
+                        .as_ref()
+                        .and_then(|c| c.get_book_if_fresh(token, max_stale_ms))
+                        .and_then(|b| b.mid_price())
+                };
+                match self.strike_at(boundary, &mark).await {
+                    Ok(s) => info!(
+                        nav_per_share = s.nav_per_share,
+                        nav_usdc = s.nav_usdc,
+                        fee_usdc = s.performance_fee_usdc,
+                        subscriptions = s.subscriptions_usdc,
+                        redemptions = s.redemptions_usdc,
+                        unmarked = s.unmarked_positions,
+                        "vault NAV strike"
+                    ),
+                    Err(e) => warn!(error = %e, "vault NAV strike failed"),
+                }
+            }
+        });
+    }
+}
+
+fn fill(req: &mut VaultShareRequestRecord, nav_per_share: f64, usdc: f64, shares: f64) {
+    req.status = "FILLED".to_string();
+    req.nav_per_share = Some(nav_per_share);
+    req.filled_usdc = Some(usdc);
+    req.filled_shares = Some(shares);
+    req.note = None;
 }
 
 pub fn approximate_nav_usdc(ledger: &VaultPaperLedger) -> f64 {
@@ -287,3 +723,171 @@ pub fn approximate_nav_usdc(ledger: &VaultPaperLedger) -> f64 {
         .sum();
     (ledger.cash_usdc + positions_value).max(0.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::VaultPaperPosition;
+
+    fn vault(cash: f64) -> (tempfile::TempDir, PooledVault) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(VaultDb::new(dir.path().join("vault.db").to_str().unwrap()).unwrap());
+        let accounts =
+            Arc::new(UserAccountsDB::new(dir.path().join("users.db").to_str().unwrap()).unwrap());
+        let ledger = VaultPaperLedger {
+            cash_usdc: cash,
+            ..Default::default()
+        };
+        let v = PooledVault::new(
+            db,
+            Arc::new(Mutex::new(ledger)),
+            Arc::new(Mutex::new(VaultShareState::default())),
+        )
+        .with_accounts(ShareAccountingConfig::default(), Some(accounts));
+        (dir, v)
+    }
+
+    fn no_marks(_: &str) -> Option<f64> {
+        None
+    }
+
+    #[tokio::test]
+    async fn test_deposits_queue_until_strike_and_do_not_dilute_holders() {
+        let (_dir, v) = vault(0.0);
+        v.deposit("0xA", 100.0).await.unwrap();
+        assert_eq!(v.shares.lock().await.total_shares, 0.0);
+
+        let now = Utc::now().timestamp() + 1;
+        v.strike_at(now, &no_marks).await.unwrap();
+        assert!((v.shares.lock().await.shares_of("0xa") - 100.0).abs() < 1e-9);
+
+        // A's holding is untouched by B's subscription.
+        v.deposit("0xB", 50.0).await.unwrap();
+        let s = v.strike_at(now + 1, &no_marks).await.unwrap();
+        let shares = v.shares.lock().await;
+        assert!((shares.shares_of("0xa") - 100.0).abs() < 1e-9);
+        assert!((shares.shares_of("0xb") - 50.0).abs() < 1e-9);
+        assert!((s.nav_per_share - 1.0).abs() < 1e-12);
+        assert!((s.nav_usdc - 150.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_strike_marks_to_mids_and_charges_fee_above_hwm() {
+        let (_dir, v) = vault(0.0);
+        v.deposit("0xA", 100.0).await.unwrap();
+        let now = Utc::now().timestamp() + 1;
+        v.strike_at(now, &no_marks).await.unwrap();
+
+        // Spend 50 on 100 shares at 0.50; the book now marks them at 0.70.
+        {
+            let mut ledger = v.ledger.lock().await;
+            ledger.cash_usdc = 50.0;
+            ledger.positions.insert(
+                "tok".to_string(),
+                VaultPaperPosition {
+                    token_id: "tok".to_string(),
+                    outcome: "Up".to_string(),
+                    shares: 100.0,
+                    cost_usdc: 50.0,
+                    avg_price: 0.5,
+                },
+            );
+        }
+        let s = v
+            .strike_at(now + 1, &|t: &str| (t == "tok").then_some(0.7))
+            .await
+            .unwrap();
+        assert_eq!(s.marked_positions, 1);
+        // NAV 120, gross 1.20/share, fee 20% of 0.20 * 100 = 4 USDC.
+        assert!((s.performance_fee_usdc - 4.0).abs() < 1e-9);
+        assert!((s.nav_per_share - 1.16).abs() < 1e-9);
+        assert!((s.high_water_mark - 1.16).abs() < 1e-9);
+        let fee_value = v.shares.lock().await.shares_of("vault-manager") * s.nav_per_share;
+        assert!((fee_value - 4.0).abs() < 1e-9);
+
+        // No new high: no fee.
+        let s = v
+            .strike_at(now + 2, &|t: &str| (t == "tok").then_some(0.7))
+            .await
+            .unwrap();
+        assert_eq!(s.performance_fee_usdc, 0.0);
+
+        let st = v.statement("0xA", 50).await.unwrap();
+        assert!((st.performance_fees_usdc - 4.0).abs() < 1e-9);
+        assert!((st.subscribed_usdc - 100.0).abs() < 1e-9);
+        assert!((st.pnl_usdc - 16.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_first_strike_seeds_hwm_from_current_nav() {
+        // Shares issued before strikes (and the fee) existed, now worth 1.50
+        let (_dir, v) = vault(150.0);
+        {
+            let mut shares = v.shares.lock().await;
+            shares.total_shares = 100.0;
+            shares.user_shares.insert("0xa".to_string(), 100.0);
+        }
+        let now = Utc::now().timestamp() + 1;
+        let s = v.strike_at(now, &no_marks).await.unwrap();
+        assert_eq!(s.performance_fee_usdc, 0.0);
+        assert!((s.high_water_mark - 1.5).abs() < 1e-9);
+
+        // Only the gain above 1.50 is billed: 20% of 0.30 * 100 = 6 USDC.
+        v.ledger.lock().await.cash_usdc = 180.0;
+        let s = v.strike_at(now + 1, &no_marks).await.unwrap();
+        assert!((s.performance_fee_usdc - 6.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_redemptions_wait_for_cash_in_fifo_order() {
+        let (_dir, v) = vault(0.0);
+        v.deposit("0xA", 60.0).await.unwrap();
+        v.deposit("0xB", 40.0).await.unwrap();
+        let now = Utc::now().timestamp() + 1;
+        v.strike_at(now, &no_marks).await.unwrap();
+
+        // Only 30 USDC of cash left; the rest is in a position at cost.
+        {
+            let mut ledger = v.ledger.lock().await;
+            ledger.cash_usdc = 30.0;
+            ledger.positions.insert(
+                "tok".to_string(),
+                VaultPaperPosition {
+                    token_id: "tok".to_string(),
+                    outcome: "Up".to_string(),
+                    shares: 140.0,
+                    cost_usdc: 70.0,
+                    avg_price: 0.5,
+                },
+            );
+        }
+        v.withdraw("0xA", 50.0).await.unwrap();
+        v.withdraw("0xB", 10.0).await.unwrap();
+        assert!(v.withdraw("0xA", 20.0).await.is_err());
+
+        let s = v.strike_at(now + 1, &no_marks).await.unwrap();
+        assert_eq!(s.shares_burned, 0.0);
+        assert_eq!(v.db.load_pending_share_requests().await.unwrap().len(), 2);
+
+        v.ledger.lock().await.cash_usdc = 100.0;
+        let s = v.strike_at(now + 2, &no_marks).await.unwrap();
+        assert!((s.shares_burned - 60.0).abs() < 1e-9);
+        assert!(v.db.load_pending_share_requests().await.unwrap().is_empty());
+        assert!((v.shares.lock().await.shares_of("0xa") - 10.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_requests_are_not_filled() {
+        let (_dir, v) = vault(0.0);
+        let r = v.deposit("0xA", 25.0).await.unwrap();
+        assert!(v.cancel_request("0xA", &r.request.id).await.unwrap());
+        assert!(!v.cancel_request("0xA", &r.request.id).await.unwrap());
+
+        let s = v
+            .strike_at(Utc::now().timestamp() + 1, &no_marks)
+            .await
+            .unwrap();
+        assert_eq!(s.shares_minted, 0.0);
+        assert_eq!(v.shares.lock().await.total_shares, 0.0);
+    }
+}
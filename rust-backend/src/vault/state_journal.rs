@@ -0,0 +1,821 @@
+//! Vault State Journal & Restart Recovery
+//!
+//! `VaultEngine` used to keep inventory only in the in-memory
+//! `VaultPaperLedger`, so a deploy mid-window lost track of open 15M
+//! positions. Every order is now journaled to `VaultDb` before it is sent
+//! (`PENDING`), and its ack and fills are written together with the resulting
+//! position and cash in one SQLite transaction.
+//!
+//! On startup, before any trading loop runs:
+//! 1. `restore_ledger` rebuilds inventory from `vault_positions`.
+//! 2. `recover_vault_state` asks the execution adapter about every order the
+//!    journal left pending or open, applying fills missed while down.
+//! 3. With a `PolymarketClobAdapter`, inventory and cash are compared with the
+//!    venue's positions and balance and, if configured, the venue is adopted.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::vault::{
+    ExecutionAdapter, OrderAck, OrderRequest, OrderSide, OrderStatus, PolymarketClobAdapter,
+    PolymarketPosition, PooledVault, VaultDb, VaultOrderRecord, VaultPaperLedger,
+    VaultPaperPosition, VaultPositionRecord,
+};
+
+/// Share quantities below this are treated as zero
+const SIZE_EPSILON: f64 = 1e-9;
+
+pub fn side_label(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "BUY",
+        OrderSide::Sell => "SELL",
+    }
+}
+
+pub fn status_label(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::Open => "OPEN",
+        OrderStatus::PartiallyFilled => "PARTIALLY_FILLED",
+        OrderStatus::Filled => "FILLED",
+        OrderStatus::Cancelled => "CANCELLED",
+        OrderStatus::Rejected => "REJECTED",
+    }
+}
+
+fn position_record(
+    ledger: &VaultPaperLedger,
+    token_id: &str,
+    outcome: &str,
+    now: i64,
+) -> VaultPositionRecord {
+    match ledger.positions.get(token_id) {
+        Some(p) => VaultPositionRecord {
+            token_id: p.token_id.clone(),
+            outcome: p.outcome.clone(),
+            shares: p.shares,
+            cost_usdc: p.cost_usdc,
+            avg_price: p.avg_price,
+            updated_at: now,
+        },
+        // Closed out: a zero-share record deletes the row
+        None => VaultPositionRecord {
+            token_id: token_id.to_string(),
+            outcome: outcome.to_string(),
+            shares: 0.0,
+            cost_usdc: 0.0,
+            avg_price: 0.0,
+            updated_at: now,
+        },
+    }
+}
+
+/// Apply a fill to the ledger by side. Returns the shares bought or sold.
+fn apply_fill(
+    ledger: &mut VaultPaperLedger,
+    side: OrderSide,
+    token_id: &str,
+    outcome: &str,
+    price: f64,
+    notional: f64,
+    fees: f64,
+) -> f64 {
+    match side {
+        OrderSide::Buy => ledger.apply_buy(token_id, outcome, price, notional, fees),
+        OrderSide::Sell => ledger.apply_sell(token_id, price, notional, fees),
+    }
+}
+
+/// Journals orders and fills for the vault ledger.
+#[derive(Clone)]
+pub struct VaultJournal {
+    db: Arc<VaultDb>,
+}
+
+impl VaultJournal {
+    pub fn new(db: Arc<VaultDb>) -> Self {
+        Self { db }
+    }
+
+    /// Write-ahead record of an order about to be sent. Must succeed before
+    /// the order reaches the venue. `config_version` attributes the order to
+    /// the strategy parameters that produced it.
+    pub async fn record_submit(
+        &self,
+        req: &OrderRequest,
+        strategy: &str,
+        config_version: Option<i64>,
+    ) -> Result<VaultOrderRecord> {
+        let now = Utc::now().timestamp();
+        let order = VaultOrderRecord {
+            client_order_id: req.client_order_id.clone(),
+            order_id: None,
+            token_id: req.token_id.clone(),
+            market_slug: req.market_slug.clone(),
+            outcome: req.outcome.clone(),
+            side: side_label(req.side).to_string(),
+            price: req.price,
+            size: req.notional_usdc / req.price.max(1e-9),
+            filled_size: 0.0,
+            filled_notional_usdc: 0.0,
+            fees_usdc: 0.0,
+            status: "PENDING".to_string(),
+            strategy: Some(strategy.to_string()),
+            config_version,
+            created_at: now,
+            updated_at: now,
+        };
+        self.db
+            .journal_order(&order, "SUBMIT", &serde_json::to_value(req)?)
+            .await?;
+        Ok(order)
+    }
+
+    pub async fn record_reject(&self, order: &mut VaultOrderRecord, reason: &str) -> Result<()> {
+        order.status = "REJECTED".to_string();
+        order.updated_at = Utc::now().timestamp();
+        self.db
+            .journal_order(order, "REJECT", &json!({ "reason": reason }))
+            .await?;
+        Ok(())
+    }
+
+    /// Journal an ack and its immediate fill. `ledger` must already include
+    /// the fill; the order, the token's position and cash are persisted
+    /// together.
+    pub async fn record_execution(
+        &self,
+        order: &mut VaultOrderRecord,
+        ack: &OrderAck,
+        ledger: &VaultPaperLedger,
+        total_shares: f64,
+    ) -> Result<()> {
+        let filled_size = if ack.filled_price > 0.0 {
+            ack.filled_notional_usdc / ack.filled_price
+        } else {
+            0.0
+        };
+        order.order_id = Some(ack.order_id.clone());
+        order.filled_size = filled_size;
+        order.filled_notional_usdc = ack.filled_notional_usdc;
+        order.fees_usdc = ack.fees_usdc;
+        order.status = if ack.resting_size > SIZE_EPSILON {
+            if filled_size > SIZE_EPSILON {
+                "PARTIALLY_FILLED"
+            } else {
+                "OPEN"
+            }
+        } else if filled_size > SIZE_EPSILON {
+            "FILLED"
+        } else {
+            "CANCELLED"
+        }
+        .to_string();
+        order.updated_at = ack.filled_at;
+
+        let position = position_record(
+            ledger,
+            &order.token_id,
+            order.outcome.as_deref().unwrap_or(""),
+            ack.filled_at,
+        );
+        self.db
+            .journal_ledger(
+                ack.filled_at,
+                "FILL",
+                Some(order),
+                &[position],
+                ledger.cash_usdc,
+                total_shares,
+                &serde_json::to_value(ack)?,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Rebuild the ledger from the journaled positions and `cash_usdc`.
+pub async fn restore_ledger(db: &VaultDb, cash_usdc: f64) -> Result<VaultPaperLedger> {
+    let mut ledger = VaultPaperLedger {
+        cash_usdc,
+        ..Default::default()
+    };
+    for p in db.load_positions().await? {
+        ledger.positions.insert(
+            p.token_id.clone(),
+            VaultPaperPosition {
+                token_id: p.token_id,
+                outcome: p.outcome,
+                shares: p.shares,
+                cost_usdc: p.cost_usdc,
+                avg_price: p.avg_price,
+            },
+        );
+    }
+    Ok(ledger)
+}
+
+// ============================================================================
+// Recovery
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct RecoveryConfig {
+    /// Compare inventory and cash with venue positions/balance (live only)
+    pub reconcile_venue: bool,
+    /// Overwrite journaled inventory and cash with the venue's on mismatch.
+    /// Off by default (a mismatch then blocks live trading); opt in with
+    /// `VAULT_RECOVERY_ADOPT_VENUE=1`
+    pub adopt_venue: bool,
+    /// Share difference tolerated per token
+    pub size_tolerance: f64,
+    pub cash_tolerance_usdc: f64,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            reconcile_venue: true,
+            adopt_venue: false,
+            size_tolerance: 0.01,
+            cash_tolerance_usdc: 0.01,
+        }
+    }
+}
+
+impl RecoveryConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("VAULT_RECOVERY_RECONCILE") {
+            config.reconcile_venue = matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON");
+        }
+        if let Ok(v) = std::env::var("VAULT_RECOVERY_ADOPT_VENUE") {
+            config.adopt_venue = matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON");
+        }
+        if let Ok(v) = std::env::var("VAULT_RECOVERY_SIZE_TOLERANCE") {
+            if let Ok(x) = v.parse() {
+                config.size_tolerance = x;
+            }
+        }
+        if let Ok(v) = std::env::var("VAULT_RECOVERY_CASH_TOLERANCE_USDC") {
+            if let Ok(x) = v.parse() {
+                config.cash_tolerance_usdc = x;
+            }
+        }
+
+        config
+    }
+}
+
+/// Journal vs venue inventory for one token.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionDiscrepancy {
+    pub token_id: String,
+    pub outcome: String,
+    pub journal_shares: f64,
+    pub venue_shares: f64,
+    /// Venue average entry price (0 when unknown)
+    pub venue_avg_price: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecoveryReport {
+    pub positions_restored: usize,
+    /// Orders still resting at the venue
+    pub orders_resumed: Vec<String>,
+    /// Orders found filled or cancelled while we were down
+    pub orders_closed: Vec<String>,
+    /// Orders the adapter could not account for (client order ids)
+    pub orders_unresolved: Vec<String>,
+    pub missed_fills_applied: usize,
+    pub venue_checked: bool,
+    pub position_discrepancies: Vec<PositionDiscrepancy>,
+    pub journal_cash_usdc: f64,
+    pub venue_cash_usdc: Option<f64>,
+    pub adopted_venue: bool,
+}
+
+impl RecoveryReport {
+    pub fn cash_mismatch(&self, tolerance: f64) -> bool {
+        self.venue_cash_usdc
+            .is_some_and(|v| (v - self.journal_cash_usdc).abs() > tolerance)
+    }
+
+    /// Inventory is known to match the venue (or was replaced by it).
+    pub fn safe_to_trade(&self, cfg: &RecoveryConfig) -> bool {
+        self.venue_checked
+            && (self.adopted_venue
+                || (self.position_discrepancies.is_empty()
+                    && !self.cash_mismatch(cfg.cash_tolerance_usdc)))
+    }
+}
+
+/// Tokens whose journaled shares differ from the venue's by more than
+/// `tolerance`, including tokens only one side holds.
+pub fn diff_positions(
+    ledger: &VaultPaperLedger,
+    venue: &[PolymarketPosition],
+    tolerance: f64,
+) -> Vec<PositionDiscrepancy> {
+    let mut venue_by_token: HashMap<&str, &PolymarketPosition> = HashMap::new();
+    for p in venue.iter().filter(|p| !p.token_id.is_empty()) {
+        venue_by_token.insert(p.token_id.as_str(), p);
+    }
+
+    let mut out = Vec::new();
+    for (token_id, pos) in &ledger.positions {
+        let venue_pos = venue_by_token.remove(token_id.as_str());
+        let venue_shares = venue_pos.map(|p| p.size).unwrap_or(0.0);
+        if (venue_shares - pos.shares).abs() > tolerance {
+            out.push(PositionDiscrepancy {
+                token_id: token_id.clone(),
+                outcome: pos.outcome.clone(),
+                journal_shares: pos.shares,
+                venue_shares,
+                venue_avg_price: venue_pos.map(|p| p.avg_price).unwrap_or(0.0),
+            });
+        }
+    }
+    for (token_id, p) in venue_by_token {
+        if p.size > tolerance {
+            out.push(PositionDiscrepancy {
+                token_id: token_id.to_string(),
+                outcome: p.outcome.clone().unwrap_or_default(),
+                journal_shares: 0.0,
+                venue_shares: p.size,
+                venue_avg_price: p.avg_price,
+            });
+        }
+    }
+    out.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+    out
+}
+
+/// Replace the ledger's inventory for each discrepant token with the venue's.
+/// Cost basis uses the venue's average price, falling back to the journaled one.
+pub fn adopt_venue_positions(ledger: &mut VaultPaperLedger, discrepancies: &[PositionDiscrepancy]) {
+    for d in discrepancies {
+        if d.venue_shares <= SIZE_EPSILON {
+            ledger.positions.remove(&d.token_id);
+            continue;
+        }
+        let prior_avg = ledger
+            .positions
+            .get(&d.token_id)
+            .map(|p| p.avg_price)
+            .unwrap_or(0.0);
+        let avg_price = if d.venue_avg_price > 0.0 {
+            d.venue_avg_price
+        } else {
+            prior_avg
+        };
+        ledger.positions.insert(
+            d.token_id.clone(),
+            VaultPaperPosition {
+                token_id: d.token_id.clone(),
+                outcome: d.outcome.clone(),
+                shares: d.venue_shares,
+                cost_usdc: d.venue_shares * avg_price,
+                avg_price,
+            },
+        );
+    }
+}
+
+/// Bring journaled orders up to date with the adapter, applying fills that
+/// happened while the engine was down.
+async fn resolve_orders(
+    vault: &PooledVault,
+    exec: &dyn ExecutionAdapter,
+    report: &mut RecoveryReport,
+) -> Result<()> {
+    for mut order in vault.db.load_unresolved_orders().await? {
+        let now = Utc::now().timestamp();
+        let venue = match order.order_id.as_deref() {
+            Some(order_id) => exec.order_status(order_id).await.ok(),
+            // Crashed between journaling and the venue ack
+            None => None,
+        };
+        let Some(venue) = venue else {
+            warn!(
+                client_order_id = %order.client_order_id,
+                status = %order.status,
+                "journaled order unknown to the execution adapter"
+            );
+            order.status = "UNRESOLVED".to_string();
+            order.updated_at = now;
+            vault
+                .db
+                .journal_order(&order, "RECOVERY_UNRESOLVED", &json!({}))
+                .await?;
+            report.orders_unresolved.push(order.client_order_id);
+            continue;
+        };
+
+        let missed = venue.filled_size - order.filled_size;
+        order.status = status_label(venue.status).to_string();
+        order.updated_at = now;
+        if missed > SIZE_EPSILON {
+            let venue_notional = venue.avg_fill_price * venue.filled_size;
+            let notional =
+                (venue_notional - order.filled_notional_usdc).max(missed * venue.price.max(0.0));
+            let price = notional / missed;
+            let side = if order.side == "SELL" {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            };
+            order.filled_size = venue.filled_size;
+            order.filled_notional_usdc += notional;
+
+            let mut ledger = vault.ledger.lock().await;
+            let total_shares = vault.shares.lock().await.total_shares;
+            let outcome = order.outcome.clone().unwrap_or_default();
+            // Fees are not reported for missed fills
+            apply_fill(
+                &mut ledger,
+                side,
+                &order.token_id,
+                &outcome,
+                price,
+                notional,
+                0.0,
+            );
+            let position = position_record(&ledger, &order.token_id, &outcome, now);
+            vault
+                .db
+                .journal_ledger(
+                    now,
+                    "RECOVERY_FILL",
+                    Some(&order),
+                    &[position],
+                    ledger.cash_usdc,
+                    total_shares,
+                    &json!({ "size": missed, "price": price, "notional_usdc": notional }),
+                )
+                .await?;
+            report.missed_fills_applied += 1;
+        } else {
+            vault
+                .db
+                .journal_order(&order, "RECOVERY_STATUS", &serde_json::to_value(&venue)?)
+                .await?;
+        }
+
+        if venue.status.is_open() {
+            report.orders_resumed.push(venue.order_id);
+        } else {
+            report.orders_closed.push(venue.order_id);
+        }
+    }
+    Ok(())
+}
+
+/// Compare inventory and cash with the venue, adopting its view when
+/// configured.
+async fn reconcile_venue(
+    vault: &PooledVault,
+    venue: &PolymarketClobAdapter,
+    cfg: &RecoveryConfig,
+    report: &mut RecoveryReport,
+) -> Result<()> {
+    let balance = venue.get_balance().await?;
+    let positions = venue.get_positions().await?;
+
+    let mut ledger = vault.ledger.lock().await;
+    report.journal_cash_usdc = ledger.cash_usdc;
+    report.venue_cash_usdc = Some(balance);
+    report.position_discrepancies = diff_positions(&ledger, &positions, cfg.size_tolerance);
+    report.venue_checked = true;
+
+    let cash_mismatch = report.cash_mismatch(cfg.cash_tolerance_usdc);
+    if report.position_discrepancies.is_empty() && !cash_mismatch {
+        return Ok(());
+    }
+    for d in &report.position_discrepancies {
+        warn!(
+            token_id = %d.token_id,
+            journal_shares = d.journal_shares,
+            venue_shares = d.venue_shares,
+            "journaled position differs from venue"
+        );
+    }
+    if cash_mismatch {
+        warn!(
+            journal_cash_usdc = report.journal_cash_usdc,
+            venue_cash_usdc = balance,
+            "journaled cash differs from venue balance"
+        );
+    }
+    if !cfg.adopt_venue {
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    adopt_venue_positions(&mut ledger, &report.position_discrepancies);
+    ledger.cash_usdc = balance;
+    let touched: Vec<VaultPositionRecord> = report
+        .position_discrepancies
+        .iter()
+        .map(|d| position_record(&ledger, &d.token_id, &d.outcome, now))
+        .collect();
+    let total_shares = vault.shares.lock().await.total_shares;
+    vault
+        .db
+        .journal_ledger(
+            now,
+            "RECONCILE",
+            None,
+            &touched,
+            ledger.cash_usdc,
+            total_shares,
+            &json!({
+                "positions": report.position_discrepancies,
+                "journal_cash_usdc": report.journal_cash_usdc,
+                "venue_cash_usdc": balance,
+            }),
+        )
+        .await?;
+    report.adopted_venue = true;
+    Ok(())
+}
+
+/// Restart recovery. Run before any trading loop starts; the ledger must
+/// already hold the journaled positions (`restore_ledger`).
+pub async fn recover_vault_state(
+    vault: &PooledVault,
+    exec: &dyn ExecutionAdapter,
+    venue: Option<&PolymarketClobAdapter>,
+    cfg: &RecoveryConfig,
+) -> Result<RecoveryReport> {
+    let mut report = RecoveryReport::default();
+    {
+        let ledger = vault.ledger.lock().await;
+        report.positions_restored = ledger.positions.len();
+        report.journal_cash_usdc = ledger.cash_usdc;
+    }
+
+    resolve_orders(vault, exec, &mut report).await?;
+
+    if cfg.reconcile_venue {
+        if let Some(venue) = venue {
+            reconcile_venue(vault, venue, cfg, &mut report)
+                .await
+                .map_err(|e| anyhow!("venue reconciliation failed: {}", e))?;
+        }
+    }
+
+    info!(
+        positions_restored = report.positions_restored,
+        orders_resumed = report.orders_resumed.len(),
+        orders_closed = report.orders_closed.len(),
+        orders_unresolved = report.orders_unresolved.len(),
+        missed_fills = report.missed_fills_applied,
+        venue_checked = report.venue_checked,
+        discrepancies = report.position_discrepancies.len(),
+        adopted_venue = report.adopted_venue,
+        "vault state recovered"
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{OrderState, TimeInForce, VaultShareState};
+    use tokio::sync::Mutex;
+
+    fn temp_db() -> (tempfile::TempDir, Arc<VaultDb>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.db");
+        let db = Arc::new(VaultDb::new(path.to_str().unwrap()).unwrap());
+        (dir, db)
+    }
+
+    fn vault_with(db: Arc<VaultDb>, ledger: VaultPaperLedger) -> PooledVault {
+        PooledVault::new(
+            db,
+            Arc::new(Mutex::new(ledger)),
+            Arc::new(Mutex::new(VaultShareState {
+                total_shares: 100.0,
+                user_shares: HashMap::new(),
+            })),
+        )
+    }
+
+    fn buy_request(client_order_id: &str, price: f64, notional: f64) -> OrderRequest {
+        OrderRequest {
+            client_order_id: client_order_id.to_string(),
+            token_id: "tok-up".to_string(),
+            side: OrderSide::Buy,
+            price,
+            notional_usdc: notional,
+            tif: TimeInForce::Gtc,
+            market_slug: Some("btc-updown-15m-1".to_string()),
+            outcome: Some("Up".to_string()),
+        }
+    }
+
+    /// Reports a fixed venue state for every order.
+    struct FixedStatusAdapter {
+        state: OrderState,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionAdapter for FixedStatusAdapter {
+        async fn place_order(&self, _req: OrderRequest) -> Result<OrderAck> {
+            Err(anyhow!("not used"))
+        }
+
+        async fn order_status(&self, order_id: &str) -> Result<OrderState> {
+            if order_id == self.state.order_id {
+                Ok(self.state.clone())
+            } else {
+                Err(anyhow!("unknown order {}", order_id))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_ledger_rebuilds_journaled_inventory() {
+        let (_dir, db) = temp_db();
+        let journal = VaultJournal::new(db.clone());
+
+        let mut ledger = VaultPaperLedger {
+            cash_usdc: 100.0,
+            ..Default::default()
+        };
+        let req = buy_request("c1", 0.40, 20.0);
+        let mut order = journal.record_submit(&req, "FAST15M", None).await.unwrap();
+        let ack = OrderAck {
+            order_id: "o1".to_string(),
+            filled_notional_usdc: 20.0,
+            filled_price: 0.40,
+            filled_at: 1_000,
+            fees_usdc: 0.1,
+            slippage_bps: 0.0,
+            latency_ms: 0,
+            resting_size: 0.0,
+        };
+        ledger.apply_buy("tok-up", "Up", 0.40, 20.0, 0.1);
+        journal
+            .record_execution(&mut order, &ack, &ledger, 100.0)
+            .await
+            .unwrap();
+        assert_eq!(order.status, "FILLED");
+
+        let (cash, _) = db.load_state().await.unwrap();
+        let restored = restore_ledger(&db, cash).await.unwrap();
+        assert!((restored.cash_usdc - ledger.cash_usdc).abs() < 1e-9);
+        let pos = &restored.positions["tok-up"];
+        assert!((pos.shares - 50.0).abs() < 1e-9);
+        assert!((pos.avg_price - 0.40).abs() < 1e-9);
+        assert!(db.load_unresolved_orders().await.unwrap().is_empty());
+
+        let kinds: Vec<String> = db
+            .list_journal(0, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(kinds, vec!["SUBMIT", "FILL"]);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_applies_fills_missed_while_down() {
+        let (_dir, db) = temp_db();
+        let journal = VaultJournal::new(db.clone());
+
+        // A GTC order rested with nothing filled, then the engine restarted.
+        let mut ledger = VaultPaperLedger {
+            cash_usdc: 100.0,
+            ..Default::default()
+        };
+        let mut resting = journal
+            .record_submit(&buy_request("c1", 0.50, 10.0), "FAST15M", Some(1))
+            .await
+            .unwrap();
+        let ack = OrderAck {
+            order_id: "o1".to_string(),
+            filled_notional_usdc: 0.0,
+            filled_price: 0.0,
+            filled_at: 1_000,
+            fees_usdc: 0.0,
+            slippage_bps: 0.0,
+            latency_ms: 0,
+            resting_size: 20.0,
+        };
+        journal
+            .record_execution(&mut resting, &ack, &ledger, 100.0)
+            .await
+            .unwrap();
+        // A second order never got its ack journaled.
+        journal
+            .record_submit(&buy_request("c2", 0.50, 5.0), "FAST15M", None)
+            .await
+            .unwrap();
+
+        ledger = restore_ledger(&db, 100.0).await.unwrap();
+        assert!(ledger.positions.is_empty());
+        let vault = vault_with(db.clone(), ledger);
+        let exec = FixedStatusAdapter {
+            state: OrderState {
+                order_id: "o1".to_string(),
+                client_order_id: "c1".to_string(),
+                token_id: "tok-up".to_string(),
+                side: OrderSide::Buy,
+                price: 0.50,
+                size: 20.0,
+                filled_size: 12.0,
+                avg_fill_price: 0.50,
+                status: OrderStatus::PartiallyFilled,
+                updated_at: 2_000,
+            },
+        };
+
+        let report = recover_vault_state(&vault, &exec, None, &RecoveryConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(report.missed_fills_applied, 1);
+        assert_eq!(report.orders_resumed, vec!["o1".to_string()]);
+        assert_eq!(report.orders_unresolved, vec!["c2".to_string()]);
+        assert!(!report.safe_to_trade(&RecoveryConfig::default()));
+
+        let ledger = vault.ledger.lock().await;
+        assert!((ledger.positions["tok-up"].shares - 12.0).abs() < 1e-9);
+        assert!((ledger.cash_usdc - 94.0).abs() < 1e-9);
+
+        // The missed fill is durable: a second restart sees the same inventory.
+        let restored = restore_ledger(&db, db.load_state().await.unwrap().0)
+            .await
+            .unwrap();
+        assert!((restored.positions["tok-up"].shares - 12.0).abs() < 1e-9);
+        let open = db.load_unresolved_orders().await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].status, "PARTIALLY_FILLED");
+        assert!((open[0].filled_size - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recovery_adopts_venue_only_on_opt_in() {
+        let cfg = RecoveryConfig::default();
+        assert!(cfg.reconcile_venue);
+        assert!(!cfg.adopt_venue);
+
+        let report = RecoveryReport {
+            venue_checked: true,
+            position_discrepancies: vec![PositionDiscrepancy {
+                token_id: "tok-a".to_string(),
+                outcome: "Up".to_string(),
+                journal_shares: 20.0,
+                venue_shares: 0.0,
+                venue_avg_price: 0.0,
+            }],
+            ..Default::default()
+        };
+        assert!(!report.safe_to_trade(&cfg));
+    }
+
+    #[test]
+    fn test_diff_and_adopt_venue_positions() {
+        let mut ledger = VaultPaperLedger::default();
+        ledger.apply_buy("tok-a", "Up", 0.50, 10.0, 0.0); // 20 shares
+        ledger.apply_buy("tok-b", "Down", 0.25, 5.0, 0.0); // 20 shares
+
+        let venue = vec![
+            PolymarketPosition {
+                token_id: "tok-a".to_string(),
+                size: 20.005,
+                avg_price: 0.50,
+                market_slug: None,
+                outcome: Some("Up".to_string()),
+                current_price: None,
+            },
+            PolymarketPosition {
+                token_id: "tok-c".to_string(),
+                size: 8.0,
+                avg_price: 0.60,
+                market_slug: None,
+                outcome: Some("Up".to_string()),
+                current_price: None,
+            },
+        ];
+
+        let diffs = diff_positions(&ledger, &venue, 0.01);
+        let tokens: Vec<&str> = diffs.iter().map(|d| d.token_id.as_str()).collect();
+        assert_eq!(tokens, vec!["tok-b", "tok-c"]);
+
+        adopt_venue_positions(&mut ledger, &diffs);
+        assert!(!ledger.positions.contains_key("tok-b"));
+        let c = &ledger.positions["tok-c"];
+        assert!((c.shares - 8.0).abs() < 1e-9);
+        assert!((c.cost_usdc - 4.8).abs() < 1e-9);
+        assert!(diff_positions(&ledger, &venue, 0.01).is_empty());
+    }
+}
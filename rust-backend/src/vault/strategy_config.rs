@@ -0,0 +1,789 @@
+//! Hot-Reloadable Strategy Configuration
+//!
+//! `VaultEngineConfig` and `Unified15mConfig` are seeded from the environment
+//! at startup and can then be replaced without a restart, either by editing the
+//! JSON file at `STRATEGY_CONFIG_PATH` (polled) or through the admin API.
+//!
+//! A new parameter set is a JSON patch over the active one. It is validated,
+//! recorded in `VaultDb` as the next config version and promoted at the next
+//! 15-minute window boundary, so no window trades under a mix of old and new
+//! parameters. Orders and trades carry the version that produced them.
+//!
+//! Settings that only take effect at startup (engine on/off, paper/live, poll
+//! intervals, LLM model set, bankroll) cannot be changed at runtime.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use crate::vault::{Unified15mConfig, VaultDb, VaultEngineConfig, VaultStrategyConfigRecord};
+
+/// New parameter sets take effect on 15-minute window boundaries
+pub const CONFIG_WINDOW_SECS: i64 = 15 * 60;
+
+/// First instant of the window after the one containing `ts`.
+pub fn next_window_boundary(ts: i64) -> i64 {
+    ts - ts.rem_euclid(CONFIG_WINDOW_SECS) + CONFIG_WINDOW_SECS
+}
+
+/// The full set of hot-reloadable strategy parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VaultStrategyParams {
+    pub engine: VaultEngineConfig,
+    pub unified_15m: Unified15mConfig,
+}
+
+impl VaultStrategyParams {
+    pub fn from_env() -> Self {
+        Self {
+            engine: VaultEngineConfig::from_env(),
+            unified_15m: Unified15mConfig::from_env(),
+        }
+    }
+
+    /// Overlay a (partial) JSON patch on these parameters.
+    pub fn patched(&self, patch: Value) -> Result<Self, String> {
+        let mut merged = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        merge_json(&mut merged, patch);
+        serde_json::from_value(merged).map_err(|e| format!("invalid config: {e}"))
+    }
+
+    /// Check value ranges, and, when replacing `active`, that no startup-only
+    /// setting changes. All problems are reported together.
+    pub fn validate(&self, active: Option<&VaultStrategyParams>) -> Result<(), String> {
+        let mut errors = Vec::new();
+        let mut check = |ok: bool, field: &str, rule: &str| {
+            if !ok {
+                errors.push(format!("{field} must be {rule}"));
+            }
+        };
+
+        let e = &self.engine;
+        check(e.updown_min_edge > 0.0, "engine.updown_min_edge", "> 0");
+        check(
+            (0.0..=1.0).contains(&e.updown_kelly_fraction),
+            "engine.updown_kelly_fraction",
+            "in [0, 1]",
+        );
+        check(
+            e.updown_max_position_pct > 0.0 && e.updown_max_position_pct <= 1.0,
+            "engine.updown_max_position_pct",
+            "in (0, 1]",
+        );
+        check(
+            (0.0..=1.0).contains(&e.updown_shrink_to_half),
+            "engine.updown_shrink_to_half",
+            "in [0, 1]",
+        );
+        check(
+            e.updown_cooldown_sec >= 0,
+            "engine.updown_cooldown_sec",
+            ">= 0",
+        );
+        check(
+            e.max_drawdown_pct > 0.0 && e.max_drawdown_pct <= 1.0,
+            "engine.max_drawdown_pct",
+            "in (0, 1]",
+        );
+        check(e.long_min_edge > 0.0, "engine.long_min_edge", "> 0");
+        check(
+            (0.0..=1.0).contains(&e.long_kelly_fraction),
+            "engine.long_kelly_fraction",
+            "in [0, 1]",
+        );
+        check(
+            e.long_max_position_pct > 0.0 && e.long_max_position_pct <= 1.0,
+            "engine.long_max_position_pct",
+            "in (0, 1]",
+        );
+        check(
+            e.long_min_trade_usd > 0.0,
+            "engine.long_min_trade_usd",
+            "> 0",
+        );
+        check(
+            e.long_max_trade_usd >= e.long_min_trade_usd,
+            "engine.long_max_trade_usd",
+            ">= long_min_trade_usd",
+        );
+        check(
+            e.long_min_infer_interval_sec >= 0,
+            "engine.long_min_infer_interval_sec",
+            ">= 0",
+        );
+        check(e.long_cooldown_sec >= 0, "engine.long_cooldown_sec", ">= 0");
+        check(
+            e.long_max_calls_per_day > 0,
+            "engine.long_max_calls_per_day",
+            "> 0",
+        );
+        check(
+            e.long_max_calls_per_market_per_day > 0,
+            "engine.long_max_calls_per_market_per_day",
+            "> 0",
+        );
+        check(
+            e.long_max_tokens_per_day > 0,
+            "engine.long_max_tokens_per_day",
+            "> 0",
+        );
+        check(
+            e.long_llm_timeout_sec >= 1,
+            "engine.long_llm_timeout_sec",
+            ">= 1",
+        );
+        check(
+            e.long_llm_max_tokens >= 16,
+            "engine.long_llm_max_tokens",
+            ">= 16",
+        );
+        check(
+            e.long_llm_temperature >= 0.0,
+            "engine.long_llm_temperature",
+            ">= 0",
+        );
+        check(e.long_max_tte_days > 0.0, "engine.long_max_tte_days", "> 0");
+        check(
+            e.long_max_spread_bps > 0.0,
+            "engine.long_max_spread_bps",
+            "> 0",
+        );
+        check(
+            e.long_min_top_of_book_usd >= 0.0,
+            "engine.long_min_top_of_book_usd",
+            ">= 0",
+        );
+        check(e.long_fee_buffer >= 0.0, "engine.long_fee_buffer", ">= 0");
+        check(
+            e.long_slippage_buffer_min >= 0.0,
+            "engine.long_slippage_buffer_min",
+            ">= 0",
+        );
+        check(
+            e.long_dispersion_max > 0.0,
+            "engine.long_dispersion_max",
+            "> 0",
+        );
+        check(
+            e.long_exit_price_90 > 0.0 && e.long_exit_price_90 < 1.0,
+            "engine.long_exit_price_90",
+            "in (0, 1)",
+        );
+        check(
+            e.long_exit_price_95 > 0.0 && e.long_exit_price_95 < 1.0,
+            "engine.long_exit_price_95",
+            "in (0, 1)",
+        );
+        check(
+            e.long_exit_price_95 >= e.long_exit_price_90,
+            "engine.long_exit_price_95",
+            ">= long_exit_price_90",
+        );
+        check(
+            (0.0..=1.0).contains(&e.long_exit_frac_90),
+            "engine.long_exit_frac_90",
+            "in [0, 1]",
+        );
+        check(
+            (0.0..=1.0).contains(&e.long_exit_frac_95),
+            "engine.long_exit_frac_95",
+            "in [0, 1]",
+        );
+        check(
+            e.long_wallet_window_sec >= 1,
+            "engine.long_wallet_window_sec",
+            ">= 1",
+        );
+        check(
+            e.long_wallet_max_trades_per_window >= 1,
+            "engine.long_wallet_max_trades_per_window",
+            ">= 1",
+        );
+        check(
+            e.long_wallet_min_notional_usd >= 0.0,
+            "engine.long_wallet_min_notional_usd",
+            ">= 0",
+        );
+
+        let u = &self.unified_15m;
+        check(
+            u.min_edge > 0.0 && u.min_edge < 0.5,
+            "unified_15m.min_edge",
+            "in (0, 0.5)",
+        );
+        check(
+            u.jump_regime_edge_mult >= 1.0,
+            "unified_15m.jump_regime_edge_mult",
+            ">= 1",
+        );
+        check(
+            u.kelly_fraction > 0.0 && u.kelly_fraction <= 1.0,
+            "unified_15m.kelly_fraction",
+            "in (0, 1]",
+        );
+        check(
+            u.max_position_pct > 0.0 && u.max_position_pct <= 1.0,
+            "unified_15m.max_position_pct",
+            "in (0, 1]",
+        );
+        check(
+            u.min_position_usd > 0.0,
+            "unified_15m.min_position_usd",
+            "> 0",
+        );
+        check(
+            u.max_position_usd >= u.min_position_usd,
+            "unified_15m.max_position_usd",
+            ">= min_position_usd",
+        );
+        check(u.bankroll > 0.0, "unified_15m.bankroll", "> 0");
+        check(
+            u.sigma_b_default > 0.0,
+            "unified_15m.sigma_b_default",
+            "> 0",
+        );
+        check(
+            (0.0..=1.0).contains(&u.shrink_factor),
+            "unified_15m.shrink_factor",
+            "in [0, 1]",
+        );
+        check(u.lambda >= 0.0, "unified_15m.lambda", ">= 0");
+        check(u.cooldown_sec >= 0, "unified_15m.cooldown_sec", ">= 0");
+        check(
+            (0..CONFIG_WINDOW_SECS).contains(&u.window_end_skip_sec),
+            "unified_15m.window_end_skip_sec",
+            "in [0, 900)",
+        );
+        check(
+            u.exit_timeout_sec > 0,
+            "unified_15m.exit_timeout_sec",
+            "> 0",
+        );
+        check(
+            u.jump_z_threshold > 0.0,
+            "unified_15m.jump_z_threshold",
+            "> 0",
+        );
+        check(u.jump_window_sec > 0, "unified_15m.jump_window_sec", "> 0");
+        check(
+            u.jump_count_threshold >= 1,
+            "unified_15m.jump_count_threshold",
+            ">= 1",
+        );
+        check(
+            (0.0..1.0).contains(&u.fee_rate_at_mid),
+            "unified_15m.fee_rate_at_mid",
+            "in [0, 1)",
+        );
+
+        if let Some(active) = active {
+            let (a, u0) = (&active.engine, &active.unified_15m);
+            let fixed = "unchanged at runtime (restart to change)";
+            check(e.enabled == a.enabled, "engine.enabled", fixed);
+            check(e.paper == a.paper, "engine.paper", fixed);
+            check(
+                e.updown_poll_ms == a.updown_poll_ms,
+                "engine.updown_poll_ms",
+                fixed,
+            );
+            check(
+                e.initial_bankroll == a.initial_bankroll,
+                "engine.initial_bankroll",
+                fixed,
+            );
+            check(
+                e.long_enabled == a.long_enabled,
+                "engine.long_enabled",
+                fixed,
+            );
+            check(
+                e.long_poll_ms == a.long_poll_ms,
+                "engine.long_poll_ms",
+                fixed,
+            );
+            check(e.long_models == a.long_models, "engine.long_models", fixed);
+            check(u.bankroll == u0.bankroll, "unified_15m.bankroll", fixed);
+        }
+
+        // Catches NaN/inf in any field
+        let finite = serde_json::to_value(self)
+            .map(|v| !has_non_finite(&v))
+            .unwrap_or(false);
+        if !finite {
+            errors.push("all numbers must be finite".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Recursively merge `patch` into `base`; objects merge key by key, anything
+/// else replaces.
+fn merge_json(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+/// serde_json serializes NaN and infinities as `null`.
+fn has_non_finite(v: &Value) -> bool {
+    match v {
+        Value::Null => true,
+        Value::Array(items) => items.iter().any(has_non_finite),
+        Value::Object(map) => map.values().any(has_non_finite),
+        _ => false,
+    }
+}
+
+/// One recorded parameter set.
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyConfigVersion {
+    pub version: i64,
+    pub params: VaultStrategyParams,
+    /// `env`, `file` or `api`
+    pub source: String,
+    pub actor: String,
+    pub note: Option<String>,
+    pub created_at: i64,
+    /// Window boundary at which the version takes (or took) effect
+    pub effective_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyConfigStatus {
+    pub active: StrategyConfigVersion,
+    pub pending: Option<StrategyConfigVersion>,
+    pub config_path: Option<String>,
+}
+
+/// Versioned store of strategy parameters shared by the running strategies.
+pub struct StrategyConfigStore {
+    db: Option<Arc<VaultDb>>,
+    path: Option<PathBuf>,
+    active: RwLock<Arc<StrategyConfigVersion>>,
+    pending: RwLock<Option<Arc<StrategyConfigVersion>>>,
+    /// Version numbers when running without a database
+    next_version: AtomicI64,
+    file_mtime: Mutex<Option<SystemTime>>,
+}
+
+impl StrategyConfigStore {
+    /// In-memory store (versions are not persisted).
+    pub fn new(params: VaultStrategyParams, path: Option<PathBuf>) -> Self {
+        let now = Utc::now().timestamp();
+        Self {
+            db: None,
+            path,
+            active: RwLock::new(Arc::new(StrategyConfigVersion {
+                version: 1,
+                params,
+                source: "env".to_string(),
+                actor: "startup".to_string(),
+                note: None,
+                created_at: now,
+                effective_at: now,
+            })),
+            pending: RwLock::new(None),
+            next_version: AtomicI64::new(2),
+            file_mtime: Mutex::new(None),
+        }
+    }
+
+    /// Startup parameters are `seed` overlaid with the config file, if one
+    /// exists. They are recorded as a new version unless identical to the
+    /// version last active in `db`.
+    pub async fn load(
+        seed: VaultStrategyParams,
+        db: Arc<VaultDb>,
+        path: Option<PathBuf>,
+    ) -> Result<Self> {
+        let mut params = seed;
+        let mut source = "env";
+        let mut file_mtime = None;
+        if let Some(path) = path.as_ref().filter(|p| p.exists()) {
+            let (patch, mtime) = read_config_file(path)?;
+            let from_file = params
+                .patched(patch)
+                .and_then(|p| p.validate(None).map(|_| p))
+                .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+            params = from_file;
+            source = "file";
+            file_mtime = mtime;
+        }
+        params
+            .validate(None)
+            .map_err(|e| anyhow::anyhow!("startup strategy config: {e}"))?;
+
+        let now = Utc::now().timestamp();
+        let payload = serde_json::to_string(&params)?;
+        let previous = db.load_active_strategy_config().await?;
+        let active = match previous.filter(|rec| rec.payload == payload) {
+            Some(rec) => StrategyConfigVersion {
+                version: rec.version,
+                params,
+                source: rec.source,
+                actor: rec.actor,
+                note: rec.note,
+                created_at: rec.created_at,
+                effective_at: rec.effective_at,
+            },
+            None => {
+                let mut rec = VaultStrategyConfigRecord {
+                    version: 0,
+                    created_at: now,
+                    effective_at: now,
+                    activated_at: Some(now),
+                    status: "ACTIVE".to_string(),
+                    source: source.to_string(),
+                    actor: "startup".to_string(),
+                    note: None,
+                    payload,
+                };
+                rec.version = db.insert_strategy_config(&rec).await?;
+                StrategyConfigVersion {
+                    version: rec.version,
+                    params,
+                    source: rec.source,
+                    actor: rec.actor,
+                    note: None,
+                    created_at: now,
+                    effective_at: now,
+                }
+            }
+        };
+        info!(
+            version = active.version,
+            source = %active.source,
+            "strategy config loaded"
+        );
+
+        let mut store = Self::new(active.params.clone(), path);
+        store.db = Some(db);
+        *store.active.get_mut() = Arc::new(active);
+        *store.file_mtime.get_mut() = file_mtime;
+        Ok(store)
+    }
+
+    /// The version in effect, without promoting a due pending version. For
+    /// startup-only settings.
+    pub fn current(&self) -> Arc<StrategyConfigVersion> {
+        self.active.read().clone()
+    }
+
+    /// The version in effect now. A pending version is promoted once its
+    /// window boundary has passed.
+    pub async fn active(&self) -> Arc<StrategyConfigVersion> {
+        self.active_at(Utc::now().timestamp()).await
+    }
+
+    pub async fn active_at(&self, now: i64) -> Arc<StrategyConfigVersion> {
+        let due = {
+            let mut pending = self.pending.write();
+            match pending.as_ref() {
+                Some(p) if p.effective_at <= now => pending.take(),
+                _ => None,
+            }
+        };
+        if let Some(next) = due {
+            let previous = std::mem::replace(&mut *self.active.write(), next.clone());
+            info!(
+                from = previous.version,
+                to = next.version,
+                effective_at = next.effective_at,
+                "strategy config version activated"
+            );
+            if let Some(db) = &self.db {
+                if let Err(e) = db.activate_strategy_config(next.version, now).await {
+                    warn!(error = %e, version = next.version, "failed to record config activation");
+                }
+            }
+        }
+        self.current()
+    }
+
+    pub fn pending(&self) -> Option<Arc<StrategyConfigVersion>> {
+        self.pending.read().clone()
+    }
+
+    pub fn status(&self) -> StrategyConfigStatus {
+        StrategyConfigStatus {
+            active: self.current().as_ref().clone(),
+            pending: self.pending().map(|p| p.as_ref().clone()),
+            config_path: self.path.as_ref().map(|p| p.display().to_string()),
+        }
+    }
+
+    /// Overlay `patch` on the latest parameters (pending, else active) and
+    /// validate the result.
+    pub fn prepare(&self, patch: Value) -> Result<VaultStrategyParams, String> {
+        let active = self.current();
+        let base = self
+            .pending()
+            .map(|p| p.params.clone())
+            .unwrap_or_else(|| active.params.clone());
+        let params = base.patched(patch)?;
+        params.validate(Some(&active.params))?;
+        Ok(params)
+    }
+
+    /// Record validated `params` as the next version, effective at the next
+    /// window boundary. A version still pending is replaced. Staging the
+    /// parameters already pending (or active, with nothing pending) is a no-op.
+    pub async fn stage(
+        &self,
+        params: VaultStrategyParams,
+        source: &str,
+        actor: &str,
+        note: Option<String>,
+    ) -> Result<Arc<StrategyConfigVersion>> {
+        if let Some(pending) = self.pending() {
+            if pending.params == params {
+                return Ok(pending);
+            }
+        } else if self.current().params == params {
+            return Ok(self.current());
+        }
+
+        let now = Utc::now().timestamp();
+        let effective_at = next_window_boundary(now);
+        let version = match &self.db {
+            Some(db) => {
+                db.insert_strategy_config(&VaultStrategyConfigRecord {
+                    version: 0,
+                    created_at: now,
+                    effective_at,
+                    activated_at: None,
+                    status: "PENDING".to_string(),
+                    source: source.to_string(),
+                    actor: actor.to_string(),
+                    note: note.clone(),
+                    payload: serde_json::to_string(&params)?,
+                })
+                .await?
+            }
+            None => self.next_version.fetch_add(1, Ordering::Relaxed),
+        };
+        let staged = Arc::new(StrategyConfigVersion {
+            version,
+            params,
+            source: source.to_string(),
+            actor: actor.to_string(),
+            note,
+            created_at: now,
+            effective_at,
+        });
+        if let Some(replaced) = self.pending.write().replace(staged.clone()) {
+            info!(
+                replaced = replaced.version,
+                "pending strategy config replaced"
+            );
+        }
+        info!(
+            version,
+            source = %source,
+            actor = %actor,
+            effective_at,
+            "strategy config staged for next window"
+        );
+        Ok(staged)
+    }
+
+    /// Recorded versions, newest first.
+    pub async fn history(&self, limit: usize) -> Result<Vec<VaultStrategyConfigRecord>> {
+        match &self.db {
+            Some(db) => db.list_strategy_configs(limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The parameters of a recorded version, for attributing a trade.
+    pub async fn version(&self, version: i64) -> Result<Option<VaultStrategyParams>> {
+        let Some(db) = &self.db else {
+            let active = self.current();
+            return Ok((active.version == version).then(|| active.params.clone()));
+        };
+        match db.get_strategy_config(version).await? {
+            Some(rec) => Ok(Some(serde_json::from_str(&rec.payload)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Poll the config file and stage its contents whenever it changes.
+    pub fn spawn_file_watcher(self: &Arc<Self>, interval: Duration) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let store = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                if mtime.is_none() || mtime == *store.file_mtime.lock() {
+                    continue;
+                }
+                *store.file_mtime.lock() = mtime;
+
+                let patch = match read_config_file(&path) {
+                    Ok((patch, _)) => patch,
+                    Err(e) => {
+                        warn!(error = %e, "strategy config file unreadable; keeping current config");
+                        continue;
+                    }
+                };
+                match store.prepare(patch) {
+                    Ok(params) => {
+                        if let Err(e) = store.stage(params, "file", "file", None).await {
+                            warn!(error = %e, "failed to stage strategy config from file");
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "strategy config file rejected"),
+                }
+            }
+        });
+    }
+}
+
+fn read_config_file(path: &Path) -> Result<(Value, Option<SystemTime>)> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("read strategy config {}", path.display()))?;
+    let patch = serde_json::from_str(&raw)
+        .with_context(|| format!("parse strategy config {}", path.display()))?;
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    Ok((patch, mtime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_db() -> (tempfile::TempDir, Arc<VaultDb>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.db");
+        let db = Arc::new(VaultDb::new(path.to_str().unwrap()).unwrap());
+        (dir, db)
+    }
+
+    #[test]
+    fn test_window_boundary() {
+        assert_eq!(next_window_boundary(0), 900);
+        assert_eq!(next_window_boundary(899), 900);
+        assert_eq!(next_window_boundary(900), 1800);
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_ranges_and_startup_changes() {
+        let active = VaultStrategyParams::default();
+
+        let ok = active
+            .patched(json!({ "unified_15m": { "min_edge": 0.07 } }))
+            .unwrap();
+        assert_eq!(ok.unified_15m.min_edge, 0.07);
+        assert_eq!(ok.engine, active.engine);
+        assert!(ok.validate(Some(&active)).is_ok());
+
+        let bad = active
+            .patched(json!({
+                "engine": { "max_drawdown_pct": 1.5, "paper": false },
+                "unified_15m": { "min_edge": 0.6 }
+            }))
+            .unwrap();
+        let err = bad.validate(Some(&active)).unwrap_err();
+        assert!(err.contains("engine.max_drawdown_pct"));
+        assert!(err.contains("engine.paper"));
+        assert!(err.contains("unified_15m.min_edge"));
+        // Startup-only fields are free before anything is running
+        assert!(bad.validate(None).unwrap_err().contains("max_drawdown_pct"));
+        assert!(!bad.validate(None).unwrap_err().contains("engine.paper"));
+
+        assert!(active
+            .patched(json!({ "engine": { "updown_min_edge": "high" } }))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_staged_config_activates_at_window_boundary() {
+        let (_dir, db) = temp_db();
+        let store = StrategyConfigStore::load(VaultStrategyParams::default(), db.clone(), None)
+            .await
+            .unwrap();
+        let v1 = store.current();
+
+        let params = store
+            .prepare(json!({ "engine": { "updown_min_edge": 0.03 } }))
+            .unwrap();
+        let staged = store
+            .stage(params, "api", "admin", Some("wider edge".into()))
+            .await
+            .unwrap();
+        assert!(staged.version > v1.version);
+
+        // Not yet at the boundary
+        let now = staged.effective_at - 1;
+        assert_eq!(store.active_at(now).await.version, v1.version);
+        assert_eq!(store.pending().unwrap().version, staged.version);
+
+        let active = store.active_at(staged.effective_at).await;
+        assert_eq!(active.version, staged.version);
+        assert_eq!(active.params.engine.updown_min_edge, 0.03);
+        assert!(store.pending().is_none());
+
+        let history = store.history(10).await.unwrap();
+        assert_eq!(history[0].version, staged.version);
+        assert_eq!(history[0].status, "ACTIVE");
+        assert_eq!(history[1].status, "SUPERSEDED");
+        let recorded = store.version(v1.version).await.unwrap().unwrap();
+        assert_eq!(
+            recorded.engine.updown_min_edge,
+            v1.params.engine.updown_min_edge
+        );
+
+        // A restart with the same parameters reuses the recorded version
+        let mut seed = VaultStrategyParams::default();
+        seed.engine.updown_min_edge = 0.03;
+        let reloaded = StrategyConfigStore::load(seed, db, None).await.unwrap();
+        assert_eq!(reloaded.current().version, staged.version);
+    }
+
+    #[tokio::test]
+    async fn test_restaging_replaces_pending_version() {
+        let store = StrategyConfigStore::new(VaultStrategyParams::default(), None);
+        let a = store
+            .prepare(json!({ "unified_15m": { "cooldown_sec": 60 } }))
+            .unwrap();
+        let first = store.stage(a.clone(), "api", "admin", None).await.unwrap();
+        // Same parameters again: no new version
+        assert_eq!(
+            store.stage(a, "file", "file", None).await.unwrap().version,
+            first.version
+        );
+
+        // Patches build on the pending set
+        let b = store
+            .prepare(json!({ "unified_15m": { "kelly_fraction": 0.1 } }))
+            .unwrap();
+        assert_eq!(b.unified_15m.cooldown_sec, 60);
+        let second = store.stage(b, "api", "admin", None).await.unwrap();
+        assert_ne!(second.version, first.version);
+        assert_eq!(store.pending().unwrap().version, second.version);
+    }
+}
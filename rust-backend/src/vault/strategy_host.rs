@@ -0,0 +1,1692 @@
+//! Live host for backtest_v2 strategies
+//!
+//! Runs any `backtest_v2::strategy::Strategy` against live data with zero code
+//! changes, so a strategy certified in backtest is deployed as-is:
+//!
+//! - `LiveOrderSender` implements `OrderSender` on top of an `ExecutionAdapter`
+//! - `LiveStrategyHost` pumps book updates and trade prints from the
+//!   Polymarket `BookStore`, fires timers, submits queued orders and feeds
+//!   acks/fills/rejects/cancel-acks back into the strategy callbacks
+//!
+//! `OrderSender` is synchronous while execution is async, so orders and cancels
+//! sent from a callback are queued and submitted once the callback returns.
+//! Replaces amend an order still in the queue; the execution adapters have no
+//! native amend, so an order already at the venue must be cancelled and resent.
+//! Batches go out in one `place_orders` call. The venue settles each order of
+//! a batch on its own, so when an all-or-none batch comes back partly refused
+//! the resting remainder of the accepted orders is cancelled. Cancelling a
+//! queued order of an all-or-none batch drops the whole batch.
+//! The unfilled remainder of a GTC order rests at the venue; its fills and
+//! cancellation arrive on the adapter's `ExecutionEvent` stream. IOC/FOK
+//! remainders are reported as cancelled straight away.
+//!
+//! With a `LiveSessionRecorder` attached, every submit, ack, reject, fill,
+//! cancel request and cancel ack is journaled in the backtest decision trace
+//! format for `backtest_v2::live_reconciliation`.
+//!
+//! With a `TradingControl` attached, orders are rejected back to the strategy
+//! while a global, strategy or asset halt is active, and order outcomes feed
+//! the reject-rate halt.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use tokio::sync::{broadcast, mpsc, watch};
+use tracing::{debug, info, warn};
+
+use crate::backtest_v2::clock::Nanos;
+use crate::backtest_v2::events::{
+    BatchMode, Level, OrderId, Side, Size, TimeInForce as StrategyTimeInForce,
+};
+use crate::backtest_v2::live_reconciliation::{LiveSessionHeader, LiveSessionRecorder};
+use crate::backtest_v2::run_diff::TraceRecord;
+use crate::backtest_v2::strategy::{
+    BookSnapshot, CancelAck, FillNotification, OpenOrder, OrderAck, OrderReject, OrderSender,
+    Position, ReplaceAck, Strategy, StrategyCancel, StrategyContext, StrategyOrder, StrategyParams,
+    StrategyReplace, TimerEvent, TradePrint,
+};
+use crate::scrapers::{BookStore, HftBookCache, PublicTradePrint};
+use crate::vault::{
+    ExecutionAdapter, ExecutionEvent, OrderAck as ExecAck, OrderRequest, OrderSide, TimeInForce,
+    TradingControl,
+};
+use crate::venue_limits::POLYMARKET_MAX_BATCH_SIZE;
+
+/// Quantities below this are treated as zero.
+const QTY_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone)]
+pub struct LiveStrategyConfig {
+    /// Strategy name (see `backtest_v2::strategy_factory::make_strategy`)
+    pub strategy_name: String,
+    /// Tokens whose books and trades drive the strategy
+    pub tokens: Vec<String>,
+    pub params: StrategyParams,
+    /// Books older than this are not delivered to the strategy
+    pub book_max_stale_ms: u64,
+    pub event_queue_size: usize,
+    /// Submit rounds per event before remaining orders wait for the next event
+    pub max_flush_rounds: usize,
+    /// Live session journal for reconciliation against backtest (None = off)
+    pub record_path: Option<String>,
+}
+
+impl Default for LiveStrategyConfig {
+    fn default() -> Self {
+        Self {
+            strategy_name: "noop".to_string(),
+            tokens: Vec::new(),
+            params: StrategyParams::new(),
+            book_max_stale_ms: 1_500,
+            event_queue_size: 10_000,
+            max_flush_rounds: 8,
+            record_path: None,
+        }
+    }
+}
+
+impl LiveStrategyConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = std::env::var("LIVE_STRATEGY_NAME") {
+            if !v.trim().is_empty() {
+                cfg.strategy_name = v.trim().to_string();
+            }
+        }
+        if let Ok(v) = std::env::var("LIVE_STRATEGY_TOKENS") {
+            cfg.tokens = v
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        // LIVE_STRATEGY_PARAMS=half_spread=0.01,token_id=123
+        if let Ok(v) = std::env::var("LIVE_STRATEGY_PARAMS") {
+            cfg.params = parse_strategy_params(&v);
+        }
+        if let Ok(v) = std::env::var("LIVE_STRATEGY_BOOK_MAX_STALE_MS") {
+            if let Ok(ms) = v.parse::<u64>() {
+                cfg.book_max_stale_ms = ms;
+            }
+        }
+        if let Ok(v) = std::env::var("LIVE_STRATEGY_RECORD_PATH") {
+            if !v.trim().is_empty() {
+                cfg.record_path = Some(v.trim().to_string());
+            }
+        }
+
+        cfg
+    }
+}
+
+/// Parse `key=value,...` into strategy params. Every value is kept as a
+/// string, since numeric-looking identifiers (Polymarket token ids) must reach
+/// `get_string` verbatim; values that parse as finite numbers are also
+/// available through `get`.
+fn parse_strategy_params(spec: &str) -> StrategyParams {
+    let mut params = StrategyParams::new();
+    for pair in spec.split(',') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if let Ok(num) = value.parse::<f64>() {
+            if num.is_finite() {
+                params = params.with_param(key, num);
+            }
+        }
+        params = params.with_string(key, value);
+    }
+    params
+}
+
+// ============================================================================
+// LiveOrderSender
+// ============================================================================
+
+/// `OrderSender` for live trading.
+///
+/// Orders are validated and queued; `LiveStrategyHost` submits them through
+/// the execution adapter after the callback that sent them returns.
+#[derive(Debug, Default)]
+pub struct LiveOrderSender {
+    now: Nanos,
+    next_order_id: OrderId,
+    next_timer_id: u64,
+    /// Queued and in-flight orders
+    open_orders: BTreeMap<OrderId, OpenOrder>,
+    /// Orders accepted from the strategy, not yet submitted
+    outbox: VecDeque<(OrderId, StrategyOrder)>,
+    /// Batches accepted from the strategy, each submitted in one venue call
+    batches: VecDeque<(BatchMode, Vec<(OrderId, StrategyOrder)>)>,
+    /// Cancels of queued orders, acknowledged on the next flush
+    cancelled: VecDeque<(OrderId, Size)>,
+    /// Queued orders dropped before submission (id, client order id, reason),
+    /// rejected on the next flush
+    rejected: VecDeque<(OrderId, String, String)>,
+    /// Cancels of submitted orders, sent to the venue on the next flush
+    cancel_requests: VecDeque<OrderId>,
+    /// Amends of queued orders (id, price, size), acknowledged on the next flush
+    replaced: VecDeque<(OrderId, f64, Size)>,
+    positions: HashMap<String, Position>,
+    timers: BTreeMap<(Nanos, u64), Option<String>>,
+    timer_fire_times: HashMap<u64, Nanos>,
+}
+
+impl LiveOrderSender {
+    pub fn new() -> Self {
+        Self {
+            next_order_id: 1,
+            next_timer_id: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Set the time reported to the strategy.
+    pub fn set_time(&mut self, now: Nanos) {
+        self.now = now;
+    }
+
+    /// Earliest pending timer.
+    pub fn next_timer_time(&self) -> Option<Nanos> {
+        self.timers.keys().next().map(|(time, _)| *time)
+    }
+
+    fn has_pending(&self) -> bool {
+        !self.outbox.is_empty()
+            || !self.batches.is_empty()
+            || !self.cancelled.is_empty()
+            || !self.rejected.is_empty()
+            || !self.cancel_requests.is_empty()
+            || !self.replaced.is_empty()
+    }
+
+    fn take_submissions(&mut self) -> Vec<(OrderId, StrategyOrder)> {
+        self.outbox.drain(..).collect()
+    }
+
+    fn take_batches(&mut self) -> Vec<(BatchMode, Vec<(OrderId, StrategyOrder)>)> {
+        self.batches.drain(..).collect()
+    }
+
+    fn take_cancel_acks(&mut self) -> Vec<(OrderId, Size)> {
+        self.cancelled.drain(..).collect()
+    }
+
+    fn take_rejects(&mut self) -> Vec<(OrderId, String, String)> {
+        self.rejected.drain(..).collect()
+    }
+
+    fn take_cancel_requests(&mut self) -> Vec<OrderId> {
+        self.cancel_requests.drain(..).collect()
+    }
+
+    fn take_replace_acks(&mut self) -> Vec<(OrderId, f64, Size)> {
+        self.replaced.drain(..).collect()
+    }
+
+    fn take_due_timers(&mut self, now: Nanos) -> Vec<(u64, Nanos, Option<String>)> {
+        let mut due = Vec::new();
+        while let Some(entry) = self.timers.first_entry() {
+            let (fire_time, timer_id) = *entry.key();
+            if fire_time > now {
+                break;
+            }
+            let payload = entry.remove();
+            self.timer_fire_times.remove(&timer_id);
+            due.push((timer_id, fire_time, payload));
+        }
+        due
+    }
+
+    /// Apply a fill to the position and the open order.
+    fn apply_fill(&mut self, order_id: OrderId, price: f64, size: Size, fee: f64) {
+        let Some(order) = self.open_orders.get_mut(&order_id) else {
+            return;
+        };
+        order.remaining_size = (order.remaining_size - size).max(0.0);
+
+        let position = self
+            .positions
+            .entry(order.token_id.clone())
+            .or_insert_with(|| Position {
+                token_id: order.token_id.clone(),
+                ..Default::default()
+            });
+        let old_shares = position.shares;
+        match order.side {
+            Side::Buy => {
+                position.shares += size;
+                position.cost_basis += size * price + fee;
+            }
+            Side::Sell => {
+                position.shares -= size;
+                let avg_cost = if old_shares.abs() > 0.0 {
+                    position.cost_basis / old_shares.abs()
+                } else {
+                    price
+                };
+                position.realized_pnl += size * (price - avg_cost) - fee;
+                position.cost_basis -= size * avg_cost;
+            }
+        }
+
+        if order.remaining_size <= QTY_EPSILON {
+            self.open_orders.remove(&order_id);
+        }
+    }
+
+    /// Remove an order, returning its unfilled size.
+    fn complete(&mut self, order_id: OrderId) -> Size {
+        self.open_orders
+            .remove(&order_id)
+            .map_or(0.0, |order| order.remaining_size)
+    }
+
+    /// Check an order against what the execution adapters accept.
+    fn validate(order: &StrategyOrder) -> Result<(), String> {
+        if !order.price.is_finite() || order.price <= 0.0 || order.price >= 1.0 {
+            return Err(format!("Invalid price {} (must be in (0, 1))", order.price));
+        }
+        if !order.size.is_finite() || order.size <= 0.0 {
+            return Err(format!("Invalid size {}", order.size));
+        }
+        if order.post_only {
+            return Err("Post-only orders are not supported by the execution adapters".to_string());
+        }
+        Ok(())
+    }
+
+    /// Assign an id to a validated order and track it as open.
+    fn register(&mut self, order: &StrategyOrder) -> OrderId {
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        self.open_orders.insert(
+            order_id,
+            OpenOrder {
+                order_id,
+                client_order_id: order.client_order_id.clone(),
+                token_id: order.token_id.clone(),
+                side: order.side,
+                price: order.price,
+                original_size: order.size,
+                remaining_size: order.size,
+                created_at: self.now,
+            },
+        );
+        order_id
+    }
+
+    /// A queued order, single or part of a batch.
+    fn queued_mut(&mut self, order_id: OrderId) -> Option<&mut StrategyOrder> {
+        self.outbox
+            .iter_mut()
+            .chain(self.batches.iter_mut().flat_map(|(_, legs)| legs.iter_mut()))
+            .find(|(id, _)| *id == order_id)
+            .map(|(_, order)| order)
+    }
+
+    /// Remove a queued order. The rest of a partial batch is still submitted;
+    /// an all-or-none batch is dropped whole and its other orders rejected.
+    fn take_queued(&mut self, order_id: OrderId) -> Option<StrategyOrder> {
+        if let Some(index) = self.outbox.iter().position(|(id, _)| *id == order_id) {
+            return self.outbox.remove(index).map(|(_, order)| order);
+        }
+        let (batch, leg) = self.batches.iter().enumerate().find_map(|(batch, (_, legs))| {
+            legs.iter()
+                .position(|(id, _)| *id == order_id)
+                .map(|leg| (batch, leg))
+        })?;
+        if self.batches[batch].0 == BatchMode::AllOrNone {
+            let (_, mut legs) = self.batches.remove(batch)?;
+            let (_, order) = legs.remove(leg);
+            for (other_id, other) in legs {
+                self.open_orders.remove(&other_id);
+                self.rejected.push_back((
+                    other_id,
+                    other.client_order_id,
+                    format!("Batch aborted: order {} was cancelled", order_id),
+                ));
+            }
+            return Some(order);
+        }
+        let legs = &mut self.batches[batch].1;
+        let (_, order) = legs.remove(leg);
+        if legs.is_empty() {
+            self.batches.remove(batch);
+        }
+        Some(order)
+    }
+}
+
+impl OrderSender for LiveOrderSender {
+    fn send_order(&mut self, order: StrategyOrder) -> Result<OrderId, String> {
+        Self::validate(&order)?;
+        let order_id = self.register(&order);
+        self.outbox.push_back((order_id, order));
+        Ok(order_id)
+    }
+
+    fn send_batch(
+        &mut self,
+        orders: Vec<StrategyOrder>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<OrderId, String>>, String> {
+        if orders.len() > POLYMARKET_MAX_BATCH_SIZE {
+            return Err(format!(
+                "Batch of {} orders exceeds venue limit {}",
+                orders.len(),
+                POLYMARKET_MAX_BATCH_SIZE
+            ));
+        }
+        let checks: Vec<Result<(), String>> = orders.iter().map(Self::validate).collect();
+        if mode == BatchMode::AllOrNone && checks.iter().any(Result::is_err) {
+            return Ok(checks
+                .into_iter()
+                .map(|check| {
+                    check.and(Err("Batch aborted: another order failed validation".to_string()))
+                })
+                .collect());
+        }
+
+        let mut results = Vec::with_capacity(orders.len());
+        let mut legs = Vec::new();
+        for (order, check) in orders.into_iter().zip(checks) {
+            results.push(check.map(|()| {
+                let order_id = self.register(&order);
+                legs.push((order_id, order));
+                order_id
+            }));
+        }
+        if !legs.is_empty() {
+            self.batches.push_back((mode, legs));
+        }
+        Ok(results)
+    }
+
+    fn send_cancel(&mut self, cancel: StrategyCancel) -> Result<(), String> {
+        let order_id = cancel.order_id;
+        let Some(order) = self.take_queued(order_id) else {
+            if !self.open_orders.contains_key(&order_id) {
+                return Err(format!("Order {} not found", order_id));
+            }
+            if !self.cancel_requests.contains(&order_id) {
+                self.cancel_requests.push_back(order_id);
+            }
+            return Ok(());
+        };
+        self.open_orders.remove(&order_id);
+        self.cancelled.push_back((order_id, order.size));
+        Ok(())
+    }
+
+    fn send_replace(&mut self, replace: StrategyReplace) -> Result<(), String> {
+        if !replace.price.is_finite() || replace.price <= 0.0 || replace.price >= 1.0 {
+            return Err(format!("Invalid price {} (must be in (0, 1))", replace.price));
+        }
+        if !replace.size.is_finite() || replace.size <= 0.0 {
+            return Err(format!("Invalid size {}", replace.size));
+        }
+
+        let order_id = replace.order_id;
+        let Some(order) = self.queued_mut(order_id) else {
+            if self.open_orders.contains_key(&order_id) {
+                return Err(format!(
+                    "Order {} is already at the venue; cancel and resend to amend",
+                    order_id
+                ));
+            }
+            return Err(format!("Order {} not found", order_id));
+        };
+        order.price = replace.price;
+        order.size = replace.size;
+        if let Some(open) = self.open_orders.get_mut(&order_id) {
+            open.price = replace.price;
+            open.original_size = replace.size;
+            open.remaining_size = replace.size;
+        }
+        self.replaced.push_back((order_id, replace.price, replace.size));
+        Ok(())
+    }
+
+    fn cancel_all(&mut self, token_id: &str) -> Result<usize, String> {
+        let ids: Vec<OrderId> = self
+            .open_orders
+            .values()
+            .filter(|order| order.token_id == token_id)
+            .map(|order| order.order_id)
+            .collect();
+        for order_id in &ids {
+            self.send_cancel(StrategyCancel {
+                order_id: *order_id,
+                client_order_id: None,
+            })?;
+        }
+        Ok(ids.len())
+    }
+
+    fn get_position(&self, token_id: &str) -> Position {
+        self.positions.get(token_id).cloned().unwrap_or_else(|| Position {
+            token_id: token_id.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn get_all_positions(&self) -> HashMap<String, Position> {
+        self.positions.clone()
+    }
+
+    fn get_open_orders(&self) -> Vec<OpenOrder> {
+        self.open_orders.values().cloned().collect()
+    }
+
+    fn now(&self) -> Nanos {
+        self.now
+    }
+
+    fn schedule_timer(&mut self, delay_ns: Nanos, payload: Option<String>) -> u64 {
+        let timer_id = self.next_timer_id;
+        self.next_timer_id += 1;
+        let fire_time = self.now + delay_ns.max(0);
+        self.timers.insert((fire_time, timer_id), payload);
+        self.timer_fire_times.insert(timer_id, fire_time);
+        timer_id
+    }
+
+    fn cancel_timer(&mut self, timer_id: u64) -> bool {
+        match self.timer_fire_times.remove(&timer_id) {
+            Some(fire_time) => self.timers.remove(&(fire_time, timer_id)).is_some(),
+            None => false,
+        }
+    }
+}
+
+// ============================================================================
+// Metrics
+// ============================================================================
+
+#[derive(Debug, Default)]
+pub struct LiveStrategyMetrics {
+    book_updates: AtomicU64,
+    stale_books: AtomicU64,
+    trades: AtomicU64,
+    trade_drops: AtomicU64,
+    timers_fired: AtomicU64,
+    orders_submitted: AtomicU64,
+    fills: AtomicU64,
+    rejects: AtomicU64,
+    deferred_flushes: AtomicU64,
+    queue_drops: AtomicU64,
+    exec_event_drops: AtomicU64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LiveStrategyMetricsSummary {
+    pub book_updates: u64,
+    pub stale_books: u64,
+    pub trades: u64,
+    pub trade_drops: u64,
+    pub timers_fired: u64,
+    pub orders_submitted: u64,
+    pub fills: u64,
+    pub rejects: u64,
+    pub deferred_flushes: u64,
+    pub queue_drops: u64,
+    pub exec_event_drops: u64,
+}
+
+impl LiveStrategyMetrics {
+    pub fn summary(&self) -> LiveStrategyMetricsSummary {
+        LiveStrategyMetricsSummary {
+            book_updates: self.book_updates.load(Ordering::Relaxed),
+            stale_books: self.stale_books.load(Ordering::Relaxed),
+            trades: self.trades.load(Ordering::Relaxed),
+            trade_drops: self.trade_drops.load(Ordering::Relaxed),
+            timers_fired: self.timers_fired.load(Ordering::Relaxed),
+            orders_submitted: self.orders_submitted.load(Ordering::Relaxed),
+            fills: self.fills.load(Ordering::Relaxed),
+            rejects: self.rejects.load(Ordering::Relaxed),
+            deferred_flushes: self.deferred_flushes.load(Ordering::Relaxed),
+            queue_drops: self.queue_drops.load(Ordering::Relaxed),
+            exec_event_drops: self.exec_event_drops.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// ============================================================================
+// LiveStrategyHost
+// ============================================================================
+
+/// An order resting at the venue.
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    order_id: OrderId,
+    client_order_id: String,
+    /// Cumulative shares filled, as last seen
+    filled: Size,
+}
+
+/// Event pump driving a `Strategy` from live books, trades, timers and fills.
+pub struct LiveStrategyHost {
+    strategy: Box<dyn Strategy>,
+    sender: LiveOrderSender,
+    exec: Arc<dyn ExecutionAdapter>,
+    book_store: Arc<BookStore>,
+    cfg: LiveStrategyConfig,
+    tokens: HashSet<String>,
+    metrics: Arc<LiveStrategyMetrics>,
+    /// Resting orders by venue order id
+    resting: HashMap<String, RestingOrder>,
+    venue_ids: HashMap<OrderId, String>,
+    recorder: Option<LiveSessionRecorder>,
+    control: Option<Arc<TradingControl>>,
+}
+
+impl LiveStrategyHost {
+    pub fn new(
+        strategy: Box<dyn Strategy>,
+        exec: Arc<dyn ExecutionAdapter>,
+        book_store: Arc<BookStore>,
+        cfg: LiveStrategyConfig,
+    ) -> Self {
+        let tokens = cfg.tokens.iter().cloned().collect();
+        Self {
+            strategy,
+            sender: LiveOrderSender::new(),
+            exec,
+            book_store,
+            cfg,
+            tokens,
+            metrics: Arc::new(LiveStrategyMetrics::default()),
+            resting: HashMap::new(),
+            venue_ids: HashMap::new(),
+            recorder: None,
+            control: None,
+        }
+    }
+
+    /// Gate order submission on trading halts and report order outcomes.
+    pub fn with_trading_control(mut self, control: Arc<TradingControl>) -> Self {
+        control.register_adapter(&self.cfg.strategy_name, self.exec.clone());
+        self.control = Some(control);
+        self
+    }
+
+    /// Journal orders and fills for live-vs-backtest reconciliation.
+    pub fn with_recorder(mut self, recorder: LiveSessionRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    pub fn recorder(&self) -> Option<&LiveSessionRecorder> {
+        self.recorder.as_ref()
+    }
+
+    fn observe_event(&mut self) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.observe_event();
+        }
+    }
+
+    fn record(&mut self, record: TraceRecord) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record(record, self.sender.now);
+        }
+    }
+
+    fn record_fill(&mut self, order_id: OrderId, price: f64, size: Size, is_maker: bool, fee: f64) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record_fill(order_id, price, size, is_maker, fee, self.sender.now);
+        }
+    }
+
+    pub fn metrics(&self) -> &Arc<LiveStrategyMetrics> {
+        &self.metrics
+    }
+
+    pub fn sender(&self) -> &LiveOrderSender {
+        &self.sender
+    }
+
+    /// Invoke a strategy callback with a context over the live sender.
+    fn dispatch(&mut self, callback: impl FnOnce(&mut dyn Strategy, &mut StrategyContext)) {
+        let mut ctx = StrategyContext {
+            timestamp: self.sender.now,
+            orders: &mut self.sender,
+            params: &self.cfg.params,
+        };
+        callback(self.strategy.as_mut(), &mut ctx);
+    }
+
+    pub async fn start(&mut self, now: Nanos) {
+        self.sender.set_time(now);
+        self.dispatch(|strategy, ctx| strategy.on_start(ctx));
+        self.flush().await;
+    }
+
+    pub async fn stop(&mut self, now: Nanos) {
+        self.sender.set_time(now);
+        self.dispatch(|strategy, ctx| strategy.on_stop(ctx));
+        self.flush().await;
+    }
+
+    /// Deliver the current book for `token_id` if it is fresh.
+    pub async fn handle_book_update(&mut self, token_id: &str, now: Nanos) {
+        let Some(book) = self
+            .book_store
+            .get_book_if_fresh(token_id, self.cfg.book_max_stale_ms)
+        else {
+            self.metrics.stale_books.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        self.metrics.book_updates.fetch_add(1, Ordering::Relaxed);
+        self.observe_event();
+
+        let snapshot = BookSnapshot {
+            token_id: token_id.to_string(),
+            bids: book.bids.iter().map(|l| Level::new(l.price, l.size)).collect(),
+            asks: book.asks.iter().map(|l| Level::new(l.price, l.size)).collect(),
+            timestamp: now,
+            exchange_seq: book.sequence.unwrap_or(0),
+        };
+        self.sender.set_time(now);
+        self.dispatch(|strategy, ctx| strategy.on_book_update(ctx, &snapshot));
+        self.flush().await;
+    }
+
+    pub async fn handle_trade(&mut self, trade: &PublicTradePrint, now: Nanos) {
+        if !self.tokens.contains(&trade.token_id) {
+            return;
+        }
+        self.metrics.trades.fetch_add(1, Ordering::Relaxed);
+        self.observe_event();
+
+        let print = TradePrint {
+            token_id: trade.token_id.clone(),
+            price: trade.price,
+            size: trade.size,
+            aggressor_side: if trade.is_buy { Side::Buy } else { Side::Sell },
+            timestamp: now,
+            trade_id: None,
+        };
+        self.sender.set_time(now);
+        self.dispatch(|strategy, ctx| strategy.on_trade(ctx, &print));
+        self.flush().await;
+    }
+
+    /// Fire every timer due at or before `now`.
+    pub async fn fire_due_timers(&mut self, now: Nanos) {
+        self.sender.set_time(now);
+        for (timer_id, scheduled_time, payload) in self.sender.take_due_timers(now) {
+            self.metrics.timers_fired.fetch_add(1, Ordering::Relaxed);
+            self.observe_event();
+            let timer = TimerEvent {
+                timer_id,
+                scheduled_time,
+                actual_time: now,
+                payload,
+            };
+            self.dispatch(|strategy, ctx| strategy.on_timer(ctx, &timer));
+            self.flush().await;
+        }
+    }
+
+    /// Submit queued orders and deliver the resulting callbacks, which may
+    /// queue further orders.
+    async fn flush(&mut self) {
+        for _ in 0..self.cfg.max_flush_rounds {
+            if !self.sender.has_pending() {
+                return;
+            }
+            for (order_id, cancelled_qty) in self.sender.take_cancel_acks() {
+                let ack = CancelAck {
+                    order_id,
+                    cancelled_qty,
+                    timestamp: self.sender.now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_cancel_ack(ctx, &ack));
+            }
+            for (order_id, client_order_id, reason) in self.sender.take_rejects() {
+                self.metrics.rejects.fetch_add(1, Ordering::Relaxed);
+                let reject = OrderReject {
+                    order_id,
+                    client_order_id: Some(client_order_id),
+                    reason,
+                    timestamp: self.sender.now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_order_reject(ctx, &reject));
+            }
+            // Amended before submission, so the venue only ever sees the new terms
+            for (order_id, price, leaves_qty) in self.sender.take_replace_acks() {
+                let ack = ReplaceAck {
+                    order_id,
+                    price,
+                    leaves_qty,
+                    kept_priority: true,
+                    timestamp: self.sender.now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_replace_ack(ctx, &ack));
+            }
+            for (order_id, order) in self.sender.take_submissions() {
+                self.submit(order_id, order).await;
+            }
+            for (mode, legs) in self.sender.take_batches() {
+                self.submit_batch(mode, legs).await;
+            }
+            for order_id in self.sender.take_cancel_requests() {
+                self.cancel_resting(order_id).await;
+            }
+        }
+        if self.sender.has_pending() {
+            self.metrics.deferred_flushes.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                strategy = self.strategy.name(),
+                "Flush round limit reached; remaining orders wait for the next event"
+            );
+        }
+    }
+
+    fn order_request(order: &StrategyOrder) -> OrderRequest {
+        OrderRequest {
+            client_order_id: order.client_order_id.clone(),
+            token_id: order.token_id.clone(),
+            side: match order.side {
+                Side::Buy => OrderSide::Buy,
+                Side::Sell => OrderSide::Sell,
+            },
+            price: order.price,
+            notional_usdc: order.price * order.size,
+            tif: match order.time_in_force {
+                StrategyTimeInForce::Ioc => TimeInForce::Ioc,
+                StrategyTimeInForce::Fok => TimeInForce::Fok,
+                _ => TimeInForce::Gtc,
+            },
+            market_slug: None,
+            outcome: None,
+        }
+    }
+
+    /// Why orders for `token_id` may not be placed right now, if halted.
+    fn halt_reason(&self, token_id: &str) -> Option<String> {
+        let halt = self
+            .control
+            .as_ref()
+            .and_then(|c| c.halt_for_token(&self.cfg.strategy_name, token_id))?;
+        Some(format!("trading halted ({}): {}", halt.scope.key(), halt.reason))
+    }
+
+    fn record_submit(&mut self, order_id: OrderId, order: &StrategyOrder) {
+        self.metrics.orders_submitted.fetch_add(1, Ordering::Relaxed);
+        self.record(TraceRecord::order_submit(
+            order_id,
+            &order.token_id,
+            order.side,
+            order.price,
+            order.size,
+        ));
+    }
+
+    async fn submit(&mut self, order_id: OrderId, order: StrategyOrder) {
+        self.record_submit(order_id, &order);
+
+        let now = self.sender.now;
+        let placed = match self.halt_reason(&order.token_id) {
+            Some(reason) => Err(anyhow::anyhow!(reason)),
+            None => {
+                let placed = self.exec.place_order(Self::order_request(&order)).await;
+                if let Some(control) = &self.control {
+                    control
+                        .record_order_outcome(&self.cfg.strategy_name, placed.is_err())
+                        .await;
+                }
+                placed
+            }
+        };
+        self.settle(order_id, order, placed, now);
+    }
+
+    /// Submit the legs of a batch in one venue call. No leg of an all-or-none
+    /// batch is sent while any of them is halted, and if the venue refuses
+    /// some legs the resting remainder of the others is cancelled. Fills on
+    /// arrival cannot be undone and are reported as usual.
+    async fn submit_batch(&mut self, mode: BatchMode, legs: Vec<(OrderId, StrategyOrder)>) {
+        let now = self.sender.now;
+        let mut halts = Vec::with_capacity(legs.len());
+        let mut requests = Vec::new();
+        for (order_id, order) in &legs {
+            self.record_submit(*order_id, order);
+            let halt = self.halt_reason(&order.token_id);
+            if halt.is_none() {
+                requests.push(Self::order_request(order));
+            }
+            halts.push(halt);
+        }
+
+        let blocked = mode == BatchMode::AllOrNone && halts.iter().any(Option::is_some);
+        let placed = if blocked || requests.is_empty() {
+            Vec::new()
+        } else {
+            self.exec.place_orders(requests).await
+        };
+        if let Some(control) = &self.control {
+            for result in &placed {
+                control
+                    .record_order_outcome(&self.cfg.strategy_name, result.is_err())
+                    .await;
+            }
+        }
+
+        let mut placed = placed.into_iter();
+        let mut accepted = Vec::new();
+        let mut refused = false;
+        for ((order_id, order), halt) in legs.into_iter().zip(halts) {
+            let result = match halt {
+                Some(reason) => Err(anyhow::anyhow!(reason)),
+                None if blocked => Err(anyhow::anyhow!("batch not sent: another order is halted")),
+                None => placed
+                    .next()
+                    .unwrap_or_else(|| Err(anyhow::anyhow!("no result for batch order"))),
+            };
+            match result {
+                Ok(_) => accepted.push(order_id),
+                Err(_) => refused = true,
+            }
+            self.settle(order_id, order, result, now);
+        }
+
+        if mode == BatchMode::AllOrNone && refused {
+            for order_id in accepted {
+                self.cancel_resting(order_id).await;
+            }
+        }
+    }
+
+    /// Report a placement outcome to the strategy and track any resting remainder.
+    fn settle(
+        &mut self,
+        order_id: OrderId,
+        order: StrategyOrder,
+        placed: anyhow::Result<ExecAck>,
+        now: Nanos,
+    ) {
+        let ack = match placed {
+            Ok(ack) => ack,
+            Err(e) => {
+                self.metrics.rejects.fetch_add(1, Ordering::Relaxed);
+                self.record(TraceRecord::order_reject(order_id, &e.to_string()));
+                self.sender.complete(order_id);
+                let reject = OrderReject {
+                    order_id,
+                    client_order_id: Some(order.client_order_id),
+                    reason: e.to_string(),
+                    timestamp: now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_order_reject(ctx, &reject));
+                return;
+            }
+        };
+
+        self.record(TraceRecord::order_ack(order_id));
+        let order_ack = OrderAck {
+            order_id,
+            client_order_id: Some(order.client_order_id.clone()),
+            timestamp: now,
+        };
+        self.dispatch(|strategy, ctx| strategy.on_order_ack(ctx, &order_ack));
+
+        let filled = if ack.filled_price > 0.0 {
+            (ack.filled_notional_usdc / ack.filled_price).clamp(0.0, order.size)
+        } else {
+            0.0
+        };
+        let leaves_qty = (order.size - filled).max(0.0);
+
+        if filled > QTY_EPSILON {
+            self.metrics.fills.fetch_add(1, Ordering::Relaxed);
+            self.record_fill(order_id, ack.filled_price, filled, false, ack.fees_usdc);
+            self.sender
+                .apply_fill(order_id, ack.filled_price, filled, ack.fees_usdc);
+            let fill = FillNotification {
+                order_id,
+                client_order_id: Some(order.client_order_id.clone()),
+                price: ack.filled_price,
+                size: filled,
+                is_maker: false,
+                leaves_qty,
+                fee: ack.fees_usdc,
+                timestamp: now,
+            };
+            self.dispatch(|strategy, ctx| strategy.on_fill(ctx, &fill));
+        }
+
+        if leaves_qty <= QTY_EPSILON {
+            return;
+        }
+        if ack.resting_size > QTY_EPSILON {
+            if let Some(control) = &self.control {
+                control.track_order(&self.cfg.strategy_name, &ack.order_id);
+            }
+            self.venue_ids.insert(order_id, ack.order_id.clone());
+            self.resting.insert(
+                ack.order_id,
+                RestingOrder {
+                    order_id,
+                    client_order_id: order.client_order_id,
+                    filled,
+                },
+            );
+        } else {
+            self.record(TraceRecord::cancel_ack(order_id, leaves_qty));
+            self.sender.complete(order_id);
+            let cancel = CancelAck {
+                order_id,
+                cancelled_qty: leaves_qty,
+                timestamp: now,
+            };
+            self.dispatch(|strategy, ctx| strategy.on_cancel_ack(ctx, &cancel));
+        }
+    }
+
+    /// Stop tracking an order that no longer rests at the venue.
+    fn forget_resting(&mut self, venue_id: &str, order_id: OrderId) {
+        self.resting.remove(venue_id);
+        self.venue_ids.remove(&order_id);
+        if let Some(control) = &self.control {
+            control.untrack_order(&self.cfg.strategy_name, venue_id);
+        }
+    }
+
+    /// Cancel an order resting at the venue. Orders already done are ignored.
+    async fn cancel_resting(&mut self, order_id: OrderId) {
+        let Some(venue_id) = self.venue_ids.get(&order_id).cloned() else {
+            return;
+        };
+        self.record(TraceRecord::cancel_request(order_id));
+        match self.exec.cancel_order(&venue_id).await {
+            Ok(()) => {
+                self.forget_resting(&venue_id, order_id);
+                let cancel = CancelAck {
+                    order_id,
+                    cancelled_qty: self.sender.complete(order_id),
+                    timestamp: self.sender.now,
+                };
+                self.record(TraceRecord::cancel_ack(order_id, cancel.cancelled_qty));
+                self.dispatch(|strategy, ctx| strategy.on_cancel_ack(ctx, &cancel));
+            }
+            // The order may have filled meanwhile; the event stream settles it
+            Err(e) => warn!(order_id, venue_id = %venue_id, error = %e, "Venue cancel failed"),
+        }
+    }
+
+    /// Deliver fills and cancellations of resting orders. Fills are applied by
+    /// cumulative size, so events already reported by `place_order` are no-ops.
+    pub async fn handle_execution_event(&mut self, event: &ExecutionEvent, now: Nanos) {
+        let Some(resting) = event.order_id().and_then(|id| self.resting.get(id)).cloned() else {
+            return;
+        };
+        self.sender.set_time(now);
+        self.observe_event();
+
+        match event {
+            ExecutionEvent::Fill {
+                order_id: venue_id,
+                price,
+                filled_size,
+                remaining_size,
+                fee_usdc,
+                is_maker,
+                ..
+            } => {
+                let size = filled_size - resting.filled;
+                if size <= QTY_EPSILON {
+                    return;
+                }
+                self.metrics.fills.fetch_add(1, Ordering::Relaxed);
+                self.record_fill(resting.order_id, *price, size, *is_maker, *fee_usdc);
+                self.sender.apply_fill(resting.order_id, *price, size, *fee_usdc);
+                if *remaining_size <= QTY_EPSILON {
+                    self.forget_resting(venue_id, resting.order_id);
+                    self.sender.complete(resting.order_id);
+                } else if let Some(entry) = self.resting.get_mut(venue_id) {
+                    entry.filled = *filled_size;
+                }
+                let fill = FillNotification {
+                    order_id: resting.order_id,
+                    client_order_id: Some(resting.client_order_id),
+                    price: *price,
+                    size,
+                    is_maker: *is_maker,
+                    leaves_qty: *remaining_size,
+                    fee: *fee_usdc,
+                    timestamp: now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_fill(ctx, &fill));
+            }
+            ExecutionEvent::Cancelled {
+                order_id: venue_id,
+                cancelled_size,
+                ..
+            } => {
+                self.forget_resting(venue_id, resting.order_id);
+                self.sender.complete(resting.order_id);
+                self.record(TraceRecord::cancel_ack(resting.order_id, *cancelled_size));
+                let cancel = CancelAck {
+                    order_id: resting.order_id,
+                    cancelled_qty: *cancelled_size,
+                    timestamp: now,
+                };
+                self.dispatch(|strategy, ctx| strategy.on_cancel_ack(ctx, &cancel));
+            }
+            ExecutionEvent::Ack { .. } | ExecutionEvent::Rejected { .. } => return,
+        }
+        self.flush().await;
+    }
+
+    /// Run until `shutdown` is set to true. `book_rx` yields token ids whose
+    /// book changed.
+    pub async fn run(
+        mut self,
+        mut book_rx: mpsc::Receiver<String>,
+        mut trade_rx: broadcast::Receiver<PublicTradePrint>,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        info!(
+            strategy = self.strategy.name(),
+            tokens = self.tokens.len(),
+            "Live strategy host started"
+        );
+        let mut exec_rx = self.exec.subscribe_events();
+        self.start(wall_clock_ns()).await;
+        let mut trades_open = true;
+        let mut exec_open = true;
+
+        loop {
+            let timer_wait = match self.sender.next_timer_time() {
+                Some(fire_time) => Duration::from_nanos((fire_time - wall_clock_ns()).max(0) as u64),
+                None => Duration::from_secs(3600),
+            };
+
+            tokio::select! {
+                Ok(()) = shutdown.changed() => {
+                    if *shutdown.borrow() {
+                        break;
+                    }
+                }
+                Some(token_id) = book_rx.recv() => {
+                    self.handle_book_update(&token_id, wall_clock_ns()).await;
+                }
+                trade = trade_rx.recv(), if trades_open => match trade {
+                    Ok(trade) => self.handle_trade(&trade, wall_clock_ns()).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        self.metrics.trade_drops.fetch_add(skipped, Ordering::Relaxed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        warn!("Trade print channel closed; continuing without trades");
+                        trades_open = false;
+                    }
+                },
+                event = exec_rx.recv(), if exec_open => match event {
+                    Ok(event) => self.handle_execution_event(&event, wall_clock_ns()).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        self.metrics.exec_event_drops.fetch_add(skipped, Ordering::Relaxed);
+                        warn!(skipped, "Execution events dropped; resting order state may lag");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        debug!("Adapter has no execution event stream");
+                        exec_open = false;
+                    }
+                },
+                _ = tokio::time::sleep(timer_wait) => {
+                    self.fire_due_timers(wall_clock_ns()).await;
+                }
+            }
+        }
+
+        self.stop(wall_clock_ns()).await;
+        info!(strategy = self.strategy.name(), "Live strategy host stopped");
+    }
+}
+
+fn wall_clock_ns() -> Nanos {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+}
+
+/// Handle to a spawned live strategy.
+pub struct LiveStrategyHandle {
+    pub metrics: Arc<LiveStrategyMetrics>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl LiveStrategyHandle {
+    /// Stop the strategy (`on_stop` runs and its orders are flushed).
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(true);
+    }
+}
+
+/// Subscribe to the configured tokens and spawn the strategy host.
+///
+/// Tokens are added to the shared book cache's universe rather than
+/// replacing it, so other consumers' subscriptions stay intact.
+pub async fn spawn_live_strategy(
+    book_cache: Arc<HftBookCache>,
+    exec: Arc<dyn ExecutionAdapter>,
+    strategy: Box<dyn Strategy>,
+    cfg: LiveStrategyConfig,
+    control: Option<Arc<TradingControl>>,
+) -> LiveStrategyHandle {
+    for token_id in &cfg.tokens {
+        book_cache.request_subscribe(token_id);
+    }
+
+    let mut host = LiveStrategyHost::new(strategy, exec, book_cache.book_store().clone(), cfg);
+    if let Some(control) = control {
+        host = host.with_trading_control(control);
+    }
+    if let Some(path) = host.cfg.record_path.clone() {
+        let header = LiveSessionHeader::new(
+            &host.cfg.strategy_name,
+            &host.cfg.params,
+            host.cfg.tokens.clone(),
+            wall_clock_ns(),
+        );
+        match LiveSessionRecorder::create(std::path::Path::new(&path), header) {
+            Ok(recorder) => {
+                info!(path = %path, "Live session journal enabled");
+                host = host.with_recorder(recorder);
+            }
+            Err(e) => warn!(error = %e, "Live session journal disabled"),
+        }
+    }
+    let metrics = host.metrics().clone();
+
+    let (tx, rx) = mpsc::channel::<String>(host.cfg.event_queue_size);
+    for token_id in &host.cfg.tokens {
+        let Some(mut update_rx) = book_cache.subscribe_updates(token_id) else {
+            warn!(token_id = %token_id, "No book state for token; book updates disabled");
+            continue;
+        };
+        let tx = tx.clone();
+        let token = token_id.clone();
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            while update_rx.changed().await.is_ok() {
+                if tx.try_send(token.clone()).is_err() {
+                    metrics.queue_drops.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+    }
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(host.run(rx, book_cache.subscribe_trades(), shutdown_rx));
+
+    LiveStrategyHandle {
+        metrics,
+        shutdown_tx,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest_v2::example_strategy::MomentumStrategy;
+    use crate::backtest_v2::run_diff::TraceRecordKind;
+    use crate::scrapers::{BookStoreConfig, PriceLevel};
+    use crate::vault::{PaperExecutionAdapter, PaperExecutionConfig};
+    use anyhow::{anyhow, Result};
+    use parking_lot::Mutex;
+
+    /// Fills `fill_ratio` of every order at its limit price (rejects if 0).
+    struct MockExecution {
+        fill_ratio: f64,
+        requests: Mutex<Vec<OrderRequest>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionAdapter for MockExecution {
+        async fn place_order(&self, req: OrderRequest) -> Result<ExecAck> {
+            self.requests.lock().push(req.clone());
+            if self.fill_ratio <= 0.0 {
+                return Err(anyhow!("mock reject"));
+            }
+            Ok(ExecAck {
+                order_id: req.client_order_id,
+                filled_notional_usdc: req.notional_usdc * self.fill_ratio,
+                filled_price: req.price,
+                filled_at: 0,
+                fees_usdc: 0.01,
+                slippage_bps: 0.0,
+                latency_ms: 0,
+                resting_size: 0.0,
+            })
+        }
+    }
+
+    /// Rests every order in full, except orders for token "REJECT".
+    struct RestingExecution {
+        cancels: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionAdapter for RestingExecution {
+        async fn place_order(&self, req: OrderRequest) -> Result<ExecAck> {
+            if req.token_id == "REJECT" {
+                return Err(anyhow!("mock reject"));
+            }
+            Ok(ExecAck {
+                order_id: req.client_order_id,
+                filled_notional_usdc: 0.0,
+                filled_price: 0.0,
+                filled_at: 0,
+                fees_usdc: 0.0,
+                slippage_bps: 0.0,
+                latency_ms: 0,
+                resting_size: req.notional_usdc / req.price,
+            })
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> Result<()> {
+            self.cancels.lock().push(order_id.to_string());
+            Ok(())
+        }
+    }
+
+    /// Buys once per book update (IOC unless `gtc`) and logs every callback.
+    struct ProbeStrategy {
+        log: Arc<Mutex<Vec<String>>>,
+        gtc: bool,
+    }
+
+    impl Strategy for ProbeStrategy {
+        fn on_book_update(&mut self, ctx: &mut StrategyContext, book: &BookSnapshot) {
+            self.log.lock().push(format!("book {}", book.best_ask().unwrap().price));
+            let order = StrategyOrder::limit("probe", &book.token_id, Side::Buy, 0.55, 10.0);
+            let order = if self.gtc { order } else { order.ioc() };
+            ctx.orders.send_order(order).unwrap();
+        }
+        fn on_trade(&mut self, _ctx: &mut StrategyContext, trade: &TradePrint) {
+            self.log.lock().push(format!("trade {}", trade.size));
+        }
+        fn on_timer(&mut self, _ctx: &mut StrategyContext, timer: &TimerEvent) {
+            self.log.lock().push(format!("timer {}", timer.payload.clone().unwrap_or_default()));
+        }
+        fn on_order_ack(&mut self, _ctx: &mut StrategyContext, _ack: &OrderAck) {
+            self.log.lock().push("ack".to_string());
+        }
+        fn on_order_reject(&mut self, _ctx: &mut StrategyContext, _reject: &OrderReject) {
+            self.log.lock().push("reject".to_string());
+        }
+        fn on_fill(&mut self, _ctx: &mut StrategyContext, fill: &FillNotification) {
+            self.log
+                .lock()
+                .push(format!("fill {:.2} leaves {:.2}", fill.size, fill.leaves_qty));
+        }
+        fn on_cancel_ack(&mut self, _ctx: &mut StrategyContext, ack: &CancelAck) {
+            self.log.lock().push(format!("cancel {:.2}", ack.cancelled_qty));
+        }
+        fn on_start(&mut self, ctx: &mut StrategyContext) {
+            ctx.orders.schedule_timer(1_000, Some("tick".to_string()));
+        }
+        fn name(&self) -> &str {
+            "probe"
+        }
+    }
+
+    fn book_store() -> Arc<BookStore> {
+        let store = BookStore::new(BookStoreConfig::default());
+        store.apply_snapshot(
+            "TOKEN",
+            vec![PriceLevel { price: 0.45, size: 100.0 }],
+            vec![PriceLevel { price: 0.55, size: 100.0 }],
+            Some(1),
+        );
+        store
+    }
+
+    fn config() -> LiveStrategyConfig {
+        LiveStrategyConfig {
+            tokens: vec!["TOKEN".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn probe_host(fill_ratio: f64) -> (LiveStrategyHost, Arc<Mutex<Vec<String>>>, Arc<MockExecution>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let exec = Arc::new(MockExecution {
+            fill_ratio,
+            requests: Mutex::new(Vec::new()),
+        });
+        let host = LiveStrategyHost::new(
+            Box::new(ProbeStrategy {
+                log: log.clone(),
+                gtc: false,
+            }),
+            exec.clone(),
+            book_store(),
+            config(),
+        );
+        (host, log, exec)
+    }
+
+    #[test]
+    fn test_parse_strategy_params_keeps_numeric_token_ids() {
+        let token = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+        let params = parse_strategy_params(&format!(
+            "token_id={}, half_spread=0.01,label=mm,bad",
+            token
+        ));
+
+        assert_eq!(params.get_string("token_id"), Some(token));
+        assert_eq!(params.get("half_spread"), Some(0.01));
+        assert_eq!(params.get_string("half_spread"), Some("0.01"));
+        assert_eq!(params.get_string("label"), Some("mm"));
+        assert_eq!(params.get("label"), None);
+        assert!(params.get_string("bad").is_none());
+    }
+
+    #[test]
+    fn test_sender_queues_and_cancels_before_submit() {
+        let mut sender = LiveOrderSender::new();
+        sender.set_time(100);
+
+        let id = sender
+            .send_order(StrategyOrder::limit("a", "TOKEN", Side::Buy, 0.40, 5.0))
+            .unwrap();
+        assert_eq!(sender.get_open_orders().len(), 1);
+        assert!(sender
+            .send_order(StrategyOrder::limit("b", "TOKEN", Side::Buy, 1.20, 5.0))
+            .is_err());
+        assert!(sender
+            .send_order(StrategyOrder::limit("c", "TOKEN", Side::Buy, 0.40, 5.0).post_only())
+            .is_err());
+
+        assert_eq!(sender.cancel_all("TOKEN").unwrap(), 1);
+        assert!(sender.get_open_orders().is_empty());
+        assert_eq!(sender.take_cancel_acks(), vec![(id, 5.0)]);
+        assert!(sender.take_submissions().is_empty());
+
+        let id = sender
+            .send_order(StrategyOrder::limit("d", "TOKEN", Side::Buy, 0.40, 5.0))
+            .unwrap();
+        sender.send_replace(StrategyReplace::new(id, 0.42, 3.0)).unwrap();
+        assert_eq!(sender.take_replace_acks(), vec![(id, 0.42, 3.0)]);
+        let submissions = sender.take_submissions();
+        assert_eq!(submissions[0].1.price, 0.42);
+        assert_eq!(submissions[0].1.size, 3.0);
+        assert!(sender
+            .send_replace(StrategyReplace::new(id, 0.43, 3.0))
+            .unwrap_err()
+            .contains("already at the venue"));
+
+        let legs = vec![
+            StrategyOrder::limit("e", "YES", Side::Buy, 0.40, 5.0),
+            StrategyOrder::limit("f", "NO", Side::Buy, 1.40, 5.0),
+        ];
+        let results = sender.send_batch(legs.clone(), BatchMode::AllOrNone).unwrap();
+        assert!(results.iter().all(|r| r.is_err()));
+        assert!(!sender.has_pending());
+        let results = sender.send_batch(legs, BatchMode::Partial).unwrap();
+        assert!(results[0].is_ok() && results[1].is_err());
+        let oversized = vec![StrategyOrder::limit("g", "YES", Side::Buy, 0.40, 5.0); 16];
+        assert!(sender.send_batch(oversized, BatchMode::Partial).is_err());
+
+        // Cancelling a queued leg drops it from a partial batch, but drops a
+        // whole all-or-none batch and rejects its other legs
+        let legs = vec![
+            StrategyOrder::limit("h", "YES", Side::Buy, 0.40, 5.0),
+            StrategyOrder::limit("i", "NO", Side::Buy, 0.55, 5.0),
+        ];
+        let results = sender.send_batch(legs.clone(), BatchMode::Partial).unwrap();
+        let id = *results[0].as_ref().unwrap();
+        sender.send_cancel(StrategyCancel { order_id: id, client_order_id: None }).unwrap();
+        let results = sender.send_batch(legs, BatchMode::AllOrNone).unwrap();
+        let (id, other) = (*results[0].as_ref().unwrap(), *results[1].as_ref().unwrap());
+        sender.send_cancel(StrategyCancel { order_id: id, client_order_id: None }).unwrap();
+        let batches = sender.take_batches();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].1.len(), 1);
+        assert_eq!(batches[1].1[0].1.client_order_id, "i");
+        let rejects = sender.take_rejects();
+        assert_eq!(rejects.len(), 1);
+        assert_eq!((rejects[0].0, rejects[0].1.as_str()), (other, "i"));
+        assert!(!sender.get_open_orders().iter().any(|o| o.order_id == other));
+
+        let timer = sender.schedule_timer(50, None);
+        assert_eq!(sender.next_timer_time(), Some(150));
+        assert!(sender.cancel_timer(timer));
+        assert!(sender.next_timer_time().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_book_update_partial_fill_cancels_remainder() {
+        let (mut host, log, exec) = probe_host(0.4);
+        host.start(0).await;
+        host.handle_book_update("TOKEN", 10).await;
+
+        assert_eq!(
+            *log.lock(),
+            vec!["book 0.55", "ack", "fill 4.00 leaves 6.00", "cancel 6.00"]
+        );
+        let request = exec.requests.lock()[0].clone();
+        assert_eq!(request.tif, TimeInForce::Ioc);
+        assert!((request.notional_usdc - 5.5).abs() < 1e-9);
+
+        let position = host.sender().get_position("TOKEN");
+        assert!((position.shares - 4.0).abs() < 1e-9);
+        assert!(host.sender().get_open_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recorder_journals_decision_trace() {
+        let (host, _log, _exec) = probe_host(0.4);
+        let header = LiveSessionHeader::new("probe", &StrategyParams::new(), vec!["TOKEN".into()], 0);
+        let mut host = host.with_recorder(LiveSessionRecorder::new(header));
+        host.start(0).await;
+        host.handle_book_update("TOKEN", 10).await;
+
+        let trace = &host.recorder().unwrap().session().trace;
+        let kinds: Vec<_> = trace.records.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TraceRecordKind::OrderSubmit,
+                TraceRecordKind::OrderAck,
+                TraceRecordKind::Fill,
+                TraceRecordKind::CancelAck,
+            ]
+        );
+        assert!(trace.records.iter().all(|r| r.event_index == 1 && r.sim_time_ns == 10));
+        assert!((host.recorder().unwrap().session().fees[&1] - 0.01).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_reject_trade_and_timer_callbacks() {
+        let (mut host, log, _exec) = probe_host(0.0);
+        host.start(0).await;
+        host.handle_book_update("TOKEN", 10).await;
+        host.handle_trade(
+            &PublicTradePrint {
+                token_id: "TOKEN".to_string(),
+                price: 0.5,
+                size: 3.0,
+                is_buy: true,
+                source_time_ms: 0,
+            },
+            20,
+        )
+        .await;
+        host.handle_trade(
+            &PublicTradePrint {
+                token_id: "OTHER".to_string(),
+                price: 0.5,
+                size: 7.0,
+                is_buy: false,
+                source_time_ms: 0,
+            },
+            30,
+        )
+        .await;
+        host.fire_due_timers(500).await;
+        host.fire_due_timers(1_000).await;
+
+        assert_eq!(
+            *log.lock(),
+            vec!["book 0.55", "reject", "trade 3", "timer tick"]
+        );
+        assert_eq!(host.metrics().summary().rejects, 1);
+
+        host.handle_book_update("UNKNOWN", 40).await;
+        assert_eq!(host.metrics().summary().stale_books, 1);
+    }
+
+    #[tokio::test]
+    async fn test_backtest_strategy_runs_unchanged() {
+        let params = StrategyParams::new()
+            .with_string("token_id", "TOKEN")
+            .with_param("lookback", 2.0)
+            .with_param("threshold", 0.01)
+            .with_param("position_size", 10.0);
+        let exec = Arc::new(MockExecution {
+            fill_ratio: 1.0,
+            requests: Mutex::new(Vec::new()),
+        });
+        let store = book_store();
+        let mut host = LiveStrategyHost::new(
+            Box::new(MomentumStrategy::new(&params)),
+            exec.clone(),
+            store.clone(),
+            LiveStrategyConfig {
+                params,
+                ..config()
+            },
+        );
+
+        host.start(0).await;
+        host.handle_book_update("TOKEN", 10).await;
+        assert!(exec.requests.lock().is_empty());
+
+        // Mid 0.50 -> 0.55: momentum signal, IOC buy filled in full
+        store.apply_snapshot(
+            "TOKEN",
+            vec![PriceLevel { price: 0.50, size: 100.0 }],
+            vec![PriceLevel { price: 0.60, size: 100.0 }],
+            Some(2),
+        );
+        host.handle_book_update("TOKEN", 20).await;
+
+        // Already long: no further orders
+        store.apply_snapshot(
+            "TOKEN",
+            vec![PriceLevel { price: 0.55, size: 100.0 }],
+            vec![PriceLevel { price: 0.65, size: 100.0 }],
+            Some(3),
+        );
+        host.handle_book_update("TOKEN", 30).await;
+
+        let summary = host.metrics().summary();
+        assert_eq!(summary.orders_submitted, 1);
+        assert_eq!(summary.fills, 1);
+        assert_eq!(exec.requests.lock()[0].side, OrderSide::Buy);
+        assert!((host.sender().get_position("TOKEN").shares - 10.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_gtc_remainder_rests_then_fills_and_cancels_at_venue() {
+        let exec = Arc::new(PaperExecutionAdapter::new(PaperExecutionConfig {
+            base_latency_ms: 0,
+            latency_jitter_ms: 0,
+            base_slippage_bps: 0.0,
+            slippage_bps_per_1k: 0.0,
+            partial_fill_prob: 1.0,
+            min_fill_ratio: 0.5,
+            reject_prob: 0.0,
+            ..Default::default()
+        }));
+        let mut events = exec.subscribe_events();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut host = LiveStrategyHost::new(
+            Box::new(ProbeStrategy {
+                log: log.clone(),
+                gtc: true,
+            }),
+            exec.clone(),
+            book_store(),
+            config(),
+        );
+
+        // Partial fill on arrival; the remainder rests
+        host.handle_book_update("TOKEN", 10).await;
+        assert_eq!(host.sender().get_open_orders().len(), 1);
+        assert!(!log.lock().iter().any(|l| l.starts_with("cancel")));
+
+        // Events already reported by place_order change nothing
+        while let Ok(event) = events.try_recv() {
+            host.handle_execution_event(&event, 20).await;
+        }
+        assert_eq!(host.metrics().summary().fills, 1);
+
+        for event in exec.match_resting_orders("TOKEN", Some(0.50), Some(0.54)) {
+            host.handle_execution_event(&event, 30).await;
+        }
+        assert_eq!(host.metrics().summary().fills, 2);
+        assert!(log.lock().last().unwrap().ends_with("leaves 0.00"));
+        assert!(host.sender().get_open_orders().is_empty());
+        assert!((host.sender().get_position("TOKEN").shares - 10.0).abs() < 1e-9);
+
+        // A cancel of a resting order goes to the venue
+        host.handle_book_update("TOKEN", 40).await;
+        let order_id = host.sender().get_open_orders()[0].order_id;
+        host.sender
+            .send_cancel(StrategyCancel {
+                order_id,
+                client_order_id: None,
+            })
+            .unwrap();
+        host.flush().await;
+        assert!(log.lock().last().unwrap().starts_with("cancel"));
+        assert!(host.sender().get_open_orders().is_empty());
+        assert!(exec.open_orders(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_all_or_none_batch_unwinds_accepted_legs() {
+        let exec = Arc::new(RestingExecution {
+            cancels: Mutex::new(Vec::new()),
+        });
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut host = LiveStrategyHost::new(
+            Box::new(ProbeStrategy {
+                log: log.clone(),
+                gtc: true,
+            }),
+            exec.clone(),
+            book_store(),
+            config(),
+        );
+        let legs = vec![
+            StrategyOrder::limit("yes", "TOKEN", Side::Buy, 0.40, 5.0),
+            StrategyOrder::limit("no", "REJECT", Side::Buy, 0.55, 5.0),
+        ];
+
+        let results = host.sender.send_batch(legs.clone(), BatchMode::AllOrNone).unwrap();
+        assert!(results.iter().all(|r| r.is_ok()));
+        host.flush().await;
+        assert_eq!(*log.lock(), vec!["ack", "reject", "cancel 5.00"]);
+        assert_eq!(*exec.cancels.lock(), vec!["yes".to_string()]);
+        assert!(host.sender().get_open_orders().is_empty());
+
+        // A partial batch keeps whatever the venue accepted
+        host.sender.send_batch(legs, BatchMode::Partial).unwrap();
+        host.flush().await;
+        assert_eq!(host.sender().get_open_orders().len(), 1);
+        assert_eq!(exec.cancels.lock().len(), 1);
+        assert_eq!(host.metrics().summary().orders_submitted, 4);
+    }
+}
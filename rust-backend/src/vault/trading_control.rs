@@ -0,0 +1,1035 @@
+//! Trading Controls: Kill Switch & Halts
+//!
+//! One place to stop trading, for operators and automatic triggers alike.
+//! Halts apply globally, to a strategy (`FAST15M`, `LONG`, a live host
+//! strategy name) or to an asset (`btc`, `eth`, ...), and persist in `VaultDb`
+//! across restarts. Halting cancels the affected resting orders on every
+//! registered execution adapter, and every halt and resume is written to the
+//! control audit log. Strategies share adapters (and venue accounts), so a
+//! strategy halt cancels only that strategy's own orders: those journaled
+//! under its name in `VaultDb` and those its host registered with
+//! `track_order`.
+//!
+//! Automatic triggers:
+//! - Drawdown: NAV down `max_drawdown_pct` from the initial bankroll (global)
+//! - Feed staleness: `BookStore::get_stale_tokens` (per asset; global when most
+//!   of the book universe is stale)
+//! - Route quality: endpoint health or packet loss alerts (global)
+//! - Reject-rate spike over a strategy's recent orders (per strategy)
+//!
+//! Feed and route halts lift themselves once the condition clears; all others
+//! stay until an operator resumes.
+
+use anyhow::Result;
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::route_quality::{RouteQualityEvent, RouteQualityHandle};
+use crate::scrapers::BookStore;
+use crate::vault::{ExecutionAdapter, VaultControlAuditRecord, VaultDb};
+
+const CONTROL_EVENT_CAPACITY: usize = 256;
+/// Actor recorded for automatic halts and resumes
+const AUTO_ACTOR: &str = "auto";
+
+/// What a halt applies to. Strategy and asset names are case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "scope", content = "target", rename_all = "snake_case")]
+pub enum HaltScope {
+    Global,
+    Strategy(String),
+    Asset(String),
+}
+
+impl HaltScope {
+    pub fn strategy(name: &str) -> Self {
+        HaltScope::Strategy(name.trim().to_lowercase())
+    }
+
+    pub fn asset(name: &str) -> Self {
+        HaltScope::Asset(name.trim().to_lowercase())
+    }
+
+    /// Parse the admin API form: `global`, or `strategy` / `asset` with a target.
+    pub fn parse(scope: &str, target: Option<&str>) -> Result<Self, String> {
+        let target = target.map(str::trim).filter(|t| !t.is_empty());
+        match (scope.trim().to_lowercase().as_str(), target) {
+            ("global", _) => Ok(HaltScope::Global),
+            ("strategy", Some(t)) => Ok(HaltScope::strategy(t)),
+            ("asset", Some(t)) => Ok(HaltScope::asset(t)),
+            ("strategy" | "asset", None) => Err(format!("scope '{}' requires a target", scope)),
+            _ => Err(format!("unknown halt scope '{}'", scope)),
+        }
+    }
+
+    /// Stable key used for persistence and the audit log.
+    pub fn key(&self) -> String {
+        match self {
+            HaltScope::Global => "global".to_string(),
+            HaltScope::Strategy(s) => format!("strategy:{}", s),
+            HaltScope::Asset(a) => format!("asset:{}", a),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HaltTrigger {
+    Operator,
+    Drawdown,
+    FeedStale,
+    RouteQuality,
+    RejectRate,
+}
+
+impl HaltTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HaltTrigger::Operator => "operator",
+            HaltTrigger::Drawdown => "drawdown",
+            HaltTrigger::FeedStale => "feed_stale",
+            HaltTrigger::RouteQuality => "route_quality",
+            HaltTrigger::RejectRate => "reject_rate",
+        }
+    }
+
+    /// Halts that lift themselves once the triggering condition clears.
+    pub fn auto_clears(self) -> bool {
+        matches!(self, HaltTrigger::FeedStale | HaltTrigger::RouteQuality)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HaltState {
+    pub scope: HaltScope,
+    pub trigger: HaltTrigger,
+    pub actor: String,
+    pub reason: String,
+    pub halted_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlEvent {
+    Halted {
+        halt: HaltState,
+        orders_cancelled: usize,
+    },
+    Resumed {
+        scope: HaltScope,
+        actor: String,
+        at: i64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct TradingControlConfig {
+    /// Cancel resting orders in the halted scope
+    pub cancel_on_halt: bool,
+    pub monitor_interval_ms: u64,
+    /// Halt globally once this fraction of ready books is stale
+    pub stale_global_fraction: f64,
+    /// Orders per strategy considered for the reject rate
+    pub reject_window: usize,
+    pub reject_min_orders: usize,
+    pub reject_rate_halt: f64,
+    /// Endpoint health score (0-100) below which trading halts
+    pub route_min_health: f64,
+    pub route_max_packet_loss: f64,
+}
+
+impl Default for TradingControlConfig {
+    fn default() -> Self {
+        Self {
+            cancel_on_halt: true,
+            monitor_interval_ms: 1_000,
+            stale_global_fraction: 0.5,
+            reject_window: 20,
+            reject_min_orders: 10,
+            reject_rate_halt: 0.5,
+            route_min_health: 30.0,
+            route_max_packet_loss: 0.05,
+        }
+    }
+}
+
+impl TradingControlConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("TRADING_CONTROL_CANCEL_ON_HALT") {
+            config.cancel_on_halt = matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON");
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_MONITOR_MS") {
+            if let Ok(ms) = v.parse() {
+                config.monitor_interval_ms = ms;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_STALE_GLOBAL_FRACTION") {
+            if let Ok(x) = v.parse() {
+                config.stale_global_fraction = x;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_REJECT_WINDOW") {
+            if let Ok(n) = v.parse() {
+                config.reject_window = n;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_REJECT_MIN_ORDERS") {
+            if let Ok(n) = v.parse() {
+                config.reject_min_orders = n;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_REJECT_RATE") {
+            if let Ok(x) = v.parse() {
+                config.reject_rate_halt = x;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_ROUTE_MIN_HEALTH") {
+            if let Ok(x) = v.parse() {
+                config.route_min_health = x;
+            }
+        }
+        if let Ok(v) = std::env::var("TRADING_CONTROL_ROUTE_MAX_LOSS") {
+            if let Ok(x) = v.parse() {
+                config.route_max_packet_loss = x;
+            }
+        }
+
+        config
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TradingControlStatus {
+    /// No global halt is active
+    pub trading_enabled: bool,
+    pub halts: Vec<HaltState>,
+    /// Reject rate over each strategy's recent orders
+    pub reject_rates: HashMap<String, f64>,
+}
+
+struct RegisteredAdapter {
+    strategy: String,
+    adapter: Arc<dyn ExecutionAdapter>,
+}
+
+/// Shared halt state checked by every trading loop before it sends orders.
+pub struct TradingControl {
+    cfg: TradingControlConfig,
+    db: Option<Arc<VaultDb>>,
+    halts: RwLock<HashMap<HaltScope, HaltState>>,
+    /// token_id -> asset, for asset halts
+    token_assets: RwLock<HashMap<String, String>>,
+    adapters: RwLock<Vec<RegisteredAdapter>>,
+    /// strategy -> venue order ids resting for hosts that do not journal
+    tracked_orders: Mutex<HashMap<String, HashSet<String>>>,
+    /// Recent order outcomes per strategy (true = rejected)
+    outcomes: Mutex<HashMap<String, VecDeque<bool>>>,
+    events: broadcast::Sender<ControlEvent>,
+}
+
+impl TradingControl {
+    pub fn new(cfg: TradingControlConfig, db: Option<Arc<VaultDb>>) -> Self {
+        let (events, _) = broadcast::channel(CONTROL_EVENT_CAPACITY);
+        Self {
+            cfg,
+            db,
+            halts: RwLock::new(HashMap::new()),
+            token_assets: RwLock::new(HashMap::new()),
+            adapters: RwLock::new(Vec::new()),
+            tracked_orders: Mutex::new(HashMap::new()),
+            outcomes: Mutex::new(HashMap::new()),
+            events,
+        }
+    }
+
+    /// Create with the halts persisted in `db` still in force.
+    pub async fn load(cfg: TradingControlConfig, db: Arc<VaultDb>) -> Result<Self> {
+        let control = Self::new(cfg, Some(db.clone()));
+        for payload in db.load_trading_halts().await? {
+            match serde_json::from_str::<HaltState>(&payload) {
+                Ok(halt) => {
+                    warn!(
+                        scope = %halt.scope.key(),
+                        trigger = halt.trigger.as_str(),
+                        reason = %halt.reason,
+                        "trading halt restored from previous run"
+                    );
+                    control.halts.write().insert(halt.scope.clone(), halt);
+                }
+                Err(e) => warn!(error = %e, "skipping unreadable persisted trading halt"),
+            }
+        }
+        Ok(control)
+    }
+
+    pub fn config(&self) -> &TradingControlConfig {
+        &self.cfg
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ControlEvent> {
+        self.events.subscribe()
+    }
+
+    /// Register an adapter whose resting orders are cancelled on halt.
+    pub fn register_adapter(&self, strategy: &str, adapter: Arc<dyn ExecutionAdapter>) {
+        self.adapters.write().push(RegisteredAdapter {
+            strategy: strategy.trim().to_lowercase(),
+            adapter,
+        });
+    }
+
+    /// Note a venue order resting for `strategy`, so halting the strategy
+    /// cancels it. For hosts whose orders are not journaled in `VaultDb`.
+    pub fn track_order(&self, strategy: &str, order_id: &str) {
+        self.tracked_orders
+            .lock()
+            .entry(strategy.trim().to_lowercase())
+            .or_default()
+            .insert(order_id.to_string());
+    }
+
+    /// Forget an order passed to `track_order` once it no longer rests.
+    pub fn untrack_order(&self, strategy: &str, order_id: &str) {
+        let key = strategy.trim().to_lowercase();
+        let mut tracked = self.tracked_orders.lock();
+        if let Some(ids) = tracked.get_mut(&key) {
+            ids.remove(order_id);
+            if ids.is_empty() {
+                tracked.remove(&key);
+            }
+        }
+    }
+
+    /// Map a token to its underlying asset so asset halts cover it.
+    pub fn register_token(&self, token_id: &str, asset: &str) {
+        self.token_assets
+            .write()
+            .insert(token_id.to_string(), asset.trim().to_lowercase());
+    }
+
+    pub fn is_halted(&self, scope: &HaltScope) -> bool {
+        self.halts.read().contains_key(scope)
+    }
+
+    /// The halt blocking `strategy` (optionally on `asset`), most general first.
+    pub fn halt_for(&self, strategy: &str, asset: Option<&str>) -> Option<HaltState> {
+        let halts = self.halts.read();
+        if halts.is_empty() {
+            return None;
+        }
+        if let Some(h) = halts.get(&HaltScope::Global) {
+            return Some(h.clone());
+        }
+        if let Some(h) = halts.get(&HaltScope::strategy(strategy)) {
+            return Some(h.clone());
+        }
+        asset.and_then(|a| halts.get(&HaltScope::asset(a)).cloned())
+    }
+
+    /// Like `halt_for`, resolving the asset from a registered token.
+    pub fn halt_for_token(&self, strategy: &str, token_id: &str) -> Option<HaltState> {
+        let asset = self.token_assets.read().get(token_id).cloned();
+        self.halt_for(strategy, asset.as_deref())
+    }
+
+    pub fn status(&self) -> TradingControlStatus {
+        let mut halts: Vec<HaltState> = self.halts.read().values().cloned().collect();
+        halts.sort_by(|a, b| a.scope.key().cmp(&b.scope.key()));
+        let reject_rates = self
+            .outcomes
+            .lock()
+            .iter()
+            .filter(|(_, w)| !w.is_empty())
+            .map(|(s, w)| {
+                let rejects = w.iter().filter(|r| **r).count();
+                (s.clone(), rejects as f64 / w.len() as f64)
+            })
+            .collect();
+        TradingControlStatus {
+            trading_enabled: !self.is_halted(&HaltScope::Global),
+            halts,
+            reject_rates,
+        }
+    }
+
+    /// Halt `scope`, cancel its resting orders and audit the action. Returns
+    /// the number of orders cancelled, or `None` if the scope was already
+    /// halted. An operator halt replaces a self-clearing automatic one so it is
+    /// not lifted behind the operator's back.
+    pub async fn halt(
+        &self,
+        scope: HaltScope,
+        trigger: HaltTrigger,
+        actor: &str,
+        reason: &str,
+    ) -> Result<Option<usize>> {
+        let now = Utc::now().timestamp();
+        let halt = HaltState {
+            scope: scope.clone(),
+            trigger,
+            actor: actor.to_string(),
+            reason: reason.to_string(),
+            halted_at: now,
+        };
+        {
+            let mut halts = self.halts.write();
+            if let Some(existing) = halts.get(&scope) {
+                if !(existing.trigger.auto_clears() && !trigger.auto_clears()) {
+                    return Ok(None);
+                }
+            }
+            halts.insert(scope.clone(), halt.clone());
+        }
+        error!(
+            scope = %scope.key(),
+            trigger = trigger.as_str(),
+            actor = %actor,
+            reason = %reason,
+            "TRADING HALTED"
+        );
+
+        if let Some(db) = &self.db {
+            db.upsert_trading_halt(&scope.key(), &serde_json::to_string(&halt)?, now)
+                .await?;
+        }
+        let orders_cancelled = if self.cfg.cancel_on_halt {
+            self.cancel_scope(&scope).await
+        } else {
+            0
+        };
+        self.audit("HALT", &scope, trigger, actor, reason, orders_cancelled)
+            .await?;
+        let _ = self.events.send(ControlEvent::Halted {
+            halt,
+            orders_cancelled,
+        });
+        Ok(Some(orders_cancelled))
+    }
+
+    /// Lift the halt on `scope`. Returns false if it was not halted.
+    pub async fn resume(&self, scope: &HaltScope, actor: &str, reason: &str) -> Result<bool> {
+        let Some(prior) = self.halts.write().remove(scope) else {
+            return Ok(false);
+        };
+        info!(
+            scope = %scope.key(),
+            actor = %actor,
+            reason = %reason,
+            "trading resumed"
+        );
+        if let Some(db) = &self.db {
+            db.delete_trading_halt(&scope.key()).await?;
+        }
+        self.audit("RESUME", scope, prior.trigger, actor, reason, 0)
+            .await?;
+        let _ = self.events.send(ControlEvent::Resumed {
+            scope: scope.clone(),
+            actor: actor.to_string(),
+            at: Utc::now().timestamp(),
+        });
+        Ok(true)
+    }
+
+    pub async fn audit_log(&self, limit: usize) -> Result<Vec<VaultControlAuditRecord>> {
+        match &self.db {
+            Some(db) => db.list_control_audit(limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn audit(
+        &self,
+        action: &str,
+        scope: &HaltScope,
+        trigger: HaltTrigger,
+        actor: &str,
+        reason: &str,
+        orders_cancelled: usize,
+    ) -> Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+        db.insert_control_audit(&VaultControlAuditRecord {
+            id: Uuid::new_v4().to_string(),
+            ts: Utc::now().timestamp(),
+            action: action.to_string(),
+            scope: scope.key(),
+            trigger: trigger.as_str().to_string(),
+            actor: actor.to_string(),
+            reason: reason.to_string(),
+            orders_cancelled: orders_cancelled as i64,
+        })
+        .await
+    }
+
+    /// Cancel resting orders covered by `scope` on the registered adapters.
+    async fn cancel_scope(&self, scope: &HaltScope) -> usize {
+        // Snapshot so no lock is held across venue calls
+        let mut targets: Vec<Arc<dyn ExecutionAdapter>> = Vec::new();
+        for reg in self.adapters.read().iter() {
+            let covered = match scope {
+                HaltScope::Strategy(s) => &reg.strategy == s,
+                HaltScope::Global | HaltScope::Asset(_) => true,
+            };
+            // One adapter may be registered under several strategies
+            if covered && !targets.iter().any(|a| Arc::ptr_eq(a, &reg.adapter)) {
+                targets.push(reg.adapter.clone());
+            }
+        }
+        let tokens: Option<Vec<String>> = match scope {
+            HaltScope::Asset(asset) => Some(
+                self.token_assets
+                    .read()
+                    .iter()
+                    .filter(|(_, a)| *a == asset)
+                    .map(|(t, _)| t.clone())
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        if let HaltScope::Strategy(strategy) = scope {
+            return self.cancel_strategy_orders(strategy, &targets).await;
+        }
+
+        let mut cancelled = 0;
+        for adapter in targets {
+            let calls: Vec<Option<&str>> = match &tokens {
+                None => vec![None],
+                Some(tokens) => tokens.iter().map(|t| Some(t.as_str())).collect(),
+            };
+            for token in calls {
+                match adapter.cancel_all(token).await {
+                    Ok(ids) => cancelled += ids.len(),
+                    Err(e) => warn!(scope = %scope.key(), error = %e, "cancel-all on halt failed"),
+                }
+            }
+        }
+        cancelled
+    }
+
+    /// Venue ids of the orders `strategy` has resting: unresolved journaled
+    /// orders under its name plus those tracked for its host.
+    async fn strategy_order_ids(&self, strategy: &str) -> Vec<String> {
+        let mut ids: HashSet<String> = self
+            .tracked_orders
+            .lock()
+            .get(strategy)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(db) = &self.db {
+            match db.load_unresolved_orders().await {
+                Ok(orders) => ids.extend(
+                    orders
+                        .into_iter()
+                        .filter(|o| {
+                            o.strategy
+                                .as_deref()
+                                .is_some_and(|s| s.eq_ignore_ascii_case(strategy))
+                        })
+                        .filter_map(|o| o.order_id),
+                ),
+                Err(e) => {
+                    warn!(strategy = %strategy, error = %e, "failed to load journaled orders")
+                }
+            }
+        }
+        let mut ids: Vec<String> = ids.into_iter().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Cancel `strategy`'s own orders one by one; other strategies' orders on
+    /// the same adapter are left alone.
+    async fn cancel_strategy_orders(
+        &self,
+        strategy: &str,
+        targets: &[Arc<dyn ExecutionAdapter>],
+    ) -> usize {
+        let mut cancelled = 0;
+        for order_id in self.strategy_order_ids(strategy).await {
+            let mut last_err = None;
+            for adapter in targets {
+                match adapter.cancel_order(&order_id).await {
+                    Ok(()) => {
+                        cancelled += 1;
+                        last_err = None;
+                        break;
+                    }
+                    Err(e) => last_err = Some(e),
+                }
+            }
+            if let Some(e) = last_err {
+                warn!(strategy = %strategy, order_id = %order_id, error = %e, "cancel on halt failed");
+            }
+        }
+        cancelled
+    }
+
+    // ------------------------------------------------------------------------
+    // Automatic triggers
+    // ------------------------------------------------------------------------
+
+    async fn auto_halt(&self, scope: HaltScope, trigger: HaltTrigger, reason: String) {
+        if let Err(e) = self.halt(scope, trigger, AUTO_ACTOR, &reason).await {
+            warn!(error = %e, "failed to persist automatic trading halt");
+        }
+    }
+
+    /// Lift an automatic halt on `scope` if it was set by `trigger`.
+    async fn auto_clear(&self, scope: &HaltScope, trigger: HaltTrigger, reason: &str) {
+        let matches = self
+            .halts
+            .read()
+            .get(scope)
+            .is_some_and(|h| h.trigger == trigger);
+        if matches {
+            if let Err(e) = self.resume(scope, AUTO_ACTOR, reason).await {
+                warn!(error = %e, "failed to persist automatic trading resume");
+            }
+        }
+    }
+
+    /// Global halt once NAV is down `max_drawdown_pct` from `initial_bankroll`.
+    pub async fn check_drawdown(
+        &self,
+        nav_usdc: f64,
+        initial_bankroll: f64,
+        max_drawdown_pct: f64,
+    ) {
+        if !(initial_bankroll > 0.0) {
+            return;
+        }
+        let drawdown = 1.0 - nav_usdc / initial_bankroll;
+        if drawdown >= max_drawdown_pct && !self.is_halted(&HaltScope::Global) {
+            self.auto_halt(
+                HaltScope::Global,
+                HaltTrigger::Drawdown,
+                format!(
+                    "drawdown {:.1}% >= {:.1}% (nav {:.2} / initial {:.2})",
+                    drawdown * 100.0,
+                    max_drawdown_pct * 100.0,
+                    nav_usdc,
+                    initial_bankroll
+                ),
+            )
+            .await;
+        }
+    }
+
+    /// Halt assets whose books are stale (globally when at least
+    /// `stale_global_fraction` of `ready_count` books are), and lift feed
+    /// halts whose books are fresh again.
+    pub async fn check_feed_staleness(&self, stale_tokens: &[String], ready_count: usize) {
+        let fraction = if ready_count > 0 {
+            stale_tokens.len() as f64 / ready_count as f64
+        } else {
+            0.0
+        };
+        if !stale_tokens.is_empty() && fraction >= self.cfg.stale_global_fraction {
+            self.auto_halt(
+                HaltScope::Global,
+                HaltTrigger::FeedStale,
+                format!("{}/{} books stale", stale_tokens.len(), ready_count),
+            )
+            .await;
+        } else {
+            self.auto_clear(&HaltScope::Global, HaltTrigger::FeedStale, "books fresh")
+                .await;
+        }
+
+        let stale_assets: HashSet<String> = {
+            let token_assets = self.token_assets.read();
+            stale_tokens
+                .iter()
+                .filter_map(|t| token_assets.get(t).cloned())
+                .collect()
+        };
+        for asset in &stale_assets {
+            let scope = HaltScope::asset(asset);
+            if !self.is_halted(&scope) {
+                self.auto_halt(
+                    scope,
+                    HaltTrigger::FeedStale,
+                    format!("{} book stale", asset),
+                )
+                .await;
+            }
+        }
+        let cleared: Vec<HaltScope> = self
+            .halts
+            .read()
+            .values()
+            .filter(|h| h.trigger == HaltTrigger::FeedStale)
+            .filter_map(|h| match &h.scope {
+                HaltScope::Asset(a) if !stale_assets.contains(a) => Some(h.scope.clone()),
+                _ => None,
+            })
+            .collect();
+        for scope in cleared {
+            self.auto_clear(&scope, HaltTrigger::FeedStale, "book fresh")
+                .await;
+        }
+    }
+
+    /// Global halt on poor endpoint health or packet loss; lifted when health
+    /// recovers.
+    pub async fn on_route_quality_event(&self, event: &RouteQualityEvent) {
+        match event {
+            RouteQualityEvent::HealthChanged {
+                endpoint,
+                health_score,
+                ..
+            } => {
+                if *health_score < self.cfg.route_min_health {
+                    self.auto_halt(
+                        HaltScope::Global,
+                        HaltTrigger::RouteQuality,
+                        format!("{} health {:.0}", endpoint, health_score),
+                    )
+                    .await;
+                } else {
+                    self.auto_clear(
+                        &HaltScope::Global,
+                        HaltTrigger::RouteQuality,
+                        &format!("{} health {:.0}", endpoint, health_score),
+                    )
+                    .await;
+                }
+            }
+            RouteQualityEvent::PacketLoss {
+                endpoint,
+                loss_rate,
+            } if *loss_rate > self.cfg.route_max_packet_loss => {
+                self.auto_halt(
+                    HaltScope::Global,
+                    HaltTrigger::RouteQuality,
+                    format!("{} packet loss {:.1}%", endpoint, loss_rate * 100.0),
+                )
+                .await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Track an order outcome; halts the strategy when its reject rate over
+    /// the last `reject_window` orders reaches `reject_rate_halt`.
+    pub async fn record_order_outcome(&self, strategy: &str, rejected: bool) {
+        let key = strategy.trim().to_lowercase();
+        let breach = {
+            let mut outcomes = self.outcomes.lock();
+            let window = outcomes.entry(key.clone()).or_default();
+            window.push_back(rejected);
+            while window.len() > self.cfg.reject_window.max(1) {
+                window.pop_front();
+            }
+            let rejects = window.iter().filter(|r| **r).count();
+            let rate = rejects as f64 / window.len() as f64;
+            (window.len() >= self.cfg.reject_min_orders && rate >= self.cfg.reject_rate_halt)
+                .then_some((rejects, window.len()))
+        };
+        if let Some((rejects, total)) = breach {
+            let scope = HaltScope::Strategy(key);
+            if !self.is_halted(&scope) {
+                self.auto_halt(
+                    scope,
+                    HaltTrigger::RejectRate,
+                    format!("{}/{} recent orders rejected", rejects, total),
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Poll `book_store` for stale books.
+    pub fn spawn_feed_monitor(self: &Arc<Self>, book_store: Arc<BookStore>) {
+        let control = self.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(control.cfg.monitor_interval_ms));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let stale = book_store.get_stale_tokens();
+                let ready = book_store.get_ready_tokens().len();
+                control.check_feed_staleness(&stale, ready).await;
+            }
+        });
+    }
+
+    /// Feed route quality alerts into the halt controls.
+    pub fn spawn_route_quality_listener(self: &Arc<Self>, mut handle: RouteQualityHandle) {
+        let control = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = handle.recv_event().await {
+                control.on_route_quality_event(&event).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{OrderAck, OrderRequest, VaultOrderRecord};
+    use anyhow::anyhow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts cancel-all calls (each cancels one order) and records
+    /// single-order cancels.
+    #[derive(Default)]
+    struct CancelCounter {
+        calls: AtomicUsize,
+        cancelled: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionAdapter for CancelCounter {
+        async fn place_order(&self, _req: OrderRequest) -> Result<OrderAck> {
+            Err(anyhow!("not used"))
+        }
+
+        async fn cancel_all(&self, token_id: Option<&str>) -> Result<Vec<String>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![format!("order-{}", token_id.unwrap_or("all"))])
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> Result<()> {
+            self.cancelled.lock().push(order_id.to_string());
+            Ok(())
+        }
+    }
+
+    fn open_order(client_order_id: &str, order_id: &str, strategy: &str) -> VaultOrderRecord {
+        VaultOrderRecord {
+            client_order_id: client_order_id.to_string(),
+            order_id: Some(order_id.to_string()),
+            token_id: "tok-btc-up".to_string(),
+            market_slug: None,
+            outcome: Some("Up".to_string()),
+            side: "BUY".to_string(),
+            price: 0.5,
+            size: 10.0,
+            filled_size: 0.0,
+            filled_notional_usdc: 0.0,
+            fees_usdc: 0.0,
+            status: "OPEN".to_string(),
+            strategy: Some(strategy.to_string()),
+            config_version: None,
+            created_at: 1_000,
+            updated_at: 1_000,
+        }
+    }
+
+    fn temp_db() -> (tempfile::TempDir, Arc<VaultDb>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.db");
+        let db = Arc::new(VaultDb::new(path.to_str().unwrap()).unwrap());
+        (dir, db)
+    }
+
+    #[test]
+    fn test_parse_scope() {
+        assert_eq!(HaltScope::parse("global", None), Ok(HaltScope::Global));
+        assert_eq!(
+            HaltScope::parse("Strategy", Some("FAST15M")),
+            Ok(HaltScope::Strategy("fast15m".to_string()))
+        );
+        assert!(HaltScope::parse("asset", None).is_err());
+        assert!(HaltScope::parse("market", Some("x")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_halt_resume_persists_and_audits() {
+        let (_dir, db) = temp_db();
+        let control = TradingControl::new(TradingControlConfig::default(), Some(db.clone()));
+        let exec = Arc::new(CancelCounter::default());
+        control.register_adapter("FAST15M", exec.clone());
+        control.register_adapter("LONG", exec.clone());
+        control.register_token("tok-btc-up", "BTC");
+
+        let cancelled = control
+            .halt(
+                HaltScope::asset("btc"),
+                HaltTrigger::Operator,
+                "alice",
+                "maintenance",
+            )
+            .await
+            .unwrap();
+        // Shared adapter cancels once per token of the asset
+        assert_eq!(cancelled, Some(1));
+        assert!(control.halt_for("FAST15M", Some("btc")).is_some());
+        assert!(control.halt_for_token("long", "tok-btc-up").is_some());
+        assert!(control.halt_for("FAST15M", Some("eth")).is_none());
+
+        // Halting again is a no-op
+        let again = control
+            .halt(
+                HaltScope::asset("btc"),
+                HaltTrigger::Operator,
+                "alice",
+                "again",
+            )
+            .await
+            .unwrap();
+        assert_eq!(again, None);
+
+        // Survives a restart
+        let reloaded = TradingControl::load(TradingControlConfig::default(), db.clone())
+            .await
+            .unwrap();
+        assert!(reloaded.halt_for("LONG", Some("BTC")).is_some());
+
+        assert!(reloaded
+            .resume(&HaltScope::asset("btc"), "bob", "done")
+            .await
+            .unwrap());
+        assert!(reloaded.halt_for("LONG", Some("btc")).is_none());
+        assert!(db.load_trading_halts().await.unwrap().is_empty());
+
+        let audit = db.list_control_audit(10).await.unwrap();
+        assert_eq!(audit.len(), 2);
+        let mut actions: Vec<(&str, &str)> = audit
+            .iter()
+            .map(|a| (a.action.as_str(), a.actor.as_str()))
+            .collect();
+        actions.sort();
+        assert_eq!(actions, vec![("HALT", "alice"), ("RESUME", "bob")]);
+        assert_eq!(exec.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_halt_cancels_only_its_orders() {
+        let (_dir, db) = temp_db();
+        let payload = serde_json::json!({});
+        db.journal_order(&open_order("c1", "o-fast", "FAST15M"), "SUBMIT", &payload)
+            .await
+            .unwrap();
+        db.journal_order(&open_order("c2", "o-long", "LONG"), "SUBMIT", &payload)
+            .await
+            .unwrap();
+
+        let control = TradingControl::new(TradingControlConfig::default(), Some(db));
+        // FAST15M, LONG and a live host share one adapter (one venue account)
+        let exec = Arc::new(CancelCounter::default());
+        control.register_adapter("FAST15M", exec.clone());
+        control.register_adapter("LONG", exec.clone());
+        control.register_adapter("mm", exec.clone());
+        control.track_order("mm", "o-mm-1");
+        control.track_order("mm", "o-mm-2");
+        control.untrack_order("mm", "o-mm-2");
+
+        let cancelled = control
+            .halt(
+                HaltScope::strategy("fast15m"),
+                HaltTrigger::Operator,
+                "alice",
+                "test",
+            )
+            .await
+            .unwrap();
+        assert_eq!(cancelled, Some(1));
+        assert_eq!(*exec.cancelled.lock(), vec!["o-fast".to_string()]);
+        assert_eq!(exec.calls.load(Ordering::SeqCst), 0);
+        assert!(control.halt_for("LONG", None).is_none());
+
+        let cancelled = control
+            .halt(
+                HaltScope::strategy("MM"),
+                HaltTrigger::Operator,
+                "alice",
+                "test",
+            )
+            .await
+            .unwrap();
+        assert_eq!(cancelled, Some(1));
+        assert_eq!(
+            *exec.cancelled.lock(),
+            vec!["o-fast".to_string(), "o-mm-1".to_string()]
+        );
+        assert_eq!(exec.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_feed_staleness_halts_and_clears_asset() {
+        let control = TradingControl::new(TradingControlConfig::default(), None);
+        control.register_token("tok-btc-up", "btc");
+        control.register_token("tok-eth-up", "eth");
+        control.register_token("tok-sol-up", "sol");
+
+        control
+            .check_feed_staleness(&["tok-btc-up".to_string()], 3)
+            .await;
+        assert!(control.is_halted(&HaltScope::asset("btc")));
+        assert!(!control.is_halted(&HaltScope::Global));
+
+        // Most of the universe stale: global halt
+        control
+            .check_feed_staleness(&["tok-btc-up".to_string(), "tok-eth-up".to_string()], 3)
+            .await;
+        assert!(control.is_halted(&HaltScope::Global));
+
+        // An operator takes over the asset halt; it must not auto-clear
+        control
+            .halt(
+                HaltScope::asset("eth"),
+                HaltTrigger::Operator,
+                "alice",
+                "investigating",
+            )
+            .await
+            .unwrap();
+
+        control.check_feed_staleness(&[], 3).await;
+        assert!(!control.is_halted(&HaltScope::Global));
+        assert!(!control.is_halted(&HaltScope::asset("btc")));
+        assert!(control.is_halted(&HaltScope::asset("eth")));
+    }
+
+    #[tokio::test]
+    async fn test_reject_spike_and_drawdown_halts() {
+        let control = TradingControl::new(
+            TradingControlConfig {
+                reject_window: 4,
+                reject_min_orders: 4,
+                reject_rate_halt: 0.5,
+                ..Default::default()
+            },
+            None,
+        );
+        control.record_order_outcome("FAST15M", true).await;
+        control.record_order_outcome("FAST15M", false).await;
+        control.record_order_outcome("FAST15M", true).await;
+        assert!(control.halt_for("FAST15M", None).is_none());
+        control.record_order_outcome("FAST15M", false).await;
+        let halt = control.halt_for("fast15m", None).unwrap();
+        assert_eq!(halt.trigger, HaltTrigger::RejectRate);
+        assert!(control.halt_for("LONG", None).is_none());
+
+        control.check_drawdown(850.0, 1000.0, 0.2).await;
+        assert!(control.status().trading_enabled);
+        control.check_drawdown(790.0, 1000.0, 0.2).await;
+        let status = control.status();
+        assert!(!status.trading_enabled);
+        assert_eq!(status.halts.len(), 2);
+        // Drawdown halts wait for an operator
+        control
+            .on_route_quality_event(&RouteQualityEvent::HealthChanged {
+                endpoint: "binance".to_string(),
+                health_score: 95.0,
+                previous_score: 20.0,
+            })
+            .await;
+        assert!(control.halt_for("LONG", None).is_some());
+    }
+}
@@ -13,6 +13,7 @@
 use anyhow::Result;
 use chrono::Utc;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     sync::{
@@ -37,7 +38,8 @@ use crate::{
 // ============================================================================
 
 /// Configuration for the Unified 15M Strategy
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Unified15mConfig {
     // === Risk Controls ===
     /// Minimum edge required to enter (e.g., 0.05 = 5%)
@@ -182,6 +184,8 @@ pub struct OpenPosition {
     pub entry_ts: i64,
     pub entry_edge: f64,
     pub sigma_b_at_entry: f64,
+    /// Strategy config version active at entry
+    pub config_version: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -229,6 +233,8 @@ pub struct TradeRecord {
     pub sigma_b: f64,
     pub jump_regime: bool,
     pub hold_time_sec: i64,
+    /// Strategy config version the position was entered under
+    pub config_version: i64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -334,6 +340,7 @@ struct AssetState {
 
 pub struct Unified15mStrategy {
     config: Unified15mConfig,
+    config_version: i64,
     binance_feed: Arc<BinancePriceFeed>,
     belief_vol_tracker: Arc<RwLock<BeliefVolTracker>>,
 
@@ -366,6 +373,7 @@ impl Unified15mStrategy {
 
         Self {
             config,
+            config_version: 0,
             binance_feed,
             belief_vol_tracker,
             positions: HashMap::new(),
@@ -384,6 +392,26 @@ impl Unified15mStrategy {
         self.shutdown.store(true, Ordering::Release);
     }
 
+    /// Version of the config currently in effect (0 until one is applied).
+    pub fn config_version(&self) -> i64 {
+        self.config_version
+    }
+
+    /// Swap in a new parameter set. Open positions keep the version they were
+    /// entered under; the running bankroll is not reset.
+    pub fn apply_config(&mut self, version: i64, config: Unified15mConfig) {
+        info!(
+            "[UNIFIED] config v{} -> v{} (min_edge: {:.1}%, kelly: {:.0}%, max_pos: ${:.0})",
+            self.config_version,
+            version,
+            config.min_edge * 100.0,
+            config.kelly_fraction * 100.0,
+            config.max_position_usd
+        );
+        self.config = config;
+        self.config_version = version;
+    }
+
     /// Calculate Polymarket 15m Up/Down fee
     /// Formula: fee = 0.25 * shares * (p * (1-p))^2
     /// Or: fee_per_share = 0.25 * (p * (1-p))^2
@@ -628,6 +656,7 @@ impl Unified15mStrategy {
                     sigma_b: position.sigma_b_at_entry,
                     jump_regime: self.is_jump_regime(market_slug, now),
                     hold_time_sec: hold_time,
+                    config_version: position.config_version,
                 };
 
                 info!(
@@ -730,6 +759,7 @@ impl Unified15mStrategy {
             entry_ts: now,
             entry_edge: edge,
             sigma_b_at_entry: sigma_b,
+            config_version: self.config_version,
         };
 
         self.positions.insert(market_slug.to_string(), position);
@@ -793,6 +823,7 @@ impl Unified15mStrategy {
             sigma_b: position.sigma_b_at_entry,
             jump_regime: false,
             hold_time_sec: hold_time,
+            config_version: position.config_version,
         };
 
         info!(
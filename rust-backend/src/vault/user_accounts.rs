@@ -60,6 +60,29 @@ pub struct TradeRecord {
     pub closed_at: Option<DateTime<Utc>>,
 }
 
+/// Vault share statement line, written when a NAV strike settles a request
+/// or crystallizes a performance fee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultStatementEntry {
+    pub wallet_address: String,
+    pub ts: i64,
+    pub kind: String, // "SUBSCRIBE", "REDEEM" or "PERFORMANCE_FEE"
+    pub amount_usdc: f64,
+    pub shares: f64, // signed: minted > 0, burned < 0
+    pub nav_per_share: f64,
+    pub shares_after: f64,
+    pub request_id: Option<String>,
+    pub strike_id: String,
+}
+
+/// Lifetime totals from a wallet's vault statement
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultStatementTotals {
+    pub subscribed_usdc: f64,
+    pub redeemed_usdc: f64,
+    pub performance_fees_usdc: f64,
+}
+
 /// User accounts database manager
 pub struct UserAccountsDB {
     conn: Arc<Mutex<Connection>>,
@@ -127,7 +150,27 @@ impl UserAccountsDB {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_share_statements (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                wallet_address TEXT NOT NULL,
+                ts INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                amount_usdc REAL NOT NULL,
+                shares REAL NOT NULL,
+                nav_per_share REAL NOT NULL,
+                shares_after REAL NOT NULL,
+                request_id TEXT,
+                strike_id TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // Create indexes
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_statements_wallet ON vault_share_statements(wallet_address, ts)",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_deposits_user ON deposits(user_id)",
             [],
@@ -362,4 +405,107 @@ impl UserAccountsDB {
 
         Ok(users)
     }
+
+    /// Record statement lines from one strike. Subscriptions and redemptions
+    /// also roll into the account's deposit/withdrawal totals; wallets without
+    /// an account get one.
+    pub async fn record_statement_entries(&self, entries: &[VaultStatementEntry]) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+
+        for e in entries {
+            tx.execute(
+                "INSERT INTO vault_share_statements (wallet_address, ts, kind, amount_usdc, shares,
+                 nav_per_share, shares_after, request_id, strike_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    e.wallet_address,
+                    e.ts,
+                    e.kind,
+                    e.amount_usdc,
+                    e.shares,
+                    e.nav_per_share,
+                    e.shares_after,
+                    e.request_id,
+                    e.strike_id
+                ],
+            )?;
+
+            let field = match e.kind.as_str() {
+                "SUBSCRIBE" => "total_deposited",
+                "REDEEM" => "total_withdrawn",
+                _ => continue,
+            };
+            tx.execute(
+                "INSERT OR IGNORE INTO user_accounts (wallet_address, wallet_type, created_at, updated_at)
+                 VALUES (?, 'unknown', ?, ?)",
+                params![e.wallet_address, &now, &now],
+            )?;
+            tx.execute(
+                &format!(
+                    "UPDATE user_accounts SET {} = {} + ?, updated_at = ? WHERE wallet_address = ?",
+                    field, field
+                ),
+                params![e.amount_usdc, &now, e.wallet_address],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// A wallet's statement lines, newest first
+    pub async fn statement_entries(
+        &self,
+        wallet_address: &str,
+        limit: usize,
+    ) -> Result<Vec<VaultStatementEntry>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT wallet_address, ts, kind, amount_usdc, shares, nav_per_share, shares_after,
+             request_id, strike_id FROM vault_share_statements
+             WHERE wallet_address = ? ORDER BY ts DESC, id DESC LIMIT ?",
+        )?;
+
+        let entries = stmt
+            .query_map(params![wallet_address, limit as i64], |row| {
+                Ok(VaultStatementEntry {
+                    wallet_address: row.get(0)?,
+                    ts: row.get(1)?,
+                    kind: row.get(2)?,
+                    amount_usdc: row.get(3)?,
+                    shares: row.get(4)?,
+                    nav_per_share: row.get(5)?,
+                    shares_after: row.get(6)?,
+                    request_id: row.get(7)?,
+                    strike_id: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Lifetime subscribed/redeemed/fee totals for a wallet
+    pub async fn statement_totals(&self, wallet_address: &str) -> Result<VaultStatementTotals> {
+        let conn = self.conn.lock().await;
+        let totals = conn.query_row(
+            "SELECT
+                COALESCE(SUM(CASE WHEN kind = 'SUBSCRIBE' THEN amount_usdc END), 0.0),
+                COALESCE(SUM(CASE WHEN kind = 'REDEEM' THEN amount_usdc END), 0.0),
+                COALESCE(SUM(CASE WHEN kind = 'PERFORMANCE_FEE' THEN amount_usdc END), 0.0)
+             FROM vault_share_statements WHERE wallet_address = ?",
+            [wallet_address],
+            |row| {
+                Ok(VaultStatementTotals {
+                    subscribed_usdc: row.get(0)?,
+                    redeemed_usdc: row.get(1)?,
+                    performance_fees_usdc: row.get(2)?,
+                })
+            },
+        )?;
+
+        Ok(totals)
+    }
 }
@@ -20,6 +20,9 @@ pub struct VaultActivityRecord {
     pub notional_usdc: Option<f64>,
     pub strategy: Option<String>,
     pub decision_id: Option<String>,
+    /// Strategy config version that produced the trade
+    #[serde(default)]
+    pub config_version: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +37,153 @@ pub struct VaultNavSnapshotRecord {
     pub source: String,
 }
 
+/// An order as recorded in the write-ahead journal. `side` and `status` use the
+/// upper-case names of `OrderSide` / `OrderStatus`, plus `PENDING` (written
+/// before submission) and `UNRESOLVED` (outcome unknown after a restart).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultOrderRecord {
+    pub client_order_id: String,
+    pub order_id: Option<String>,
+    pub token_id: String,
+    pub market_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub filled_size: f64,
+    pub filled_notional_usdc: f64,
+    pub fees_usdc: f64,
+    pub status: String,
+    pub strategy: Option<String>,
+    #[serde(default)]
+    pub config_version: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl VaultOrderRecord {
+    pub fn is_unresolved(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "PENDING" | "OPEN" | "PARTIALLY_FILLED"
+        )
+    }
+}
+
+/// Persisted inventory for one token (mirrors `VaultPaperPosition`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultPositionRecord {
+    pub token_id: String,
+    pub outcome: String,
+    pub shares: f64,
+    pub cost_usdc: f64,
+    pub avg_price: f64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultJournalEntry {
+    pub seq: i64,
+    pub ts: i64,
+    pub kind: String,
+    pub client_order_id: Option<String>,
+    pub order_id: Option<String>,
+    pub token_id: Option<String>,
+    pub payload: String,
+}
+
+/// One operator or automatic halt/resume action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultControlAuditRecord {
+    pub id: String,
+    pub ts: i64,
+    /// `HALT` or `RESUME`
+    pub action: String,
+    pub scope: String,
+    pub trigger: String,
+    pub actor: String,
+    pub reason: String,
+    pub orders_cancelled: i64,
+}
+
+/// One sleeve's budget as set by a capital allocator rebalance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultAllocationRecord {
+    pub id: String,
+    pub ts: i64,
+    pub sleeve: String,
+    /// `fixed`, `risk_parity` or `kelly`
+    pub method: String,
+    pub weight: f64,
+    pub budget_usdc: f64,
+    pub deployed_usdc: f64,
+    pub mean_return: Option<f64>,
+    pub return_vol: Option<f64>,
+    pub samples: i64,
+}
+
+/// A recorded strategy parameter set. `status` is `PENDING` (waiting for the
+/// next window boundary), `ACTIVE`, `SUPERSEDED` or `REPLACED` (a newer set
+/// was staged before this one took effect).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultStrategyConfigRecord {
+    pub version: i64,
+    pub created_at: i64,
+    pub effective_at: i64,
+    pub activated_at: Option<i64>,
+    pub status: String,
+    pub source: String,
+    pub actor: String,
+    pub note: Option<String>,
+    pub payload: String,
+}
+
+/// A queued subscription (`amount_usdc`) or redemption (`shares`), filled at
+/// the next NAV strike. `status` is `PENDING`, `FILLED`, `REJECTED` or
+/// `CANCELLED`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultShareRequestRecord {
+    pub id: String,
+    pub wallet_address: String,
+    /// `SUBSCRIBE` or `REDEEM`
+    pub kind: String,
+    pub amount_usdc: Option<f64>,
+    pub shares: Option<f64>,
+    pub status: String,
+    pub requested_at: i64,
+    pub strike_id: Option<String>,
+    pub struck_at: Option<i64>,
+    pub nav_per_share: Option<f64>,
+    pub filled_usdc: Option<f64>,
+    pub filled_shares: Option<f64>,
+    pub note: Option<String>,
+}
+
+/// One NAV strike: positions marked to book mids, performance fee
+/// crystallized, queued requests filled at `nav_per_share`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultNavStrikeRecord {
+    pub id: String,
+    pub ts: i64,
+    /// NAV after fills
+    pub nav_usdc: f64,
+    pub cash_usdc: f64,
+    pub positions_value_usdc: f64,
+    /// Positions marked to a fresh book mid / valued at cost
+    pub marked_positions: i64,
+    pub unmarked_positions: i64,
+    /// Shares outstanding after fills
+    pub total_shares: f64,
+    pub nav_per_share: f64,
+    pub high_water_mark: f64,
+    pub performance_fee_usdc: f64,
+    pub fee_shares: f64,
+    pub subscriptions_usdc: f64,
+    pub redemptions_usdc: f64,
+    pub shares_minted: f64,
+    pub shares_burned: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct VaultTokenMeta {
     pub market_slug: String,
@@ -120,6 +270,181 @@ impl VaultDb {
             [],
         )?;
 
+        // Write-ahead journal: every order transition and fill is appended here
+        // in the same transaction that updates `vault_orders` / `vault_positions`,
+        // so a restart can rebuild open orders and inventory exactly.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_journal (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                ts INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                client_order_id TEXT,
+                order_id TEXT,
+                token_id TEXT,
+                payload TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_journal_client_order ON vault_journal(client_order_id, seq)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_orders (
+                client_order_id TEXT PRIMARY KEY,
+                order_id TEXT,
+                token_id TEXT NOT NULL,
+                market_slug TEXT,
+                outcome TEXT,
+                side TEXT NOT NULL,
+                price REAL NOT NULL,
+                size REAL NOT NULL,
+                filled_size REAL NOT NULL,
+                filled_notional_usdc REAL NOT NULL,
+                fees_usdc REAL NOT NULL,
+                status TEXT NOT NULL,
+                strategy TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_orders_status ON vault_orders(status)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_positions (
+                token_id TEXT PRIMARY KEY,
+                outcome TEXT NOT NULL,
+                shares REAL NOT NULL,
+                cost_usdc REAL NOT NULL,
+                avg_price REAL NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Trading halts survive restarts; every halt/resume is audited.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_trading_halts (
+                scope TEXT PRIMARY KEY,
+                payload TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_control_audit (
+                id TEXT PRIMARY KEY,
+                ts INTEGER NOT NULL,
+                action TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                trigger TEXT NOT NULL,
+                actor TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                orders_cancelled INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_control_audit_ts ON vault_control_audit(ts DESC)",
+            [],
+        )?;
+
+        // Versioned strategy parameters; trades and orders carry the version
+        // that produced them.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_strategy_configs (
+                version INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at INTEGER NOT NULL,
+                effective_at INTEGER NOT NULL,
+                activated_at INTEGER,
+                status TEXT NOT NULL,
+                source TEXT NOT NULL,
+                actor TEXT NOT NULL,
+                note TEXT,
+                payload TEXT NOT NULL
+            )",
+            [],
+        )?;
+        ensure_column(&conn, "vault_activity", "config_version", "INTEGER")?;
+
+        // Subscriptions/redemptions wait here for the next NAV strike.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_share_requests (
+                id TEXT PRIMARY KEY,
+                wallet_address TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                amount_usdc REAL,
+                shares REAL,
+                status TEXT NOT NULL,
+                requested_at INTEGER NOT NULL,
+                strike_id TEXT,
+                struck_at INTEGER,
+                nav_per_share REAL,
+                filled_usdc REAL,
+                filled_shares REAL,
+                note TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_share_requests_status ON vault_share_requests(status, requested_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_share_requests_wallet ON vault_share_requests(wallet_address, requested_at DESC)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_nav_strikes (
+                id TEXT PRIMARY KEY,
+                ts INTEGER NOT NULL,
+                nav_usdc REAL NOT NULL,
+                cash_usdc REAL NOT NULL,
+                positions_value_usdc REAL NOT NULL,
+                marked_positions INTEGER NOT NULL,
+                unmarked_positions INTEGER NOT NULL,
+                total_shares REAL NOT NULL,
+                nav_per_share REAL NOT NULL,
+                high_water_mark REAL NOT NULL,
+                performance_fee_usdc REAL NOT NULL,
+                fee_shares REAL NOT NULL,
+                subscriptions_usdc REAL NOT NULL,
+                redemptions_usdc REAL NOT NULL,
+                shares_minted REAL NOT NULL,
+                shares_burned REAL NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_nav_strikes_ts ON vault_nav_strikes(ts DESC)",
+            [],
+        )?;
+
+        // Capital allocator budgets, one row per sleeve per rebalance.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_allocations (
+                id TEXT PRIMARY KEY,
+                ts INTEGER NOT NULL,
+                sleeve TEXT NOT NULL,
+                method TEXT NOT NULL,
+                weight REAL NOT NULL,
+                budget_usdc REAL NOT NULL,
+                deployed_usdc REAL NOT NULL,
+                mean_return REAL,
+                return_vol REAL,
+                samples INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vault_allocations_ts ON vault_allocations(ts DESC)",
+            [],
+        )?;
+        ensure_column(&conn, "vault_orders", "config_version", "INTEGER")?;
+
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
         })
@@ -214,8 +539,8 @@ impl VaultDb {
         let conn = self.conn.lock().await;
         conn.execute(
             "INSERT OR REPLACE INTO vault_activity \
-             (id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+             (id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id, config_version) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 &rec.id,
                 rec.ts,
@@ -231,6 +556,7 @@ impl VaultDb {
                 rec.notional_usdc,
                 rec.strategy.as_deref(),
                 rec.decision_id.as_deref(),
+                rec.config_version,
             ],
         )?;
         Ok(())
@@ -248,7 +574,7 @@ impl VaultDb {
         if let Some(wallet) = wallet_address {
             let wallet = wallet.trim().to_lowercase();
             let mut stmt = conn.prepare_cached(
-                "SELECT id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id \
+                "SELECT id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id, config_version \
                  FROM vault_activity WHERE wallet_address = ?1 ORDER BY ts DESC LIMIT ?2",
             )?;
             let rows = stmt.query_map(params![wallet, limit], |row| {
@@ -267,6 +593,7 @@ impl VaultDb {
                     notional_usdc: row.get(11)?,
                     strategy: row.get(12)?,
                     decision_id: row.get(13)?,
+                    config_version: row.get(14)?,
                 })
             })?;
             for r in rows {
@@ -278,7 +605,7 @@ impl VaultDb {
         }
 
         let mut stmt = conn.prepare_cached(
-            "SELECT id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id \
+            "SELECT id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id, config_version \
              FROM vault_activity ORDER BY ts DESC LIMIT ?1",
         )?;
         let rows = stmt.query_map(params![limit], |row| {
@@ -297,6 +624,7 @@ impl VaultDb {
                 notional_usdc: row.get(11)?,
                 strategy: row.get(12)?,
                 decision_id: row.get(13)?,
+                config_version: row.get(14)?,
             })
         })?;
         for r in rows {
@@ -413,4 +741,775 @@ impl VaultDb {
             decision_id,
         }))
     }
+
+    /// Append `kind` to the journal and upsert the order, atomically.
+    pub async fn journal_order(
+        &self,
+        order: &VaultOrderRecord,
+        kind: &str,
+        payload: &serde_json::Value,
+    ) -> Result<i64> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        upsert_order_tx(&tx, order)?;
+        let seq = append_journal_tx(
+            &tx,
+            order.updated_at,
+            kind,
+            Some(&order.client_order_id),
+            order.order_id.as_deref(),
+            Some(&order.token_id),
+            payload,
+        )?;
+        tx.commit()?;
+        Ok(seq)
+    }
+
+    /// Append a ledger change (fill or reconciliation) to the journal and
+    /// persist the touched positions, cash and (optionally) the order in one
+    /// transaction. Positions with no shares left are deleted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn journal_ledger(
+        &self,
+        ts: i64,
+        kind: &str,
+        order: Option<&VaultOrderRecord>,
+        positions: &[VaultPositionRecord],
+        cash_usdc: f64,
+        total_shares: f64,
+        payload: &serde_json::Value,
+    ) -> Result<i64> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        if let Some(order) = order {
+            upsert_order_tx(&tx, order)?;
+        }
+        for pos in positions {
+            if pos.shares <= 1e-9 {
+                tx.execute(
+                    "DELETE FROM vault_positions WHERE token_id = ?1",
+                    [&pos.token_id],
+                )?;
+            } else {
+                tx.execute(
+                    "INSERT INTO vault_positions (token_id, outcome, shares, cost_usdc, avg_price, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT(token_id) DO UPDATE SET
+                        outcome = excluded.outcome,
+                        shares = excluded.shares,
+                        cost_usdc = excluded.cost_usdc,
+                        avg_price = excluded.avg_price,
+                        updated_at = excluded.updated_at",
+                    params![
+                        &pos.token_id,
+                        &pos.outcome,
+                        pos.shares,
+                        pos.cost_usdc,
+                        pos.avg_price,
+                        pos.updated_at,
+                    ],
+                )?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO vault_state (id, cash_usdc, total_shares, updated_at)
+             VALUES (1, ?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET
+                cash_usdc = excluded.cash_usdc,
+                total_shares = excluded.total_shares,
+                updated_at = excluded.updated_at",
+            params![cash_usdc, total_shares, ts],
+        )?;
+        let seq = append_journal_tx(
+            &tx,
+            ts,
+            kind,
+            order.map(|o| o.client_order_id.as_str()),
+            order.and_then(|o| o.order_id.as_deref()),
+            order.map(|o| o.token_id.as_str()),
+            payload,
+        )?;
+        tx.commit()?;
+        Ok(seq)
+    }
+
+    pub async fn load_positions(&self) -> Result<Vec<VaultPositionRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT token_id, outcome, shares, cost_usdc, avg_price, updated_at \
+             FROM vault_positions ORDER BY token_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(VaultPositionRecord {
+                token_id: row.get(0)?,
+                outcome: row.get(1)?,
+                shares: row.get(2)?,
+                cost_usdc: row.get(3)?,
+                avg_price: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Orders that were pending, open or partially filled when last journaled.
+    pub async fn load_unresolved_orders(&self) -> Result<Vec<VaultOrderRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT client_order_id, order_id, token_id, market_slug, outcome, side, price, size, filled_size, filled_notional_usdc, fees_usdc, status, strategy, created_at, updated_at, config_version \
+             FROM vault_orders WHERE status IN ('PENDING', 'OPEN', 'PARTIALLY_FILLED') ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(VaultOrderRecord {
+                client_order_id: row.get(0)?,
+                order_id: row.get(1)?,
+                token_id: row.get(2)?,
+                market_slug: row.get(3)?,
+                outcome: row.get(4)?,
+                side: row.get(5)?,
+                price: row.get(6)?,
+                size: row.get(7)?,
+                filled_size: row.get(8)?,
+                filled_notional_usdc: row.get(9)?,
+                fees_usdc: row.get(10)?,
+                status: row.get(11)?,
+                strategy: row.get(12)?,
+                config_version: row.get(15)?,
+                created_at: row.get(13)?,
+                updated_at: row.get(14)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Journal entries after `after_seq`, oldest first.
+    pub async fn list_journal(
+        &self,
+        after_seq: i64,
+        limit: usize,
+    ) -> Result<Vec<VaultJournalEntry>> {
+        let limit = limit.clamp(1, 20_000) as i64;
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT seq, ts, kind, client_order_id, order_id, token_id, payload \
+             FROM vault_journal WHERE seq > ?1 ORDER BY seq ASC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![after_seq, limit], |row| {
+            Ok(VaultJournalEntry {
+                seq: row.get(0)?,
+                ts: row.get(1)?,
+                kind: row.get(2)?,
+                client_order_id: row.get(3)?,
+                order_id: row.get(4)?,
+                token_id: row.get(5)?,
+                payload: row.get(6)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Persist an active halt (`payload` is the serialized halt state).
+    pub async fn upsert_trading_halt(&self, scope: &str, payload: &str, ts: i64) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO vault_trading_halts (scope, payload, updated_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(scope) DO UPDATE SET
+                payload = excluded.payload,
+                updated_at = excluded.updated_at",
+            params![scope, payload, ts],
+        )?;
+        Ok(())
+    }
+
+    pub async fn delete_trading_halt(&self, scope: &str) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute("DELETE FROM vault_trading_halts WHERE scope = ?1", [scope])?;
+        Ok(())
+    }
+
+    pub async fn load_trading_halts(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().await;
+        let mut stmt =
+            conn.prepare_cached("SELECT payload FROM vault_trading_halts ORDER BY scope ASC")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    pub async fn insert_control_audit(&self, rec: &VaultControlAuditRecord) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO vault_control_audit \
+             (id, ts, action, scope, trigger, actor, reason, orders_cancelled) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                &rec.id,
+                rec.ts,
+                &rec.action,
+                &rec.scope,
+                &rec.trigger,
+                &rec.actor,
+                &rec.reason,
+                rec.orders_cancelled,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent audit entries first.
+    pub async fn list_control_audit(&self, limit: usize) -> Result<Vec<VaultControlAuditRecord>> {
+        let limit = limit.clamp(1, 1000) as i64;
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, ts, action, scope, trigger, actor, reason, orders_cancelled \
+             FROM vault_control_audit ORDER BY ts DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(VaultControlAuditRecord {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                action: row.get(2)?,
+                scope: row.get(3)?,
+                trigger: row.get(4)?,
+                actor: row.get(5)?,
+                reason: row.get(6)?,
+                orders_cancelled: row.get(7)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Record a strategy config version and return its number. Staging a
+    /// `PENDING` set replaces any set still waiting; recording an `ACTIVE` set
+    /// also supersedes the current one.
+    pub async fn insert_strategy_config(&self, rec: &VaultStrategyConfigRecord) -> Result<i64> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE vault_strategy_configs SET status = 'REPLACED' WHERE status = 'PENDING'",
+            [],
+        )?;
+        if rec.status == "ACTIVE" {
+            tx.execute(
+                "UPDATE vault_strategy_configs SET status = 'SUPERSEDED' WHERE status = 'ACTIVE'",
+                [],
+            )?;
+        }
+        tx.execute(
+            "INSERT INTO vault_strategy_configs \
+             (created_at, effective_at, activated_at, status, source, actor, note, payload) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                rec.created_at,
+                rec.effective_at,
+                rec.activated_at,
+                &rec.status,
+                &rec.source,
+                &rec.actor,
+                rec.note.as_deref(),
+                &rec.payload,
+            ],
+        )?;
+        let version = tx.last_insert_rowid();
+        tx.commit()?;
+        Ok(version)
+    }
+
+    /// Promote a staged version to `ACTIVE`, superseding the previous one.
+    pub async fn activate_strategy_config(&self, version: i64, ts: i64) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE vault_strategy_configs SET status = 'SUPERSEDED' WHERE status = 'ACTIVE'",
+            [],
+        )?;
+        tx.execute(
+            "UPDATE vault_strategy_configs SET status = 'ACTIVE', activated_at = ?2 WHERE version = ?1",
+            params![version, ts],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub async fn load_active_strategy_config(&self) -> Result<Option<VaultStrategyConfigRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT version, created_at, effective_at, activated_at, status, source, actor, note, payload \
+             FROM vault_strategy_configs WHERE status = 'ACTIVE' ORDER BY version DESC LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map([], strategy_config_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    pub async fn get_strategy_config(
+        &self,
+        version: i64,
+    ) -> Result<Option<VaultStrategyConfigRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT version, created_at, effective_at, activated_at, status, source, actor, note, payload \
+             FROM vault_strategy_configs WHERE version = ?1",
+        )?;
+        let mut rows = stmt.query_map(params![version], strategy_config_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Most recent versions first.
+    pub async fn list_strategy_configs(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<VaultStrategyConfigRecord>> {
+        let limit = limit.clamp(1, 1000) as i64;
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT version, created_at, effective_at, activated_at, status, source, actor, note, payload \
+             FROM vault_strategy_configs ORDER BY version DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], strategy_config_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Every `TRADE` activity row at or after `since`, oldest first.
+    pub async fn list_trade_activity_since(&self, since: i64) -> Result<Vec<VaultActivityRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, ts, kind, wallet_address, amount_usdc, shares, token_id, market_slug, outcome, side, price, notional_usdc, strategy, decision_id, config_version \
+             FROM vault_activity WHERE kind = 'TRADE' AND ts >= ?1 ORDER BY ts ASC, rowid ASC",
+        )?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok(VaultActivityRecord {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                kind: row.get(2)?,
+                wallet_address: row.get(3)?,
+                amount_usdc: row.get(4)?,
+                shares: row.get(5)?,
+                token_id: row.get(6)?,
+                market_slug: row.get(7)?,
+                outcome: row.get(8)?,
+                side: row.get(9)?,
+                price: row.get(10)?,
+                notional_usdc: row.get(11)?,
+                strategy: row.get(12)?,
+                decision_id: row.get(13)?,
+                config_version: row.get(14)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    pub async fn insert_allocations(&self, records: &[VaultAllocationRecord]) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        for r in records {
+            tx.execute(
+                "INSERT INTO vault_allocations \
+                 (id, ts, sleeve, method, weight, budget_usdc, deployed_usdc, mean_return, return_vol, samples) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    &r.id,
+                    r.ts,
+                    &r.sleeve,
+                    &r.method,
+                    r.weight,
+                    r.budget_usdc,
+                    r.deployed_usdc,
+                    r.mean_return,
+                    r.return_vol,
+                    r.samples,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Allocation rows, most recent rebalance first.
+    pub async fn list_allocations(&self, limit: usize) -> Result<Vec<VaultAllocationRecord>> {
+        let limit = limit.clamp(1, 5000) as i64;
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, ts, sleeve, method, weight, budget_usdc, deployed_usdc, mean_return, return_vol, samples \
+             FROM vault_allocations ORDER BY ts DESC, sleeve ASC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(VaultAllocationRecord {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                sleeve: row.get(2)?,
+                method: row.get(3)?,
+                weight: row.get(4)?,
+                budget_usdc: row.get(5)?,
+                deployed_usdc: row.get(6)?,
+                mean_return: row.get(7)?,
+                return_vol: row.get(8)?,
+                samples: row.get(9)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    pub async fn insert_share_request(&self, req: &VaultShareRequestRecord) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        upsert_share_request_tx(&tx, req)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Pending requests in arrival order.
+    pub async fn load_pending_share_requests(&self) -> Result<Vec<VaultShareRequestRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {SHARE_REQUEST_COLUMNS} FROM vault_share_requests \
+             WHERE status = 'PENDING' ORDER BY requested_at ASC, rowid ASC"
+        ))?;
+        let rows = stmt.query_map([], share_request_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// A wallet's requests, newest first.
+    pub async fn list_share_requests(
+        &self,
+        wallet_address: &str,
+        limit: usize,
+    ) -> Result<Vec<VaultShareRequestRecord>> {
+        let limit = limit.clamp(1, 1000) as i64;
+        let wallet = wallet_address.trim().to_lowercase();
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {SHARE_REQUEST_COLUMNS} FROM vault_share_requests \
+             WHERE wallet_address = ?1 ORDER BY requested_at DESC LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(params![wallet, limit], share_request_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Cancel a wallet's pending request. Returns false if there was none.
+    pub async fn cancel_share_request(&self, id: &str, wallet_address: &str) -> Result<bool> {
+        let wallet = wallet_address.trim().to_lowercase();
+        let conn = self.conn.lock().await;
+        let n = conn.execute(
+            "UPDATE vault_share_requests SET status = 'CANCELLED' \
+             WHERE id = ?1 AND wallet_address = ?2 AND status = 'PENDING'",
+            params![id, wallet],
+        )?;
+        Ok(n > 0)
+    }
+
+    /// Persist a NAV strike atomically: the strike row, every request it
+    /// settled, vault cash/shares, the touched holders' share balances and
+    /// the NAV snapshot.
+    pub async fn commit_nav_strike(
+        &self,
+        strike: &VaultNavStrikeRecord,
+        requests: &[VaultShareRequestRecord],
+        user_shares: &[(String, f64)],
+        snapshot: &VaultNavSnapshotRecord,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            &format!(
+                "INSERT INTO vault_nav_strikes ({NAV_STRIKE_COLUMNS}) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+            ),
+            params![
+                &strike.id,
+                strike.ts,
+                strike.nav_usdc,
+                strike.cash_usdc,
+                strike.positions_value_usdc,
+                strike.marked_positions,
+                strike.unmarked_positions,
+                strike.total_shares,
+                strike.nav_per_share,
+                strike.high_water_mark,
+                strike.performance_fee_usdc,
+                strike.fee_shares,
+                strike.subscriptions_usdc,
+                strike.redemptions_usdc,
+                strike.shares_minted,
+                strike.shares_burned,
+            ],
+        )?;
+        for req in requests {
+            upsert_share_request_tx(&tx, req)?;
+        }
+        for (wallet, shares) in user_shares {
+            if *shares <= 0.0 {
+                tx.execute(
+                    "DELETE FROM vault_user_shares WHERE wallet_address = ?1",
+                    [wallet],
+                )?;
+            } else {
+                tx.execute(
+                    "INSERT INTO vault_user_shares (wallet_address, shares, updated_at)
+                     VALUES (?1, ?2, ?3)
+                     ON CONFLICT(wallet_address) DO UPDATE SET
+                        shares = excluded.shares,
+                        updated_at = excluded.updated_at",
+                    params![wallet, shares, strike.ts],
+                )?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO vault_state (id, cash_usdc, total_shares, updated_at)
+             VALUES (1, ?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET
+                cash_usdc = excluded.cash_usdc,
+                total_shares = excluded.total_shares,
+                updated_at = excluded.updated_at",
+            params![strike.cash_usdc, strike.total_shares, strike.ts],
+        )?;
+        tx.execute(
+            "INSERT OR REPLACE INTO vault_nav_snapshots \
+             (id, ts, nav_usdc, cash_usdc, positions_value_usdc, total_shares, nav_per_share, source) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                &snapshot.id,
+                snapshot.ts,
+                snapshot.nav_usdc,
+                snapshot.cash_usdc,
+                snapshot.positions_value_usdc,
+                snapshot.total_shares,
+                snapshot.nav_per_share,
+                &snapshot.source,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub async fn load_last_nav_strike(&self) -> Result<Option<VaultNavStrikeRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {NAV_STRIKE_COLUMNS} FROM vault_nav_strikes ORDER BY ts DESC LIMIT 1"
+        ))?;
+        let mut rows = stmt.query_map([], nav_strike_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Most recent strikes first.
+    pub async fn list_nav_strikes(&self, limit: usize) -> Result<Vec<VaultNavStrikeRecord>> {
+        let limit = limit.clamp(1, 1000) as i64;
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {NAV_STRIKE_COLUMNS} FROM vault_nav_strikes ORDER BY ts DESC LIMIT ?1"
+        ))?;
+        let rows = stmt.query_map(params![limit], nav_strike_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+}
+
+const SHARE_REQUEST_COLUMNS: &str = "id, wallet_address, kind, amount_usdc, shares, status, requested_at, strike_id, struck_at, nav_per_share, filled_usdc, filled_shares, note";
+
+fn share_request_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<VaultShareRequestRecord> {
+    Ok(VaultShareRequestRecord {
+        id: row.get(0)?,
+        wallet_address: row.get(1)?,
+        kind: row.get(2)?,
+        amount_usdc: row.get(3)?,
+        shares: row.get(4)?,
+        status: row.get(5)?,
+        requested_at: row.get(6)?,
+        strike_id: row.get(7)?,
+        struck_at: row.get(8)?,
+        nav_per_share: row.get(9)?,
+        filled_usdc: row.get(10)?,
+        filled_shares: row.get(11)?,
+        note: row.get(12)?,
+    })
+}
+
+fn upsert_share_request_tx(
+    tx: &rusqlite::Transaction<'_>,
+    req: &VaultShareRequestRecord,
+) -> Result<()> {
+    tx.execute(
+        "INSERT INTO vault_share_requests \
+         (id, wallet_address, kind, amount_usdc, shares, status, requested_at, strike_id, struck_at, nav_per_share, filled_usdc, filled_shares, note) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13) \
+         ON CONFLICT(id) DO UPDATE SET \
+            status = excluded.status, \
+            strike_id = excluded.strike_id, \
+            struck_at = excluded.struck_at, \
+            nav_per_share = excluded.nav_per_share, \
+            filled_usdc = excluded.filled_usdc, \
+            filled_shares = excluded.filled_shares, \
+            note = excluded.note",
+        params![
+            &req.id,
+            &req.wallet_address,
+            &req.kind,
+            req.amount_usdc,
+            req.shares,
+            &req.status,
+            req.requested_at,
+            req.strike_id.as_deref(),
+            req.struck_at,
+            req.nav_per_share,
+            req.filled_usdc,
+            req.filled_shares,
+            req.note.as_deref(),
+        ],
+    )?;
+    Ok(())
+}
+
+const NAV_STRIKE_COLUMNS: &str = "id, ts, nav_usdc, cash_usdc, positions_value_usdc, marked_positions, unmarked_positions, total_shares, nav_per_share, high_water_mark, performance_fee_usdc, fee_shares, subscriptions_usdc, redemptions_usdc, shares_minted, shares_burned";
+
+fn nav_strike_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<VaultNavStrikeRecord> {
+    Ok(VaultNavStrikeRecord {
+        id: row.get(0)?,
+        ts: row.get(1)?,
+        nav_usdc: row.get(2)?,
+        cash_usdc: row.get(3)?,
+        positions_value_usdc: row.get(4)?,
+        marked_positions: row.get(5)?,
+        unmarked_positions: row.get(6)?,
+        total_shares: row.get(7)?,
+        nav_per_share: row.get(8)?,
+        high_water_mark: row.get(9)?,
+        performance_fee_usdc: row.get(10)?,
+        fee_shares: row.get(11)?,
+        subscriptions_usdc: row.get(12)?,
+        redemptions_usdc: row.get(13)?,
+        shares_minted: row.get(14)?,
+        shares_burned: row.get(15)?,
+    })
+}
+
+fn strategy_config_from_row(
+    row: &rusqlite::Row<'_>,
+) -> rusqlite::Result<VaultStrategyConfigRecord> {
+    Ok(VaultStrategyConfigRecord {
+        version: row.get(0)?,
+        created_at: row.get(1)?,
+        effective_at: row.get(2)?,
+        activated_at: row.get(3)?,
+        status: row.get(4)?,
+        source: row.get(5)?,
+        actor: row.get(6)?,
+        note: row.get(7)?,
+        payload: row.get(8)?,
+    })
+}
+
+/// Add a column to a table created by an older build.
+fn ensure_column(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {decl}"),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn upsert_order_tx(tx: &rusqlite::Transaction<'_>, order: &VaultOrderRecord) -> Result<()> {
+    tx.execute(
+        "INSERT INTO vault_orders \
+         (client_order_id, order_id, token_id, market_slug, outcome, side, price, size, filled_size, filled_notional_usdc, fees_usdc, status, strategy, created_at, updated_at, config_version) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16) \
+         ON CONFLICT(client_order_id) DO UPDATE SET \
+            order_id = COALESCE(excluded.order_id, vault_orders.order_id), \
+            filled_size = excluded.filled_size, \
+            filled_notional_usdc = excluded.filled_notional_usdc, \
+            fees_usdc = excluded.fees_usdc, \
+            status = excluded.status, \
+            updated_at = excluded.updated_at",
+        params![
+            &order.client_order_id,
+            order.order_id.as_deref(),
+            &order.token_id,
+            order.market_slug.as_deref(),
+            order.outcome.as_deref(),
+            &order.side,
+            order.price,
+            order.size,
+            order.filled_size,
+            order.filled_notional_usdc,
+            order.fees_usdc,
+            &order.status,
+            order.strategy.as_deref(),
+            order.created_at,
+            order.updated_at,
+            order.config_version,
+        ],
+    )?;
+    Ok(())
+}
+
+fn append_journal_tx(
+    tx: &rusqlite::Transaction<'_>,
+    ts: i64,
+    kind: &str,
+    client_order_id: Option<&str>,
+    order_id: Option<&str>,
+    token_id: Option<&str>,
+    payload: &serde_json::Value,
+) -> Result<i64> {
+    tx.execute(
+        "INSERT INTO vault_journal (ts, kind, client_order_id, order_id, token_id, payload) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            ts,
+            kind,
+            client_order_id,
+            order_id,
+            token_id,
+            payload.to_string()
+        ],
+    )?;
+    Ok(tx.last_insert_rowid())
 }
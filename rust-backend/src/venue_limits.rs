@@ -0,0 +1,7 @@
+//! Venue Limits
+//!
+//! Hard limits imposed by the venues we trade on. Shared by the live execution
+//! adapters and the backtest OMS so both refuse the same requests.
+
+/// Maximum orders per batch post on the Polymarket CLOB.
+pub const POLYMARKET_MAX_BATCH_SIZE: usize = 15;